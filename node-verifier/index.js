@@ -0,0 +1,65 @@
+// zkML verifier for Node
+// Native addon (napi-rs) when one is built for this platform, the WASM build otherwise.
+// Set ZKML_VERIFIER_BACKEND=wasm or =native to force one; `backend` says which loaded.
+//
+// Both backends export the WASM build's API with the same names, signatures and
+// return shapes: `WasmVerifier`, `VerifierConfig`, `ProofData`, the report classes,
+// the `HashAlgorithm`, `Check`, `Confidence` and `VerificationError` enums, and the
+// free functions. Errors are thrown the same way (`name === "VerificationError"`,
+// numeric `code`, `kind`). `test/backends.test.js` runs the same proofs through both.
+//
+// On the native side `verify_async`, `verify_report_async` and `verify_batch` run
+// off the JS thread. The addon also shares the verifier with `VerifierSession` rather
+// than copying it, and finalized hashers throw instead of being freed.
+//
+// WASM-only, because they need a browser, a JS callback on the verification path
+// or WASM linear memory (setters by their JS property names):
+// - fetch and browser storage: `intercept_fetch`, `use_indexed_db_cache`,
+//   `verify_cached`, `proof_cache`, `verify_from_url`, `verify_report_from_url`,
+//   `proof_fetcher`
+// - web streams: `verify_streams`, `hash_stream`
+// - on-chain lookups through a JS provider: `verify_report_onchain`,
+//   `set_onchain_registry`, `chain_query`, `verify_anchored`
+// - JS hooks: `clock`, `did_fetcher`, `KeyResolver.fetcher`,
+//   `on_check_started`, `on_check_passed`, `on_check_failed`,
+//   `on_hash_progress`
+// - Web Workers and shared memory: `WorkerVerifier`, `IoBuffer`, `verify_buffers`,
+//   `verify_buffers_report`, `hash_buffer`, `init_threads`, `threads_enabled`
+// - WASM runtime setup: `init`, `simd_enabled`, `simd_supported`, `init_tracing`
+
+const fs = require("fs");
+const path = require("path");
+
+const BINARY = "zkml-node-verifier";
+
+function loadNative() {
+    // napi build --platform names files <binary>.<platform>-<arch>[-<abi>].node
+    const prefix = `${BINARY}.${process.platform}-${process.arch}`;
+    const file = fs.readdirSync(__dirname).find((name) => name.startsWith(prefix) && name.endsWith(".node"));
+    if (!file) {
+        return null;
+    }
+    try {
+        return require(path.join(__dirname, file));
+    } catch (e) {
+        if (process.env.ZKML_VERIFIER_BACKEND === "native") {
+            throw e;
+        }
+        return null;
+    }
+}
+
+function load() {
+    const forced = process.env.ZKML_VERIFIER_BACKEND;
+    const native = forced === "wasm" ? null : loadNative();
+    if (native) {
+        return { ...native, backend: "native" };
+    }
+    if (forced === "native") {
+        throw new Error(`No native verifier built for ${process.platform}-${process.arch}`);
+    }
+    // CommonJS WASM build from `../wasm-verifier/build.sh --node`
+    return { ...require("./wasm"), backend: "wasm" };
+}
+
+module.exports = load();
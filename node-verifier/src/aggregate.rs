@@ -0,0 +1,141 @@
+// Aggregated inference proofs
+// One proof over a Merkle root of N (input, output) pairs of the same model, with per-inference inclusion proofs
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::{error, failure};
+use crate::{u64_of, HashAlgorithm, ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// A proof over many inferences of one model
+///
+/// `input_hash` is the Merkle root (`aggregate_inferences`) over the
+/// inferences' `(input_hash, output_hash)` pairs and `output_hash` commits
+/// to their count (`AggregatedProof.count_commitment`), so a signature over
+/// the proof covers both.
+#[napi]
+#[derive(Clone)]
+pub struct AggregatedProof(pub(crate) zkml_verifier_core::aggregate::AggregatedProof);
+
+#[napi]
+impl AggregatedProof {
+    #[napi(constructor)]
+    pub fn new(proof: &ProofData, count: BigInt) -> AggregatedProof {
+        AggregatedProof(zkml_verifier_core::aggregate::AggregatedProof::new(proof.0.clone(), u64_of(count)))
+    }
+
+    #[napi(getter, js_name = "proof")]
+    pub fn proof(&self) -> ProofData {
+        ProofData(self.0.proof().clone())
+    }
+
+    /// Number of aggregated inferences
+    #[napi(getter, js_name = "count")]
+    pub fn count(&self) -> u64 {
+        self.0.count()
+    }
+
+    /// Merkle root over the inferences (the proof's `input_hash`)
+    #[napi(getter, js_name = "root")]
+    pub fn root(&self) -> String {
+        self.0.root().to_string()
+    }
+
+    /// `output_hash` an aggregate over `count` inferences must carry
+    #[napi(js_name = "count_commitment")]
+    pub fn count_commitment(env: Env, count: BigInt, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        let alg = algorithm.map(Into::into).unwrap_or_default();
+        zkml_verifier_core::aggregate::AggregatedProof::count_commitment(u64_of(count), alg)
+            .map_err(|e| failure(&env)((e, "Hash algorithm is not enabled in this build")))
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<AggregatedProof> {
+        zkml_verifier_core::aggregate::AggregatedProof::from_json(&json).map(AggregatedProof).map_err(|e| {
+            error(&env, Kind::MalformedProof, &format!("Failed to parse aggregated proof JSON: {}", e))
+        })
+    }
+}
+
+/// Inclusion proof for one inference of an `AggregatedProof`
+#[napi]
+#[derive(Clone)]
+pub struct InferenceProof(pub(crate) zkml_verifier_core::aggregate::InferenceProof);
+
+#[napi]
+impl InferenceProof {
+    /// Position of the inference in the aggregate
+    #[napi(getter, js_name = "index")]
+    pub fn index(&self) -> u64 {
+        self.0.index()
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<InferenceProof> {
+        zkml_verifier_core::aggregate::InferenceProof::from_json(&json)
+            .map(InferenceProof)
+            .map_err(|_| error(&env, Kind::MalformedProof, "Invalid inference proof JSON"))
+    }
+}
+
+/// Merkle root over `(input_hashes[i], output_hashes[i])` pairs, for an aggregate's `input_hash`
+#[napi(js_name = "aggregate_inferences")]
+pub fn aggregate_inferences(
+    env: Env,
+    input_hashes: Vec<String>,
+    output_hashes: Vec<String>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String> {
+    let alg = algorithm.map(Into::into).unwrap_or_default();
+    zkml_verifier_core::aggregate::aggregate_inferences(&input_hashes, &output_hashes, alg)
+        .map_err(|e| error(&env, e, "Expected equally many, non-empty, hex input and output hashes"))
+}
+
+/// Build the inclusion proof for inference `index` (see `aggregate_inferences`)
+#[napi(js_name = "prove_inference")]
+pub fn prove_inference(
+    env: Env,
+    input_hashes: Vec<String>,
+    output_hashes: Vec<String>,
+    index: u32,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<InferenceProof> {
+    let alg = algorithm.map(Into::into).unwrap_or_default();
+    zkml_verifier_core::aggregate::InferenceProof::create(&input_hashes, &output_hashes, index as usize, alg)
+        .map(InferenceProof)
+        .map_err(|e| error(&env, e, "Expected equally many hex hashes and an index within them"))
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify an aggregated proof (model binding, count, validity, signature, ...)
+    #[napi(js_name = "verify_aggregate")]
+    pub fn verify_aggregate(&self, aggregate: &AggregatedProof) -> VerificationReport {
+        self.read().aggregate_report(&aggregate.0).into()
+    }
+
+    /// Verify an aggregate and that `input`/`output` is one of its inferences
+    #[napi(js_name = "verify_inference")]
+    pub fn verify_inference(
+        &self,
+        aggregate: &AggregatedProof,
+        input: Uint8Array,
+        output: Uint8Array,
+        inclusion: &InferenceProof,
+    ) -> VerificationReport {
+        self.read().inference_report(&aggregate.0, &input, &output, &inclusion.0).into()
+    }
+}
@@ -0,0 +1,73 @@
+// On-chain anchoring
+// The anchor records provers publish; checking them against a chain is WASM-only (see `index.js`)
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::inclusion::MerkleProof;
+use crate::{u64_of, Optional};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// Where a prover says a proof was anchored
+///
+/// `path` leads from the proof's `leaf_hash` to `root`, which the
+/// transaction `tx_hash` committed.
+#[napi]
+#[derive(Clone)]
+pub struct AnchorRecord(zkml_verifier_core::anchor::AnchorRecord);
+
+#[napi]
+impl AnchorRecord {
+    #[napi(constructor)]
+    pub fn new(
+        tx_hash: String,
+        block_number: BigInt,
+        root: String,
+        path: &MerkleProof,
+        chain: Option<String>,
+    ) -> AnchorRecord {
+        let path = path.0.clone();
+        AnchorRecord(zkml_verifier_core::anchor::AnchorRecord::new(tx_hash, u64_of(block_number), root, path, chain))
+    }
+
+    /// Chain identifier passed on to the chain query (e.g. CAIP-2)
+    #[napi(getter, js_name = "chain")]
+    pub fn chain(&self) -> Optional<String> {
+        self.0.chain().map(str::to_string).into()
+    }
+
+    #[napi(getter, js_name = "tx_hash")]
+    pub fn tx_hash(&self) -> String {
+        self.0.tx_hash().to_string()
+    }
+
+    #[napi(getter, js_name = "block_number")]
+    pub fn block_number(&self) -> u64 {
+        self.0.block_number()
+    }
+
+    #[napi(getter, js_name = "root")]
+    pub fn root(&self) -> String {
+        self.0.root().to_string()
+    }
+
+    #[napi(getter, js_name = "path")]
+    pub fn path(&self) -> MerkleProof {
+        MerkleProof(self.0.path().clone())
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<AnchorRecord> {
+        zkml_verifier_core::anchor::AnchorRecord::from_json(&json)
+            .map(AnchorRecord)
+            .map_err(|_| error(&env, Kind::MalformedProof, "Invalid anchor record JSON"))
+    }
+}
@@ -0,0 +1,143 @@
+// Audit log
+// Append-only record of verification attempts, each entry hash-chained to the one before, exportable as JSONL
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::{Optional, ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// One verification attempt
+#[napi]
+#[derive(Clone)]
+pub struct AuditEntry(zkml_verifier_core::audit::AuditEntry);
+
+#[napi]
+impl AuditEntry {
+    /// Position in the log, from 0
+    #[napi(getter, js_name = "seq")]
+    pub fn seq(&self) -> u64 {
+        self.0.seq()
+    }
+
+    /// When it was verified (Unix ms, by the verifier's clock)
+    #[napi(getter, js_name = "timestamp")]
+    pub fn timestamp(&self) -> u64 {
+        self.0.timestamp()
+    }
+
+    #[napi(getter, js_name = "proof_id")]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id().to_string()
+    }
+
+    #[napi(getter, js_name = "model_hash")]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash().to_string()
+    }
+
+    #[napi(getter, js_name = "passed")]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    /// `VerificationError` name, if it failed
+    #[napi(getter, js_name = "error")]
+    pub fn error(&self) -> Optional<String> {
+        self.0.error().map(str::to_string).into()
+    }
+
+    /// `hash` of the entry before, `AUDIT_GENESIS` for the first
+    #[napi(getter, js_name = "prev_hash")]
+    pub fn prev_hash(&self) -> String {
+        self.0.prev_hash().to_string()
+    }
+
+    /// Tagged SHA3-256 of the RFC 8785 canonical JSON of the other fields
+    #[napi(getter, js_name = "hash")]
+    pub fn hash(&self) -> String {
+        self.0.hash().to_string()
+    }
+}
+
+/// Hash-chained verification history
+///
+/// Each entry commits to the one before it, so changing or dropping an
+/// entry breaks every later hash. Dropping entries from the end can only
+/// be caught against a `head` kept elsewhere.
+#[napi]
+#[derive(Clone, Default)]
+pub struct AuditLog(zkml_verifier_core::audit::AuditLog);
+
+#[napi]
+impl AuditLog {
+    #[napi(constructor)]
+    pub fn new() -> AuditLog {
+        AuditLog::default()
+    }
+
+    /// Append `report`'s outcome for `proof_data`, verified now
+    #[napi(js_name = "record")]
+    pub fn record(&mut self, proof_data: &ProofData, report: &VerificationReport) {
+        self.0.append(&proof_data.0, &report.0, zkml_verifier_core::clock::system_now_ms());
+    }
+
+    #[napi(getter, js_name = "entries")]
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.0.entries().iter().cloned().map(AuditEntry).collect()
+    }
+
+    #[napi(getter, js_name = "length")]
+    pub fn length(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    /// Hash of the last entry, which commits to the whole log (`AUDIT_GENESIS` while empty)
+    #[napi(getter, js_name = "head")]
+    pub fn head(&self) -> String {
+        self.0.head()
+    }
+
+    /// One JSON entry per line, oldest first
+    #[napi(js_name = "to_jsonl")]
+    pub fn to_jsonl(&self) -> String {
+        self.0.to_jsonl()
+    }
+
+    /// Read a `to_jsonl` export back, checking its chain
+    ///
+    /// Throws `VerificationError.InvalidArgument` naming the first entry that
+    /// doesn't parse or doesn't chain.
+    #[napi(factory, js_name = "from_jsonl")]
+    pub fn from_jsonl(env: Env, jsonl: String) -> Result<AuditLog> {
+        zkml_verifier_core::audit::AuditLog::try_from_jsonl(&jsonl)
+            .map(AuditLog)
+            .map_err(|reason| error(&env, Kind::InvalidArgument, &reason))
+    }
+
+    /// Recompute every hash and link; throws `VerificationError.InvalidArgument` at the first broken one
+    #[napi(js_name = "verify")]
+    pub fn verify(&self, env: Env) -> Result<()> {
+        self.0.try_verify().map_err(|reason| error(&env, Kind::InvalidArgument, &reason))
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Log every verification this verifier runs from now on to `log` (`undefined` stops logging)
+    ///
+    /// Pass a log read back with `AuditLog.from_jsonl` to continue its chain.
+    #[napi(setter, js_name = "audit_log")]
+    pub fn set_audit_log(&mut self, log: Option<ClassInstance<AuditLog>>) {
+        self.write().set_audit_log(log.map(|log| log.0.clone()));
+    }
+
+    /// Copy of the audit log so far (`undefined` unless one was set)
+    #[napi(getter, js_name = "audit_log")]
+    pub fn audit_log(&self) -> Optional<AuditLog> {
+        self.read().audit_log().map(AuditLog).into()
+    }
+}
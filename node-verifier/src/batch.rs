@@ -0,0 +1,90 @@
+// Batch verification
+// The WASM build's `verify_batch` and `BatchReport`, with the proofs spread across threads
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::{parallel, ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// Per-proof results plus aggregate counts for a batch
+#[napi]
+#[derive(Clone, Default)]
+pub struct BatchReport(zkml_verifier_core::batch::BatchReport);
+
+#[napi]
+impl BatchReport {
+    /// Per-proof reports, in input order
+    #[napi(getter, js_name = "reports")]
+    pub fn reports(&self) -> Vec<VerificationReport> {
+        self.0.reports().iter().cloned().map(VerificationReport::from).collect()
+    }
+
+    /// Per-proof pass/fail flags, in input order
+    #[napi(getter, js_name = "results")]
+    pub fn results(&self) -> Uint8Array {
+        self.0.results().into()
+    }
+
+    #[napi(getter, js_name = "total")]
+    pub fn total(&self) -> u32 {
+        self.0.total()
+    }
+
+    #[napi(getter, js_name = "passed")]
+    pub fn passed(&self) -> u32 {
+        self.0.passed()
+    }
+
+    #[napi(getter, js_name = "failed")]
+    pub fn failed(&self) -> u32 {
+        self.0.failed()
+    }
+
+    /// True when the batch is non-empty and every proof passed
+    #[napi(getter, js_name = "all_passed")]
+    pub fn all_passed(&self) -> bool {
+        self.0.all_passed()
+    }
+
+    #[napi(getter, js_name = "total_ms")]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    /// Serialize batch report to JSON string
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify many proofs at once, across all cores
+    ///
+    /// `inputs[i]` and `outputs[i]` are the I/O buffers for `proofs[i]`.
+    /// Throws `VerificationError.InvalidArgument` if the arrays differ in length.
+    #[napi(js_name = "verify_batch")]
+    pub fn verify_batch(
+        &self,
+        env: Env,
+        proofs: Vec<ClassInstance<ProofData>>,
+        inputs: Vec<Uint8Array>,
+        outputs: Vec<Uint8Array>,
+    ) -> Result<BatchReport> {
+        if proofs.len() != inputs.len() || proofs.len() != outputs.len() {
+            let message = "proofs, inputs and outputs must have the same length";
+            return Err(error(&env, Kind::InvalidArgument, message));
+        }
+        let jobs: Vec<(&zkml_verifier_core::ProofData, &[u8], &[u8])> =
+            proofs.iter().zip(&inputs).zip(&outputs).map(|((p, i), o)| (&p.0, &i[..], &o[..])).collect();
+
+        let verifier = self.read();
+        let reports = parallel::map(&jobs, |(proof, input, output)| verifier.verify_report(proof, input, output));
+        Ok(BatchReport(reports.into_iter().collect()))
+    }
+}
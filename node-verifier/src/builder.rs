@@ -0,0 +1,121 @@
+// Verifier builder
+// Fluent construction of a `WasmVerifier`, so new options don't change any constructor signature
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::{u64_of, HashAlgorithm, VerifierConfig, WasmVerifier};
+
+/// Step-by-step `WasmVerifier` setup
+///
+/// Every method returns the builder to keep chaining from, as in the WASM
+/// build (where each call consumes the builder it was called on; here that
+/// one is left reset). Options not set keep their `VerifierConfig` defaults.
+#[napi]
+#[derive(Clone, Default)]
+pub struct VerifierBuilder(zkml_verifier_core::VerifierBuilder);
+
+impl VerifierBuilder {
+    /// Move the builder out to apply one option, as the WASM build's `self` methods do
+    fn step<F>(&mut self, option: F) -> VerifierBuilder
+    where
+        F: FnOnce(zkml_verifier_core::VerifierBuilder) -> zkml_verifier_core::VerifierBuilder,
+    {
+        VerifierBuilder(option(std::mem::take(&mut self.0)))
+    }
+}
+
+#[napi]
+impl VerifierBuilder {
+    #[napi(constructor)]
+    pub fn new() -> VerifierBuilder {
+        VerifierBuilder::default()
+    }
+
+    /// Model the proofs must be bound to (required)
+    #[napi(js_name = "model_hash")]
+    pub fn model_hash(&mut self, model_hash: String) -> VerifierBuilder {
+        self.step(|builder| builder.model_hash(model_hash))
+    }
+
+    /// Start from an existing policy; later options override its fields
+    #[napi(js_name = "config")]
+    pub fn config(&mut self, config: &VerifierConfig) -> VerifierBuilder {
+        self.step(|builder| builder.config(&config.0))
+    }
+
+    #[napi(js_name = "max_age_ms")]
+    pub fn max_age_ms(&mut self, max_age_ms: BigInt) -> VerifierBuilder {
+        self.step(|builder| builder.max_age_ms(u64_of(max_age_ms)))
+    }
+
+    #[napi(js_name = "check_freshness")]
+    pub fn check_freshness(&mut self, check_freshness: bool) -> VerifierBuilder {
+        self.step(|builder| builder.check_freshness(check_freshness))
+    }
+
+    #[napi(js_name = "clock_skew_ms")]
+    pub fn clock_skew_ms(&mut self, clock_skew_ms: BigInt) -> VerifierBuilder {
+        self.step(|builder| builder.clock_skew_ms(u64_of(clock_skew_ms)))
+    }
+
+    #[napi(js_name = "hash_algorithm")]
+    pub fn hash_algorithm(&mut self, hash_algorithm: HashAlgorithm) -> VerifierBuilder {
+        self.step(|builder| builder.hash_algorithm(hash_algorithm.into()))
+    }
+
+    #[napi(js_name = "require_nonce")]
+    pub fn require_nonce(&mut self, require_nonce: bool) -> VerifierBuilder {
+        self.step(|builder| builder.require_nonce(require_nonce))
+    }
+
+    #[napi(js_name = "require_signature")]
+    pub fn require_signature(&mut self, require_signature: bool) -> VerifierBuilder {
+        self.step(|builder| builder.require_signature(require_signature))
+    }
+
+    #[napi(js_name = "min_attestors")]
+    pub fn min_attestors(&mut self, min_attestors: u32) -> VerifierBuilder {
+        self.step(|builder| builder.min_attestors(min_attestors))
+    }
+
+    #[napi(js_name = "max_decompressed_bytes")]
+    pub fn max_decompressed_bytes(&mut self, max_decompressed_bytes: BigInt) -> VerifierBuilder {
+        self.step(|builder| builder.max_decompressed_bytes(u64_of(max_decompressed_bytes)))
+    }
+
+    #[napi(js_name = "max_proof_bytes")]
+    pub fn max_proof_bytes(&mut self, max_proof_bytes: BigInt) -> VerifierBuilder {
+        self.step(|builder| builder.max_proof_bytes(u64_of(max_proof_bytes)))
+    }
+
+    #[napi(js_name = "max_input_bytes")]
+    pub fn max_input_bytes(&mut self, max_input_bytes: BigInt) -> VerifierBuilder {
+        self.step(|builder| builder.max_input_bytes(u64_of(max_input_bytes)))
+    }
+
+    /// Trust an Ed25519 key or Ethereum address (see `WasmVerifier::add_trusted_signer`)
+    #[napi(js_name = "trusted_signer")]
+    pub fn trusted_signer(&mut self, pubkey: String) -> VerifierBuilder {
+        self.step(|builder| builder.trusted_signer(pubkey))
+    }
+
+    /// Trust a BLS attestor key (see `WasmVerifier::add_trusted_attestor`)
+    #[napi(js_name = "trusted_attestor")]
+    pub fn trusted_attestor(&mut self, pubkey: String) -> VerifierBuilder {
+        self.step(|builder| builder.trusted_attestor(pubkey))
+    }
+
+    /// Create the verifier
+    ///
+    /// Throws `VerificationError.InvalidArgument` if no model hash was set.
+    #[napi(js_name = "build")]
+    pub fn build(&mut self, env: Env) -> Result<WasmVerifier> {
+        std::mem::take(&mut self.0)
+            .try_build()
+            .map(WasmVerifier::from)
+            .map_err(|e| error(&env, e, "VerifierBuilder needs a model_hash"))
+    }
+}
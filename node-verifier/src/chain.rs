@@ -0,0 +1,74 @@
+// Pipeline proof chaining
+// Verifies multi-stage pipelines (e.g. embedder -> classifier) where each stage's output feeds the next
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Per-stage results plus the links between stages
+#[napi]
+#[derive(Clone, Default)]
+pub struct ChainReport(zkml_verifier_core::chain::ChainReport);
+
+#[napi]
+impl ChainReport {
+    /// Per-stage reports, in pipeline order
+    #[napi(getter, js_name = "stages")]
+    pub fn stages(&self) -> Vec<VerificationReport> {
+        self.0.stages().iter().cloned().map(VerificationReport::from).collect()
+    }
+
+    /// `links[i]`: stage `i`'s output is stage `i + 1`'s input
+    #[napi(getter, js_name = "links")]
+    pub fn links(&self) -> Uint8Array {
+        self.0.links().iter().map(|&l| l as u8).collect::<Vec<_>>().into()
+    }
+
+    /// The first stage consumed the pipeline input and the last produced the pipeline output
+    #[napi(getter, js_name = "end_to_end")]
+    pub fn end_to_end(&self) -> bool {
+        self.0.end_to_end()
+    }
+
+    /// True when every stage passed, every link holds and the ends are bound
+    #[napi(getter, js_name = "passed")]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    #[napi(getter, js_name = "total_ms")]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify a chain of proofs where each stage's output is the next stage's input
+    ///
+    /// `io_buffers` holds the pipeline input, every intermediate tensor and
+    /// the pipeline output, so it has one more entry than `proofs`. Stages
+    /// for different models need a registry verifier (`with_registry`).
+    /// Throws `VerificationError.InvalidArgument` on a length mismatch.
+    #[napi(js_name = "verify_chain")]
+    pub fn verify_chain(
+        &self,
+        env: Env,
+        proofs: Vec<ClassInstance<ProofData>>,
+        io_buffers: Vec<Uint8Array>,
+    ) -> Result<ChainReport> {
+        let proofs: Vec<zkml_verifier_core::ProofData> = proofs.iter().map(|proof| proof.0.clone()).collect();
+        self.read()
+            .check_chain(&proofs, &io_buffers)
+            .map(ChainReport)
+            .map_err(|e| error(&env, e, "io_buffers must have exactly one more entry than proofs"))
+    }
+}
@@ -0,0 +1,37 @@
+// Classification semantics
+// Check the committed output's argmax / top-k against a claimed label set, without decoding logits in JS
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{list, ProofData, VerificationReport, WasmVerifier, U32s};
+
+#[napi]
+impl WasmVerifier {
+    /// Verify a proof and that its output's highest-scoring class is `label`
+    #[napi(js_name = "verify_argmax")]
+    pub fn verify_argmax(
+        &self,
+        proof_data: &ProofData,
+        input: Uint8Array,
+        output: Uint8Array,
+        label: u32,
+    ) -> VerificationReport {
+        self.read().top_k_report(&proof_data.0, &input, &output, &[label]).into()
+    }
+
+    /// Verify a proof and that its output's `labels.length` highest-scoring classes are `labels` (in any order)
+    ///
+    /// Scores are decoded per the proof's `output_spec`, or as raw
+    /// float32 without one; ties rank the lower class index first.
+    #[napi(js_name = "verify_top_k")]
+    pub fn verify_top_k(
+        &self,
+        proof_data: &ProofData,
+        input: Uint8Array,
+        output: Uint8Array,
+        labels: U32s,
+    ) -> VerificationReport {
+        self.read().top_k_report(&proof_data.0, &input, &output, &list(labels)).into()
+    }
+}
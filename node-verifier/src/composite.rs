@@ -0,0 +1,94 @@
+// Composite proofs
+// A proof attesting to the verification of other proofs, folded server-side and checked in one call
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::{error, failure};
+use crate::{HashAlgorithm, ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+const NOT_ENABLED: &str = "Hash algorithm is not enabled in this build";
+
+/// A folding proof plus the proofs it attests to
+///
+/// The folding proof's `input_hash` commits to the children (the canonical
+/// hashes of their proofs, in order) and its `output_hash` to their
+/// `output_hash`es; see `children_input_hash`/`children_output_hash`. A
+/// child with children of its own is itself a composite.
+#[napi]
+#[derive(Clone)]
+pub struct CompositeProof(pub(crate) zkml_verifier_core::composite::CompositeProof);
+
+#[napi]
+impl CompositeProof {
+    #[napi(constructor)]
+    pub fn new(proof: &ProofData, children: Vec<ClassInstance<CompositeProof>>) -> CompositeProof {
+        let children = children.iter().map(|child| child.0.clone()).collect();
+        CompositeProof(zkml_verifier_core::composite::CompositeProof::new(proof.0.clone(), children))
+    }
+
+    /// Wrap a plain proof as a leaf child
+    #[napi(factory, js_name = "leaf")]
+    pub fn leaf(proof: &ProofData) -> CompositeProof {
+        CompositeProof(zkml_verifier_core::composite::CompositeProof::leaf(proof.0.clone()))
+    }
+
+    #[napi(getter, js_name = "proof")]
+    pub fn proof(&self) -> ProofData {
+        ProofData(self.0.proof().clone())
+    }
+
+    #[napi(getter, js_name = "children")]
+    pub fn children(&self) -> Vec<CompositeProof> {
+        self.0.children().iter().cloned().map(CompositeProof).collect()
+    }
+
+    /// Total number of leaf proofs under this composite
+    #[napi(getter, js_name = "leaf_count")]
+    pub fn leaf_count(&self) -> u32 {
+        self.0.leaf_count()
+    }
+
+    /// `input_hash` the folding proof must carry for these children
+    #[napi(js_name = "children_input_hash")]
+    pub fn children_input_hash(&self, env: Env, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        let alg = algorithm.map(Into::into).unwrap_or_default();
+        self.0.children_input_hash(alg).map_err(|e| failure(&env)((e, NOT_ENABLED)))
+    }
+
+    /// `output_hash` the folding proof must carry for these children
+    #[napi(js_name = "children_output_hash")]
+    pub fn children_output_hash(&self, env: Env, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        let alg = algorithm.map(Into::into).unwrap_or_default();
+        self.0.children_output_hash(alg).map_err(|e| failure(&env)((e, NOT_ENABLED)))
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<CompositeProof> {
+        zkml_verifier_core::composite::CompositeProof::from_json(&json).map(CompositeProof).map_err(|e| {
+            error(&env, Kind::MalformedProof, &format!("Failed to parse composite proof JSON: {}", e))
+        })
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify a composite proof and, recursively, every nested composite
+    ///
+    /// This verifier's model is the folding model. Its input/output checks
+    /// bind the folding proof to its children, a `Composite` check reports
+    /// the first failing nested composite. Leaf proofs are covered by the
+    /// folding proof and aren't re-checked individually.
+    #[napi(js_name = "verify_composite")]
+    pub fn verify_composite(&self, composite: &CompositeProof) -> VerificationReport {
+        self.read().composite_report(&composite.0).into()
+    }
+}
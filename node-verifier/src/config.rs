@@ -0,0 +1,146 @@
+// Verifier configuration
+// The WASM build's `VerifierConfig` class over the core config
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsUnknown};
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::hash::HashAlgorithm;
+use crate::object::to_value;
+use crate::u64_of;
+
+/// Verification policy exposed to JS
+#[napi]
+#[derive(Clone, Copy, Default)]
+pub struct VerifierConfig(pub(crate) zkml_verifier_core::VerifierConfig);
+
+#[napi]
+impl VerifierConfig {
+    /// Create config with the default policy (1 hour max age, 1 minute skew)
+    #[napi(constructor)]
+    pub fn new() -> VerifierConfig {
+        VerifierConfig::default()
+    }
+
+    /// Config for archival verification: timestamps are not checked
+    #[napi(factory, js_name = "archival")]
+    pub fn archival() -> VerifierConfig {
+        VerifierConfig(zkml_verifier_core::VerifierConfig::archival())
+    }
+
+    /// Build a config from a partial object, defaulting omitted fields
+    #[napi(factory, js_name = "from_object")]
+    pub fn from_object(env: Env, value: JsUnknown) -> Result<VerifierConfig> {
+        let kind = zkml_verifier_core::VerificationError::InvalidArgument;
+        let config = to_value(&value).and_then(|value| serde_json::from_value(value).map_err(|e| e.to_string()));
+        config.map(VerifierConfig).map_err(|e| error(&env, kind, &format!("Invalid verifier config: {}", e)))
+    }
+
+    #[napi(getter, js_name = "max_age_ms")]
+    pub fn max_age_ms(&self) -> u64 {
+        self.0.max_age_ms()
+    }
+
+    #[napi(setter, js_name = "max_age_ms")]
+    pub fn set_max_age_ms(&mut self, max_age_ms: BigInt) {
+        self.0.set_max_age_ms(u64_of(max_age_ms));
+    }
+
+    #[napi(getter, js_name = "check_freshness")]
+    pub fn check_freshness(&self) -> bool {
+        self.0.check_freshness()
+    }
+
+    #[napi(setter, js_name = "check_freshness")]
+    pub fn set_check_freshness(&mut self, check_freshness: bool) {
+        self.0.set_check_freshness(check_freshness);
+    }
+
+    #[napi(getter, js_name = "clock_skew_ms")]
+    pub fn clock_skew_ms(&self) -> u64 {
+        self.0.clock_skew_ms()
+    }
+
+    #[napi(setter, js_name = "clock_skew_ms")]
+    pub fn set_clock_skew_ms(&mut self, clock_skew_ms: BigInt) {
+        self.0.set_clock_skew_ms(u64_of(clock_skew_ms));
+    }
+
+    /// Hash algorithm assumed for proofs that don't declare `hash_alg`
+    #[napi(getter, js_name = "hash_algorithm")]
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.0.hash_algorithm().into()
+    }
+
+    #[napi(setter, js_name = "hash_algorithm")]
+    pub fn set_hash_algorithm(&mut self, hash_algorithm: HashAlgorithm) {
+        self.0.set_hash_algorithm(hash_algorithm.into());
+    }
+
+    /// Challenge-response mode: proofs must carry a nonce from `issue_nonce`
+    #[napi(getter, js_name = "require_nonce")]
+    pub fn require_nonce(&self) -> bool {
+        self.0.require_nonce()
+    }
+
+    #[napi(setter, js_name = "require_nonce")]
+    pub fn set_require_nonce(&mut self, require_nonce: bool) {
+        self.0.set_require_nonce(require_nonce);
+    }
+
+    /// Reject proofs without a valid prover signature
+    #[napi(getter, js_name = "require_signature")]
+    pub fn require_signature(&self) -> bool {
+        self.0.require_signature()
+    }
+
+    #[napi(setter, js_name = "require_signature")]
+    pub fn set_require_signature(&mut self, require_signature: bool) {
+        self.0.set_require_signature(require_signature);
+    }
+
+    /// Minimum number of BLS attestors that must co-sign (0 = not required)
+    #[napi(getter, js_name = "min_attestors")]
+    pub fn min_attestors(&self) -> u32 {
+        self.0.min_attestors()
+    }
+
+    #[napi(setter, js_name = "min_attestors")]
+    pub fn set_min_attestors(&mut self, min_attestors: u32) {
+        self.0.set_min_attestors(min_attestors);
+    }
+
+    /// Largest payload `verify_compressed` will inflate (default 64 MiB)
+    #[napi(getter, js_name = "max_decompressed_bytes")]
+    pub fn max_decompressed_bytes(&self) -> u64 {
+        self.0.max_decompressed_bytes()
+    }
+
+    #[napi(setter, js_name = "max_decompressed_bytes")]
+    pub fn set_max_decompressed_bytes(&mut self, max_decompressed_bytes: BigInt) {
+        self.0.set_max_decompressed_bytes(u64_of(max_decompressed_bytes));
+    }
+
+    /// Largest proof `verify_from_url` will download in the WASM build (default 16 MiB)
+    #[napi(getter, js_name = "max_proof_bytes")]
+    pub fn max_proof_bytes(&self) -> u64 {
+        self.0.max_proof_bytes()
+    }
+
+    #[napi(setter, js_name = "max_proof_bytes")]
+    pub fn set_max_proof_bytes(&mut self, max_proof_bytes: BigInt) {
+        self.0.set_max_proof_bytes(u64_of(max_proof_bytes));
+    }
+
+    /// Largest input, output or proof blob accepted for hashing (0 = no limit)
+    #[napi(getter, js_name = "max_input_bytes")]
+    pub fn max_input_bytes(&self) -> u64 {
+        self.0.max_input_bytes()
+    }
+
+    #[napi(setter, js_name = "max_input_bytes")]
+    pub fn set_max_input_bytes(&mut self, max_input_bytes: BigInt) {
+        self.0.set_max_input_bytes(u64_of(max_input_bytes));
+    }
+}
@@ -0,0 +1,80 @@
+// Proof inspection
+// `describe`: decodes a proof in any supported encoding and lists what it claims, for debugging failed verifications
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::failure;
+use crate::{Optional, ProofData};
+
+/// What `describe` found in a proof
+#[napi]
+#[derive(Clone)]
+pub struct ProofDescription(zkml_verifier_core::describe::ProofDescription);
+
+#[napi]
+impl ProofDescription {
+    /// Encoding the proof was decoded from: `json`, `binary`, `cbor`, `protobuf`, `jws` or `cose`
+    #[napi(getter, js_name = "format")]
+    pub fn format(&self) -> String {
+        self.0.format().to_string()
+    }
+
+    /// `gzip` or `zstd` if the proof was compressed
+    #[napi(getter, js_name = "compression")]
+    pub fn compression(&self) -> Optional<String> {
+        self.0.compression().map(str::to_string).into()
+    }
+
+    #[napi(getter, js_name = "proof_id")]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id().to_string()
+    }
+
+    /// The decoded proof (the payload, for JWS and COSE envelopes)
+    #[napi(getter, js_name = "proof")]
+    pub fn proof(&self) -> ProofData {
+        ProofData(self.0.proof().clone())
+    }
+
+    /// Every commitment with the algorithm it's checked with
+    #[napi(getter, js_name = "commitments")]
+    pub fn commitments(&self) -> serde_json::Value {
+        serde_json::to_value(self.0.commitments()).unwrap_or_default()
+    }
+
+    /// Keys, addresses, DIDs and certificate subjects the proof names as signers
+    #[napi(getter, js_name = "signers")]
+    pub fn signers(&self) -> serde_json::Value {
+        serde_json::to_value(self.0.signers()).unwrap_or_default()
+    }
+
+    /// Milliseconds since the proof's `timestamp`; negative if it's in the future
+    #[napi(getter, js_name = "age_ms")]
+    pub fn age_ms(&self) -> f64 {
+        self.0.age_ms()
+    }
+
+    /// Schema problems: unknown fields, undecodable values, half-present signatures
+    #[napi(getter, js_name = "issues")]
+    pub fn issues(&self) -> Vec<String> {
+        self.0.issues().to_vec()
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Decode a proof in any encoding the verifier reads and list its fields, algorithms, signers, age and schema issues
+///
+/// Nothing is verified: use this to see why a proof fails. Throws
+/// `VerificationError.MalformedProof` if the bytes can't be decoded.
+#[napi(js_name = "describe")]
+pub fn describe(env: Env, bytes: Uint8Array) -> Result<ProofDescription> {
+    zkml_verifier_core::describe::describe(&bytes, zkml_verifier_core::clock::system_now_ms())
+        .map(ProofDescription)
+        .map_err(failure(&env))
+}
@@ -0,0 +1,85 @@
+// EAS attestations
+// Turns a passed verification into an Ethereum Attestation Service `attest` request
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::failure;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::eas::EAS_SCHEMA;
+
+/// An `attest` request, ready for the EAS SDK or contract
+#[napi]
+#[derive(Clone)]
+pub struct EasAttestation(zkml_verifier_core::eas::EasAttestation);
+
+#[napi]
+impl EasAttestation {
+    /// UID of the schema the data is encoded with
+    #[napi(getter, js_name = "schema_uid")]
+    pub fn schema_uid(&self) -> String {
+        self.0.schema_uid().to_string()
+    }
+
+    /// ABI-encoded attestation data (hex)
+    #[napi(getter, js_name = "data")]
+    pub fn data(&self) -> String {
+        self.0.data().to_string()
+    }
+
+    /// Address the attestation is about (zero address if none)
+    #[napi(getter, js_name = "recipient")]
+    pub fn recipient(&self) -> String {
+        self.0.recipient().to_string()
+    }
+
+    #[napi(getter, js_name = "revocable")]
+    pub fn revocable(&self) -> bool {
+        self.0.revocable()
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// EAS attestation request for a passed verification
+    ///
+    /// The data encodes `EAS_SCHEMA` (see `eas_schema`): the proof's
+    /// commitments, its ID, when it was proven and when it was verified.
+    /// `schema_uid` defaults to the schema registered revocable with no
+    /// resolver (`eas_schema_uid()`). Throws `VerificationError.InvalidArgument`
+    /// if the report did not pass, an address is malformed or a commitment
+    /// isn't a 32-byte digest.
+    #[napi(js_name = "eas_attestation")]
+    pub fn eas_attestation(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+        recipient: Option<String>,
+        schema_uid: Option<String>,
+    ) -> Result<EasAttestation> {
+        self.read()
+            .eas_attestation(&proof_data.0, &report.0, recipient.as_deref(), schema_uid.as_deref())
+            .map(EasAttestation)
+            .map_err(failure(&env))
+    }
+}
+
+/// `EAS_SCHEMA`, for registering it with EAS's SchemaRegistry
+#[napi(js_name = "eas_schema")]
+pub fn eas_schema() -> String {
+    EAS_SCHEMA.to_string()
+}
+
+/// UID of `EAS_SCHEMA` as registered with `resolver` (default none) and `revocable`
+#[napi(js_name = "eas_schema_uid")]
+pub fn eas_schema_uid(env: Env, resolver: Option<String>, revocable: bool) -> Result<String> {
+    zkml_verifier_core::eas::schema_uid(resolver.as_deref(), revocable).map_err(failure(&env))
+}
@@ -0,0 +1,88 @@
+// EIP-712 proof signatures
+// `signature_alg = "eip712"`: wallet signatures (`eth_signTypedData_v4`) over a proof as typed data
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::failure;
+use crate::{u64_of, Optional, ProofData, WasmVerifier};
+
+/// EIP-712 signing domain; defaults to `{ name: "zkML Proof", version: "1" }`
+///
+/// Contracts that check proof signatures should pin `chain_id` and
+/// `verifying_contract` so signatures can't be replayed elsewhere.
+#[napi]
+#[derive(Clone, Default)]
+pub struct Eip712Domain(zkml_verifier_core::Eip712Domain);
+
+#[napi]
+impl Eip712Domain {
+    /// Domain with the given fields (`name` and `version` default as above)
+    ///
+    /// Throws `VerificationError.InvalidArgument` if `verifying_contract`
+    /// isn't a 20-byte hex address.
+    #[napi(constructor)]
+    pub fn new(
+        env: Env,
+        name: Option<String>,
+        version: Option<String>,
+        chain_id: Option<BigInt>,
+        verifying_contract: Option<String>,
+    ) -> Result<Eip712Domain> {
+        let chain_id = chain_id.map(u64_of);
+        zkml_verifier_core::Eip712Domain::try_new(name, version, chain_id, verifying_contract.as_deref())
+            .map(Eip712Domain)
+            .map_err(failure(&env))
+    }
+
+    #[napi(getter, js_name = "name")]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    #[napi(getter, js_name = "version")]
+    pub fn version(&self) -> String {
+        self.0.version().to_string()
+    }
+
+    #[napi(getter, js_name = "chain_id")]
+    pub fn chain_id(&self) -> Optional<u64> {
+        self.0.chain_id().into()
+    }
+
+    /// Checksummed address of the verifying contract
+    #[napi(getter, js_name = "verifying_contract")]
+    pub fn verifying_contract(&self) -> Optional<String> {
+        self.0.verifying_contract().into()
+    }
+}
+
+#[napi]
+impl ProofData {
+    /// Typed data to sign with a wallet, as JSON for `eth_signTypedData_v4`
+    ///
+    /// The `Proof` message carries the commitments, timestamp, `verified`
+    /// and nonce, plus `extensionsHash` over every other signed field.
+    /// Throws `VerificationError.InvalidArgument` unless the commitments are
+    /// 32-byte digests.
+    #[napi(js_name = "eip712_typed_data")]
+    pub fn eip712_typed_data(&self, env: Env, domain: Option<ClassInstance<Eip712Domain>>) -> Result<String> {
+        let domain = domain.map(|domain| domain.0.clone()).unwrap_or_default();
+        self.0.eip712_typed_data(&domain).map_err(failure(&env))
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Domain `eip712` proof signatures are checked under (`undefined` resets the default)
+    #[napi(setter, js_name = "eip712_domain")]
+    pub fn set_eip712_domain(&mut self, domain: Option<ClassInstance<Eip712Domain>>) {
+        self.write().set_eip712_domain(domain.map(|domain| domain.0.clone()));
+    }
+
+    #[napi(getter, js_name = "eip712_domain")]
+    pub fn eip712_domain(&self) -> Eip712Domain {
+        Eip712Domain(self.read().eip712_domain().clone())
+    }
+}
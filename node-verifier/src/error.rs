@@ -0,0 +1,70 @@
+// Verification errors
+// The WASM build's `VerificationError` enum, and errors thrown with its `name`, `code` and `kind`
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsObject};
+use napi_derive::napi;
+
+use crate::mirror;
+
+mirror! {
+    /// Why a proof was rejected (same codes as the WASM build)
+    VerificationError => zkml_verifier_core::VerificationError {
+        ModelMismatch = 1,
+        InputHashMismatch = 2,
+        OutputHashMismatch = 3,
+        Expired = 4,
+        NotVerified = 5,
+        ProofHashMismatch = 6,
+        MalformedProof = 7,
+        InvalidArgument = 8,
+        UnsupportedHashAlgorithm = 9,
+        NonceMismatch = 10,
+        NonceReplayed = 11,
+        SignatureInvalid = 12,
+        UntrustedSigner = 13,
+        AggregateSignatureInvalid = 14,
+        InsufficientAttestors = 15,
+        CertificateChainInvalid = 16,
+        DidUnresolvable = 17,
+        PayloadTooLarge = 18,
+        InvalidModel = 19,
+        PolicyViolation = 20,
+        QuantizationMismatch = 21,
+        ShapeMismatch = 22,
+        PreprocessingMismatch = 23,
+        RecomputationMismatch = 24,
+        Revoked = 25,
+        VersionNotAllowed = 26,
+        CompositeInvalid = 27,
+        InclusionInvalid = 28,
+        ToleranceExceeded = 29,
+        ClassMismatch = 30,
+        ProofUnavailable = 31,
+        Aborted = 32,
+        AnchorInvalid = 33,
+        NotRegistered = 34,
+    }
+}
+
+/// JS `Error` like the WASM build's `VerificationError.to_js`: `name === "VerificationError"`, numeric `code`, `kind`
+pub(crate) fn error(env: &Env, kind: zkml_verifier_core::VerificationError, message: &str) -> Error {
+    match error_object(env, kind, message) {
+        Ok(error) => Error::from(error.into_unknown()),
+        Err(e) => e,
+    }
+}
+
+/// The object `error` throws
+pub(crate) fn error_object(env: &Env, kind: zkml_verifier_core::VerificationError, message: &str) -> Result<JsObject> {
+    let mut error = env.create_error(Error::from_reason(message))?;
+    error.set_named_property("name", env.create_string("VerificationError")?)?;
+    error.set_named_property("code", env.create_uint32(kind.code())?)?;
+    error.set_named_property("kind", env.create_string(kind.name())?)?;
+    Ok(error)
+}
+
+/// `error` for a core `(VerificationError, reason)` failure
+pub(crate) fn failure<R: AsRef<str>>(env: &Env) -> impl Fn((zkml_verifier_core::VerificationError, R)) -> Error + '_ {
+    move |(kind, reason)| error(env, kind, reason.as_ref())
+}
@@ -0,0 +1,34 @@
+// EVM calldata
+// ABI-encodes a proof's commitments for an on-chain verifier contract's `verifyInference` call
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::failure;
+use crate::ProofData;
+
+#[napi]
+impl ProofData {
+    /// Calldata (hex) for the proof's `verifyInference` call on an on-chain verifier
+    ///
+    /// Encodes `verifyInference(bytes32 modelHash, bytes32 inputHash,
+    /// bytes32 outputHash, bytes32 proofHash, uint64 timestamp, bytes32 nonce,
+    /// bytes signature)` (see `evm_verifier_abi`). Hash tags are dropped, and
+    /// a missing nonce or signature encodes as zero or empty. Throws
+    /// `VerificationError.InvalidArgument` unless every commitment is a
+    /// 32-byte digest.
+    #[napi(js_name = "to_evm_calldata")]
+    pub fn to_evm_calldata(&self, env: Env) -> Result<String> {
+        self.0
+            .evm_calldata()
+            .map(|calldata| format!("0x{}", zkml_verifier_core::hex::encode(&calldata)))
+            .map_err(failure(&env))
+    }
+}
+
+/// ABI fragment of the verifier function `to_evm_calldata` calls, for `new ethers.Interface([...])`
+#[napi(js_name = "evm_verifier_abi")]
+pub fn evm_verifier_abi() -> String {
+    zkml_verifier_core::evm::EVM_VERIFY_ABI.to_string()
+}
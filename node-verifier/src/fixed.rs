@@ -0,0 +1,37 @@
+// Fixed-point conversions
+// The float <-> scaled integer mapping JOLT-Atlas's onnx-tracer applies to model I/O
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::{f32s, list, F32s, I32s};
+
+const UNREPRESENTABLE: &str = "Value cannot be represented at this fixed-point scale";
+
+/// Convert floats to fixed-point integers with `scale` fractional bits
+///
+/// Throws `VerificationError.InvalidArgument` if a value is NaN, infinite
+/// or out of the i32 range once scaled.
+#[napi(js_name = "to_fixed_point")]
+pub fn to_fixed_point(env: Env, values: F32s, scale: i32) -> Result<Int32Array> {
+    zkml_verifier_core::fixed::to_fixed_point(&f32s(values), scale)
+        .map(Int32Array::new)
+        .map_err(|e| error(&env, e, UNREPRESENTABLE))
+}
+
+/// Convert fixed-point integers with `scale` fractional bits back to floats
+#[napi(js_name = "from_fixed_point")]
+pub fn from_fixed_point(values: I32s, scale: i32) -> Float32Array {
+    Float32Array::new(zkml_verifier_core::fixed::from_fixed_point(&list(values), scale))
+}
+
+/// Fixed-point I/O encoding the prover commits to: the integers as i32 little-endian
+#[napi(js_name = "encode_fixed_point")]
+pub fn encode_fixed_point(env: Env, values: F32s, scale: i32) -> Result<Uint8Array> {
+    zkml_verifier_core::fixed::encode_fixed_point(&f32s(values), scale)
+        .map(Uint8Array::from)
+        .map_err(|e| error(&env, e, UNREPRESENTABLE))
+}
+
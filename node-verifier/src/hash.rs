@@ -0,0 +1,115 @@
+// Hashing
+// `HashAlgorithm`, the hash utilities and `StreamingHasher`, as the WASM build exports them
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::failure;
+use crate::{mirror, parallel, Optional, ProofData, VerificationReport, WasmVerifier};
+
+mirror! {
+    /// Hash function used for model/proof/I/O commitments
+    HashAlgorithm => zkml_verifier_core::HashAlgorithm {
+        Sha3_256,
+        Keccak256,
+        Sha256,
+        Blake3,
+    }
+}
+
+const NOT_ENABLED: &str = "Hash algorithm is not enabled in this build";
+
+/// Utility: Compute hash of data (SHA3-256 unless `algorithm` is given)
+#[napi(js_name = "hash_data")]
+pub fn hash_data(env: Env, data: Uint8Array, algorithm: Option<HashAlgorithm>) -> Result<String> {
+    hash_bytes(&env, &data, algorithm)
+}
+
+/// `hash_data` over bytes already in Rust
+pub(crate) fn hash_bytes(env: &Env, data: &[u8], algorithm: Option<HashAlgorithm>) -> Result<String> {
+    let algorithm = algorithm.map(Into::into).unwrap_or_default();
+    zkml_verifier_core::hash::hash_bytes(data, algorithm).map_err(|e| failure(env)((e, NOT_ENABLED)))
+}
+
+/// Utility: Compute hash of data as a tagged string, e.g. `blake3:0x...`
+#[napi(js_name = "hash_data_tagged")]
+pub fn hash_data_tagged(env: Env, data: Uint8Array, algorithm: HashAlgorithm) -> Result<String> {
+    zkml_verifier_core::hash::hash_bytes_tagged(&data, algorithm.into()).map_err(|e| failure(&env)((e, NOT_ENABLED)))
+}
+
+/// Incremental hasher for inputs too large for one `Uint8Array`
+#[napi]
+#[derive(Clone)]
+pub struct StreamingHasher(pub(crate) zkml_verifier_core::hash::StreamingHasher);
+
+#[napi]
+impl StreamingHasher {
+    /// Create hasher (SHA3-256 unless `algorithm` is given)
+    #[napi(constructor)]
+    pub fn new(env: Env, algorithm: Option<HashAlgorithm>) -> Result<StreamingHasher> {
+        zkml_verifier_core::hash::StreamingHasher::with_algorithm(algorithm.map(Into::into).unwrap_or_default())
+            .map(StreamingHasher)
+            .map_err(|e| failure(&env)((e, NOT_ENABLED)))
+    }
+
+    /// Absorb the next chunk
+    #[napi(js_name = "update")]
+    pub fn update(&mut self, chunk: Uint8Array) {
+        self.0.update(&chunk);
+    }
+
+    #[napi(getter, js_name = "algorithm")]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm().into()
+    }
+
+    /// Total bytes absorbed so far
+    #[napi(getter, js_name = "bytes_processed")]
+    pub fn bytes_processed(&self) -> u64 {
+        self.0.bytes_processed()
+    }
+
+    /// Finish hashing and return the `0x`-prefixed hex digest; the hasher is reset
+    #[napi(js_name = "finalize")]
+    pub fn finalize(&mut self) -> String {
+        self.0.finalize()
+    }
+}
+
+/// Hash many buffers at once, across threads (SHA3-256 unless `algorithm` is given)
+#[napi(js_name = "hash_data_batch")]
+pub fn hash_data_batch(env: Env, buffers: Vec<Uint8Array>, algorithm: Option<HashAlgorithm>) -> Result<Vec<String>> {
+    let algorithm = algorithm.map(Into::into).unwrap_or_default();
+    let buffers: Vec<&[u8]> = buffers.iter().map(|buffer| &buffer[..]).collect();
+    parallel::map(&buffers, |data| zkml_verifier_core::hash::hash_bytes(data, algorithm))
+        .into_iter()
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|e| failure(&env)((e, NOT_ENABLED)))
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify proof against inputs/outputs hashed chunk-by-chunk; both hashers are finalized (and reset)
+    #[napi(js_name = "verify_streamed")]
+    pub fn verify_streamed(
+        &self,
+        proof_data: &ProofData,
+        input_hasher: &mut StreamingHasher,
+        output_hasher: &mut StreamingHasher,
+    ) -> VerificationReport {
+        self.read().verify_streamed(&proof_data.0, &mut input_hasher.0, &mut output_hasher.0).into()
+    }
+
+    /// `StreamingHasher` for `verify_streamed` matching the proof's input commitment
+    #[napi(js_name = "input_hasher")]
+    pub fn input_hasher(&self, proof_data: &ProofData) -> Optional<StreamingHasher> {
+        self.read().input_hasher(&proof_data.0).map(StreamingHasher).into()
+    }
+
+    /// `StreamingHasher` for `verify_streamed` matching the proof's output commitment
+    #[napi(js_name = "output_hasher")]
+    pub fn output_hasher(&self, proof_data: &ProofData) -> Optional<StreamingHasher> {
+        self.read().output_hasher(&proof_data.0).map(StreamingHasher).into()
+    }
+}
@@ -0,0 +1,116 @@
+// Proof log inclusion
+// Merkle audit paths showing one proof is in a published batch root, checked without the rest of the batch
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::{error, failure};
+use crate::{u64_of, HashAlgorithm, ProofData};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+const NOT_ENABLED: &str = "Hash algorithm is not enabled in this build";
+
+/// Audit path of one leaf in an RFC 9162 Merkle tree
+#[napi]
+#[derive(Clone)]
+pub struct MerkleProof(pub(crate) zkml_verifier_core::inclusion::MerkleProof);
+
+#[napi]
+impl MerkleProof {
+    /// Audit path as a prover publishes it, leaf first (`algorithm` defaults to SHA3-256)
+    #[napi(constructor)]
+    pub fn new(index: BigInt, leaf_count: BigInt, path: Vec<String>, algorithm: Option<HashAlgorithm>) -> MerkleProof {
+        let alg = algorithm.map(Into::into).unwrap_or_default();
+        MerkleProof(zkml_verifier_core::inclusion::MerkleProof::new(u64_of(index), u64_of(leaf_count), path, alg))
+    }
+
+    /// Position of the leaf in the batch
+    #[napi(getter, js_name = "index")]
+    pub fn index(&self) -> u64 {
+        self.0.index()
+    }
+
+    /// Number of leaves in the batch
+    #[napi(getter, js_name = "leaf_count")]
+    pub fn leaf_count(&self) -> u64 {
+        self.0.leaf_count()
+    }
+
+    #[napi(getter, js_name = "algorithm")]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm().into()
+    }
+
+    #[napi(getter, js_name = "path")]
+    pub fn path(&self) -> Vec<String> {
+        self.0.path().to_vec()
+    }
+
+    /// Check the path against a batch root (see `verify_inclusion`)
+    #[napi(js_name = "verify")]
+    pub fn verify(&self, leaf_hash: String, root: String) -> bool {
+        self.0.verifies(&leaf_hash, &root)
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<MerkleProof> {
+        zkml_verifier_core::inclusion::MerkleProof::from_json(&json)
+            .map(MerkleProof)
+            .map_err(|_| error(&env, Kind::MalformedProof, "Invalid Merkle proof JSON"))
+    }
+}
+
+#[napi]
+impl ProofData {
+    /// This proof's leaf in a proof log: the Merkle leaf hash of its `proof_id`
+    #[napi(js_name = "leaf_hash")]
+    pub fn leaf_hash(&self, env: Env, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        let alg = algorithm.map(Into::into).unwrap_or_default();
+        self.0.leaf_hash(alg).map_err(|e| failure(&env)((e, NOT_ENABLED)))
+    }
+}
+
+/// Merkle leaf hash of `data`, `H(0x00 || data)` (`algorithm` defaults to SHA3-256)
+#[napi(js_name = "merkle_leaf_hash")]
+pub fn merkle_leaf_hash(env: Env, data: Uint8Array, algorithm: Option<HashAlgorithm>) -> Result<String> {
+    let alg = algorithm.map(Into::into).unwrap_or_default();
+    zkml_verifier_core::inclusion::merkle_leaf_hash(&data, alg).map_err(|e| failure(&env)((e, NOT_ENABLED)))
+}
+
+/// Root of a batch of leaf hashes, as a prover publishes it (e.g. daily)
+#[napi(js_name = "merkle_root")]
+pub fn merkle_root(env: Env, leaf_hashes: Vec<String>, algorithm: Option<HashAlgorithm>) -> Result<String> {
+    let alg = algorithm.map(Into::into).unwrap_or_default();
+    zkml_verifier_core::inclusion::merkle_root(&leaf_hashes, alg)
+        .map_err(|e| error(&env, e, "Expected hex leaf hashes"))
+}
+
+/// Build the audit path of leaf `index` of a batch (see `merkle_root`)
+#[napi(js_name = "prove_inclusion")]
+pub fn prove_inclusion(
+    env: Env,
+    leaf_hashes: Vec<String>,
+    index: u32,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<MerkleProof> {
+    let alg = algorithm.map(Into::into).unwrap_or_default();
+    zkml_verifier_core::inclusion::MerkleProof::create(&leaf_hashes, index as usize, alg)
+        .map(MerkleProof)
+        .map_err(|e| error(&env, e, "Expected hex leaf hashes and an index within them"))
+}
+
+/// Check that `leaf_hash` is in the batch with root `root`, given its audit path
+///
+/// Only the path is needed, not the batch. `false` for paths that are
+/// malformed or lead to another root.
+#[napi(js_name = "verify_inclusion")]
+pub fn verify_inclusion(leaf_hash: String, path: &MerkleProof, root: String) -> bool {
+    path.0.verifies(&leaf_hash, &root)
+}
@@ -0,0 +1,26 @@
+// JSON Canonicalization Scheme
+// RFC 8785 serialization, so hashes and signatures over proofs are byte-for-byte reproducible
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::hash::hash_bytes;
+use crate::{HashAlgorithm, ProofData};
+
+use zkml_verifier_core::jcs::canonicalize;
+
+#[napi]
+impl ProofData {
+    /// The whole proof as RFC 8785 canonical JSON
+    #[napi(js_name = "canonical_json")]
+    pub fn canonical_json(&self) -> String {
+        canonicalize(&serde_json::to_value(&self.0).unwrap_or_default())
+    }
+
+    /// Hash of `canonical_json()` (default SHA3-256), stable across re-serialization
+    #[napi(js_name = "canonical_hash")]
+    pub fn canonical_hash(&self, env: Env, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        hash_bytes(&env, self.canonical_json().as_bytes(), algorithm)
+    }
+}
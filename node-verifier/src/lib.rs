@@ -0,0 +1,620 @@
+// Node-native verifier
+// napi-rs bindings mirroring the WASM build's API over the same core checks, with async runs off the JS thread
+
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsFunction, JsObject, JsUnknown, Ref, ValueType};
+use napi_derive::napi;
+
+mod aggregate;
+mod anchor;
+mod audit;
+mod batch;
+mod builder;
+mod chain;
+mod classify;
+mod composite;
+mod config;
+mod describe;
+mod eas;
+mod eip712;
+mod error;
+mod evm;
+mod fixed;
+mod hash;
+mod inclusion;
+mod jcs;
+mod messages;
+mod metadata;
+mod multi;
+mod object;
+mod onnx;
+mod parallel;
+mod policy;
+mod proof;
+mod registry;
+mod report;
+mod session;
+mod signatures;
+mod tensor;
+mod tolerance;
+
+pub use aggregate::{aggregate_inferences, prove_inference, AggregatedProof, InferenceProof};
+pub use anchor::AnchorRecord;
+pub use audit::{AuditEntry, AuditLog};
+pub use batch::BatchReport;
+pub use builder::VerifierBuilder;
+pub use chain::ChainReport;
+pub use composite::CompositeProof;
+pub use config::VerifierConfig;
+pub use describe::{describe, ProofDescription};
+pub use eas::{eas_schema, eas_schema_uid, EasAttestation};
+pub use eip712::Eip712Domain;
+pub use error::VerificationError;
+pub use evm::evm_verifier_abi;
+pub use fixed::{encode_fixed_point, from_fixed_point, to_fixed_point};
+pub use hash::{hash_data, hash_data_batch, hash_data_tagged, HashAlgorithm, StreamingHasher};
+pub use inclusion::{merkle_leaf_hash, merkle_root, prove_inclusion, verify_inclusion, MerkleProof};
+pub use messages::{supported_locales, user_message};
+pub use metadata::{memory_stats, reset_memory_stats, MemoryStats, VerifierMetadata};
+pub use onnx::{
+    commit_onnx_model, hash_onnx_model, hash_onnx_model_external, inspect_model, onnx_merkle_layers, onnx_merkle_root,
+    onnx_partial_hash, prove_onnx_layer, CommitmentPolicy, ExternalDataHasher, LayerProof, ModelCommitment,
+    ModelDiagnostic, ModelHasher, ModelInfo, ModelPolicy, TensorInfo,
+};
+pub use policy::{PreprocessingConfig, QuantizationConfig, VerifierPolicy};
+pub use proof::ProofData;
+pub use registry::{ModelEntry, ModelRegistry};
+pub use report::{Check, CheckResult, Confidence, VerificationReport};
+pub use session::{SessionSummary, VerifierSession};
+pub use signatures::{rekor_receipt_entry, verify_receipt, verify_rekor_entry, KeyResolver, Receipt, RekorInclusion};
+pub use tensor::{
+    encode_tensor_f32, encode_typed_tensor, hash_tensor, hash_tensor_f32, hash_typed_tensor, DType, TensorSpec,
+};
+pub use tolerance::OutputTolerance;
+
+use crate::error::{error, error_object, failure};
+
+use zkml_verifier_core::memory::Tracking;
+use zkml_verifier_core::VerificationError as Kind;
+
+/// Counts the addon's Rust heap for `memory_stats`
+#[global_allocator]
+static ALLOC: Tracking<std::alloc::System> = Tracking::new(std::alloc::System);
+
+/// JS enum for a core enum, with the same variants (and codes), and `From` both ways
+macro_rules! mirror {
+    ($(#[$doc:meta])* $name:ident => $core:ty { $($variant:ident $(= $code:literal)?,)* }) => {
+        $(#[$doc])*
+        #[napi]
+        #[derive(Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($variant $(= $code)?,)*
+        }
+
+        impl From<$core> for $name {
+            fn from(value: $core) -> Self {
+                type Core = $core;
+                match value {
+                    $(Core::$variant => $name::$variant,)*
+                }
+            }
+        }
+
+        impl From<$name> for $core {
+            fn from(value: $name) -> Self {
+                type Core = $core;
+                match value {
+                    $($name::$variant => Core::$variant,)*
+                }
+            }
+        }
+    };
+}
+pub(crate) use mirror;
+
+/// Getter value that is `undefined` when absent, as wasm-bindgen returns `None` (napi's `Option` gives `null`)
+pub struct Optional<T>(Option<T>);
+
+impl<T> From<Option<T>> for Optional<T> {
+    fn from(value: Option<T>) -> Self {
+        Optional(value)
+    }
+}
+
+impl<T: TypeName> TypeName for Optional<T> {
+    fn type_name() -> &'static str {
+        T::type_name()
+    }
+
+    fn value_type() -> ValueType {
+        T::value_type()
+    }
+}
+
+impl<T: ToNapiValue> ToNapiValue for Optional<T> {
+    unsafe fn to_napi_value(env: napi::sys::napi_env, value: Self) -> Result<napi::sys::napi_value> {
+        match value.0 {
+            Some(value) => T::to_napi_value(env, value),
+            None => Undefined::to_napi_value(env, ()),
+        }
+    }
+}
+
+/// Value of a JS `bigint` argument (the WASM build takes `u64` as `bigint` too)
+pub(crate) fn u64_of(value: BigInt) -> u64 {
+    value.get_u64().1
+}
+
+/// `u32` list argument: a `Uint32Array` or a plain array, as wasm-bindgen accepts for `&[u32]`
+pub type U32s = Either<Uint32Array, Vec<u32>>;
+
+/// `i32` list argument: an `Int32Array` or a plain array
+pub type I32s = Either<Int32Array, Vec<i32>>;
+
+/// `f32` list argument: a `Float32Array` or a plain array of numbers
+pub type F32s = Either<Float32Array, Vec<f64>>;
+
+/// Elements of a `U32s` or `I32s` argument
+pub(crate) fn list<A: std::ops::Deref<Target = [T]>, T: Copy>(values: Either<A, Vec<T>>) -> Vec<T> {
+    match values {
+        Either::A(array) => array.to_vec(),
+        Either::B(values) => values,
+    }
+}
+
+/// Elements of an `F32s` argument
+pub(crate) fn f32s(values: F32s) -> Vec<f32> {
+    match values {
+        Either::A(array) => array.to_vec(),
+        Either::B(values) => values.into_iter().map(|value| value as f32).collect(),
+    }
+}
+
+/// `WasmVerifier` with the WASM build's API, natively
+///
+/// The core verifier is shared with the tasks of `verify_async` and
+/// `verify_report_async`, which run on the libuv thread pool. Members backed
+/// by JS callbacks or browser storage are WASM-only (see `index.js`).
+#[napi]
+pub struct WasmVerifier {
+    verifier: Arc<RwLock<zkml_verifier_core::Verifier>>,
+}
+
+impl From<zkml_verifier_core::Verifier> for WasmVerifier {
+    fn from(verifier: zkml_verifier_core::Verifier) -> Self {
+        WasmVerifier { verifier: Arc::new(RwLock::new(verifier)) }
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Create new verifier with model hash
+    #[napi(constructor)]
+    pub fn new(model_hash: String) -> WasmVerifier {
+        zkml_verifier_core::Verifier::new(model_hash).into()
+    }
+
+    /// Create new verifier with model hash and verification policy
+    #[napi(factory, js_name = "with_config")]
+    pub fn with_config(model_hash: String, config: &VerifierConfig) -> WasmVerifier {
+        zkml_verifier_core::Verifier::with_config(model_hash, &config.0).into()
+    }
+
+    /// Create new verifier whose default commitment hash is `algorithm`
+    #[napi(factory, js_name = "with_hash_algorithm")]
+    pub fn with_hash_algorithm(model_hash: String, algorithm: HashAlgorithm) -> WasmVerifier {
+        zkml_verifier_core::Verifier::with_hash_algorithm(model_hash, algorithm.into()).into()
+    }
+
+    /// Create a verifier for an ONNX model file, hashing it internally
+    #[napi(factory, js_name = "from_model_bytes")]
+    pub fn from_model_bytes(
+        env: Env,
+        model: Uint8Array,
+        config: Option<ClassInstance<VerifierConfig>>,
+    ) -> Result<WasmVerifier> {
+        let config = config.map(|config| config.0).unwrap_or_default();
+        zkml_verifier_core::Verifier::try_from_model_bytes(&model, &config)
+            .map(WasmVerifier::from)
+            .map_err(|e| onnx::model_error(&env, e))
+    }
+
+    /// Create a verifier binding proofs to structured model commitments allowed by `policy`
+    ///
+    /// `model_hash` is then unused: proofs must carry the three leaves,
+    /// their root must be the proof's `model_hash`, and pinned leaves must match.
+    #[napi(factory, js_name = "with_commitment_policy")]
+    pub fn with_commitment_policy(
+        policy: &CommitmentPolicy,
+        config: Option<ClassInstance<VerifierConfig>>,
+    ) -> WasmVerifier {
+        let config = config.map(|config| config.0).unwrap_or_default();
+        zkml_verifier_core::Verifier::with_commitment_policy(&policy.0, &config).into()
+    }
+
+    /// Create a verifier for an ONNX model, enforcing `policy` first
+    ///
+    /// The model hash is the canonical `hash_onnx_model` hash. Throws
+    /// `VerificationError.PolicyViolation` listing every violation.
+    #[napi(factory, js_name = "from_onnx_model")]
+    pub fn from_onnx_model(
+        env: Env,
+        model_bytes: Uint8Array,
+        policy: &ModelPolicy,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<WasmVerifier> {
+        let algorithm = algorithm.map(Into::into).unwrap_or_default();
+        zkml_verifier_core::Verifier::from_onnx_model(&model_bytes, &policy.0, algorithm)
+            .map(WasmVerifier::from)
+            .map_err(failure(&env))
+    }
+
+    /// Create a verifier bound only to some layers of a model
+    ///
+    /// Model binding then checks the proof's `partial_model_hash` and
+    /// `partial_layers` instead of `model_hash`.
+    #[napi(factory, js_name = "from_model_layers")]
+    pub fn from_model_layers(
+        env: Env,
+        model_bytes: Uint8Array,
+        layers: Vec<String>,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<WasmVerifier> {
+        let algorithm = algorithm.map(Into::into).unwrap_or_default();
+        zkml_verifier_core::Verifier::from_model_layers(&model_bytes, &layers, algorithm)
+            .map(WasmVerifier::from)
+            .map_err(|e| onnx::partial_error(&env, e))
+    }
+
+    /// Create a verifier accepting proofs for any model in `registry`
+    ///
+    /// The report's `matched_model` says which entry a proof matched.
+    #[napi(factory, js_name = "with_registry")]
+    pub fn with_registry(registry: &ModelRegistry, config: Option<ClassInstance<VerifierConfig>>) -> WasmVerifier {
+        let config = config.map(|config| config.0).unwrap_or_default();
+        zkml_verifier_core::Verifier::with_registry(registry.0.clone(), &config).into()
+    }
+
+    /// Create a verifier accepting proofs for the models `policy` lists
+    ///
+    /// Throws `VerificationError.InvalidArgument` if it lists none: create the
+    /// verifier for its model instead, then call `set_policy`.
+    #[napi(factory, js_name = "from_policy")]
+    pub fn from_policy(env: Env, policy: &VerifierPolicy) -> Result<WasmVerifier> {
+        zkml_verifier_core::Verifier::try_from_policy(&policy.0)
+            .map(WasmVerifier::from)
+            .map_err(|e| error(&env, e, "Policy lists no models"))
+    }
+
+    /// Get the active verification policy
+    #[napi(getter, js_name = "config")]
+    pub fn config(&self) -> VerifierConfig {
+        VerifierConfig(self.read().config())
+    }
+
+    /// Replace the verification policy
+    #[napi(setter, js_name = "config")]
+    pub fn set_config(&mut self, config: &VerifierConfig) {
+        self.write().set_config(&config.0);
+    }
+
+    /// Verify proof cryptographically (see `verify_report` for which check failed)
+    #[napi(js_name = "verify")]
+    pub fn verify(&self, proof_data: &ProofData, input_bytes: Uint8Array, output_bytes: Uint8Array) -> bool {
+        self.read().verify(&proof_data.0, &input_bytes, &output_bytes)
+    }
+
+    /// Verify proof and return per-check results
+    #[napi(js_name = "verify_report")]
+    pub fn verify_report(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> VerificationReport {
+        self.read().verify_report(&proof_data.0, &input_bytes, &output_bytes).into()
+    }
+
+    /// Verify proof, throwing a typed `VerificationError` on the first failed check
+    #[napi(js_name = "verify_strict")]
+    pub fn verify_strict(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<()> {
+        let report = self.read().verify_report(&proof_data.0, &input_bytes, &output_bytes);
+        report.into_result().map_err(failure(&env))
+    }
+
+    /// Verify proof from JSON string
+    #[napi(js_name = "verify_json")]
+    pub fn verify_json(
+        &self,
+        env: Env,
+        proof_json: String,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<bool> {
+        let proof_data = ProofData::parse_json(&env, &proof_json)?;
+        Ok(self.verify(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// Verify proof from JSON string and return per-check results
+    #[napi(js_name = "verify_json_report")]
+    pub fn verify_json_report(
+        &self,
+        env: Env,
+        proof_json: String,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<VerificationReport> {
+        let proof_data = ProofData::parse_json(&env, &proof_json)?;
+        Ok(self.verify_report(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// Verify a proof given as a plain object (`proof.to_object()` or parsed JSON)
+    #[napi(js_name = "verify_object")]
+    pub fn verify_object(
+        &self,
+        env: Env,
+        proof: JsUnknown,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<bool> {
+        let proof_data = ProofData::from_object(env, proof)?;
+        Ok(self.verify(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// `verify_object`, returning per-check results
+    #[napi(js_name = "verify_object_report")]
+    pub fn verify_object_report(
+        &self,
+        env: Env,
+        proof: JsUnknown,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<VerificationReport> {
+        let proof_data = ProofData::from_object(env, proof)?;
+        Ok(self.verify_report(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// Verify proof together with the serialized JOLT proof blob, bound to `proof_hash`
+    #[napi(js_name = "verify_with_proof_bytes")]
+    pub fn verify_with_proof_bytes(
+        &self,
+        proof_data: &ProofData,
+        proof_bytes: Uint8Array,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> bool {
+        let verifier = self.read();
+        let report = verifier.verify_report_with_proof_bytes(&proof_data.0, &proof_bytes, &input_bytes, &output_bytes);
+        report.passed()
+    }
+
+    /// Verify proof JSON and an optional JOLT proof blob, either of which may be gzip- or zstd-compressed
+    #[napi(js_name = "verify_compressed")]
+    pub fn verify_compressed(
+        &self,
+        env: Env,
+        proof_json: Uint8Array,
+        proof_blob: Option<Uint8Array>,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<bool> {
+        let limit = self.read().config().max_decompressed_bytes();
+
+        let json = zkml_verifier_core::compress::decompress(&proof_json, limit).map_err(failure(&env))?;
+        let json =
+            std::str::from_utf8(&json).map_err(|_| error(&env, Kind::MalformedProof, "Proof JSON is not UTF-8"))?;
+        let proof_data = ProofData::parse_json(&env, json)?;
+
+        let verifier = self.read();
+        Ok(match proof_blob {
+            Some(blob) => {
+                let blob = zkml_verifier_core::compress::decompress(&blob, limit).map_err(failure(&env))?;
+                verifier.verify_report_with_proof_bytes(&proof_data.0, &blob, &input_bytes, &output_bytes).passed()
+            }
+            None => verifier.verify(&proof_data.0, &input_bytes, &output_bytes),
+        })
+    }
+
+    /// Verify proof from a `zkml.verifier.v1.Proof` protobuf message
+    #[napi(js_name = "verify_protobuf")]
+    pub fn verify_protobuf(
+        &self,
+        env: Env,
+        proof_bytes: Uint8Array,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<bool> {
+        let proof_data = ProofData::from_protobuf(env, proof_bytes)?;
+        Ok(self.verify(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// `verify`, returning a Promise; the checks run on the libuv thread pool
+    ///
+    /// Once `signal` is aborted the Promise rejects with its `reason`, or
+    /// with `VerificationError.Aborted` for tokens without one. Unlike the
+    /// WASM build the checks aren't interrupted: the abort takes effect
+    /// when they finish.
+    #[napi(js_name = "verify_async", ts_return_type = "Promise<boolean>")]
+    pub fn verify_async(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+        signal: Option<JsObject>,
+    ) -> Result<AsyncTask<Verification<bool>>> {
+        let task = self.task(env, proof_data, input_bytes, output_bytes, signal, |r| r.passed())?;
+        Ok(AsyncTask::new(task))
+    }
+
+    /// `verify_report`, returning a Promise (see `verify_async`)
+    #[napi(js_name = "verify_report_async", ts_return_type = "Promise<VerificationReport>")]
+    pub fn verify_report_async(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+        signal: Option<JsObject>,
+    ) -> Result<AsyncTask<Verification<VerificationReport>>> {
+        let task = self.task(env, proof_data, input_bytes, output_bytes, signal, VerificationReport::from)?;
+        Ok(AsyncTask::new(task))
+    }
+
+    /// Get verifier info as display text
+    #[napi(js_name = "info")]
+    pub fn info(&self) -> String {
+        let verifier = self.read();
+        let model_hash = verifier.model_hash();
+        let models = match verifier.registry() {
+            Some(registry) => format!("{} registered models", registry.len()),
+            None => format!("model: {}...", model_hash.get(..16).unwrap_or(model_hash)),
+        };
+        format!("WASM Verifier for {}\nVerifies: Model binding, I/O integrity, Proof validity, Timestamp", models)
+    }
+
+    /// Issue a single-use challenge nonce for the prover to bind into its proof
+    #[napi(js_name = "issue_nonce")]
+    pub fn issue_nonce(&self) -> Result<String> {
+        self.read()
+            .issue_nonce()
+            .map_err(|e| Error::from_reason(format!("Failed to generate nonce: {}", e)))
+    }
+
+    /// Trust proofs signed by this Ed25519 public key or Ethereum address (hex)
+    #[napi(js_name = "add_trusted_signer")]
+    pub fn add_trusted_signer(&mut self, pubkey: String) {
+        self.write().add_trusted_signer(pubkey);
+    }
+
+    /// Trust a BLS12-381 attestor public key (48-byte compressed G1, hex)
+    #[napi(js_name = "add_trusted_attestor")]
+    pub fn add_trusted_attestor(&mut self, pubkey: String) {
+        self.write().add_trusted_attestor(pubkey);
+    }
+
+    /// Add a root CA certificate (PEM or base64 DER) to the trust store
+    #[napi(js_name = "add_trusted_root")]
+    pub fn add_trusted_root(&mut self, env: Env, certificate: String) -> Result<()> {
+        self.write().add_trusted_root(&certificate).map_err(failure(&env))
+    }
+
+    /// Load a signed revocation list, replacing the current one
+    #[napi(js_name = "load_revocation_list")]
+    pub fn load_revocation_list(&mut self, env: Env, list_json: String, issuer_pubkey: String) -> Result<()> {
+        self.write().load_revocation_list(&list_json, &issuer_pubkey).map_err(failure(&env))
+    }
+
+    /// `issued_at` of the loaded revocation list (`undefined` if none)
+    #[napi(getter, js_name = "revocation_list_issued_at")]
+    pub fn revocation_list_issued_at(&self) -> Optional<u64> {
+        self.read().revocation_list_issued_at().into()
+    }
+
+    /// Only accept proofs whose model version satisfies `policy`, e.g. `">=1.2, <2"`
+    #[napi(js_name = "set_version_policy")]
+    pub fn set_version_policy(&mut self, env: Env, policy: String) -> Result<()> {
+        self.write().set_version_policy(&policy).map_err(|e| error(&env, e, "Invalid version policy"))
+    }
+}
+
+impl WasmVerifier {
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, zkml_verifier_core::Verifier> {
+        self.verifier.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn write(&self) -> RwLockWriteGuard<'_, zkml_verifier_core::Verifier> {
+        self.verifier.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Background run of the checks on owned copies of the proof and I/O
+    fn task<T>(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+        signal: Option<JsObject>,
+        finish: fn(zkml_verifier_core::VerificationReport) -> T,
+    ) -> Result<Verification<T>> {
+        Ok(Verification {
+            verifier: Arc::clone(&self.verifier),
+            proof_data: proof_data.0.clone(),
+            input_bytes: input_bytes.to_vec(),
+            output_bytes: output_bytes.to_vec(),
+            signal: signal.map(|signal| env.create_reference(signal)).transpose()?,
+            finish,
+        })
+    }
+}
+
+/// Background run for `verify_async` and `verify_report_async`
+pub struct Verification<T> {
+    verifier: Arc<RwLock<zkml_verifier_core::Verifier>>,
+    proof_data: zkml_verifier_core::ProofData,
+    input_bytes: Vec<u8>,
+    output_bytes: Vec<u8>,
+    /// `AbortSignal` (or any object with an `aborted` flag) checked before resolving
+    signal: Option<Ref<()>>,
+    finish: fn(zkml_verifier_core::VerificationReport) -> T,
+}
+
+impl<T> Verification<T> {
+    /// What an aborted signal rejects with: its `reason`, or `VerificationError.Aborted`
+    fn aborted(&self, env: &Env) -> Result<Option<JsUnknown>> {
+        let Some(signal) = &self.signal else {
+            return Ok(None);
+        };
+        let signal: JsObject = env.get_reference_value(signal)?;
+        if !signal.get_named_property::<JsUnknown>("aborted")?.coerce_to_bool()?.get_value()? {
+            return Ok(None);
+        }
+        let reason: JsUnknown = signal.get_named_property("reason")?;
+        Ok(Some(match reason.get_type()? {
+            ValueType::Undefined => error_object(env, Kind::Aborted, "Verification aborted")?.into_unknown(),
+            _ => reason,
+        }))
+    }
+}
+
+impl<T: ToNapiValue + TypeName + Send + 'static> Task for Verification<T> {
+    type Output = T;
+    /// The result, or a rejected Promise the task's Promise adopts (napi would
+    /// replace a rejection value that isn't an `Error`, e.g. a `DOMException`)
+    type JsValue = Either<T, JsObject>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let verifier = self.verifier.read().unwrap_or_else(PoisonError::into_inner);
+        Ok((self.finish)(verifier.verify_report(&self.proof_data, &self.input_bytes, &self.output_bytes)))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let Some(reason) = self.aborted(&env)? else {
+            return Ok(Either::A(output));
+        };
+        let promise = env.get_global()?.get_named_property::<JsFunction>("Promise")?.coerce_to_object()?;
+        let reject: JsFunction = promise.get_named_property("reject")?;
+        Ok(Either::B(reject.call(Some(&promise), &[reason])?.coerce_to_object()?))
+    }
+
+    fn finally(&mut self, env: Env) -> Result<()> {
+        if let Some(mut signal) = self.signal.take() {
+            signal.unref(env)?;
+        }
+        Ok(())
+    }
+}
+
+/// Utility: Get current timestamp
+#[napi(js_name = "get_timestamp")]
+pub fn get_timestamp() -> u64 {
+    zkml_verifier_core::clock::system_now_ms()
+}
@@ -0,0 +1,41 @@
+// End-user messages
+// Localized, non-technical wording for each `VerificationError`, separate from the developer-facing reasons
+
+use napi_derive::napi;
+
+use crate::error::VerificationError;
+use crate::report::{CheckResult, VerificationReport};
+use crate::Optional;
+
+/// End-user description of `error`, in `locale` (e.g. `Intl.DateTimeFormat().resolvedOptions().locale`)
+///
+/// Unknown locales fall back to English. Unlike a check's `reason`, the
+/// wording is meant for end users and stays the same across releases.
+#[napi(js_name = "user_message")]
+pub fn user_message(error: VerificationError, locale: Option<String>) -> String {
+    zkml_verifier_core::messages::user_message(error.into(), locale.as_deref()).to_string()
+}
+
+/// Language tags `user_message` has a catalog for
+#[napi(js_name = "supported_locales")]
+pub fn supported_locales() -> Vec<String> {
+    zkml_verifier_core::messages::supported_locales().into_iter().map(str::to_string).collect()
+}
+
+#[napi]
+impl CheckResult {
+    /// `user_message` for this check's failure (`undefined` when it passed)
+    #[napi(js_name = "user_message")]
+    pub fn user_message(&self, locale: Option<String>) -> Optional<String> {
+        self.0.user_message(locale.as_deref()).map(str::to_string).into()
+    }
+}
+
+#[napi]
+impl VerificationReport {
+    /// `user_message` for the first failed check (`undefined` when all passed)
+    #[napi(js_name = "user_message")]
+    pub fn user_message(&self, locale: Option<String>) -> Optional<String> {
+        self.0.user_message(locale.as_deref()).map(str::to_string).into()
+    }
+}
@@ -0,0 +1,117 @@
+// Verifier metadata
+// Structured description of a configured verifier, and the addon's memory accounting
+
+use napi_derive::napi;
+
+use crate::{Optional, WasmVerifier};
+
+/// What `WasmVerifier::metadata` reports
+#[napi]
+#[derive(Clone)]
+pub struct VerifierMetadata(zkml_verifier_core::metadata::VerifierMetadata);
+
+#[napi]
+impl VerifierMetadata {
+    /// Version of this verifier package
+    #[napi(getter, js_name = "version")]
+    pub fn version(&self) -> String {
+        self.0.version().to_string()
+    }
+
+    /// Proof encodings this build accepts, e.g. `"json"` or `"zkpf/1"` (binary format version 1)
+    #[napi(getter, js_name = "proof_formats")]
+    pub fn proof_formats(&self) -> Vec<String> {
+        self.0.proof_formats().to_vec()
+    }
+
+    /// Models proofs may be bound to: the pinned hash, or every registry entry
+    #[napi(getter, js_name = "model_hashes")]
+    pub fn model_hashes(&self) -> Vec<String> {
+        self.0.model_hashes().to_vec()
+    }
+
+    /// Checks this verifier runs, by `CheckName`, in report order
+    #[napi(getter, js_name = "checks")]
+    pub fn checks(&self) -> Vec<String> {
+        self.0.checks().to_vec()
+    }
+
+    /// Hash algorithms enabled in this build
+    #[napi(getter, js_name = "hash_algorithms")]
+    pub fn hash_algorithms(&self) -> Vec<String> {
+        self.0.hash_algorithms().to_vec()
+    }
+
+    /// Algorithm for commitments of proofs that don't name one
+    #[napi(getter, js_name = "default_hash_algorithm")]
+    pub fn default_hash_algorithm(&self) -> String {
+        self.0.default_hash_algorithm().to_string()
+    }
+
+    /// Serialize metadata to JSON string
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Structured description of this verifier (see `VerifierMetadata`)
+    #[napi(js_name = "metadata")]
+    pub fn metadata(&self) -> VerifierMetadata {
+        VerifierMetadata(self.read().metadata(env!("CARGO_PKG_VERSION")))
+    }
+}
+
+/// What `memory_stats` reports
+#[napi]
+#[derive(Clone)]
+pub struct MemoryStats(zkml_verifier_core::memory::MemoryStats);
+
+#[napi]
+impl MemoryStats {
+    /// Bytes currently allocated on the addon's Rust heap
+    #[napi(getter, js_name = "heap_bytes")]
+    pub fn heap_bytes(&self) -> u64 {
+        self.0.heap_bytes()
+    }
+
+    /// Most bytes allocated at once since load (or `reset_memory_stats`)
+    #[napi(getter, js_name = "peak_heap_bytes")]
+    pub fn peak_heap_bytes(&self) -> u64 {
+        self.0.peak_heap_bytes()
+    }
+
+    /// Size of WASM linear memory (always `undefined` for the native addon)
+    #[napi(getter, js_name = "memory_bytes")]
+    pub fn memory_bytes(&self) -> Optional<u64> {
+        self.0.memory_bytes().into()
+    }
+
+    /// Bytes hashed since load (or `reset_memory_stats`), including proof and model data
+    #[napi(getter, js_name = "bytes_hashed")]
+    pub fn bytes_hashed(&self) -> u64 {
+        self.0.bytes_hashed()
+    }
+
+    /// Serialize stats to JSON string
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Current Rust heap usage of the addon, its peak, and bytes hashed
+///
+/// Counts are shared by every verifier in the process.
+#[napi(js_name = "memory_stats")]
+pub fn memory_stats() -> MemoryStats {
+    MemoryStats(zkml_verifier_core::memory::memory_stats())
+}
+
+/// Restart the peak at the current heap size and the hashed byte count at zero
+#[napi(js_name = "reset_memory_stats")]
+pub fn reset_memory_stats() {
+    zkml_verifier_core::memory::reset_memory_stats();
+}
@@ -0,0 +1,98 @@
+// Named multi-input / multi-output proofs
+// Per-name I/O commitments for models with several inputs or outputs (e.g. tokens + attention mask)
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsObject, JsString, JsTypedArray, JsUnknown, KeyCollectionMode, KeyConversion, KeyFilter};
+use napi::{TypedArrayType, ValueType};
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::{Optional, ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// Collect a JS object of `name -> Uint8Array` into named buffers
+pub(crate) fn named_buffers(env: &Env, object: &JsObject, what: &str) -> Result<Vec<(String, Vec<u8>)>> {
+    let (own, enumerable) = (KeyCollectionMode::OwnOnly, KeyFilter::Enumerable);
+    let names = object.get_all_property_names(own, enumerable, KeyConversion::NumbersToStrings)?;
+    let mut buffers = Vec::new();
+    for i in 0..names.get_array_length()? {
+        // Symbol keys are skipped, as `Object.entries` does
+        let name: JsUnknown = names.get_element(i)?;
+        if name.get_type()? != ValueType::String {
+            continue;
+        }
+        // SAFETY: checked to be a string just above
+        let name = unsafe { name.cast::<JsString>() }.into_utf8()?.into_owned()?;
+        let data: JsUnknown = object.get_named_property(&name)?;
+        let not_bytes = || error(env, Kind::InvalidArgument, &format!("{} '{}' is not a Uint8Array", what, name));
+        if !data.is_typedarray()? {
+            return Err(not_bytes());
+        }
+        // SAFETY: checked to be a typed array just above
+        let data = unsafe { data.cast::<JsTypedArray>() }.into_value()?;
+        if data.typedarray_type != TypedArrayType::Uint8 {
+            return Err(not_bytes());
+        }
+        let bytes = AsRef::<[u8]>::as_ref(&data).to_vec();
+        buffers.push((name, bytes));
+    }
+    Ok(buffers)
+}
+
+#[napi]
+impl ProofData {
+    /// Names of the committed inputs, in sorted order
+    #[napi(getter, js_name = "input_names")]
+    pub fn input_names(&self) -> Vec<String> {
+        self.0.inputs.keys().cloned().collect()
+    }
+
+    /// Names of the committed outputs, in sorted order
+    #[napi(getter, js_name = "output_names")]
+    pub fn output_names(&self) -> Vec<String> {
+        self.0.outputs.keys().cloned().collect()
+    }
+
+    #[napi(js_name = "input_commitment")]
+    pub fn input_commitment(&self, name: String) -> Optional<String> {
+        self.0.inputs.get(&name).cloned().into()
+    }
+
+    #[napi(js_name = "output_commitment")]
+    pub fn output_commitment(&self, name: String) -> Optional<String> {
+        self.0.outputs.get(&name).cloned().into()
+    }
+
+    /// Commit to a named input (`hash_data` of its buffer)
+    #[napi(js_name = "set_input_commitment")]
+    pub fn set_input_commitment(&mut self, name: String, commitment: String) {
+        self.0.inputs.insert(name, commitment);
+    }
+
+    /// Commit to a named output
+    #[napi(js_name = "set_output_commitment")]
+    pub fn set_output_commitment(&mut self, name: String, commitment: String) {
+        self.0.outputs.insert(name, commitment);
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify a proof over named inputs and outputs
+    ///
+    /// Each buffer is checked against the proof's commitment of the same
+    /// name; `input_hash` and `output_hash` are not used.
+    #[napi(js_name = "verify_multi")]
+    pub fn verify_multi(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        inputs: JsObject,
+        outputs: JsObject,
+    ) -> Result<VerificationReport> {
+        let inputs = named_buffers(&env, &inputs, "Input")?;
+        let outputs = named_buffers(&env, &outputs, "Output")?;
+        Ok(self.read().multi_report(&proof_data.0, &inputs, &outputs).into())
+    }
+}
@@ -0,0 +1,96 @@
+// Plain JS objects
+// Proofs and settings passed as already-parsed objects, walked into `serde_json::Value` as the WASM build does
+
+use napi::{
+    JsBigInt, JsBoolean, JsNumber, JsObject, JsString, JsUnknown, KeyCollectionMode, KeyConversion, KeyFilter,
+    ValueType,
+};
+use serde_json::{Map, Number, Value};
+
+/// Largest integer a JS number holds exactly (`Number.MAX_SAFE_INTEGER`)
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Deepest nesting `to_value` walks, so a cyclic object fails instead of overflowing the stack
+const MAX_DEPTH: usize = 32;
+
+/// JSON number for a JS number; integral values become integers so they deserialize into `u64` fields
+fn number(n: f64) -> Option<Number> {
+    if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER {
+        Some(if n < 0.0 { Number::from(n as i64) } else { Number::from(n as u64) })
+    } else {
+        Number::from_f64(n)
+    }
+}
+
+/// Walk a JS value into the `serde_json::Value` that `JSON.stringify` + parse would give
+///
+/// Like `JSON.stringify`, `undefined` properties are dropped. `BigInt`s are
+/// accepted as integers (e.g. `timestamp: 1700000000000n`); functions,
+/// symbols, non-finite numbers and objects nested over `MAX_DEPTH` deep
+/// (including cyclic ones) are rejected. napi's own `serde_json::Value`
+/// conversion can't be used: it panics on `BigInt`s.
+pub(crate) fn to_value(value: &JsUnknown) -> Result<Value, String> {
+    walk(value, 0)
+}
+
+fn walk(value: &JsUnknown, depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Nested more than {} levels deep", MAX_DEPTH));
+    }
+    let unreadable = |e: napi::Error| e.reason;
+    // SAFETY: each cast matches the value type just read
+    match value.get_type().map_err(unreadable)? {
+        ValueType::Null | ValueType::Undefined => Ok(Value::Null),
+        ValueType::Boolean => Ok(Value::Bool(unsafe { value.cast::<JsBoolean>() }.get_value().map_err(unreadable)?)),
+        ValueType::Number => {
+            let n = unsafe { value.cast::<JsNumber>() }.get_double().map_err(unreadable)?;
+            number(n).map(Value::Number).ok_or_else(|| format!("{} is not a JSON number", n))
+        }
+        ValueType::String => {
+            let s = unsafe { value.cast::<JsString>() }.into_utf8().and_then(|s| s.into_owned());
+            Ok(Value::String(s.map_err(unreadable)?))
+        }
+        ValueType::BigInt => {
+            let bigint = unsafe { value.cast::<JsBigInt>() };
+            match (bigint.get_u64().map_err(unreadable)?, bigint.get_i64().map_err(unreadable)?) {
+                ((n, true), _) => Ok(Value::Number(n.into())),
+                (_, (n, true)) => Ok(Value::Number(n.into())),
+                _ => Err("BigInt does not fit in 64 bits".to_string()),
+            }
+        }
+        ValueType::Object => {
+            let object = unsafe { value.cast::<JsObject>() };
+            if object.is_array().map_err(unreadable)? {
+                let len = object.get_array_length().map_err(unreadable)?;
+                let items = (0..len).map(|i| {
+                    let item = object.get_element::<JsUnknown>(i).map_err(unreadable)?;
+                    walk(&item, depth + 1)
+                });
+                return items.collect::<Result<_, _>>().map(Value::Array);
+            }
+            let keys = object
+                .get_all_property_names(
+                    KeyCollectionMode::OwnOnly,
+                    KeyFilter::Enumerable,
+                    KeyConversion::NumbersToStrings,
+                )
+                .map_err(unreadable)?;
+            let mut fields = Map::new();
+            for i in 0..keys.get_array_length().map_err(unreadable)? {
+                let key = keys.get_element::<JsUnknown>(i).map_err(unreadable)?;
+                if key.get_type().map_err(unreadable)? != ValueType::String {
+                    continue;
+                }
+                let key = unsafe { key.cast::<JsString>() }.into_utf8().and_then(|s| s.into_owned());
+                let key = key.map_err(unreadable)?;
+                let field = object.get_named_property::<JsUnknown>(&key).map_err(|_| "Unreadable property")?;
+                if field.get_type().map_err(unreadable)? != ValueType::Undefined {
+                    let field = walk(&field, depth + 1).map_err(|e| format!("{}: {}", key, e))?;
+                    fields.insert(key, field);
+                }
+            }
+            Ok(Value::Object(fields))
+        }
+        _ => Err("Value has no JSON form".to_string()),
+    }
+}
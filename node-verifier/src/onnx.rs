@@ -0,0 +1,595 @@
+// ONNX model hashing
+// The WASM build's `onnx` exports: canonical and structured model hashes, layer commitments and model policies
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsObject};
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::multi::named_buffers;
+use crate::{u64_of, HashAlgorithm, Optional, ProofData, WasmVerifier};
+
+use zkml_verifier_core::onnx::merkle::{layer_labels, merkle_root};
+use zkml_verifier_core::onnx::{model_hash, partial::partial_root};
+use zkml_verifier_core::VerificationError as Kind;
+
+const INVALID_MODEL: &str = "Bytes are not a valid ONNX model";
+const NOT_ENABLED: &str = "Hash algorithm is not enabled in this build";
+const CANNOT_COMMIT: &str = "Cannot commit to ONNX model";
+
+/// Error for a failed canonical model hash
+pub(crate) fn model_error(env: &Env, e: Kind) -> Error {
+    match e {
+        Kind::InvalidModel => error(env, e, INVALID_MODEL),
+        e => error(env, e, NOT_ENABLED),
+    }
+}
+
+/// Error for a failed partial-model commitment
+pub(crate) fn partial_error(env: &Env, e: Kind) -> Error {
+    match e {
+        Kind::InvalidArgument => error(env, e, "Selection is empty or names a layer the model doesn't have"),
+        e => error(env, e, CANNOT_COMMIT),
+    }
+}
+
+/// Thrown when a hasher is used after `finalize`, which consumes it in the WASM build
+fn finalized(env: &Env) -> Error {
+    error(env, Kind::InvalidArgument, "Hasher was already finalized")
+}
+
+/// Canonical hash of an ONNX model, for use as `model_hash` (default SHA3-256)
+///
+/// Unlike `hash_data` on the file bytes, re-exporting the same model with a
+/// different producer, doc strings or node names gives the same hash.
+#[napi(js_name = "hash_onnx_model")]
+pub fn hash_onnx_model(env: Env, model_bytes: Uint8Array, algorithm: Option<HashAlgorithm>) -> Result<String> {
+    model_hash(&model_bytes, algorithm.map(Into::into).unwrap_or_default()).map_err(|e| model_error(&env, e))
+}
+
+/// Architecture, weights and metadata hashes of a model, and their root
+#[napi]
+#[derive(Clone)]
+pub struct ModelCommitment(pub(crate) zkml_verifier_core::ModelCommitment);
+
+#[napi]
+impl ModelCommitment {
+    #[napi(getter, js_name = "architecture_hash")]
+    pub fn architecture_hash(&self) -> String {
+        self.0.architecture_hash().to_string()
+    }
+
+    #[napi(getter, js_name = "weights_hash")]
+    pub fn weights_hash(&self) -> String {
+        self.0.weights_hash().to_string()
+    }
+
+    #[napi(getter, js_name = "metadata_hash")]
+    pub fn metadata_hash(&self) -> String {
+        self.0.metadata_hash().to_string()
+    }
+
+    /// Root over the three leaves, for use as `model_hash`
+    #[napi(getter, js_name = "root")]
+    pub fn root(&self) -> String {
+        self.0.root().to_string()
+    }
+}
+
+/// Structured commitment to an ONNX model (see `ProofData.set_model_commitment`)
+#[napi(js_name = "commit_onnx_model")]
+pub fn commit_onnx_model(
+    env: Env,
+    model_bytes: Uint8Array,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<ModelCommitment> {
+    zkml_verifier_core::ModelCommitment::create(&model_bytes, algorithm.map(Into::into).unwrap_or_default())
+        .map(ModelCommitment)
+        .map_err(|e| model_error(&env, e))
+}
+
+#[napi]
+impl ProofData {
+    /// Set `model_hash` to the commitment's root and carry its three leaves
+    #[napi(js_name = "set_model_commitment")]
+    pub fn set_model_commitment(&mut self, commitment: &ModelCommitment) {
+        self.0.set_model_commitment(&commitment.0);
+    }
+}
+
+/// Which leaves of a structured model commitment a verifier pins
+///
+/// Unpinned leaves may take any value, e.g. pin the architecture and
+/// approve a list of fine-tuned weights.
+#[napi]
+#[derive(Clone, Default)]
+pub struct CommitmentPolicy(pub(crate) zkml_verifier_core::CommitmentPolicy);
+
+#[napi]
+impl CommitmentPolicy {
+    /// Policy pinning nothing (any well-formed commitment passes)
+    #[napi(constructor)]
+    pub fn new() -> CommitmentPolicy {
+        CommitmentPolicy::default()
+    }
+
+    #[napi(getter, js_name = "architecture_hash")]
+    pub fn architecture_hash(&self) -> Optional<String> {
+        self.0.architecture_hash().map(str::to_string).into()
+    }
+
+    #[napi(setter, js_name = "architecture_hash")]
+    pub fn set_architecture_hash(&mut self, architecture_hash: Option<String>) {
+        self.0.set_architecture_hash(architecture_hash);
+    }
+
+    /// Approved weights hashes (empty = any weights)
+    #[napi(getter, js_name = "weights_hashes")]
+    pub fn weights_hashes(&self) -> Vec<String> {
+        self.0.weights_hashes().to_vec()
+    }
+
+    #[napi(js_name = "approve_weights")]
+    pub fn approve_weights(&mut self, weights_hash: String) {
+        self.0.approve_weights(weights_hash);
+    }
+
+    #[napi(getter, js_name = "metadata_hash")]
+    pub fn metadata_hash(&self) -> Optional<String> {
+        self.0.metadata_hash().map(str::to_string).into()
+    }
+
+    #[napi(setter, js_name = "metadata_hash")]
+    pub fn set_metadata_hash(&mut self, metadata_hash: Option<String>) {
+        self.0.set_metadata_hash(metadata_hash);
+    }
+}
+
+/// Which parts of the model a failed binding differs in
+///
+/// Leaf comparisons are `undefined` when the proof doesn't carry that
+/// sub-hash (see `ProofData.set_model_commitment`).
+#[napi]
+#[derive(Clone)]
+pub struct ModelDiagnostic(pub(crate) zkml_verifier_core::ModelDiagnostic);
+
+#[napi]
+impl ModelDiagnostic {
+    /// Whether the proof's `architecture_hash` matches (`undefined` if it has none)
+    #[napi(getter, js_name = "architecture")]
+    pub fn architecture(&self) -> Optional<bool> {
+        self.0.architecture().into()
+    }
+
+    #[napi(getter, js_name = "weights")]
+    pub fn weights(&self) -> Optional<bool> {
+        self.0.weights().into()
+    }
+
+    #[napi(getter, js_name = "metadata")]
+    pub fn metadata(&self) -> Optional<bool> {
+        self.0.metadata().into()
+    }
+
+    /// Whether `model_hash` is the plain hash of the model file bytes
+    #[napi(getter, js_name = "file_hash")]
+    pub fn file_hash(&self) -> bool {
+        self.0.file_hash()
+    }
+
+    /// One-line explanation, also appended to the binding failure reason
+    #[napi(getter, js_name = "summary")]
+    pub fn summary(&self) -> String {
+        self.0.summary().to_string()
+    }
+}
+
+/// Error for a failed external-data model hash
+fn external_error(env: &Env, e: Kind) -> Error {
+    match e {
+        Kind::InvalidModel => error(env, e, "Not a valid ONNX model, or its external data is incomplete"),
+        Kind::InvalidArgument => error(env, e, "Model has no external data in that file"),
+        e => error(env, e, NOT_ENABLED),
+    }
+}
+
+/// Canonical hash of an ONNX model plus its external data files, fed in chunks
+///
+/// External tensors are hashed on their own, so the hash doesn't depend on
+/// file names or how tensors are laid out across files.
+#[napi]
+pub struct ExternalDataHasher(Option<zkml_verifier_core::onnx::external::ExternalDataHasher>);
+
+#[napi]
+impl ExternalDataHasher {
+    /// Start hashing a model (SHA3-256 unless `algorithm` is given)
+    #[napi(constructor)]
+    pub fn new(env: Env, model_bytes: Uint8Array, algorithm: Option<HashAlgorithm>) -> Result<ExternalDataHasher> {
+        let algorithm = algorithm.map(Into::into).unwrap_or_default();
+        zkml_verifier_core::onnx::external::ExternalDataHasher::create(&model_bytes, algorithm)
+            .map(|hasher| ExternalDataHasher(Some(hasher)))
+            .map_err(|e| external_error(&env, e))
+    }
+
+    /// External data files the model references (`location` entries)
+    #[napi(getter, js_name = "required_files")]
+    pub fn required_files(&self, env: Env) -> Result<Vec<String>> {
+        self.0.as_ref().map(|hasher| hasher.required_files()).ok_or_else(|| finalized(&env))
+    }
+
+    /// Absorb the next chunk of file `location`; chunks of a file must arrive in order
+    #[napi(js_name = "update")]
+    pub fn update(&mut self, env: Env, location: String, chunk: Uint8Array) -> Result<()> {
+        let hasher = self.0.as_mut().ok_or_else(|| finalized(&env))?;
+        hasher.feed(&location, &chunk).map_err(|e| external_error(&env, e))
+    }
+
+    /// Finish and return the canonical model hash
+    ///
+    /// Throws `VerificationError.InvalidModel` if any external tensor
+    /// wasn't fed in full.
+    #[napi(js_name = "finalize")]
+    pub fn finalize(&mut self, env: Env) -> Result<String> {
+        let hasher = self.0.take().ok_or_else(|| finalized(&env))?;
+        hasher.finish().map_err(|e| external_error(&env, e))
+    }
+}
+
+/// Canonical hash of a model and its external data, given as `{ "weights.bin": Uint8Array }`
+#[napi(js_name = "hash_onnx_model_external")]
+pub fn hash_onnx_model_external(
+    env: Env,
+    model_bytes: Uint8Array,
+    files: JsObject,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String> {
+    let mut hasher = ExternalDataHasher::new(env, model_bytes, algorithm)?;
+    for (location, data) in named_buffers(&env, &files, "External data file")? {
+        hasher.update(env, location, data.into())?;
+    }
+    ExternalDataHasher::finalize(&mut hasher, env)
+}
+
+/// A graph input or output
+#[napi]
+#[derive(Clone)]
+pub struct TensorInfo(zkml_verifier_core::onnx::inspect::TensorInfo);
+
+/// What `inspect_model` reports about an ONNX model
+#[napi]
+#[derive(Clone)]
+pub struct ModelInfo(zkml_verifier_core::onnx::inspect::ModelInfo);
+
+#[napi]
+impl TensorInfo {
+    #[napi(getter, js_name = "name")]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    /// Element type, e.g. `"float32"` (`"unknown"` for non-tensor values)
+    #[napi(getter, js_name = "dtype")]
+    pub fn dtype(&self) -> String {
+        self.0.dtype().to_string()
+    }
+
+    /// Dimensions, with -1 for dynamic ones
+    #[napi(getter, js_name = "shape")]
+    pub fn shape(&self) -> BigInt64Array {
+        BigInt64Array::new(self.0.shape().to_vec())
+    }
+
+    /// Symbolic names of the dimensions (`""` where the size is fixed or unnamed)
+    #[napi(getter, js_name = "dim_params")]
+    pub fn dim_params(&self) -> Vec<String> {
+        self.0.dim_params().to_vec()
+    }
+}
+
+#[napi]
+impl ModelInfo {
+    #[napi(getter, js_name = "ir_version")]
+    pub fn ir_version(&self) -> BigInt {
+        self.0.ir_version().into()
+    }
+
+    /// Version of the default (`ai.onnx`) operator set, 0 if not imported
+    #[napi(getter, js_name = "opset_version")]
+    pub fn opset_version(&self) -> BigInt {
+        self.0.opset_version().into()
+    }
+
+    #[napi(getter, js_name = "producer_name")]
+    pub fn producer_name(&self) -> String {
+        self.0.producer_name().to_string()
+    }
+
+    /// Runtime inputs (initializers listed as graph inputs are excluded)
+    #[napi(getter, js_name = "inputs")]
+    pub fn inputs(&self) -> Vec<TensorInfo> {
+        self.0.inputs().iter().cloned().map(TensorInfo).collect()
+    }
+
+    #[napi(getter, js_name = "outputs")]
+    pub fn outputs(&self) -> Vec<TensorInfo> {
+        self.0.outputs().iter().cloned().map(TensorInfo).collect()
+    }
+
+    /// Total number of weight elements across all initializers
+    #[napi(getter, js_name = "parameter_count")]
+    pub fn parameter_count(&self) -> u64 {
+        self.0.parameter_count()
+    }
+
+    #[napi(getter, js_name = "node_count")]
+    pub fn node_count(&self) -> u32 {
+        self.0.node_count()
+    }
+
+    /// Distinct operator types, `domain::Op` for non-default domains, sorted
+    #[napi(getter, js_name = "op_types")]
+    pub fn op_types(&self) -> Vec<String> {
+        self.0.op_types().to_vec()
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Describe an ONNX model: opset, inputs/outputs (names, shapes, dtypes) and parameter count
+#[napi(js_name = "inspect_model")]
+pub fn inspect_model(env: Env, model_bytes: Uint8Array) -> Result<ModelInfo> {
+    zkml_verifier_core::onnx::inspect::inspect_model(&model_bytes)
+        .map(ModelInfo)
+        .map_err(|e| error(&env, e, INVALID_MODEL))
+}
+
+/// Inclusion proof for one layer of a Merkle-committed ONNX model
+#[napi]
+#[derive(Clone)]
+pub struct LayerProof(zkml_verifier_core::onnx::merkle::LayerProof);
+
+#[napi]
+impl LayerProof {
+    /// Layer label, e.g. `node/3` or `initializer/classifier.weight`
+    #[napi(getter, js_name = "layer")]
+    pub fn layer(&self) -> String {
+        self.0.layer().to_string()
+    }
+
+    #[napi(getter, js_name = "index")]
+    pub fn index(&self) -> u64 {
+        self.0.index()
+    }
+
+    #[napi(getter, js_name = "leaf_count")]
+    pub fn leaf_count(&self) -> u64 {
+        self.0.leaf_count()
+    }
+
+    #[napi(getter, js_name = "algorithm")]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm().into()
+    }
+
+    /// Canonical protobuf encoding of the layer (`NodeProto` or `TensorProto`)
+    #[napi(getter, js_name = "data")]
+    pub fn data(&self) -> Uint8Array {
+        self.0.data().into()
+    }
+
+    /// Hash of the layer's canonical protobuf, to compare against a published layer hash
+    #[napi(getter, js_name = "layer_hash")]
+    pub fn layer_hash(&self) -> String {
+        self.0.layer_hash()
+    }
+
+    /// Check the proof against a Merkle root (`onnx_merkle_root`)
+    #[napi(js_name = "verify")]
+    pub fn verify(&self, root: String) -> bool {
+        self.0.verifies(&root)
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<LayerProof> {
+        zkml_verifier_core::onnx::merkle::LayerProof::from_json(&json)
+            .map(LayerProof)
+            .map_err(|_| error(&env, Kind::MalformedProof, "Invalid layer proof JSON"))
+    }
+}
+
+/// Merkle root over an ONNX model's header, nodes and initializers
+///
+/// Use it as `model_hash` to be able to prove individual layers later
+/// with `prove_onnx_layer`, without shipping the whole model.
+#[napi(js_name = "onnx_merkle_root")]
+pub fn onnx_merkle_root(env: Env, model_bytes: Uint8Array, algorithm: Option<HashAlgorithm>) -> Result<String> {
+    merkle_root(&model_bytes, algorithm.map(Into::into).unwrap_or_default()).map_err(|e| error(&env, e, CANNOT_COMMIT))
+}
+
+/// Labels of the layers committed by `onnx_merkle_root`, in leaf order
+#[napi(js_name = "onnx_merkle_layers")]
+pub fn onnx_merkle_layers(env: Env, model_bytes: Uint8Array) -> Result<Vec<String>> {
+    layer_labels(&model_bytes).map_err(|e| error(&env, e, INVALID_MODEL))
+}
+
+/// Build an inclusion proof for one layer (see `onnx_merkle_layers` for labels)
+#[napi(js_name = "prove_onnx_layer")]
+pub fn prove_onnx_layer(
+    env: Env,
+    model_bytes: Uint8Array,
+    layer: String,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<LayerProof> {
+    let alg = algorithm.map(Into::into).unwrap_or_default();
+    let proof = zkml_verifier_core::onnx::merkle::LayerProof::create(&model_bytes, &layer, alg);
+    proof.map(LayerProof).map_err(|e| match e {
+        Kind::InvalidArgument => error(&env, e, "Model has no such layer"),
+        e => error(&env, e, CANNOT_COMMIT),
+    })
+}
+
+/// Commitment to a subset of a model's layers, for `ProofData.partial_model_hash`
+///
+/// Set `partial_layers` on the proof to the same labels.
+#[napi(js_name = "onnx_partial_hash")]
+pub fn onnx_partial_hash(
+    env: Env,
+    model_bytes: Uint8Array,
+    layers: Vec<String>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String> {
+    partial_root(&model_bytes, &layers, algorithm.map(Into::into).unwrap_or_default())
+        .map(|(root, _)| root)
+        .map_err(|e| partial_error(&env, e))
+}
+
+/// Architecture constraints for ONNX models
+#[napi]
+#[derive(Clone, Default)]
+pub struct ModelPolicy(pub(crate) zkml_verifier_core::onnx::policy::ModelPolicy);
+
+#[napi]
+impl ModelPolicy {
+    /// Policy that only rejects custom (non-standard-domain) ops
+    #[napi(constructor)]
+    pub fn new() -> ModelPolicy {
+        ModelPolicy::default()
+    }
+
+    /// Lowest accepted `ai.onnx` opset (0 = no minimum)
+    #[napi(getter, js_name = "min_opset")]
+    pub fn min_opset(&self) -> BigInt {
+        self.0.min_opset().into()
+    }
+
+    #[napi(setter, js_name = "min_opset")]
+    pub fn set_min_opset(&mut self, min_opset: BigInt) {
+        self.0.set_min_opset(min_opset.get_i64().0);
+    }
+
+    /// Highest accepted `ai.onnx` opset (0 = no maximum)
+    #[napi(getter, js_name = "max_opset")]
+    pub fn max_opset(&self) -> BigInt {
+        self.0.max_opset().into()
+    }
+
+    #[napi(setter, js_name = "max_opset")]
+    pub fn set_max_opset(&mut self, max_opset: BigInt) {
+        self.0.set_max_opset(max_opset.get_i64().0);
+    }
+
+    /// Operator types the graph may use (empty = any standard op)
+    #[napi(getter, js_name = "allowed_ops")]
+    pub fn allowed_ops(&self) -> Vec<String> {
+        self.0.allowed_ops().to_vec()
+    }
+
+    #[napi(setter, js_name = "allowed_ops")]
+    pub fn set_allowed_ops(&mut self, allowed_ops: Vec<String>) {
+        self.0.set_allowed_ops(allowed_ops);
+    }
+
+    /// Accept ops from non-standard domains and model-local functions
+    #[napi(getter, js_name = "allow_custom_ops")]
+    pub fn allow_custom_ops(&self) -> bool {
+        self.0.allow_custom_ops()
+    }
+
+    #[napi(setter, js_name = "allow_custom_ops")]
+    pub fn set_allow_custom_ops(&mut self, allow_custom_ops: bool) {
+        self.0.set_allow_custom_ops(allow_custom_ops);
+    }
+
+    /// Largest accepted parameter count (0 = unlimited)
+    #[napi(getter, js_name = "max_parameters")]
+    pub fn max_parameters(&self) -> u64 {
+        self.0.max_parameters()
+    }
+
+    #[napi(setter, js_name = "max_parameters")]
+    pub fn set_max_parameters(&mut self, max_parameters: BigInt) {
+        self.0.set_max_parameters(u64_of(max_parameters));
+    }
+
+    /// Every way the model breaks this policy (empty if it complies)
+    #[napi(js_name = "violations")]
+    pub fn violations(&self, env: Env, model_bytes: Uint8Array) -> Result<Vec<String>> {
+        self.0.violations(&model_bytes).map_err(|e| error(&env, e, INVALID_MODEL))
+    }
+}
+
+/// Error for a failed streamed model hash
+fn stream_error(env: &Env, e: Kind) -> Error {
+    match e {
+        Kind::InvalidModel => error(env, e, "Not a valid (or not a complete) ONNX model"),
+        e => error(env, e, NOT_ENABLED),
+    }
+}
+
+/// Chunked hasher producing `onnx_merkle_root` of a model, without holding its weights in memory
+#[napi]
+pub struct ModelHasher(Option<zkml_verifier_core::onnx::stream::ModelHasher>);
+
+#[napi]
+impl ModelHasher {
+    /// Start hashing a model (SHA3-256 unless `algorithm` is given)
+    #[napi(constructor)]
+    pub fn new(env: Env, algorithm: Option<HashAlgorithm>) -> Result<ModelHasher> {
+        zkml_verifier_core::onnx::stream::ModelHasher::create(algorithm.map(Into::into).unwrap_or_default())
+            .map(|hasher| ModelHasher(Some(hasher)))
+            .map_err(|e| stream_error(&env, e))
+    }
+
+    /// Absorb the next chunk of the model file
+    ///
+    /// Throws `VerificationError.InvalidModel` as soon as the bytes can't be
+    /// an ONNX model; the hasher is unusable afterwards.
+    #[napi(js_name = "update")]
+    pub fn update(&mut self, env: Env, chunk: Uint8Array) -> Result<()> {
+        let hasher = self.0.as_mut().ok_or_else(|| finalized(&env))?;
+        hasher.feed(&chunk).map_err(|e| stream_error(&env, e))
+    }
+
+    #[napi(getter, js_name = "algorithm")]
+    pub fn algorithm(&self, env: Env) -> Result<HashAlgorithm> {
+        self.0.as_ref().map(|hasher| hasher.algorithm().into()).ok_or_else(|| finalized(&env))
+    }
+
+    /// Total bytes absorbed so far
+    #[napi(getter, js_name = "bytes_processed")]
+    pub fn bytes_processed(&self, env: Env) -> Result<u64> {
+        self.0.as_ref().map(|hasher| hasher.bytes_processed()).ok_or_else(|| finalized(&env))
+    }
+
+    /// Finish and return the model's `onnx_merkle_root`
+    ///
+    /// Throws `VerificationError.InvalidModel` if the model is truncated.
+    #[napi(js_name = "finalize")]
+    pub fn finalize(&mut self, env: Env) -> Result<String> {
+        let hasher = self.0.take().ok_or_else(|| finalized(&env))?;
+        hasher.finish().map_err(|e| stream_error(&env, e))
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Check that a layer belongs to this verifier's (Merkle-root) model hash
+    #[napi(js_name = "verify_layer")]
+    pub fn verify_layer(&self, proof: &LayerProof) -> bool {
+        proof.0.verifies(self.read().model_hash())
+    }
+
+    /// Layers this verifier is bound to (`undefined` when bound to the whole model)
+    #[napi(getter, js_name = "partial_layers")]
+    pub fn partial_layers(&self) -> Optional<Vec<String>> {
+        self.read().partial_layers().map(<[String]>::to_vec).into()
+    }
+}
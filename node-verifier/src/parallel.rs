@@ -0,0 +1,19 @@
+// Parallel helpers
+// Work spread across scoped threads, one chunk of the items per core
+
+/// `items.iter().map(f)` across all cores; results keep input order
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = items.len().div_ceil(threads).max(1);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let workers: Vec<_> =
+            items.chunks(chunk).map(|items| scope.spawn(move || items.iter().map(f).collect::<Vec<_>>())).collect();
+        workers.into_iter().flat_map(|worker| worker.join().expect("verifier thread panicked")).collect()
+    })
+}
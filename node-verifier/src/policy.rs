@@ -0,0 +1,191 @@
+// Verifier policy
+// Allowed models, freshness, trusted signers, hash algorithms and required checks as one versionable document
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsUnknown};
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::object::to_value;
+use crate::registry::ModelEntry;
+use crate::{HashAlgorithm, VerifierConfig, WasmVerifier};
+use crate::{f32s, list, F32s, I32s};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+const NOT_ENABLED: &str = "Hash algorithm is not enabled in this build";
+
+/// Policy for a `WasmVerifier`, kept in config rather than code
+///
+/// `VerifierConfig` fields sit at the top level. Omitted fields keep their
+/// defaults; unknown ones are rejected, so a typo can't loosen the policy.
+#[napi]
+#[derive(Clone, Default)]
+pub struct VerifierPolicy(pub(crate) zkml_verifier_core::VerifierPolicy);
+
+#[napi]
+impl VerifierPolicy {
+    /// Default policy: `VerifierConfig` defaults, no models, keys or restrictions
+    #[napi(constructor)]
+    pub fn new() -> VerifierPolicy {
+        VerifierPolicy::default()
+    }
+
+    /// Parse a policy document
+    ///
+    /// Throws `VerificationError.InvalidArgument` naming the offending field.
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<VerifierPolicy> {
+        zkml_verifier_core::VerifierPolicy::try_from_json(&json)
+            .map(VerifierPolicy)
+            .map_err(|reason| error(&env, Kind::InvalidArgument, &reason))
+    }
+
+    /// Build a policy from its plain-object form
+    #[napi(factory, js_name = "from_object")]
+    pub fn from_object(env: Env, value: JsUnknown) -> Result<VerifierPolicy> {
+        to_value(&value)
+            .and_then(zkml_verifier_core::VerifierPolicy::try_from_value)
+            .map(VerifierPolicy)
+            .map_err(|reason| error(&env, Kind::InvalidArgument, &reason))
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(getter, js_name = "config")]
+    pub fn config(&self) -> VerifierConfig {
+        VerifierConfig(self.0.config())
+    }
+
+    #[napi(getter, js_name = "models")]
+    pub fn models(&self) -> Vec<ModelEntry> {
+        self.0.models().iter().cloned().map(ModelEntry).collect()
+    }
+
+    #[napi(getter, js_name = "trusted_signers")]
+    pub fn trusted_signers(&self) -> Vec<String> {
+        self.0.trusted_signers().to_vec()
+    }
+
+    #[napi(getter, js_name = "trusted_attestors")]
+    pub fn trusted_attestors(&self) -> Vec<String> {
+        self.0.trusted_attestors().to_vec()
+    }
+}
+
+/// Quantization scheme of a model: per-tensor or per-channel scales and zero-points
+#[napi]
+#[derive(Clone, Default)]
+pub struct QuantizationConfig(zkml_verifier_core::QuantizationConfig);
+
+#[napi]
+impl QuantizationConfig {
+    #[napi(constructor)]
+    pub fn new(scheme: String, bit_width: u32, scales: F32s, zero_points: I32s) -> QuantizationConfig {
+        let config = zkml_verifier_core::QuantizationConfig::new(scheme, bit_width, f32s(scales), list(zero_points));
+        QuantizationConfig(config)
+    }
+
+    /// Scheme name, e.g. `"per-tensor-affine"` or `"per-channel-symmetric"`
+    #[napi(getter, js_name = "scheme")]
+    pub fn scheme(&self) -> String {
+        self.0.scheme().to_string()
+    }
+
+    #[napi(getter, js_name = "bit_width")]
+    pub fn bit_width(&self) -> u32 {
+        self.0.bit_width()
+    }
+
+    #[napi(getter, js_name = "scales")]
+    pub fn scales(&self) -> Float32Array {
+        Float32Array::new(self.0.scales().to_vec())
+    }
+
+    #[napi(getter, js_name = "zero_points")]
+    pub fn zero_points(&self) -> Int32Array {
+        Int32Array::new(self.0.zero_points().to_vec())
+    }
+
+    /// Commitment to this config, for `ProofData.quantization_hash` (default SHA3-256)
+    #[napi(js_name = "hash")]
+    pub fn hash(&self, env: Env, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        self.0.hash(algorithm.map(Into::into).unwrap_or_default()).map_err(|e| error(&env, e, NOT_ENABLED))
+    }
+}
+
+/// Preprocessing applied to raw data before it became the model input
+///
+/// Prover and verifier build the same config and compare hashes.
+#[napi]
+#[derive(Clone, Default)]
+pub struct PreprocessingConfig(zkml_verifier_core::PreprocessingConfig);
+
+#[napi]
+impl PreprocessingConfig {
+    #[napi(constructor)]
+    pub fn new() -> PreprocessingConfig {
+        PreprocessingConfig::default()
+    }
+
+    /// Images were resized to `width` x `height` with `interpolation` (e.g. `"bilinear"`)
+    #[napi(js_name = "set_resize")]
+    pub fn set_resize(&mut self, width: u32, height: u32, interpolation: String) {
+        self.0.set_resize(width, height, interpolation);
+    }
+
+    /// Values were multiplied by `scale`, then normalized per channel as `(x - mean) / std`
+    #[napi(js_name = "set_normalization")]
+    pub fn set_normalization(&mut self, scale: f64, mean: F32s, std: F32s) {
+        self.0.set_normalization(scale as f32, f32s(mean), f32s(std));
+    }
+
+    /// Text was tokenized by `name` with the vocabulary hashing to `vocab_hash`
+    #[napi(js_name = "set_tokenizer")]
+    pub fn set_tokenizer(&mut self, name: String, vocab_hash: String, max_length: u32, lowercase: bool) {
+        self.0.set_tokenizer(name, vocab_hash, max_length, lowercase);
+    }
+
+    /// Commitment to this config, for `ProofData.preprocessing_hash` (default SHA3-256)
+    #[napi(js_name = "hash")]
+    pub fn hash(&self, env: Env, algorithm: Option<HashAlgorithm>) -> Result<String> {
+        self.0.hash(algorithm.map(Into::into).unwrap_or_default()).map_err(|e| error(&env, e, NOT_ENABLED))
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<PreprocessingConfig> {
+        zkml_verifier_core::PreprocessingConfig::from_json(&json)
+            .map(PreprocessingConfig)
+            .map_err(|_| error(&env, Kind::InvalidArgument, "Invalid preprocessing config JSON"))
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Apply `policy`: its config replaces the current one, its trusted keys are
+    /// added, and its models (if any) replace the expected model hash
+    #[napi(js_name = "set_policy")]
+    pub fn set_policy(&mut self, policy: &VerifierPolicy) {
+        self.write().set_policy(&policy.0);
+    }
+
+    /// Require proofs to commit to this quantization config via `quantization_hash`
+    #[napi(js_name = "set_quantization")]
+    pub fn set_quantization(&mut self, quantization: &QuantizationConfig) {
+        self.write().set_quantization(&quantization.0);
+    }
+
+    /// Require proofs to commit to this preprocessing pipeline via `preprocessing_hash`
+    #[napi(js_name = "set_preprocessing")]
+    pub fn set_preprocessing(&mut self, preprocessing: &PreprocessingConfig) {
+        self.write().set_preprocessing(&preprocessing.0);
+    }
+}
@@ -0,0 +1,393 @@
+// Proof data
+// The WASM build's `ProofData` class: getters and setters, canonical forms and the binary encodings
+
+use std::collections::BTreeMap;
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsUnknown};
+use napi_derive::napi;
+
+use crate::error::{error, failure};
+use crate::hash::HashAlgorithm;
+use crate::object::to_value;
+use crate::tensor::TensorSpec;
+use crate::{u64_of, Optional};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// A proof claim: which model ran on which input to give which output, plus
+/// optional signatures, attestations and policy commitments
+#[napi]
+#[derive(Clone)]
+pub struct ProofData(pub(crate) zkml_verifier_core::ProofData);
+
+#[napi]
+impl ProofData {
+    #[napi(constructor)]
+    pub fn new(
+        model_hash: String,
+        proof_hash: String,
+        input_hash: String,
+        output_hash: String,
+        timestamp: BigInt,
+        verified: bool,
+    ) -> ProofData {
+        let timestamp = u64_of(timestamp);
+        ProofData(zkml_verifier_core::ProofData::new(
+            model_hash,
+            proof_hash,
+            input_hash,
+            output_hash,
+            timestamp,
+            verified,
+        ))
+    }
+
+    #[napi(getter, js_name = "model_hash")]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash.clone()
+    }
+
+    #[napi(getter, js_name = "proof_hash")]
+    pub fn proof_hash(&self) -> String {
+        self.0.proof_hash.clone()
+    }
+
+    /// Input commitment (empty for proofs using named `inputs`)
+    #[napi(getter, js_name = "input_hash")]
+    pub fn input_hash(&self) -> String {
+        self.0.input_hash.clone()
+    }
+
+    /// Output commitment (empty for proofs using named `outputs`)
+    #[napi(getter, js_name = "output_hash")]
+    pub fn output_hash(&self) -> String {
+        self.0.output_hash.clone()
+    }
+
+    /// Generation time, Unix milliseconds
+    #[napi(getter, js_name = "timestamp")]
+    pub fn timestamp(&self) -> u64 {
+        self.0.timestamp
+    }
+
+    #[napi(getter, js_name = "verified")]
+    pub fn verified(&self) -> bool {
+        self.0.verified
+    }
+
+    /// Named input commitments, by input name
+    #[napi(getter, js_name = "inputs")]
+    pub fn inputs(&self) -> BTreeMap<String, String> {
+        self.0.inputs.clone()
+    }
+
+    /// Named output commitments, by output name
+    #[napi(getter, js_name = "outputs")]
+    pub fn outputs(&self) -> BTreeMap<String, String> {
+        self.0.outputs.clone()
+    }
+
+    #[napi(getter, js_name = "hash_alg")]
+    pub fn hash_alg(&self) -> Optional<String> {
+        self.0.hash_alg.clone().into()
+    }
+
+    #[napi(setter, js_name = "hash_alg")]
+    pub fn set_hash_alg(&mut self, hash_alg: Option<String>) {
+        self.0.hash_alg = hash_alg;
+    }
+
+    #[napi(getter, js_name = "nonce")]
+    pub fn nonce(&self) -> Optional<String> {
+        self.0.nonce.clone().into()
+    }
+
+    #[napi(setter, js_name = "nonce")]
+    pub fn set_nonce(&mut self, nonce: Option<String>) {
+        self.0.nonce = nonce;
+    }
+
+    /// Declare the commitment hash algorithm (`undefined` = verifier default)
+    #[napi(js_name = "set_hash_algorithm")]
+    pub fn set_hash_algorithm(&mut self, algorithm: Option<HashAlgorithm>) {
+        self.0.set_hash_algorithm(algorithm.map(Into::into));
+    }
+
+    #[napi(getter, js_name = "signature_alg")]
+    pub fn signature_alg(&self) -> Optional<String> {
+        self.0.signature_alg.clone().into()
+    }
+
+    #[napi(setter, js_name = "signature_alg")]
+    pub fn set_signature_alg(&mut self, signature_alg: Option<String>) {
+        self.0.signature_alg = signature_alg;
+    }
+
+    #[napi(getter, js_name = "signature")]
+    pub fn signature(&self) -> Optional<String> {
+        self.0.signature.clone().into()
+    }
+
+    #[napi(setter, js_name = "signature")]
+    pub fn set_signature(&mut self, signature: Option<String>) {
+        self.0.signature = signature;
+    }
+
+    #[napi(getter, js_name = "signer_pubkey")]
+    pub fn signer_pubkey(&self) -> Optional<String> {
+        self.0.signer_pubkey.clone().into()
+    }
+
+    #[napi(setter, js_name = "signer_pubkey")]
+    pub fn set_signer_pubkey(&mut self, signer_pubkey: Option<String>) {
+        self.0.signer_pubkey = signer_pubkey;
+    }
+
+    #[napi(getter, js_name = "kid")]
+    pub fn kid(&self) -> Optional<String> {
+        self.0.kid.clone().into()
+    }
+
+    #[napi(setter, js_name = "kid")]
+    pub fn set_kid(&mut self, kid: Option<String>) {
+        self.0.kid = kid;
+    }
+
+    #[napi(getter, js_name = "prover_did")]
+    pub fn prover_did(&self) -> Optional<String> {
+        self.0.prover_did.clone().into()
+    }
+
+    #[napi(setter, js_name = "prover_did")]
+    pub fn set_prover_did(&mut self, prover_did: Option<String>) {
+        self.0.prover_did = prover_did;
+    }
+
+    #[napi(getter, js_name = "attestor_pubkeys")]
+    pub fn attestor_pubkeys(&self) -> Vec<String> {
+        self.0.attestor_pubkeys.clone()
+    }
+
+    #[napi(setter, js_name = "attestor_pubkeys")]
+    pub fn set_attestor_pubkeys(&mut self, attestor_pubkeys: Vec<String>) {
+        self.0.attestor_pubkeys = attestor_pubkeys;
+    }
+
+    #[napi(getter, js_name = "aggregate_signature")]
+    pub fn aggregate_signature(&self) -> Optional<String> {
+        self.0.aggregate_signature.clone().into()
+    }
+
+    #[napi(setter, js_name = "aggregate_signature")]
+    pub fn set_aggregate_signature(&mut self, aggregate_signature: Option<String>) {
+        self.0.aggregate_signature = aggregate_signature;
+    }
+
+    #[napi(getter, js_name = "input_spec")]
+    pub fn input_spec(&self) -> Optional<TensorSpec> {
+        self.0.input_spec.clone().map(TensorSpec).into()
+    }
+
+    #[napi(setter, js_name = "input_spec")]
+    pub fn set_input_spec(&mut self, input_spec: Option<ClassInstance<TensorSpec>>) {
+        self.0.input_spec = input_spec.map(|spec| spec.0.clone());
+    }
+
+    #[napi(getter, js_name = "output_spec")]
+    pub fn output_spec(&self) -> Optional<TensorSpec> {
+        self.0.output_spec.clone().map(TensorSpec).into()
+    }
+
+    #[napi(setter, js_name = "output_spec")]
+    pub fn set_output_spec(&mut self, output_spec: Option<ClassInstance<TensorSpec>>) {
+        self.0.output_spec = output_spec.map(|spec| spec.0.clone());
+    }
+
+    #[napi(getter, js_name = "model_version")]
+    pub fn model_version(&self) -> Optional<String> {
+        self.0.model_version.clone().into()
+    }
+
+    #[napi(setter, js_name = "model_version")]
+    pub fn set_model_version(&mut self, model_version: Option<String>) {
+        self.0.model_version = model_version;
+    }
+
+    #[napi(getter, js_name = "architecture_hash")]
+    pub fn architecture_hash(&self) -> Optional<String> {
+        self.0.architecture_hash.clone().into()
+    }
+
+    #[napi(setter, js_name = "architecture_hash")]
+    pub fn set_architecture_hash(&mut self, architecture_hash: Option<String>) {
+        self.0.architecture_hash = architecture_hash;
+    }
+
+    #[napi(getter, js_name = "weights_hash")]
+    pub fn weights_hash(&self) -> Optional<String> {
+        self.0.weights_hash.clone().into()
+    }
+
+    #[napi(setter, js_name = "weights_hash")]
+    pub fn set_weights_hash(&mut self, weights_hash: Option<String>) {
+        self.0.weights_hash = weights_hash;
+    }
+
+    #[napi(getter, js_name = "metadata_hash")]
+    pub fn metadata_hash(&self) -> Optional<String> {
+        self.0.metadata_hash.clone().into()
+    }
+
+    #[napi(setter, js_name = "metadata_hash")]
+    pub fn set_metadata_hash(&mut self, metadata_hash: Option<String>) {
+        self.0.metadata_hash = metadata_hash;
+    }
+
+    #[napi(getter, js_name = "partial_model_hash")]
+    pub fn partial_model_hash(&self) -> Optional<String> {
+        self.0.partial_model_hash.clone().into()
+    }
+
+    #[napi(setter, js_name = "partial_model_hash")]
+    pub fn set_partial_model_hash(&mut self, partial_model_hash: Option<String>) {
+        self.0.partial_model_hash = partial_model_hash;
+    }
+
+    #[napi(getter, js_name = "partial_layers")]
+    pub fn partial_layers(&self) -> Vec<String> {
+        self.0.partial_layers.clone()
+    }
+
+    #[napi(setter, js_name = "partial_layers")]
+    pub fn set_partial_layers(&mut self, partial_layers: Vec<String>) {
+        self.0.partial_layers = partial_layers;
+    }
+
+    #[napi(getter, js_name = "quantization_hash")]
+    pub fn quantization_hash(&self) -> Optional<String> {
+        self.0.quantization_hash.clone().into()
+    }
+
+    #[napi(setter, js_name = "quantization_hash")]
+    pub fn set_quantization_hash(&mut self, quantization_hash: Option<String>) {
+        self.0.quantization_hash = quantization_hash;
+    }
+
+    #[napi(getter, js_name = "preprocessing_hash")]
+    pub fn preprocessing_hash(&self) -> Optional<String> {
+        self.0.preprocessing_hash.clone().into()
+    }
+
+    #[napi(setter, js_name = "preprocessing_hash")]
+    pub fn set_preprocessing_hash(&mut self, preprocessing_hash: Option<String>) {
+        self.0.preprocessing_hash = preprocessing_hash;
+    }
+
+    #[napi(getter, js_name = "cert_chain")]
+    pub fn cert_chain(&self) -> Vec<String> {
+        self.0.cert_chain.clone()
+    }
+
+    #[napi(setter, js_name = "cert_chain")]
+    pub fn set_cert_chain(&mut self, cert_chain: Vec<String>) {
+        self.0.cert_chain = cert_chain;
+    }
+
+    /// Canonical bytes the prover (and every attestor) signs
+    #[napi(js_name = "signing_bytes")]
+    pub fn signing_bytes(&self) -> Uint8Array {
+        self.0.signing_bytes().into()
+    }
+
+    /// Content hash identifying the proof, e.g. as a storage key
+    #[napi(js_name = "proof_id")]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id()
+    }
+
+    /// Ethereum address that signed this proof (`signature_alg = "secp256k1"`)
+    #[napi(js_name = "recover_signer_address")]
+    pub fn recover_signer_address(&self, env: Env) -> Result<String> {
+        self.0.recover_signer_address().map_err(failure(&env))
+    }
+
+    /// Serialize proof to JSON string
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    /// Parse a proof from JSON (what `to_json` or `JSON.stringify(proof)` produce)
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, proof_json: String) -> Result<ProofData> {
+        ProofData::parse_json(&env, &proof_json)
+    }
+
+    /// Plain-object form of the proof
+    #[napi(js_name = "to_object")]
+    pub fn to_object(&self) -> serde_json::Value {
+        serde_json::to_value(&self.0).unwrap_or_default()
+    }
+
+    /// `to_object`, under the name `JSON.stringify` looks for
+    #[napi(js_name = "toJSON")]
+    pub fn to_json_object(&self) -> serde_json::Value {
+        self.to_object()
+    }
+
+    /// Build a proof from its plain-object form
+    #[napi(factory, js_name = "from_object")]
+    pub fn from_object(env: Env, value: JsUnknown) -> Result<ProofData> {
+        let proof = to_value(&value).and_then(|value| serde_json::from_value(value).map_err(|e| e.to_string()));
+        proof.map(ProofData).map_err(|e| error(&env, Kind::MalformedProof, &format!("Invalid proof object: {}", e)))
+    }
+
+    /// Serialize to the compact binary format (`ZKPF`, version 1)
+    #[napi(js_name = "encode_binary")]
+    pub fn encode_binary(&self) -> Uint8Array {
+        self.0.to_binary().into()
+    }
+
+    /// Deserialize from the compact binary format
+    #[napi(factory, js_name = "decode_binary")]
+    pub fn decode_binary(env: Env, bytes: Uint8Array) -> Result<ProofData> {
+        let proof = zkml_verifier_core::ProofData::from_binary(&bytes);
+        proof.map(ProofData).map_err(|e| error(&env, e, "Invalid binary proof"))
+    }
+
+    /// Deserialize from CBOR
+    #[napi(factory, js_name = "from_cbor")]
+    pub fn from_cbor(env: Env, bytes: Uint8Array) -> Result<ProofData> {
+        let proof = zkml_verifier_core::ProofData::decode_cbor(&bytes);
+        proof.map(ProofData).map_err(|e| error(&env, e, "Invalid ProofData CBOR"))
+    }
+
+    /// Serialize to CBOR (roughly half the size of the JSON form)
+    #[napi(js_name = "to_cbor")]
+    pub fn to_cbor(&self) -> Uint8Array {
+        self.0.encode_cbor().into()
+    }
+
+    /// Deserialize from a `zkml.verifier.v1.Proof` protobuf message
+    #[napi(factory, js_name = "from_protobuf")]
+    pub fn from_protobuf(env: Env, bytes: Uint8Array) -> Result<ProofData> {
+        let proof = zkml_verifier_core::ProofData::decode_protobuf(&bytes);
+        proof.map(ProofData).map_err(|e| error(&env, e, "Invalid Proof protobuf"))
+    }
+
+    /// Serialize to a `zkml.verifier.v1.Proof` protobuf message
+    #[napi(js_name = "to_protobuf")]
+    pub fn to_protobuf(&self) -> Uint8Array {
+        self.0.encode_protobuf().into()
+    }
+}
+
+impl ProofData {
+    /// Parse proof JSON, mapping failures to `VerificationError::MalformedProof`
+    pub(crate) fn parse_json(env: &Env, proof_json: &str) -> Result<ProofData> {
+        zkml_verifier_core::ProofData::from_json(proof_json)
+            .map(ProofData)
+            .map_err(|reason| error(env, Kind::MalformedProof, &reason))
+    }
+}
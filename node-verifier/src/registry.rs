@@ -0,0 +1,81 @@
+// Multi-model allowlist
+// One verifier for several approved models, each with a label and version
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::error;
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// An approved model
+#[napi]
+#[derive(Clone)]
+pub struct ModelEntry(pub(crate) zkml_verifier_core::ModelEntry);
+
+/// Set of approved model hashes
+#[napi]
+#[derive(Clone, Default)]
+pub struct ModelRegistry(pub(crate) zkml_verifier_core::ModelRegistry);
+
+#[napi]
+impl ModelEntry {
+    #[napi(getter, js_name = "model_hash")]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash().to_string()
+    }
+
+    /// Human-readable name, e.g. `"sentiment-classifier"`
+    #[napi(getter, js_name = "label")]
+    pub fn label(&self) -> String {
+        self.0.label().to_string()
+    }
+
+    #[napi(getter, js_name = "version")]
+    pub fn version(&self) -> String {
+        self.0.version().to_string()
+    }
+}
+
+#[napi]
+impl ModelRegistry {
+    #[napi(constructor)]
+    pub fn new() -> ModelRegistry {
+        ModelRegistry::default()
+    }
+
+    /// Approve a model hash under `label` and `version`
+    #[napi(js_name = "add")]
+    pub fn add(&mut self, model_hash: String, label: String, version: String) {
+        self.0.add(model_hash, label, version);
+    }
+
+    #[napi(getter, js_name = "entries")]
+    pub fn entries(&self) -> Vec<ModelEntry> {
+        self.0.entries().iter().cloned().map(ModelEntry).collect()
+    }
+
+    #[napi(getter, js_name = "len")]
+    pub fn len(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    #[napi(js_name = "is_empty")]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Load entries from `[{"model_hash", "label", "version"}, ...]`
+    #[napi(factory, js_name = "from_json")]
+    pub fn from_json(env: Env, json: String) -> Result<ModelRegistry> {
+        zkml_verifier_core::ModelRegistry::from_json(&json)
+            .map(ModelRegistry)
+            .map_err(|_| error(&env, Kind::InvalidArgument, "Invalid model registry JSON"))
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
@@ -0,0 +1,186 @@
+// Verification report
+// The WASM build's `Check`, `CheckResult` and `VerificationReport`, with the same getters
+
+use napi_derive::napi;
+
+use crate::error::VerificationError;
+use crate::onnx::ModelDiagnostic;
+use crate::registry::ModelEntry;
+use crate::{mirror, Optional};
+
+mirror! {
+    /// Individual checks performed during verification
+    Check => zkml_verifier_core::Check {
+        ModelBinding,
+        InputHash,
+        OutputHash,
+        InputSpec,
+        OutputSpec,
+        Timestamp,
+        ProofValidity,
+        ProofBinding,
+        Nonce,
+        Signature,
+        AggregateSignature,
+        CertificateChain,
+        Quantization,
+        Preprocessing,
+        Recomputation,
+        Revocation,
+        ModelVersion,
+        Composite,
+        Inclusion,
+        OutputTolerance,
+        Classification,
+        Anchor,
+        RegistryModel,
+        RegistryProver,
+    }
+}
+
+mirror! {
+    /// How much a passing report vouches for the claimed output
+    Confidence => zkml_verifier_core::Confidence {
+        Unverified,
+        Proof,
+        Recomputed,
+    }
+}
+
+/// Result of a single check
+#[napi]
+#[derive(Clone)]
+pub struct CheckResult(pub(crate) zkml_verifier_core::CheckResult);
+
+/// Outcome of a full verification run
+#[napi]
+#[derive(Clone, Default)]
+pub struct VerificationReport(pub(crate) zkml_verifier_core::VerificationReport);
+
+impl From<zkml_verifier_core::VerificationReport> for VerificationReport {
+    fn from(report: zkml_verifier_core::VerificationReport) -> Self {
+        VerificationReport(report)
+    }
+}
+
+#[napi]
+impl CheckResult {
+    #[napi(getter, js_name = "check")]
+    pub fn check(&self) -> Check {
+        self.0.check().into()
+    }
+
+    #[napi(getter, js_name = "passed")]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    /// Why the check failed (`undefined` when it passed)
+    #[napi(getter, js_name = "reason")]
+    pub fn reason(&self) -> Optional<String> {
+        self.0.reason().map(str::to_string).into()
+    }
+
+    /// Error code of the failure (`undefined` when it passed)
+    #[napi(getter, js_name = "error")]
+    pub fn error(&self) -> Optional<VerificationError> {
+        self.0.error().map(Into::into).into()
+    }
+
+    #[napi(getter, js_name = "duration_ms")]
+    pub fn duration_ms(&self) -> f64 {
+        self.0.duration_ms()
+    }
+}
+
+#[napi]
+impl VerificationReport {
+    /// True when every check passed
+    #[napi(getter, js_name = "passed")]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    #[napi(getter, js_name = "checks")]
+    pub fn checks(&self) -> Vec<CheckResult> {
+        self.0.checks().iter().cloned().map(CheckResult).collect()
+    }
+
+    /// Reasons of all failed checks, in check order
+    #[napi(getter, js_name = "failure_reasons")]
+    pub fn failure_reasons(&self) -> Vec<String> {
+        self.0.failure_reasons()
+    }
+
+    /// Error code of the first failed check (`undefined` when all passed)
+    #[napi(getter, js_name = "error")]
+    pub fn error(&self) -> Optional<VerificationError> {
+        self.0.error().map(Into::into).into()
+    }
+
+    /// Registry entry the proof's model matched (registry verifiers only)
+    #[napi(getter, js_name = "matched_model")]
+    pub fn matched_model(&self) -> Optional<ModelEntry> {
+        self.0.matched_model().cloned().map(ModelEntry).into()
+    }
+
+    /// Why model binding failed, for verifiers built with `from_model_bytes`
+    #[napi(getter, js_name = "model_diagnostic")]
+    pub fn model_diagnostic(&self) -> Optional<ModelDiagnostic> {
+        self.0.model_diagnostic().cloned().map(ModelDiagnostic).into()
+    }
+
+    /// Block the proof was anchored at (always `undefined` here: `verify_anchored` is WASM-only)
+    #[napi(getter, js_name = "anchored_block")]
+    pub fn anchored_block(&self) -> Optional<u64> {
+        self.0.anchored_block().into()
+    }
+
+    /// Confidence tier of the result
+    #[napi(getter, js_name = "confidence")]
+    pub fn confidence(&self) -> Confidence {
+        self.0.confidence().into()
+    }
+
+    #[napi(getter, js_name = "total_ms")]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    #[napi(getter, js_name = "model_binding")]
+    pub fn model_binding(&self) -> bool {
+        self.0.check_passed(zkml_verifier_core::Check::ModelBinding)
+    }
+
+    #[napi(getter, js_name = "input_hash")]
+    pub fn input_hash(&self) -> bool {
+        self.0.check_passed(zkml_verifier_core::Check::InputHash)
+    }
+
+    #[napi(getter, js_name = "output_hash")]
+    pub fn output_hash(&self) -> bool {
+        self.0.check_passed(zkml_verifier_core::Check::OutputHash)
+    }
+
+    #[napi(getter, js_name = "timestamp")]
+    pub fn timestamp(&self) -> bool {
+        self.0.check_passed(zkml_verifier_core::Check::Timestamp)
+    }
+
+    #[napi(getter, js_name = "proof_validity")]
+    pub fn proof_validity(&self) -> bool {
+        self.0.check_passed(zkml_verifier_core::Check::ProofValidity)
+    }
+
+    /// Serialize report to JSON string
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    /// Plain-object form of the report
+    #[napi(js_name = "to_object")]
+    pub fn to_object(&self) -> serde_json::Value {
+        serde_json::to_value(&self.0).unwrap_or_default()
+    }
+}
@@ -0,0 +1,151 @@
+// Verifier sessions
+// Running pass/fail tallies and seen proof IDs across many verifications, for UI state
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::{Optional, ProofData, VerificationReport, WasmVerifier};
+
+/// Tallies of a `VerifierSession` so far
+#[napi]
+#[derive(Clone, Default)]
+pub struct SessionSummary(zkml_verifier_core::session::SessionSummary);
+
+#[napi]
+impl SessionSummary {
+    /// Distinct proofs verified
+    #[napi(getter, js_name = "total")]
+    pub fn total(&self) -> u32 {
+        self.0.total()
+    }
+
+    #[napi(getter, js_name = "passed")]
+    pub fn passed(&self) -> u32 {
+        self.0.passed()
+    }
+
+    #[napi(getter, js_name = "failed")]
+    pub fn failed(&self) -> u32 {
+        self.0.failed()
+    }
+
+    /// Proofs verified again after their first time (not in `total`)
+    #[napi(getter, js_name = "duplicates")]
+    pub fn duplicates(&self) -> u32 {
+        self.0.duplicates()
+    }
+
+    /// Failed proofs counted by error kind, e.g. `{ Expired: 2, OutputHashMismatch: 1 }`
+    #[napi(getter, js_name = "failures_by_error")]
+    pub fn failures_by_error(&self) -> BTreeMap<String, u32> {
+        self.0.failures_by_error().clone()
+    }
+
+    /// When the session started (ms since the Unix epoch, verifier clock)
+    #[napi(getter, js_name = "started_at")]
+    pub fn started_at(&self) -> u64 {
+        self.0.started_at()
+    }
+
+    /// When the latest proof was recorded (`undefined` before the first)
+    #[napi(getter, js_name = "last_verified_at")]
+    pub fn last_verified_at(&self) -> Optional<u64> {
+        self.0.last_verified_at().into()
+    }
+
+    /// Time spent in checks, over all recorded proofs
+    #[napi(getter, js_name = "total_ms")]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    /// Serialize summary to JSON string
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// A verifier that keeps count of what it has verified
+///
+/// Proofs are told apart by `ProofData.proof_id`. A proof seen before is
+/// verified again (its report may differ, e.g. once it expires) but only
+/// counted under `duplicates`, so tallies count distinct proofs.
+#[napi]
+pub struct VerifierSession {
+    verifier: WasmVerifier,
+    session: zkml_verifier_core::session::Session,
+}
+
+#[napi]
+impl VerifierSession {
+    /// Start a session with `verifier`
+    ///
+    /// Unlike the WASM build, which takes the verifier over, the session
+    /// shares it: `verifier` stays usable and later changes to it apply.
+    #[napi(constructor)]
+    pub fn new(verifier: &WasmVerifier) -> VerifierSession {
+        let verifier = WasmVerifier { verifier: Arc::clone(&verifier.verifier) };
+        let session = zkml_verifier_core::session::Session::new(verifier.read().now_ms());
+        VerifierSession { verifier, session }
+    }
+
+    /// Verify a proof and record the outcome
+    #[napi(js_name = "verify")]
+    pub fn verify(
+        &mut self,
+        proof_data: &ProofData,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> VerificationReport {
+        let report = self.verifier.verify_report(proof_data, input_bytes, output_bytes);
+        self.record(proof_data, &report);
+        report
+    }
+
+    /// `verify` for a proof JSON string
+    ///
+    /// Throws `VerificationError.MalformedProof` (recording nothing) if the
+    /// JSON isn't a proof.
+    #[napi(js_name = "verify_json")]
+    pub fn verify_json(
+        &mut self,
+        env: Env,
+        proof_json: String,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<VerificationReport> {
+        let proof_data = ProofData::parse_json(&env, &proof_json)?;
+        Ok(self.verify(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// Record a report produced elsewhere, e.g. by `verify_report_async` on the same verifier
+    #[napi(js_name = "record")]
+    pub fn record(&mut self, proof_data: &ProofData, report: &VerificationReport) {
+        let now = self.verifier.read().now_ms();
+        self.session.record(&proof_data.0, &report.0, now);
+    }
+
+    /// Whether a proof with this `proof_id` was recorded in this session
+    #[napi(js_name = "has_seen")]
+    pub fn has_seen(&self, proof_id: String) -> bool {
+        self.session.has_seen(&proof_id)
+    }
+
+    /// Snapshot of the session's tallies
+    #[napi(js_name = "summary")]
+    pub fn summary(&self) -> SessionSummary {
+        SessionSummary(self.session.summary().clone())
+    }
+
+    /// Forget every recorded proof and start the tallies over
+    #[napi(js_name = "reset")]
+    pub fn reset(&mut self) {
+        let now = self.verifier.read().now_ms();
+        self.session.reset(now);
+    }
+}
@@ -0,0 +1,241 @@
+// Credentials, receipts and transparency logs
+// The WASM build's issuer key, Verifiable Credentials, receipts, Rekor checks and JWS/COSE envelopes
+
+use napi::bindgen_prelude::*;
+use napi::Env;
+use napi_derive::napi;
+
+use crate::error::{error, failure};
+use crate::{Optional, ProofData, VerificationReport, WasmVerifier};
+
+use zkml_verifier_core::VerificationError as Kind;
+
+/// Claims of a receipt from `issue_receipt`
+#[napi]
+#[derive(Clone)]
+pub struct Receipt(zkml_verifier_core::receipt::Receipt);
+
+#[napi]
+impl Receipt {
+    #[napi(getter, js_name = "verifier")]
+    pub fn verifier(&self) -> String {
+        self.0.verifier().to_string()
+    }
+
+    #[napi(getter, js_name = "proof_id")]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id().to_string()
+    }
+
+    #[napi(getter, js_name = "model_hash")]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash().to_string()
+    }
+
+    /// Always `"passed"`: failed verifications get no receipt
+    #[napi(getter, js_name = "result")]
+    pub fn result(&self) -> String {
+        self.0.result().to_string()
+    }
+
+    #[napi(getter, js_name = "verified_at")]
+    pub fn verified_at(&self) -> u64 {
+        self.0.verified_at()
+    }
+
+    #[napi(js_name = "to_json")]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Where a verified entry sits in the log
+#[napi]
+#[derive(Clone)]
+pub struct RekorInclusion(zkml_verifier_core::rekor::RekorInclusion);
+
+#[napi]
+impl RekorInclusion {
+    /// Global index of the entry
+    #[napi(getter, js_name = "log_index")]
+    pub fn log_index(&self) -> u64 {
+        self.0.log_index()
+    }
+
+    /// When the log took the entry in (Unix seconds)
+    #[napi(getter, js_name = "integrated_time")]
+    pub fn integrated_time(&self) -> u64 {
+        self.0.integrated_time()
+    }
+
+    #[napi(getter, js_name = "log_id")]
+    pub fn log_id(&self) -> String {
+        self.0.log_id().to_string()
+    }
+
+    /// Size of the tree the checkpoint signs
+    #[napi(getter, js_name = "tree_size")]
+    pub fn tree_size(&self) -> u64 {
+        self.0.tree_size()
+    }
+
+    /// Root of that tree (hex)
+    #[napi(getter, js_name = "root_hash")]
+    pub fn root_hash(&self) -> String {
+        self.0.root_hash().to_string()
+    }
+
+    /// Ed25519 key (hex) the entry's signature over its data verifies under
+    #[napi(getter, js_name = "signer_pubkey")]
+    pub fn signer_pubkey(&self) -> String {
+        self.0.signer_pubkey().to_string()
+    }
+}
+
+/// Maps key ids (`kid`) to prover keys, so provers can rotate keys without
+/// redeploying the verifier
+///
+/// Load it with `load_jwks` (the WASM build's `fetcher` callback is
+/// WASM-only). Supported keys: OKP/Ed25519 and EC/secp256k1.
+#[napi]
+#[derive(Default)]
+pub struct KeyResolver(zkml_verifier_core::KeyResolver);
+
+#[napi]
+impl KeyResolver {
+    #[napi(constructor)]
+    pub fn new() -> KeyResolver {
+        KeyResolver::default()
+    }
+
+    /// Add the signing keys of a JWKS document; returns how many were loaded
+    #[napi(js_name = "load_jwks")]
+    pub fn load_jwks(&self, env: Env, jwks: String) -> Result<u32> {
+        self.0.insert_jwks(&jwks).map(|loaded| loaded as u32).map_err(failure(&env))
+    }
+
+    /// Number of keys currently known
+    #[napi(getter, js_name = "len")]
+    pub fn len(&self) -> u32 {
+        self.0.len() as u32
+    }
+
+    #[napi(js_name = "is_empty")]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Resolve proof `kid`s through `resolver` before checking signatures
+    ///
+    /// Keys resolved this way are trusted without `add_trusted_signer`.
+    /// The resolver's keys are moved into the verifier, leaving it empty.
+    #[napi(js_name = "set_key_resolver")]
+    pub fn set_key_resolver(&mut self, resolver: &mut KeyResolver) {
+        self.write().set_key_resolver(std::mem::take(&mut resolver.0));
+    }
+
+    /// Set the Ed25519 secret key (32 bytes, hex) credentials and receipts are issued with
+    #[napi(js_name = "set_issuer_key")]
+    pub fn set_issuer_key(&mut self, env: Env, secret_key: String) -> Result<()> {
+        self.write()
+            .set_issuer_key(&secret_key)
+            .map_err(|_| error(&env, Kind::InvalidArgument, "Issuer key must be 32 bytes of hex"))
+    }
+
+    /// `did:key` of the credential issuer (`undefined` until `set_issuer_key`)
+    #[napi(getter, js_name = "issuer_did")]
+    pub fn issuer_did(&self) -> Optional<String> {
+        self.read().issuer_did().into()
+    }
+
+    /// Export a passed verification as a signed W3C Verifiable Credential (JSON-LD)
+    #[napi(js_name = "to_verifiable_credential")]
+    pub fn to_verifiable_credential(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+    ) -> Result<String> {
+        self.read().to_verifiable_credential(&proof_data.0, &report.0).map_err(failure(&env))
+    }
+
+    /// Issue a signed receipt for a passed verification (check it with `verify_receipt`)
+    #[napi(js_name = "issue_receipt")]
+    pub fn issue_receipt(&self, env: Env, proof_data: &ProofData, report: &VerificationReport) -> Result<String> {
+        self.read().issue_receipt(&proof_data.0, &report.0).map_err(failure(&env))
+    }
+
+    /// Check that a prover published `proof_data` in Rekor (see `verify_rekor_entry`)
+    #[napi(js_name = "verify_rekor_proof")]
+    pub fn verify_rekor_proof(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        entry: String,
+        log_key: String,
+    ) -> Result<RekorInclusion> {
+        self.read()
+            .verify_rekor_proof(&proof_data.0, &entry, &log_key)
+            .map(RekorInclusion)
+            .map_err(failure(&env))
+    }
+
+    /// Verify a proof carried as the claims of a compact JWS
+    #[napi(js_name = "verify_jws")]
+    pub fn verify_jws(
+        &self,
+        env: Env,
+        token: String,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<VerificationReport> {
+        self.read()
+            .verify_jws(&token, &input_bytes, &output_bytes)
+            .map(VerificationReport::from)
+            .map_err(failure(&env))
+    }
+
+    /// Verify a proof carried as the payload of a `COSE_Sign1` envelope
+    #[napi(js_name = "verify_cose")]
+    pub fn verify_cose(
+        &self,
+        env: Env,
+        envelope: Uint8Array,
+        input_bytes: Uint8Array,
+        output_bytes: Uint8Array,
+    ) -> Result<VerificationReport> {
+        self.read()
+            .verify_cose(&envelope, &input_bytes, &output_bytes)
+            .map(VerificationReport::from)
+            .map_err(failure(&env))
+    }
+}
+
+/// Check a receipt from `issue_receipt` against the issuer's `did:key` (or hex Ed25519 key)
+#[napi(js_name = "verify_receipt")]
+pub fn verify_receipt(env: Env, token: String, issuer: String) -> Result<Receipt> {
+    zkml_verifier_core::receipt::verify_receipt(&token, &issuer).map(Receipt).map_err(failure(&env))
+}
+
+/// Check that a Rekor entry logs `data` signed by `signer_pubkey` and is included in the log
+#[napi(js_name = "verify_rekor_entry")]
+pub fn verify_rekor_entry(
+    env: Env,
+    entry: String,
+    data: Uint8Array,
+    signer_pubkey: String,
+    log_key: String,
+) -> Result<RekorInclusion> {
+    zkml_verifier_core::rekor::verify_rekor_entry(&entry, &data, &signer_pubkey, &log_key)
+        .map(RekorInclusion)
+        .map_err(failure(&env))
+}
+
+/// Rekor `rekord` entry for a receipt from `issue_receipt`, to `POST /api/v1/log/entries`
+#[napi(js_name = "rekor_receipt_entry")]
+pub fn rekor_receipt_entry(env: Env, token: String) -> Result<String> {
+    zkml_verifier_core::rekor::rekor_receipt_entry(&token).map_err(failure(&env))
+}
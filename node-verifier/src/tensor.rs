@@ -0,0 +1,162 @@
+// Typed tensor commitments
+// Canonical little-endian, row-major encoding of a tensor with its shape and dtype
+
+use napi::bindgen_prelude::*;
+use napi::{Env, JsTypedArray, JsUnknown, TypedArrayType};
+use napi_derive::napi;
+
+use crate::error::error;
+use crate::hash::hash_bytes;
+use crate::{f32s, list, mirror, HashAlgorithm, Optional, ProofData, VerificationReport, WasmVerifier, F32s, U32s};
+
+use zkml_verifier_core::tensor::{encode, encode_f32, TypedElements};
+use zkml_verifier_core::VerificationError as Kind;
+
+mirror! {
+    /// Element type of a tensor, named as in ONNX
+    DType => zkml_verifier_core::DType {
+        Float32,
+        Float64,
+        Float16,
+        Int8,
+        Uint8,
+        Int16,
+        Int32,
+        Int64,
+        Bool,
+    }
+}
+
+/// Copy out a `Float32Array`, `Int8Array` or `Int32Array`
+fn typed_array(array: JsUnknown) -> Result<Option<TypedElements>> {
+    if !array.is_typedarray()? {
+        return Ok(None);
+    }
+    // SAFETY: checked to be a typed array just above
+    let array = unsafe { array.cast::<JsTypedArray>() }.into_value()?;
+    Ok(match array.typedarray_type {
+        TypedArrayType::Float32 => Some(TypedElements::Float32(AsRef::<[f32]>::as_ref(&array).to_vec())),
+        TypedArrayType::Int8 => Some(TypedElements::Int8(AsRef::<[i8]>::as_ref(&array).to_vec())),
+        TypedArrayType::Int32 => Some(TypedElements::Int32(AsRef::<[i32]>::as_ref(&array).to_vec())),
+        _ => None,
+    })
+}
+
+/// Read a typed-array argument, throwing `InvalidArgument` for anything else
+fn typed_elements(env: &Env, array: JsUnknown, what: &str) -> Result<TypedElements> {
+    typed_array(array)?.ok_or_else(|| {
+        error(env, Kind::InvalidArgument, &format!("{} is not a Float32Array, Int8Array or Int32Array", what))
+    })
+}
+
+/// Declared shape, dtype and (optionally) memory layout of a model input or output
+///
+/// The compact form, used by the binary proof format, is `dtype[d0,d1,...]`
+/// with an optional `@layout` suffix, e.g. `float32[1,3,224,224]@NCHW`.
+#[napi]
+#[derive(Clone)]
+pub struct TensorSpec(pub(crate) zkml_verifier_core::TensorSpec);
+
+#[napi]
+impl TensorSpec {
+    #[napi(constructor)]
+    pub fn new(shape: U32s, dtype: DType, layout: Option<String>) -> TensorSpec {
+        TensorSpec(zkml_verifier_core::TensorSpec::new(list(shape), dtype.into(), layout))
+    }
+
+    #[napi(getter, js_name = "shape")]
+    pub fn shape(&self) -> Uint32Array {
+        Uint32Array::new(self.0.shape().to_vec())
+    }
+
+    #[napi(getter, js_name = "dtype")]
+    pub fn dtype(&self) -> DType {
+        self.0.dtype().into()
+    }
+
+    #[napi(getter, js_name = "layout")]
+    pub fn layout(&self) -> Optional<String> {
+        self.0.layout().map(str::to_string).into()
+    }
+
+    /// Size in bytes of a raw buffer holding this tensor
+    #[napi(getter, js_name = "byte_length")]
+    pub fn byte_length(&self) -> Optional<u64> {
+        self.0.byte_length().into()
+    }
+}
+
+const SHAPE_MISMATCH: &str = "Tensor data does not match its shape and dtype";
+
+/// Canonical bytes of a `Float32Array` tensor (row-major)
+#[napi(js_name = "encode_tensor_f32")]
+pub fn encode_tensor_f32(env: Env, data: F32s, shape: U32s) -> Result<Uint8Array> {
+    encode_f32(&f32s(data), &list(shape)).map(Uint8Array::from).map_err(|e| error(&env, e, SHAPE_MISMATCH))
+}
+
+/// Hash a `Float32Array` tensor (row-major) together with its shape and dtype
+#[napi(js_name = "hash_tensor_f32")]
+pub fn hash_tensor_f32(env: Env, data: F32s, shape: U32s, algorithm: Option<HashAlgorithm>) -> Result<String> {
+    let encoded = encode_f32(&f32s(data), &list(shape)).map_err(|e| error(&env, e, SHAPE_MISMATCH))?;
+    hash_bytes(&env, &encoded, algorithm)
+}
+
+/// Hash a tensor given as raw little-endian element bytes (e.g. `new Uint8Array(typed.buffer)`)
+#[napi(js_name = "hash_tensor")]
+pub fn hash_tensor(
+    env: Env,
+    data: Uint8Array,
+    dtype: DType,
+    shape: U32s,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String> {
+    let encoded = encode(dtype.into(), &list(shape), &data).map_err(|e| error(&env, e, SHAPE_MISMATCH))?;
+    hash_bytes(&env, &encoded, algorithm)
+}
+
+/// Canonical bytes of a `Float32Array`, `Int8Array` or `Int32Array` tensor (row-major)
+///
+/// The dtype follows from the array type; `shape` defaults to one dimension
+/// holding every element.
+#[napi(js_name = "encode_typed_tensor")]
+pub fn encode_typed_tensor(env: Env, data: JsUnknown, shape: Option<U32s>) -> Result<Uint8Array> {
+    let shape = shape.map(list);
+    typed_elements(&env, data, "Tensor")?
+        .encode(shape.as_deref())
+        .map(Uint8Array::from)
+        .map_err(|e| error(&env, e, SHAPE_MISMATCH))
+}
+
+/// Hash a `Float32Array`, `Int8Array` or `Int32Array` tensor together with its shape and dtype
+#[napi(js_name = "hash_typed_tensor")]
+pub fn hash_typed_tensor(
+    env: Env,
+    data: JsUnknown,
+    shape: Option<U32s>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String> {
+    let encoded = encode_typed_tensor(env, data, shape)?;
+    hash_bytes(&env, &encoded, algorithm)
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Verify a proof whose I/O commitments are tensor hashes, given the typed arrays themselves
+    ///
+    /// Each array is canonically encoded (as by `encode_typed_tensor`) in
+    /// the shape of the proof's `input_spec`/`output_spec`, or as a 1-d
+    /// tensor when the proof declares none.
+    #[napi(js_name = "verify_tensors")]
+    pub fn verify_tensors(
+        &self,
+        env: Env,
+        proof_data: &ProofData,
+        input: JsUnknown,
+        output: JsUnknown,
+    ) -> Result<VerificationReport> {
+        let input = typed_elements(&env, input, "Input")?;
+        let output = typed_elements(&env, output, "Output")?;
+        let report = self.read().tensors_report(&proof_data.0, &input, &output);
+        report.map(Into::into).map_err(|e| error(&env, e, SHAPE_MISMATCH))
+    }
+}
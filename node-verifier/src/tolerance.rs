@@ -0,0 +1,88 @@
+// Tolerance-based output verification
+// Proofs commit to the quantized output; float outputs are re-quantized and compared within epsilon
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{f32s, Optional, ProofData, VerificationReport, WasmVerifier, F32s};
+
+/// Affine per-tensor quantization of outputs, and how far re-quantized floats may drift
+///
+/// `q = clamp(round(x / scale) + zero_point)` as a signed `bit_width`-bit
+/// integer; the committed output is these integers, little-endian.
+#[napi]
+#[derive(Clone, Copy)]
+pub struct OutputTolerance(pub(crate) zkml_verifier_core::tolerance::OutputTolerance);
+
+#[napi]
+impl OutputTolerance {
+    /// `epsilon` is the allowed difference in quantization steps
+    #[napi(constructor)]
+    pub fn new(scale: f64, zero_point: i32, bit_width: u32, epsilon: u32) -> OutputTolerance {
+        OutputTolerance(zkml_verifier_core::tolerance::OutputTolerance::new(
+            scale as f32,
+            zero_point,
+            bit_width,
+            epsilon,
+        ))
+    }
+
+    #[napi(getter, js_name = "scale")]
+    pub fn scale(&self) -> f64 {
+        self.0.scale().into()
+    }
+
+    #[napi(getter, js_name = "zero_point")]
+    pub fn zero_point(&self) -> i32 {
+        self.0.zero_point()
+    }
+
+    #[napi(getter, js_name = "bit_width")]
+    pub fn bit_width(&self) -> u32 {
+        self.0.bit_width()
+    }
+
+    #[napi(getter, js_name = "epsilon")]
+    pub fn epsilon(&self) -> u32 {
+        self.0.epsilon()
+    }
+
+    /// Quantized encoding of a float output, as provers commit to it in `output_hash`
+    ///
+    /// Returns `undefined` for unsupported bit widths or non-finite values.
+    #[napi(js_name = "quantize_output")]
+    pub fn quantize_output(&self, output: F32s) -> Optional<Uint8Array> {
+        self.0.encode(&f32s(output)).map(Uint8Array::from).into()
+    }
+
+    /// Tolerance over fixed-point outputs (`encode_fixed_point` with `scale`), `epsilon` steps apart
+    #[napi(factory, js_name = "fixed_point")]
+    pub fn fixed_point(scale: i32, epsilon: u32) -> OutputTolerance {
+        OutputTolerance(zkml_verifier_core::tolerance::OutputTolerance::fixed_point(scale, epsilon))
+    }
+}
+
+#[napi]
+impl WasmVerifier {
+    /// Accept float outputs within `tolerance` of the committed quantized output (see `verify_tolerant`)
+    #[napi(js_name = "set_output_tolerance")]
+    pub fn set_output_tolerance(&mut self, tolerance: &OutputTolerance) {
+        self.write().set_output_tolerance(tolerance.0);
+    }
+
+    /// Verify a proof committing to a quantized output, given the float output observed
+    ///
+    /// `output_hash` is checked against `quantized_output` exactly; `output`
+    /// (e.g. computed on other hardware) must re-quantize to within the
+    /// configured epsilon of it, element by element.
+    #[napi(js_name = "verify_tolerant")]
+    pub fn verify_tolerant(
+        &self,
+        proof_data: &ProofData,
+        input: Uint8Array,
+        output: F32s,
+        quantized_output: Uint8Array,
+    ) -> VerificationReport {
+        self.read().tolerant_report(&proof_data.0, &input, &f32s(output), &quantized_output).into()
+    }
+}
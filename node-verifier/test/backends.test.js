@@ -0,0 +1,185 @@
+// Backend parity
+// Runs the same proofs through the native addon and the WASM build and compares what they return
+
+const assert = require("node:assert/strict");
+const test = require("node:test");
+
+// Documented as WASM-only in index.js; setters are the properties JS sees
+const WASM_ONLY = new Set([
+    "intercept_fetch",
+    "use_indexed_db_cache",
+    "verify_cached",
+    "proof_cache",
+    "verify_from_url",
+    "verify_report_from_url",
+    "proof_fetcher",
+    "verify_streams",
+    "hash_stream",
+    "verify_report_onchain",
+    "set_onchain_registry",
+    "chain_query",
+    "verify_anchored",
+    "clock",
+    "did_fetcher",
+    "fetcher",
+    "on_check_started",
+    "on_check_passed",
+    "on_check_failed",
+    "on_hash_progress",
+    "WorkerVerifier",
+    "IoBuffer",
+    "verify_buffers",
+    "verify_buffers_report",
+    "hash_buffer",
+    "init_threads",
+    "threads_enabled",
+    "init",
+    "simd_enabled",
+    "simd_supported",
+    "init_tracing",
+]);
+
+/// The package as loaded with ZKML_VERIFIER_BACKEND=`backend`; throws if that backend isn't built
+///
+/// Parity is the point of these tests: a missing backend fails them rather than skipping
+/// them. Build both first (`npm run build && npm run build:wasm`).
+function load(backend) {
+    const entry = require.resolve("..");
+    const forced = process.env.ZKML_VERIFIER_BACKEND;
+    delete require.cache[entry];
+    process.env.ZKML_VERIFIER_BACKEND = backend;
+    try {
+        return require("..");
+    } catch (e) {
+        const build = backend === "native" ? "npm run build" : "npm run build:wasm";
+        throw new Error(`The ${backend} backend isn't built (${build}): ${e.message}`);
+    } finally {
+        delete require.cache[entry];
+        if (forced === undefined) {
+            delete process.env.ZKML_VERIFIER_BACKEND;
+        } else {
+            process.env.ZKML_VERIFIER_BACKEND = forced;
+        }
+    }
+}
+
+const native = load("native");
+const wasm = load("wasm");
+
+/// Names a backend exports, without the WASM-only ones and wasm-bindgen internals
+function names(object) {
+    const skip = new Set(["length", "name", "arguments", "caller", "prototype", "constructor", "free", "backend"]);
+    return Object.getOwnPropertyNames(object)
+        .filter((name) => !skip.has(name) && !name.startsWith("__") && !WASM_ONLY.has(name))
+        .sort();
+}
+
+/// Exported names, and the members of every exported class
+function api(m) {
+    const classes = {};
+    for (const name of names(m)) {
+        if (typeof m[name] === "function" && /^[A-Z]/.test(name)) {
+            classes[name] = { statics: names(m[name]), members: names(m[name].prototype) };
+        }
+    }
+    return { exports: names(m), classes };
+}
+
+/// Variant name -> value of an exported enum (wasm-bindgen also maps values back to names)
+function variants(m, name) {
+    return Object.fromEntries(Object.entries(m[name]).filter(([key]) => Number.isNaN(Number(key))));
+}
+
+/// Report as a plain object, without timings
+function normalize(report) {
+    const object = report.to_object();
+    delete object.total_ms;
+    for (const check of object.checks) {
+        delete check.duration_ms;
+    }
+    return { passed: report.passed, confidence: report.confidence, object };
+}
+
+/// What `f` returns, or the thrown error's name, code, kind and message
+function attempt(f) {
+    try {
+        return f();
+    } catch (e) {
+        return { name: e.name, code: e.code, kind: e.kind, message: e.message };
+    }
+}
+
+const MODEL = "0x" + "ab".repeat(32);
+const INPUT = new Uint8Array([1, 2, 3, 4]);
+const OUTPUT = new Uint8Array([5, 6, 7]);
+const NOW = BigInt(Date.now());
+
+/// Proofs, with the verifier and I/O each is checked against
+function scenarios(m) {
+    const proof = (algorithm, timestamp) => {
+        const p = new m.ProofData(
+            MODEL,
+            "0x01",
+            m.hash_data(INPUT, algorithm),
+            m.hash_data(OUTPUT, algorithm),
+            timestamp,
+            true,
+        );
+        if (algorithm !== undefined) {
+            p.set_hash_algorithm(algorithm);
+        }
+        return p;
+    };
+    const verifier = new m.WasmVerifier(MODEL);
+    return {
+        valid: [verifier, proof(undefined, NOW), INPUT, OUTPUT],
+        tampered_output: [verifier, proof(undefined, NOW), INPUT, new Uint8Array([5, 6, 8])],
+        wrong_model: [new m.WasmVerifier("0x" + "cd".repeat(32)), proof(undefined, NOW), INPUT, OUTPUT],
+        keccak: [verifier, proof(m.HashAlgorithm.Keccak256, NOW), INPUT, OUTPUT],
+        expired: [verifier, proof(undefined, 1n), INPUT, OUTPUT],
+    };
+}
+
+/// Everything compared across backends, for one backend
+function run(m) {
+    const results = {};
+    for (const [name, [verifier, proof, input, output]] of Object.entries(scenarios(m))) {
+        results[name] = {
+            verify: verifier.verify(proof, input, output),
+            report: normalize(verifier.verify_report(proof, input, output)),
+            strict: attempt(() => verifier.verify_strict(proof, input, output)),
+            proof_id: proof.proof_id(),
+            binary: Array.from(proof.encode_binary()),
+        };
+    }
+    const algorithms = Object.keys(variants(m, "HashAlgorithm"));
+    return {
+        enums: Object.fromEntries(
+            ["HashAlgorithm", "Check", "Confidence", "VerificationError"].map((name) => [name, variants(m, name)]),
+        ),
+        hashes: algorithms.map((name) => attempt(() => m.hash_data(INPUT, m.HashAlgorithm[name]))),
+        results,
+    };
+}
+
+test("native backend rejects each broken proof with the WASM build's error", () => {
+    const { results } = run(native);
+    assert.equal(results.valid.verify, true);
+    assert.equal(results.valid.strict, undefined);
+    assert.equal(results.keccak.verify, true);
+    const expected = { tampered_output: "OutputHashMismatch", wrong_model: "ModelMismatch", expired: "Expired" };
+    for (const [name, kind] of Object.entries(expected)) {
+        assert.equal(results[name].verify, false, name);
+        assert.equal(results[name].strict.name, "VerificationError", name);
+        assert.equal(results[name].strict.kind, kind, name);
+        assert.equal(results[name].strict.code, native.VerificationError[kind], name);
+    }
+});
+
+test("both backends export the same API", () => {
+    assert.deepEqual(api(native), api(wasm));
+});
+
+test("both backends give the same results for the same proofs", () => {
+    assert.deepEqual(run(native), run(wasm));
+});
@@ -3,14 +3,97 @@
 
 set -e
 
+# `--minimal` builds with opt-level z and a std without panic messages (nightly `build-std`), and must stay
+# under this many bytes gzipped (its transfer size): `--size-check` checks it without wasm-pack or wasm-opt
+MINIMAL_MAX_GZIP_BYTES=100000
+
+minimal() {
+    CARGO_PROFILE_RELEASE_OPT_LEVEL=z RUSTFLAGS="-Zunstable-options -Cpanic=immediate-abort" rustup run nightly "$@"
+}
+
+check_minimal_size() {
+    size=$(gzip -9c "$1" | wc -c)
+    echo "📏 Minimal module: $size bytes gzipped (limit $MINIMAL_MAX_GZIP_BYTES)"
+    if [ "$size" -ge "$MINIMAL_MAX_GZIP_BYTES" ]; then
+        echo "❌ Minimal module is over the size limit"
+        exit 1
+    fi
+}
+
+if [ "$1" = "--size-check" ]; then
+    # Its own target dir: build-std and the flags would otherwise rebuild the regular target
+    minimal cargo build --release --lib --target wasm32-unknown-unknown --target-dir target/minimal \
+        --no-default-features -Z build-std=std,panic_abort
+    wasm-bindgen --remove-name-section --remove-producers-section --target web --out-dir target/minimal/pkg \
+        target/minimal/wasm32-unknown-unknown/release/zkml_wasm_verifier.wasm
+    check_minimal_size target/minimal/pkg/zkml_wasm_verifier_bg.wasm
+    exit 0
+fi
+
+if [ "$1" = "--node" ]; then
+    # CommonJS build for Node: the fallback of the npm package in ../node-verifier
+    # on platforms without a native addon
+    wasm-pack build --target nodejs --out-dir ../node-verifier/wasm
+    echo "📦 Output: ../node-verifier/wasm/"
+    exit 0
+fi
+
+if [ "$1" = "--wasi" ]; then
+    # The onnx-verify CLI as a WASI command for wasmtime, Spin and server-side
+    # WASM hosts: files come from preopened dirs, time from the WASI clock, e.g.
+    #   wasmtime --dir . onnx-verify.wasm verify --proof p.json --input in.bin --output out.bin --model m.onnx
+    # Hosts embedding the library instead get WasmVerifier::load_proof and
+    # verify_files, which take paths in the same preopened dirs
+    cargo build --release --target wasm32-wasip1 -p onnx-verify
+    echo "📦 Output: target/wasm32-wasip1/release/onnx-verify.wasm"
+    exit 0
+fi
+
+if [ "$1" = "--test" ]; then
+    # wasm-bindgen tests of the JS-facing code (streams, fetch, AbortSignal) in Node;
+    # `--test --browser` adds tests/browser.rs (IndexedDB, Web Workers) in headless
+    # Chrome or Firefox, whichever of chromedriver and geckodriver is on PATH
+    cargo test --target wasm32-unknown-unknown --lib
+    if [ "$2" = "--browser" ]; then
+        cargo test --target wasm32-unknown-unknown --test browser
+    fi
+    exit 0
+fi
+
 echo "Building WASM verifier..."
-wasm-pack build --target web --out-dir pkg
+if [ "$1" = "--threads" ]; then
+    # Parallel batch verification (`atomics` feature): shared memory needs a
+    # nightly std rebuilt with atomics, and a cross-origin isolated page
+    RUSTFLAGS="-C target-feature=+atomics,+bulk-memory" \
+        rustup run nightly wasm-pack build --target web --out-dir pkg -- \
+        --features atomics -Z build-std=panic_abort,std
+elif [ "$1" = "--minimal" ]; then
+    # Hashing and proof checks only: no signatures, ONNX parsing, compressed
+    # proofs, async APIs or extras (see the features in Cargo.toml)
+    minimal wasm-pack build --target web --out-dir pkg -- --no-default-features -Z build-std=std,panic_abort
+    check_minimal_size pkg/zkml_wasm_verifier_bg.wasm
+elif [ "$1" = "--webcrypto" ]; then
+    # Everything but the Rust Ed25519/P-256: the async verify paths check
+    # them with WebCrypto, the sync ones and receipt/credential issuing can't
+    wasm-pack build --target web --out-dir pkg -- --no-default-features \
+        --features console_error_panic_hook,signatures,onnx,compression,cbor,async,extras
+elif [ "$1" = "--simd" ]; then
+    # simd128 Keccak; load this build only where `simd_supported()` is true
+    RUSTFLAGS="-C target-feature=+simd128" wasm-pack build --target web --out-dir pkg -- --features simd
+elif [ "$1" = "--tracing" ]; then
+    # Check spans in the browser console: call `init_tracing("debug")` once
+    wasm-pack build --target web --out-dir pkg -- --features tracing
+else
+    wasm-pack build --target web --out-dir pkg
+fi
 
 echo "Patching for browser compatibility..."
 node patch.js
 
 echo "Copying to root directory..."
 cp pkg/zkml_wasm_verifier*.{js,wasm,ts} ../
+# JS snippets (the WorkerVerifier worker script) are imported relative to the package
+cp -r pkg/snippets ../
 
 echo "✅ WASM verifier built and patched successfully!"
 echo "📦 Output: pkg/ (copied to root)"
@@ -0,0 +1,301 @@
+// Batch verification
+// `verify-batch`: proofs matched by a glob, checked against a manifest's I/O files; table, JSON, NDJSON or JUnit output
+
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use zkml_verifier_core::{ProofData, VerificationError, Verifier};
+
+use crate::{failure_code, read, read_string, verifier, Format, Model, EXIT_ERROR};
+
+/// Arguments of `onnx-verify verify-batch`
+#[derive(Debug, PartialEq)]
+pub(crate) struct BatchArgs {
+    pub(crate) pattern: String,
+    pub(crate) manifest: String,
+    pub(crate) model: Model,
+    pub(crate) config: Option<String>,
+    pub(crate) format: Format,
+    pub(crate) webhook: Option<String>,
+}
+
+/// I/O files of one proof, relative to the manifest
+///
+/// ```json
+/// { "proofs/a.json": { "input": "io/a.in", "output": "io/a.out" } }
+/// ```
+#[derive(Deserialize)]
+struct ManifestEntry {
+    input: String,
+    output: String,
+}
+
+/// Manifest entries keyed by the proof's canonical path
+fn load_manifest(path: &str) -> Result<HashMap<PathBuf, (PathBuf, PathBuf)>, String> {
+    let entries: HashMap<String, ManifestEntry> =
+        serde_json::from_str(&read_string(path)?).map_err(|e| format!("Invalid manifest {}: {}", path, e))?;
+    let dir = Path::new(path).parent().unwrap_or(Path::new("."));
+    let canonical = |p: &str| dir.join(p).canonicalize().unwrap_or_else(|_| dir.join(p));
+    Ok(entries
+        .into_iter()
+        .map(|(proof, io)| (canonical(&proof), (dir.join(io.input), dir.join(io.output))))
+        .collect())
+}
+
+/// What happened to one proof
+#[derive(Debug, PartialEq)]
+pub(crate) enum Outcome {
+    Passed,
+    Failed { error: VerificationError, reasons: Vec<String> },
+    /// The proof couldn't be checked, e.g. a missing file or manifest entry
+    Error(String),
+}
+
+#[derive(Debug)]
+struct ProofResult {
+    proof: String,
+    outcome: Outcome,
+    duration_ms: f64,
+}
+
+/// Results of a `verify-batch` run, in proof path order
+#[derive(Debug)]
+pub(crate) struct BatchReport {
+    results: Vec<ProofResult>,
+}
+
+impl BatchReport {
+    fn count(&self, status: &str) -> usize {
+        self.results.iter().filter(|r| status_of(&r.outcome) == status).count()
+    }
+
+    /// 0 if every proof verified, else the failures' exit code if they share one, 1 if they don't
+    pub(crate) fn exit_code(&self) -> u8 {
+        let codes: BTreeSet<u8> = self
+            .results
+            .iter()
+            .filter_map(|r| match &r.outcome {
+                Outcome::Passed => None,
+                Outcome::Failed { error, .. } => Some(failure_code(*error)),
+                Outcome::Error(_) => Some(EXIT_ERROR),
+            })
+            .collect();
+        match codes.len() {
+            0 | 1 => codes.into_iter().next().unwrap_or(0),
+            _ => 1,
+        }
+    }
+
+    pub(crate) fn render(&self, format: Format) -> String {
+        match format {
+            Format::Table => self.to_text(),
+            Format::Json => self.to_json(),
+            Format::Ndjson => self.proofs().iter().map(|p| p.to_string()).collect::<Vec<_>>().join("\n"),
+            Format::Junit => self.to_junit(),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines: Vec<String> = self.results.iter().map(|r| text_line(&r.proof, &r.outcome)).collect();
+        lines.push(format!(
+            "{} proofs: {} passed, {} failed, {} could not be checked",
+            self.results.len(),
+            self.count("passed"),
+            self.count("failed"),
+            self.count("error")
+        ));
+        lines.join("\n")
+    }
+
+    fn proofs(&self) -> Vec<serde_json::Value> {
+        self.results
+            .iter()
+            .map(|r| {
+                let (error, reasons) = match &r.outcome {
+                    Outcome::Passed => (None, Vec::new()),
+                    Outcome::Failed { error, reasons } => (Some(error.name()), reasons.clone()),
+                    Outcome::Error(message) => (None, vec![message.clone()]),
+                };
+                serde_json::json!({
+                    "proof": r.proof,
+                    "status": status_of(&r.outcome),
+                    "error": error,
+                    "reasons": reasons,
+                    "duration_ms": r.duration_ms,
+                })
+            })
+            .collect()
+    }
+
+    /// Counts and per-proof results, as `--format json` prints them
+    pub(crate) fn summary(&self) -> serde_json::Value {
+        serde_json::json!({
+            "total": self.results.len(),
+            "passed": self.count("passed"),
+            "failed": self.count("failed"),
+            "errors": self.count("error"),
+            "proofs": self.proofs(),
+        })
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.summary()).unwrap_or_default()
+    }
+
+    /// JUnit XML: one test case per proof, verification failures as `<failure>`, unreadable proofs as `<error>`
+    fn to_junit(&self) -> String {
+        let seconds = |ms: f64| format!("{:.3}", ms / 1000.0);
+        let total_ms: f64 = self.results.iter().map(|r| r.duration_ms).sum();
+        let mut xml = format!(
+            "{}\n<testsuite name=\"onnx-verify\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{}\">\n",
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            self.results.len(),
+            self.count("failed"),
+            self.count("error"),
+            seconds(total_ms)
+        );
+        for r in &self.results {
+            let (name, time) = (escape(&r.proof), seconds(r.duration_ms));
+            let case = format!("  <testcase classname=\"onnx-verify\" name=\"{}\" time=\"{}\"", name, time);
+            xml += &match &r.outcome {
+                Outcome::Passed => format!("{}/>\n", case),
+                Outcome::Failed { error, reasons } => format!(
+                    "{}>\n    <failure type=\"{}\" message=\"{}\"/>\n  </testcase>\n",
+                    case,
+                    error.name(),
+                    escape(&reasons.join("; "))
+                ),
+                Outcome::Error(message) => {
+                    format!("{}>\n    <error message=\"{}\"/>\n  </testcase>\n", case, escape(message))
+                }
+            };
+        }
+        xml + "</testsuite>"
+    }
+}
+
+/// `PASS`/`FAIL`/`ERROR`, the proof, and why it didn't pass
+pub(crate) fn text_line(proof: &str, outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Passed => format!("PASS   {}", proof),
+        Outcome::Failed { error, reasons } => format!("FAIL   {}  {}: {}", proof, error.name(), reasons.join("; ")),
+        Outcome::Error(message) => format!("ERROR  {}  {}", proof, message),
+    }
+}
+
+pub(crate) fn status_of(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Passed => "passed",
+        Outcome::Failed { .. } => "failed",
+        Outcome::Error(_) => "error",
+    }
+}
+
+/// Escape text for an XML attribute
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+pub(crate) fn check(verifier: &Verifier, proof: &Path, io: Option<&(PathBuf, PathBuf)>) -> Outcome {
+    let checked = || -> Result<Outcome, String> {
+        let (input, output) = io.ok_or("No manifest entry")?;
+        let path = proof.to_string_lossy();
+        let proof: ProofData = serde_json::from_str(&read_string(&path)?).map_err(|e| format!("Invalid proof: {}", e))?;
+        let (input, output) = (read(&input.to_string_lossy())?, read(&output.to_string_lossy())?);
+        let report = verifier.verify_report(&proof, &input, &output);
+        Ok(match report.error() {
+            None if report.passed() => Outcome::Passed,
+            error => Outcome::Failed {
+                error: error.unwrap_or(VerificationError::NotVerified),
+                reasons: report.failure_reasons(),
+            },
+        })
+    };
+    checked().unwrap_or_else(Outcome::Error)
+}
+
+/// Verify every proof matching `args.pattern`
+///
+/// Fails only if nothing can be checked at all: a bad pattern, manifest,
+/// model or config, or no matching proofs. Problems with single proofs
+/// are reported as their outcome.
+pub(crate) fn run_batch(args: &BatchArgs) -> Result<BatchReport, String> {
+    let proofs = crate::glob::glob(&args.pattern)?;
+    if proofs.is_empty() {
+        return Err(format!("No proofs match '{}'", args.pattern));
+    }
+    let manifest = load_manifest(&args.manifest)?;
+    let verifier = verifier(&args.model, &args.config)?;
+    let results = proofs
+        .iter()
+        .map(|proof| {
+            let started = Instant::now();
+            let io = proof.canonicalize().ok().and_then(|canonical| manifest.get(&canonical));
+            let outcome = check(&verifier, proof, io);
+            ProofResult {
+                proof: proof.to_string_lossy().into_owned(),
+                outcome,
+                duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            }
+        })
+        .collect();
+    Ok(BatchReport { results })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+
+    #[test]
+    fn test_batch() {
+        let dir = std::env::temp_dir().join(format!("onnx-verify-batch-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("proofs/nested")).unwrap();
+        std::fs::create_dir_all(dir.join("io")).unwrap();
+        let proof = fixtures::proof("0xmodel");
+        for name in ["proofs/a.json", "proofs/nested/b.json", "proofs/c.json"] {
+            std::fs::write(dir.join(name), proof.to_json()).unwrap();
+        }
+        std::fs::write(dir.join("io/in"), b"in").unwrap();
+        std::fs::write(dir.join("io/out"), b"out").unwrap();
+        std::fs::write(dir.join("io/tampered"), b"<tampered>").unwrap();
+        let manifest = r#"{
+            "proofs/a.json": { "input": "io/in", "output": "io/out" },
+            "proofs/nested/b.json": { "input": "io/in", "output": "io/tampered" }
+        }"#;
+        std::fs::write(dir.join("manifest.json"), manifest).unwrap();
+
+        let args = BatchArgs {
+            pattern: format!("{}/proofs/**/*.json", dir.display()),
+            manifest: dir.join("manifest.json").to_string_lossy().into_owned(),
+            model: Model::Hash("0xmodel".to_string()),
+            config: None,
+            format: Format::Junit,
+            webhook: None,
+        };
+        let report = run_batch(&args).unwrap();
+        let outcomes: Vec<_> = report.results.iter().map(|r| status_of(&r.outcome)).collect();
+        assert_eq!(outcomes, ["passed", "error", "failed"]);
+        assert_eq!(report.exit_code(), 1);
+
+        let junit = report.render(Format::Junit);
+        assert!(junit.contains("tests=\"3\" failures=\"1\" errors=\"1\""));
+        assert!(junit.contains("<failure type=\"OutputHashMismatch\" message=\"Output does not match output_hash\"/>"));
+        assert!(junit.contains("<error message=\"No manifest entry\"/>"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.render(Format::Json)).unwrap();
+        assert_eq!((json["total"].as_u64(), json["passed"].as_u64()), (Some(3), Some(1)));
+        assert_eq!(json["proofs"][2]["error"], "OutputHashMismatch");
+        assert!(report.render(Format::Table).ends_with("3 proofs: 1 passed, 1 failed, 1 could not be checked"));
+        let ndjson = report.render(Format::Ndjson);
+        let ndjson: Vec<&str> = ndjson.lines().collect();
+        assert_eq!(ndjson.len(), 3);
+        assert!(ndjson[2].contains(r#""error":"OutputHashMismatch""#));
+
+        let args = BatchArgs { pattern: format!("{}/none/*.json", dir.display()), ..args };
+        assert!(run_batch(&args).unwrap_err().starts_with("No proofs match"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+// Path globs
+// `proofs/**/*.json`-style patterns: `*` and `?` within a path segment, `**` across directories
+
+use std::path::{Path, PathBuf};
+
+/// Whether `name` matches one pattern segment, where `*` is any run of characters and `?` any one
+fn segment_matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            segment_matches(&pattern[1..], name) || (!name.is_empty() && segment_matches(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => segment_matches(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => segment_matches(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+fn is_literal(segment: &str) -> bool {
+    !segment.contains(['*', '?'])
+}
+
+fn walk(dir: &Path, segments: &[&str], found: &mut Vec<PathBuf>) -> Result<(), String> {
+    let Some((segment, rest)) = segments.split_first() else {
+        return Ok(());
+    };
+    if *segment == "**" {
+        walk(dir, rest, found)?;
+    }
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Cannot read {}: {}", dir.display(), e))?;
+    let mut entries: Vec<_> = entries.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    entries.sort();
+    let pattern: Vec<char> = segment.chars().collect();
+    for path in entries {
+        let name: Vec<char> = path.file_name().unwrap_or_default().to_string_lossy().chars().collect();
+        // Like shells, wildcards skip dot-files
+        if name.first() == Some(&'.') && pattern.first() != Some(&'.') {
+            continue;
+        }
+        if *segment == "**" {
+            if path.is_dir() {
+                walk(&path, segments, found)?;
+            }
+        } else if segment_matches(&pattern, &name) {
+            if rest.is_empty() {
+                if path.is_file() {
+                    found.push(path);
+                }
+            } else if path.is_dir() {
+                walk(&path, rest, found)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Files matching `pattern`, in path order
+///
+/// Leading segments without wildcards are taken as the directory to
+/// search from (no matches if it doesn't exist); a pattern without
+/// wildcards names a single file.
+pub(crate) fn glob(pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let literal = segments.iter().take_while(|s| is_literal(s)).count();
+    if literal == segments.len() {
+        let path = PathBuf::from(pattern);
+        return Ok(if path.is_file() { vec![path] } else { Vec::new() });
+    }
+    let base = match segments[..literal].join("/") {
+        prefix if prefix.is_empty() && literal > 0 => PathBuf::from("/"),
+        prefix if prefix.is_empty() => PathBuf::from("."),
+        prefix => PathBuf::from(prefix),
+    };
+    let mut found = Vec::new();
+    if base.is_dir() {
+        walk(&base, &segments[literal..], &mut found)?;
+    }
+    // `**` can reach a file by more than one route, e.g. `**/**/x`
+    found.sort();
+    found.dedup();
+    Ok(found.into_iter().map(|path| path.strip_prefix("./").map(Path::to_path_buf).unwrap_or(path)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, name: &str) -> bool {
+        segment_matches(&pattern.chars().collect::<Vec<_>>(), &name.chars().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_glob() {
+        assert!(matches("*.json", "a.json"));
+        assert!(matches("proof-??.json", "proof-01.json"));
+        assert!(!matches("*.json", "a.json.bak"));
+        assert!(!matches("proof-?.json", "proof-10.json"));
+
+        let dir = std::env::temp_dir().join(format!("onnx-verify-glob-{}", std::process::id()));
+        for file in ["a.json", "b.txt", "nested/c.json", "nested/deeper/d.json", "nested/.hidden.json"] {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"{}").unwrap();
+        }
+        let names = |pattern: &str| -> Vec<String> {
+            let found = glob(&format!("{}/{}", dir.display(), pattern)).unwrap();
+            found.iter().map(|p| p.strip_prefix(&dir).unwrap().to_string_lossy().into_owned()).collect()
+        };
+        assert_eq!(names("*.json"), ["a.json"]);
+        assert_eq!(names("**/*.json"), ["a.json", "nested/c.json", "nested/deeper/d.json"]);
+        assert_eq!(names("nested/*/*.json"), ["nested/deeper/d.json"]);
+        assert_eq!(names("b.txt"), ["b.txt"]);
+        assert!(names("missing/*.json").is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,372 @@
+// gRPC verification service
+// `zkml.verifier.v1.Verifier` of proto/verifier_service.proto: hand-written prost messages and tonic routing
+
+use prost::Message;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tonic::codec::ProstCodec;
+use tonic::codegen::{http, Body, BoxFuture, Service, StdError};
+use tonic::server::{Grpc, NamedService};
+use tonic::{Request, Response, Status, Streaming};
+use tower::service_fn;
+
+use zkml_verifier_core::{ProofData, VerificationError, VerificationReport, Verifier};
+
+use crate::metrics::Metrics;
+use crate::pool::{Pool, Refused};
+use crate::rekor::Rekor;
+use crate::server::{notify, publish, result_json};
+use crate::webhook::Webhook;
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct VerifyRequest {
+    /// Encoded `zkml.verifier.v1.Proof` (same wire format as the message field)
+    #[prost(bytes = "vec", tag = "1")]
+    pub proof: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub input: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub output: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct VerifyResponse {
+    #[prost(bool, tag = "1")]
+    pub passed: bool,
+    #[prost(string, tag = "2")]
+    pub error: String,
+    #[prost(uint32, tag = "3")]
+    pub error_code: u32,
+    #[prost(string, repeated, tag = "4")]
+    pub failure_reasons: Vec<String>,
+    #[prost(string, tag = "5")]
+    pub report_json: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct VerifyBatchRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub requests: Vec<VerifyRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct VerifyBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub responses: Vec<VerifyResponse>,
+    #[prost(uint32, tag = "2")]
+    pub passed: u32,
+    #[prost(uint32, tag = "3")]
+    pub failed: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct StreamVerifyRequest {
+    #[prost(oneof = "Part", tags = "1, 2, 3")]
+    pub part: Option<Part>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub(crate) enum Part {
+    #[prost(bytes, tag = "1")]
+    Proof(Vec<u8>),
+    #[prost(bytes, tag = "2")]
+    InputChunk(Vec<u8>),
+    #[prost(bytes, tag = "3")]
+    OutputChunk(Vec<u8>),
+}
+
+fn response(report: &VerificationReport) -> VerifyResponse {
+    let error = report.error();
+    VerifyResponse {
+        passed: report.passed(),
+        error: error.map_or("", |e| e.name()).to_string(),
+        error_code: error.map_or(0, |e| e.code()),
+        failure_reasons: report.failure_reasons(),
+        report_json: report.to_json(),
+    }
+}
+
+const INVALID_PROOF: &str = "Invalid Proof message";
+
+/// The `Verifier` service, answering on the shared verifier threads
+#[derive(Clone)]
+pub(crate) struct VerifierService {
+    pool: Pool,
+    metrics: Arc<Metrics>,
+    webhook: Option<Arc<Webhook>>,
+    rekor: Option<Arc<Rekor>>,
+}
+
+impl VerifierService {
+    async fn check(&self, proof: ProofData, input: Vec<u8>, output: Vec<u8>) -> Result<VerifyResponse, Status> {
+        self.run(proof, move |verifier, proof| verifier.verify_report(proof, &input, &output)).await
+    }
+
+    /// Run `verify` on a verifier thread, recording its report, sending it to the webhook and logging a receipt
+    async fn run<F>(&self, proof: ProofData, verify: F) -> Result<VerifyResponse, Status>
+    where
+        F: FnOnce(&Verifier, &ProofData) -> VerificationReport + Send + 'static,
+    {
+        let (metrics, notifying, rekor) = (self.metrics.clone(), self.webhook.is_some(), self.rekor.clone());
+        let job = move |verifier: &Verifier| {
+            let report = verify(verifier, &proof);
+            metrics.record(&report);
+            let receipt = rekor.and_then(|rekor| rekor.receipt(verifier, &proof, &report));
+            (response(&report), notifying.then(|| result_json(&report)), receipt)
+        };
+        let (response, result, receipt) = self.pool.run(job).await.map_err(|refused| self.refuse(refused))?;
+        if let Some(result) = result {
+            notify(&self.webhook, "grpc", || result);
+        }
+        publish(&self.rekor, receipt);
+        Ok(response)
+    }
+
+    /// `RESOURCE_EXHAUSTED` if the queue is full, else `UNAVAILABLE`
+    fn refuse(&self, refused: Refused) -> Status {
+        self.metrics.unavailable();
+        match refused {
+            Refused::Busy => Status::resource_exhausted(refused.to_string()),
+            Refused::TimedOut | Refused::Closed => Status::unavailable(refused.to_string()),
+        }
+    }
+
+    /// `INVALID_ARGUMENT`, counted as a rejected verification
+    fn reject(&self, message: &str) -> Status {
+        self.metrics.reject();
+        Status::invalid_argument(message)
+    }
+
+    fn invalid_proof(&self, _: VerificationError) -> Status {
+        self.reject(INVALID_PROOF)
+    }
+
+    async fn verify(self, request: Request<VerifyRequest>) -> Result<Response<VerifyResponse>, Status> {
+        let VerifyRequest { proof, input, output } = request.into_inner();
+        let proof = ProofData::decode_protobuf(&proof).map_err(|e| self.invalid_proof(e))?;
+        Ok(Response::new(self.check(proof, input, output).await?))
+    }
+
+    async fn verify_batch(self, request: Request<VerifyBatchRequest>) -> Result<Response<VerifyBatchResponse>, Status> {
+        let tasks: Vec<_> = request
+            .into_inner()
+            .requests
+            .into_iter()
+            .map(|VerifyRequest { proof, input, output }| {
+                let service = self.clone();
+                tokio::spawn(async move {
+                    match ProofData::decode_protobuf(&proof) {
+                        Ok(proof) => service.check(proof, input, output).await,
+                        Err(error) => {
+                            service.metrics.reject();
+                            Ok(VerifyResponse {
+                                error: error.name().to_string(),
+                                error_code: error.code(),
+                                failure_reasons: vec![INVALID_PROOF.to_string()],
+                                ..VerifyResponse::default()
+                            })
+                        }
+                    }
+                })
+            })
+            .collect();
+        let mut batch = VerifyBatchResponse::default();
+        for task in tasks {
+            let response = task.await.map_err(|e| Status::internal(e.to_string()))??;
+            if response.passed {
+                batch.passed += 1;
+            } else {
+                batch.failed += 1;
+            }
+            batch.responses.push(response);
+        }
+        Ok(Response::new(batch))
+    }
+
+    /// Hash chunks as they arrive, so inputs of any size verify in constant memory
+    async fn stream_verify(
+        self,
+        request: Request<Streaming<StreamVerifyRequest>>,
+    ) -> Result<Response<VerifyResponse>, Status> {
+        let mut parts = request.into_inner();
+        let proof = match parts.message().await? {
+            Some(StreamVerifyRequest { part: Some(Part::Proof(proof)) }) => {
+                ProofData::decode_protobuf(&proof).map_err(|e| self.invalid_proof(e))?
+            }
+            _ => return Err(self.reject("The first message must be the proof")),
+        };
+        let for_proof = proof.clone();
+        let hashers = self.pool.run(move |v| v.input_hasher(&for_proof).zip(v.output_hasher(&for_proof)));
+        let Some((mut input, mut output)) = hashers.await.map_err(|refused| self.refuse(refused))? else {
+            return Err(self.reject("The proof's hash algorithm is not enabled in this build"));
+        };
+        while let Some(StreamVerifyRequest { part }) = parts.message().await? {
+            match part {
+                Some(Part::InputChunk(chunk)) => input.update(&chunk),
+                Some(Part::OutputChunk(chunk)) => output.update(&chunk),
+                Some(Part::Proof(_)) => return Err(self.reject("Only the first message may be the proof")),
+                None => {}
+            }
+        }
+        let verify =
+            move |verifier: &Verifier, proof: &ProofData| verifier.verify_streamed(proof, &mut input, &mut output);
+        Ok(Response::new(self.run(proof, verify).await?))
+    }
+}
+
+impl<B> Service<http::Request<B>> for VerifierService
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let service = self.clone();
+        let metrics = self.metrics.clone();
+        let max_bytes = self.pool.limits().max_request_bytes;
+        Box::pin(async move {
+            let (path, started) = (request.uri().path().to_string(), Instant::now());
+            // Held until the response is ready, so requests over the limit are refused before being read
+            let _admitted = match service.pool.admit(None) {
+                Ok(admitted) => admitted,
+                Err(refused) => return Ok(service.refuse(refused).into_http()),
+            };
+            let response = match path.strip_prefix("/zkml.verifier.v1.Verifier/") {
+                Some("Verify") => {
+                    let handler = service_fn(move |request| service.clone().verify(request));
+                    let mut grpc = Grpc::new(ProstCodec::default()).max_decoding_message_size(max_bytes);
+                    grpc.unary(handler, request).await
+                }
+                Some("VerifyBatch") => {
+                    let handler = service_fn(move |request| service.clone().verify_batch(request));
+                    let mut grpc = Grpc::new(ProstCodec::default()).max_decoding_message_size(max_bytes);
+                    grpc.unary(handler, request).await
+                }
+                Some("StreamVerify") => {
+                    let handler = service_fn(move |request| service.clone().stream_verify(request));
+                    let mut grpc = Grpc::new(ProstCodec::default()).max_decoding_message_size(max_bytes);
+                    grpc.client_streaming(handler, request).await
+                }
+                _ => return Ok(Status::unimplemented(format!("Unknown method {}", path)).into_http()),
+            };
+            metrics.observe_request("grpc", started.elapsed());
+            Ok(response)
+        })
+    }
+}
+
+impl NamedService for VerifierService {
+    const NAME: &'static str = "zkml.verifier.v1.Verifier";
+}
+
+/// Serve the `Verifier` service on `addr` until the process is stopped
+pub(crate) async fn serve(
+    addr: &str,
+    pool: Pool,
+    metrics: Arc<Metrics>,
+    webhook: Option<Arc<Webhook>>,
+    rekor: Option<Arc<Rekor>>,
+) -> Result<(), String> {
+    let socket = addr.parse().map_err(|e| format!("Invalid gRPC address {}: {}", addr, e))?;
+    eprintln!("onnx-verify serving gRPC on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(VerifierService { pool, metrics, webhook, rekor })
+        .serve(socket)
+        .await
+        .map_err(|e| format!("gRPC server error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+    use crate::{Expected, Limits};
+    use tower::ServiceExt;
+    use zkml_verifier_core::{HashAlgorithm, VerifierPolicy};
+
+    /// Length-prefixed gRPC frames of `messages`
+    fn frames<M: Message>(messages: &[M]) -> Vec<u8> {
+        let mut frames = Vec::new();
+        for message in messages {
+            let body = message.encode_to_vec();
+            frames.push(0);
+            frames.extend((body.len() as u32).to_be_bytes());
+            frames.extend(body);
+        }
+        frames
+    }
+
+    fn call<M: Message + Default>(service: &VerifierService, method: &str, body: Vec<u8>) -> Result<M, String> {
+        let request = http::Request::post(format!("/zkml.verifier.v1.Verifier/{}", method))
+            .header("content-type", "application/grpc")
+            .body(axum::body::Body::from(body))
+            .unwrap();
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let response = service.clone().oneshot(request).await.unwrap();
+            if let Some(status) = Status::from_header_map(response.headers()) {
+                return Err(status.message().to_string());
+            }
+            let body = axum::body::to_bytes(axum::body::Body::new(response.into_body()), usize::MAX).await.unwrap();
+            Ok(M::decode(&body[5..]).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_grpc() {
+        let limits = Limits { workers: 2, queue: 0, max_request_bytes: 64 * 1024, ..Limits::default() };
+        let expected = Expected::Hash("0xmodel".to_string());
+        let pool = Pool::start(&expected, VerifierPolicy::default(), limits, None).unwrap();
+        let metrics = Arc::new(Metrics::default());
+        let service = VerifierService { pool, metrics: metrics.clone(), webhook: None, rekor: None };
+        let input = vec![7u8; 10_000];
+        let proof = fixtures::proof_for("0xmodel", &input, b"out", HashAlgorithm::default())
+        .encode_protobuf();
+        let request = |output: &[u8]| VerifyRequest {
+            proof: proof.clone(),
+            input: input.clone(),
+            output: output.to_vec(),
+        };
+
+        let verified: VerifyResponse = call(&service, "Verify", frames(&[request(b"out")])).unwrap();
+        assert!(verified.passed && verified.error.is_empty());
+
+        let malformed = VerifyRequest { proof: vec![0x0a, 0xff], ..request(b"out") };
+        let batch = VerifyBatchRequest { requests: vec![request(b"out"), request(b"tampered"), malformed.clone()] };
+        let batch: VerifyBatchResponse = call(&service, "VerifyBatch", frames(&[batch])).unwrap();
+        assert_eq!((batch.passed, batch.failed), (1, 2));
+        let errors: Vec<_> = batch.responses.iter().map(|r| r.error.as_str()).collect();
+        assert_eq!(errors, ["", "OutputHashMismatch", "MalformedProof"]);
+        let invalid = call::<VerifyResponse>(&service, "Verify", frames(&[malformed]));
+        assert_eq!(invalid.unwrap_err(), "Invalid Proof message");
+
+        let part = |part: Part| StreamVerifyRequest { part: Some(part) };
+        let mut parts = vec![part(Part::Proof(proof.clone())), part(Part::OutputChunk(b"out".to_vec()))];
+        parts.extend(input.chunks(3000).map(|chunk| part(Part::InputChunk(chunk.to_vec()))));
+        let streamed: VerifyResponse = call(&service, "StreamVerify", frames(&parts)).unwrap();
+        assert!(streamed.passed, "{:?}", streamed.failure_reasons);
+        let unordered = call::<VerifyResponse>(&service, "StreamVerify", frames(&parts[1..]));
+        assert_eq!(unordered.unwrap_err(), "The first message must be the proof");
+        let huge = VerifyRequest { input: vec![0; 64 * 1024], ..request(b"out") };
+        assert!(call::<VerifyResponse>(&service, "Verify", frames(&[huge])).is_err());
+
+        let busy = (service.pool.admit(None).unwrap(), service.pool.admit(None).unwrap());
+        let refused = call::<VerifyResponse>(&service, "Verify", frames(&[request(b"out")]));
+        assert_eq!(refused.unwrap_err(), "Too many verifications in progress, retry later");
+        drop(busy);
+
+        let metrics = metrics.render();
+        assert!(metrics.contains("onnx_verify_verifications_total{outcome=\"passed\"} 3\n"));
+        assert!(metrics.contains("onnx_verify_verifications_total{outcome=\"rejected\"} 3\n"));
+        assert!(metrics.contains("onnx_verify_verifications_total{outcome=\"unavailable\"} 1\n"));
+        assert!(metrics.contains("onnx_verify_request_duration_seconds_count{transport=\"grpc\"} 6\n"));
+    }
+}
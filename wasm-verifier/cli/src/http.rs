@@ -0,0 +1,112 @@
+// Outgoing HTTP
+// JSON POSTs for webhooks and Rekor through ureq, over rustls with the `tls` feature
+
+use std::time::Duration;
+
+use ureq::http::Uri;
+use ureq::Agent;
+
+/// Timeout of each request, from connecting to reading the whole response
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest response body read
+const MAX_RESPONSE_BYTES: u64 = 1024 * 1024;
+
+/// Where requests go
+#[derive(Debug)]
+pub(crate) struct Endpoint {
+    url: String,
+    agent: Agent,
+}
+
+impl Endpoint {
+    /// `http://host[:port]/path`, or `https://` with the `tls` feature; `what` names the URL in errors
+    pub(crate) fn parse(url: &str, what: &str) -> Result<Endpoint, String> {
+        let invalid = || format!("Invalid {} URL '{}'", what, url);
+        let uri: Uri = url.parse().map_err(|_| invalid())?;
+        // A host, no userinfo, and a port only if it's a number
+        let authority = uri.authority().filter(|a| {
+            let port = &a.as_str()[a.host().len()..];
+            !a.host().is_empty() && port.strip_prefix(':').map_or(port.is_empty(), |p| p.parse::<u16>().is_ok())
+        });
+        match (uri.scheme_str(), authority) {
+            (Some("http"), Some(_)) => {}
+            (Some("https"), Some(_)) if cfg!(feature = "tls") => {}
+            (Some("https"), Some(_)) => {
+                return Err(format!("https {} URLs need onnx-verify built with the `tls` feature", what))
+            }
+            _ => return Err(invalid()),
+        }
+        // Redirects are answers like any other: a signed POST isn't re-sent somewhere else
+        let config = Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .http_status_as_error(false)
+            .max_redirects(0)
+            .max_redirects_will_error(false)
+            .user_agent(concat!("onnx-verify/", env!("CARGO_PKG_VERSION")))
+            .build();
+        Ok(Endpoint { url: url.to_string(), agent: config.into() })
+    }
+
+    /// The endpoint at `path` under this one's URL
+    pub(crate) fn join(&self, path: &str) -> Endpoint {
+        let url = format!("{}/{}", self.url.trim_end_matches('/'), path.trim_start_matches('/'));
+        Endpoint { url, agent: self.agent.clone() }
+    }
+
+    /// POST JSON `body` with extra `headers`, returning the response status and body
+    pub(crate) fn post(&self, headers: &[(&str, String)], body: &[u8]) -> Result<(u16, Vec<u8>), ureq::Error> {
+        let mut request = self.agent.post(&self.url).header("Content-Type", "application/json");
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let mut response = request.send(body)?;
+        let body = response.body_mut().with_config().limit(MAX_RESPONSE_BYTES).read_to_vec()?;
+        Ok((response.status().as_u16(), body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_endpoints() {
+        let url = |url: &str| Endpoint::parse(url, "webhook").map(|e| e.url);
+        assert_eq!(url("http://hooks.local:8080/v1?x=1").unwrap(), "http://hooks.local:8080/v1?x=1");
+        assert!(url("http://[::1]").is_ok());
+        assert_eq!(url("ftp://hooks.local").unwrap_err(), "Invalid webhook URL 'ftp://hooks.local'");
+        assert!(url("hooks.local/v1").is_err());
+        assert!(url("http://user@hooks.local").is_err());
+        assert!(url("http://hooks.local:port").is_err());
+        #[cfg(feature = "tls")]
+        assert!(url("https://hooks.example.com/in").is_ok());
+        #[cfg(not(feature = "tls"))]
+        assert!(url("https://hooks.example.com/in").unwrap_err().contains("`tls` feature"));
+
+        let rekor = Endpoint::parse("http://rekor.local/", "Rekor").unwrap();
+        assert_eq!(rekor.join("api/v1/log/entries").url, "http://rekor.local/api/v1/log/entries");
+    }
+
+    #[test]
+    fn test_post_reads_chunked_answers() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = Endpoint::parse(&format!("http://{}/in", listener.local_addr().unwrap()), "webhook").unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let answer = "HTTP/1.1 202 Accepted\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nWiki\r\n6\r\npedia \r\n0\r\n\r\n";
+            (&stream).write_all(answer.as_bytes()).unwrap();
+        });
+        // The empty body leaves nothing for the server to read: only the answer matters here
+        let (status, body) = endpoint.post(&[], b"").unwrap();
+        assert_eq!((status, body.as_slice()), (202, &b"Wikipedia "[..]));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,125 @@
+// Proof inspection
+// `inspect`: every field of a proof in any encoding, with its hash algorithms, signers, age and schema issues
+
+use serde_json::Value;
+
+use zkml_verifier_core::{clock::system_now_ms, describe::{describe, ProofDescription}};
+
+use crate::{read, Format};
+
+/// Arguments of `onnx-verify inspect`
+#[derive(Debug, PartialEq)]
+pub(crate) struct InspectArgs {
+    pub(crate) proof: String,
+    pub(crate) format: Format,
+}
+
+pub(crate) fn run_inspect(args: &InspectArgs) -> Result<ProofDescription, String> {
+    describe(&read(&args.proof)?, system_now_ms()).map_err(|(_, reason)| format!("{}: {}", args.proof, reason))
+}
+
+/// `3d 4h`, `5m 3s`, `250 ms`
+fn duration(ms: f64) -> String {
+    let secs = (ms / 1000.0) as u64;
+    match secs {
+        0 => format!("{} ms", ms as u64),
+        1..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+        3600..=86_399 => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {}h", secs / 86_400, secs % 86_400 / 3600),
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "-".to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// A heading, then one line per row with the columns padded to line up
+fn section(title: &str, rows: Vec<Vec<String>>) -> String {
+    if rows.is_empty() {
+        return format!("{}\n  none", title);
+    }
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let width = |i| rows.iter().filter_map(|row: &Vec<String>| row.get(i)).map(String::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns - 1).map(width).collect();
+    let lines = rows.iter().map(|row| {
+        let cells = row.iter().enumerate().map(|(i, cell)| match widths.get(i) {
+            Some(width) if i + 1 < row.len() => format!("{:<width$}", cell, width = width),
+            _ => cell.clone(),
+        });
+        format!("  {}", cells.collect::<Vec<_>>().join("  "))
+    });
+    format!("{}\n{}", title, lines.collect::<Vec<_>>().join("\n"))
+}
+
+/// Encoding, id and age, then every proof field, commitment, signer and issue
+pub(crate) fn render(description: &ProofDescription) -> String {
+    let json: Value = serde_json::from_str(&description.to_json()).unwrap_or_default();
+    let format = match description.compression() {
+        Some(compression) => format!("{} ({})", description.format(), compression),
+        None => description.format().to_string(),
+    };
+    let age = match description.age_ms() {
+        age if age < 0.0 => format!("{} in the future", duration(-age)),
+        age => format!("{} old", duration(age)),
+    };
+    let summary = [("Format", format), ("Proof id", description.proof_id().to_string()), ("Age", age)];
+    let summary = section("Proof", summary.into_iter().map(|(k, v)| vec![k.to_string(), v]).collect());
+
+    let fields = json["proof"].as_object().into_iter().flatten().map(|(field, value)| vec![field.clone(), text(value)]);
+    let commitments = json["commitments"].as_array().into_iter().flatten().map(|c| {
+        let algorithm = c["algorithm"].as_str().unwrap_or("default");
+        vec![text(&c["field"]), algorithm.to_string(), text(&c["value"])]
+    });
+    let signers = json["signers"].as_array().into_iter().flatten().map(|s| {
+        let algorithm = s["algorithm"].as_str().unwrap_or("-");
+        vec![text(&s["source"]), algorithm.to_string(), text(&s["identity"])]
+    });
+    let issues = description.issues().iter().map(|issue| vec![issue.clone()]);
+    [
+        summary,
+        section("Fields", fields.collect()),
+        section("Commitments", commitments.collect()),
+        section("Signers", signers.collect()),
+        section("Issues", issues.collect()),
+    ]
+    .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::{hash::hash_bytes, HashAlgorithm, ProofData};
+
+    #[test]
+    fn test_inspect() {
+        let path = std::env::temp_dir().join(format!("onnx-verify-inspect-{}.bin", std::process::id()));
+        let mut proof = ProofData::new(
+            "0xab".to_string(),
+            "0xcd".to_string(),
+            hash_bytes(b"in", HashAlgorithm::default()).unwrap(),
+            hash_bytes(b"out", HashAlgorithm::default()).unwrap(),
+            system_now_ms() - 90_000,
+            true,
+        );
+        proof.kid = Some("prover-1".to_string());
+        std::fs::write(&path, proof.to_binary()).unwrap();
+        let args = InspectArgs { proof: path.to_string_lossy().into_owned(), format: Format::Table };
+
+        let rendered = render(&run_inspect(&args).unwrap());
+        assert!(rendered.starts_with("Proof\n  Format    binary\n"));
+        assert!(rendered.contains("  Age       1m 30s old\n"));
+        assert!(rendered.contains(&format!("  input_hash   default  {}", proof.input_hash)));
+        assert!(rendered.contains("Signers\n  kid  ed25519  prover-1"));
+        assert!(rendered.contains("signature: the proof names a signer but carries no signature"));
+
+        std::fs::write(&path, b"junk").unwrap();
+        assert!(run_inspect(&args).unwrap_err().ends_with("Not a JSON, binary, CBOR, protobuf, JWS or COSE proof"));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((duration(250.0), duration(90_061_000.0)), ("250 ms".to_string(), "1d 1h".to_string()));
+    }
+}
@@ -0,0 +1,779 @@
+// onnx-verify
+// Command-line proof verification on the native build of the verifier, for CI and scripts
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::IsTerminal;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::util::SubscriberInitExt;
+use zkml_verifier_core::{ProofData, VerificationError, VerificationReport, Verifier, VerifierPolicy};
+
+mod batch;
+mod glob;
+mod http;
+mod inspect;
+#[cfg(feature = "grpc")]
+mod grpc;
+#[cfg(feature = "server")]
+mod metrics;
+#[cfg(feature = "server")]
+mod pool;
+mod rekor;
+#[cfg(feature = "server")]
+mod server;
+mod watch;
+mod webhook;
+
+use batch::BatchArgs;
+use inspect::InspectArgs;
+use rekor::Rekor;
+use watch::WatchArgs;
+use webhook::Webhook;
+
+const USAGE: &str = "\
+Usage: onnx-verify verify --proof <proof.json> --input <file> --output <file>
+                          (--model <model.onnx> | --model-hash <hash>) [--config <config.json>] [--format <format>]
+                          [--webhook <url>] [--rekor <url>] [--rekor-entry <entry.json> --rekor-key <key.pem>]
+       onnx-verify verify-batch <pattern> --manifest <manifest.json>
+                          (--model <model.onnx> | --model-hash <hash>) [--config <config.json>] [--format <format>]
+                          [--webhook <url>]
+       onnx-verify serve (--model <model.onnx> | --model-hash <hash>) [--config <config.json>] [--listen <addr>]
+                          [--grpc <addr>] [--workers <n>] [--max-queue <n>] [--queue-timeout <seconds>]
+                          [--max-request-bytes <bytes>] [--max-inflight-bytes <bytes>] [--webhook <url>] [--rekor <url>]
+       onnx-verify inspect <proof> [--format <format>]
+       onnx-verify watch <dir> --report <report.jsonl> (--model <model.onnx> | --model-hash <hash>)
+                          [--config <config.json>] [--interval <seconds>] [--format <format>] [--webhook <url>]
+
+Options:
+  --proof <file>        Proof JSON
+  --input <file>        Input bytes the proof commits to
+  --output <file>       Output bytes the proof commits to
+  --model <file>        ONNX model the proofs must be for
+  --model-hash <hash>   Expected model hash, instead of --model
+  --config <file>       Verifier policy as `VerifierPolicy` JSON, or TOML if the file ends in .toml: `VerifierConfig`
+                        fields, models, trusted_signers, trusted_attestors, hash_algorithms and required_checks
+                        (missing fields keep their defaults). If it lists models, leave out --model/--model-hash
+  --manifest <file>     JSON object mapping each proof to its files, paths relative to the manifest:
+                        { \"proofs/a.json\": { \"input\": \"io/a.in\", \"output\": \"io/a.out\" } }
+  --format <format>     table (default), json, or ndjson (one compact object per proof, bundle or report);
+                        verify-batch also takes junit, and watch only table or ndjson
+  --json, --junit       Shorthands for --format json and --format junit
+  -q, --quiet           Print nothing but errors (on stderr): the exit code tells the outcome
+  --listen <addr>       Address `serve` listens on (default 127.0.0.1:8080); needs the `server` feature
+                        (POST /verify, GET /healthz, and GET /metrics for Prometheus)
+  --grpc <addr>         Also serve gRPC on <addr>, e.g. 127.0.0.1:50051; needs the `grpc` feature
+  --workers <n>         Verifications `serve` runs at once, one per thread (default: one per core)
+  --max-queue <n>       Requests `serve` admits beyond those running (default 64); more get 503 / RESOURCE_EXHAUSTED
+  --queue-timeout <s>   How long an admitted request waits for a thread before failing (default 30)
+  --max-request-bytes <bytes>
+                        Largest request `serve` reads, proof and I/O together (default 32 MiB); StreamVerify
+                        input isn't limited, as it's hashed as it arrives
+  --max-inflight-bytes <bytes>
+                        Memory `serve` lets admitted requests hold together (default 256 MiB), reserving twice a
+                        request's Content-Length, or twice --max-request-bytes if it has none; at least twice
+                        --max-request-bytes
+  --report <file>       JSON-lines file `watch` appends a result to per bundle; bundles already in it are skipped
+  --interval <seconds>  How often `watch` looks for new bundles (default 2)
+  --webhook <url>       POST each result as JSON to <url> (verify-batch: one summary per run), signed in the
+                        X-Onnx-Verify-Signature-256 header with HMAC-SHA256 keyed by ONNX_VERIFY_WEBHOOK_SECRET;
+                        https needs the `tls` feature. Failed deliveries are reported but don't change the
+                        exit code
+  --rekor <url>         Log a receipt (see issue_receipt) of each passed verification in the Rekor transparency
+                        log at <url>, e.g. https://rekor.sigstore.dev (https needs the `tls` feature). Receipts are
+                        signed with the Ed25519 secret key, in hex, in ONNX_VERIFY_ISSUER_KEY. Failures are reported
+                        but don't change the exit code
+  --rekor-entry <file>  The proof's entry in a Rekor log, as GET /api/v1/log/entries/<uuid> returns it: the proof
+                        fails unless the entry logs its signing bytes, signed by a trusted signer (--config
+                        trusted_signers, or the key its kid or prover DID resolves to), and is included in the log
+  --rekor-key <file>    Public key of that log (PEM, as GET /api/v1/log/publicKey returns it)
+
+<pattern> is a path glob such as 'proofs/**/*.json' (quote it so the shell leaves it alone).
+`inspect` decodes a proof in any supported encoding (JSON, binary, CBOR, protobuf, JWS, COSE; optionally
+gzip/zstd) and prints its fields, hash algorithms, signers, age and schema issues without verifying it.
+`watch` verifies each bundle (a subdirectory of <dir> with proof.json, input.bin and output.bin) once its
+files stop changing, and runs until stopped.
+Set ONNX_VERIFY_LOG to a level (error, warn, info, debug, trace) to log checks to stderr: failures at warn,
+passes at debug.
+
+Exit codes:
+  0  Every proof verified (inspect: the proof has no schema issues)
+  1  A proof failed for a reason without a code below, or batch proofs failed for different reasons
+  2  Usage or I/O error, or a proof that couldn't be read
+  3  Model mismatch
+  4  Expired
+  5  Input or output doesn't match its commitment
+  6  Signature, signer, attestor, certificate or revocation check failed
+  7  Malformed proof
+  8  Proof not valid: not verified, or a proof hash, composite or inclusion mismatch
+  9  Nonce mismatch or replay";
+
+/// Exit code for usage and I/O errors
+const EXIT_ERROR: u8 = 2;
+
+/// Where the expected model comes from
+#[derive(Debug, PartialEq)]
+enum Model {
+    File(String),
+    Hash(String),
+    /// The `models` of the `--config` policy
+    Policy,
+}
+
+/// How a command prints its results
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Table,
+    Json,
+    /// One compact JSON object per line: per proof for batches, per bundle for `watch`
+    Ndjson,
+    /// JUnit XML, for `verify-batch`
+    Junit,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "table" => Some(Format::Table),
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            "junit" => Some(Format::Junit),
+            _ => None,
+        }
+    }
+}
+
+/// Arguments of `onnx-verify verify`
+#[derive(Debug, PartialEq)]
+struct Args {
+    proof: String,
+    input: String,
+    output: String,
+    model: Model,
+    config: Option<String>,
+    format: Format,
+    webhook: Option<String>,
+    rekor: Option<String>,
+    /// `--rekor-entry` and `--rekor-key`
+    rekor_entry: Option<(String, String)>,
+}
+
+/// Arguments of `onnx-verify serve`
+#[derive(Debug, PartialEq)]
+struct ServeArgs {
+    listen: String,
+    grpc: Option<String>,
+    model: Model,
+    config: Option<String>,
+    limits: Limits,
+    webhook: Option<String>,
+    rekor: Option<String>,
+}
+
+/// How much work `serve` takes on at once, so bursts of large requests can't exhaust memory
+///
+/// Requests are admitted up to `workers + queue`, and while their bodies fit
+/// in `max_inflight_bytes`; the rest are refused right away rather than
+/// buffered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Limits {
+    /// Verifier threads, so verifications running at once
+    workers: usize,
+    /// Admitted requests waiting for a thread
+    queue: usize,
+    /// How long an admitted request waits for a thread
+    queue_timeout: Duration,
+    /// Largest request body or gRPC message, proof and I/O together
+    max_request_bytes: usize,
+    /// Bytes all admitted requests may hold at once, bodies and decoded I/O together
+    max_inflight_bytes: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            workers: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            queue: 64,
+            queue_timeout: Duration::from_secs(30),
+            max_request_bytes: 32 * 1024 * 1024,
+            max_inflight_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Verify(Args),
+    Batch(BatchArgs),
+    Serve(ServeArgs),
+    Inspect(InspectArgs),
+    Watch(WatchArgs),
+}
+
+/// What follows the command: `--name value` options, `--json`-style switches and bare arguments
+#[derive(Default)]
+struct Options {
+    values: BTreeMap<String, String>,
+    switches: BTreeSet<String>,
+    positional: Vec<String>,
+}
+
+const SWITCHES: [&str; 2] = ["--json", "--junit"];
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if SWITCHES.contains(&arg.as_str()) {
+                options.switches.insert(arg);
+            } else if arg.starts_with("--") {
+                let value = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+                options.values.insert(arg, value);
+            } else {
+                options.positional.push(arg);
+            }
+        }
+        Ok(options)
+    }
+
+    fn take(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+
+    fn require(&mut self, name: &str) -> Result<String, String> {
+        self.take(name).ok_or_else(|| format!("Missing {}", name))
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        self.take(name).map(|value| value.parse().map_err(|_| format!("Invalid {} '{}'", name, value))).transpose()
+    }
+
+    fn switch(&mut self, name: &str) -> bool {
+        self.switches.remove(name)
+    }
+
+    fn model(&mut self) -> Result<Model, String> {
+        match (self.take("--model"), self.take("--model-hash")) {
+            (Some(path), None) => Ok(Model::File(path)),
+            (None, Some(hash)) => Ok(Model::Hash(hash)),
+            (Some(_), Some(_)) => Err("Give either --model or --model-hash, not both".to_string()),
+            (None, None) if self.values.contains_key("--config") => Ok(Model::Policy),
+            (None, None) => Err("Missing --model or --model-hash".to_string()),
+        }
+    }
+
+    /// `--format`, or its `--json`/`--junit` shorthand, if `command` can print it
+    fn format(&mut self, command: &str, allowed: &[Format]) -> Result<Format, String> {
+        let shorthands = [("--json", Format::Json), ("--junit", Format::Junit)];
+        let mut given: Vec<Format> = shorthands.into_iter().filter(|(s, _)| self.switch(s)).map(|(_, f)| f).collect();
+        if let Some(name) = self.take("--format") {
+            given.push(Format::from_name(&name).ok_or_else(|| format!("Unknown format '{}'", name))?);
+        }
+        match given[..] {
+            [] => Ok(Format::Table),
+            [format] if allowed.contains(&format) => Ok(format),
+            [format] => Err(format!("{} can't print {:?}", command, format).to_lowercase()),
+            _ => Err("Give only one of --format, --json and --junit".to_string()),
+        }
+    }
+
+    /// Fail on anything the command didn't use
+    fn finish(self) -> Result<(), String> {
+        match self.values.into_keys().chain(self.switches).next() {
+            Some(option) => Err(format!("Unknown option '{}'", option)),
+            None => match self.positional.first() {
+                Some(arg) => Err(format!("Unexpected argument '{}'", arg)),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let command = args.next().ok_or("Missing command")?;
+    let mut options = Options::parse(args)?;
+    let documents = [Format::Table, Format::Json, Format::Ndjson];
+    let parsed = match command.as_str() {
+        "verify" => {
+            let model = options.model()?;
+            Command::Verify(Args {
+                proof: options.require("--proof")?,
+                input: options.require("--input")?,
+                output: options.require("--output")?,
+                model,
+                config: options.take("--config"),
+                format: options.format(&command, &documents)?,
+                webhook: options.take("--webhook"),
+                rekor: options.take("--rekor"),
+                rekor_entry: match (options.take("--rekor-entry"), options.take("--rekor-key")) {
+                    (Some(entry), Some(key)) => Some((entry, key)),
+                    (None, None) => None,
+                    _ => return Err("Give --rekor-entry and --rekor-key together".to_string()),
+                },
+            })
+        }
+        "verify-batch" => {
+            if options.positional.is_empty() {
+                return Err("Missing <pattern>".to_string());
+            }
+            Command::Batch(BatchArgs {
+                pattern: options.positional.remove(0),
+                manifest: options.require("--manifest")?,
+                model: options.model()?,
+                config: options.take("--config"),
+                format: options.format(&command, &[Format::Table, Format::Json, Format::Ndjson, Format::Junit])?,
+                webhook: options.take("--webhook"),
+            })
+        }
+        "serve" => {
+            let defaults = Limits::default();
+            let limits = Limits {
+                workers: options.number("--workers")?.unwrap_or(defaults.workers).max(1),
+                queue: options.number("--max-queue")?.unwrap_or(defaults.queue),
+                queue_timeout: options.number("--queue-timeout")?.map_or(defaults.queue_timeout, Duration::from_secs),
+                max_request_bytes: options.number("--max-request-bytes")?.unwrap_or(defaults.max_request_bytes),
+                max_inflight_bytes: options.number("--max-inflight-bytes")?.unwrap_or(defaults.max_inflight_bytes),
+            };
+            Command::Serve(ServeArgs {
+                listen: options.take("--listen").unwrap_or_else(|| "127.0.0.1:8080".to_string()),
+                grpc: options.take("--grpc"),
+                model: options.model()?,
+                config: options.take("--config"),
+                limits,
+                webhook: options.take("--webhook"),
+                rekor: options.take("--rekor"),
+            })
+        }
+        "inspect" => {
+            if options.positional.is_empty() {
+                return Err("Missing <proof>".to_string());
+            }
+            let format = options.format(&command, &documents)?;
+            Command::Inspect(InspectArgs { proof: options.positional.remove(0), format })
+        }
+        "watch" => {
+            if options.positional.is_empty() {
+                return Err("Missing <dir>".to_string());
+            }
+            let interval = options.number("--interval")?.unwrap_or(2);
+            Command::Watch(WatchArgs {
+                dir: options.positional.remove(0),
+                report: options.require("--report")?,
+                model: options.model()?,
+                config: options.take("--config"),
+                interval: Duration::from_secs(interval),
+                format: options.format(&command, &[Format::Table, Format::Ndjson])?,
+                webhook: options.take("--webhook"),
+            })
+        }
+        command => return Err(format!("Unknown command '{}'", command)),
+    };
+    options.finish()?;
+    Ok(parsed)
+}
+
+fn read(path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path, e))
+}
+
+fn read_string(path: &str) -> Result<String, String> {
+    String::from_utf8(read(path)?).map_err(|_| format!("{} is not UTF-8", path))
+}
+
+/// The expected model as read from disk
+#[derive(Debug, Clone)]
+enum Expected {
+    Hash(String),
+    Model { path: String, bytes: Vec<u8> },
+    /// The policy's `models`
+    Policy,
+}
+
+/// Policy JSON, or TOML for `.toml` files
+fn load_policy(path: &str) -> Result<VerifierPolicy, String> {
+    let text = read_string(path)?;
+    let value: serde_json::Value = if path.ends_with(".toml") {
+        toml::from_str(&text).map_err(|e| format!("Invalid config {}: {}", path, e))?
+    } else {
+        serde_json::from_str(&text).map_err(|e| format!("Invalid config {}: {}", path, e))?
+    };
+    VerifierPolicy::try_from_value(value).map_err(|e| format!("Invalid config {}: {}", path, e))
+}
+
+/// Read the model and policy a verifier is built from
+fn load(model: &Model, config: &Option<String>) -> Result<(Expected, VerifierPolicy), String> {
+    let policy = match config {
+        Some(path) => load_policy(path)?,
+        None => VerifierPolicy::default(),
+    };
+    let expected = match (model, policy.models().is_empty()) {
+        (Model::Policy, true) => return Err("Missing --model or --model-hash, or models in the policy".to_string()),
+        (Model::Policy, false) => Expected::Policy,
+        (_, false) => return Err("Give either --model/--model-hash or models in the policy, not both".to_string()),
+        (Model::Hash(hash), true) => Expected::Hash(hash.clone()),
+        (Model::File(path), true) => Expected::Model { path: path.clone(), bytes: read(path)? },
+    };
+    Ok((expected, policy))
+}
+
+fn build(expected: &Expected, policy: &VerifierPolicy) -> Result<Verifier, String> {
+    let mut verifier = match expected {
+        Expected::Hash(hash) => Verifier::new(hash.clone()),
+        Expected::Model { path, bytes } => {
+            Verifier::try_from_model_bytes(bytes, &policy.config()).map_err(|e| match e {
+                VerificationError::InvalidModel => format!("{} is not an ONNX model", path),
+                e => format!("Cannot hash {}: {}", path, e),
+            })?
+        }
+        Expected::Policy => Verifier::try_from_policy(policy).map_err(|e| format!("Invalid policy: {}", e))?,
+    };
+    verifier.set_policy(policy);
+    Ok(verifier)
+}
+
+fn verifier(model: &Model, config: &Option<String>) -> Result<Verifier, String> {
+    let (expected, config) = load(model, config)?;
+    build(&expected, &config)
+}
+
+fn read_proof(path: &str) -> Result<ProofData, String> {
+    serde_json::from_str(&read_string(path)?).map_err(|e| format!("Invalid proof {}: {}", path, e))
+}
+
+/// Verify, logging a receipt in Rekor if it passed and `rekor` is given
+fn run(args: &Args, proof: &ProofData, rekor: Option<&Rekor>, quiet: bool) -> Result<VerificationReport, String> {
+    let (input, output) = (read(&args.input)?, read(&args.output)?);
+    let mut verifier = verifier(&args.model, &args.config)?;
+    if let Some(rekor) = rekor {
+        rekor.sign_with(&mut verifier);
+    }
+    let report = verifier.verify_report(proof, &input, &output);
+    if let Some((rekor, receipt)) = rekor.and_then(|rekor| Some((rekor, rekor.receipt(&verifier, proof, &report)?))) {
+        rekor.log(&receipt, quiet);
+    }
+    Ok(report)
+}
+
+/// Check `--rekor-entry`: `Err` if it can't be read, `Ok(Some(error))` if it doesn't show the proof is logged
+fn check_rekor_entry(
+    args: &Args,
+    proof: &ProofData,
+    (entry, key): &(String, String),
+) -> Result<Option<VerificationError>, String> {
+    let verifier = verifier(&args.model, &args.config)?;
+    match verifier.verify_rekor_proof(proof, &read_string(entry)?, &read_string(key)?) {
+        Ok(_) => Ok(None),
+        Err((VerificationError::InvalidArgument, _)) => Err(format!("{} is not a P-256 public key", key)),
+        Err((error, _)) => Ok(Some(error)),
+    }
+}
+
+/// One line per check, then the verdict
+fn render(report: &VerificationReport) -> String {
+    let mut lines: Vec<String> = report
+        .checks()
+        .iter()
+        .map(|c| {
+            let name = serde_json::to_value(c.check()).ok().and_then(|v| v.as_str().map(str::to_string));
+            let name = name.unwrap_or_default();
+            let status = if c.passed() { "PASS" } else { "FAIL" };
+            let detail = c.reason().map_or_else(|| format!("{:.2} ms", c.duration_ms()), str::to_string);
+            format!("{}  {:<20} {}", status, name, detail)
+        })
+        .collect();
+    lines.push(match report.error() {
+        None if report.passed() => {
+            format!("Proof verified ({} checks, {:.2} ms)", report.checks().len(), report.total_ms())
+        }
+        error => format!("Proof failed: {}", error.unwrap_or(VerificationError::NotVerified)),
+    });
+    lines.join("\n")
+}
+
+/// The report JSON with `passed` and `error` (the first failed check's kind) added
+fn report_json(report: &VerificationReport) -> serde_json::Value {
+    let mut json = serde_json::to_value(report).unwrap_or_default();
+    json["passed"] = report.passed().into();
+    json["error"] = report.error().map(|e| e.name()).into();
+    json
+}
+
+/// Print a JSON document pretty for `--format json`, on one line for `ndjson`
+fn json_text(json: &serde_json::Value, format: Format) -> String {
+    match format {
+        Format::Json => serde_json::to_string_pretty(json).unwrap_or_default(),
+        _ => json.to_string(),
+    }
+}
+
+/// `--webhook`, if given, with its secret from the environment
+fn webhook(url: &Option<String>) -> Result<Option<Webhook>, String> {
+    url.as_deref().map(Webhook::from_env).transpose()
+}
+
+/// `--rekor`, if given, with its signing key from the environment
+fn rekor(url: &Option<String>) -> Result<Option<Rekor>, String> {
+    url.as_deref().map(Rekor::from_env).transpose()
+}
+
+/// Exit code for a proof that failed with `error` (see "Exit codes" in USAGE)
+fn failure_code(error: VerificationError) -> u8 {
+    use VerificationError::*;
+    match error {
+        ModelMismatch => 3,
+        Expired => 4,
+        InputHashMismatch | OutputHashMismatch => 5,
+        SignatureInvalid | UntrustedSigner | AggregateSignatureInvalid | InsufficientAttestors
+        | CertificateChainInvalid | DidUnresolvable | Revoked => 6,
+        MalformedProof | UnsupportedHashAlgorithm | PayloadTooLarge => 7,
+        NotVerified | ProofHashMismatch | CompositeInvalid | InclusionInvalid => 8,
+        NonceMismatch | NonceReplayed => 9,
+        _ => 1,
+    }
+}
+
+fn report_code(report: &VerificationReport) -> u8 {
+    match report.error() {
+        None if report.passed() => 0,
+        error => failure_code(error.unwrap_or(VerificationError::NotVerified)),
+    }
+}
+
+/// Subscriber printing the verifier's check spans up to `level`, colored if `ansi`
+fn logger<W>(level: &str, writer: W, ansi: bool) -> Result<impl SubscriberInitExt, String>
+where
+    W: for<'a> tracing_subscriber::fmt::MakeWriter<'a> + Send + Sync + 'static,
+{
+    let level: LevelFilter = level.parse().map_err(|_| format!("Unknown ONNX_VERIFY_LOG level '{}'", level))?;
+    Ok(tracing_subscriber::fmt().with_writer(writer).with_max_level(level).with_ansi(ansi).finish())
+}
+
+fn main() -> ExitCode {
+    let (quiet, args): (Vec<String>, Vec<String>) =
+        std::env::args().skip(1).partition(|arg| arg == "--quiet" || arg == "-q");
+    let quiet = !quiet.is_empty();
+    if let Ok(level) = std::env::var("ONNX_VERIFY_LOG") {
+        match logger(&level, std::io::stderr, std::io::stderr().is_terminal()) {
+            Ok(logger) => logger.init(),
+            Err(message) => {
+                eprintln!("onnx-verify: {}", message);
+                return ExitCode::from(EXIT_ERROR);
+            }
+        }
+    }
+    let command = match parse_args(args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("onnx-verify: {}\n\n{}", message, USAGE);
+            return ExitCode::from(EXIT_ERROR);
+        }
+    };
+    let print = |text: String| {
+        if !quiet {
+            println!("{}", text);
+        }
+    };
+    let outcome = match &command {
+        Command::Verify(args) => webhook(&args.webhook).and_then(|webhook| {
+            let rekor = rekor(&args.rekor)?;
+            let proof = read_proof(&args.proof)?;
+            if let Some(entry) = &args.rekor_entry {
+                if let Some(error) = check_rekor_entry(args, &proof, entry)? {
+                    eprintln!("onnx-verify: {} is not in the Rekor log: {}", args.proof, error);
+                    return Ok(failure_code(error));
+                }
+            }
+            let report = run(args, &proof, rekor.as_ref(), quiet)?;
+            print(match args.format {
+                Format::Table => render(&report),
+                format => json_text(&report_json(&report), format),
+            });
+            if let Some(webhook) = webhook {
+                webhook.deliver("verification.completed", &args.proof, report_json(&report));
+            }
+            Ok(report_code(&report))
+        }),
+        Command::Batch(args) => webhook(&args.webhook).and_then(|webhook| {
+            let report = batch::run_batch(args)?;
+            print(report.render(args.format));
+            if let Some(webhook) = webhook {
+                webhook.deliver("batch.completed", &args.pattern, report.summary());
+            }
+            Ok(report.exit_code())
+        }),
+        #[cfg(feature = "server")]
+        Command::Serve(args) => server::serve(args).map(|()| 0),
+        #[cfg(not(feature = "server"))]
+        Command::Serve(_) => Err("serve needs onnx-verify built with the `server` feature".to_string()),
+        Command::Inspect(args) => inspect::run_inspect(args).map(|description| {
+            print(match args.format {
+                Format::Table => inspect::render(&description),
+                format => json_text(&serde_json::from_str(&description.to_json()).unwrap_or_default(), format),
+            });
+            u8::from(!description.issues().is_empty())
+        }),
+        Command::Watch(args) => watch::run_watch(args, &print).map(|()| 0),
+    };
+    match outcome {
+        Ok(code) => ExitCode::from(code),
+        Err(message) => {
+            eprintln!("onnx-verify: {}", message);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+
+    fn command(line: &str) -> Result<Command, String> {
+        parse_args(line.split_whitespace().map(str::to_string))
+    }
+
+    /// Log lines captured by a test subscriber
+    struct Log(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Log {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn args(line: &str) -> Result<Args, String> {
+        match command(line)? {
+            Command::Verify(args) => Ok(args),
+            _ => panic!("not a verify command"),
+        }
+    }
+
+    fn verify(args: &Args) -> Result<VerificationReport, String> {
+        run(args, &read_proof(&args.proof)?, None, true)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args("verify --proof p.json --input in.bin --output out.bin --model m.onnx --json").unwrap();
+        assert_eq!(parsed.model, Model::File("m.onnx".to_string()));
+        assert_eq!(parsed.format, Format::Json);
+        let parsed = args("verify --proof p --input i --output o --model-hash 0xab").unwrap();
+        assert_eq!(parsed.model, Model::Hash("0xab".into()));
+        let parsed = args("verify --proof p --input i --output o --model m --webhook http://hooks.local/in").unwrap();
+        assert_eq!(parsed.webhook.as_deref(), Some("http://hooks.local/in"));
+        let parsed = args("verify --proof p --input i --output o --model m --rekor-entry e.json --rekor-key k.pem");
+        assert_eq!(parsed.unwrap().rekor_entry, Some(("e.json".to_string(), "k.pem".to_string())));
+        let unpaired = args("verify --proof p --input i --output o --model m --rekor-entry e.json").unwrap_err();
+        assert_eq!(unpaired, "Give --rekor-entry and --rekor-key together");
+
+        assert_eq!(args("check").unwrap_err(), "Unknown command 'check'");
+        assert_eq!(args("verify --proof p --input i --output o").unwrap_err(), "Missing --model or --model-hash");
+        assert_eq!(args("verify --proof p --input i --output o --config c.toml").unwrap().model, Model::Policy);
+        assert_eq!(args("verify --input i --output o --model m").unwrap_err(), "Missing --proof");
+        assert_eq!(args("verify --proof").unwrap_err(), "--proof needs a value");
+        let unknown = args("verify --proof p --input i --output o --model m --manifest x").unwrap_err();
+        assert_eq!(unknown, "Unknown option '--manifest'");
+        let format = |f: &str| args(&format!("verify --proof p --input i --output o --model m --format {}", f));
+        assert_eq!(format("ndjson").unwrap().format, Format::Ndjson);
+        assert_eq!(format("junit").unwrap_err(), "verify can't print junit");
+        assert_eq!(format("yaml").unwrap_err(), "Unknown format 'yaml'");
+        assert_eq!(format("json --json").unwrap_err(), "Give only one of --format, --json and --junit");
+
+        let line = "verify-batch proofs/**/*.json --manifest m.json --model-hash 0xab --junit";
+        let Command::Batch(batch) = command(line).unwrap() else {
+            panic!("not a batch command");
+        };
+        assert_eq!((batch.pattern.as_str(), batch.format), ("proofs/**/*.json", Format::Junit));
+        assert_eq!(command("verify-batch --manifest m.json --model m").unwrap_err(), "Missing <pattern>");
+
+        let Command::Serve(serve) = command("serve --model-hash 0xab").unwrap() else {
+            panic!("not a serve command");
+        };
+        assert_eq!((serve.listen.as_str(), serve.model), ("127.0.0.1:8080", Model::Hash("0xab".into())));
+        assert_eq!(serve.grpc, None);
+        assert_eq!(serve.limits, Limits::default());
+        let Command::Serve(serve) = command("serve --model-hash 0xab --max-inflight-bytes 1024").unwrap() else {
+            panic!("not a serve command");
+        };
+        assert_eq!(serve.limits.max_inflight_bytes, 1024);
+        let Command::Serve(serve) = command("serve --model-hash 0xab --workers 4 --queue-timeout 5").unwrap() else {
+            panic!("not a serve command");
+        };
+        assert_eq!((serve.limits.workers, serve.limits.queue_timeout), (4, Duration::from_secs(5)));
+        assert_eq!(command("serve --model-hash 0xab --max-queue -1").unwrap_err(), "Invalid --max-queue '-1'");
+
+        let Command::Inspect(inspect) = command("inspect p.cbor --json").unwrap() else {
+            panic!("not an inspect command");
+        };
+        assert_eq!((inspect.proof.as_str(), inspect.format), ("p.cbor", Format::Json));
+        assert_eq!(command("inspect --json").unwrap_err(), "Missing <proof>");
+
+        let line = "watch share --report r.jsonl --model-hash 0xab --interval 10";
+        let Command::Watch(watch) = command(line).unwrap() else {
+            panic!("not a watch command");
+        };
+        assert_eq!((watch.dir.as_str(), watch.interval.as_secs()), ("share", 10));
+        assert_eq!(command("watch share --model-hash 0xab").unwrap_err(), "Missing --report");
+        assert_eq!(command(&format!("{} --json", line)).unwrap_err(), "watch can't print json");
+    }
+
+    #[test]
+    fn test_verify_files() {
+        let dir = std::env::temp_dir().join(format!("onnx-verify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        let proof = fixtures::proof("0xmodel");
+        std::fs::write(path("proof.json"), proof.to_json()).unwrap();
+        std::fs::write(path("in.bin"), b"in").unwrap();
+        std::fs::write(path("out.bin"), b"out").unwrap();
+        std::fs::write(path("tampered.bin"), b"tampered").unwrap();
+        let line = |output: &str| {
+            let (proof, input, output) = (path("proof.json"), path("in.bin"), path(output));
+            let line = format!("verify --proof {} --input {} --output {} --model-hash 0xmodel", proof, input, output);
+            args(&line).unwrap()
+        };
+
+        let report = verify(&line("out.bin")).unwrap();
+        assert!(report.passed());
+        assert!(render(&report).ends_with(&format!("({} checks, {:.2} ms)", report.checks().len(), report.total_ms())));
+
+        let report = verify(&line("tampered.bin")).unwrap();
+        assert!(render(&report).contains(&format!("FAIL  {:<20} Output does not match output_hash", "output_hash")));
+        assert!(render(&report).ends_with("Proof failed: OutputHashMismatch (code 3)"));
+        assert_eq!(report_code(&report), 5);
+        let json = report_json(&report);
+        assert_eq!((&json["passed"], &json["error"]), (&false.into(), &"OutputHashMismatch".into()));
+        assert!(!json_text(&json, Format::Ndjson).contains('\n'));
+
+        assert!(verify(&line("missing.bin")).unwrap_err().starts_with("Cannot read"));
+
+        let policy = "required_checks = ['signature']\n[[models]]\nmodel_hash = '0xmodel'\nlabel = 'm'\nversion = '1'";
+        std::fs::write(path("policy.toml"), policy).unwrap();
+        let with_policy = |model: Model| Args { model, config: Some(path("policy.toml")), ..line("out.bin") };
+        let report = verify(&with_policy(Model::Policy)).unwrap();
+        assert_eq!((report.matched_model().unwrap().label(), report_code(&report)), ("m", 6));
+        let both = verify(&with_policy(Model::Hash("0xmodel".to_string()))).unwrap_err();
+        assert_eq!(both, "Give either --model/--model-hash or models in the policy, not both");
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let writer = log.clone();
+        let guard = logger("warn", move || Log(writer.clone()), false).unwrap().set_default();
+        verify(&line("tampered.bin")).unwrap();
+        drop(guard);
+        let log = String::from_utf8(log.lock().unwrap().clone()).unwrap();
+        assert!(log.contains("failed check=OutputHash error=\"OutputHashMismatch\""));
+        assert!(!log.contains("InputHash"), "passes log at debug");
+        assert!(logger("loud", std::io::stderr, false).is_err());
+
+        std::fs::write(path("policy.toml"), "max_age = 1").unwrap();
+        assert!(verify(&with_policy(Model::Policy)).unwrap_err().ends_with("Unknown policy field 'max_age'"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+// Server metrics
+// Prometheus counters and latency histograms for `serve`, shared by HTTP and gRPC and exposed at `GET /metrics`
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use zkml_verifier_core::{memory::memory_stats, Check, VerificationReport};
+
+/// Upper bounds of the latency buckets, in seconds
+const BUCKETS: [f64; 12] = [0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 10.0];
+
+/// `outcome` label values, always exported so dashboards see zeros
+const OUTCOMES: [&str; 4] = ["passed", "failed", "rejected", "unavailable"];
+
+#[derive(Default)]
+struct Histogram {
+    /// Cumulative, per bucket of `BUCKETS`
+    buckets: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            *bucket += u64::from(seconds <= bound);
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+
+    /// `_bucket`, `_sum` and `_count` series, with `label` (`name="value"`) if not empty
+    fn render(&self, out: &mut String, name: &str, label: &str) {
+        let le = |bound: &str| match label {
+            "" => format!("{{le=\"{}\"}}", bound),
+            label => format!("{{{},le=\"{}\"}}", label, bound),
+        };
+        for (bucket, bound) in self.buckets.iter().zip(BUCKETS) {
+            let _ = writeln!(out, "{}_bucket{} {}", name, le(&bound.to_string()), bucket);
+        }
+        let label = if label.is_empty() { String::new() } else { format!("{{{}}}", label) };
+        let _ = writeln!(out, "{}_bucket{} {}", name, le("+Inf"), self.count);
+        let _ = writeln!(out, "{}_sum{} {}", name, label, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, label, self.count);
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    outcomes: BTreeMap<&'static str, u64>,
+    check_failures: BTreeMap<String, u64>,
+    checks: BTreeMap<String, Histogram>,
+    /// Per transport, `http` or `grpc`
+    requests: BTreeMap<&'static str, Histogram>,
+    hash_seconds: f64,
+}
+
+/// What `GET /metrics` reports, since the server started
+#[derive(Default)]
+pub(crate) struct Metrics {
+    counters: Mutex<Counters>,
+}
+
+fn check_name(check: Check) -> String {
+    serde_json::to_value(check).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+impl Metrics {
+    fn update(&self, update: impl FnOnce(&mut Counters)) {
+        update(&mut self.counters.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    }
+
+    /// Count a verification and time its checks
+    pub(crate) fn record(&self, report: &VerificationReport) {
+        self.update(|counters| {
+            *counters.outcomes.entry(if report.passed() { "passed" } else { "failed" }).or_default() += 1;
+            for result in report.checks() {
+                let (check, seconds) = (result.check(), result.duration_ms() / 1000.0);
+                if !result.passed() {
+                    *counters.check_failures.entry(check_name(check)).or_default() += 1;
+                }
+                if matches!(check, Check::InputHash | Check::OutputHash) {
+                    counters.hash_seconds += seconds;
+                }
+                counters.checks.entry(check_name(check)).or_default().observe(seconds);
+            }
+        });
+    }
+
+    /// Count a request that couldn't be verified at all (malformed, unsupported, ...)
+    pub(crate) fn reject(&self) {
+        self.update(|counters| *counters.outcomes.entry("rejected").or_default() += 1);
+    }
+
+    /// Count a request refused for lack of a verifier thread (queue full or timed out)
+    pub(crate) fn unavailable(&self) {
+        self.update(|counters| *counters.outcomes.entry("unavailable").or_default() += 1);
+    }
+
+    /// Time a request end to end, queueing for a verifier thread included
+    pub(crate) fn observe_request(&self, transport: &'static str, elapsed: Duration) {
+        self.update(|counters| counters.requests.entry(transport).or_default().observe(elapsed.as_secs_f64()));
+    }
+
+    /// Prometheus text exposition format
+    pub(crate) fn render(&self) -> String {
+        let counters = self.counters.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut out = String::new();
+        let header = |out: &mut String, name: &str, kind: &str, help: &str| {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} {}", name, help, name, kind);
+        };
+
+        let name = "onnx_verify_verifications_total";
+        let help = "Verification requests by outcome: rejected ones were invalid, unavailable ones refused under load";
+        header(&mut out, name, "counter", help);
+        for outcome in OUTCOMES {
+            let count = counters.outcomes.get(outcome).copied().unwrap_or(0);
+            let _ = writeln!(out, "{}{{outcome=\"{}\"}} {}", name, outcome, count);
+        }
+
+        let name = "onnx_verify_check_failures_total";
+        header(&mut out, name, "counter", "Failed checks by check");
+        for (check, count) in &counters.check_failures {
+            let _ = writeln!(out, "{}{{check=\"{}\"}} {}", name, check, count);
+        }
+
+        let name = "onnx_verify_check_duration_seconds";
+        header(&mut out, name, "histogram", "Time spent in each check");
+        for (check, histogram) in &counters.checks {
+            histogram.render(&mut out, name, &format!("check=\"{}\"", check));
+        }
+
+        let name = "onnx_verify_request_duration_seconds";
+        header(&mut out, name, "histogram", "Verification request latency by transport, queueing included");
+        for (transport, histogram) in &counters.requests {
+            histogram.render(&mut out, name, &format!("transport=\"{}\"", transport));
+        }
+
+        let name = "onnx_verify_hashed_bytes_total";
+        header(&mut out, name, "counter", "Bytes hashed by the verifier: I/O, proofs and models");
+        let _ = writeln!(out, "{} {}", name, memory_stats().bytes_hashed());
+
+        let name = "onnx_verify_hash_seconds_total";
+        header(&mut out, name, "counter", "Time spent in the input and output hash checks");
+        let _ = writeln!(out, "{} {}", name, counters.hash_seconds);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+    use zkml_verifier_core::Verifier;
+
+    #[test]
+    fn test_metrics() {
+        let proof = fixtures::proof("0xmodel");
+        let verifier = Verifier::new("0xmodel".to_string());
+        let metrics = Metrics::default();
+        metrics.record(&verifier.verify_report(&proof, b"in", b"out"));
+        metrics.record(&verifier.verify_report(&proof, b"in", b"tampered"));
+        metrics.reject();
+        metrics.observe_request("http", Duration::from_millis(3));
+
+        let text = metrics.render();
+        assert!(text.contains("onnx_verify_verifications_total{outcome=\"passed\"} 1\n"));
+        assert!(text.contains("onnx_verify_verifications_total{outcome=\"rejected\"} 1\n"));
+        assert!(text.contains("onnx_verify_check_failures_total{check=\"output_hash\"} 1\n"));
+        assert!(text.contains("onnx_verify_check_duration_seconds_count{check=\"input_hash\"} 2\n"));
+        assert!(text.contains("onnx_verify_request_duration_seconds_bucket{transport=\"http\",le=\"0.001\"} 0\n"));
+        assert!(text.contains("onnx_verify_request_duration_seconds_bucket{transport=\"http\",le=\"0.005\"} 1\n"));
+        assert!(text.contains("# TYPE onnx_verify_hashed_bytes_total counter\n"));
+    }
+}
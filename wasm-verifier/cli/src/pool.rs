@@ -0,0 +1,204 @@
+// Verifier threads
+// A fixed set of threads sharing one `Verifier`, used by the HTTP and gRPC services
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+
+use zkml_verifier_core::{Verifier, VerifierPolicy};
+
+use crate::rekor::Rekor;
+use crate::{build, Expected, Limits};
+
+type Job = Box<dyn FnOnce(&Verifier) + Send>;
+
+/// Why a request got no verifier thread
+#[derive(Debug, PartialEq)]
+pub(crate) enum Refused {
+    /// `workers + queue` requests are already admitted
+    Busy,
+    /// No thread freed up within the queue timeout
+    TimedOut,
+    /// The verifier threads stopped
+    Closed,
+}
+
+impl std::fmt::Display for Refused {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Refused::Busy => "Too many verifications in progress, retry later",
+            Refused::TimedOut => "Timed out waiting for a verifier thread",
+            Refused::Closed => "Verifier unavailable",
+        })
+    }
+}
+
+/// Granularity of the in-flight byte budget
+const BUDGET_UNIT: usize = 1024;
+
+/// Handle to the verifier threads
+///
+/// Verification is CPU-bound, so jobs run on these threads rather than the
+/// async runtime's and are answered over a oneshot channel.
+#[derive(Clone)]
+pub(crate) struct Pool {
+    jobs: mpsc::Sender<Job>,
+    /// `workers + queue` permits, one held per admitted request
+    slots: Arc<Semaphore>,
+    /// `max_inflight_bytes`, in `BUDGET_UNIT`s, shared by the admitted requests
+    budget: Arc<Semaphore>,
+    limits: Limits,
+}
+
+/// An admitted request's queue slot and share of the byte budget, given back when dropped
+pub(crate) struct Admission {
+    _slot: OwnedSemaphorePermit,
+    _bytes: OwnedSemaphorePermit,
+}
+
+impl Pool {
+    /// Threads verifying against `expected`, signing receipts for `rekor` if given
+    pub(crate) fn start(
+        expected: &Expected,
+        policy: VerifierPolicy,
+        limits: Limits,
+        rekor: Option<Arc<Rekor>>,
+    ) -> Result<Pool, String> {
+        if limits.max_inflight_bytes < 2 * limits.max_request_bytes {
+            return Err("--max-inflight-bytes must be at least twice --max-request-bytes".to_string());
+        }
+        // Fails before serving if the model can't be hashed
+        let mut verifier = build(expected, &policy)?;
+        if let Some(rekor) = rekor {
+            rekor.sign_with(&mut verifier);
+        }
+        let verifier = Arc::new(verifier);
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        let workers = limits.workers.max(1);
+        for _ in 0..workers {
+            let (queue, verifier) = (queue.clone(), verifier.clone());
+            std::thread::spawn(move || {
+                loop {
+                    let Ok(job) = queue.lock().expect("worker panicked").recv() else {
+                        return;
+                    };
+                    job(&verifier);
+                }
+            });
+        }
+        let slots = Arc::new(Semaphore::new(workers.saturating_add(limits.queue).min(Semaphore::MAX_PERMITS)));
+        let budget = Arc::new(Semaphore::new((limits.max_inflight_bytes / BUDGET_UNIT).min(Semaphore::MAX_PERMITS)));
+        Ok(Pool { jobs, slots, budget, limits })
+    }
+
+    pub(crate) fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Admit a request, before its body is read, or refuse it if the queue or byte budget is full
+    ///
+    /// Reserves twice the request's size, for its body and the I/O decoded
+    /// from it: `declared` (its `Content-Length`) if known, else the most
+    /// `max_request_bytes` allows. So however many requests are admitted,
+    /// together they hold at most `max_inflight_bytes`. The request counts
+    /// against both limits until the admission is dropped.
+    pub(crate) fn admit(&self, declared: Option<u64>) -> Result<Admission, Refused> {
+        let size = declared.map_or(self.limits.max_request_bytes, |declared| {
+            usize::try_from(declared).unwrap_or(usize::MAX).min(self.limits.max_request_bytes)
+        });
+        let units = (2 * size).div_ceil(BUDGET_UNIT).max(1);
+        let units = u32::try_from(units).map_err(|_| Refused::Busy)?;
+        let slot = self.slots.clone().try_acquire_owned().map_err(|_| Refused::Busy)?;
+        let bytes = self.budget.clone().try_acquire_many_owned(units).map_err(|_| Refused::Busy)?;
+        Ok(Admission { _slot: slot, _bytes: bytes })
+    }
+
+    /// Run `job` on the next free verifier thread, unless none frees up within the queue timeout
+    pub(crate) async fn run<T, F>(&self, job: F) -> Result<T, Refused>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Verifier) -> T + Send + 'static,
+    {
+        let (reply, mut result) = oneshot::channel();
+        // Claimed by the thread starting the job or by the timeout, whichever comes first
+        let claimed = Arc::new(AtomicBool::new(false));
+        let claim = claimed.clone();
+        let job: Job = Box::new(move |verifier| {
+            if !claim.swap(true, Ordering::AcqRel) {
+                let _ = reply.send(job(verifier));
+            }
+        });
+        self.jobs.send(job).map_err(|_| Refused::Closed)?;
+        match tokio::time::timeout(self.limits.queue_timeout, &mut result).await {
+            Ok(result) => result.map_err(|_| Refused::Closed),
+            Err(_) if !claimed.swap(true, Ordering::AcqRel) => Err(Refused::TimedOut),
+            // Started just in time: let it finish
+            Err(_) => result.await.map_err(|_| Refused::Closed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_limits() {
+        let limits = Limits { workers: 1, queue: 1, queue_timeout: Duration::from_millis(50), ..Limits::default() };
+        let expected = Expected::Hash("0xmodel".to_string());
+        let pool = Pool::start(&expected, VerifierPolicy::default(), limits, None).unwrap();
+        let (first, second) = (pool.admit(None).unwrap(), pool.admit(None).unwrap());
+        assert_eq!(pool.admit(None).err(), Some(Refused::Busy));
+        drop(second);
+        assert!(pool.admit(None).is_ok());
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (slow, queued) = runtime.block_on(async {
+            let slow = pool.run(|_| std::thread::sleep(Duration::from_millis(200)));
+            tokio::join!(slow, pool.run(|_| ()))
+        });
+        assert_eq!((slow, queued), (Ok(()), Err(Refused::TimedOut)));
+        assert_eq!(runtime.block_on(pool.run(|_| "next")), Ok("next"));
+        drop(first);
+    }
+
+    #[test]
+    fn test_inflight_bytes() {
+        let limits = Limits {
+            workers: 4,
+            queue: 60,
+            max_request_bytes: 64 * 1024,
+            max_inflight_bytes: 512 * 1024,
+            ..Limits::default()
+        };
+        let expected = Expected::Hash("0xmodel".to_string());
+        let pool = Pool::start(&expected, VerifierPolicy::default(), limits, None).unwrap();
+
+        // Unsized requests reserve for the largest body, so the budget binds before the slots do
+        let largest: Vec<_> = (0..4).map(|_| pool.admit(None).unwrap()).collect();
+        assert_eq!(pool.admit(None).err(), Some(Refused::Busy));
+        assert_eq!(pool.admit(Some(1)).err(), Some(Refused::Busy));
+        drop(largest);
+
+        // However the sizes mix, what's admitted stays within the budget
+        let sizes = [1, 100, 5_000, 64 * 1024, 10_000_000, 40_000, 0, 3_000];
+        let mut admitted = Vec::new();
+        let mut reserved = 0;
+        for &size in sizes.iter().cycle().take(200) {
+            if let Ok(admission) = pool.admit(Some(size)) {
+                reserved += (2 * (size as usize).min(64 * 1024)).div_ceil(BUDGET_UNIT).max(1) * BUDGET_UNIT;
+                admitted.push(admission);
+            }
+        }
+        assert!(admitted.len() > 4);
+        assert!(reserved <= 512 * 1024);
+        drop(admitted);
+        assert!(pool.admit(None).is_ok());
+
+        let small = Limits { max_inflight_bytes: 100 * 1024, ..limits };
+        let error = Pool::start(&expected, VerifierPolicy::default(), small, None).err().unwrap();
+        assert_eq!(error, "--max-inflight-bytes must be at least twice --max-request-bytes");
+    }
+}
@@ -0,0 +1,145 @@
+// Rekor publication
+// `--rekor <url>`: a signed receipt for each passed verification, logged in a Sigstore Rekor transparency log
+
+use zkml_verifier_core::{rekor::rekor_receipt_entry, ProofData, VerificationReport, Verifier};
+
+use crate::http::Endpoint;
+
+/// Environment variable holding the Ed25519 secret key (hex) receipts are signed with
+pub(crate) const ISSUER_KEY_VAR: &str = "ONNX_VERIFY_ISSUER_KEY";
+
+/// Entries API, under the log's URL
+const ENTRIES_PATH: &str = "api/v1/log/entries";
+
+/// Where a receipt was logged
+#[derive(Debug, PartialEq)]
+pub(crate) struct Logged {
+    pub(crate) uuid: String,
+    pub(crate) log_index: u64,
+}
+
+/// A Rekor instance, and the key receipts for it are signed with
+#[derive(Debug)]
+pub(crate) struct Rekor {
+    endpoint: Endpoint,
+    issuer_key: String,
+}
+
+impl Rekor {
+    /// Rekor at `url` (e.g. `https://rekor.sigstore.dev`, which needs the `tls` feature)
+    pub(crate) fn new(url: &str, issuer_key: &str) -> Result<Rekor, String> {
+        let endpoint = Endpoint::parse(url, "Rekor")?.join(ENTRIES_PATH);
+        if Verifier::new(String::new()).set_issuer_key(issuer_key).is_err() {
+            return Err(format!("{} must be an Ed25519 secret key, 32 bytes of hex", ISSUER_KEY_VAR));
+        }
+        Ok(Rekor { endpoint, issuer_key: issuer_key.to_string() })
+    }
+
+    /// `new`, signing with the key in `ONNX_VERIFY_ISSUER_KEY`
+    pub(crate) fn from_env(url: &str) -> Result<Rekor, String> {
+        match std::env::var(ISSUER_KEY_VAR) {
+            Ok(key) if !key.is_empty() => Rekor::new(url, key.trim()),
+            _ => Err(format!("--rekor needs {} set to the key receipts are signed with", ISSUER_KEY_VAR)),
+        }
+    }
+
+    /// Give `verifier` the receipt signing key
+    pub(crate) fn sign_with(&self, verifier: &mut Verifier) {
+        verifier.set_issuer_key(&self.issuer_key).expect("key checked in Rekor::new");
+    }
+
+    /// Log `receipt` (from `issue_receipt`) as a `rekord` entry
+    pub(crate) fn publish(&self, receipt: &str) -> Result<Logged, String> {
+        let entry = rekor_receipt_entry(receipt).map_err(|(_, reason)| format!("Cannot log receipt: {}", reason))?;
+        let (status, body) = self
+            .endpoint
+            .post(&[("Accept", "application/json".to_string())], entry.as_bytes())
+            .map_err(|e| format!("Cannot reach Rekor: {}", e))?;
+        if status != 201 {
+            let message = String::from_utf8_lossy(&body);
+            return Err(format!("Rekor answered {}: {}", status, message.trim()));
+        }
+        // `{ "<uuid>": { "logIndex": ..., "body": ..., "verification": ... } }`
+        let logged: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(&body).map_err(|_| "Rekor's answer is not a log entry".to_string())?;
+        let (uuid, entry) = logged.into_iter().next().ok_or("Rekor's answer is not a log entry")?;
+        let log_index = entry["logIndex"].as_u64().ok_or("Rekor's answer has no logIndex")?;
+        Ok(Logged { uuid, log_index })
+    }
+
+    /// Receipt for `report` from `verifier` (given the key by `sign_with`), if it passed
+    pub(crate) fn receipt(
+        &self,
+        verifier: &Verifier,
+        proof: &ProofData,
+        report: &VerificationReport,
+    ) -> Option<String> {
+        if !report.passed() {
+            return None;
+        }
+        let receipt = verifier.issue_receipt(proof, report);
+        receipt.map_err(|(_, reason)| eprintln!("onnx-verify: Cannot issue receipt: {}", reason)).ok()
+    }
+
+    /// `publish`, reporting the outcome on stderr (only failures if `quiet`)
+    ///
+    /// The verification outcome stands either way.
+    pub(crate) fn log(&self, receipt: &str, quiet: bool) {
+        match self.publish(receipt) {
+            Ok(logged) if !quiet => {
+                eprintln!("onnx-verify: receipt logged in Rekor at index {} (entry {})", logged.log_index, logged.uuid)
+            }
+            Ok(_) => {}
+            Err(message) => eprintln!("onnx-verify: {}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+    use std::io::{BufRead, Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_rekor() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let log = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let (mut request_line, mut length) = (String::new(), 0);
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                match line.trim().split_once(": ") {
+                    Some((name, value)) if name.eq_ignore_ascii_case("Content-Length") => length = value.parse().unwrap(),
+                    _ if line.trim().is_empty() => break,
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let answer = r#"{"24296fb24b8ad77a":{"logIndex":12345,"integratedTime":1700000000}}"#;
+            write!(&stream, "HTTP/1.1 201 Created\r\nContent-Length: {}\r\n\r\n{}", answer.len(), answer).unwrap();
+            (request_line, serde_json::from_slice::<serde_json::Value>(&body).unwrap())
+        });
+
+        let rekor = Rekor::new(&url, &"04".repeat(32)).unwrap();
+        let mut verifier = Verifier::new("0xmodel".to_string());
+        rekor.sign_with(&mut verifier);
+        let proof = fixtures::proof("0xmodel");
+        let receipt = rekor.receipt(&verifier, &proof, &verifier.verify_report(&proof, b"in", b"out")).unwrap();
+        assert_eq!(rekor.receipt(&verifier, &proof, &verifier.verify_report(&proof, b"in", b"no")), None);
+        let logged = rekor.publish(&receipt).unwrap();
+        assert_eq!(logged, Logged { uuid: "24296fb24b8ad77a".to_string(), log_index: 12345 });
+        let (request_line, entry) = log.join().unwrap();
+        assert_eq!(request_line.trim(), "POST /api/v1/log/entries HTTP/1.1");
+        assert_eq!((&entry["kind"], &entry["spec"]["signature"]["format"]), (&"rekord".into(), &"x509".into()));
+
+        let error = Rekor::new(&url, "abc").unwrap_err();
+        assert_eq!(error, "ONNX_VERIFY_ISSUER_KEY must be an Ed25519 secret key, 32 bytes of hex");
+    }
+}
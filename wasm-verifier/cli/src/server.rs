@@ -0,0 +1,288 @@
+// HTTP verification service
+// `onnx-verify serve`: `POST /verify` with a proof and its I/O as multipart or JSON, `GET /healthz`, `GET /metrics`
+
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, FromRequest, Multipart, Request, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::Engine;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Instant;
+
+use zkml_verifier_core::{ProofData, VerificationReport};
+
+use crate::metrics::Metrics;
+use crate::pool::{Pool, Refused};
+use crate::rekor::Rekor;
+use crate::webhook::Webhook;
+use crate::{load, rekor, webhook, ServeArgs};
+
+/// Shared by the handlers
+#[derive(Clone)]
+struct AppState {
+    pool: Pool,
+    metrics: Arc<Metrics>,
+    webhook: Option<Arc<Webhook>>,
+    rekor: Option<Arc<Rekor>>,
+}
+
+/// A request that couldn't be verified, sent as `{"error": "..."}`
+#[derive(Debug)]
+struct Rejection(StatusCode, String);
+
+impl Rejection {
+    fn bad_request(message: impl Into<String>) -> Rejection {
+        Rejection(StatusCode::BAD_REQUEST, message.into())
+    }
+}
+
+impl From<Refused> for Rejection {
+    fn from(refused: Refused) -> Rejection {
+        Rejection(StatusCode::SERVICE_UNAVAILABLE, refused.to_string())
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+/// `POST /verify` JSON body; `proof` is the proof object or its JSON string, I/O is base64
+///
+/// I/O borrows from the body where it can, so only the decoded bytes are allocated.
+#[derive(Deserialize)]
+struct VerifyRequest<'a> {
+    proof: serde_json::Value,
+    #[serde(borrow)]
+    input: Cow<'a, str>,
+    #[serde(borrow)]
+    output: Cow<'a, str>,
+}
+
+fn parse_proof(proof: serde_json::Value) -> Result<ProofData, Rejection> {
+    match proof {
+        serde_json::Value::String(json) => serde_json::from_str(&json),
+        proof => serde_json::from_value(proof),
+    }
+    .map_err(|e| Rejection::bad_request(format!("Invalid proof: {}", e)))
+}
+
+fn from_json(body: &[u8]) -> Result<(ProofData, Vec<u8>, Vec<u8>), Rejection> {
+    let request: VerifyRequest =
+        serde_json::from_slice(body).map_err(|e| Rejection::bad_request(format!("Invalid request: {}", e)))?;
+    let decode = |field: &str, data: &str| {
+        let engine = base64::engine::general_purpose::STANDARD;
+        engine.decode(data).map_err(|e| Rejection::bad_request(format!("Invalid base64 in '{}': {}", field, e)))
+    };
+    Ok((parse_proof(request.proof)?, decode("input", &request.input)?, decode("output", &request.output)?))
+}
+
+/// Multipart fields `proof` (JSON), `input` and `output` (raw bytes)
+async fn from_multipart(mut multipart: Multipart) -> Result<(ProofData, Vec<u8>, Vec<u8>), Rejection> {
+    let (mut proof, mut input, mut output) = (None, None, None);
+    while let Some(field) = multipart.next_field().await.map_err(|e| Rejection::bad_request(e.body_text()))? {
+        let slot = match field.name() {
+            Some("proof") => &mut proof,
+            Some("input") => &mut input,
+            Some("output") => &mut output,
+            _ => continue,
+        };
+        *slot = Some(Vec::from(field.bytes().await.map_err(|e| Rejection::bad_request(e.body_text()))?));
+    }
+    let missing = |field: &str| Rejection::bad_request(format!("Missing field '{}'", field));
+    let proof = proof.ok_or_else(|| missing("proof"))?;
+    let proof = serde_json::from_slice(&proof).map_err(|e| Rejection::bad_request(format!("Invalid proof: {}", e)))?;
+    Ok((proof, input.ok_or_else(|| missing("input"))?, output.ok_or_else(|| missing("output"))?))
+}
+
+/// Verify a proof
+///
+/// 200 with `{"passed", "error", "report"}` whether or not the proof
+/// verified; 4xx with `{"error"}` if it couldn't be checked at all, 503 if
+/// the server is at its limits.
+async fn verify(State(state): State<AppState>, request: Request) -> Result<Json<serde_json::Value>, Rejection> {
+    let started = Instant::now();
+    let declared = request.headers().get(header::CONTENT_LENGTH).and_then(|v| v.to_str().ok()?.parse().ok());
+    let response = match state.pool.admit(declared) {
+        Ok(_admitted) => check(&state, request).await,
+        Err(refused) => Err(refused.into()),
+    };
+    match &response {
+        Err(Rejection(StatusCode::SERVICE_UNAVAILABLE, _)) => state.metrics.unavailable(),
+        Err(_) => state.metrics.reject(),
+        Ok(_) => {}
+    }
+    state.metrics.observe_request("http", started.elapsed());
+    response
+}
+
+async fn check(state: &AppState, request: Request) -> Result<Json<serde_json::Value>, Rejection> {
+    let content_type = request.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()).unwrap_or("");
+    let (proof, input, output) = if content_type.starts_with("multipart/form-data") {
+        let multipart = Multipart::from_request(request, &()).await.map_err(|e| Rejection::bad_request(e.body_text()))?;
+        from_multipart(multipart).await?
+    } else if content_type.starts_with("application/json") {
+        let body = Bytes::from_request(request, &()).await.map_err(|e| Rejection(e.status(), e.body_text()))?;
+        from_json(&body)?
+    } else {
+        let message = "Send multipart/form-data or application/json";
+        return Err(Rejection(StatusCode::UNSUPPORTED_MEDIA_TYPE, message.to_string()));
+    };
+    let (metrics, rekor) = (state.metrics.clone(), state.rekor.clone());
+    let response = state.pool.run(move |verifier| {
+        let report = verifier.verify_report(&proof, &input, &output);
+        metrics.record(&report);
+        (result_json(&report), rekor.and_then(|rekor| rekor.receipt(verifier, &proof, &report)))
+    });
+    let (response, receipt) = response.await?;
+    notify(&state.webhook, "http", || response.clone());
+    publish(&state.rekor, receipt);
+    Ok(Json(response))
+}
+
+/// `POST /verify` response, and the `result` webhooks get from either service
+pub(crate) fn result_json(report: &VerificationReport) -> serde_json::Value {
+    serde_json::json!({
+        "passed": report.passed(),
+        "error": report.error().map(|e| e.name()),
+        "report": report,
+    })
+}
+
+/// Send a result to the webhook, if there is one, off the request path so a slow receiver can't hold up responses
+pub(crate) fn notify(webhook: &Option<Arc<Webhook>>, source: &'static str, result: impl FnOnce() -> serde_json::Value) {
+    if let Some(webhook) = webhook.clone() {
+        let result = result();
+        tokio::task::spawn_blocking(move || webhook.deliver("verification.completed", source, result));
+    }
+}
+
+/// Log a receipt in Rekor, off the request path like `notify`
+pub(crate) fn publish(rekor: &Option<Arc<Rekor>>, receipt: Option<String>) {
+    if let (Some(rekor), Some(receipt)) = (rekor.clone(), receipt) {
+        tokio::task::spawn_blocking(move || rekor.log(&receipt, false));
+    }
+}
+
+async fn healthz() -> Json<serde_json::Value> {
+    Json(serde_json::json!({ "status": "ok" }))
+}
+
+/// Prometheus text format
+async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], state.metrics.render())
+}
+
+fn router(pool: Pool, metrics: Arc<Metrics>, webhook: Option<Arc<Webhook>>, rekor: Option<Arc<Rekor>>) -> Router {
+    Router::new()
+        .route("/verify", post(verify))
+        .route("/healthz", get(healthz))
+        .route("/metrics", get(self::metrics))
+        .layer(DefaultBodyLimit::max(pool.limits().max_request_bytes))
+        .with_state(AppState { pool, metrics, webhook, rekor })
+}
+
+/// Serve until the process is stopped
+pub(crate) fn serve(args: &ServeArgs) -> Result<(), String> {
+    #[cfg(not(feature = "grpc"))]
+    if args.grpc.is_some() {
+        return Err("--grpc needs onnx-verify built with the `grpc` feature".to_string());
+    }
+    let webhook = webhook(&args.webhook)?.map(Arc::new);
+    let rekor = rekor(&args.rekor)?.map(Arc::new);
+    let (expected, policy) = load(&args.model, &args.config)?;
+    let pool = Pool::start(&expected, policy, args.limits, rekor.clone())?;
+    let metrics = Arc::new(Metrics::default());
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| format!("Cannot start runtime: {}", e))?;
+    runtime.block_on(async {
+        #[cfg(feature = "grpc")]
+        if let Some(grpc) = &args.grpc {
+            let router = router(pool.clone(), metrics.clone(), webhook.clone(), rekor.clone());
+            let grpc = crate::grpc::serve(grpc, pool, metrics, webhook, rekor);
+            return tokio::try_join!(serve_http(&args.listen, router), grpc).map(|_| ());
+        }
+        serve_http(&args.listen, router(pool, metrics, webhook, rekor)).await
+    })
+}
+
+async fn serve_http(listen: &str, router: Router) -> Result<(), String> {
+    let listener =
+        tokio::net::TcpListener::bind(listen).await.map_err(|e| format!("Cannot listen on {}: {}", listen, e))?;
+    eprintln!("onnx-verify listening on http://{}", listen);
+    axum::serve(listener, router).await.map_err(|e| format!("Server error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+    use axum::body::Body;
+    use tower::ServiceExt;
+    use crate::{Expected, Limits};
+    use zkml_verifier_core::VerifierPolicy;
+
+    fn send(router: &Router, request: Request) -> (StatusCode, Bytes) {
+        tokio::runtime::Runtime::new().unwrap().block_on(async {
+            let response = router.clone().oneshot(request).await.unwrap();
+            let status = response.status();
+            (status, axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap())
+        })
+    }
+
+    fn call(router: &Router, content_type: &str, body: Vec<u8>) -> (StatusCode, serde_json::Value) {
+        let request = Request::post("/verify").header(header::CONTENT_TYPE, content_type).body(Body::from(body));
+        let (status, body) = send(router, request.unwrap());
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn test_server() {
+        let limits = Limits { workers: 2, queue: 0, max_request_bytes: 4096, ..Limits::default() };
+        let expected = Expected::Hash("0xmodel".to_string());
+        let pool = Pool::start(&expected, VerifierPolicy::default(), limits, None).unwrap();
+        let router = router(pool.clone(), Arc::new(Metrics::default()), None, None);
+        let proof = fixtures::proof("0xmodel");
+
+        let body = serde_json::json!({ "proof": proof.to_json(), "input": "aW4=", "output": "b3V0" });
+        let (status, report) = call(&router, "application/json", body.to_string().into_bytes());
+        assert_eq!((status, &report["passed"]), (StatusCode::OK, &serde_json::Value::Bool(true)));
+
+        let multipart = |output: &str| {
+            let part = |name: &str, data: &str| {
+                format!("--b\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n", name, data)
+            };
+            format!("{}{}{}--b--\r\n", part("proof", &proof.to_json()), part("input", "in"), part("output", output))
+        };
+        let (status, report) = call(&router, "multipart/form-data; boundary=b", multipart("tampered").into_bytes());
+        assert_eq!((status, &report["passed"]), (StatusCode::OK, &serde_json::Value::Bool(false)));
+        assert_eq!(report["error"], "OutputHashMismatch");
+        assert!(report["report"]["checks"].is_array());
+
+        let body = serde_json::json!({ "proof": {}, "input": "", "output": "" });
+        let (status, error) = call(&router, "application/json", body.to_string().into_bytes());
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].as_str().unwrap().starts_with("Invalid proof"));
+        assert_eq!(call(&router, "text/plain", Vec::new()).0, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let huge = serde_json::json!({ "proof": proof.to_json(), "input": "A".repeat(4096), "output": "" });
+        assert_eq!(call(&router, "application/json", huge.to_string().into_bytes()).0, StatusCode::PAYLOAD_TOO_LARGE);
+
+        let busy = (pool.admit(None).unwrap(), pool.admit(None).unwrap());
+        let (status, error) = call(&router, "application/json", body.to_string().into_bytes());
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(error["error"], "Too many verifications in progress, retry later");
+        drop(busy);
+
+        let (status, metrics) = send(&router, Request::get("/metrics").body(Body::empty()).unwrap());
+        let metrics = String::from_utf8(metrics.to_vec()).unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert!(metrics.contains("onnx_verify_verifications_total{outcome=\"failed\"} 1\n"));
+        assert!(metrics.contains("onnx_verify_verifications_total{outcome=\"rejected\"} 3\n"));
+        assert!(metrics.contains("onnx_verify_verifications_total{outcome=\"unavailable\"} 1\n"));
+        assert!(metrics.contains("onnx_verify_request_duration_seconds_count{transport=\"http\"} 6\n"));
+    }
+}
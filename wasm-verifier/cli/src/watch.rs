@@ -0,0 +1,204 @@
+// Watch mode
+// `watch`: verifies proof bundles as they land in a directory and appends each result to a JSON-lines report
+
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use zkml_verifier_core::{clock::system_now_ms, Verifier};
+
+use crate::batch::{check, status_of, text_line, Outcome};
+use crate::{verifier, webhook, Format, Model};
+
+/// Files of a bundle: a subdirectory of the watched directory holding all three
+const BUNDLE_FILES: [&str; 3] = ["proof.json", "input.bin", "output.bin"];
+
+/// Arguments of `onnx-verify watch`
+#[derive(Debug, PartialEq)]
+pub(crate) struct WatchArgs {
+    pub(crate) dir: String,
+    pub(crate) report: String,
+    pub(crate) model: Model,
+    pub(crate) config: Option<String>,
+    pub(crate) interval: Duration,
+    /// `Table` prints a line per bundle, `Ndjson` its report entry
+    pub(crate) format: Format,
+    pub(crate) webhook: Option<String>,
+}
+
+/// Sizes and modification times of a bundle's files
+type Snapshot = Vec<(u64, Option<SystemTime>)>;
+
+/// Bundles seen so far
+struct Watcher {
+    dir: PathBuf,
+    report: PathBuf,
+    /// Bundles already in the report, never verified again
+    done: HashSet<String>,
+    /// Complete bundles waiting for their files to stop changing
+    settling: HashMap<String, Snapshot>,
+}
+
+/// Names of the bundles already recorded in `report`, so a restart doesn't verify them twice
+fn recorded(report: &Path) -> Result<HashSet<String>, String> {
+    let text = match std::fs::read_to_string(report) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(e) => return Err(format!("Cannot read {}: {}", report.display(), e)),
+    };
+    let entries = text.lines().filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok());
+    Ok(entries.filter_map(|entry| entry["bundle"].as_str().map(str::to_string)).collect())
+}
+
+fn snapshot(bundle: &Path) -> Option<Snapshot> {
+    let file = |name| std::fs::metadata(bundle.join(name)).ok().map(|m| (m.len(), m.modified().ok()));
+    BUNDLE_FILES.iter().map(file).collect()
+}
+
+impl Watcher {
+    fn new(dir: &str, report: &str) -> Result<Watcher, String> {
+        let (dir, report) = (PathBuf::from(dir), PathBuf::from(report));
+        if !dir.is_dir() {
+            return Err(format!("{} is not a directory", dir.display()));
+        }
+        Ok(Watcher { done: recorded(&report)?, dir, report, settling: HashMap::new() })
+    }
+
+    /// Bundles whose files are all there and unchanged since the last poll, by name
+    ///
+    /// Waiting a poll keeps half-copied bundles (common on network shares)
+    /// from being verified before they're complete.
+    fn ready(&mut self) -> Result<Vec<String>, String> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| format!("Cannot read {}: {}", self.dir.display(), e))?;
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| !self.done.contains(name))
+            .collect();
+        names.sort();
+
+        let mut ready = Vec::new();
+        for name in names {
+            let Some(snapshot) = snapshot(&self.dir.join(&name)) else {
+                continue;
+            };
+            match self.settling.insert(name.clone(), snapshot.clone()) {
+                Some(previous) if previous == snapshot => ready.push(name),
+                _ => {}
+            }
+        }
+        Ok(ready)
+    }
+
+    /// Verify the bundles that are ready and append them to the report, returning each with its entry
+    fn poll(&mut self, verifier: &Verifier) -> Result<Vec<(String, Outcome, serde_json::Value)>, String> {
+        let mut results = Vec::new();
+        for name in self.ready()? {
+            let bundle = self.dir.join(&name);
+            let started = Instant::now();
+            let io = (bundle.join(BUNDLE_FILES[1]), bundle.join(BUNDLE_FILES[2]));
+            let outcome = check(verifier, &bundle.join(BUNDLE_FILES[0]), Some(&io));
+            let entry = entry(&name, &outcome, started.elapsed().as_secs_f64() * 1000.0);
+            self.append(&entry)?;
+            self.settling.remove(&name);
+            self.done.insert(name.clone());
+            results.push((name, outcome, entry));
+        }
+        Ok(results)
+    }
+
+    /// One JSON line per bundle, written and flushed as soon as it's verified
+    fn append(&self, entry: &serde_json::Value) -> Result<(), String> {
+        let cannot = |e: std::io::Error| format!("Cannot write {}: {}", self.report.display(), e);
+        let mut report = OpenOptions::new().create(true).append(true).open(&self.report).map_err(cannot)?;
+        writeln!(report, "{}", entry).and_then(|()| report.sync_data()).map_err(cannot)
+    }
+}
+
+/// A bundle's report entry
+fn entry(bundle: &str, outcome: &Outcome, duration_ms: f64) -> serde_json::Value {
+    let (error, reasons) = match outcome {
+        Outcome::Passed => (None, Vec::new()),
+        Outcome::Failed { error, reasons } => (Some(error.name()), reasons.clone()),
+        Outcome::Error(message) => (None, vec![message.clone()]),
+    };
+    serde_json::json!({
+        "bundle": bundle,
+        "status": status_of(outcome),
+        "error": error,
+        "reasons": reasons,
+        "verified_at": system_now_ms(),
+        "duration_ms": duration_ms,
+    })
+}
+
+/// Verify bundles as they arrive, until the process is stopped, handing each result line to `print`
+pub(crate) fn run_watch(args: &WatchArgs, print: &dyn Fn(String)) -> Result<(), String> {
+    let webhook = webhook(&args.webhook)?;
+    let verifier = verifier(&args.model, &args.config)?;
+    let mut watcher = Watcher::new(&args.dir, &args.report)?;
+    eprintln!("onnx-verify watching {} ({} bundles already in {})", args.dir, watcher.done.len(), args.report);
+    loop {
+        for (name, outcome, entry) in watcher.poll(&verifier)? {
+            print(match args.format {
+                Format::Ndjson => entry.to_string(),
+                _ => text_line(&name, &outcome),
+            });
+            if let Some(webhook) = &webhook {
+                webhook.deliver("verification.completed", &name, entry);
+            }
+        }
+        std::thread::sleep(args.interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+
+    #[test]
+    fn test_watch() {
+        let dir = std::env::temp_dir().join(format!("onnx-verify-watch-{}", std::process::id()));
+        let report = dir.with_extension("jsonl");
+        let proof = fixtures::proof("0xmodel");
+        let bundle = |name: &str, output: &[u8]| {
+            std::fs::create_dir_all(dir.join(name)).unwrap();
+            std::fs::write(dir.join(name).join("proof.json"), proof.to_json()).unwrap();
+            std::fs::write(dir.join(name).join("input.bin"), b"in").unwrap();
+            std::fs::write(dir.join(name).join("output.bin"), output).unwrap();
+        };
+        bundle("a", b"out");
+        bundle("b", b"tampered");
+        std::fs::create_dir_all(dir.join("incomplete")).unwrap();
+
+        let verifier = Verifier::new("0xmodel".to_string());
+        let (dir_path, report_path) = (dir.to_string_lossy(), report.to_string_lossy());
+        let mut watcher = Watcher::new(&dir_path, &report_path).unwrap();
+        assert!(watcher.poll(&verifier).unwrap().is_empty(), "bundles settle for a poll first");
+        let statuses = |results: Vec<(String, Outcome, serde_json::Value)>| {
+            results.iter().map(|(name, _, entry)| format!("{} {}", name, entry["status"])).collect::<Vec<_>>()
+        };
+        assert_eq!(statuses(watcher.poll(&verifier).unwrap()), [r#"a "passed""#, r#"b "failed""#]);
+        assert!(watcher.poll(&verifier).unwrap().is_empty());
+
+        bundle("c", b"out");
+        let mut restarted = Watcher::new(&dir_path, &report_path).unwrap();
+        restarted.poll(&verifier).unwrap();
+        assert_eq!(statuses(restarted.poll(&verifier).unwrap()), [r#"c "passed""#]);
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&report)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!((&lines[1]["bundle"], &lines[1]["error"]), (&"b".into(), &"OutputHashMismatch".into()));
+        assert!(Watcher::new(&report_path, &report_path).is_err_and(|e| e.ends_with("is not a directory")));
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_file(&report).unwrap();
+    }
+}
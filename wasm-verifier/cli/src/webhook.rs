@@ -0,0 +1,136 @@
+// Webhooks
+// `--webhook <url>`: each verification result POSTed as HMAC-signed JSON, so downstream systems needn't poll
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use zkml_verifier_core::clock::system_now_ms;
+
+use crate::http::Endpoint;
+
+/// Environment variable holding the signing secret, kept off the command line
+pub(crate) const SECRET_VAR: &str = "ONNX_VERIFY_WEBHOOK_SECRET";
+
+/// `sha256=<hex HMAC-SHA256 of the body>`, as GitHub webhooks sign theirs
+const SIGNATURE_HEADER: &str = "X-Onnx-Verify-Signature-256";
+
+/// Tries per payload; connection errors, 429 and 5xx are retried, other answers aren't
+const ATTEMPTS: u32 = 3;
+
+/// Where results go, and the secret signing them
+#[derive(Debug)]
+pub(crate) struct Webhook {
+    endpoint: Endpoint,
+    secret: Vec<u8>,
+}
+
+impl Webhook {
+    /// Webhook at `url` (`http://host[:port]/path`, or `https://` with the `tls` feature)
+    pub(crate) fn new(url: &str, secret: &[u8]) -> Result<Webhook, String> {
+        Ok(Webhook { endpoint: Endpoint::parse(url, "webhook")?, secret: secret.to_vec() })
+    }
+
+    /// `new`, signing with the secret in `ONNX_VERIFY_WEBHOOK_SECRET`
+    pub(crate) fn from_env(url: &str) -> Result<Webhook, String> {
+        match std::env::var(SECRET_VAR) {
+            Ok(secret) if !secret.is_empty() => Webhook::new(url, secret.as_bytes()),
+            _ => Err(format!("--webhook needs {} set to the secret signing its payloads", SECRET_VAR)),
+        }
+    }
+
+    /// Value of the signature header for `body`
+    pub(crate) fn signature(&self, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC takes keys of any size");
+        mac.update(body);
+        let hex: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+        format!("sha256={}", hex)
+    }
+
+    /// POST `{"event", "source", "sent_at", "result"}`, retrying transient failures
+    pub(crate) fn notify(&self, event: &str, source: &str, result: serde_json::Value) -> Result<(), String> {
+        let sent_at = system_now_ms();
+        let payload = serde_json::json!({ "event": event, "source": source, "sent_at": sent_at, "result": result });
+        let body = payload.to_string();
+        for attempt in 1..=ATTEMPTS {
+            let headers = [(SIGNATURE_HEADER, self.signature(body.as_bytes()))];
+            let retry = match self.endpoint.post(&headers, body.as_bytes()) {
+                Ok((status, _)) if (200..300).contains(&status) => return Ok(()),
+                Ok((status, _)) if status == 429 || status >= 500 => format!("webhook answered {}", status),
+                Ok((status, _)) => return Err(format!("Webhook answered {}", status)),
+                Err(e) => format!("cannot reach webhook: {}", e),
+            };
+            if attempt == ATTEMPTS {
+                return Err(format!("Gave up after {} tries, {}", ATTEMPTS, retry));
+            }
+            std::thread::sleep(Duration::from_millis(500 << attempt));
+        }
+        Ok(())
+    }
+
+    /// `notify`, reporting a failed delivery on stderr: the verification outcome stands either way
+    pub(crate) fn deliver(&self, event: &str, source: &str, result: serde_json::Value) {
+        if let Err(message) = self.notify(event, source, result) {
+            eprintln!("onnx-verify: {}", message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, Read, Write};
+    use std::net::TcpListener;
+
+    /// Answer one request with `status`, handing back its headers and body
+    fn receiver(status: u16) -> (String, std::thread::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hooks/verified", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = std::io::BufReader::new(stream.try_clone().unwrap());
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                // Header names are case-insensitive: compare them lowercased
+                headers.push(match line.trim().split_once(": ") {
+                    Some((name, value)) => format!("{}: {}", name.to_ascii_lowercase(), value),
+                    None => line.trim().to_string(),
+                });
+            }
+            let length = headers.iter().find_map(|h| h.strip_prefix("content-length: ")).unwrap().parse().unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            write!(&stream, "HTTP/1.1 {} OK\r\nContent-Length: 0\r\n\r\n", status).unwrap();
+            (headers, String::from_utf8(body).unwrap())
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_webhook() {
+        let (url, received) = receiver(204);
+        let webhook = Webhook::new(&url, b"secret").unwrap();
+        webhook.notify("verification.completed", "proof.json", serde_json::json!({ "passed": true })).unwrap();
+        let (headers, body) = received.join().unwrap();
+        assert_eq!(headers[0], "POST /hooks/verified HTTP/1.1");
+        assert!(headers.contains(&format!("{}: {}", SIGNATURE_HEADER.to_ascii_lowercase(), webhook.signature(body.as_bytes()))));
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!((&payload["event"], &payload["result"]["passed"]), (&"verification.completed".into(), &true.into()));
+
+        // RFC 4231 test case 2
+        let known = Webhook::new("http://localhost", b"Jefe").unwrap();
+        let signature = known.signature(b"what do ya want for nothing?");
+        assert_eq!(signature, "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+
+        let (url, received) = receiver(404);
+        let refused = Webhook::new(&url, b"secret").unwrap().notify("verification.completed", "p", 1.into());
+        assert_eq!(refused.unwrap_err(), "Webhook answered 404");
+        received.join().unwrap();
+    }
+}
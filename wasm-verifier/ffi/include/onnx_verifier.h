@@ -0,0 +1,102 @@
+#ifndef ONNX_VERIFIER_H
+#define ONNX_VERIFIER_H
+
+/* Generated by cbindgen from ffi/src/lib.rs; do not edit */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// A verifier for one model
+//
+// onnx_verifier_verify may be called on one verifier from several threads
+// at once; configure it (onnx_verifier_add_trusted_signer) before sharing.
+typedef struct OnnxVerifier OnnxVerifier;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a verifier for proofs of the model with `model_hash`
+//
+// `config_json` is a `VerifierConfig` as JSON (missing fields keep their
+// defaults), or NULL for the default policy. Returns NULL on error; see
+// `onnx_verifier_last_error`. Free with `onnx_verifier_free`.
+//
+// # Safety
+// `model_hash` and `config_json` must be NULL or NUL-terminated strings.
+struct OnnxVerifier *onnx_verifier_new(const char *model_hash, const char *config_json);
+
+// Create a verifier for proofs of the ONNX model in `model`, hashing it canonically
+//
+// As `onnx_verifier_new`; also fails if `model` isn't an ONNX model.
+//
+// # Safety
+// `model` must point to `model_len` readable bytes; `config_json` must be
+// NULL or a NUL-terminated string.
+struct OnnxVerifier *onnx_verifier_from_model(const uint8_t *model,
+                                              size_t model_len,
+                                              const char *config_json);
+
+// Free a verifier; NULL is ignored
+//
+// # Safety
+// `verifier` must be NULL or come from `onnx_verifier_new` or
+// `onnx_verifier_from_model`, and not be used again.
+void onnx_verifier_free(struct OnnxVerifier *verifier);
+
+// Trust an Ed25519 key or Ethereum address (hex) as a prover signer
+//
+// Returns false (see `onnx_verifier_last_error`) on bad arguments.
+//
+// # Safety
+// `verifier` must be a live verifier; `pubkey` a NUL-terminated string.
+bool onnx_verifier_add_trusted_signer(struct OnnxVerifier *verifier, const char *pubkey);
+
+// Verify a proof (JSON, as `ProofData.to_json` writes it) against its input and output
+//
+// Returns 0 when every check passed, otherwise the `VerificationError`
+// code of the first failed check (see `onnx_verifier_error_name`).
+// `MalformedProof` and `InvalidArgument` mean nothing was checked; the
+// reason is in `onnx_verifier_last_error`.
+//
+// If `report_json` isn't NULL it receives the full report as JSON
+// (`VerificationReport.to_json`), to free with `onnx_verifier_string_free`,
+// or NULL when nothing was checked.
+//
+// # Safety
+// `verifier` must be a live verifier; `proof_json` a NUL-terminated
+// string; `input`/`output` must point to `input_len`/`output_len`
+// readable bytes; `report_json` must be NULL or writable.
+uint32_t onnx_verifier_verify(const struct OnnxVerifier *verifier,
+                              const char *proof_json,
+                              const uint8_t *input,
+                              size_t input_len,
+                              const uint8_t *output,
+                              size_t output_len,
+                              char **report_json);
+
+// Name of a `VerificationError` code, e.g. "OutputHashMismatch"; "Passed" for 0 and "Unknown" otherwise
+//
+// The string is static: don't free it.
+const char *onnx_verifier_error_name(uint32_t code);
+
+// Why the last call on this thread failed, or NULL if none has
+//
+// Valid until the next failing call on this thread; don't free it.
+const char *onnx_verifier_last_error(void);
+
+// Free a report from `onnx_verifier_verify`; NULL is ignored
+//
+// # Safety
+// `string` must be NULL or a `report_json` from `onnx_verifier_verify`, and not be used again.
+void onnx_verifier_string_free(char *string);
+
+// Version of this library, e.g. "0.1.0"
+const char *onnx_verifier_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ONNX_VERIFIER_H */
@@ -0,0 +1,275 @@
+// C bindings
+// `onnx_verifier_*` functions over the core `Verifier` for native apps; include/onnx_verifier.h is generated from here
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use std::sync::OnceLock;
+
+use zkml_verifier_core::{ProofData, VerificationError, Verifier, VerifierConfig};
+
+#[cfg(feature = "pyo3")]
+mod python;
+
+/// A verifier for one model
+///
+/// `onnx_verifier_verify` may be called on one verifier from several threads
+/// at once; configure it (`onnx_verifier_add_trusted_signer`) before sharing.
+pub struct OnnxVerifier {
+    verifier: Verifier,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Record `message` for `onnx_verifier_last_error` and return `value`
+fn fail<T>(message: impl Into<String>, value: T) -> T {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    value
+}
+
+/// Borrow a NUL-terminated UTF-8 string; `None` for a null pointer
+unsafe fn text<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, String> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr).to_str().map(Some).map_err(|_| format!("{} is not UTF-8", name))
+}
+
+/// Borrow `len` bytes at `ptr`, which may be null when `len` is 0
+unsafe fn bytes<'a>(ptr: *const u8, len: usize, name: &str) -> Result<&'a [u8], String> {
+    match (ptr.is_null(), len) {
+        (true, 0) => Ok(&[]),
+        (true, _) => Err(format!("{} is null", name)),
+        (false, _) => Ok(std::slice::from_raw_parts(ptr, len)),
+    }
+}
+
+fn config(config_json: Option<&str>) -> Result<VerifierConfig, String> {
+    config_json.map_or(Ok(VerifierConfig::default()), |json| {
+        serde_json::from_str(json).map_err(|e| format!("Invalid config JSON: {}", e))
+    })
+}
+
+fn boxed(verifier: Verifier) -> *mut OnnxVerifier {
+    Box::into_raw(Box::new(OnnxVerifier { verifier }))
+}
+
+/// Create a verifier for proofs of the model with `model_hash`
+///
+/// `config_json` is a `VerifierConfig` as JSON (missing fields keep their
+/// defaults), or NULL for the default policy. Returns NULL on error; see
+/// `onnx_verifier_last_error`. Free with `onnx_verifier_free`.
+///
+/// # Safety
+/// `model_hash` and `config_json` must be NULL or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn onnx_verifier_new(
+    model_hash: *const c_char,
+    config_json: *const c_char,
+) -> *mut OnnxVerifier {
+    let created = || -> Result<*mut OnnxVerifier, String> {
+        let model_hash = text(model_hash, "model_hash")?.ok_or("model_hash is null")?;
+        let config = config(text(config_json, "config_json")?)?;
+        Ok(boxed(Verifier::with_config(model_hash.to_string(), &config)))
+    };
+    created().unwrap_or_else(|message| fail(message, ptr::null_mut()))
+}
+
+/// Create a verifier for proofs of the ONNX model in `model`, hashing it canonically
+///
+/// As `onnx_verifier_new`; also fails if `model` isn't an ONNX model.
+///
+/// # Safety
+/// `model` must point to `model_len` readable bytes; `config_json` must be
+/// NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn onnx_verifier_from_model(
+    model: *const u8,
+    model_len: usize,
+    config_json: *const c_char,
+) -> *mut OnnxVerifier {
+    let created = || -> Result<*mut OnnxVerifier, String> {
+        let model = bytes(model, model_len, "model")?;
+        let config = config(text(config_json, "config_json")?)?;
+        let verifier = Verifier::try_from_model_bytes(model, &config).map_err(|e| match e {
+            VerificationError::InvalidModel => "model is not an ONNX model".to_string(),
+            e => format!("Cannot hash model: {}", e),
+        })?;
+        Ok(boxed(verifier))
+    };
+    created().unwrap_or_else(|message| fail(message, ptr::null_mut()))
+}
+
+/// Free a verifier; NULL is ignored
+///
+/// # Safety
+/// `verifier` must be NULL or come from `onnx_verifier_new` or
+/// `onnx_verifier_from_model`, and not be used again.
+#[no_mangle]
+pub unsafe extern "C" fn onnx_verifier_free(verifier: *mut OnnxVerifier) {
+    if !verifier.is_null() {
+        drop(Box::from_raw(verifier));
+    }
+}
+
+/// Trust an Ed25519 key or Ethereum address (hex) as a prover signer
+///
+/// Returns false (see `onnx_verifier_last_error`) on bad arguments.
+///
+/// # Safety
+/// `verifier` must be a live verifier; `pubkey` a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn onnx_verifier_add_trusted_signer(
+    verifier: *mut OnnxVerifier,
+    pubkey: *const c_char,
+) -> bool {
+    match (verifier.as_mut(), text(pubkey, "pubkey")) {
+        (Some(verifier), Ok(Some(pubkey))) => {
+            verifier.verifier.add_trusted_signer(pubkey.to_string());
+            true
+        }
+        (None, _) => fail("verifier is null", false),
+        (_, Err(message)) => fail(message, false),
+        (_, Ok(None)) => fail("pubkey is null", false),
+    }
+}
+
+/// Verify a proof (JSON, as `ProofData.to_json` writes it) against its input and output
+///
+/// Returns 0 when every check passed, otherwise the `VerificationError`
+/// code of the first failed check (see `onnx_verifier_error_name`).
+/// `MalformedProof` and `InvalidArgument` mean nothing was checked; the
+/// reason is in `onnx_verifier_last_error`.
+///
+/// If `report_json` isn't NULL it receives the full report as JSON
+/// (`VerificationReport.to_json`), to free with `onnx_verifier_string_free`,
+/// or NULL when nothing was checked.
+///
+/// # Safety
+/// `verifier` must be a live verifier; `proof_json` a NUL-terminated
+/// string; `input`/`output` must point to `input_len`/`output_len`
+/// readable bytes; `report_json` must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn onnx_verifier_verify(
+    verifier: *const OnnxVerifier,
+    proof_json: *const c_char,
+    input: *const u8,
+    input_len: usize,
+    output: *const u8,
+    output_len: usize,
+    report_json: *mut *mut c_char,
+) -> u32 {
+    if let Some(report_json) = report_json.as_mut() {
+        *report_json = ptr::null_mut();
+    }
+    let checked = || -> Result<u32, (VerificationError, String)> {
+        let invalid = |message: String| (VerificationError::InvalidArgument, message);
+        let verifier = &verifier.as_ref().ok_or_else(|| invalid("verifier is null".to_string()))?.verifier;
+        let proof_json = text(proof_json, "proof_json").map_err(invalid)?;
+        let proof_json = proof_json.ok_or_else(|| invalid("proof_json is null".to_string()))?;
+        let input = bytes(input, input_len, "input").map_err(invalid)?;
+        let output = bytes(output, output_len, "output").map_err(invalid)?;
+        let proof: ProofData = serde_json::from_str(proof_json)
+            .map_err(|e| (VerificationError::MalformedProof, format!("Failed to parse proof JSON: {}", e)))?;
+        let report = verifier.verify_report(&proof, input, output);
+        if let Some(report_json) = report_json.as_mut() {
+            *report_json = CString::new(report.to_json()).map_or(ptr::null_mut(), CString::into_raw);
+        }
+        Ok(match report.error() {
+            None if report.passed() => 0,
+            error => error.unwrap_or(VerificationError::NotVerified).code(),
+        })
+    };
+    checked().unwrap_or_else(|(error, message)| fail(message, error.code()))
+}
+
+/// Name of a `VerificationError` code, e.g. "OutputHashMismatch"; "Passed" for 0 and "Unknown" otherwise
+///
+/// The string is static: don't free it.
+#[no_mangle]
+pub extern "C" fn onnx_verifier_error_name(code: u32) -> *const c_char {
+    static NAMES: OnceLock<Vec<CString>> = OnceLock::new();
+    let names = NAMES.get_or_init(|| {
+        VerificationError::ALL.iter().map(|e| CString::new(e.name()).expect("names have no NUL")).collect()
+    });
+    match VerificationError::from_code(code) {
+        Some(error) => names[error.code() as usize - 1].as_ptr(),
+        None if code == 0 => c"Passed".as_ptr(),
+        None => c"Unknown".as_ptr(),
+    }
+}
+
+/// Why the last call on this thread failed, or NULL if none has
+///
+/// Valid until the next failing call on this thread; don't free it.
+#[no_mangle]
+pub extern "C" fn onnx_verifier_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Free a report from `onnx_verifier_verify`; NULL is ignored
+///
+/// # Safety
+/// `string` must be NULL or a `report_json` from `onnx_verifier_verify`, and not be used again.
+#[no_mangle]
+pub unsafe extern "C" fn onnx_verifier_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Version of this library, e.g. "0.1.0"
+#[no_mangle]
+pub extern "C" fn onnx_verifier_version() -> *const c_char {
+    static VERSION: OnceLock<CString> = OnceLock::new();
+    VERSION.get_or_init(|| CString::new(env!("CARGO_PKG_VERSION")).expect("version has no NUL")).as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zkml_verifier_core::fixtures;
+
+    fn string(ptr: *const c_char) -> String {
+        unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_c_api() {
+        let proof = fixtures::proof("0xmodel");
+        let proof = CString::new(proof.to_json()).unwrap();
+        let model_hash = CString::new("0xmodel").unwrap();
+        let verifier = unsafe { onnx_verifier_new(model_hash.as_ptr(), ptr::null()) };
+        assert!(!verifier.is_null());
+
+        let verify = |output: &[u8], report: *mut *mut c_char| unsafe {
+            onnx_verifier_verify(verifier, proof.as_ptr(), b"in".as_ptr(), 2, output.as_ptr(), output.len(), report)
+        };
+        assert_eq!(verify(b"out", ptr::null_mut()), 0);
+        let mut report = ptr::null_mut();
+        let code = verify(b"tampered", &mut report);
+        assert_eq!(string(onnx_verifier_error_name(code)), "OutputHashMismatch");
+        assert!(string(report).contains("\"checks\""));
+        unsafe { onnx_verifier_string_free(report) };
+
+        let raw = |proof: *const c_char, input: *const u8, input_len: usize, report: *mut *mut c_char| unsafe {
+            onnx_verifier_verify(verifier, proof, input, input_len, ptr::null(), 0, report)
+        };
+        let code = raw(c"{".as_ptr(), ptr::null(), 0, &mut report);
+        assert_eq!((code, report), (VerificationError::MalformedProof.code(), ptr::null_mut()));
+        assert!(string(onnx_verifier_last_error()).starts_with("Failed to parse proof JSON"));
+        let code = raw(proof.as_ptr(), ptr::null(), 2, &mut report);
+        assert_eq!(code, VerificationError::InvalidArgument.code());
+        assert_eq!(string(onnx_verifier_last_error()), "input is null");
+        unsafe { onnx_verifier_free(verifier) };
+
+        assert!(unsafe { onnx_verifier_new(model_hash.as_ptr(), c"{\"max_age_ms\": -1}".as_ptr()) }.is_null());
+        assert!(string(onnx_verifier_last_error()).starts_with("Invalid config JSON"));
+        assert!(unsafe { onnx_verifier_from_model(b"junk".as_ptr(), 4, ptr::null()) }.is_null());
+        assert_eq!(string(onnx_verifier_error_name(0)), "Passed");
+        assert_eq!(string(onnx_verifier_version()), env!("CARGO_PKG_VERSION"));
+    }
+}
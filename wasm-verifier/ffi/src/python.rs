@@ -0,0 +1,160 @@
+// Python bindings
+// `onnx_verifier` extension module (`pyo3` feature, built with maturin), mirroring the Node addon's API
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use zkml_verifier_core::{HashAlgorithm, ProofData, VerificationError as Kind, VerifierBuilder, VerifierConfig};
+
+create_exception!(
+    onnx_verifier,
+    VerificationError,
+    PyException,
+    "Raised for proofs and arguments that can't be checked; the message starts with the error kind"
+);
+
+fn error(kind: Kind, message: impl std::fmt::Display) -> PyErr {
+    VerificationError::new_err(format!("{}: {}", kind.name(), message))
+}
+
+/// JSON text of a `str`, or of a dict (or other JSON-serializable object) via `json.dumps`
+fn json_text(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    match value.extract::<String>() {
+        Ok(json) => Ok(json),
+        Err(_) => value.py().import("json")?.call_method1("dumps", (value,))?.extract(),
+    }
+}
+
+/// Verifier for proofs of one model
+///
+/// ```python
+/// verifier = onnx_verifier.Verifier(model_hash, config={"max_age_ms": 600_000})
+/// assert verifier.verify(json.load(open("proof.json")), input_bytes, output_bytes)
+/// ```
+///
+/// Holds the verifier's settings and builds a verifier per call, so calls
+/// run without the GIL and can go in parallel from Python threads.
+#[pyclass(module = "onnx_verifier")]
+pub struct Verifier {
+    builder: VerifierBuilder,
+}
+
+impl Verifier {
+    /// The report as a dict, with `passed` and `error` (kind of the first failed check, or None) added
+    fn report(&self, py: Python<'_>, proof: &Bound<'_, PyAny>, input: &[u8], output: &[u8]) -> PyResult<Py<PyAny>> {
+        let proof: ProofData = serde_json::from_str(&json_text(proof)?)
+            .map_err(|e| error(Kind::MalformedProof, format!("Failed to parse proof: {}", e)))?;
+        let builder = self.builder.clone();
+        let report = py.detach(move || {
+            let verifier = builder.try_build().map_err(|e| e.name())?;
+            let report = verifier.verify_report(&proof, input, output);
+            let mut json = serde_json::to_value(&report).unwrap_or_default();
+            json["passed"] = report.passed().into();
+            json["error"] = report.error().map(Kind::name).into();
+            Ok::<_, &str>(json.to_string())
+        });
+        let report = report.map_err(|kind| error(Kind::InvalidArgument, format!("Cannot build verifier ({})", kind)))?;
+        Ok(py.import("json")?.call_method1("loads", (report,))?.unbind())
+    }
+}
+
+#[pymethods]
+impl Verifier {
+    /// `config` is a `VerifierConfig` as a dict or JSON string; missing fields keep their defaults
+    #[new]
+    #[pyo3(signature = (model_hash, config = None))]
+    fn new(model_hash: String, config: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let config: VerifierConfig = match config {
+            Some(config) => serde_json::from_str(&json_text(config)?)
+                .map_err(|e| error(Kind::InvalidArgument, format!("Invalid config: {}", e)))?,
+            None => VerifierConfig::default(),
+        };
+        Ok(Verifier { builder: VerifierBuilder::new().config(&config).model_hash(model_hash) })
+    }
+
+    /// Trust an Ed25519 key or Ethereum address (hex)
+    fn add_trusted_signer(&mut self, pubkey: String) {
+        self.builder = std::mem::take(&mut self.builder).trusted_signer(pubkey);
+    }
+
+    /// Trust a BLS12-381 attestor public key (hex)
+    fn add_trusted_attestor(&mut self, pubkey: String) {
+        self.builder = std::mem::take(&mut self.builder).trusted_attestor(pubkey);
+    }
+
+    /// Whether the proof (a dict or JSON string) checks out against its input and output
+    fn verify(&self, py: Python<'_>, proof: &Bound<'_, PyAny>, input: &[u8], output: &[u8]) -> PyResult<bool> {
+        self.report(py, proof, input, output)?.bind(py).get_item("passed")?.extract()
+    }
+
+    /// Per-check results as a dict: `passed`, `error`, `checks`, `total_ms`, ...
+    fn verify_report(
+        &self,
+        py: Python<'_>,
+        proof: &Bound<'_, PyAny>,
+        input: &[u8],
+        output: &[u8],
+    ) -> PyResult<Py<PyAny>> {
+        self.report(py, proof, input, output)
+    }
+}
+
+/// Commitment of `data`: SHA3-256 unless `algorithm` names another, e.g. "sha256"
+#[pyfunction]
+#[pyo3(signature = (data, algorithm = None))]
+fn hash_data(data: &[u8], algorithm: Option<&str>) -> PyResult<String> {
+    let algorithm = match algorithm {
+        Some(name) => HashAlgorithm::from_name(name)
+            .ok_or_else(|| error(Kind::UnsupportedHashAlgorithm, format!("Unknown hash algorithm '{}'", name)))?,
+        None => HashAlgorithm::default(),
+    };
+    zkml_verifier_core::hash::hash_bytes(data, algorithm)
+        .map_err(|e| error(e, format!("{} is not enabled in this build", algorithm.name())))
+}
+
+#[pymodule]
+fn onnx_verifier(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Verifier>()?;
+    module.add_function(wrap_pyfunction!(hash_data, module)?)?;
+    module.add("VerificationError", module.py().get_type::<VerificationError>())?;
+    module.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+    use zkml_verifier_core::clock::system_now_ms;
+
+    #[test]
+    fn test_python_module() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "onnx_verifier").unwrap();
+            onnx_verifier(&module).unwrap();
+            let hash = |data: &[u8]| module.getattr("hash_data").unwrap().call1((data,)).unwrap();
+            let proof = PyDict::new(py);
+            proof.set_item("model_hash", "0xmodel").unwrap();
+            proof.set_item("proof_hash", "0xproof").unwrap();
+            proof.set_item("input_hash", hash(b"in")).unwrap();
+            proof.set_item("output_hash", hash(b"out")).unwrap();
+            proof.set_item("timestamp", system_now_ms()).unwrap();
+            proof.set_item("verified", true).unwrap();
+
+            let verifier = module.getattr("Verifier").unwrap().call1(("0xmodel",)).unwrap();
+            let verify = |method: &str, output: &[u8]| verifier.call_method1(method, (&proof, b"in", output));
+            assert!(verify("verify", b"out").unwrap().extract::<bool>().unwrap());
+            let report = verify("verify_report", b"tampered").unwrap();
+            assert_eq!(report.get_item("error").unwrap().extract::<String>().unwrap(), "OutputHashMismatch");
+            assert!(report.get_item("checks").unwrap().len().unwrap() > 0);
+
+            let malformed = verifier.call_method1("verify", ("{}", b"in", b"out")).unwrap_err();
+            assert!(malformed.is_instance_of::<VerificationError>(py));
+            assert!(malformed.to_string().contains("MalformedProof: Failed to parse proof"));
+            let config = module.getattr("Verifier").unwrap().call1(("0xmodel", "{\"max_age_ms\": -1}"));
+            assert!(config.unwrap_err().to_string().contains("InvalidArgument: Invalid config"));
+        });
+    }
+}
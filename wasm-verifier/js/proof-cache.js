@@ -0,0 +1,32 @@
+// IndexedDB storage for the verified-proof cache (src/cache.rs)
+// One object store of string values keyed by string; every call runs in its own transaction.
+
+const STORE = "verified-io";
+
+export class IndexedDbStorage {
+  constructor(database) {
+    this.db = new Promise((resolve, reject) => {
+      const request = indexedDB.open(database ?? "zkml-verifier", 1);
+      request.onupgradeneeded = () => request.result.createObjectStore(STORE);
+      request.onsuccess = () => resolve(request.result);
+      request.onerror = () => reject(request.error);
+    });
+  }
+
+  get(key) {
+    return this.run("readonly", (store) => store.get(key));
+  }
+
+  async set(key, value) {
+    await this.run("readwrite", (store) => store.put(value, key));
+  }
+
+  async run(mode, operation) {
+    const db = await this.db;
+    return new Promise((resolve, reject) => {
+      const request = operation(db.transaction(STORE, mode).objectStore(STORE));
+      request.onsuccess = () => resolve(request.result);
+      request.onerror = () => reject(request.error);
+    });
+  }
+}
@@ -0,0 +1,79 @@
+// Web Worker glue for `WorkerVerifier` (src/worker.rs)
+// On the main thread this module only exports `VerifierWorker`; loaded as a
+// module worker, the same file serves verification requests.
+//
+// wasm-bindgen copies it to pkg/snippets/<crate>-<hash>/js/worker.js, three
+// levels below the package entry point.
+
+const PACKAGE_URL = new URL("../../../zkml_wasm_verifier.js", import.meta.url);
+
+// Main-thread handle: one dedicated worker, with request/response matching by id
+export class VerifierWorker {
+  constructor(url) {
+    this.worker = new Worker(url ?? import.meta.url, { type: "module" });
+    this.pending = new Map();
+    this.nextId = 0;
+    this.worker.onmessage = ({ data }) => {
+      const request = this.pending.get(data.id);
+      if (!request) return;
+      this.pending.delete(data.id);
+      if (data.error) {
+        request.reject(Object.assign(new Error(data.error.message), data.error));
+      } else {
+        request.resolve(data.report);
+      }
+    };
+    this.worker.onerror = (event) => this.failAll(new Error(event.message || "Verifier worker failed"));
+  }
+
+  request(message, transfer) {
+    return new Promise((resolve, reject) => {
+      const id = this.nextId++;
+      this.pending.set(id, { resolve, reject });
+      this.worker.postMessage({ ...message, id }, transfer);
+    });
+  }
+
+  terminate() {
+    this.worker.terminate();
+    this.failAll(new Error("Verifier worker was terminated"));
+  }
+
+  failAll(error) {
+    for (const { reject } of this.pending.values()) reject(error);
+    this.pending.clear();
+  }
+}
+
+// Worker side: instantiate the package once, keep one verifier per model/config pair
+function serve() {
+  const ready = import(PACKAGE_URL.href).then(async (pkg) => {
+    await pkg.default();
+    return pkg;
+  });
+  const verifiers = new Map();
+
+  self.onmessage = async ({ data }) => {
+    const { id, model_hash, config, proof, input, output } = data;
+    try {
+      const pkg = await ready;
+      const key = model_hash + "\n" + config;
+      if (!verifiers.has(key)) {
+        const verifierConfig = pkg.VerifierConfig.from_object(JSON.parse(config));
+        verifiers.set(key, pkg.WasmVerifier.with_config(model_hash, verifierConfig));
+      }
+      const proofData = pkg.ProofData.from_object(JSON.parse(proof));
+      const report = verifiers.get(key).verify_report(proofData, input, output);
+      self.postMessage({ id, report: report.to_json() });
+      report.free();
+      proofData.free();
+    } catch (e) {
+      const error = { message: String(e?.message ?? e), name: e?.name, code: e?.code, kind: e?.kind };
+      self.postMessage({ id, error });
+    }
+  };
+}
+
+if (typeof WorkerGlobalScope !== "undefined" && self instanceof WorkerGlobalScope) {
+  serve();
+}
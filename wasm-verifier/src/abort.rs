@@ -0,0 +1,142 @@
+// Cancellation
+// `AbortSignal`s checked between hash chunks and stream reads, so abandoned verifications stop early
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+
+#[wasm_bindgen]
+extern "C" {
+    /// DOM `AbortSignal`, or any object with an `aborted` flag used as a cancellation token
+    #[wasm_bindgen(typescript_type = "AbortSignal | { readonly aborted: boolean }")]
+    pub type AbortSignal;
+
+    #[wasm_bindgen(method, getter)]
+    fn aborted(this: &AbortSignal) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    fn reason(this: &AbortSignal) -> JsValue;
+}
+
+/// A verification was cancelled; `Cancellation::error` gives what to reject with
+#[derive(Debug)]
+pub(crate) struct Aborted;
+
+/// Whatever can cancel a verification in progress
+#[derive(Clone, Copy)]
+pub(crate) enum Cancellation<'a> {
+    Never,
+    Signal(&'a AbortSignal),
+    /// Aborts after this many more checks, for native tests
+    #[cfg(test)]
+    Countdown(&'a std::cell::Cell<u32>),
+}
+
+impl<'a> Cancellation<'a> {
+    pub(crate) fn new(signal: Option<&'a AbortSignal>) -> Self {
+        signal.map_or(Cancellation::Never, Cancellation::Signal)
+    }
+
+    /// `Err(Aborted)` once cancelled
+    pub(crate) fn check(self) -> Result<(), Aborted> {
+        let aborted = match self {
+            Cancellation::Never => false,
+            Cancellation::Signal(signal) => signal.aborted(),
+            #[cfg(test)]
+            Cancellation::Countdown(left) => {
+                let aborted = left.get() == 0;
+                left.set(left.get().saturating_sub(1));
+                aborted
+            }
+        };
+        if aborted {
+            Err(Aborted)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The signal's `reason` (an `AbortError` `DOMException` unless given
+    /// to `abort()`), or `VerificationError.Aborted` for tokens without one
+    pub(crate) fn error(self) -> JsValue {
+        match self {
+            Cancellation::Signal(signal) => Some(signal.reason()).filter(|reason| !reason.is_undefined()),
+            _ => None,
+        }
+        .unwrap_or_else(|| VerificationError::Aborted.to_js("Verification aborted"))
+    }
+
+    /// `check`, as the value to reject with
+    pub(crate) fn check_js(self) -> Result<(), JsValue> {
+        self.check().map_err(|Aborted| self.error())
+    }
+}
+
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::{kind, stream};
+    use crate::readable::{hash_stream, ReadableStream};
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    fn new_controller() -> JsValue {
+        js_sys::Function::new_no_args("return new AbortController();").call0(&JsValue::NULL).unwrap()
+    }
+
+    fn signal_of(controller: &JsValue) -> AbortSignal {
+        js_sys::Reflect::get(controller, &"signal".into()).unwrap().unchecked_into()
+    }
+
+    fn abort(controller: &JsValue, reason: &JsValue) {
+        let abort = js_sys::Reflect::get(controller, &"abort".into()).unwrap().unchecked_into::<js_sys::Function>();
+        abort.call1(controller, reason).unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_abort_signal() {
+        let controller = new_controller();
+        let signal = signal_of(&controller);
+        assert!(Cancellation::new(Some(&signal)).check().is_ok());
+        abort(&controller, &JsValue::UNDEFINED);
+        let error = Cancellation::new(Some(&signal)).check_js().unwrap_err();
+        assert_eq!(js_sys::Reflect::get(&error, &"name".into()).unwrap(), "AbortError");
+
+        let controller = new_controller();
+        abort(&controller, &"navigated away".into());
+        assert_eq!(Cancellation::new(Some(&signal_of(&controller))).check_js().unwrap_err(), "navigated away");
+
+        // Plain tokens have no reason
+        let token: AbortSignal = js_sys::JSON::parse(r#"{"aborted": true}"#).unwrap().unchecked_into();
+        let error = Cancellation::new(Some(&token)).check_js().unwrap_err();
+        assert_eq!(kind(&error).as_deref(), Some(VerificationError::Aborted.name()));
+        assert!(Cancellation::new(None).check_js().is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_abort_cancels_stream() {
+        let controller = new_controller();
+        assert!(hash_stream(stream(&[b"in"]), None, None, Some(signal_of(&controller))).await.is_ok());
+
+        // Aborted while the second chunk is being read
+        let cancelled = js_sys::Array::new();
+        let source = "let n = 0; return new ReadableStream({ \
+            pull(c) { if (n++) controller.abort('stop'); c.enqueue(new Uint8Array([n])); }, \
+            cancel(reason) { cancelled.push(reason); } });";
+        let endless: ReadableStream = js_sys::Function::new_with_args("controller, cancelled", source)
+            .call2(&JsValue::NULL, &controller, &cancelled)
+            .unwrap()
+            .unchecked_into();
+        let signal = signal_of(&controller);
+        assert!(!signal.aborted());
+        let error = hash_stream(endless, None, None, Some(signal)).await.unwrap_err();
+        assert_eq!(error, "stop");
+        assert_eq!(cancelled.to_vec(), [JsValue::from("stop")]);
+
+        // An already aborted signal rejects before the stream is touched
+        let untouched = stream(&[b"in"]);
+        let signal = signal_of(&controller);
+        assert!(hash_stream(untouched.clone().unchecked_into(), None, None, Some(signal)).await.is_err());
+        assert!(!js_sys::Reflect::get(&untouched, &"locked".into()).unwrap().is_truthy());
+    }
+}
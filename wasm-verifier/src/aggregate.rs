@@ -0,0 +1,143 @@
+// Aggregated inference proofs
+// One proof over a Merkle root of N (input, output) pairs of the same model, with per-inference inclusion proofs
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+/// A proof over many inferences of one model
+///
+/// `input_hash` is the Merkle root (`aggregate_inferences`) over the
+/// inferences' `(input_hash, output_hash)` pairs and `output_hash` commits
+/// to their count (`AggregatedProof.count_commitment`), so a signature over
+/// the proof covers both.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct AggregatedProof(zkml_verifier_core::aggregate::AggregatedProof);
+
+wrap!(AggregatedProof => zkml_verifier_core::aggregate::AggregatedProof);
+
+#[wasm_bindgen]
+impl AggregatedProof {
+    #[wasm_bindgen(constructor)]
+    pub fn new(proof: ProofData, count: u64) -> AggregatedProof {
+        AggregatedProof(zkml_verifier_core::aggregate::AggregatedProof::new(proof.into(), count))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proof(&self) -> ProofData {
+        self.0.proof().clone().into()
+    }
+
+    /// Number of aggregated inferences
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> u64 {
+        self.0.count()
+    }
+
+    /// Merkle root over the inferences (the proof's `input_hash`)
+    #[wasm_bindgen(getter)]
+    pub fn root(&self) -> String {
+        self.0.root().to_string()
+    }
+
+    /// `output_hash` an aggregate over `count` inferences must carry
+    #[wasm_bindgen]
+    pub fn count_commitment(count: u64, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        zkml_verifier_core::aggregate::AggregatedProof::count_commitment(count, algorithm.unwrap_or_default())
+            .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<AggregatedProof, JsValue> {
+        zkml_verifier_core::aggregate::AggregatedProof::from_json(json).map(AggregatedProof).map_err(|e| {
+            VerificationError::MalformedProof.to_js(&format!("Failed to parse aggregated proof JSON: {}", e))
+        })
+    }
+}
+
+/// Inclusion proof for one inference of an `AggregatedProof`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct InferenceProof(zkml_verifier_core::aggregate::InferenceProof);
+
+wrap!(InferenceProof => zkml_verifier_core::aggregate::InferenceProof);
+
+#[wasm_bindgen]
+impl InferenceProof {
+    /// Position of the inference in the aggregate
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> u64 {
+        self.0.index()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<InferenceProof, JsValue> {
+        zkml_verifier_core::aggregate::InferenceProof::from_json(json)
+            .map(InferenceProof)
+            .map_err(|_| VerificationError::MalformedProof.to_js("Invalid inference proof JSON"))
+    }
+}
+
+/// Merkle root over `(input_hashes[i], output_hashes[i])` pairs, for an aggregate's `input_hash`
+#[wasm_bindgen]
+pub fn aggregate_inferences(
+    input_hashes: Vec<String>,
+    output_hashes: Vec<String>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String, JsValue> {
+    zkml_verifier_core::aggregate::aggregate_inferences(&input_hashes, &output_hashes, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Expected equally many, non-empty, hex input and output hashes"))
+}
+
+/// Build the inclusion proof for inference `index` (see `aggregate_inferences`)
+#[wasm_bindgen]
+pub fn prove_inference(
+    input_hashes: Vec<String>,
+    output_hashes: Vec<String>,
+    index: usize,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<InferenceProof, JsValue> {
+    let alg = algorithm.unwrap_or_default();
+    zkml_verifier_core::aggregate::InferenceProof::create(&input_hashes, &output_hashes, index, alg)
+        .map(InferenceProof)
+        .map_err(|e| e.to_js("Expected equally many hex hashes and an index within them"))
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify an aggregated proof (model binding, count, validity, signature, ...)
+    #[wasm_bindgen]
+    pub fn verify_aggregate(&self, aggregate: &AggregatedProof) -> VerificationReport {
+        self.verifier.aggregate_report(aggregate).into()
+    }
+
+    /// Verify an aggregate and that `input`/`output` is one of its inferences
+    #[wasm_bindgen]
+    pub fn verify_inference(
+        &self,
+        aggregate: &AggregatedProof,
+        input: &[u8],
+        output: &[u8],
+        inclusion: &InferenceProof,
+    ) -> VerificationReport {
+        self.verifier.inference_report(aggregate, input, output, inclusion).into()
+    }
+}
@@ -0,0 +1,135 @@
+// On-chain anchoring
+// Checks that a proof is in a Merkle root a transaction committed on chain, looked up through a JS callback
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::inclusion::MerkleProof;
+use crate::report::{Check, VerificationReport};
+use crate::{ProofData, WasmVerifier};
+
+pub use zkml_verifier_core::anchor::AnchorTx;
+use zkml_verifier_core::anchor::record_anchor;
+
+/// Where a prover says a proof was anchored
+///
+/// ```json
+/// { "chain": "eip155:1", "tx_hash": "0x…", "block_number": 19000000, "root": "0x…",
+///   "path": { "index": 3, "leaf_count": 1000, "algorithm": "sha3-256", "path": ["0x…"] } }
+/// ```
+///
+/// `path` leads from the proof's `leaf_hash` to `root`, which the
+/// transaction `tx_hash` committed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct AnchorRecord(zkml_verifier_core::anchor::AnchorRecord);
+
+wrap!(AnchorRecord => zkml_verifier_core::anchor::AnchorRecord);
+
+#[wasm_bindgen]
+impl AnchorRecord {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        tx_hash: String,
+        block_number: u64,
+        root: String,
+        path: MerkleProof,
+        chain: Option<String>,
+    ) -> AnchorRecord {
+        AnchorRecord(zkml_verifier_core::anchor::AnchorRecord::new(tx_hash, block_number, root, path.into(), chain))
+    }
+
+    /// Chain identifier passed on to the chain query (e.g. CAIP-2)
+    #[wasm_bindgen(getter)]
+    pub fn chain(&self) -> Option<String> {
+        self.0.chain().map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn tx_hash(&self) -> String {
+        self.0.tx_hash().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn block_number(&self) -> u64 {
+        self.0.block_number()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn root(&self) -> String {
+        self.0.root().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> MerkleProof {
+        self.0.path().clone().into()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<AnchorRecord, JsValue> {
+        zkml_verifier_core::anchor::AnchorRecord::from_json(json)
+            .map(AnchorRecord)
+            .map_err(|_| VerificationError::MalformedProof.to_js("Invalid anchor record JSON"))
+    }
+}
+
+impl WasmVerifier {
+    /// Ask the chain query about `anchor`'s transaction
+    fn query_anchor(&self, anchor: &AnchorRecord) -> Result<Option<AnchorTx>, &'static str> {
+        let query = self.chain_query.as_ref().ok_or("Checking anchors requires a chain query")?;
+        let answer = query
+            .call2(&JsValue::NULL, &JsValue::from_str(&anchor.tx_hash()), &anchor.chain().into())
+            .map_err(|_| "Chain query failed")?;
+        if answer.is_null() || answer.is_undefined() {
+            return Ok(None);
+        }
+        let json = match answer.as_string() {
+            Some(json) => json,
+            None => js_sys::JSON::stringify(&answer).ok().and_then(|json| json.as_string()).unwrap_or_default(),
+        };
+        serde_json::from_str(&json).map(Some).map_err(|_| "Chain query did not return { block_number, root }")
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Callback `(tx_hash, chain) => { block_number, root } | undefined` for `verify_anchored`
+    ///
+    /// Returns the block an anchoring transaction was included in and the
+    /// root it committed, decoded however the anchoring contract stores it,
+    /// or `undefined` if there is no such transaction. Must answer
+    /// synchronously, e.g. from a cache your app fills from an RPC node.
+    #[wasm_bindgen(setter)]
+    pub fn set_chain_query(&mut self, query: Option<js_sys::Function>) {
+        self.chain_query = query;
+    }
+
+    /// Verify a proof and that it was anchored on chain where `anchor` says
+    ///
+    /// Adds an `anchor` check to the usual ones and, when it passes, sets
+    /// the report's `anchored_block`. The check fails if no chain query is
+    /// set, the transaction isn't found or disagrees with the record, or
+    /// the proof isn't in the anchored root.
+    #[wasm_bindgen]
+    pub fn verify_anchored(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[u8],
+        anchor: &AnchorRecord,
+    ) -> VerificationReport {
+        let mut report = self.verify_report(proof_data, input, output);
+        let started = report.start(Check::Anchor);
+        let anchored = self.query_anchor(anchor).and_then(|tx| anchor.check(proof_data, tx.as_ref()));
+        record_anchor(&mut report, anchored, started);
+        report
+    }
+}
@@ -0,0 +1,154 @@
+// Audit log
+// Append-only record of verification attempts, each entry hash-chained to the one before, exportable as JSONL
+
+use wasm_bindgen::prelude::*;
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+pub use zkml_verifier_core::audit::AUDIT_GENESIS;
+
+/// One verification attempt
+#[derive(Debug, Clone, PartialEq)]
+#[wasm_bindgen]
+pub struct AuditEntry(zkml_verifier_core::audit::AuditEntry);
+
+wrap!(AuditEntry => zkml_verifier_core::audit::AuditEntry);
+
+#[wasm_bindgen]
+impl AuditEntry {
+    /// Position in the log, from 0
+    #[wasm_bindgen(getter)]
+    pub fn seq(&self) -> u64 {
+        self.0.seq()
+    }
+
+    /// When it was verified (Unix ms, by the verifier's clock)
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> u64 {
+        self.0.timestamp()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    /// `VerificationError` name, if it failed
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<String> {
+        self.0.error().map(str::to_string)
+    }
+
+    /// `hash` of the entry before, `AUDIT_GENESIS` for the first
+    #[wasm_bindgen(getter)]
+    pub fn prev_hash(&self) -> String {
+        self.0.prev_hash().to_string()
+    }
+
+    /// Tagged SHA3-256 of the RFC 8785 canonical JSON of the other fields
+    #[wasm_bindgen(getter)]
+    pub fn hash(&self) -> String {
+        self.0.hash().to_string()
+    }
+}
+
+/// Hash-chained verification history
+///
+/// ```js
+/// verifier.audit_log = new AuditLog();
+/// verifier.verify_report(proof, input, output);
+/// const log = verifier.audit_log;
+/// upload(log.to_jsonl(), log.head); // publish `head` somewhere it can't be rewritten
+/// AuditLog.from_jsonl(text);         // throws if any entry was altered, removed or reordered
+/// ```
+///
+/// Each entry commits to the one before it, so changing or dropping an
+/// entry breaks every later hash. Dropping entries from the end can only
+/// be caught against a `head` kept elsewhere.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[wasm_bindgen]
+pub struct AuditLog(zkml_verifier_core::audit::AuditLog);
+
+wrap!(AuditLog => zkml_verifier_core::audit::AuditLog);
+
+#[wasm_bindgen]
+impl AuditLog {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> AuditLog {
+        AuditLog::default()
+    }
+
+    /// Append `report`'s outcome for `proof_data`, verified now
+    #[wasm_bindgen]
+    pub fn record(&mut self, proof_data: &ProofData, report: &VerificationReport) {
+        self.0.append(proof_data, report, crate::get_timestamp());
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.0.entries().iter().cloned().map(AuditEntry).collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Hash of the last entry, which commits to the whole log (`AUDIT_GENESIS` while empty)
+    #[wasm_bindgen(getter)]
+    pub fn head(&self) -> String {
+        self.0.head()
+    }
+
+    /// One JSON entry per line, oldest first
+    #[wasm_bindgen]
+    pub fn to_jsonl(&self) -> String {
+        self.0.to_jsonl()
+    }
+
+    /// Read a `to_jsonl` export back, checking its chain
+    ///
+    /// Throws `VerificationError.InvalidArgument` naming the first entry that
+    /// doesn't parse or doesn't chain.
+    #[wasm_bindgen]
+    pub fn from_jsonl(jsonl: &str) -> Result<AuditLog, JsValue> {
+        zkml_verifier_core::audit::AuditLog::try_from_jsonl(jsonl)
+            .map(AuditLog)
+            .map_err(|reason| VerificationError::InvalidArgument.to_js(&reason))
+    }
+
+    /// Recompute every hash and link; throws `VerificationError.InvalidArgument` at the first broken one
+    #[wasm_bindgen]
+    pub fn verify(&self) -> Result<(), JsValue> {
+        self.0.try_verify().map_err(|reason| VerificationError::InvalidArgument.to_js(&reason))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Log every verification this verifier runs from now on to `log` (`undefined` stops logging)
+    ///
+    /// Pass a log read back with `AuditLog.from_jsonl` to continue its chain.
+    #[wasm_bindgen(setter)]
+    pub fn set_audit_log(&mut self, log: Option<AuditLog>) {
+        self.verifier.set_audit_log(log.map(Into::into));
+    }
+
+    /// Copy of the audit log so far (`undefined` unless one was set)
+    #[wasm_bindgen(getter)]
+    pub fn audit_log(&self) -> Option<AuditLog> {
+        self.verifier.audit_log().map(AuditLog)
+    }
+}
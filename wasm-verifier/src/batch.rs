@@ -0,0 +1,152 @@
+// Batch verification
+// Verifies many proofs in one call to avoid per-proof JS/WASM boundary overhead
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::IoSource;
+use crate::parallel;
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+/// Per-proof results plus aggregate counts for a batch
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct BatchReport(zkml_verifier_core::batch::BatchReport);
+
+wrap!(BatchReport => zkml_verifier_core::batch::BatchReport);
+
+#[wasm_bindgen]
+impl BatchReport {
+    /// Per-proof reports, in input order
+    #[wasm_bindgen(getter)]
+    pub fn reports(&self) -> Vec<VerificationReport> {
+        self.0.reports().iter().cloned().map(VerificationReport::from).collect()
+    }
+
+    /// Per-proof pass/fail flags, in input order
+    #[wasm_bindgen(getter)]
+    pub fn results(&self) -> Vec<u8> {
+        self.0.results()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> u32 {
+        self.0.total()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn passed(&self) -> u32 {
+        self.0.passed()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn failed(&self) -> u32 {
+        self.0.failed()
+    }
+
+    /// True when the batch is non-empty and every proof passed
+    #[wasm_bindgen(getter)]
+    pub fn all_passed(&self) -> bool {
+        self.0.all_passed()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    /// Serialize batch report to JSON string
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify many proofs at once
+    ///
+    /// `inputs[i]` and `outputs[i]` are the I/O buffers for `proofs[i]`.
+    /// Throws `VerificationError.InvalidArgument` if the arrays differ in length.
+    #[wasm_bindgen]
+    pub fn verify_batch(
+        &self,
+        proofs: Vec<ProofData>,
+        inputs: Vec<js_sys::Uint8Array>,
+        outputs: Vec<js_sys::Uint8Array>,
+    ) -> Result<BatchReport, JsValue> {
+        let inputs: Vec<Vec<u8>> = inputs.iter().map(|a| a.to_vec()).collect();
+        let outputs: Vec<Vec<u8>> = outputs.iter().map(|a| a.to_vec()).collect();
+
+        self.check_batch(&proofs, &inputs, &outputs)
+            .map_err(|e| e.to_js("proofs, inputs and outputs must have the same length"))
+    }
+}
+
+impl WasmVerifier {
+    /// Verify a batch of proofs against their I/O buffers
+    ///
+    /// The I/O buffers are hashed up front, across threads with the `atomics`
+    /// feature; the checks themselves then run in order on this thread.
+    pub fn check_batch<I: AsRef<[u8]> + Sync, O: AsRef<[u8]> + Sync>(
+        &self,
+        proofs: &[ProofData],
+        inputs: &[I],
+        outputs: &[O],
+    ) -> Result<BatchReport, VerificationError> {
+        if proofs.len() != inputs.len() || proofs.len() != outputs.len() {
+            return Err(VerificationError::InvalidArgument);
+        }
+
+        let jobs: Vec<_> = proofs
+            .iter()
+            .zip(inputs)
+            .zip(outputs)
+            .map(|((proof, input), output)| (proof, input.as_ref(), output.as_ref(), self.hash_algorithm_for(proof)))
+            .collect();
+        let sources = parallel::map(&jobs, |(proof, input, output, alg)| {
+            (IoSource::prehash(input, &proof.input_hash, alg), IoSource::prehash(output, &proof.output_hash, alg))
+        });
+
+        let batch = jobs.iter().zip(sources).map(|((proof, ..), (input, output))| {
+            let mut report = self.new_report();
+            self.run_checks(&mut report, proof, input, output);
+            report.into()
+        });
+        Ok(BatchReport(batch.collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_check_batch() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = WasmVerifier::new(model_hash.clone());
+
+        let proof = |input: &[u8], output: &[u8]| fixtures::proof_for(&model_hash, input, output, Default::default());
+
+        let proofs = vec![proof(b"a", b"1"), proof(b"b", b"2"), proof(b"c", b"3")];
+        let inputs: Vec<&[u8]> = vec![b"a", b"b", b"c"];
+        let outputs: Vec<&[u8]> = vec![b"1", b"2", b"wrong"];
+
+        let batch = verifier.check_batch(&proofs, &inputs, &outputs).unwrap();
+        assert_eq!(batch.total(), 3);
+        assert_eq!(batch.passed(), 2);
+        assert_eq!(batch.failed(), 1);
+        assert_eq!(batch.results(), vec![1, 1, 0]);
+        assert!(!batch.all_passed());
+
+        assert_eq!(
+            verifier.check_batch(&proofs, &inputs[..2], &outputs).unwrap_err(),
+            VerificationError::InvalidArgument
+        );
+    }
+}
@@ -0,0 +1,25 @@
+// Compact binary proof encoding
+// Versioned wire format: magic, version, fixed-width digests, then tagged optional fields
+
+use wasm_bindgen::prelude::*;
+
+use crate::ProofData;
+
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Serialize to the compact binary format (`ZKPF`, version 1)
+    #[wasm_bindgen]
+    pub fn encode_binary(&self) -> Vec<u8> {
+        self.to_binary()
+    }
+
+    /// Deserialize from the compact binary format
+    ///
+    /// Throws `VerificationError.MalformedProof` on a bad magic, an unknown
+    /// version or field, or truncated input.
+    #[wasm_bindgen]
+    pub fn decode_binary(bytes: &[u8]) -> Result<ProofData, JsValue> {
+        zkml_verifier_core::ProofData::from_binary(bytes).map(ProofData).map_err(|e| e.to_js("Invalid binary proof"))
+    }
+}
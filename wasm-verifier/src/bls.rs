@@ -0,0 +1,18 @@
+// BLS aggregate signatures
+// Multi-attestor proofs: every attestor signs `signing_bytes()`, signatures are
+// aggregated into one BLS12-381 signature checked with a single pairing equation
+
+use wasm_bindgen::prelude::*;
+
+use crate::WasmVerifier;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Trust a BLS12-381 attestor public key (48-byte compressed G1, hex)
+    ///
+    /// Only add keys whose proof of possession you have checked.
+    #[wasm_bindgen]
+    pub fn add_trusted_attestor(&mut self, pubkey: String) {
+        self.verifier.add_trusted_attestor(pubkey);
+    }
+}
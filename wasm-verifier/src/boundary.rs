@@ -0,0 +1,45 @@
+// JS boundary
+// `#[wasm_bindgen]` classes are newtypes over the core's plain Rust types, converted here
+
+/// `Deref`/`DerefMut` to the wrapped core type, and `From` both ways
+///
+/// Each class is declared `struct Class(core::Class)` with `#[serde(transparent)]`,
+/// so it reads and writes the same JSON as the core type.
+macro_rules! wrap {
+    ($($class:ident => $core:ty),* $(,)?) => {$(
+        impl std::ops::Deref for $class {
+            type Target = $core;
+
+            fn deref(&self) -> &$core {
+                &self.0
+            }
+        }
+
+        impl std::ops::DerefMut for $class {
+            fn deref_mut(&mut self) -> &mut $core {
+                &mut self.0
+            }
+        }
+
+        impl From<$core> for $class {
+            fn from(inner: $core) -> Self {
+                $class(inner)
+            }
+        }
+
+        impl From<$class> for $core {
+            fn from(class: $class) -> Self {
+                class.0
+            }
+        }
+    )*};
+}
+
+pub(crate) use wrap;
+
+/// A JS `(key) => string | undefined` callback as a core `Fetcher`
+#[cfg(feature = "signatures")]
+pub(crate) fn fetcher(fetch: js_sys::Function) -> zkml_verifier_core::host::Fetcher {
+    let fetch = crate::handle::JsHandle::new(fetch);
+    Box::new(move |key: &str| fetch.get()?.call1(&wasm_bindgen::JsValue::NULL, &key.into()).ok()?.as_string())
+}
@@ -0,0 +1,130 @@
+// Zero-copy I/O buffers
+// Input/output bytes written by JS straight into WASM memory, so verification never copies them
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+use crate::hash::{self, HashAlgorithm, IoSource};
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Bytes living in WASM memory, filled from JS through a view instead of being passed by value
+///
+/// ```js
+/// const input = new IoBuffer(file.size);
+/// input.view().set(new Uint8Array(await file.arrayBuffer()));
+/// verifier.verify_buffers(proof, input, output);
+/// ```
+///
+/// Passing a `Uint8Array` to `verify` copies it into WASM memory for the
+/// duration of the call; an `IoBuffer` is allocated there once, so large
+/// inputs are held only once. Stream readers can write chunk by chunk with
+/// `view().set(chunk, offset)`.
+#[wasm_bindgen]
+pub struct IoBuffer {
+    data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl IoBuffer {
+    /// Allocate `len` zeroed bytes
+    ///
+    /// Throws `VerificationError.PayloadTooLarge` if WASM memory can't grow that far.
+    #[wasm_bindgen(constructor)]
+    pub fn new(len: usize) -> Result<IoBuffer, JsValue> {
+        IoBuffer::zeroed(len).map_err(|e| e.to_js("Not enough WASM memory for the buffer"))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[wasm_bindgen]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Offset of the bytes in `memory.buffer`, for building views by hand
+    #[wasm_bindgen(getter)]
+    pub fn ptr(&self) -> usize {
+        self.data.as_ptr() as usize
+    }
+
+    /// Writable `Uint8Array` over the buffer, without copying
+    ///
+    /// The view is detached whenever WASM memory grows (any allocation may
+    /// do that), so take a fresh one after calling into the verifier.
+    #[wasm_bindgen]
+    pub fn view(&mut self) -> js_sys::Uint8Array {
+        // SAFETY: the view aliases `data` only until the next allocation, as documented above
+        unsafe { js_sys::Uint8Array::view_mut_raw(self.data.as_mut_ptr(), self.data.len()) }
+    }
+}
+
+impl IoBuffer {
+    fn zeroed(len: usize) -> Result<IoBuffer, VerificationError> {
+        let mut data = Vec::new();
+        data.try_reserve_exact(len).map_err(|_| VerificationError::PayloadTooLarge)?;
+        data.resize(len, 0);
+        Ok(IoBuffer { data })
+    }
+}
+
+impl From<Vec<u8>> for IoBuffer {
+    fn from(data: Vec<u8>) -> Self {
+        IoBuffer { data }
+    }
+}
+
+/// Hash the contents of an `IoBuffer` (SHA3-256 unless `algorithm` is given)
+#[wasm_bindgen]
+pub fn hash_buffer(buffer: &IoBuffer, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    hash::hash_bytes(&buffer.data, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// `verify` against bytes already in WASM memory
+    #[wasm_bindgen]
+    pub fn verify_buffers(&self, proof_data: &ProofData, input: &IoBuffer, output: &IoBuffer) -> bool {
+        self.verify_buffers_report(proof_data, input, output).passed()
+    }
+
+    /// `verify_report` against bytes already in WASM memory
+    #[wasm_bindgen]
+    pub fn verify_buffers_report(
+        &self,
+        proof_data: &ProofData,
+        input: &IoBuffer,
+        output: &IoBuffer,
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(&mut report, proof_data, IoSource::Bytes(&input.data), IoSource::Bytes(&output.data));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hash_data;
+
+    #[test]
+    fn test_verify_buffers_matches_verify() {
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let proof = fixtures::proof("0xmodel");
+
+        let input = IoBuffer::from(b"in".to_vec());
+        assert!(verifier.verify_buffers(&proof, &input, &IoBuffer::from(b"out".to_vec())));
+        assert_eq!(hash_buffer(&input, None).unwrap(), hash_data(b"in", None).unwrap());
+
+        let report = verifier.verify_buffers_report(&proof, &input, &IoBuffer::from(b"tampered".to_vec()));
+        assert_eq!(report.failure_reasons(), verifier.verify_report(&proof, b"in", b"tampered").failure_reasons());
+
+        let zeroed = IoBuffer::zeroed(4).unwrap();
+        assert_eq!(zeroed.data, [0; 4]);
+        assert_eq!(IoBuffer::zeroed(usize::MAX).err(), Some(VerificationError::PayloadTooLarge));
+    }
+}
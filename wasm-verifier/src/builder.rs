@@ -0,0 +1,126 @@
+// Verifier builder
+// Fluent construction of a `WasmVerifier`, so new options don't change any constructor signature
+
+use wasm_bindgen::prelude::*;
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::{VerifierConfig, WasmVerifier};
+
+/// Step-by-step `WasmVerifier` setup
+///
+/// ```js
+/// const verifier = new VerifierBuilder()
+///   .model_hash(modelHash)
+///   .max_age_ms(10 * 60 * 1000)
+///   .require_signature(true)
+///   .trusted_signer(proverKey)
+///   .build();
+/// ```
+///
+/// Every method consumes the builder and returns it, so keep chaining from
+/// the returned value. Options not set keep their `VerifierConfig` defaults.
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct VerifierBuilder(zkml_verifier_core::VerifierBuilder);
+
+wrap!(VerifierBuilder => zkml_verifier_core::VerifierBuilder);
+
+#[wasm_bindgen]
+impl VerifierBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> VerifierBuilder {
+        VerifierBuilder::default()
+    }
+
+    /// Model the proofs must be bound to (required)
+    #[wasm_bindgen]
+    pub fn model_hash(self, model_hash: String) -> VerifierBuilder {
+        VerifierBuilder(self.0.model_hash(model_hash))
+    }
+
+    /// Start from an existing policy; later options override its fields
+    #[wasm_bindgen]
+    pub fn config(self, config: &VerifierConfig) -> VerifierBuilder {
+        VerifierBuilder(self.0.config(config))
+    }
+
+    #[wasm_bindgen]
+    pub fn max_age_ms(self, max_age_ms: u64) -> VerifierBuilder {
+        VerifierBuilder(self.0.max_age_ms(max_age_ms))
+    }
+
+    #[wasm_bindgen]
+    pub fn check_freshness(self, check_freshness: bool) -> VerifierBuilder {
+        VerifierBuilder(self.0.check_freshness(check_freshness))
+    }
+
+    #[wasm_bindgen]
+    pub fn clock_skew_ms(self, clock_skew_ms: u64) -> VerifierBuilder {
+        VerifierBuilder(self.0.clock_skew_ms(clock_skew_ms))
+    }
+
+    #[wasm_bindgen]
+    pub fn hash_algorithm(self, hash_algorithm: HashAlgorithm) -> VerifierBuilder {
+        VerifierBuilder(self.0.hash_algorithm(hash_algorithm))
+    }
+
+    #[wasm_bindgen]
+    pub fn require_nonce(self, require_nonce: bool) -> VerifierBuilder {
+        VerifierBuilder(self.0.require_nonce(require_nonce))
+    }
+
+    #[wasm_bindgen]
+    pub fn require_signature(self, require_signature: bool) -> VerifierBuilder {
+        VerifierBuilder(self.0.require_signature(require_signature))
+    }
+
+    #[wasm_bindgen]
+    pub fn min_attestors(self, min_attestors: u32) -> VerifierBuilder {
+        VerifierBuilder(self.0.min_attestors(min_attestors))
+    }
+
+    #[wasm_bindgen]
+    pub fn max_decompressed_bytes(self, max_decompressed_bytes: u64) -> VerifierBuilder {
+        VerifierBuilder(self.0.max_decompressed_bytes(max_decompressed_bytes))
+    }
+
+    #[wasm_bindgen]
+    pub fn max_proof_bytes(self, max_proof_bytes: u64) -> VerifierBuilder {
+        VerifierBuilder(self.0.max_proof_bytes(max_proof_bytes))
+    }
+
+    #[wasm_bindgen]
+    pub fn max_input_bytes(self, max_input_bytes: u64) -> VerifierBuilder {
+        VerifierBuilder(self.0.max_input_bytes(max_input_bytes))
+    }
+
+    /// Trust an Ed25519 key or Ethereum address (see `WasmVerifier::add_trusted_signer`)
+    #[cfg(feature = "signatures")]
+    #[wasm_bindgen]
+    pub fn trusted_signer(self, pubkey: String) -> VerifierBuilder {
+        VerifierBuilder(self.0.trusted_signer(pubkey))
+    }
+
+    /// Trust a BLS attestor key (see `WasmVerifier::add_trusted_attestor`)
+    #[cfg(feature = "signatures")]
+    #[wasm_bindgen]
+    pub fn trusted_attestor(self, pubkey: String) -> VerifierBuilder {
+        VerifierBuilder(self.0.trusted_attestor(pubkey))
+    }
+
+    /// Create the verifier
+    ///
+    /// Throws `VerificationError.InvalidArgument` if no model hash was set.
+    #[wasm_bindgen]
+    pub fn build(self) -> Result<WasmVerifier, JsValue> {
+        self.try_build().map_err(|e| e.to_js("VerifierBuilder needs a model_hash"))
+    }
+}
+
+impl VerifierBuilder {
+    pub fn try_build(self) -> Result<WasmVerifier, VerificationError> {
+        self.0.try_build().map(WasmVerifier::from)
+    }
+}
@@ -0,0 +1,252 @@
+// Verified-proof cache
+// Digests of already verified I/O kept in app-provided storage (IndexedDB by default), so revisits skip re-hashing
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::JsFuture;
+use zkml_verifier_core::hash::StreamingHasher;
+
+use crate::hash::{HashAlgorithm, IoSource};
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+#[wasm_bindgen(module = "/js/proof-cache.js")]
+extern "C" {
+    type IndexedDbStorage;
+
+    #[wasm_bindgen(constructor)]
+    fn new(database: Option<String>) -> IndexedDbStorage;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// `{ get(key), set(key, value) }` over strings, each returning a value or a Promise of one
+    #[wasm_bindgen(typescript_type = "ProofCacheStorage")]
+    pub type CacheStorage;
+
+    #[wasm_bindgen(method, catch)]
+    fn get(this: &CacheStorage, key: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn set(this: &CacheStorage, key: &str, value: &str) -> Result<JsValue, JsValue>;
+}
+
+/// Digest of one I/O buffer, as it was checked against its commitment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct CachedDigest {
+    alg: HashAlgorithm,
+    digest: String,
+    len: u64,
+}
+
+impl CachedDigest {
+    /// Hash `data` with the algorithm `commitment` will be checked with
+    fn hash(data: &[u8], commitment: &str, alg: &Result<HashAlgorithm, String>) -> Option<CachedDigest> {
+        let mut hasher = StreamingHasher::for_commitment(commitment, alg)?;
+        hasher.update(data);
+        Some(CachedDigest { alg: hasher.algorithm(), digest: hasher.finalize(), len: data.len() as u64 })
+    }
+
+    /// Stand-in for the data, if it was hashed the way `commitment` needs
+    fn source(&self, commitment: &str, alg: &Result<HashAlgorithm, String>) -> Option<IoSource<'static>> {
+        let needed = StreamingHasher::for_commitment(commitment, alg)?.algorithm();
+        (needed == self.alg).then(|| IoSource::Hashed(self.alg, self.digest.clone(), self.len))
+    }
+}
+
+/// What the cache stores per proof and `io_key`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct CacheEntry {
+    input: CachedDigest,
+    output: CachedDigest,
+}
+
+fn cache_key(proof_data: &ProofData, io_key: &str) -> String {
+    format!("{}\n{}", proof_data.proof_hash, io_key)
+}
+
+/// Await a storage result that may or may not be a Promise
+async fn settle(value: Result<JsValue, JsValue>) -> Result<JsValue, JsValue> {
+    JsFuture::from(js_sys::Promise::resolve(&value?)).await
+}
+
+impl WasmVerifier {
+    /// Full report with `entry`'s digests in place of the data
+    ///
+    /// `None` if the proof's commitments call for other hash algorithms.
+    fn report_from_cache(&self, proof_data: &ProofData, entry: &CacheEntry) -> Option<VerificationReport> {
+        let alg = self.hash_algorithm_for(proof_data);
+        let input = entry.input.source(&proof_data.input_hash, &alg)?;
+        let output = entry.output.source(&proof_data.output_hash, &alg)?;
+        let mut report = self.new_report();
+        self.run_checks(&mut report, proof_data, input, output);
+        Some(report)
+    }
+
+    /// Report on the data itself, plus the entry to cache if both I/O checks passed
+    fn report_and_entry(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[u8],
+    ) -> (VerificationReport, Option<CacheEntry>) {
+        let alg = self.hash_algorithm_for(proof_data);
+        let digests = CachedDigest::hash(input, &proof_data.input_hash, &alg)
+            .zip(CachedDigest::hash(output, &proof_data.output_hash, &alg));
+        let mut report = self.new_report();
+        let Some((input_digest, output_digest)) = digests else {
+            self.run_checks(&mut report, proof_data, IoSource::Bytes(input), IoSource::Bytes(output));
+            return (report, None);
+        };
+
+        let input_source = IoSource::Prehashed(input, input_digest.alg, input_digest.digest.clone());
+        let output_source = IoSource::Prehashed(output, output_digest.alg, output_digest.digest.clone());
+        self.run_checks(&mut report, proof_data, input_source, output_source);
+        let entry = (report.input_hash() && report.output_hash())
+            .then_some(CacheEntry { input: input_digest, output: output_digest });
+        (report, entry)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Storage for `verify_cached`; see `use_indexed_db_cache` for the built-in one
+    #[wasm_bindgen(setter)]
+    pub fn set_proof_cache(&mut self, storage: Option<CacheStorage>) {
+        self.proof_cache = storage;
+    }
+
+    /// Keep the `verify_cached` cache in IndexedDB (database `"zkml-verifier"` unless named)
+    #[wasm_bindgen]
+    pub fn use_indexed_db_cache(&mut self, database: Option<String>) {
+        self.proof_cache = Some(IndexedDbStorage::new(database).unchecked_into());
+    }
+
+    /// `verify_report`, skipping the I/O hashing for data verified before
+    ///
+    /// ```js
+    /// verifier.use_indexed_db_cache();
+    /// const ioKey = `${file.name}:${file.size}:${file.lastModified}`;
+    /// const report = await verifier.verify_cached(proof, ioKey, input, output);
+    /// ```
+    ///
+    /// `io_key` identifies the input/output bytes and must change whenever
+    /// they may (e.g. URL plus `ETag`): on a hit the digests stored under it
+    /// stand in for the data, which is not read. Every other check runs as
+    /// usual. Digests are stored only once both I/O checks pass; storage
+    /// errors are ignored, costing at most a re-hash.
+    #[wasm_bindgen]
+    pub async fn verify_cached(
+        &self,
+        proof_data: &ProofData,
+        io_key: String,
+        input: js_sys::Uint8Array,
+        output: js_sys::Uint8Array,
+    ) -> VerificationReport {
+        let Some(storage) = &self.proof_cache else {
+            return self.verify_report(proof_data, &input.to_vec(), &output.to_vec());
+        };
+        let key = cache_key(proof_data, &io_key);
+
+        let cached = settle(storage.get(&key)).await.ok().and_then(|value| value.as_string());
+        let hit = cached.and_then(|json| serde_json::from_str::<CacheEntry>(&json).ok());
+        if let Some(report) = hit.and_then(|entry| self.report_from_cache(proof_data, &entry)) {
+            return report;
+        }
+
+        let (report, entry) = self.report_and_entry(proof_data, &input.to_vec(), &output.to_vec());
+        if let Some(entry) = entry {
+            let json = serde_json::to_string(&entry).unwrap_or_default();
+            let _ = settle(storage.set(&key, &json)).await;
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_timestamp, hash_bytes_tagged, hash_data};
+
+    #[test]
+    fn test_cached_digests_stand_in_for_data() {
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let proof = ProofData::new(
+            "0xmodel".to_string(),
+            "0xproof".to_string(),
+            hash_data(b"in", None).unwrap(),
+            hash_bytes_tagged(b"out", HashAlgorithm::Keccak256).unwrap(),
+            get_timestamp(),
+            true,
+        );
+
+        let (report, entry) = verifier.report_and_entry(&proof, b"in", b"out");
+        assert!(report.passed());
+        let entry: CacheEntry = serde_json::from_str(&serde_json::to_string(&entry.unwrap()).unwrap()).unwrap();
+        assert_eq!(entry.output.alg, HashAlgorithm::Keccak256);
+
+        let cached = verifier.report_from_cache(&proof, &entry).unwrap();
+        assert!(cached.passed());
+        assert_eq!(cached.checks().len(), report.checks().len());
+
+        // Nothing is cached for data that doesn't match
+        assert!(verifier.report_and_entry(&proof, b"in", b"tampered").1.is_none());
+
+        // Digests made with another algorithm can't be used
+        let mut sha256 = proof.clone();
+        sha256.set_hash_alg(Some("sha-256".to_string()));
+        assert!(verifier.report_from_cache(&sha256, &entry).is_none());
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod js_tests {
+    use super::*;
+    use crate::fixtures;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use zkml_verifier_core::fixtures::{INPUT, OUTPUT};
+
+    /// `CacheStorage` over a `Map`, answering `get` with a Promise and `set` synchronously
+    fn map_storage(map: &js_sys::Map) -> CacheStorage {
+        let source = "return { get: async (key) => map.get(key), set: (key, value) => { map.set(key, value); } };";
+        js_sys::Function::new_with_args("map", source).call1(&JsValue::NULL, map).unwrap().unchecked_into()
+    }
+
+    async fn verify(verifier: &WasmVerifier, io_key: &str, input: &[u8], output: &[u8]) -> VerificationReport {
+        let proof = fixtures::proof("0xmodel");
+        let (input, output) = (js_sys::Uint8Array::from(input), js_sys::Uint8Array::from(output));
+        verifier.verify_cached(&proof, io_key.to_string(), input, output).await
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_verify_cached() {
+        let map = js_sys::Map::new();
+        let mut verifier = WasmVerifier::new("0xmodel".to_string());
+        verifier.set_proof_cache(Some(map_storage(&map)));
+
+        assert!(!verify(&verifier, "bad", INPUT, b"tampered").await.passed());
+        assert_eq!(map.size(), 0);
+        assert!(verify(&verifier, "good", INPUT, OUTPUT).await.passed());
+        assert_eq!(map.size(), 1);
+
+        // A hit doesn't read the data
+        let hit = verify(&verifier, "good", b"", b"").await;
+        assert!(hit.passed());
+        assert_eq!(hit.checks().len(), verify(&verifier, "good", INPUT, OUTPUT).await.checks().len());
+        assert!(!verify(&verifier, "other", b"", b"").await.passed());
+
+        // Unparsable entries are a miss
+        let key = map.keys().next().unwrap().value();
+        map.set(&key, &"{".into());
+        assert!(!verify(&verifier, "good", b"", b"").await.passed());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_storage_errors_are_ignored() {
+        let source = "return { get() { throw new Error('get'); }, set: () => Promise.reject(new Error('set')) };";
+        let failing = js_sys::Function::new_no_args(source).call0(&JsValue::NULL).unwrap();
+        let mut verifier = WasmVerifier::new("0xmodel".to_string());
+        verifier.set_proof_cache(Some(failing.unchecked_into()));
+        assert!(verify(&verifier, "good", INPUT, OUTPUT).await.passed());
+        assert!(!verify(&verifier, "good", b"", b"").await.passed());
+    }
+}
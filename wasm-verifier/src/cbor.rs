@@ -0,0 +1,21 @@
+// CBOR encoding for ProofData
+// Compact binary form for constrained channels; hex strings travel as raw bytes
+
+use wasm_bindgen::prelude::*;
+
+use crate::ProofData;
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Deserialize from CBOR
+    #[wasm_bindgen]
+    pub fn from_cbor(bytes: &[u8]) -> Result<ProofData, JsValue> {
+        zkml_verifier_core::ProofData::decode_cbor(bytes).map(ProofData).map_err(|e| e.to_js("Invalid ProofData CBOR"))
+    }
+
+    /// Serialize to CBOR (roughly half the size of the JSON form)
+    #[wasm_bindgen]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        self.encode_cbor()
+    }
+}
@@ -0,0 +1,77 @@
+// Pipeline proof chaining
+// Verifies multi-stage pipelines (e.g. embedder -> classifier) where each stage's output feeds the next
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+/// Per-stage results plus the links between stages
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ChainReport(zkml_verifier_core::chain::ChainReport);
+
+wrap!(ChainReport => zkml_verifier_core::chain::ChainReport);
+
+#[wasm_bindgen]
+impl ChainReport {
+    /// Per-stage reports, in pipeline order
+    #[wasm_bindgen(getter)]
+    pub fn stages(&self) -> Vec<VerificationReport> {
+        self.0.stages().iter().cloned().map(VerificationReport::from).collect()
+    }
+
+    /// `links[i]`: stage `i`'s output is stage `i + 1`'s input
+    #[wasm_bindgen(getter)]
+    pub fn links(&self) -> Vec<u8> {
+        self.0.links().iter().map(|&l| l as u8).collect()
+    }
+
+    /// The first stage consumed the pipeline input and the last produced the pipeline output
+    #[wasm_bindgen(getter)]
+    pub fn end_to_end(&self) -> bool {
+        self.0.end_to_end()
+    }
+
+    /// True when every stage passed, every link holds and the ends are bound
+    #[wasm_bindgen(getter)]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a chain of proofs where each stage's output is the next stage's input
+    ///
+    /// `io_buffers` holds the pipeline input, every intermediate tensor and
+    /// the pipeline output, so it has one more entry than `proofs`. Stages
+    /// for different models need a registry verifier (`with_registry`).
+    /// Throws `VerificationError.InvalidArgument` on a length mismatch.
+    #[wasm_bindgen]
+    pub fn verify_chain(
+        &self,
+        proofs: Vec<ProofData>,
+        io_buffers: Vec<js_sys::Uint8Array>,
+    ) -> Result<ChainReport, JsValue> {
+        let proofs: Vec<zkml_verifier_core::ProofData> = proofs.into_iter().map(Into::into).collect();
+        let io_buffers: Vec<Vec<u8>> = io_buffers.iter().map(|a| a.to_vec()).collect();
+        self.verifier
+            .check_chain(&proofs, &io_buffers)
+            .map(ChainReport)
+            .map_err(|e| e.to_js("io_buffers must have exactly one more entry than proofs"))
+    }
+}
@@ -0,0 +1,31 @@
+// Classification semantics
+// Check the committed output's argmax / top-k against a claimed label set, without decoding logits in JS
+
+use wasm_bindgen::prelude::*;
+
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a proof and that its output's highest-scoring class is `label`
+    #[wasm_bindgen]
+    pub fn verify_argmax(&self, proof_data: &ProofData, input: &[u8], output: &[u8], label: u32) -> VerificationReport {
+        self.verifier.top_k_report(proof_data, input, output, &[label]).into()
+    }
+
+    /// Verify a proof and that its output's `labels.length` highest-scoring classes are `labels` (in any order)
+    ///
+    /// Scores are decoded per the proof's `output_spec`, or as raw
+    /// float32 without one; ties rank the lower class index first.
+    #[wasm_bindgen]
+    pub fn verify_top_k(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[u8],
+        labels: Vec<u32>,
+    ) -> VerificationReport {
+        self.verifier.top_k_report(proof_data, input, output, &labels).into()
+    }
+}
@@ -0,0 +1,65 @@
+// Time source
+// Where freshness, nonce and certificate checks read "now" from: the host runtime's clock, or a JS callback
+
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::handle::JsHandle;
+use crate::WasmVerifier;
+
+pub(crate) use zkml_verifier_core::clock::{system_now_ms, ClockSource};
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Callback `() => number` giving the current time in ms since the Unix epoch
+    ///
+    /// ```js
+    /// verifier.clock = () => Temporal.Now.instant().epochMilliseconds;
+    /// verifier.clock = () => fakeNow; // tests
+    /// ```
+    ///
+    /// Used for the timestamp freshness, nonce and certificate validity
+    /// checks, and when issuing nonces or credentials. Without one the
+    /// runtime's clock is used: `Date.now()`, or `performance.timeOrigin +
+    /// performance.now()` where `Date` is unavailable. If the callback throws
+    /// or doesn't return a non-negative number, the runtime's clock is used
+    /// for that reading. `undefined` restores the default.
+    #[wasm_bindgen(setter)]
+    pub fn set_clock(&mut self, callback: Option<js_sys::Function>) {
+        let clock = callback.map_or(ClockSource::System, |callback| {
+            let callback = JsHandle::new(callback);
+            ClockSource::Callback(Arc::new(move || callback.get()?.call0(&JsValue::NULL).ok()?.as_f64()))
+        });
+        self.verifier.set_clock(clock);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hash_data, ProofData, VerificationError, VerifierConfig};
+
+    #[test]
+    fn test_fake_clock_drives_freshness() {
+        let mut config = VerifierConfig::new();
+        config.set_max_age_ms(1_000);
+        config.set_clock_skew_ms(0);
+        let mut verifier = WasmVerifier::with_config("0xmodel".to_string(), &config);
+        let io = hash_data(b"io", None).unwrap();
+        let proof = ProofData::new("0xmodel".to_string(), "0xproof".to_string(), io.clone(), io, 5_000, true);
+
+        verifier.verifier.set_clock(ClockSource::Fixed(5_500));
+        assert_eq!(verifier.now_ms(), 5_500);
+        assert!(verifier.verify_report(&proof, b"io", b"io").passed());
+
+        verifier.verifier.set_clock(ClockSource::Fixed(6_001));
+        assert_eq!(verifier.verify_report(&proof, b"io", b"io").error(), Some(VerificationError::Expired));
+
+        verifier.verifier.set_clock(ClockSource::Fixed(4_999));
+        assert!(!verifier.verify_report(&proof, b"io", b"io").passed());
+
+        verifier.set_clock(None);
+        assert!(verifier.now_ms() >= 1_700_000_000_000);
+    }
+}
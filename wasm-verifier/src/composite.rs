@@ -0,0 +1,99 @@
+// Composite proofs
+// A proof attesting to the verification of other proofs, folded server-side and checked in one call
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+pub use zkml_verifier_core::composite::MAX_COMPOSITE_DEPTH;
+
+/// A folding proof plus the proofs it attests to
+///
+/// The folding proof's `input_hash` commits to the children (the canonical
+/// hashes of their proofs, in order) and its `output_hash` to their
+/// `output_hash`es; see `children_input_hash`/`children_output_hash`. A
+/// child with children of its own is itself a composite.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct CompositeProof(zkml_verifier_core::composite::CompositeProof);
+
+wrap!(CompositeProof => zkml_verifier_core::composite::CompositeProof);
+
+#[wasm_bindgen]
+impl CompositeProof {
+    #[wasm_bindgen(constructor)]
+    pub fn new(proof: ProofData, children: Vec<CompositeProof>) -> CompositeProof {
+        let children = children.into_iter().map(Into::into).collect();
+        CompositeProof(zkml_verifier_core::composite::CompositeProof::new(proof.into(), children))
+    }
+
+    /// Wrap a plain proof as a leaf child
+    #[wasm_bindgen]
+    pub fn leaf(proof: ProofData) -> CompositeProof {
+        CompositeProof(zkml_verifier_core::composite::CompositeProof::leaf(proof.into()))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proof(&self) -> ProofData {
+        self.0.proof().clone().into()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn children(&self) -> Vec<CompositeProof> {
+        self.0.children().iter().cloned().map(CompositeProof).collect()
+    }
+
+    /// Total number of leaf proofs under this composite
+    #[wasm_bindgen(getter)]
+    pub fn leaf_count(&self) -> u32 {
+        self.0.leaf_count()
+    }
+
+    /// `input_hash` the folding proof must carry for these children
+    #[wasm_bindgen]
+    pub fn children_input_hash(&self, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        self.0
+            .children_input_hash(algorithm.unwrap_or_default())
+            .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+
+    /// `output_hash` the folding proof must carry for these children
+    #[wasm_bindgen]
+    pub fn children_output_hash(&self, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        self.0
+            .children_output_hash(algorithm.unwrap_or_default())
+            .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<CompositeProof, JsValue> {
+        zkml_verifier_core::composite::CompositeProof::from_json(json).map(CompositeProof).map_err(|e| {
+            VerificationError::MalformedProof.to_js(&format!("Failed to parse composite proof JSON: {}", e))
+        })
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a composite proof and, recursively, every nested composite
+    ///
+    /// This verifier's model is the folding model. Its input/output checks
+    /// bind the folding proof to its children, a `Composite` check reports
+    /// the first failing nested composite. Leaf proofs are covered by the
+    /// folding proof and aren't re-checked individually.
+    #[wasm_bindgen]
+    pub fn verify_composite(&self, composite: &CompositeProof) -> VerificationReport {
+        self.verifier.composite_report(composite).into()
+    }
+}
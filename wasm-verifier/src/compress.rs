@@ -0,0 +1,42 @@
+// Compressed proof payloads
+// `verify_compressed` over the core's bounded gzip/zstd decompression
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::{Failure, VerificationError};
+use crate::{ProofData, WasmVerifier};
+
+pub(crate) use zkml_verifier_core::compress::decompress;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify proof JSON and an optional JOLT proof blob, either of which may be gzip- or zstd-compressed
+    ///
+    /// Uncompressed input is accepted as-is. Throws
+    /// `VerificationError.PayloadTooLarge` if either payload decompresses to
+    /// more than `config.max_decompressed_bytes`.
+    #[wasm_bindgen]
+    pub fn verify_compressed(
+        &self,
+        proof_json: &[u8],
+        proof_blob: Option<Vec<u8>>,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<bool, JsValue> {
+        let to_js = |(error, reason): Failure| error.to_js(reason);
+        let limit = self.verifier.config().max_decompressed_bytes();
+
+        let json = decompress(proof_json, limit).map_err(to_js)?;
+        let json = std::str::from_utf8(&json)
+            .map_err(|_| VerificationError::MalformedProof.to_js("Proof JSON is not UTF-8"))?;
+        let proof_data = ProofData::parse_json(json)?;
+
+        match proof_blob {
+            Some(blob) => {
+                let blob = decompress(&blob, limit).map_err(to_js)?;
+                self.verify_with_proof_bytes(&proof_data, &blob, input_bytes, output_bytes)
+            }
+            None => self.verify(&proof_data, input_bytes, output_bytes),
+        }
+    }
+}
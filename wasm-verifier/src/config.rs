@@ -0,0 +1,143 @@
+// Verifier configuration
+// Policy knobs for the checks performed by `WasmVerifier::verify`
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::hash::HashAlgorithm;
+
+/// Verification policy exposed to JS
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct VerifierConfig(zkml_verifier_core::VerifierConfig);
+
+wrap!(VerifierConfig => zkml_verifier_core::VerifierConfig);
+
+#[wasm_bindgen]
+impl VerifierConfig {
+    /// Create config with the default policy (1 hour max age, 1 minute skew)
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> VerifierConfig {
+        VerifierConfig::default()
+    }
+
+    /// Config for archival verification: timestamps are not checked
+    #[wasm_bindgen]
+    pub fn archival() -> VerifierConfig {
+        VerifierConfig(zkml_verifier_core::VerifierConfig::archival())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn max_age_ms(&self) -> u64 {
+        self.0.max_age_ms()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_age_ms(&mut self, max_age_ms: u64) {
+        self.0.set_max_age_ms(max_age_ms);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn check_freshness(&self) -> bool {
+        self.0.check_freshness()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_check_freshness(&mut self, check_freshness: bool) {
+        self.0.set_check_freshness(check_freshness);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn clock_skew_ms(&self) -> u64 {
+        self.0.clock_skew_ms()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_clock_skew_ms(&mut self, clock_skew_ms: u64) {
+        self.0.set_clock_skew_ms(clock_skew_ms);
+    }
+
+    /// Hash algorithm assumed for proofs that don't declare `hash_alg`
+    #[wasm_bindgen(getter)]
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.0.hash_algorithm()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_hash_algorithm(&mut self, hash_algorithm: HashAlgorithm) {
+        self.0.set_hash_algorithm(hash_algorithm);
+    }
+
+    /// Challenge-response mode: proofs must carry a nonce from `issue_nonce`
+    #[wasm_bindgen(getter)]
+    pub fn require_nonce(&self) -> bool {
+        self.0.require_nonce()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_require_nonce(&mut self, require_nonce: bool) {
+        self.0.set_require_nonce(require_nonce);
+    }
+
+    /// Reject proofs without a valid prover signature
+    #[wasm_bindgen(getter)]
+    pub fn require_signature(&self) -> bool {
+        self.0.require_signature()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_require_signature(&mut self, require_signature: bool) {
+        self.0.set_require_signature(require_signature);
+    }
+
+    /// Minimum number of BLS attestors that must co-sign (0 = not required)
+    #[wasm_bindgen(getter)]
+    pub fn min_attestors(&self) -> u32 {
+        self.0.min_attestors()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_min_attestors(&mut self, min_attestors: u32) {
+        self.0.set_min_attestors(min_attestors);
+    }
+
+    /// Largest payload `verify_compressed` will inflate (default 64 MiB)
+    #[wasm_bindgen(getter)]
+    pub fn max_decompressed_bytes(&self) -> u64 {
+        self.0.max_decompressed_bytes()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_decompressed_bytes(&mut self, max_decompressed_bytes: u64) {
+        self.0.set_max_decompressed_bytes(max_decompressed_bytes);
+    }
+
+    /// Largest proof `verify_from_url` will download (default 16 MiB)
+    #[wasm_bindgen(getter)]
+    pub fn max_proof_bytes(&self) -> u64 {
+        self.0.max_proof_bytes()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_proof_bytes(&mut self, max_proof_bytes: u64) {
+        self.0.set_max_proof_bytes(max_proof_bytes);
+    }
+
+    /// Largest input, output or proof blob accepted for hashing (0 = no limit)
+    ///
+    /// Larger ones fail their hash check with `PayloadTooLarge` before any
+    /// hashing. A `Uint8Array` passed to `verify` has already been copied
+    /// into WASM memory by then: check sizes up front, or use `IoBuffer` or
+    /// `verify_streams`, to stay within a memory budget.
+    #[wasm_bindgen(getter)]
+    pub fn max_input_bytes(&self) -> u64 {
+        self.0.max_input_bytes()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_input_bytes(&mut self, max_input_bytes: u64) {
+        self.0.set_max_input_bytes(max_input_bytes);
+    }
+}
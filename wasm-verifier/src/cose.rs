@@ -0,0 +1,49 @@
+// COSE_Sign1 proof envelopes
+// `verify_cose` over the core's COSE_Sign1 (RFC 9052) parsing and signature check
+
+use wasm_bindgen::prelude::*;
+
+use crate::hash::IoSource;
+use crate::report::VerificationReport;
+use crate::signature::Failure;
+use crate::{ProofData, WasmVerifier};
+
+pub(crate) use zkml_verifier_core::cose::CoseSign1;
+
+impl WasmVerifier {
+    /// Check the envelope signature, then run the usual checks on its payload
+    pub(crate) fn cose_report(
+        &self,
+        envelope: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, Failure> {
+        let cose = CoseSign1::parse(envelope)?;
+        let proof_data = ProofData(cose.proof()?);
+
+        let mut report = self.new_report();
+        self.check_cose(&mut report, &cose, &proof_data);
+        self.run_checks(&mut report, &proof_data, IoSource::Bytes(input_bytes), IoSource::Bytes(output_bytes));
+        Ok(report)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a proof wrapped in a COSE_Sign1 envelope
+    ///
+    /// The envelope signature (EdDSA or ES256K) takes the place of the
+    /// in-proof `signature`; its key is found like a proof signer's, with
+    /// the COSE `kid` header naming a `KeyResolver` key. Throws
+    /// `VerificationError.MalformedProof` if the envelope cannot be parsed.
+    #[wasm_bindgen]
+    pub fn verify_cose(
+        &self,
+        envelope: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, JsValue> {
+        self.cose_report(envelope, input_bytes, output_bytes)
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+}
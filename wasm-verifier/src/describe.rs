@@ -0,0 +1,84 @@
+// Proof inspection
+// `describe`: decodes a proof in any supported encoding and lists what it claims, for debugging failed verifications
+
+use wasm_bindgen::prelude::*;
+use serde::Serialize;
+
+use crate::boundary::wrap;
+use crate::{clock, typescript, ProofData};
+
+/// What `describe` found in a proof
+#[derive(Serialize, Debug, Clone)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ProofDescription(zkml_verifier_core::describe::ProofDescription);
+
+wrap!(ProofDescription => zkml_verifier_core::describe::ProofDescription);
+
+#[wasm_bindgen]
+impl ProofDescription {
+    /// Encoding the proof was decoded from: `json`, `binary`, `cbor`, `protobuf`, `jws` or `cose`
+    #[wasm_bindgen(getter)]
+    pub fn format(&self) -> String {
+        self.0.format().to_string()
+    }
+
+    /// `gzip` or `zstd` if the proof was compressed
+    #[wasm_bindgen(getter)]
+    pub fn compression(&self) -> Option<String> {
+        self.0.compression().map(str::to_string)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id().to_string()
+    }
+
+    /// The decoded proof (the payload, for JWS and COSE envelopes)
+    #[wasm_bindgen(getter)]
+    pub fn proof(&self) -> ProofData {
+        ProofData(self.0.proof().clone())
+    }
+
+    /// Every commitment with the algorithm it's checked with
+    #[wasm_bindgen(getter, unchecked_return_type = "CommitmentJson[]")]
+    pub fn commitments(&self) -> JsValue {
+        typescript::to_object(&self.0.commitments())
+    }
+
+    /// Keys, addresses, DIDs and certificate subjects the proof names as signers
+    #[wasm_bindgen(getter, unchecked_return_type = "SignerJson[]")]
+    pub fn signers(&self) -> JsValue {
+        typescript::to_object(&self.0.signers())
+    }
+
+    /// Milliseconds since the proof's `timestamp`; negative if it's in the future
+    #[wasm_bindgen(getter)]
+    pub fn age_ms(&self) -> f64 {
+        self.0.age_ms()
+    }
+
+    /// Schema problems: unknown fields, undecodable values, half-present signatures
+    #[wasm_bindgen(getter)]
+    pub fn issues(&self) -> Vec<String> {
+        self.0.issues().to_vec()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Decode a proof in any encoding the verifier reads and list its fields, algorithms, signers, age and schema issues
+///
+/// Takes the raw bytes of a JSON, binary, CBOR, protobuf, JWS or COSE proof,
+/// optionally gzip- or zstd-compressed. Nothing is verified: use this to see
+/// why a proof fails. Throws `VerificationError.MalformedProof` if the bytes
+/// can't be decoded.
+#[wasm_bindgen]
+pub fn describe(bytes: &[u8]) -> Result<ProofDescription, JsValue> {
+    zkml_verifier_core::describe::describe(bytes, clock::system_now_ms())
+        .map(ProofDescription)
+        .map_err(|(error, reason)| error.to_js(&reason))
+}
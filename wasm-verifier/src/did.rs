@@ -0,0 +1,19 @@
+// DID prover identifiers
+// Resolves `did:key` locally and `did:web` through a JS fetch callback
+
+use wasm_bindgen::prelude::*;
+
+use crate::boundary;
+use crate::WasmVerifier;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Callback `(url) => string | undefined` returning `did:web` documents
+    ///
+    /// Must return the DID document JSON synchronously, e.g. from a cache
+    /// your app fills with `fetch`.
+    #[wasm_bindgen(setter)]
+    pub fn set_did_fetcher(&mut self, fetcher: Option<js_sys::Function>) {
+        self.verifier.set_did_fetcher(fetcher.map(boundary::fetcher));
+    }
+}
@@ -0,0 +1,100 @@
+// EAS attestations
+// Turns a passed verification into an Ethereum Attestation Service `attest` request
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+pub use zkml_verifier_core::eas::EAS_SCHEMA;
+
+/// An `attest` request, ready for the EAS SDK or contract
+///
+/// `to_json` gives the `AttestationRequest` shape:
+///
+/// ```json
+/// { "schema": "0x…", "data": { "recipient": "0x…", "expirationTime": 0, "revocable": true,
+///   "refUID": "0x00…", "data": "0x…", "value": 0 } }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct EasAttestation(zkml_verifier_core::eas::EasAttestation);
+
+wrap!(EasAttestation => zkml_verifier_core::eas::EasAttestation);
+
+#[wasm_bindgen]
+impl EasAttestation {
+    /// UID of the schema the data is encoded with
+    #[wasm_bindgen(getter)]
+    pub fn schema_uid(&self) -> String {
+        self.0.schema_uid().to_string()
+    }
+
+    /// ABI-encoded attestation data (hex)
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> String {
+        self.0.data().to_string()
+    }
+
+    /// Address the attestation is about (zero address if none)
+    #[wasm_bindgen(getter)]
+    pub fn recipient(&self) -> String {
+        self.0.recipient().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn revocable(&self) -> bool {
+        self.0.revocable()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// EAS attestation request for a passed verification
+    ///
+    /// ```js
+    /// const report = verifier.verify_report(proof, input, output);
+    /// const { schema, data } = JSON.parse(verifier.eas_attestation(proof, report, userAddress).to_json());
+    /// await eas.attest({ schema, data: { ...data, expirationTime: 0n, value: 0n } });
+    /// ```
+    ///
+    /// The data encodes `EAS_SCHEMA` (see `eas_schema`): the proof's
+    /// commitments, its ID, when it was proven and when it was verified.
+    /// `schema_uid` defaults to the schema registered revocable with no
+    /// resolver (`eas_schema_uid()`). Throws `VerificationError.InvalidArgument`
+    /// if the report did not pass, an address is malformed or a commitment
+    /// isn't a 32-byte digest.
+    #[wasm_bindgen]
+    pub fn eas_attestation(
+        &self,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+        recipient: Option<String>,
+        schema_uid: Option<String>,
+    ) -> Result<EasAttestation, JsValue> {
+        self.verifier
+            .eas_attestation(proof_data, report, recipient.as_deref(), schema_uid.as_deref())
+            .map(EasAttestation)
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+}
+
+/// `EAS_SCHEMA`, for registering it with EAS's SchemaRegistry
+#[wasm_bindgen]
+pub fn eas_schema() -> String {
+    EAS_SCHEMA.to_string()
+}
+
+/// UID of `EAS_SCHEMA` as registered with `resolver` (default none) and `revocable`
+#[wasm_bindgen]
+pub fn eas_schema_uid(resolver: Option<String>, revocable: bool) -> Result<String, JsValue> {
+    zkml_verifier_core::eas::schema_uid(resolver.as_deref(), revocable).map_err(|(error, reason)| error.to_js(reason))
+}
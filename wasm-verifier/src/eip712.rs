@@ -0,0 +1,93 @@
+// EIP-712 proof signatures
+// `signature_alg = "eip712"`: wallet signatures (`eth_signTypedData_v4`) over a proof as typed data
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::{ProofData, WasmVerifier};
+
+/// EIP-712 signing domain; defaults to `{ name: "zkML Proof", version: "1" }`
+///
+/// Contracts that check proof signatures should pin `chain_id` and
+/// `verifying_contract` so signatures can't be replayed elsewhere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct Eip712Domain(zkml_verifier_core::Eip712Domain);
+
+wrap!(Eip712Domain => zkml_verifier_core::Eip712Domain);
+
+#[wasm_bindgen]
+impl Eip712Domain {
+    /// Domain with the given fields (`name` and `version` default as above)
+    ///
+    /// Throws `VerificationError.InvalidArgument` if `verifying_contract`
+    /// isn't a 20-byte hex address.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        name: Option<String>,
+        version: Option<String>,
+        chain_id: Option<u64>,
+        verifying_contract: Option<String>,
+    ) -> Result<Eip712Domain, JsValue> {
+        zkml_verifier_core::Eip712Domain::try_new(name, version, chain_id, verifying_contract.as_deref())
+            .map(Eip712Domain)
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> String {
+        self.0.version().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn chain_id(&self) -> Option<u64> {
+        self.0.chain_id()
+    }
+
+    /// Checksummed address of the verifying contract
+    #[wasm_bindgen(getter)]
+    pub fn verifying_contract(&self) -> Option<String> {
+        self.0.verifying_contract()
+    }
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Typed data to sign with a wallet, as JSON for `eth_signTypedData_v4`
+    ///
+    /// ```js
+    /// proof.signature_alg = "eip712";
+    /// proof.signature = await ethereum.request({
+    ///   method: "eth_signTypedData_v4", params: [address, proof.eip712_typed_data()] });
+    /// ```
+    ///
+    /// The `Proof` message carries the commitments, timestamp, `verified`
+    /// and nonce, plus `extensionsHash` over every other signed field.
+    /// Throws `VerificationError.InvalidArgument` unless the commitments are
+    /// 32-byte digests.
+    #[wasm_bindgen]
+    pub fn eip712_typed_data(&self, domain: Option<Eip712Domain>) -> Result<String, JsValue> {
+        self.0.eip712_typed_data(&domain.unwrap_or_default()).map_err(|(error, reason)| error.to_js(reason))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Domain `eip712` proof signatures are checked under (`undefined` resets the default)
+    #[wasm_bindgen(setter)]
+    pub fn set_eip712_domain(&mut self, domain: Option<Eip712Domain>) {
+        self.verifier.set_eip712_domain(domain.map(Into::into));
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn eip712_domain(&self) -> Eip712Domain {
+        Eip712Domain(self.verifier.eip712_domain().clone())
+    }
+}
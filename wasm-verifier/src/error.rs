@@ -0,0 +1,6 @@
+// Verification error codes
+// Stable numeric codes so JS callers can branch on failure type
+
+pub use zkml_verifier_core::error::VerificationError;
+
+pub(crate) use zkml_verifier_core::error::Failure;
@@ -0,0 +1,158 @@
+// Check events
+// JS callbacks fired as each check starts and finishes, and as large I/O is hashed, for live progress UIs
+
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+use zkml_verifier_core::report::CheckObserver;
+
+use crate::handle::JsHandle;
+use crate::report::{Check, CheckResult};
+use crate::{VerificationReport, WasmVerifier};
+
+/// Callbacks a report notifies as checks are recorded
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CheckEvents {
+    started: Option<JsHandle<js_sys::Function>>,
+    passed: Option<JsHandle<js_sys::Function>>,
+    failed: Option<JsHandle<js_sys::Function>>,
+}
+
+impl CheckObserver for CheckEvents {
+    fn started(&self, check: Check) {
+        if let Some(callback) = self.started.as_ref().and_then(JsHandle::get) {
+            let _ = callback.call1(&JsValue::NULL, &check.into());
+        }
+    }
+
+    fn finished(&self, result: &zkml_verifier_core::CheckResult) {
+        let callback = if result.passed() { &self.passed } else { &self.failed };
+        if let Some(callback) = callback.as_ref().and_then(JsHandle::get) {
+            let _ = callback.call1(&JsValue::NULL, &CheckResult::from(result.clone()).into());
+        }
+    }
+}
+
+/// Running byte count passed to a `(processed, total) => void` callback
+#[cfg(feature = "async")]
+pub(crate) struct HashProgress<'a> {
+    callback: Option<&'a js_sys::Function>,
+    processed: u64,
+    /// `undefined` for the callback when unknown (streams)
+    total: Option<u64>,
+}
+
+#[cfg(feature = "async")]
+impl<'a> HashProgress<'a> {
+    pub(crate) fn new(callback: Option<&'a js_sys::Function>, total: Option<u64>) -> Self {
+        HashProgress { callback, processed: 0, total }
+    }
+
+    /// Count `bytes` more as hashed and notify the callback
+    pub(crate) fn advance(&mut self, bytes: usize) {
+        self.processed += bytes as u64;
+        if let Some(callback) = self.callback {
+            let total = self.total.map_or(JsValue::UNDEFINED, |total| JsValue::from(total as f64));
+            let _ = callback.call2(&JsValue::NULL, &JsValue::from(self.processed as f64), &total);
+        }
+    }
+}
+
+impl WasmVerifier {
+    /// Empty report wired to this verifier's check callbacks
+    pub(crate) fn new_report(&self) -> VerificationReport {
+        self.verifier.new_report().into()
+    }
+
+    /// Hand the current callbacks to the core verifier, which wires them into its reports
+    fn observe(&mut self) {
+        self.verifier.set_observer(Some(Arc::new(self.check_events.clone())));
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Callback `(check: Check) => void`, fired as each check begins
+    ///
+    /// ```js
+    /// verifier.on_check_started = (check) => row(check).classList.add("running");
+    /// verifier.on_check_passed = (result) => row(result.check).textContent += " ✓";
+    /// verifier.on_check_failed = (result) => row(result.check).textContent += ` ✗ ${result.reason}`;
+    /// const report = await verifier.verify_report_async(proof, input, output);
+    /// ```
+    ///
+    /// Every check that ends up in the report is announced once, before
+    /// its outcome; outcomes fire in report order. In the async and
+    /// streaming paths the I/O hash checks are announced before their data
+    /// is hashed, so the UI can show them as running meanwhile. Exceptions
+    /// thrown by the callbacks are ignored.
+    #[wasm_bindgen(setter)]
+    pub fn set_on_check_started(&mut self, callback: Option<js_sys::Function>) {
+        self.check_events.started = callback.map(JsHandle::new);
+        self.observe();
+    }
+
+    /// Callback `(result: CheckResult) => void`, fired as each check passes
+    #[wasm_bindgen(setter)]
+    pub fn set_on_check_passed(&mut self, callback: Option<js_sys::Function>) {
+        self.check_events.passed = callback.map(JsHandle::new);
+        self.observe();
+    }
+
+    /// Callback `(result: CheckResult) => void`, fired as each check fails
+    #[wasm_bindgen(setter)]
+    pub fn set_on_check_failed(&mut self, callback: Option<js_sys::Function>) {
+        self.check_events.failed = callback.map(JsHandle::new);
+        self.observe();
+    }
+
+    /// Callback `(processed: number, total?: number) => void`, fired as I/O is hashed
+    ///
+    /// ```js
+    /// verifier.on_hash_progress = (done, total) => (bar.value = done / total);
+    /// const ok = await verifier.verify_async(proof, input, output);
+    /// ```
+    ///
+    /// Counts input and output bytes together, once per hashed chunk, in
+    /// `verify_async`, `verify_report_async` and `verify_streams`. `total`
+    /// is their combined length, or `undefined` for streams. Exceptions
+    /// thrown by the callback are ignored.
+    #[cfg(feature = "async")]
+    #[wasm_bindgen(setter)]
+    pub fn set_on_hash_progress(&mut self, callback: Option<js_sys::Function>) {
+        self.hash_progress = callback;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Stopwatch;
+
+    #[test]
+    fn test_checks_announced_once() {
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let mut report = verifier.new_report();
+
+        // Announced ahead of a spec check recorded in between
+        assert!(report.announce(Check::InputHash));
+        assert!(!report.announce(Check::InputHash));
+        report.record(Check::InputSpec, true, "", Stopwatch::start());
+        let started = report.start(Check::InputHash);
+        assert!(!report.announce(Check::InputHash));
+        report.record(Check::InputHash, true, "", started);
+
+        // Finished checks are announced afresh if recorded again
+        assert!(report.announce(Check::InputHash));
+        assert_eq!(report.checks().len(), 2);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_hash_progress_counts_bytes() {
+        let mut progress = HashProgress::new(None, Some(10));
+        progress.advance(4);
+        progress.advance(6);
+        assert_eq!(progress.processed, 10);
+    }
+}
@@ -0,0 +1,35 @@
+// EVM calldata
+// ABI-encodes a proof's commitments for an on-chain verifier contract's `verifyInference` call
+
+use wasm_bindgen::prelude::*;
+
+use crate::{hex, ProofData};
+pub use zkml_verifier_core::evm::EVM_VERIFY_SIGNATURE;
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Calldata (hex) for the proof's `verifyInference` call on an on-chain verifier
+    ///
+    /// ```js
+    /// await signer.sendTransaction({ to: verifierAddress, data: proof.to_evm_calldata() });
+    /// ```
+    ///
+    /// Encodes `verifyInference(bytes32 modelHash, bytes32 inputHash,
+    /// bytes32 outputHash, bytes32 proofHash, uint64 timestamp, bytes32 nonce,
+    /// bytes signature)` (see `evm_verifier_abi`). Hash tags are dropped, and
+    /// a missing nonce or signature encodes as zero or empty. Throws
+    /// `VerificationError.InvalidArgument` unless every commitment is a
+    /// 32-byte digest.
+    #[wasm_bindgen]
+    pub fn to_evm_calldata(&self) -> Result<String, JsValue> {
+        self.evm_calldata()
+            .map(|calldata| format!("0x{}", hex::encode(&calldata)))
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+}
+
+/// ABI fragment of the verifier function `to_evm_calldata` calls, for `new ethers.Interface([...])`
+#[wasm_bindgen]
+pub fn evm_verifier_abi() -> String {
+    zkml_verifier_core::evm::EVM_VERIFY_ABI.to_string()
+}
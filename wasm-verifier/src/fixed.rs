@@ -0,0 +1,38 @@
+// Fixed-point conversions
+// The float <-> scaled integer mapping JOLT-Atlas's onnx-tracer applies to model I/O
+
+use wasm_bindgen::prelude::*;
+
+use crate::tolerance::OutputTolerance;
+
+/// Convert floats to fixed-point integers with `scale` fractional bits
+///
+/// Throws `VerificationError.InvalidArgument` if a value is NaN, infinite
+/// or out of the i32 range once scaled.
+#[wasm_bindgen]
+pub fn to_fixed_point(values: &[f32], scale: i32) -> Result<Vec<i32>, JsValue> {
+    zkml_verifier_core::fixed::to_fixed_point(values, scale)
+        .map_err(|e| e.to_js("Value cannot be represented at this fixed-point scale"))
+}
+
+/// Convert fixed-point integers with `scale` fractional bits back to floats
+#[wasm_bindgen]
+pub fn from_fixed_point(values: &[i32], scale: i32) -> Vec<f32> {
+    zkml_verifier_core::fixed::from_fixed_point(values, scale)
+}
+
+/// Fixed-point I/O encoding the prover commits to: the integers as i32 little-endian
+#[wasm_bindgen]
+pub fn encode_fixed_point(values: &[f32], scale: i32) -> Result<Vec<u8>, JsValue> {
+    zkml_verifier_core::fixed::encode_fixed_point(values, scale)
+        .map_err(|e| e.to_js("Value cannot be represented at this fixed-point scale"))
+}
+
+#[wasm_bindgen]
+impl OutputTolerance {
+    /// Tolerance over fixed-point outputs (`encode_fixed_point` with `scale`), `epsilon` steps apart
+    #[wasm_bindgen]
+    pub fn fixed_point(scale: i32, epsilon: u32) -> OutputTolerance {
+        zkml_verifier_core::tolerance::OutputTolerance::fixed_point(scale, epsilon).into()
+    }
+}
@@ -0,0 +1,32 @@
+// Test fixtures
+// The core's fixture proofs, as the JS-facing `ProofData`
+
+#[cfg(feature = "extras")]
+use crate::HashAlgorithm;
+use crate::ProofData;
+
+/// Fresh, verified proof for `model_hash` over `INPUT` and `OUTPUT`, with the default hash
+pub(crate) fn proof(model_hash: &str) -> ProofData {
+    zkml_verifier_core::fixtures::proof(model_hash).into()
+}
+
+/// Fresh, verified proof for `model_hash` over `input` and `output`, hashed with `algorithm`
+#[cfg(feature = "extras")]
+pub(crate) fn proof_for(model_hash: &str, input: &[u8], output: &[u8], algorithm: HashAlgorithm) -> ProofData {
+    zkml_verifier_core::fixtures::proof_for(model_hash, input, output, algorithm).into()
+}
+
+/// `ReadableStream` yielding `chunks` one per pull, then closing
+#[cfg(all(target_arch = "wasm32", feature = "async"))]
+pub(crate) fn stream(chunks: &[&[u8]]) -> crate::readable::ReadableStream {
+    use wasm_bindgen::{JsCast, JsValue};
+    let chunks: js_sys::Array = chunks.iter().map(|chunk| js_sys::Uint8Array::from(*chunk)).collect();
+    let source = "return new ReadableStream({ pull(c) { chunks.length ? c.enqueue(chunks.shift()) : c.close(); } });";
+    js_sys::Function::new_with_args("chunks", source).call1(&JsValue::NULL, &chunks).unwrap().unchecked_into()
+}
+
+/// `kind` of a thrown `VerificationError`
+#[cfg(all(target_arch = "wasm32", feature = "async"))]
+pub(crate) fn kind(error: &wasm_bindgen::JsValue) -> Option<String> {
+    js_sys::Reflect::get(error, &"kind".into()).ok()?.as_string()
+}
@@ -0,0 +1,134 @@
+// Filesystem I/O
+// Path-based proof loading and verification for WASI hosts (wasmtime, Spin), reading from preopened dirs
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use zkml_verifier_core::binary::BINARY_MAGIC;
+use zkml_verifier_core::hash::StreamingHasher;
+
+use crate::compress::decompress;
+use crate::error::{Failure, VerificationError};
+use crate::hash::{HashAlgorithm, IoSource};
+use crate::report::Check;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Bytes read from an I/O file per hasher update
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Parse a proof file's contents: JSON or `ZKPF` binary, optionally gzip- or zstd-compressed
+fn parse_proof(bytes: &[u8], max_decompressed_bytes: u64) -> Result<ProofData, Failure> {
+    let bytes = decompress(bytes, max_decompressed_bytes)?;
+    if bytes.starts_with(&BINARY_MAGIC) {
+        zkml_verifier_core::ProofData::from_binary(&bytes).map(ProofData).map_err(|e| (e, "Invalid binary proof"))
+    } else {
+        serde_json::from_slice(&bytes).map_err(|_| (VerificationError::MalformedProof, "Invalid proof JSON"))
+    }
+}
+
+/// Hash the file at `path` with the algorithm `commitment` will be checked with, a chunk at a time
+///
+/// When no algorithm can be settled on the file is left unread, and
+/// `check_commitment` records the error as it would for a buffer.
+fn file_source(
+    path: &Path,
+    commitment: &str,
+    alg: &Result<HashAlgorithm, String>,
+    unreadable: &'static str,
+) -> Result<IoSource<'static>, Failure> {
+    let Some(mut hasher) = StreamingHasher::for_commitment(commitment, alg) else {
+        return Ok(IoSource::Bytes(&[]));
+    };
+    let mut file = File::open(path).map_err(|_| (VerificationError::InvalidArgument, unreadable))?;
+    let mut chunk = vec![0u8; READ_CHUNK_BYTES];
+    loop {
+        match file.read(&mut chunk) {
+            Ok(0) => return Ok(hasher.finalize_source()),
+            Ok(n) => hasher.update(&chunk[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(_) => return Err((VerificationError::InvalidArgument, unreadable)),
+        }
+    }
+}
+
+impl WasmVerifier {
+    /// Load the proof file at `path`, within this verifier's `max_proof_bytes` and `max_decompressed_bytes`
+    ///
+    /// JSON and `ZKPF` binary proofs are told apart by their first bytes,
+    /// after any gzip or zstd compression is undone. Fails with
+    /// `ProofUnavailable` if the file can't be read.
+    pub fn load_proof(&self, path: impl AsRef<Path>) -> Result<ProofData, Failure> {
+        let config = self.verifier.config();
+        let unavailable = |_| (VerificationError::ProofUnavailable, "Cannot read proof file");
+        let file = File::open(path).map_err(unavailable)?;
+        let mut bytes = Vec::new();
+        file.take(config.max_proof_bytes().saturating_add(1)).read_to_end(&mut bytes).map_err(unavailable)?;
+        if bytes.len() as u64 > config.max_proof_bytes() {
+            return Err((VerificationError::PayloadTooLarge, "Proof file exceeds max_proof_bytes"));
+        }
+        parse_proof(&bytes, config.max_decompressed_bytes())
+    }
+
+    /// `verify_report` on files: the proof at `proof`, checked against the files at `input` and `output`
+    ///
+    /// The I/O files are hashed as they are read and never held in memory.
+    /// `Err` only if a file can't be read or the proof can't be parsed: a
+    /// mismatch is a failed check in the report, as with buffers.
+    pub fn verify_files(
+        &self,
+        proof: impl AsRef<Path>,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<VerificationReport, Failure> {
+        let proof_data = self.load_proof(proof)?;
+        let alg = self.hash_algorithm_for(&proof_data);
+        let mut report = self.new_report();
+        report.announce(Check::InputHash);
+        let input = file_source(input.as_ref(), &proof_data.input_hash, &alg, "Cannot read input file")?;
+        report.announce(Check::OutputHash);
+        let output = file_source(output.as_ref(), &proof_data.output_hash, &alg, "Cannot read output file")?;
+        self.run_checks(&mut report, &proof_data, input, output);
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use zkml_verifier_core::fixtures::{INPUT, OUTPUT};
+
+    #[test]
+    fn test_verify_files() {
+        // WASI has no temp_dir() or process id: run under `wasmtime --dir /tmp --env TMPDIR=/tmp`
+        let tmp = std::env::var_os("TMPDIR").map_or_else(std::env::temp_dir, std::path::PathBuf::from);
+        let dir = tmp.join(format!("zkml-wasm-verifier-fs-{}", crate::clock::system_now_ms()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let proof = fixtures::proof("0xmodel");
+        let path = |name: &str| dir.join(name);
+        std::fs::write(path("proof.json"), proof.to_json()).unwrap();
+        std::fs::write(path("proof.bin"), proof.to_binary()).unwrap();
+        std::fs::write(path("in.bin"), INPUT).unwrap();
+        std::fs::write(path("out.bin"), OUTPUT).unwrap();
+        std::fs::write(path("tampered.bin"), b"tampered").unwrap();
+
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        assert!(verifier.verify_files(path("proof.json"), path("in.bin"), path("out.bin")).unwrap().passed());
+        assert!(verifier.verify_files(path("proof.bin"), path("in.bin"), path("out.bin")).unwrap().passed());
+        let tampered = verifier.verify_files(path("proof.json"), path("in.bin"), path("tampered.bin")).unwrap();
+        assert_eq!(tampered.error(), Some(VerificationError::OutputHashMismatch));
+
+        let missing = verifier.verify_files(path("missing.json"), path("in.bin"), path("out.bin")).unwrap_err();
+        assert_eq!(missing.0, VerificationError::ProofUnavailable);
+        let missing = verifier.verify_files(path("proof.json"), path("missing.bin"), path("out.bin")).unwrap_err();
+        assert_eq!(missing, (VerificationError::InvalidArgument, "Cannot read input file"));
+        assert_eq!(verifier.load_proof(path("in.bin")).unwrap_err().0, VerificationError::MalformedProof);
+
+        let mut config = crate::VerifierConfig::default();
+        config.set_max_proof_bytes(8);
+        let limited = WasmVerifier::with_config("0xmodel".to_string(), &config);
+        assert_eq!(limited.load_proof(path("proof.json")).unwrap_err().0, VerificationError::PayloadTooLarge);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
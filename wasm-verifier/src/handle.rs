@@ -0,0 +1,85 @@
+// JS handles
+// JS values the core `Verifier` can hold while staying `Send + Sync`: the value itself stays on its thread
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Source of slot ids, unique across threads so a lookup on another thread finds nothing
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Values of the handles created on this thread
+    static SLOTS: RefCell<HashMap<u64, JsValue>> = RefCell::new(HashMap::new());
+}
+
+/// Frees its value when the last clone of a handle drops (leaked if that happens on another thread)
+#[derive(Debug)]
+struct Slot(u64);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        let _ = SLOTS.try_with(|slots| slots.borrow_mut().remove(&self.0));
+    }
+}
+
+/// A JS value, reachable only from the thread that wrapped it
+///
+/// Core callbacks must be `Send + Sync` so a native `Verifier` can be shared
+/// between threads. A handle only holds an id into a thread-local table, so
+/// it is, and on any other thread (a wasm worker, say) `get` returns `None`
+/// and the callback acts as if unset.
+#[derive(Debug)]
+pub(crate) struct JsHandle<T> {
+    slot: Arc<Slot>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for JsHandle<T> {
+    fn clone(&self) -> Self {
+        JsHandle { slot: self.slot.clone(), value: PhantomData }
+    }
+}
+
+impl<T: JsCast> JsHandle<T> {
+    pub(crate) fn new(value: T) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        SLOTS.with(|slots| slots.borrow_mut().insert(id, value.into()));
+        JsHandle { slot: Arc::new(Slot(id)), value: PhantomData }
+    }
+
+    /// The value, if called on the thread that wrapped it
+    ///
+    /// Returns a clone of the JS reference, so the callback it names may
+    /// itself create or drop handles.
+    pub(crate) fn get(&self) -> Option<T> {
+        SLOTS.with(|slots| slots.borrow().get(&self.slot.0).cloned()).map(JsCast::unchecked_into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn test_handle_is_local_to_its_thread() {
+        let handle = JsHandle::new(JsValue::NULL);
+        assert_send_sync(&handle);
+        let id = handle.slot.0;
+        assert!(SLOTS.with(|slots| slots.borrow().contains_key(&id)));
+        let elsewhere = handle.clone();
+        assert!(std::thread::spawn(move || elsewhere.get().is_none()).join().unwrap());
+
+        let clone = handle.clone();
+        drop(handle);
+        assert!(SLOTS.with(|slots| slots.borrow().contains_key(&id)));
+        drop(clone);
+        assert!(!SLOTS.with(|slots| slots.borrow().contains_key(&id)));
+    }
+}
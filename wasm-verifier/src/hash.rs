@@ -0,0 +1,80 @@
+// Hashing
+// Commitments over I/O buffers, one-shot or chunk-by-chunk
+
+use wasm_bindgen::prelude::*;
+
+use crate::boundary::wrap;
+pub use zkml_verifier_core::hash::HashAlgorithm;
+pub(crate) use zkml_verifier_core::hash::{hash_bytes, hash_bytes_tagged, IoSource};
+
+/// Smallest module using a v128 instruction, as probed by wasm-feature-detect
+const SIMD_PROBE: [u8; 31] = [
+    0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 123, 3, 2, 1, 0, 10, 10, 1, 8, 0, 65, 0, 253, 15, 253, 98, 11,
+];
+
+/// Whether this build hashes SHA3/Keccak with the simd128 permutation
+///
+/// Built with `./build.sh --simd`; other builds use the portable `sha3` crate.
+#[wasm_bindgen]
+pub fn simd_enabled() -> bool {
+    cfg!(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))
+}
+
+/// Whether the running engine supports wasm SIMD, i.e. whether a `--simd` build will load
+#[wasm_bindgen]
+pub fn simd_supported() -> bool {
+    let probe = js_sys::Uint8Array::from(&SIMD_PROBE[..]);
+    js_sys::WebAssembly::validate(&probe.into()).unwrap_or(false)
+}
+
+/// Incremental hasher for inputs too large for one `Uint8Array`
+///
+/// ```js
+/// const hasher = new StreamingHasher();
+/// for await (const chunk of chunks) hasher.update(chunk);
+/// verifier.verify_streamed(proof, hasher, outputHasher);
+/// ```
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct StreamingHasher(zkml_verifier_core::hash::StreamingHasher);
+
+wrap!(StreamingHasher => zkml_verifier_core::hash::StreamingHasher);
+
+#[wasm_bindgen]
+impl StreamingHasher {
+    /// Create hasher (SHA3-256 unless `algorithm` is given)
+    ///
+    /// Throws `VerificationError.UnsupportedHashAlgorithm` if the algorithm
+    /// is not compiled into this build.
+    #[wasm_bindgen(constructor)]
+    pub fn new(algorithm: Option<HashAlgorithm>) -> Result<StreamingHasher, JsValue> {
+        zkml_verifier_core::hash::StreamingHasher::with_algorithm(algorithm.unwrap_or_default())
+            .map(StreamingHasher)
+            .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+
+    /// Absorb the next chunk
+    #[wasm_bindgen]
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.0.update(chunk);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm()
+    }
+
+    /// Total bytes absorbed so far
+    #[wasm_bindgen(getter)]
+    pub fn bytes_processed(&self) -> u64 {
+        self.0.bytes_processed()
+    }
+
+    /// Finish hashing and return the `0x`-prefixed hex digest
+    ///
+    /// The hasher is reset and can be reused afterwards.
+    #[wasm_bindgen]
+    pub fn finalize(&mut self) -> String {
+        self.0.finalize()
+    }
+}
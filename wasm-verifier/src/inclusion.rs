@@ -0,0 +1,123 @@
+// Proof log inclusion
+// Merkle audit paths showing one proof is in a published batch root, checked without the rest of the batch
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::ProofData;
+
+/// Audit path of one leaf in an RFC 9162 Merkle tree
+///
+/// ```json
+/// { "index": 3, "leaf_count": 1000, "algorithm": "sha3-256", "path": ["0x…", "0x…"] }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct MerkleProof(zkml_verifier_core::inclusion::MerkleProof);
+
+wrap!(MerkleProof => zkml_verifier_core::inclusion::MerkleProof);
+
+#[wasm_bindgen]
+impl MerkleProof {
+    /// Audit path as a prover publishes it, leaf first (`algorithm` defaults to SHA3-256)
+    #[wasm_bindgen(constructor)]
+    pub fn new(index: u64, leaf_count: u64, path: Vec<String>, algorithm: Option<HashAlgorithm>) -> MerkleProof {
+        let alg = algorithm.unwrap_or_default();
+        MerkleProof(zkml_verifier_core::inclusion::MerkleProof::new(index, leaf_count, path, alg))
+    }
+
+    /// Position of the leaf in the batch
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> u64 {
+        self.0.index()
+    }
+
+    /// Number of leaves in the batch
+    #[wasm_bindgen(getter)]
+    pub fn leaf_count(&self) -> u64 {
+        self.0.leaf_count()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> Vec<String> {
+        self.0.path().to_vec()
+    }
+
+    /// Check the path against a batch root (see `verify_inclusion`)
+    #[wasm_bindgen]
+    pub fn verify(&self, leaf_hash: &str, root: &str) -> bool {
+        self.0.verifies(leaf_hash, root)
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<MerkleProof, JsValue> {
+        zkml_verifier_core::inclusion::MerkleProof::from_json(json)
+            .map(MerkleProof)
+            .map_err(|_| VerificationError::MalformedProof.to_js("Invalid Merkle proof JSON"))
+    }
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    /// This proof's leaf in a proof log: the Merkle leaf hash of its `proof_id`
+    #[wasm_bindgen]
+    pub fn leaf_hash(&self, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        self.0
+            .leaf_hash(algorithm.unwrap_or_default())
+            .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+}
+
+/// Merkle leaf hash of `data`, `H(0x00 || data)` (`algorithm` defaults to SHA3-256)
+#[wasm_bindgen]
+pub fn merkle_leaf_hash(data: &[u8], algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    zkml_verifier_core::inclusion::merkle_leaf_hash(data, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+}
+
+/// Root of a batch of leaf hashes, as a prover publishes it (e.g. daily)
+#[wasm_bindgen]
+pub fn merkle_root(leaf_hashes: Vec<String>, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    zkml_verifier_core::inclusion::merkle_root(&leaf_hashes, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Expected hex leaf hashes"))
+}
+
+/// Build the audit path of leaf `index` of a batch (see `merkle_root`)
+#[wasm_bindgen]
+pub fn prove_inclusion(
+    leaf_hashes: Vec<String>,
+    index: usize,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<MerkleProof, JsValue> {
+    zkml_verifier_core::inclusion::MerkleProof::create(&leaf_hashes, index, algorithm.unwrap_or_default())
+        .map(MerkleProof)
+        .map_err(|e| e.to_js("Expected hex leaf hashes and an index within them"))
+}
+
+/// Check that `leaf_hash` is in the batch with root `root`, given its audit path
+///
+/// ```js
+/// const leaf = proof.leaf_hash();
+/// const included = verify_inclusion(leaf, MerkleProof.from_json(pathJson), publishedRoot);
+/// ```
+///
+/// Only the path is needed, not the batch. `false` for paths that are
+/// malformed or lead to another root.
+#[wasm_bindgen]
+pub fn verify_inclusion(leaf_hash: &str, path: &MerkleProof, root: &str) -> bool {
+    path.verifies(leaf_hash, root)
+}
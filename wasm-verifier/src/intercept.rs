@@ -0,0 +1,300 @@
+// Service Worker interception
+// `intercept_fetch`: forward a request, verify the response body against its `X-Inference-Proof`, annotate or block
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::error::{Failure, VerificationError};
+use crate::{ProofData, WasmVerifier};
+
+/// Response header carrying the proof: inline JSON, or a URL to fetch it from
+const PROOF_HEADER: &str = "x-inference-proof";
+
+/// Added to verified responses: `"true"` or `"false"`
+const VERIFIED_HEADER: &str = "x-inference-verified";
+
+/// Added to failed responses: the `VerificationError` kind
+const ERROR_HEADER: &str = "x-inference-verification-error";
+
+/// Status of the response that replaces a blocked one
+const BLOCKED_STATUS: u16 = 502;
+
+#[wasm_bindgen]
+extern "C" {
+    /// Fetch API `Request`, e.g. a `FetchEvent`'s `request`
+    #[wasm_bindgen(js_name = Request, typescript_type = "Request")]
+    pub type FetchRequest;
+
+    #[wasm_bindgen(method, catch)]
+    fn clone(this: &FetchRequest) -> Result<FetchRequest, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = arrayBuffer)]
+    fn array_buffer(this: &FetchRequest) -> Result<js_sys::Promise, JsValue>;
+
+    /// Fetch API `Response`
+    #[wasm_bindgen(js_name = Response, typescript_type = "Response")]
+    pub type FetchResponse;
+
+    #[wasm_bindgen(constructor, catch, js_class = "Response")]
+    fn new(body: &JsValue, init: &js_sys::Object) -> Result<FetchResponse, JsValue>;
+
+    #[wasm_bindgen(method, getter)]
+    fn status(this: &FetchResponse) -> u16;
+
+    #[wasm_bindgen(method, getter, js_name = statusText)]
+    fn status_text(this: &FetchResponse) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn url(this: &FetchResponse) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn headers(this: &FetchResponse) -> Headers;
+
+    #[wasm_bindgen(method, catch, js_name = arrayBuffer)]
+    fn array_buffer(this: &FetchResponse) -> Result<js_sys::Promise, JsValue>;
+
+    type Headers;
+
+    #[wasm_bindgen(constructor, catch, js_class = "Headers")]
+    fn new(init: &JsValue) -> Result<Headers, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn get(this: &Headers, name: &str) -> Option<String>;
+
+    #[wasm_bindgen(method)]
+    fn set(this: &Headers, name: &str, value: &str);
+
+    #[wasm_bindgen(js_name = URL)]
+    type Url;
+
+    #[wasm_bindgen(constructor, catch, js_class = "URL")]
+    fn new(url: &str, base: &str) -> Result<Url, JsValue>;
+
+    #[wasm_bindgen(method, getter)]
+    fn href(this: &Url) -> String;
+}
+
+/// Where an `X-Inference-Proof` value says the proof is
+#[derive(Debug)]
+enum ProofSource {
+    Inline(Box<ProofData>),
+    /// Possibly relative to the response URL
+    Url(String),
+}
+
+impl ProofSource {
+    /// A value starting with `{` is the proof JSON; anything else is a URL
+    fn parse(value: &str) -> Result<ProofSource, Failure> {
+        let value = value.trim();
+        if value.starts_with('{') {
+            let proof = serde_json::from_str(value).map_err(|_| (VerificationError::MalformedProof, "Invalid proof JSON"))?;
+            Ok(ProofSource::Inline(Box::new(proof)))
+        } else if value.is_empty() {
+            Err((VerificationError::ProofUnavailable, "Empty proof header"))
+        } else {
+            Ok(ProofSource::Url(value.to_string()))
+        }
+    }
+}
+
+/// Statuses whose responses can't have a body (Fetch standard "null body status")
+fn is_null_body_status(status: u16) -> bool {
+    matches!(status, 101 | 103 | 204 | 205 | 304)
+}
+
+async fn bytes(promise: js_sys::Promise) -> Result<Vec<u8>, JsValue> {
+    Ok(js_sys::Uint8Array::new(&JsFuture::from(promise).await?).to_vec())
+}
+
+/// `{ status, statusText, headers }` for the `Response` constructor
+fn response_init(status: u16, status_text: &str, headers: &Headers) -> js_sys::Object {
+    let init = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&init, &"status".into(), &status.into());
+    let _ = js_sys::Reflect::set(&init, &"statusText".into(), &status_text.into());
+    let _ = js_sys::Reflect::set(&init, &"headers".into(), headers);
+    init
+}
+
+/// `kind` of a thrown `VerificationError`
+fn error_kind(error: &JsValue) -> String {
+    js_sys::Reflect::get(error, &"kind".into())
+        .ok()
+        .and_then(|kind| kind.as_string())
+        .unwrap_or_else(|| VerificationError::ProofUnavailable.name().to_string())
+}
+
+impl WasmVerifier {
+    /// Get the proof an `X-Inference-Proof` value points at
+    async fn header_proof(&self, value: &str, response_url: &str) -> Result<ProofData, JsValue> {
+        match ProofSource::parse(value).map_err(|(e, reason)| e.to_js(reason))? {
+            ProofSource::Inline(proof) => Ok(*proof),
+            ProofSource::Url(url) => {
+                let url = match Url::new(&url, response_url) {
+                    Ok(resolved) => resolved.href(),
+                    Err(_) => url,
+                };
+                self.fetch_proof(&url).await
+            }
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Fetch `request` and verify the response before the page sees it, for use in a Service Worker
+    ///
+    /// ```js
+    /// // sw.js
+    /// self.addEventListener("fetch", (event) => {
+    ///   if (new URL(event.request.url).pathname.startsWith("/api/infer")) {
+    ///     event.respondWith(verifier.intercept_fetch(event.request));
+    ///   }
+    /// });
+    /// ```
+    ///
+    /// Responses without an `X-Inference-Proof` header are passed through
+    /// untouched. The header holds the proof JSON or a URL to fetch it
+    /// from (relative to the response); the proof is checked with the
+    /// request body as input and the response body as output.
+    ///
+    /// Verified responses get `X-Inference-Verified: true`. Failed ones get
+    /// `X-Inference-Verified: false` and `X-Inference-Verification-Error`
+    /// set to the error kind, and unless `block` is `false` are replaced by
+    /// an empty 502 response carrying those two headers. Requests go
+    /// through `proof_fetcher` when set, as do proof URLs.
+    #[wasm_bindgen]
+    pub async fn intercept_fetch(&self, request: FetchRequest, block: Option<bool>) -> Result<FetchResponse, JsValue> {
+        // The forwarded request consumes its body; the copy is read only if there's a proof
+        let copy = request.clone()?;
+        let pending = self.fetcher()?.call1(&JsValue::NULL, &request)?;
+        let response: FetchResponse = JsFuture::from(js_sys::Promise::resolve(&pending)).await?.unchecked_into();
+        let Some(header) = response.headers().get(PROOF_HEADER) else {
+            return Ok(response);
+        };
+
+        let output = bytes(response.array_buffer()?).await?;
+        let outcome = match self.header_proof(&header, &response.url()).await {
+            Ok(proof) => {
+                let input = bytes(copy.array_buffer()?).await?;
+                let report = self.verify_report(&proof, &input, &output);
+                match report.error() {
+                    None if report.passed() => Ok(()),
+                    error => Err(error.unwrap_or(VerificationError::NotVerified).name().to_string()),
+                }
+            }
+            Err(error) => Err(error_kind(&error)),
+        };
+
+        let headers = Headers::new(&response.headers())?;
+        headers.set(VERIFIED_HEADER, if outcome.is_ok() { "true" } else { "false" });
+        if let Err(kind) = &outcome {
+            headers.set(ERROR_HEADER, kind);
+            if block != Some(false) {
+                let headers = Headers::new(&JsValue::UNDEFINED)?;
+                headers.set(VERIFIED_HEADER, "false");
+                headers.set(ERROR_HEADER, kind);
+                let init = response_init(BLOCKED_STATUS, "Inference proof failed verification", &headers);
+                return FetchResponse::new(&JsValue::NULL, &init);
+            }
+        }
+        let body = if output.is_empty() && is_null_body_status(response.status()) {
+            JsValue::NULL
+        } else {
+            js_sys::Uint8Array::from(output.as_slice()).into()
+        };
+        FetchResponse::new(&body, &response_init(response.status(), &response.status_text(), &headers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_proof_header_values() {
+        let proof = fixtures::proof("0xmodel");
+        let inline = format!(" {} ", proof.to_json());
+        assert!(matches!(ProofSource::parse(&inline), Ok(ProofSource::Inline(p)) if p.proof_id() == proof.proof_id()));
+        assert!(matches!(ProofSource::parse("/proofs/42"), Ok(ProofSource::Url(url)) if url == "/proofs/42"));
+        assert_eq!(ProofSource::parse("{not json").unwrap_err().0, VerificationError::MalformedProof);
+        assert_eq!(ProofSource::parse("  ").unwrap_err().0, VerificationError::ProofUnavailable);
+
+        assert!(is_null_body_status(204));
+        assert!(!is_null_body_status(200));
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod js_tests {
+    use super::*;
+    use crate::fixtures;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use zkml_verifier_core::fixtures::{INPUT, OUTPUT};
+
+    /// `fetch` stub: inference requests get `output` with `header` as the proof, proof URLs `proof` or a 404
+    fn fetcher(header: Option<&str>, output: &[u8], proof: Option<&str>) -> js_sys::Function {
+        let source = "return (request) => typeof request === 'string' \
+            ? new Response(proof, { status: proof == null ? 404 : 200, headers: { 'content-type': 'text/json' } }) \
+            : new Response(output, { headers: header == null ? {} : { 'x-inference-proof': header } });";
+        js_sys::Function::new_with_args("header, output, proof", source)
+            .call3(&JsValue::NULL, &header.into(), &js_sys::Uint8Array::from(output), &proof.into())
+            .unwrap()
+            .unchecked_into()
+    }
+
+    fn request(input: &[u8]) -> FetchRequest {
+        let source = "return new Request('https://api.example/infer', { method: 'POST', body: input });";
+        js_sys::Function::new_with_args("input", source)
+            .call1(&JsValue::NULL, &js_sys::Uint8Array::from(input))
+            .unwrap()
+            .unchecked_into()
+    }
+
+    async fn intercept(fetcher: js_sys::Function, input: &[u8], block: Option<bool>) -> FetchResponse {
+        let mut verifier = WasmVerifier::new("0xmodel".to_string());
+        verifier.set_proof_fetcher(Some(fetcher));
+        verifier.intercept_fetch(request(input), block).await.unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_intercept_fetch() {
+        let proof = fixtures::proof("0xmodel").to_json();
+
+        let response = intercept(fetcher(None, OUTPUT, None), INPUT, None).await;
+        assert_eq!(response.headers().get(VERIFIED_HEADER), None);
+
+        let response = intercept(fetcher(Some(&proof), OUTPUT, None), INPUT, None).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers().get(VERIFIED_HEADER).as_deref(), Some("true"));
+        assert_eq!(response.headers().get(PROOF_HEADER), Some(proof.clone()));
+        assert_eq!(bytes(response.array_buffer().unwrap()).await.unwrap(), OUTPUT);
+
+        // Proof URLs go through the same fetcher
+        let response = intercept(fetcher(Some("/proofs/1"), OUTPUT, Some(&proof)), INPUT, None).await;
+        assert_eq!(response.headers().get(VERIFIED_HEADER).as_deref(), Some("true"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_intercept_blocks_failures() {
+        let proof = fixtures::proof("0xmodel").to_json();
+        let mismatch = Some(VerificationError::InputHashMismatch.name().to_string());
+
+        let response = intercept(fetcher(Some(&proof), OUTPUT, None), b"tampered", None).await;
+        assert_eq!(response.status(), BLOCKED_STATUS);
+        assert_eq!(response.headers().get(VERIFIED_HEADER).as_deref(), Some("false"));
+        assert_eq!(response.headers().get(ERROR_HEADER), mismatch);
+        assert_eq!(response.headers().get(PROOF_HEADER), None);
+        assert!(bytes(response.array_buffer().unwrap()).await.unwrap().is_empty());
+
+        let response = intercept(fetcher(Some(&proof), OUTPUT, None), b"tampered", Some(false)).await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers().get(ERROR_HEADER), mismatch);
+        assert_eq!(bytes(response.array_buffer().unwrap()).await.unwrap(), OUTPUT);
+
+        let response = intercept(fetcher(Some("/proofs/1"), OUTPUT, None), INPUT, None).await;
+        assert_eq!(response.status(), BLOCKED_STATUS);
+        let unavailable = VerificationError::ProofUnavailable.name();
+        assert_eq!(response.headers().get(ERROR_HEADER).as_deref(), Some(unavailable));
+    }
+}
@@ -0,0 +1,25 @@
+// JSON Canonicalization Scheme
+// RFC 8785 serialization, so hashes and signatures over proofs are byte-for-byte reproducible
+
+use wasm_bindgen::prelude::*;
+
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::ProofData;
+
+pub(crate) use zkml_verifier_core::jcs::canonicalize;
+
+#[wasm_bindgen]
+impl ProofData {
+    /// The whole proof as RFC 8785 canonical JSON
+    #[wasm_bindgen]
+    pub fn canonical_json(&self) -> String {
+        canonicalize(&serde_json::to_value(self).unwrap_or_default())
+    }
+
+    /// Hash of `canonical_json()` (default SHA3-256), stable across re-serialization
+    #[wasm_bindgen]
+    pub fn canonical_hash(&self, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        hash_bytes(self.canonical_json().as_bytes(), algorithm.unwrap_or_default())
+            .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+}
@@ -0,0 +1,63 @@
+// JWKS signer key resolution
+// Resolves `ProofData.kid` to a prover public key from a JSON Web Key Set
+
+use wasm_bindgen::prelude::*;
+
+use crate::boundary::{self, wrap};
+use crate::WasmVerifier;
+
+/// Maps key ids (`kid`) to prover keys, so provers can rotate keys without
+/// redeploying the verifier
+///
+/// Load it with `load_jwks`, or give it a `fetcher` that is called with an
+/// unknown `kid` and must synchronously return a JWKS JSON string (for
+/// example from a cache your app refreshes with `fetch`).
+/// Supported keys: OKP/Ed25519 and EC/secp256k1.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct KeyResolver(zkml_verifier_core::KeyResolver);
+
+wrap!(KeyResolver => zkml_verifier_core::KeyResolver);
+
+#[wasm_bindgen]
+impl KeyResolver {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> KeyResolver {
+        KeyResolver::default()
+    }
+
+    /// Add the signing keys of a JWKS document; returns how many were loaded
+    #[wasm_bindgen]
+    pub fn load_jwks(&self, jwks: &str) -> Result<usize, JsValue> {
+        self.0.insert_jwks(jwks).map_err(|(error, reason)| error.to_js(reason))
+    }
+
+    /// Callback `(kid) => string | undefined` consulted for unknown key ids
+    #[wasm_bindgen(setter)]
+    pub fn set_fetcher(&mut self, fetcher: Option<js_sys::Function>) {
+        self.0.set_fetcher(fetcher.map(boundary::fetcher));
+    }
+
+    /// Number of keys currently known
+    #[wasm_bindgen(getter)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[wasm_bindgen]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Resolve proof `kid`s through `resolver` before checking signatures
+    ///
+    /// Keys resolved this way are trusted without `add_trusted_signer`.
+    /// The resolver is moved into the verifier.
+    #[wasm_bindgen]
+    pub fn set_key_resolver(&mut self, resolver: KeyResolver) {
+        self.verifier.set_key_resolver(resolver.0);
+    }
+}
@@ -0,0 +1,46 @@
+// JWS proof envelopes
+// `verify_jws` over the core's compact JWS (RFC 7515) parsing and signature check
+
+use wasm_bindgen::prelude::*;
+
+use crate::hash::IoSource;
+use crate::report::VerificationReport;
+use crate::signature::Failure;
+use crate::{ProofData, WasmVerifier};
+
+pub(crate) use zkml_verifier_core::jws::Jws;
+
+impl WasmVerifier {
+    /// Check the token signature, then run the usual checks on its claims
+    pub(crate) fn jws_report(
+        &self,
+        token: &str,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, Failure> {
+        let jws = Jws::parse(token)?;
+        let proof_data = ProofData(jws.proof()?);
+
+        let mut report = self.new_report();
+        self.check_jws(&mut report, &jws, &proof_data);
+        self.run_checks(&mut report, &proof_data, IoSource::Bytes(input_bytes), IoSource::Bytes(output_bytes));
+        Ok(report)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a compact JWS whose payload is the ProofData claims
+    ///
+    /// The token signature (EdDSA or ES256K) takes the place of the in-proof
+    /// `signature`. It must verify under a configured key: the `KeyResolver`
+    /// key the header `kid` names, or else the claims' `signer_pubkey` once it
+    /// was added with `add_trusted_signer`. Freshness comes from the
+    /// `timestamp` claim; JWT `exp`/`nbf` are not interpreted. Throws
+    /// `VerificationError.MalformedProof` if the token cannot be parsed.
+    #[wasm_bindgen]
+    pub fn verify_jws(&self, token: &str, input_bytes: &[u8], output_bytes: &[u8]) -> Result<VerificationReport, JsValue> {
+        self.jws_report(token, input_bytes, output_bytes)
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+}
@@ -3,11 +3,245 @@
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use sha3::{Digest, Sha3_256};
 
-#[cfg(feature = "wee_alloc")]
+#[cfg(feature = "async")]
+mod abort;
+#[cfg(feature = "extras")]
+mod aggregate;
+#[cfg(feature = "extras")]
+mod anchor;
+#[cfg(feature = "extras")]
+mod audit;
+#[cfg(feature = "extras")]
+mod batch;
+#[cfg(feature = "extras")]
+mod binary;
+mod boundary;
+#[cfg(feature = "extras")]
+mod buffer;
+#[cfg(feature = "extras")]
+mod builder;
+#[cfg(feature = "async")]
+mod cache;
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "extras")]
+mod chain;
+#[cfg(feature = "extras")]
+mod classify;
+mod clock;
+#[cfg(feature = "extras")]
+mod composite;
+mod compress;
+#[cfg(feature = "signatures")]
+mod bls;
+mod config;
+#[cfg(feature = "signatures")]
+mod cose;
+#[cfg(feature = "extras")]
+mod describe;
+#[cfg(feature = "signatures")]
+mod did;
+#[cfg(feature = "extras")]
+mod eas;
+#[cfg(feature = "signatures")]
+mod eip712;
+mod error;
+mod events;
+#[cfg(feature = "extras")]
+mod evm;
+#[cfg(feature = "extras")]
+mod fixed;
+#[cfg(test)]
+mod fixtures;
+#[cfg(any(not(target_arch = "wasm32"), target_os = "wasi"))]
+mod fs;
+mod handle;
+mod hash;
+#[cfg(feature = "extras")]
+mod inclusion;
+#[cfg(feature = "async")]
+mod intercept;
+#[cfg(feature = "extras")]
+mod jcs;
+#[cfg(feature = "signatures")]
+mod jwks;
+#[cfg(feature = "signatures")]
+mod jws;
+#[cfg(feature = "extras")]
+mod memory;
+#[cfg(feature = "extras")]
+mod messages;
+#[cfg(feature = "extras")]
+mod metadata;
+#[cfg(feature = "extras")]
+mod multi;
+#[cfg(feature = "extras")]
+mod nonce;
+#[cfg(feature = "js-objects")]
+mod object;
+#[cfg(feature = "async")]
+mod onchain;
+#[cfg(feature = "onnx")]
+mod onnx;
+#[cfg(feature = "extras")]
+mod parallel;
+#[cfg(feature = "extras")]
+mod policy;
+#[cfg(feature = "extras")]
+mod preprocessing;
+#[cfg(feature = "async")]
+mod promise;
+#[cfg(feature = "onnx")]
+mod protobuf;
+#[cfg(feature = "extras")]
+mod quantization;
+#[cfg(feature = "async")]
+mod readable;
+#[cfg(feature = "signatures")]
+mod receipt;
+#[cfg(all(feature = "onnxruntime", not(target_arch = "wasm32")))]
+mod recompute;
+#[cfg(feature = "signatures")]
+mod rekor;
+#[cfg(feature = "extras")]
+mod registry;
+#[cfg(feature = "async")]
+mod remote;
+mod report;
+#[cfg(feature = "signatures")]
+mod revocation;
+#[cfg(feature = "extras")]
+mod session;
+#[cfg(feature = "signatures")]
+mod signature;
+#[cfg(feature = "extras")]
+mod tensor;
+#[cfg(feature = "extras")]
+mod tolerance;
+#[cfg(feature = "tracing")]
+mod trace;
+mod typescript;
+#[cfg(feature = "rust-signatures")]
+mod vc;
+#[cfg(feature = "extras")]
+mod version;
+#[cfg(all(feature = "signatures", feature = "async"))]
+mod webcrypto;
+#[cfg(feature = "async")]
+mod worker;
+#[cfg(feature = "signatures")]
+mod x509;
+
+#[cfg(any(feature = "extras", feature = "async"))]
+pub(crate) use zkml_verifier_core::hex;
+
+#[cfg(feature = "async")]
+pub use abort::AbortSignal;
+#[cfg(feature = "extras")]
+pub use aggregate::{aggregate_inferences, prove_inference, AggregatedProof, InferenceProof};
+#[cfg(feature = "extras")]
+pub use anchor::{AnchorRecord, AnchorTx};
+#[cfg(feature = "extras")]
+pub use audit::{AuditEntry, AuditLog, AUDIT_GENESIS};
+#[cfg(feature = "extras")]
+pub use batch::BatchReport;
+#[cfg(feature = "extras")]
+pub use buffer::{hash_buffer, IoBuffer};
+#[cfg(feature = "extras")]
+pub use builder::VerifierBuilder;
+#[cfg(feature = "async")]
+pub use cache::CacheStorage;
+#[cfg(feature = "extras")]
+pub use chain::ChainReport;
+#[cfg(feature = "extras")]
+pub use composite::{CompositeProof, MAX_COMPOSITE_DEPTH};
+pub use config::VerifierConfig;
+#[cfg(feature = "extras")]
+pub use describe::{describe, ProofDescription};
+#[cfg(feature = "extras")]
+pub use eas::{eas_schema, eas_schema_uid, EasAttestation, EAS_SCHEMA};
+#[cfg(feature = "signatures")]
+pub use eip712::Eip712Domain;
+pub use error::VerificationError;
+#[cfg(feature = "extras")]
+pub use evm::{evm_verifier_abi, EVM_VERIFY_SIGNATURE};
+#[cfg(feature = "extras")]
+pub use fixed::{encode_fixed_point, from_fixed_point, to_fixed_point};
+pub use hash::{simd_enabled, simd_supported, HashAlgorithm, StreamingHasher};
+#[cfg(feature = "extras")]
+pub use inclusion::{merkle_leaf_hash, merkle_root, prove_inclusion, verify_inclusion, MerkleProof};
+#[cfg(feature = "async")]
+pub use intercept::{FetchRequest, FetchResponse};
+#[cfg(feature = "signatures")]
+pub use jwks::KeyResolver;
+#[cfg(feature = "extras")]
+pub use memory::{memory_stats, reset_memory_stats, MemoryStats};
+#[cfg(feature = "extras")]
+pub use messages::{supported_locales, user_message};
+#[cfg(feature = "extras")]
+pub use metadata::VerifierMetadata;
+#[cfg(feature = "async")]
+pub use onchain::{MODEL_APPROVED_SIGNATURE, PROVER_REGISTERED_SIGNATURE};
+#[cfg(feature = "extras")]
+pub use parallel::{hash_data_batch, threads_enabled};
+#[cfg(feature = "extras")]
+pub use policy::VerifierPolicy;
+#[cfg(feature = "extras")]
+pub use preprocessing::PreprocessingConfig;
+#[cfg(feature = "async")]
+pub use promise::ASYNC_CHUNK_BYTES;
+#[cfg(feature = "extras")]
+pub use quantization::QuantizationConfig;
+#[cfg(feature = "async")]
+pub use readable::{hash_stream, ReadableStream};
+#[cfg(feature = "signatures")]
+pub use receipt::{verify_receipt, Receipt};
+#[cfg(feature = "signatures")]
+pub use rekor::{rekor_receipt_entry, verify_rekor_entry, RekorInclusion};
+#[cfg(feature = "extras")]
+pub use tolerance::OutputTolerance;
+#[cfg(feature = "extras")]
+pub use tensor::{
+    encode_tensor_f32, encode_typed_tensor, hash_tensor, hash_tensor_f32, hash_typed_tensor, DType, TensorSpec,
+};
+#[cfg(feature = "onnx")]
+pub use onnx::{
+    commitment::{commit_onnx_model, CommitmentPolicy, ModelCommitment},
+    diagnostic::ModelDiagnostic,
+    external::{hash_onnx_model_external, ExternalDataHasher},
+    hash_onnx_model,
+    inspect::{inspect_model, ModelInfo, TensorInfo},
+    merkle::{onnx_merkle_layers, onnx_merkle_root, prove_onnx_layer, LayerProof},
+    partial::onnx_partial_hash,
+    policy::ModelPolicy,
+    stream::ModelHasher,
+};
+#[cfg(all(feature = "onnxruntime", not(target_arch = "wasm32")))]
+pub use recompute::Tolerance;
+#[cfg(feature = "extras")]
+pub use registry::{ModelEntry, ModelRegistry};
+pub use report::{Check, CheckResult, Confidence, VerificationReport};
+#[cfg(feature = "extras")]
+pub use session::{SessionSummary, VerifierSession};
+#[cfg(all(feature = "tracing", target_arch = "wasm32", not(target_os = "wasi")))]
+pub use trace::init_tracing;
+#[cfg(feature = "async")]
+pub use worker::WorkerVerifier;
+
+use hash::{hash_bytes, hash_bytes_tagged, IoSource};
+use zkml_verifier_core::multi::Io;
+
+// Only in the wasm module itself: a native binary linking the rlib keeps its own allocator
+#[cfg(all(target_arch = "wasm32", feature = "wee_alloc"))]
+#[global_allocator]
+static ALLOC: zkml_verifier_core::memory::Tracking<wee_alloc::WeeAlloc> =
+    zkml_verifier_core::memory::Tracking::new(wee_alloc::WeeAlloc::INIT);
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wee_alloc")))]
 #[global_allocator]
-static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+static ALLOC: zkml_verifier_core::memory::Tracking<std::alloc::System> =
+    zkml_verifier_core::memory::Tracking::new(std::alloc::System);
 
 /// Initialize panic hook for better error messages in WASM
 #[wasm_bindgen(start)]
@@ -16,16 +250,14 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
+/// A proof claim: which model ran on which input to give which output, plus
+/// optional signatures, attestations and policy commitments
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
 #[wasm_bindgen]
-pub struct ProofData {
-    model_hash: String,
-    proof_hash: String,
-    input_hash: String,
-    output_hash: String,
-    timestamp: u64,
-    verified: bool,
-}
+pub struct ProofData(zkml_verifier_core::ProofData);
+
+boundary::wrap!(ProofData => zkml_verifier_core::ProofData);
 
 #[wasm_bindgen]
 impl ProofData {
@@ -38,14 +270,14 @@ impl ProofData {
         timestamp: u64,
         verified: bool,
     ) -> ProofData {
-        ProofData {
+        ProofData(zkml_verifier_core::ProofData::new(
             model_hash,
             proof_hash,
             input_hash,
             output_hash,
             timestamp,
             verified,
-        }
+        ))
     }
 
     #[wasm_bindgen(getter)]
@@ -58,24 +290,360 @@ impl ProofData {
         self.proof_hash.clone()
     }
 
+    /// Input commitment (empty for proofs using named `inputs`)
+    #[wasm_bindgen(getter)]
+    pub fn input_hash(&self) -> String {
+        self.input_hash.clone()
+    }
+
+    /// Output commitment (empty for proofs using named `outputs`)
+    #[wasm_bindgen(getter)]
+    pub fn output_hash(&self) -> String {
+        self.output_hash.clone()
+    }
+
+    /// Generation time, Unix milliseconds
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
     #[wasm_bindgen(getter)]
     pub fn verified(&self) -> bool {
         self.verified
     }
+
+    /// Named input commitments, by input name
+    #[wasm_bindgen(getter, unchecked_return_type = "Record<string, string>")]
+    pub fn inputs(&self) -> JsValue {
+        typescript::to_object(&self.inputs)
+    }
+
+    /// Named output commitments, by output name
+    #[wasm_bindgen(getter, unchecked_return_type = "Record<string, string>")]
+    pub fn outputs(&self) -> JsValue {
+        typescript::to_object(&self.outputs)
+    }
+
+    /// Declared hash algorithm name (`undefined` = verifier default)
+    #[wasm_bindgen(getter)]
+    pub fn hash_alg(&self) -> Option<String> {
+        self.hash_alg.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_hash_alg(&mut self, hash_alg: Option<String>) {
+        self.hash_alg = hash_alg;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn nonce(&self) -> Option<String> {
+        self.nonce.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_nonce(&mut self, nonce: Option<String>) {
+        self.nonce = nonce;
+    }
+
+    /// Declare the commitment hash algorithm (`undefined` = verifier default)
+    #[wasm_bindgen]
+    pub fn set_hash_algorithm(&mut self, algorithm: Option<HashAlgorithm>) {
+        self.0.set_hash_algorithm(algorithm);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn signature_alg(&self) -> Option<String> {
+        self.signature_alg.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_signature_alg(&mut self, signature_alg: Option<String>) {
+        self.signature_alg = signature_alg;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn signature(&self) -> Option<String> {
+        self.signature.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_signature(&mut self, signature: Option<String>) {
+        self.signature = signature;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn signer_pubkey(&self) -> Option<String> {
+        self.signer_pubkey.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_signer_pubkey(&mut self, signer_pubkey: Option<String>) {
+        self.signer_pubkey = signer_pubkey;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn kid(&self) -> Option<String> {
+        self.kid.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_kid(&mut self, kid: Option<String>) {
+        self.kid = kid;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn prover_did(&self) -> Option<String> {
+        self.prover_did.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_prover_did(&mut self, prover_did: Option<String>) {
+        self.prover_did = prover_did;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn attestor_pubkeys(&self) -> Vec<String> {
+        self.attestor_pubkeys.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_attestor_pubkeys(&mut self, attestor_pubkeys: Vec<String>) {
+        self.attestor_pubkeys = attestor_pubkeys;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn aggregate_signature(&self) -> Option<String> {
+        self.aggregate_signature.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_aggregate_signature(&mut self, aggregate_signature: Option<String>) {
+        self.aggregate_signature = aggregate_signature;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn model_version(&self) -> Option<String> {
+        self.model_version.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_model_version(&mut self, model_version: Option<String>) {
+        self.model_version = model_version;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn architecture_hash(&self) -> Option<String> {
+        self.architecture_hash.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_architecture_hash(&mut self, architecture_hash: Option<String>) {
+        self.architecture_hash = architecture_hash;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn weights_hash(&self) -> Option<String> {
+        self.weights_hash.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_weights_hash(&mut self, weights_hash: Option<String>) {
+        self.weights_hash = weights_hash;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn metadata_hash(&self) -> Option<String> {
+        self.metadata_hash.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_metadata_hash(&mut self, metadata_hash: Option<String>) {
+        self.metadata_hash = metadata_hash;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn partial_model_hash(&self) -> Option<String> {
+        self.partial_model_hash.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_partial_model_hash(&mut self, partial_model_hash: Option<String>) {
+        self.partial_model_hash = partial_model_hash;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn partial_layers(&self) -> Vec<String> {
+        self.partial_layers.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_partial_layers(&mut self, partial_layers: Vec<String>) {
+        self.partial_layers = partial_layers;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn quantization_hash(&self) -> Option<String> {
+        self.quantization_hash.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_quantization_hash(&mut self, quantization_hash: Option<String>) {
+        self.quantization_hash = quantization_hash;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn preprocessing_hash(&self) -> Option<String> {
+        self.preprocessing_hash.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_preprocessing_hash(&mut self, preprocessing_hash: Option<String>) {
+        self.preprocessing_hash = preprocessing_hash;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn cert_chain(&self) -> Vec<String> {
+        self.cert_chain.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_cert_chain(&mut self, cert_chain: Vec<String>) {
+        self.cert_chain = cert_chain;
+    }
+
+    /// Canonical bytes the prover (and every attestor) signs
+    ///
+    /// RFC 8785 (JCS) canonical JSON of every field except `signature`,
+    /// `signer_pubkey`, `aggregate_signature` and `cert_chain`, so
+    /// re-serializing the proof in JS doesn't change what was signed.
+    #[wasm_bindgen]
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        self.0.signing_bytes()
+    }
+
+    /// Content hash identifying the proof, e.g. as a storage key
+    ///
+    /// Tagged SHA3-256 over the RFC 8785 canonical JSON of every field,
+    /// signatures included, so it doesn't depend on key order or whitespace
+    /// of the JSON the proof was parsed from.
+    #[wasm_bindgen]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id()
+    }
+
+    /// Serialize proof to JSON string
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    /// Parse a proof from JSON (what `to_json` or `JSON.stringify(proof)` produce)
+    #[wasm_bindgen]
+    pub fn from_json(proof_json: &str) -> Result<ProofData, JsValue> {
+        ProofData::parse_json(proof_json)
+    }
+}
+
+impl ProofData {
+    /// Parse proof JSON, mapping failures to `VerificationError::MalformedProof`
+    fn parse_json(proof_json: &str) -> Result<ProofData, JsValue> {
+        zkml_verifier_core::ProofData::from_json(proof_json)
+            .map(ProofData)
+            .map_err(|reason| VerificationError::MalformedProof.to_js(&reason))
+    }
 }
 
 /// WASM Verifier - validates cryptographic proofs client-side
+///
+/// Wraps the core `Verifier`, which runs the checks, with what only JS
+/// callers need: callbacks, storage and the providers async methods read.
 #[wasm_bindgen]
 pub struct WasmVerifier {
-    model_hash: String,
+    verifier: zkml_verifier_core::Verifier,
+    /// Replaces the global `fetch` in `verify_from_url`
+    #[cfg(feature = "async")]
+    proof_fetcher: Option<js_sys::Function>,
+    /// Storage for `verify_cached`
+    #[cfg(feature = "async")]
+    proof_cache: Option<CacheStorage>,
+    check_events: events::CheckEvents,
+    /// `(processed, total)` callback for the async and streaming I/O hashing
+    #[cfg(feature = "async")]
+    hash_progress: Option<js_sys::Function>,
+    /// Looks up anchoring transactions for `verify_anchored`
+    #[cfg(feature = "extras")]
+    chain_query: Option<js_sys::Function>,
+    /// Registry contract `verify_report_onchain` consults
+    #[cfg(feature = "async")]
+    onchain_registry: Option<onchain::OnChainRegistry>,
+}
+
+impl std::ops::Deref for WasmVerifier {
+    type Target = zkml_verifier_core::Verifier;
+
+    fn deref(&self) -> &zkml_verifier_core::Verifier {
+        &self.verifier
+    }
+}
+
+impl std::ops::DerefMut for WasmVerifier {
+    fn deref_mut(&mut self) -> &mut zkml_verifier_core::Verifier {
+        &mut self.verifier
+    }
+}
+
+impl From<zkml_verifier_core::Verifier> for WasmVerifier {
+    fn from(verifier: zkml_verifier_core::Verifier) -> Self {
+        WasmVerifier {
+            verifier,
+            #[cfg(feature = "async")]
+            proof_fetcher: None,
+            #[cfg(feature = "async")]
+            proof_cache: None,
+            check_events: events::CheckEvents::default(),
+            #[cfg(feature = "async")]
+            hash_progress: None,
+            #[cfg(feature = "extras")]
+            chain_query: None,
+            #[cfg(feature = "async")]
+            onchain_registry: None,
+        }
+    }
 }
 
 #[wasm_bindgen]
 impl WasmVerifier {
     /// Create new verifier with model hash
+    ///
+    /// Shorthand for `new VerifierBuilder().model_hash(model_hash).build()`.
     #[wasm_bindgen(constructor)]
     pub fn new(model_hash: String) -> WasmVerifier {
-        WasmVerifier { model_hash }
+        WasmVerifier::with_config(model_hash, &VerifierConfig::default())
+    }
+
+    /// Create new verifier with model hash and verification policy
+    #[wasm_bindgen]
+    pub fn with_config(model_hash: String, config: &VerifierConfig) -> WasmVerifier {
+        zkml_verifier_core::Verifier::with_config(model_hash, config).into()
+    }
+
+    /// Create new verifier whose default commitment hash is `algorithm`
+    #[wasm_bindgen]
+    pub fn with_hash_algorithm(model_hash: String, algorithm: HashAlgorithm) -> WasmVerifier {
+        zkml_verifier_core::Verifier::with_hash_algorithm(model_hash, algorithm).into()
+    }
+
+    /// Get the active verification policy
+    #[wasm_bindgen(getter)]
+    pub fn config(&self) -> VerifierConfig {
+        self.verifier.config().into()
+    }
+
+    /// Replace the verification policy
+    #[wasm_bindgen(setter)]
+    pub fn set_config(&mut self, config: &VerifierConfig) {
+        self.verifier.set_config(config);
     }
 
     /// Verify proof cryptographically
@@ -83,8 +651,12 @@ impl WasmVerifier {
     /// This performs:
     /// 1. Model binding check (proof must be for THIS model)
     /// 2. I/O integrity check (hashes must match)
-    /// 3. Proof validity check (verified flag from JOLT generation)
-    /// 4. Timestamp freshness check
+    /// 3. Proof validity check (the prover's self-reported verified flag; the SNARK is not re-verified)
+    /// 4. Timestamp freshness check (see `VerifierConfig`)
+    /// 5. Prover signature check, when signed or `require_signature` is set
+    /// 6. Certificate chain check, when `add_trusted_root` has been called
+    ///
+    /// Use `verify_report` to find out which check failed.
     #[wasm_bindgen]
     pub fn verify(
         &self,
@@ -92,37 +664,73 @@ impl WasmVerifier {
         input_bytes: &[u8],
         output_bytes: &[u8],
     ) -> Result<bool, JsValue> {
-        // 1. Model Binding Check
-        if proof_data.model_hash != self.model_hash {
-            return Ok(false);
-        }
+        Ok(self.verify_report(proof_data, input_bytes, output_bytes).passed())
+    }
 
-        // 2. Input Hash Verification
-        let computed_input_hash = Self::hash_bytes(input_bytes);
-        if computed_input_hash != proof_data.input_hash {
-            return Ok(false);
-        }
+    /// Verify proof and return per-check results
+    #[wasm_bindgen]
+    pub fn verify_report(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(
+            &mut report,
+            proof_data,
+            IoSource::Bytes(input_bytes),
+            IoSource::Bytes(output_bytes),
+        );
+        report
+    }
 
-        // 3. Output Hash Verification
-        let computed_output_hash = Self::hash_bytes(output_bytes);
-        if computed_output_hash != proof_data.output_hash {
-            return Ok(false);
-        }
+    /// Verify proof against inputs/outputs hashed chunk-by-chunk
+    ///
+    /// Both hashers are finalized (and reset) by this call.
+    #[wasm_bindgen]
+    pub fn verify_streamed(
+        &self,
+        proof_data: &ProofData,
+        input_hasher: &mut StreamingHasher,
+        output_hasher: &mut StreamingHasher,
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(
+            &mut report,
+            proof_data,
+            input_hasher.finalize_source(),
+            output_hasher.finalize_source(),
+        );
+        report
+    }
 
-        // 4. Timestamp Check (must be recent)
-        let current_time = js_sys::Date::now() as u64;
-        let age_ms = current_time.saturating_sub(proof_data.timestamp);
-        if age_ms > 3600000 { // 1 hour max age
-            return Ok(false);
-        }
+    /// `StreamingHasher` for `verify_streamed` matching the proof's input commitment
+    ///
+    /// `undefined` if the commitment needs an algorithm this build can't compute.
+    #[wasm_bindgen]
+    pub fn input_hasher(&self, proof_data: &ProofData) -> Option<StreamingHasher> {
+        self.verifier.input_hasher(proof_data).map(StreamingHasher::from)
+    }
 
-        // 5. JOLT Verification Flag Check
-        // This flag is set during proof generation after JOLT cryptographic verification
-        if !proof_data.verified {
-            return Ok(false);
-        }
+    /// `StreamingHasher` for `verify_streamed` matching the proof's output commitment
+    #[wasm_bindgen]
+    pub fn output_hasher(&self, proof_data: &ProofData) -> Option<StreamingHasher> {
+        self.verifier.output_hasher(proof_data).map(StreamingHasher::from)
+    }
 
-        Ok(true)
+    /// Verify proof, throwing a typed `VerificationError` on the first failed check
+    ///
+    /// The thrown `Error` has `name === "VerificationError"` plus numeric
+    /// `code` and string `kind` properties.
+    #[wasm_bindgen]
+    pub fn verify_strict(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<(), JsValue> {
+        self.verify_report(proof_data, input_bytes, output_bytes).into_js_result()
     }
 
     /// Verify proof from JSON string
@@ -133,56 +741,101 @@ impl WasmVerifier {
         input_bytes: &[u8],
         output_bytes: &[u8],
     ) -> Result<bool, JsValue> {
-        let proof_data: ProofData = serde_json::from_str(proof_json)
-            .map_err(|e| JsValue::from_str(&format!("Failed to parse proof JSON: {}", e)))?;
+        let proof_data = ProofData::parse_json(proof_json)?;
 
         self.verify(&proof_data, input_bytes, output_bytes)
     }
 
-    /// Get verifier info
+    /// Verify proof from JSON string and return per-check results
+    #[wasm_bindgen]
+    pub fn verify_json_report(
+        &self,
+        proof_json: &str,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, JsValue> {
+        let proof_data = ProofData::parse_json(proof_json)?;
+
+        Ok(self.verify_report(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// Verify proof together with the serialized JOLT proof blob
+    ///
+    /// Binds the supplied proof bytes to `proof_hash` before running the
+    /// checks in `verify`. The SNARK itself is not re-verified here: the
+    /// JOLT-Atlas verifier does not yet build for `wasm32-unknown-unknown`
+    /// (see LIMITATIONS.md), so this only guarantees the blob is the one
+    /// the prover committed to.
+    #[wasm_bindgen]
+    pub fn verify_with_proof_bytes(
+        &self,
+        proof_data: &ProofData,
+        proof_bytes: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<bool, JsValue> {
+        let mut report = self.new_report();
+        self.check_proof_bytes(&mut report, proof_data, proof_bytes);
+        self.run_checks(
+            &mut report,
+            proof_data,
+            IoSource::Bytes(input_bytes),
+            IoSource::Bytes(output_bytes),
+        );
+        Ok(report.passed())
+    }
+
+    /// Get verifier info as display text (see `metadata` for the structured form)
     #[wasm_bindgen]
     pub fn info(&self) -> String {
-        format!(
-            "WASM Verifier for model: {}...\nVerifies: Model binding, I/O integrity, Proof validity, Timestamp",
-            &self.model_hash[..16]
-        )
+        let model_hash = self.verifier.model_hash();
+        let models = match self.verifier.registry() {
+            Some(registry) => format!("{} registered models", registry.len()),
+            None => format!("model: {}...", model_hash.get(..16).unwrap_or(model_hash)),
+        };
+        format!("WASM Verifier for {}\nVerifies: Model binding, I/O integrity, Proof validity, Timestamp", models)
+    }
+
+}
+
+impl WasmVerifier {
+    /// Run all binding checks, recording each outcome in `report` and the audit log
+    fn run_checks(
+        &self,
+        report: &mut VerificationReport,
+        proof_data: &ProofData,
+        input: IoSource<'_>,
+        output: IoSource<'_>,
+    ) {
+        self.run_checks_io(report, proof_data, Io::Single(input), Io::Single(output));
     }
 
-    /// Hash bytes to hex string (SHA3-256)
-    fn hash_bytes(data: &[u8]) -> String {
-        let mut hasher = Sha3_256::new();
-        hasher.update(data);
-        format!("0x{}", hex::encode(hasher.finalize().as_slice()))
+    /// `run_checks` against single or named I/O
+    fn run_checks_io(&self, report: &mut VerificationReport, proof_data: &ProofData, input: Io<'_>, output: Io<'_>) {
+        self.verifier.run_checks_io(report, proof_data, input, output);
     }
 }
 
-/// Utility: Compute SHA3-256 hash of data
+/// Utility: Compute hash of data (SHA3-256 unless `algorithm` is given)
 #[wasm_bindgen]
-pub fn hash_data(data: &[u8]) -> String {
-    let mut hasher = Sha3_256::new();
-    hasher.update(data);
-    format!("0x{}", hex::encode(hasher.finalize().as_slice()))
+pub fn hash_data(data: &[u8], algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    hash_bytes(data, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
 }
 
-/// Utility: Get current timestamp
+/// Utility: Compute hash of data as a tagged string, e.g. `blake3:0x...`
+///
+/// Tagged commitments verify regardless of the proof's `hash_alg`, so a
+/// single proof can mix algorithms.
 #[wasm_bindgen]
-pub fn get_timestamp() -> u64 {
-    js_sys::Date::now() as u64
+pub fn hash_data_tagged(data: &[u8], algorithm: HashAlgorithm) -> Result<String, JsValue> {
+    hash_bytes_tagged(data, algorithm).map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
 }
 
-// Add hex dependency
-use std::fmt::Write as FmtWrite;
-
-mod hex {
-    use std::fmt::Write;
-
-    pub fn encode(bytes: &[u8]) -> String {
-        let mut s = String::with_capacity(bytes.len() * 2);
-        for &b in bytes {
-            write!(&mut s, "{:02x}", b).unwrap();
-        }
-        s
-    }
+/// Utility: Get current timestamp
+#[wasm_bindgen]
+pub fn get_timestamp() -> u64 {
+    clock::system_now_ms()
 }
 
 #[cfg(test)]
@@ -190,26 +843,30 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_verifier() {
-        let model_hash = "0x1234567890abcdef".to_string();
-        let verifier = WasmVerifier::new(model_hash.clone());
-
-        let input_data = b"test input";
-        let output_data = b"test output";
-
-        let input_hash = hash_data(input_data);
-        let output_hash = hash_data(output_data);
-
-        let proof = ProofData::new(
-            model_hash,
+    fn test_proof_json_round_trip() {
+        let mut proof = ProofData::new(
+            "0xmodel".to_string(),
             "0xproof".to_string(),
-            input_hash,
-            output_hash,
-            get_timestamp(),
+            hash_data(b"in", None).unwrap(),
+            hash_data(b"out", None).unwrap(),
+            1_700_000_000_000,
             true,
         );
+        proof.set_nonce(Some("0xnonce".to_string()));
+
+        let parsed = ProofData::from_json(&proof.to_json()).unwrap();
+        assert_eq!(parsed.input_hash(), proof.input_hash());
+        assert_eq!(parsed.timestamp(), 1_700_000_000_000);
+        assert_eq!(parsed.proof_id(), proof.proof_id());
+        assert!(proof.proof_id().starts_with("sha3:0x"));
 
-        let result = verifier.verify(&proof, input_data, output_data).unwrap();
-        assert!(result, "Verification should succeed");
+        // Key order doesn't matter, content does
+        let mut value: serde_json::Value = serde_json::from_str(&proof.to_json()).unwrap();
+        let fields: Vec<String> =
+            value.as_object().unwrap().iter().rev().map(|(k, v)| format!("{:?}: {}", k, v)).collect();
+        let reordered = format!("{{{}}}", fields.join(", "));
+        assert_eq!(ProofData::from_json(&reordered).unwrap().proof_id(), proof.proof_id());
+        value["nonce"] = "0xother".into();
+        assert_ne!(ProofData::from_json(&value.to_string()).unwrap().proof_id(), proof.proof_id());
     }
 }
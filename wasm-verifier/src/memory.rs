@@ -0,0 +1,69 @@
+// Memory accounting
+// `memory_stats` for JS: heap usage counted by the core's tracking allocator, and bytes hashed
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+
+/// What `memory_stats` reports
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct MemoryStats(zkml_verifier_core::memory::MemoryStats);
+
+wrap!(MemoryStats => zkml_verifier_core::memory::MemoryStats);
+
+#[wasm_bindgen]
+impl MemoryStats {
+    /// Bytes currently allocated on the WASM heap
+    #[wasm_bindgen(getter)]
+    pub fn heap_bytes(&self) -> u64 {
+        self.0.heap_bytes()
+    }
+
+    /// Most bytes allocated at once since load (or `reset_memory_stats`)
+    #[wasm_bindgen(getter)]
+    pub fn peak_heap_bytes(&self) -> u64 {
+        self.0.peak_heap_bytes()
+    }
+
+    /// Size of WASM linear memory (`undefined` outside WASM); it grows to fit the heap and never shrinks
+    #[wasm_bindgen(getter)]
+    pub fn memory_bytes(&self) -> Option<u64> {
+        self.0.memory_bytes()
+    }
+
+    /// Bytes hashed since load (or `reset_memory_stats`), including proof and model data
+    #[wasm_bindgen(getter)]
+    pub fn bytes_hashed(&self) -> u64 {
+        self.0.bytes_hashed()
+    }
+
+    /// Serialize stats to JSON string
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Current WASM heap usage, its peak, and bytes hashed
+///
+/// ```js
+/// const { heap_bytes, peak_heap_bytes } = memory_stats();
+/// if (peak_heap_bytes > 64n * 1024n * 1024n) console.warn("verifier used over 64 MiB");
+/// ```
+///
+/// Counts are shared by every verifier in this module instance. Use with
+/// `VerifierConfig.max_input_bytes` to keep verification within a budget.
+/// Heap figures are only counted in WASM builds; natively they stay zero.
+#[wasm_bindgen]
+pub fn memory_stats() -> MemoryStats {
+    MemoryStats(zkml_verifier_core::memory::memory_stats())
+}
+
+/// Restart the peak at the current heap size and the hashed byte count at zero
+#[wasm_bindgen]
+pub fn reset_memory_stats() {
+    zkml_verifier_core::memory::reset_memory_stats();
+}
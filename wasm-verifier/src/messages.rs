@@ -0,0 +1,44 @@
+// End-user messages
+// Localized, non-technical wording for each `VerificationError`, separate from the developer-facing reasons
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+use crate::report::{CheckResult, VerificationReport};
+
+/// End-user description of `error`, in `locale` (e.g. `navigator.language`)
+///
+/// Unknown locales fall back to English. Unlike a check's `reason`, the
+/// wording is meant for end users and stays the same across releases.
+#[wasm_bindgen]
+pub fn user_message(error: VerificationError, locale: Option<String>) -> String {
+    zkml_verifier_core::messages::user_message(error, locale.as_deref()).to_string()
+}
+
+/// Language tags `user_message` has a catalog for
+#[wasm_bindgen]
+pub fn supported_locales() -> Vec<String> {
+    zkml_verifier_core::messages::supported_locales().into_iter().map(str::to_string).collect()
+}
+
+#[wasm_bindgen]
+impl CheckResult {
+    /// `user_message` for this check's failure (`undefined` when it passed)
+    #[wasm_bindgen]
+    pub fn user_message(&self, locale: Option<String>) -> Option<String> {
+        zkml_verifier_core::CheckResult::user_message(self, locale.as_deref()).map(str::to_string)
+    }
+}
+
+#[wasm_bindgen]
+impl VerificationReport {
+    /// `user_message` for the first failed check (`undefined` when all passed)
+    ///
+    /// ```js
+    /// if (!report.passed) banner.textContent = report.user_message(navigator.language);
+    /// ```
+    #[wasm_bindgen]
+    pub fn user_message(&self, locale: Option<String>) -> Option<String> {
+        zkml_verifier_core::VerificationReport::user_message(self, locale.as_deref()).map(str::to_string)
+    }
+}
@@ -0,0 +1,88 @@
+// Verifier metadata
+// Structured description of a configured verifier, for UIs and diagnostics (`info()` is the text summary)
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::WasmVerifier;
+
+/// What `WasmVerifier::metadata` reports
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct VerifierMetadata(zkml_verifier_core::metadata::VerifierMetadata);
+
+wrap!(VerifierMetadata => zkml_verifier_core::metadata::VerifierMetadata);
+
+#[wasm_bindgen]
+impl VerifierMetadata {
+    /// Version of this verifier package
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> String {
+        self.0.version().to_string()
+    }
+
+    /// Proof encodings this build accepts, e.g. `"json"` or `"zkpf/1"` (binary format version 1)
+    #[wasm_bindgen(getter)]
+    pub fn proof_formats(&self) -> Vec<String> {
+        self.0.proof_formats().to_vec()
+    }
+
+    /// Models proofs may be bound to: the pinned hash, or every registry entry
+    #[wasm_bindgen(getter)]
+    pub fn model_hashes(&self) -> Vec<String> {
+        self.0.model_hashes().to_vec()
+    }
+
+    /// Checks this verifier runs, by `CheckName`, in report order
+    ///
+    /// Includes the ones that only run when a proof carries the data they
+    /// check (I/O specs, signatures); policy checks that are switched off
+    /// are left out.
+    #[wasm_bindgen(getter)]
+    pub fn checks(&self) -> Vec<String> {
+        self.0.checks().to_vec()
+    }
+
+    /// Hash algorithms enabled in this build
+    #[wasm_bindgen(getter)]
+    pub fn hash_algorithms(&self) -> Vec<String> {
+        self.0.hash_algorithms().to_vec()
+    }
+
+    /// Algorithm for commitments of proofs that don't name one
+    #[wasm_bindgen(getter)]
+    pub fn default_hash_algorithm(&self) -> String {
+        self.0.default_hash_algorithm().to_string()
+    }
+
+    /// Serialize metadata to JSON string
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Structured description of this verifier (see `VerifierMetadata`)
+    #[wasm_bindgen]
+    pub fn metadata(&self) -> VerifierMetadata {
+        VerifierMetadata(self.verifier.metadata(env!("CARGO_PKG_VERSION")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VerifierConfig;
+
+    #[test]
+    fn test_metadata() {
+        // Shorter than the 16 characters `info()` abbreviates to
+        let verifier = WasmVerifier::with_config("0xab".to_string(), &VerifierConfig::archival());
+        assert!(verifier.info().contains("0xab"));
+        assert_eq!(verifier.metadata().version(), env!("CARGO_PKG_VERSION"));
+    }
+}
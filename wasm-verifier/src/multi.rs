@@ -0,0 +1,82 @@
+// Named multi-input / multi-output proofs
+// Per-name I/O commitments for models with several inputs or outputs (e.g. tokens + attention mask)
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Collect a JS object of `name -> Uint8Array` into named buffers
+pub(crate) fn named_buffers(object: &js_sys::Object, what: &str) -> Result<Vec<(String, Vec<u8>)>, JsValue> {
+    js_sys::Object::entries(object)
+        .iter()
+        .map(|entry| {
+            let entry: js_sys::Array = entry.unchecked_into();
+            let name = entry.get(0).as_string().unwrap_or_default();
+            let data = entry.get(1).dyn_into::<js_sys::Uint8Array>().map_err(|_| {
+                VerificationError::InvalidArgument.to_js(&format!("{} '{}' is not a Uint8Array", what, name))
+            })?;
+            Ok((name, data.to_vec()))
+        })
+        .collect()
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Names of the committed inputs, in sorted order
+    #[wasm_bindgen(getter)]
+    pub fn input_names(&self) -> Vec<String> {
+        self.inputs.keys().cloned().collect()
+    }
+
+    /// Names of the committed outputs, in sorted order
+    #[wasm_bindgen(getter)]
+    pub fn output_names(&self) -> Vec<String> {
+        self.outputs.keys().cloned().collect()
+    }
+
+    #[wasm_bindgen]
+    pub fn input_commitment(&self, name: &str) -> Option<String> {
+        self.inputs.get(name).cloned()
+    }
+
+    #[wasm_bindgen]
+    pub fn output_commitment(&self, name: &str) -> Option<String> {
+        self.outputs.get(name).cloned()
+    }
+
+    /// Commit to a named input (`hash_data` of its buffer)
+    #[wasm_bindgen]
+    pub fn set_input_commitment(&mut self, name: String, commitment: String) {
+        self.inputs.insert(name, commitment);
+    }
+
+    /// Commit to a named output
+    #[wasm_bindgen]
+    pub fn set_output_commitment(&mut self, name: String, commitment: String) {
+        self.outputs.insert(name, commitment);
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a proof over named inputs and outputs
+    ///
+    /// ```js
+    /// verifier.verify_multi(proof, { input_ids, attention_mask }, { logits });
+    /// ```
+    ///
+    /// Each buffer is checked against the proof's commitment of the same
+    /// name; `input_hash` and `output_hash` are not used.
+    #[wasm_bindgen]
+    pub fn verify_multi(
+        &self,
+        proof_data: &ProofData,
+        inputs: &js_sys::Object,
+        outputs: &js_sys::Object,
+    ) -> Result<VerificationReport, JsValue> {
+        let inputs = named_buffers(inputs, "Input")?;
+        let outputs = named_buffers(outputs, "Output")?;
+        Ok(self.verifier.multi_report(proof_data, &inputs, &outputs).into())
+    }
+}
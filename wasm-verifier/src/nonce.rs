@@ -0,0 +1,20 @@
+// Challenge/nonce replay protection
+// The verifier issues single-use nonces that provers bind into `ProofData.nonce`
+
+use wasm_bindgen::prelude::*;
+
+use crate::WasmVerifier;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Issue a single-use challenge nonce for the prover to bind into its proof
+    ///
+    /// Only checked when `VerifierConfig.require_nonce` is set. Nonces expire
+    /// after `max_age_ms` and are consumed by the first proof that verifies.
+    #[wasm_bindgen]
+    pub fn issue_nonce(&self) -> Result<String, JsValue> {
+        self.verifier
+            .issue_nonce()
+            .map_err(|e| JsValue::from_str(&format!("Failed to generate nonce: {}", e)))
+    }
+}
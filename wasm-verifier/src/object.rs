@@ -0,0 +1,176 @@
+// Plain JS objects
+// Proofs and settings passed as already-parsed objects, read straight into serde types instead of through JSON text
+
+use wasm_bindgen::prelude::*;
+use serde_json::{Map, Number, Value};
+
+use crate::error::VerificationError;
+use crate::{ProofData, VerificationReport, VerifierConfig, VerifierPolicy, WasmVerifier};
+
+/// Largest integer a JS number holds exactly (`Number.MAX_SAFE_INTEGER`)
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Deepest nesting `to_value` walks, so a cyclic object fails instead of overflowing the stack
+const MAX_DEPTH: usize = 32;
+
+/// JSON number for a JS number; integral values become integers so they deserialize into `u64` fields
+fn number(n: f64) -> Option<Number> {
+    if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER {
+        Some(if n < 0.0 { Number::from(n as i64) } else { Number::from(n as u64) })
+    } else {
+        Number::from_f64(n)
+    }
+}
+
+/// Walk a JS value into the `serde_json::Value` that `JSON.stringify` + parse would give
+///
+/// Like `JSON.stringify`, `undefined` properties are dropped. `BigInt`s are
+/// accepted as integers (e.g. `timestamp: 1700000000000n`); functions,
+/// symbols, non-finite numbers and objects nested over `MAX_DEPTH` deep
+/// (including cyclic ones) are rejected.
+pub(crate) fn to_value(value: &JsValue) -> Result<Value, String> {
+    walk(value, 0)
+}
+
+fn walk(value: &JsValue, depth: usize) -> Result<Value, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("Nested more than {} levels deep", MAX_DEPTH));
+    }
+    if value.is_null() || value.is_undefined() {
+        return Ok(Value::Null);
+    }
+    if let Some(b) = value.as_bool() {
+        return Ok(Value::Bool(b));
+    }
+    if let Some(n) = value.as_f64() {
+        return number(n).map(Value::Number).ok_or_else(|| format!("{} is not a JSON number", n));
+    }
+    if let Some(s) = value.as_string() {
+        return Ok(Value::String(s));
+    }
+    if value.is_bigint() {
+        let digits = String::from(js_sys::BigInt::from(value.clone()).to_string(10).map_err(|_| "Invalid BigInt")?);
+        return serde_json::from_str::<Number>(&digits).map(Value::Number).map_err(|e| e.to_string());
+    }
+    if js_sys::Array::is_array(value) {
+        let array = js_sys::Array::from(value);
+        return array.iter().map(|item| walk(&item, depth + 1)).collect::<Result<_, _>>().map(Value::Array);
+    }
+    if value.is_object() && !value.is_function() {
+        let mut fields = Map::new();
+        for key in js_sys::Object::keys(value.unchecked_ref()).iter() {
+            let field = js_sys::Reflect::get(value, &key).map_err(|_| "Unreadable property")?;
+            if !field.is_undefined() {
+                let key = key.as_string().unwrap_or_default();
+                let field = walk(&field, depth + 1).map_err(|e| format!("{}: {}", key, e))?;
+                fields.insert(key, field);
+            }
+        }
+        return Ok(Value::Object(fields));
+    }
+    Err("Value has no JSON form".to_string())
+}
+
+/// Read a serde value from a plain JS object
+fn from_object<T>(value: &JsValue, error: VerificationError, what: &str) -> Result<T, JsValue>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_wasm_bindgen::from_value(value.clone()).map_err(|e| error.to_js(&format!("{}: {}", what, e)))
+}
+
+impl ProofData {
+    /// Read a proof from its plain-object form, mapping failures to `MalformedProof`
+    ///
+    /// Only `ProofData`'s own fields are read, so extra properties (DOM
+    /// nodes, back-references to the enclosing response) are never walked.
+    pub(crate) fn parse_object(value: &JsValue) -> Result<ProofData, JsValue> {
+        serde_wasm_bindgen::from_value(value.clone())
+            .map_err(|e| VerificationError::MalformedProof.to_js(&format!("Invalid proof object: {}", e)))
+    }
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Build a proof from its plain-object form
+    #[wasm_bindgen]
+    pub fn from_object(
+        #[wasm_bindgen(unchecked_param_type = "ProofDataJson")] value: JsValue,
+    ) -> Result<ProofData, JsValue> {
+        ProofData::parse_object(&value)
+    }
+}
+
+#[wasm_bindgen]
+impl VerifierConfig {
+    /// Build a config from a partial object, defaulting omitted fields
+    #[wasm_bindgen]
+    pub fn from_object(
+        #[wasm_bindgen(unchecked_param_type = "VerifierConfigJson")] value: JsValue,
+    ) -> Result<VerifierConfig, JsValue> {
+        from_object(&value, VerificationError::InvalidArgument, "Invalid verifier config")
+    }
+}
+
+#[wasm_bindgen]
+impl VerifierPolicy {
+    /// Build a policy from its plain-object form
+    #[wasm_bindgen]
+    pub fn from_object(
+        #[wasm_bindgen(unchecked_param_type = "VerifierPolicyJson")] value: JsValue,
+    ) -> Result<VerifierPolicy, JsValue> {
+        let json = to_value(&value).map_err(|e| VerificationError::InvalidArgument.to_js(&e))?;
+        VerifierPolicy::try_from_value(json).map_err(|reason| VerificationError::InvalidArgument.to_js(&reason))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// `verify_json` for a proof that is already a JS object
+    ///
+    /// ```js
+    /// const { proof } = await (await fetch("/inference")).json();
+    /// const ok = verifier.verify_object(proof, input, output);
+    /// ```
+    ///
+    /// The object is read field by field, so there's no need to
+    /// `JSON.stringify` it first. Throws `VerificationError.MalformedProof`
+    /// if it isn't a valid proof.
+    #[wasm_bindgen]
+    pub fn verify_object(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "ProofDataJson")] proof: JsValue,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<bool, JsValue> {
+        let proof_data = ProofData::parse_object(&proof)?;
+        self.verify(&proof_data, input_bytes, output_bytes)
+    }
+
+    /// `verify_object`, returning per-check results
+    #[wasm_bindgen]
+    pub fn verify_object_report(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "ProofDataJson")] proof: JsValue,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, JsValue> {
+        let proof_data = ProofData::parse_object(&proof)?;
+        Ok(self.verify_report(&proof_data, input_bytes, output_bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_keep_integers_exact() {
+        let timestamp = number(1_700_000_000_000.0).unwrap();
+        assert_eq!(timestamp.as_u64(), Some(1_700_000_000_000));
+        assert_eq!(number(-3.0).unwrap().as_i64(), Some(-3));
+        assert_eq!(number(0.5).unwrap().as_f64(), Some(0.5));
+        assert!(number(2f64.powi(60)).unwrap().as_u64().is_none());
+        assert!(number(f64::NAN).is_none());
+    }
+}
@@ -0,0 +1,222 @@
+// On-chain registry
+// Model approvals and prover registrations read from a registry contract through an EIP-1193 provider
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use sha3::{Digest, Keccak256};
+use zkml_verifier_core::evm::bytes32;
+
+use crate::error::VerificationError;
+use crate::report::{Check, Stopwatch};
+use crate::{hex, ProofData, VerificationReport, WasmVerifier};
+
+/// Registry function asked whether a model hash is approved
+pub const MODEL_APPROVED_SIGNATURE: &str = "isModelApproved(bytes32)";
+
+/// Registry function asked whether a prover key is registered
+pub const PROVER_REGISTERED_SIGNATURE: &str = "isProverRegistered(bytes32)";
+
+/// A registry contract, and the provider (`window.ethereum` or similar) it is read through
+pub(crate) struct OnChainRegistry {
+    provider: JsValue,
+    address: [u8; 20],
+}
+
+/// `eth_call` data for `signature(argument)`
+fn call_data(signature: &str, argument: &[u8; 32]) -> String {
+    let selector = &Keccak256::digest(signature.as_bytes())[..4];
+    format!("0x{}{}", hex::encode(selector), hex::encode(argument))
+}
+
+/// Decode an ABI-encoded `bool` return value
+fn decode_bool(result: &str) -> Result<bool, String> {
+    match hex::decode(result).as_deref() {
+        Some([zeros @ .., last]) if zeros.len() == 31 && zeros.iter().all(|&b| b == 0) && *last <= 1 => Ok(*last == 1),
+        _ => Err(format!("Registry returned '{}', not a bool", result)),
+    }
+}
+
+/// Registry argument for a prover key: a 32-byte key, or an address left-padded to 32 bytes
+fn registry_key(key: &str) -> Result<[u8; 32], String> {
+    match hex::decode(key) {
+        Some(key) if key.len() == 20 => Ok([[0u8; 12].as_slice(), &key].concat().try_into().unwrap_or_default()),
+        Some(key) => key.try_into().map_err(|_| "signer_pubkey is not an address or 32-byte key".to_string()),
+        None => Err("signer_pubkey is not hex".to_string()),
+    }
+}
+
+/// Registry argument for the proof's `signer_pubkey`, if it names one
+///
+/// A key only says who proved the inference once the signature check has
+/// passed under it: until then, asking the registry would vouch for a
+/// prover that never signed.
+fn prover_key(proof_data: &ProofData, report: &VerificationReport) -> Option<Result<[u8; 32], String>> {
+    let key = proof_data.signer_pubkey.as_deref()?;
+    if !report.check_passed(Check::Signature) {
+        return Some(Err("Proof is not signed by signer_pubkey".to_string()));
+    }
+    Some(registry_key(key))
+}
+
+/// Record a registry answer as `check`; `refused` is the reason when the registry says no
+fn record_lookup(
+    report: &mut VerificationReport,
+    check: Check,
+    answer: Result<bool, String>,
+    refused: &str,
+    started: Stopwatch,
+) {
+    match answer {
+        Ok(approved) => report.record(check, approved, refused, started),
+        Err(reason) => report.record(check, false, &reason, started),
+    }
+}
+
+impl OnChainRegistry {
+    /// Call a `bool`-returning registry function through the provider
+    async fn ask(&self, signature: &str, argument: &[u8; 32]) -> Result<bool, String> {
+        let request = js_sys::Reflect::get(&self.provider, &"request".into())
+            .ok()
+            .and_then(|request| request.dyn_into::<js_sys::Function>().ok())
+            .ok_or("Provider has no request() method")?;
+        let to = format!("0x{}", hex::encode(&self.address));
+        let call = serde_json::json!({
+            "method": "eth_call",
+            "params": [{ "to": to, "data": call_data(signature, argument) }, "latest"],
+        });
+        let args = js_sys::JSON::parse(&call.to_string()).map_err(|_| "Cannot build the registry call")?;
+        let failed = |_| format!("Registry call {} failed", signature);
+        let pending = request.call1(&self.provider, &args).map_err(failed)?;
+        let result = JsFuture::from(js_sys::Promise::resolve(&pending)).await.map_err(failed)?;
+        decode_bool(&result.as_string().unwrap_or_default())
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Consult the registry contract at `registry_address` through an EIP-1193 `provider`
+    ///
+    /// ```js
+    /// verifier.set_onchain_registry(window.ethereum, "0x…");
+    /// const report = await verifier.verify_report_onchain(proof, input, output);
+    /// ```
+    ///
+    /// The contract answers `isModelApproved(bytes32 modelHash)` and
+    /// `isProverRegistered(bytes32 key)` (addresses left-padded) with a
+    /// `bool`. Pass a `null` provider to stop consulting it. Throws
+    /// `VerificationError.InvalidArgument` for a malformed address.
+    #[wasm_bindgen]
+    pub fn set_onchain_registry(&mut self, provider: JsValue, registry_address: &str) -> Result<(), JsValue> {
+        if provider.is_null() || provider.is_undefined() {
+            self.onchain_registry = None;
+            return Ok(());
+        }
+        let address = hex::decode(registry_address)
+            .and_then(|address| address.try_into().ok())
+            .ok_or_else(|| VerificationError::InvalidArgument.to_js("Registry address is not an Ethereum address"))?;
+        self.onchain_registry = Some(OnChainRegistry { provider, address });
+        Ok(())
+    }
+
+    /// `verify_report` plus the on-chain registry's say, returning a Promise
+    ///
+    /// Adds a `registry_model` check that the proof's model is approved and,
+    /// for proofs naming a `signer_pubkey`, a `registry_prover` check that
+    /// the key is registered. Either fails with `NotRegistered` when the
+    /// registry says no or can't be reached; `registry_prover` also fails,
+    /// without asking, unless the proof's signature verified under the key. Rejects with
+    /// `VerificationError.InvalidArgument` if no registry is set.
+    #[wasm_bindgen]
+    pub async fn verify_report_onchain(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+    ) -> Result<VerificationReport, JsValue> {
+        let registry = self.onchain_registry.as_ref().ok_or_else(|| {
+            VerificationError::InvalidArgument.to_js("No on-chain registry set; call set_onchain_registry first")
+        })?;
+        let mut report = self.verify_report(proof_data, &input_bytes, &output_bytes);
+
+        let started = report.start(Check::RegistryModel);
+        let answer = match bytes32(&proof_data.model_hash, "model_hash is not a 32-byte digest") {
+            Ok(model) => registry.ask(MODEL_APPROVED_SIGNATURE, &model).await,
+            Err((_, reason)) => Err(reason.to_string()),
+        };
+        let refused = "Model is not approved in the on-chain registry";
+        record_lookup(&mut report, Check::RegistryModel, answer, refused, started);
+
+        if let Some(key) = prover_key(proof_data, &report) {
+            let started = report.start(Check::RegistryProver);
+            let answer = match key {
+                Ok(key) => registry.ask(PROVER_REGISTERED_SIGNATURE, &key).await,
+                Err(reason) => Err(reason),
+            };
+            let refused = "Prover key is not registered in the on-chain registry";
+            record_lookup(&mut report, Check::RegistryProver, answer, refused, started);
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_registry_lookup() {
+        let model = [0x11; 32];
+        let data = call_data(MODEL_APPROVED_SIGNATURE, &model);
+        let selector = hex::encode(&Keccak256::digest(MODEL_APPROVED_SIGNATURE)[..4]);
+        assert_eq!(data, format!("0x{}{}", selector, "11".repeat(32)));
+
+        assert_eq!(decode_bool(&format!("0x{}01", "00".repeat(31))), Ok(true));
+        assert_eq!(decode_bool(&format!("0x{}", "00".repeat(32))), Ok(false));
+        assert!(decode_bool(&format!("0x{}02", "00".repeat(31))).is_err());
+        assert!(decode_bool("0x").is_err());
+
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let mut proof = fixtures::proof("0xmodel");
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        assert_eq!(prover_key(&proof, &report), None);
+        let key = registry_key(&format!("0x{}", "ab".repeat(20))).unwrap();
+        assert_eq!((key[..12].to_vec(), key[12..].to_vec()), (vec![0; 12], vec![0xab; 20]));
+        assert_eq!(registry_key(&"cd".repeat(32)), Ok([0xcd; 32]));
+        assert!(registry_key("0x1234").is_err());
+
+        // A registered key on an unsigned proof is not asked about
+        proof.signer_pubkey = Some("cd".repeat(32));
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        assert!(report.passed());
+        let unsigned = prover_key(&proof, &report).unwrap();
+        assert_eq!(unsigned, Err("Proof is not signed by signer_pubkey".to_string()));
+        let mut failed = report.clone();
+        record_lookup(&mut failed, Check::RegistryProver, unsigned.map(|_| true), "", Stopwatch::start());
+        assert_eq!(failed.error(), Some(VerificationError::NotRegistered));
+        assert_eq!(failed.failure_reasons(), ["Proof is not signed by signer_pubkey"]);
+
+        #[cfg(feature = "rust-signatures")]
+        {
+            use ed25519_dalek::{Signer, SigningKey};
+            let mut verifier = WasmVerifier::new("0xmodel".to_string());
+            let signing = SigningKey::from_bytes(&[9u8; 32]);
+            let pubkey = hex::encode(signing.verifying_key().as_bytes());
+            verifier.add_trusted_signer(pubkey.clone());
+            let mut signed = proof.clone();
+            signed.set_signer_pubkey(Some(pubkey));
+            signed.set_signature(Some(hex::encode(&signing.sign(&signed.signing_bytes()).to_bytes())));
+            let report = verifier.verify_report(&signed, b"in", b"out");
+            assert_eq!(prover_key(&signed, &report), Some(Ok(*signing.verifying_key().as_bytes())));
+        }
+
+        // Registry answers merge into the local report
+        let mut report = verifier.verify_report(&proof, b"in", b"out");
+        record_lookup(&mut report, Check::RegistryModel, Ok(true), "", Stopwatch::start());
+        assert!(report.check_passed(Check::RegistryModel));
+        let refused = "Prover key is not registered in the on-chain registry";
+        record_lookup(&mut report, Check::RegistryProver, Ok(false), refused, Stopwatch::start());
+        assert_eq!(report.error(), Some(VerificationError::NotRegistered));
+        assert_eq!(report.failure_reasons(), vec![refused.to_string()]);
+    }
+}
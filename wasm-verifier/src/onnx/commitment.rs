@@ -0,0 +1,125 @@
+// Structured model commitment
+// `model_hash` as the root over separate architecture, weights and metadata hashes
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::{ProofData, VerifierConfig, WasmVerifier};
+
+/// Architecture, weights and metadata hashes of a model, and their root
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ModelCommitment(zkml_verifier_core::ModelCommitment);
+
+/// Which leaves of a structured model commitment a verifier pins
+///
+/// Unpinned leaves may take any value, e.g. pin the architecture and
+/// approve a list of fine-tuned weights.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct CommitmentPolicy(zkml_verifier_core::CommitmentPolicy);
+
+wrap!(
+    ModelCommitment => zkml_verifier_core::ModelCommitment,
+    CommitmentPolicy => zkml_verifier_core::CommitmentPolicy,
+);
+
+#[wasm_bindgen]
+impl ModelCommitment {
+    #[wasm_bindgen(getter)]
+    pub fn architecture_hash(&self) -> String {
+        self.0.architecture_hash().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn weights_hash(&self) -> String {
+        self.0.weights_hash().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn metadata_hash(&self) -> String {
+        self.0.metadata_hash().to_string()
+    }
+
+    /// Root over the three leaves, for use as `model_hash`
+    #[wasm_bindgen(getter)]
+    pub fn root(&self) -> String {
+        self.0.root().to_string()
+    }
+}
+
+/// Structured commitment to an ONNX model (see `ProofData::set_model_commitment`)
+#[wasm_bindgen]
+pub fn commit_onnx_model(model_bytes: &[u8], algorithm: Option<HashAlgorithm>) -> Result<ModelCommitment, JsValue> {
+    let commitment = zkml_verifier_core::ModelCommitment::create(model_bytes, algorithm.unwrap_or_default());
+    commitment.map(ModelCommitment).map_err(|e| match e {
+        VerificationError::InvalidModel => e.to_js("Bytes are not a valid ONNX model"),
+        e => e.to_js("Hash algorithm is not enabled in this build"),
+    })
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Set `model_hash` to the commitment's root and carry its three leaves
+    #[wasm_bindgen]
+    pub fn set_model_commitment(&mut self, commitment: &ModelCommitment) {
+        self.0.set_model_commitment(commitment);
+    }
+}
+
+#[wasm_bindgen]
+impl CommitmentPolicy {
+    /// Policy pinning nothing (any well-formed commitment passes)
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> CommitmentPolicy {
+        CommitmentPolicy::default()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn architecture_hash(&self) -> Option<String> {
+        self.0.architecture_hash().map(str::to_string)
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_architecture_hash(&mut self, architecture_hash: Option<String>) {
+        self.0.set_architecture_hash(architecture_hash);
+    }
+
+    /// Approved weights hashes (empty = any weights)
+    #[wasm_bindgen(getter)]
+    pub fn weights_hashes(&self) -> Vec<String> {
+        self.0.weights_hashes().to_vec()
+    }
+
+    #[wasm_bindgen]
+    pub fn approve_weights(&mut self, weights_hash: String) {
+        self.0.approve_weights(weights_hash);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn metadata_hash(&self) -> Option<String> {
+        self.0.metadata_hash().map(str::to_string)
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_metadata_hash(&mut self, metadata_hash: Option<String>) {
+        self.0.set_metadata_hash(metadata_hash);
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Create a verifier binding proofs to structured model commitments allowed by `policy`
+    ///
+    /// `model_hash` is then unused: proofs must carry the three leaves,
+    /// their root must be the proof's `model_hash`, and pinned leaves must match.
+    #[wasm_bindgen]
+    pub fn with_commitment_policy(policy: &CommitmentPolicy, config: Option<VerifierConfig>) -> WasmVerifier {
+        zkml_verifier_core::Verifier::with_commitment_policy(policy, &config.unwrap_or_default()).into()
+    }
+}
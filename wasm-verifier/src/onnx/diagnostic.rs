@@ -0,0 +1,49 @@
+// Model binding diagnostics
+// Why a proof's model_hash doesn't match a verifier built from the model file
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+
+/// Which parts of the model a failed binding differs in
+///
+/// Leaf comparisons are `undefined` when the proof doesn't carry that
+/// sub-hash (see `ProofData::set_model_commitment`).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ModelDiagnostic(zkml_verifier_core::ModelDiagnostic);
+
+wrap!(ModelDiagnostic => zkml_verifier_core::ModelDiagnostic);
+
+#[wasm_bindgen]
+impl ModelDiagnostic {
+    /// Whether the proof's `architecture_hash` matches (`undefined` if it has none)
+    #[wasm_bindgen(getter)]
+    pub fn architecture(&self) -> Option<bool> {
+        self.0.architecture()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn weights(&self) -> Option<bool> {
+        self.0.weights()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn metadata(&self) -> Option<bool> {
+        self.0.metadata()
+    }
+
+    /// Whether `model_hash` is the plain hash of the model file bytes
+    #[wasm_bindgen(getter)]
+    pub fn file_hash(&self) -> bool {
+        self.0.file_hash()
+    }
+
+    /// One-line explanation, also appended to the binding failure reason
+    #[wasm_bindgen(getter)]
+    pub fn summary(&self) -> String {
+        self.0.summary().to_string()
+    }
+}
@@ -0,0 +1,82 @@
+// External-data ONNX model hashing
+// Canonical hash of a model whose initializers live in separate `.bin` files
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::multi::named_buffers;
+
+fn to_js(e: VerificationError) -> JsValue {
+    match e {
+        VerificationError::InvalidModel => e.to_js("Not a valid ONNX model, or its external data is incomplete"),
+        VerificationError::InvalidArgument => e.to_js("Model has no external data in that file"),
+        e => e.to_js("Hash algorithm is not enabled in this build"),
+    }
+}
+
+/// Canonical hash of an ONNX model plus its external data files
+///
+/// Each external tensor is hashed on its own and its `external_data`
+/// entries are replaced by that digest and its length before the model is
+/// hashed as by `hash_onnx_model`. The hash therefore doesn't depend on
+/// file names or how tensors are laid out across files, and files can be
+/// streamed in chunks of any size:
+///
+/// ```js
+/// const hasher = new ExternalDataHasher(modelBytes);
+/// for (const file of hasher.required_files) {
+///   for await (const chunk of open(file)) hasher.update(file, chunk);
+/// }
+/// const modelHash = hasher.finalize();
+/// ```
+#[wasm_bindgen]
+pub struct ExternalDataHasher(zkml_verifier_core::onnx::external::ExternalDataHasher);
+
+#[wasm_bindgen]
+impl ExternalDataHasher {
+    /// Start hashing a model (SHA3-256 unless `algorithm` is given)
+    #[wasm_bindgen(constructor)]
+    pub fn new(model_bytes: &[u8], algorithm: Option<HashAlgorithm>) -> Result<ExternalDataHasher, JsValue> {
+        zkml_verifier_core::onnx::external::ExternalDataHasher::create(model_bytes, algorithm.unwrap_or_default())
+            .map(ExternalDataHasher)
+            .map_err(to_js)
+    }
+
+    /// External data files the model references (`location` entries)
+    #[wasm_bindgen(getter)]
+    pub fn required_files(&self) -> Vec<String> {
+        self.0.required_files()
+    }
+
+    /// Absorb the next chunk of file `location`; chunks of a file must arrive in order
+    #[wasm_bindgen]
+    pub fn update(&mut self, location: &str, chunk: &[u8]) -> Result<(), JsValue> {
+        self.0.feed(location, chunk).map_err(to_js)
+    }
+
+    /// Finish and return the canonical model hash
+    ///
+    /// Throws `VerificationError.InvalidModel` if any external tensor
+    /// wasn't fed in full.
+    #[wasm_bindgen]
+    pub fn finalize(self) -> Result<String, JsValue> {
+        self.0.finish().map_err(to_js)
+    }
+}
+
+/// Canonical hash of a model and its external data, given as `{ "weights.bin": Uint8Array }`
+///
+/// For files too large to hold in memory use `ExternalDataHasher`.
+#[wasm_bindgen]
+pub fn hash_onnx_model_external(
+    model_bytes: &[u8],
+    files: &js_sys::Object,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String, JsValue> {
+    let mut hasher = ExternalDataHasher::new(model_bytes, algorithm)?;
+    for (location, data) in named_buffers(files, "External data file")? {
+        hasher.update(&location, &data)?;
+    }
+    hasher.finalize()
+}
@@ -0,0 +1,112 @@
+// ONNX model inspection
+// Summarizes a model's interface and size for display in verifier UIs
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+
+/// A graph input or output
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct TensorInfo(zkml_verifier_core::onnx::inspect::TensorInfo);
+
+/// What `inspect_model` reports about an ONNX model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ModelInfo(zkml_verifier_core::onnx::inspect::ModelInfo);
+
+wrap!(
+    TensorInfo => zkml_verifier_core::onnx::inspect::TensorInfo,
+    ModelInfo => zkml_verifier_core::onnx::inspect::ModelInfo,
+);
+
+#[wasm_bindgen]
+impl TensorInfo {
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.0.name().to_string()
+    }
+
+    /// Element type, e.g. `"float32"` (`"unknown"` for non-tensor values)
+    #[wasm_bindgen(getter)]
+    pub fn dtype(&self) -> String {
+        self.0.dtype().to_string()
+    }
+
+    /// Dimensions, with -1 for dynamic ones
+    #[wasm_bindgen(getter)]
+    pub fn shape(&self) -> Vec<i64> {
+        self.0.shape().to_vec()
+    }
+
+    /// Symbolic names of the dimensions (`""` where the size is fixed or unnamed)
+    #[wasm_bindgen(getter)]
+    pub fn dim_params(&self) -> Vec<String> {
+        self.0.dim_params().to_vec()
+    }
+}
+
+#[wasm_bindgen]
+impl ModelInfo {
+    #[wasm_bindgen(getter)]
+    pub fn ir_version(&self) -> i64 {
+        self.0.ir_version()
+    }
+
+    /// Version of the default (`ai.onnx`) operator set, 0 if not imported
+    #[wasm_bindgen(getter)]
+    pub fn opset_version(&self) -> i64 {
+        self.0.opset_version()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn producer_name(&self) -> String {
+        self.0.producer_name().to_string()
+    }
+
+    /// Runtime inputs (initializers listed as graph inputs are excluded)
+    #[wasm_bindgen(getter)]
+    pub fn inputs(&self) -> Vec<TensorInfo> {
+        self.0.inputs().iter().cloned().map(TensorInfo).collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn outputs(&self) -> Vec<TensorInfo> {
+        self.0.outputs().iter().cloned().map(TensorInfo).collect()
+    }
+
+    /// Total number of weight elements across all initializers
+    #[wasm_bindgen(getter)]
+    pub fn parameter_count(&self) -> u64 {
+        self.0.parameter_count()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn node_count(&self) -> u32 {
+        self.0.node_count()
+    }
+
+    /// Distinct operator types, `domain::Op` for non-default domains, sorted
+    #[wasm_bindgen(getter)]
+    pub fn op_types(&self) -> Vec<String> {
+        self.0.op_types().to_vec()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// Describe an ONNX model: opset, inputs/outputs (names, shapes, dtypes) and parameter count
+///
+/// Throws `VerificationError.InvalidModel` if the bytes are not an ONNX model.
+#[wasm_bindgen]
+pub fn inspect_model(model_bytes: &[u8]) -> Result<ModelInfo, JsValue> {
+    zkml_verifier_core::onnx::inspect::inspect_model(model_bytes)
+        .map(ModelInfo)
+        .map_err(|e| e.to_js("Bytes are not a valid ONNX model"))
+}
@@ -0,0 +1,111 @@
+// Per-layer Merkle commitment of an ONNX graph
+// RFC 9162-style tree over the graph header, each node and each initializer
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use zkml_verifier_core::onnx::merkle::{layer_labels, merkle_root};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::WasmVerifier;
+
+/// Inclusion proof for one layer of a Merkle-committed ONNX model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct LayerProof(zkml_verifier_core::onnx::merkle::LayerProof);
+
+wrap!(LayerProof => zkml_verifier_core::onnx::merkle::LayerProof);
+
+#[wasm_bindgen]
+impl LayerProof {
+    /// Layer label, e.g. `node/3` or `initializer/classifier.weight`
+    #[wasm_bindgen(getter)]
+    pub fn layer(&self) -> String {
+        self.0.layer().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn index(&self) -> u64 {
+        self.0.index()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn leaf_count(&self) -> u64 {
+        self.0.leaf_count()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm()
+    }
+
+    /// Canonical protobuf encoding of the layer (`NodeProto` or `TensorProto`)
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.0.data()
+    }
+
+    /// Hash of the layer's canonical protobuf, to compare against a published layer hash
+    #[wasm_bindgen(getter)]
+    pub fn layer_hash(&self) -> String {
+        self.0.layer_hash()
+    }
+
+    /// Check the proof against a Merkle root (`onnx_merkle_root`)
+    #[wasm_bindgen]
+    pub fn verify(&self, root: &str) -> bool {
+        self.0.verifies(root)
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<LayerProof, JsValue> {
+        zkml_verifier_core::onnx::merkle::LayerProof::from_json(json)
+            .map(LayerProof)
+            .map_err(|_| VerificationError::MalformedProof.to_js("Invalid layer proof JSON"))
+    }
+}
+
+/// Merkle root over an ONNX model's header, nodes and initializers
+///
+/// Use it as `model_hash` to be able to prove individual layers later
+/// with `prove_onnx_layer`, without shipping the whole model.
+#[wasm_bindgen]
+pub fn onnx_merkle_root(model_bytes: &[u8], algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    merkle_root(model_bytes, algorithm.unwrap_or_default()).map_err(|e| e.to_js("Cannot commit to ONNX model"))
+}
+
+/// Labels of the layers committed by `onnx_merkle_root`, in leaf order
+#[wasm_bindgen]
+pub fn onnx_merkle_layers(model_bytes: &[u8]) -> Result<Vec<String>, JsValue> {
+    layer_labels(model_bytes).map_err(|e| e.to_js("Bytes are not a valid ONNX model"))
+}
+
+/// Build an inclusion proof for one layer (see `onnx_merkle_layers` for labels)
+#[wasm_bindgen]
+pub fn prove_onnx_layer(
+    model_bytes: &[u8],
+    layer: &str,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<LayerProof, JsValue> {
+    let proof = zkml_verifier_core::onnx::merkle::LayerProof::create(model_bytes, layer, algorithm.unwrap_or_default());
+    proof.map(LayerProof).map_err(|e| match e {
+        VerificationError::InvalidArgument => e.to_js("Model has no such layer"),
+        e => e.to_js("Cannot commit to ONNX model"),
+    })
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Check that a layer belongs to this verifier's (Merkle-root) model hash
+    #[wasm_bindgen]
+    pub fn verify_layer(&self, proof: &LayerProof) -> bool {
+        proof.verifies(self.verifier.model_hash())
+    }
+}
@@ -0,0 +1,58 @@
+// ONNX model hashing
+// Commits to a model's semantics rather than its exact serialized bytes
+
+use wasm_bindgen::prelude::*;
+
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::WasmVerifier;
+
+pub(crate) mod commitment;
+pub(crate) mod diagnostic;
+pub(crate) mod external;
+pub(crate) mod inspect;
+pub(crate) mod merkle;
+pub(crate) mod partial;
+pub(crate) mod policy;
+pub(crate) mod stream;
+
+pub(crate) use zkml_verifier_core::onnx::model_hash;
+
+/// Canonical hash of an ONNX model, for use as `model_hash` (default SHA3-256)
+///
+/// Unlike `hash_data` on the file bytes, re-exporting the same model with a
+/// different producer, doc strings or node names gives the same hash.
+/// Throws `VerificationError.InvalidModel` if the bytes are not an ONNX model.
+#[wasm_bindgen]
+pub fn hash_onnx_model(model_bytes: &[u8], algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    model_hash(model_bytes, algorithm.unwrap_or_default()).map_err(|e| match e {
+        VerificationError::InvalidModel => e.to_js("Bytes are not a valid ONNX model"),
+        e => e.to_js("Hash algorithm is not enabled in this build"),
+    })
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Create a verifier for an ONNX model file, hashing it internally
+    ///
+    /// The model is kept in canonical form and re-hashed with each proof's
+    /// algorithm (its `hash_alg` or `model_hash` tag), so callers never pick
+    /// one up front. `info()` reports the hash under the config's algorithm.
+    /// When binding fails, the report's `model_diagnostic` says which parts
+    /// of the model (architecture, weights, metadata) differ.
+    #[wasm_bindgen]
+    pub fn from_model_bytes(model: &[u8], config: Option<VerifierConfig>) -> Result<WasmVerifier, JsValue> {
+        WasmVerifier::try_from_model_bytes(model, &config.unwrap_or_default()).map_err(|e| match e {
+            VerificationError::InvalidModel => e.to_js("Bytes are not a valid ONNX model"),
+            e => e.to_js("Hash algorithm is not enabled in this build"),
+        })
+    }
+}
+
+impl WasmVerifier {
+    /// `from_model_bytes` for native callers
+    pub fn try_from_model_bytes(model: &[u8], config: &VerifierConfig) -> Result<WasmVerifier, VerificationError> {
+        zkml_verifier_core::Verifier::try_from_model_bytes(model, config).map(WasmVerifier::from)
+    }
+}
@@ -0,0 +1,53 @@
+// Partial model commitment
+// Commits to a chosen subset of an ONNX model's layers, e.g. only a fine-tuned classification head
+
+use wasm_bindgen::prelude::*;
+use zkml_verifier_core::onnx::partial::partial_root;
+
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::WasmVerifier;
+
+fn to_js(e: VerificationError) -> JsValue {
+    match e {
+        VerificationError::InvalidArgument => e.to_js("Selection is empty or names a layer the model doesn't have"),
+        e => e.to_js("Cannot commit to ONNX model"),
+    }
+}
+
+/// Commitment to a subset of a model's layers, for `ProofData.partial_model_hash`
+///
+/// Set `partial_layers` on the proof to the same labels.
+#[wasm_bindgen]
+pub fn onnx_partial_hash(
+    model_bytes: &[u8],
+    layers: Vec<String>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String, JsValue> {
+    partial_root(model_bytes, &layers, algorithm.unwrap_or_default()).map(|(root, _)| root).map_err(to_js)
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Create a verifier bound only to some layers of a model
+    ///
+    /// Model binding then checks the proof's `partial_model_hash` and
+    /// `partial_layers` instead of `model_hash`, so any model sharing these
+    /// layers (e.g. the same fine-tuned head on a public base) is accepted.
+    #[wasm_bindgen]
+    pub fn from_model_layers(
+        model_bytes: &[u8],
+        layers: Vec<String>,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<WasmVerifier, JsValue> {
+        zkml_verifier_core::Verifier::from_model_layers(model_bytes, &layers, algorithm.unwrap_or_default())
+            .map(WasmVerifier::from)
+            .map_err(to_js)
+    }
+
+    /// Layers this verifier is bound to (`undefined` when bound to the whole model)
+    #[wasm_bindgen(getter)]
+    pub fn partial_layers(&self) -> Option<Vec<String>> {
+        self.verifier.partial_layers().map(<[String]>::to_vec)
+    }
+}
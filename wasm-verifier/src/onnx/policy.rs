@@ -0,0 +1,106 @@
+// Model architecture policy
+// Constraints an ONNX graph must satisfy before its hash is accepted by a verifier
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::hash::HashAlgorithm;
+use crate::WasmVerifier;
+
+/// Architecture constraints for ONNX models
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ModelPolicy(zkml_verifier_core::onnx::policy::ModelPolicy);
+
+wrap!(ModelPolicy => zkml_verifier_core::onnx::policy::ModelPolicy);
+
+#[wasm_bindgen]
+impl ModelPolicy {
+    /// Policy that only rejects custom (non-standard-domain) ops
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ModelPolicy {
+        ModelPolicy::default()
+    }
+
+    /// Lowest accepted `ai.onnx` opset (0 = no minimum)
+    #[wasm_bindgen(getter)]
+    pub fn min_opset(&self) -> i64 {
+        self.0.min_opset()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_min_opset(&mut self, min_opset: i64) {
+        self.0.set_min_opset(min_opset);
+    }
+
+    /// Highest accepted `ai.onnx` opset (0 = no maximum)
+    #[wasm_bindgen(getter)]
+    pub fn max_opset(&self) -> i64 {
+        self.0.max_opset()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_opset(&mut self, max_opset: i64) {
+        self.0.set_max_opset(max_opset);
+    }
+
+    /// Operator types the graph may use (empty = any standard op)
+    #[wasm_bindgen(getter)]
+    pub fn allowed_ops(&self) -> Vec<String> {
+        self.0.allowed_ops().to_vec()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_allowed_ops(&mut self, allowed_ops: Vec<String>) {
+        self.0.set_allowed_ops(allowed_ops);
+    }
+
+    /// Accept ops from non-standard domains and model-local functions
+    #[wasm_bindgen(getter)]
+    pub fn allow_custom_ops(&self) -> bool {
+        self.0.allow_custom_ops()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_allow_custom_ops(&mut self, allow_custom_ops: bool) {
+        self.0.set_allow_custom_ops(allow_custom_ops);
+    }
+
+    /// Largest accepted parameter count (0 = unlimited)
+    #[wasm_bindgen(getter)]
+    pub fn max_parameters(&self) -> u64 {
+        self.0.max_parameters()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_max_parameters(&mut self, max_parameters: u64) {
+        self.0.set_max_parameters(max_parameters);
+    }
+
+    /// Every way the model breaks this policy (empty if it complies)
+    #[wasm_bindgen]
+    pub fn violations(&self, model_bytes: &[u8]) -> Result<Vec<String>, JsValue> {
+        self.0.violations(model_bytes).map_err(|e| e.to_js("Bytes are not a valid ONNX model"))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Create a verifier for an ONNX model, enforcing `policy` first
+    ///
+    /// The model hash is the canonical `hash_onnx_model` hash. Throws
+    /// `VerificationError.PolicyViolation` listing every violation, so a
+    /// model with custom ops is rejected even if its hash is allowlisted.
+    #[wasm_bindgen]
+    pub fn from_onnx_model(
+        model_bytes: &[u8],
+        policy: &ModelPolicy,
+        algorithm: Option<HashAlgorithm>,
+    ) -> Result<WasmVerifier, JsValue> {
+        zkml_verifier_core::Verifier::from_onnx_model(model_bytes, policy, algorithm.unwrap_or_default())
+            .map(WasmVerifier::from)
+            .map_err(|(error, reason)| error.to_js(&reason))
+    }
+}
@@ -0,0 +1,70 @@
+// Streaming ONNX model hashing
+// Layer Merkle root of a model fed in chunks, without ever holding its weights in memory
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+
+fn to_js(e: VerificationError) -> JsValue {
+    match e {
+        VerificationError::InvalidModel => e.to_js("Not a valid (or not a complete) ONNX model"),
+        e => e.to_js("Hash algorithm is not enabled in this build"),
+    }
+}
+
+/// Chunked hasher producing `onnx_merkle_root` of a model
+///
+/// The flat `hash_onnx_model` hash can't be streamed: the canonical graph
+/// is length-prefixed, and its length depends on fields after the weights.
+/// The Merkle root can, so use it as `model_hash` for models too large to
+/// load at once. Initializer `raw_data` is hashed as it arrives; everything
+/// else (nodes, graph inputs and outputs, tensor headers) is buffered, one
+/// field at a time. Initializers must have their fields in tag order, as
+/// ONNX exporters write them.
+///
+/// ```js
+/// const hasher = new ModelHasher();
+/// for await (const chunk of response.body) hasher.update(chunk);
+/// const modelHash = hasher.finalize();
+/// ```
+#[wasm_bindgen]
+pub struct ModelHasher(zkml_verifier_core::onnx::stream::ModelHasher);
+
+#[wasm_bindgen]
+impl ModelHasher {
+    /// Start hashing a model (SHA3-256 unless `algorithm` is given)
+    #[wasm_bindgen(constructor)]
+    pub fn new(algorithm: Option<HashAlgorithm>) -> Result<ModelHasher, JsValue> {
+        let hasher = zkml_verifier_core::onnx::stream::ModelHasher::create(algorithm.unwrap_or_default());
+        hasher.map(ModelHasher).map_err(to_js)
+    }
+
+    /// Absorb the next chunk of the model file
+    ///
+    /// Throws `VerificationError.InvalidModel` as soon as the bytes can't be
+    /// an ONNX model; the hasher is unusable afterwards.
+    #[wasm_bindgen]
+    pub fn update(&mut self, chunk: &[u8]) -> Result<(), JsValue> {
+        self.0.feed(chunk).map_err(to_js)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.0.algorithm()
+    }
+
+    /// Total bytes absorbed so far
+    #[wasm_bindgen(getter)]
+    pub fn bytes_processed(&self) -> u64 {
+        self.0.bytes_processed()
+    }
+
+    /// Finish and return the model's `onnx_merkle_root`
+    ///
+    /// Throws `VerificationError.InvalidModel` if the model is truncated.
+    #[wasm_bindgen]
+    pub fn finalize(self) -> Result<String, JsValue> {
+        self.0.finish().map_err(to_js)
+    }
+}
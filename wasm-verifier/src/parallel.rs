@@ -0,0 +1,78 @@
+// Parallel batch work
+// Spreads batch hashing over a rayon thread pool (`atomics` feature), with a single-threaded fallback
+
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "atomics")]
+use rayon::prelude::*;
+#[cfg(all(feature = "atomics", target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::hash::{hash_bytes, HashAlgorithm};
+
+/// Set once `init_threads` has started the Web Worker pool
+#[cfg(all(feature = "atomics", target_arch = "wasm32"))]
+static POOL_READY: AtomicBool = AtomicBool::new(false);
+
+/// Start `num_threads` Web Workers for parallel batch verification
+///
+/// Needs a cross-origin isolated page (`SharedArrayBuffer`); without it,
+/// don't call this and batches keep running on the calling thread.
+///
+/// ```js
+/// if (crossOriginIsolated) await init_threads(navigator.hardwareConcurrency);
+/// ```
+#[cfg(all(feature = "atomics", target_arch = "wasm32"))]
+#[wasm_bindgen]
+pub async fn init_threads(num_threads: usize) -> Result<(), JsValue> {
+    wasm_bindgen_futures::JsFuture::from(wasm_bindgen_rayon::init_thread_pool(num_threads)).await?;
+    POOL_READY.store(true, Ordering::Release);
+    Ok(())
+}
+
+/// Whether batch work is spread across threads
+///
+/// Native builds with `atomics` use rayon's default pool; wasm builds need `init_threads` first.
+#[wasm_bindgen]
+pub fn threads_enabled() -> bool {
+    #[cfg(all(feature = "atomics", target_arch = "wasm32"))]
+    return POOL_READY.load(Ordering::Acquire);
+    #[cfg(not(all(feature = "atomics", target_arch = "wasm32")))]
+    return cfg!(feature = "atomics");
+}
+
+/// `items.iter().map(f)`, in parallel when threads are enabled; results keep input order
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    #[cfg(feature = "atomics")]
+    if threads_enabled() {
+        return items.par_iter().map(f).collect();
+    }
+    items.iter().map(f).collect()
+}
+
+/// Hash many buffers at once, across threads when enabled (SHA3-256 unless `algorithm` is given)
+#[wasm_bindgen]
+pub fn hash_data_batch(buffers: Vec<js_sys::Uint8Array>, algorithm: Option<HashAlgorithm>) -> Result<Vec<String>, JsValue> {
+    let buffers: Vec<Vec<u8>> = buffers.iter().map(|b| b.to_vec()).collect();
+    let alg = algorithm.unwrap_or_default();
+    map(&buffers, |data| hash_bytes(data, alg))
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u32> = (0..1000).collect();
+        assert_eq!(map(&items, |i| i * 2), items.iter().map(|i| i * 2).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,113 @@
+// Verifier policy
+// Allowed models, freshness, trusted signers, hash algorithms and required checks as one versionable document
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::boundary::wrap;
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::registry::ModelEntry;
+use crate::WasmVerifier;
+
+/// Policy for a `WasmVerifier`, kept in config rather than code
+///
+/// ```json
+/// {
+///   "models": [{ "model_hash": "0x…", "label": "sentiment", "version": "1.2.0" }],
+///   "max_age_ms": 600000,
+///   "require_signature": true,
+///   "trusted_signers": ["0x…"],
+///   "hash_algorithms": ["sha3-256", "sha-256"],
+///   "required_checks": ["signature", "input_spec"]
+/// }
+/// ```
+///
+/// `VerifierConfig` fields sit at the top level. Omitted fields keep their
+/// defaults; unknown ones are rejected, so a typo can't loosen the policy.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct VerifierPolicy(zkml_verifier_core::VerifierPolicy);
+
+wrap!(VerifierPolicy => zkml_verifier_core::VerifierPolicy);
+
+#[wasm_bindgen]
+impl VerifierPolicy {
+    /// Default policy: `VerifierConfig` defaults, no models, keys or restrictions
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> VerifierPolicy {
+        VerifierPolicy::default()
+    }
+
+    /// Parse a policy document
+    ///
+    /// Throws `VerificationError.InvalidArgument` naming the offending field.
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<VerifierPolicy, JsValue> {
+        VerifierPolicy::try_from_json(json).map_err(|reason| VerificationError::InvalidArgument.to_js(&reason))
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn config(&self) -> VerifierConfig {
+        self.0.config().into()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn models(&self) -> Vec<ModelEntry> {
+        self.0.models().iter().cloned().map(ModelEntry::from).collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn trusted_signers(&self) -> Vec<String> {
+        self.0.trusted_signers().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn trusted_attestors(&self) -> Vec<String> {
+        self.0.trusted_attestors().to_vec()
+    }
+}
+
+impl VerifierPolicy {
+    pub fn try_from_json(json: &str) -> Result<VerifierPolicy, String> {
+        zkml_verifier_core::VerifierPolicy::try_from_json(json).map(VerifierPolicy)
+    }
+
+    /// Read a policy from an already-parsed document (e.g. TOML converted to JSON values)
+    pub fn try_from_value(value: Value) -> Result<VerifierPolicy, String> {
+        zkml_verifier_core::VerifierPolicy::try_from_value(value).map(VerifierPolicy)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Create a verifier accepting proofs for the models `policy` lists
+    ///
+    /// Throws `VerificationError.InvalidArgument` if it lists none: create the
+    /// verifier for its model instead, then call `set_policy`.
+    #[wasm_bindgen]
+    pub fn from_policy(policy: &VerifierPolicy) -> Result<WasmVerifier, JsValue> {
+        WasmVerifier::try_from_policy(policy).map_err(|e| e.to_js("Policy lists no models"))
+    }
+
+    /// Apply `policy`: its config replaces the current one, its trusted keys are
+    /// added, and its models (if any) replace the expected model hash
+    #[wasm_bindgen]
+    pub fn set_policy(&mut self, policy: &VerifierPolicy) {
+        self.verifier.set_policy(policy);
+    }
+}
+
+impl WasmVerifier {
+    /// `from_policy` for native callers
+    pub fn try_from_policy(policy: &VerifierPolicy) -> Result<WasmVerifier, VerificationError> {
+        zkml_verifier_core::Verifier::try_from_policy(policy).map(WasmVerifier::from)
+    }
+}
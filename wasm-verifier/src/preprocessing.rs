@@ -0,0 +1,112 @@
+// Preprocessing pipeline commitment
+// Binds a proof to the resize, normalization and tokenizer settings its inputs went through
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::WasmVerifier;
+
+/// Preprocessing applied to raw data before it became the model input
+///
+/// Prover and verifier build the same config and compare hashes:
+///
+/// ```js
+/// const pre = new PreprocessingConfig();
+/// pre.set_resize(224, 224, "bilinear");
+/// pre.set_normalization(1 / 255, [0.485, 0.456, 0.406], [0.229, 0.224, 0.225]);
+/// proof.preprocessing_hash = pre.hash();
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct PreprocessingConfig(zkml_verifier_core::PreprocessingConfig);
+
+wrap!(PreprocessingConfig => zkml_verifier_core::PreprocessingConfig);
+
+#[wasm_bindgen]
+impl PreprocessingConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> PreprocessingConfig {
+        PreprocessingConfig::default()
+    }
+
+    /// Images were resized to `width` x `height` with `interpolation` (e.g. `"bilinear"`)
+    #[wasm_bindgen]
+    pub fn set_resize(&mut self, width: u32, height: u32, interpolation: String) {
+        self.0.set_resize(width, height, interpolation);
+    }
+
+    /// Values were multiplied by `scale`, then normalized per channel as `(x - mean) / std`
+    #[wasm_bindgen]
+    pub fn set_normalization(&mut self, scale: f32, mean: Vec<f32>, std: Vec<f32>) {
+        self.0.set_normalization(scale, mean, std);
+    }
+
+    /// Text was tokenized by `name` with the vocabulary hashing to `vocab_hash`
+    #[wasm_bindgen]
+    pub fn set_tokenizer(&mut self, name: String, vocab_hash: String, max_length: u32, lowercase: bool) {
+        self.0.set_tokenizer(name, vocab_hash, max_length, lowercase);
+    }
+
+    /// Commitment to this config, for `ProofData.preprocessing_hash` (default SHA3-256)
+    ///
+    /// Hashes a fixed little-endian encoding of the set stages, so provers
+    /// in any language get the same value.
+    #[wasm_bindgen]
+    pub fn hash(&self, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        self.0.hash(algorithm.unwrap_or_default()).map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<PreprocessingConfig, JsValue> {
+        zkml_verifier_core::PreprocessingConfig::from_json(json)
+            .map(PreprocessingConfig)
+            .map_err(|_| VerificationError::InvalidArgument.to_js("Invalid preprocessing config JSON"))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Require proofs to commit to this preprocessing pipeline via `preprocessing_hash`
+    #[wasm_bindgen]
+    pub fn set_preprocessing(&mut self, preprocessing: &PreprocessingConfig) {
+        self.verifier.set_preprocessing(preprocessing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::Check;
+
+    #[test]
+    fn test_preprocessing_binding() {
+        let mut pre = PreprocessingConfig::new();
+        pre.set_resize(224, 224, "bilinear".into());
+        pre.set_normalization(1.0 / 255.0, vec![0.485, 0.456, 0.406], vec![0.229, 0.224, 0.225]);
+        let roundtrip = PreprocessingConfig::from_json(&pre.to_json()).unwrap();
+        assert_eq!(roundtrip.encode(), pre.encode());
+
+        let model_hash = "0x1234567890abcdef".to_string();
+        let mut verifier = WasmVerifier::new(model_hash.clone());
+        verifier.set_preprocessing(&pre);
+        let mut proof = fixtures::proof(&model_hash);
+        proof.set_preprocessing_hash(Some(pre.hash(None).unwrap()));
+        assert!(verifier.verify_report(&proof, b"in", b"out").passed());
+
+        // Same image pipeline resized to 256: a different commitment
+        let mut other = pre.clone();
+        other.set_resize(256, 256, "bilinear".into());
+        proof.set_preprocessing_hash(Some(other.hash(None).unwrap()));
+        assert!(!verifier.verify_report(&proof, b"in", b"out").check_passed(Check::Preprocessing));
+    }
+}
@@ -0,0 +1,262 @@
+// Promise-based verification
+// `verify_async` variants that hand the event loop back between checks and hash chunks
+
+use wasm_bindgen::prelude::*;
+use zkml_verifier_core::hash::StreamingHasher;
+use zkml_verifier_core::verifier::{Verifier, POLICY_CHECKS};
+
+use crate::abort::{AbortSignal, Aborted, Cancellation};
+use crate::events::HashProgress;
+use crate::hash::{HashAlgorithm, IoSource};
+use crate::report::{Check, Stopwatch};
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Bytes of input/output hashed between yield points (1 MiB)
+pub const ASYNC_CHUNK_BYTES: usize = 1 << 20;
+
+/// Longest stretch of work before the event loop gets a turn (half a 60 Hz frame)
+const SLICE_MS: f64 = 8.0;
+
+/// Resolve on the next macrotask, so rendering and input handlers can run
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+async fn yield_now() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let set_timeout = js_sys::Reflect::get(&js_sys::global(), &"setTimeout".into())
+            .ok()
+            .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+        let _ = match set_timeout {
+            Some(set_timeout) => set_timeout.call2(&JsValue::NULL, &resolve, &JsValue::from(0)),
+            None => resolve.call0(&JsValue::NULL),
+        };
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "wasi"))]
+async fn yield_now() {}
+
+/// Yield points that only yield once the current slice has used up `SLICE_MS`
+///
+/// Yielding unconditionally would cost a timer tick (clamped to 4ms once
+/// nested) per check, even for proofs that verify in well under a frame.
+/// Every checkpoint also checks for cancellation.
+struct Slicer<'a> {
+    slice: Stopwatch,
+    cancel: Cancellation<'a>,
+}
+
+impl<'a> Slicer<'a> {
+    fn new(cancel: Cancellation<'a>) -> Self {
+        Slicer { slice: Stopwatch::start(), cancel }
+    }
+
+    async fn checkpoint(&mut self) -> Result<(), Aborted> {
+        if self.slice.elapsed_ms() >= SLICE_MS {
+            yield_now().await;
+            self.slice = Stopwatch::start();
+        }
+        self.cancel.check()
+    }
+}
+
+/// Hash `data` with the algorithm `commitment` will be checked with, in chunks
+///
+/// Returns the raw bytes when no algorithm can be settled on, so that
+/// `check_commitment` records the error exactly as `verify_report` would.
+async fn hash_chunked<'a>(
+    slicer: &mut Slicer<'_>,
+    progress: &mut HashProgress<'_>,
+    data: &'a [u8],
+    commitment: &str,
+    alg: &Result<HashAlgorithm, String>,
+) -> Result<IoSource<'a>, Aborted> {
+    let Some(mut hasher) = StreamingHasher::for_commitment(commitment, alg) else {
+        return Ok(IoSource::Bytes(data));
+    };
+    for chunk in data.chunks(ASYNC_CHUNK_BYTES) {
+        hasher.update(chunk);
+        progress.advance(chunk.len());
+        slicer.checkpoint().await?;
+    }
+    Ok(hasher.finalize_source())
+}
+
+impl WasmVerifier {
+    /// `verify_report`, cooperatively scheduled; records the same checks in the same order
+    ///
+    /// Stops with `Aborted` at the first yield point after `cancel` fires.
+    pub(crate) async fn report_async(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[u8],
+        cancel: Cancellation<'_>,
+    ) -> Result<VerificationReport, Aborted> {
+        cancel.check()?;
+        let mut report = self.new_report();
+        let mut slicer = Slicer::new(cancel);
+        let total = (input.len() + output.len()) as u64;
+        let mut progress = HashProgress::new(self.hash_progress.as_ref(), Some(total));
+        let alg = self.hash_algorithm_for(proof_data);
+
+        self.check_model_binding(&mut report, proof_data, &alg);
+        slicer.checkpoint().await?;
+
+        let spec = proof_data.input_spec.as_ref();
+        Verifier::check_spec(&mut report, Check::InputSpec, spec, &IoSource::Bytes(input), "Input");
+        report.announce(Check::InputHash);
+        let source = hash_chunked(&mut slicer, &mut progress, input, &proof_data.input_hash, &alg).await?;
+        let reason = "Input does not match input_hash";
+        self.check_commitment(&mut report, Check::InputHash, source, &proof_data.input_hash, &alg, reason);
+
+        let spec = proof_data.output_spec.as_ref();
+        Verifier::check_spec(&mut report, Check::OutputSpec, spec, &IoSource::Bytes(output), "Output");
+        report.announce(Check::OutputHash);
+        let source = hash_chunked(&mut slicer, &mut progress, output, &proof_data.output_hash, &alg).await?;
+        let reason = "Output does not match output_hash";
+        self.check_commitment(&mut report, Check::OutputHash, source, &proof_data.output_hash, &alg, reason);
+
+        for check in POLICY_CHECKS {
+            slicer.checkpoint().await?;
+            // Ed25519 and P-256 signatures go to WebCrypto where the engine has it
+            #[cfg(feature = "signatures")]
+            if crate::webcrypto::DELEGATED.contains(&check)
+                && self.run_policy_check_subtle(check, &mut report, proof_data, &alg).await
+            {
+                continue;
+            }
+            self.run_policy_check(check, &mut report, proof_data, &alg);
+        }
+        Ok(report)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// `verify`, returning a Promise
+    ///
+    /// Hashing runs in `ASYNC_CHUNK_BYTES` chunks, and the event loop gets a
+    /// turn between chunks and between checks whenever a slice has run for
+    /// more than a few milliseconds, so large inputs don't freeze the page.
+    /// Ed25519 and P-256 signatures (prover and certificate chain) are
+    /// checked with WebCrypto when the engine supports them, and in WASM
+    /// otherwise; builds without `rust-signatures` can only check them here.
+    ///
+    /// ```js
+    /// const controller = new AbortController();
+    /// window.addEventListener("pagehide", () => controller.abort());
+    /// const ok = await verifier.verify_async(proof, input, output, controller.signal);
+    /// ```
+    ///
+    /// Once `signal` is aborted the Promise rejects with its `reason` at the
+    /// next chunk or check, and the copies of the input and output are
+    /// freed. Any object with an `aborted` flag works as a token; those
+    /// reject with `VerificationError.Aborted`.
+    #[wasm_bindgen]
+    pub async fn verify_async(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+        signal: Option<AbortSignal>,
+    ) -> Result<bool, JsValue> {
+        Ok(self.verify_report_async(proof_data, input_bytes, output_bytes, signal).await?.passed())
+    }
+
+    /// `verify_report`, returning a Promise (see `verify_async`)
+    #[wasm_bindgen]
+    pub async fn verify_report_async(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+        signal: Option<AbortSignal>,
+    ) -> Result<VerificationReport, JsValue> {
+        let cancel = Cancellation::new(signal.as_ref());
+        self.report_async(proof_data, &input_bytes, &output_bytes, cancel).await.map_err(|Aborted| cancel.error())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{get_timestamp, hash_data};
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Drive a future that never actually waits (yields are no-ops natively)
+    pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_report_matches_sync() {
+        let input = vec![3u8; 3 * ASYNC_CHUNK_BYTES + 17];
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let proof = ProofData::new(
+            "0xmodel".to_string(),
+            "0xproof".to_string(),
+            hash_data(&input, None).unwrap(),
+            crate::hash::hash_bytes_tagged(b"out", HashAlgorithm::Keccak256).unwrap(),
+            get_timestamp(),
+            true,
+        );
+
+        let report = block_on(verifier.report_async(&proof, &input, b"out", Cancellation::Never)).unwrap();
+        assert!(report.passed());
+        assert_eq!(report.checks().len(), verifier.verify_report(&proof, &input, b"out").checks().len());
+
+        let tampered = block_on(verifier.report_async(&proof, &input, b"tampered", Cancellation::Never)).unwrap();
+        let sync = verifier.verify_report(&proof, &input, b"tampered");
+        assert_eq!(tampered.error(), Some(crate::error::VerificationError::OutputHashMismatch));
+        assert_eq!(tampered.failure_reasons(), sync.failure_reasons());
+    }
+
+    #[test]
+    fn test_async_abort() {
+        let input = vec![3u8; 4 * ASYNC_CHUNK_BYTES];
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let io = hash_data(&input, None).unwrap();
+        let proof = ProofData::new("0xmodel".to_string(), "0xproof".to_string(), io.clone(), io, get_timestamp(), true);
+
+        // Already aborted: nothing runs
+        let left = std::cell::Cell::new(0);
+        assert!(block_on(verifier.report_async(&proof, &input, &input, Cancellation::Countdown(&left))).is_err());
+
+        // Aborted partway through hashing the input
+        left.set(3);
+        assert!(block_on(verifier.report_async(&proof, &input, &input, Cancellation::Countdown(&left))).is_err());
+        assert_eq!(left.get(), 0);
+
+        left.set(1_000);
+        assert!(block_on(verifier.report_async(&proof, &input, &input, Cancellation::Countdown(&left))).unwrap().passed());
+    }
+
+    #[test]
+    #[cfg(feature = "rust-signatures")]
+    fn test_async_signature_falls_back_to_rust() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut proof = crate::fixtures::proof("0xmodel");
+        proof.set_signer_pubkey(Some(crate::hex::encode(key.verifying_key().as_bytes())));
+        proof.set_signature(Some(crate::hex::encode(&key.sign(&proof.signing_bytes()).to_bytes())));
+        let mut verifier = WasmVerifier::new("0xmodel".to_string());
+        verifier.add_trusted_signer(crate::hex::encode(key.verifying_key().as_bytes()));
+
+        let report = block_on(verifier.report_async(&proof, b"in", b"out", Cancellation::Never)).unwrap();
+        assert!(report.passed());
+        assert!(report.ran(Check::Signature));
+
+        proof.set_signature(Some("00".repeat(64)));
+        let report = block_on(verifier.report_async(&proof, b"in", b"out", Cancellation::Never)).unwrap();
+        assert_eq!(report.error(), Some(crate::error::VerificationError::SignatureInvalid));
+    }
+}
@@ -0,0 +1,55 @@
+// Protobuf proof format
+// Decodes `zkml.verifier.v1.Proof` messages (proto/proof.proto) into ProofData
+
+use wasm_bindgen::prelude::*;
+
+use crate::{ProofData, WasmVerifier};
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Deserialize from a `zkml.verifier.v1.Proof` protobuf message
+    #[wasm_bindgen]
+    pub fn from_protobuf(bytes: &[u8]) -> Result<ProofData, JsValue> {
+        let proof = zkml_verifier_core::ProofData::decode_protobuf(bytes);
+        proof.map(ProofData).map_err(|e| e.to_js("Invalid Proof protobuf"))
+    }
+
+    /// Serialize to a `zkml.verifier.v1.Proof` protobuf message
+    #[wasm_bindgen]
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        self.encode_protobuf()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify proof from a `zkml.verifier.v1.Proof` protobuf message
+    #[wasm_bindgen]
+    pub fn verify_protobuf(
+        &self,
+        proof_bytes: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<bool, JsValue> {
+        let proof_data = ProofData::from_protobuf(proof_bytes)?;
+
+        self.verify(&proof_data, input_bytes, output_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_verify_protobuf() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = WasmVerifier::new(model_hash.clone());
+        let mut proof = fixtures::proof(&model_hash);
+        proof.set_kid(Some("prover-1".to_string()));
+
+        let bytes = proof.to_protobuf();
+        assert!(verifier.verify_protobuf(&bytes, b"in", b"out").unwrap());
+    }
+}
@@ -0,0 +1,92 @@
+// Quantization parameter binding
+// Commits to the quantization scheme JOLT-Atlas ran the model with
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::hash::HashAlgorithm;
+use crate::WasmVerifier;
+
+/// Quantization scheme of a model: per-tensor or per-channel scales and zero-points
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct QuantizationConfig(zkml_verifier_core::QuantizationConfig);
+
+wrap!(QuantizationConfig => zkml_verifier_core::QuantizationConfig);
+
+#[wasm_bindgen]
+impl QuantizationConfig {
+    #[wasm_bindgen(constructor)]
+    pub fn new(scheme: String, bit_width: u32, scales: Vec<f32>, zero_points: Vec<i32>) -> QuantizationConfig {
+        QuantizationConfig(zkml_verifier_core::QuantizationConfig::new(scheme, bit_width, scales, zero_points))
+    }
+
+    /// Scheme name, e.g. `"per-tensor-affine"` or `"per-channel-symmetric"`
+    #[wasm_bindgen(getter)]
+    pub fn scheme(&self) -> String {
+        self.0.scheme().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bit_width(&self) -> u32 {
+        self.0.bit_width()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn scales(&self) -> Vec<f32> {
+        self.0.scales().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn zero_points(&self) -> Vec<i32> {
+        self.0.zero_points().to_vec()
+    }
+
+    /// Commitment to this config, for `ProofData.quantization_hash` (default SHA3-256)
+    ///
+    /// Hashes a fixed little-endian encoding (domain tag, scheme, bit width,
+    /// scales as IEEE-754 f32, zero-points as i32), so provers in any
+    /// language get the same value.
+    #[wasm_bindgen]
+    pub fn hash(&self, algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+        self.0.hash(algorithm.unwrap_or_default()).map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Require proofs to commit to this quantization config via `quantization_hash`
+    #[wasm_bindgen]
+    pub fn set_quantization(&mut self, quantization: &QuantizationConfig) {
+        self.verifier.set_quantization(quantization);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::{Check, VerificationError};
+
+    #[test]
+    fn test_quantization_binding() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let config = QuantizationConfig::new("per-tensor-affine".into(), 8, vec![0.02], vec![128]);
+        let mut verifier = WasmVerifier::new(model_hash.clone());
+        verifier.set_quantization(&config);
+
+        let mut proof = fixtures::proof(&model_hash);
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        assert!(!report.check_passed(Check::Quantization));
+
+        proof.set_quantization_hash(Some(config.hash(None).unwrap()));
+        assert!(verifier.verify_report(&proof, b"in", b"out").passed());
+
+        // Same scale, different bit width: the prover can't swap schemes
+        let int4 = QuantizationConfig::new("per-tensor-affine".into(), 4, vec![0.02], vec![128]);
+        proof.set_quantization_hash(Some(int4.hash(None).unwrap()));
+        assert_eq!(verifier.verify_report(&proof, b"in", b"out").error(), Some(VerificationError::QuantizationMismatch));
+    }
+}
@@ -0,0 +1,230 @@
+// ReadableStream hashing
+// Commitments over `fetch` bodies and other byte streams, read chunk by chunk without buffering
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use zkml_verifier_core::hash::StreamingHasher;
+
+use crate::abort::{AbortSignal, Cancellation};
+use crate::error::VerificationError;
+use crate::events::HashProgress;
+use crate::hash::{self, HashAlgorithm, IoSource};
+use crate::report::Check;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+#[wasm_bindgen]
+extern "C" {
+    /// WHATWG `ReadableStream` of `Uint8Array` chunks, e.g. `(await fetch(url)).body`
+    #[wasm_bindgen(typescript_type = "ReadableStream<Uint8Array>")]
+    pub type ReadableStream;
+
+    #[wasm_bindgen(method, catch, js_name = getReader)]
+    fn get_reader(this: &ReadableStream) -> Result<StreamReader, JsValue>;
+
+    type StreamReader;
+
+    #[wasm_bindgen(method)]
+    fn read(this: &StreamReader) -> js_sys::Promise;
+
+    #[wasm_bindgen(method)]
+    fn cancel(this: &StreamReader, reason: &JsValue) -> js_sys::Promise;
+
+    #[wasm_bindgen(method, js_name = releaseLock)]
+    fn release_lock(this: &StreamReader);
+}
+
+/// Pass every chunk of `stream` to `sink`, releasing the stream afterwards
+///
+/// Rejects if the stream is locked, errors, or yields anything but
+/// `Uint8Array`s, or once `cancel` fires (checked as each chunk arrives);
+/// the stream is cancelled with the rejection when `sink` fails or on abort.
+async fn drain(
+    stream: &ReadableStream,
+    cancel: Cancellation<'_>,
+    mut sink: impl FnMut(&[u8]) -> Result<(), JsValue>,
+) -> Result<(), JsValue> {
+    cancel.check_js()?;
+    let reader = stream.get_reader()?;
+    let result = read_all(&reader, cancel, &mut sink).await;
+    if let Err(error) = &result {
+        let _ = reader.cancel(error);
+    }
+    reader.release_lock();
+    result
+}
+
+async fn read_all(
+    reader: &StreamReader,
+    cancel: Cancellation<'_>,
+    sink: &mut impl FnMut(&[u8]) -> Result<(), JsValue>,
+) -> Result<(), JsValue> {
+    loop {
+        let step = JsFuture::from(reader.read()).await?;
+        cancel.check_js()?;
+        if js_sys::Reflect::get(&step, &"done".into())?.is_truthy() {
+            return Ok(());
+        }
+        let chunk = js_sys::Reflect::get(&step, &"value".into())?
+            .dyn_into::<js_sys::Uint8Array>()
+            .map_err(|_| VerificationError::InvalidArgument.to_js("Stream chunk is not a Uint8Array"))?;
+        sink(&chunk.to_vec())?;
+    }
+}
+
+async fn absorb(
+    stream: &ReadableStream,
+    hasher: &mut StreamingHasher,
+    progress: &mut HashProgress<'_>,
+    cancel: Cancellation<'_>,
+) -> Result<(), JsValue> {
+    drain(stream, cancel, |chunk| {
+        hasher.update(chunk);
+        progress.advance(chunk.len());
+        Ok(())
+    })
+    .await
+}
+
+/// Read `stream` into memory, failing with `PayloadTooLarge` once it exceeds `limit` bytes
+pub(crate) async fn read_limited(stream: &ReadableStream, limit: u64, what: &str) -> Result<Vec<u8>, JsValue> {
+    let mut data = Vec::new();
+    drain(stream, Cancellation::Never, |chunk| {
+        if (data.len() + chunk.len()) as u64 > limit {
+            return Err(VerificationError::PayloadTooLarge.to_js(&format!("{} exceeds {} bytes", what, limit)));
+        }
+        data.extend_from_slice(chunk);
+        Ok(())
+    })
+    .await?;
+    Ok(data)
+}
+
+/// Hash a `ReadableStream` of bytes (SHA3-256 unless `algorithm` is given)
+///
+/// ```js
+/// const response = await fetch(url);
+/// const total = Number(response.headers.get("content-length"));
+/// const digest = await hash_stream(response.body, undefined, (done) => (bar.value = done / total));
+/// ```
+///
+/// `on_progress` is called with the bytes hashed so far after every chunk.
+/// Aborting `signal` cancels the stream and rejects with its `reason`.
+#[wasm_bindgen]
+pub async fn hash_stream(
+    stream: ReadableStream,
+    algorithm: Option<HashAlgorithm>,
+    on_progress: Option<js_sys::Function>,
+    signal: Option<AbortSignal>,
+) -> Result<String, JsValue> {
+    let mut hasher = hash::StreamingHasher::new(algorithm)?;
+    let mut progress = HashProgress::new(on_progress.as_ref(), None);
+    absorb(&stream, &mut hasher, &mut progress, Cancellation::new(signal.as_ref())).await?;
+    Ok(hasher.finalize())
+}
+
+/// Hash `stream` with the algorithm `commitment` will be checked with
+///
+/// When no algorithm can be settled on the stream is left unread, and
+/// `check_commitment` records the error as it would for a buffer.
+async fn stream_source(
+    stream: &ReadableStream,
+    commitment: &str,
+    alg: &Result<HashAlgorithm, String>,
+    progress: &mut HashProgress<'_>,
+    cancel: Cancellation<'_>,
+) -> Result<IoSource<'static>, JsValue> {
+    match StreamingHasher::for_commitment(commitment, alg) {
+        Some(mut hasher) => {
+            absorb(stream, &mut hasher, progress, cancel).await?;
+            Ok(hasher.finalize_source())
+        }
+        None => Ok(IoSource::Bytes(&[])),
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify proof against input and output `ReadableStream`s, resolving to the per-check report
+    ///
+    /// ```js
+    /// const [input, output] = await Promise.all([fetch(inputUrl), fetch(outputUrl)]);
+    /// const report = await verifier.verify_streams(proof, input.body, output.body, controller.signal);
+    /// ```
+    ///
+    /// Each stream is hashed as it arrives and never held in memory; the
+    /// input is read to the end before the output. Rejects if a stream
+    /// fails, rather than reporting a mismatch. Aborting `signal` cancels
+    /// the stream being read and rejects as `verify_async` does.
+    #[wasm_bindgen]
+    pub async fn verify_streams(
+        &self,
+        proof_data: &ProofData,
+        input: ReadableStream,
+        output: ReadableStream,
+        signal: Option<AbortSignal>,
+    ) -> Result<VerificationReport, JsValue> {
+        let cancel = Cancellation::new(signal.as_ref());
+        let alg = self.hash_algorithm_for(proof_data);
+        let mut report = self.new_report();
+        let mut progress = HashProgress::new(self.hash_progress.as_ref(), None);
+        report.announce(Check::InputHash);
+        let input = stream_source(&input, &proof_data.input_hash, &alg, &mut progress, cancel).await?;
+        report.announce(Check::OutputHash);
+        let output = stream_source(&output, &proof_data.output_hash, &alg, &mut progress, cancel).await?;
+        self.run_checks(&mut report, proof_data, input, output);
+        Ok(report)
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, kind, stream};
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use zkml_verifier_core::fixtures::{INPUT, OUTPUT};
+
+    #[wasm_bindgen_test]
+    async fn test_hash_stream() {
+        let seen = js_sys::Array::new();
+        let on_progress = js_sys::Function::new_with_args("done", "this.push(done)").bind(&seen);
+        let digest = hash_stream(stream(&[b"hello ", b"", b"world"]), None, Some(on_progress), None).await.unwrap();
+        assert_eq!(digest, crate::hash_data(b"hello world", None).unwrap());
+        assert_eq!(seen.iter().map(|done| done.as_f64().unwrap()).collect::<Vec<_>>(), [6.0, 6.0, 11.0]);
+
+        let keccak = hash_stream(stream(&[b"hello world"]), Some(HashAlgorithm::Keccak256), None, None).await.unwrap();
+        assert_eq!(keccak, crate::hash_data(b"hello world", Some(HashAlgorithm::Keccak256)).unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_bad_streams_reject() {
+        // A chunk that isn't bytes cancels the stream with the rejection
+        let cancelled = js_sys::Array::new();
+        let source = "return new ReadableStream({ \
+            pull(c) { c.enqueue('text'); }, cancel(reason) { cancelled.push(reason); } });";
+        let text = js_sys::Function::new_with_args("cancelled", source).call1(&JsValue::NULL, &cancelled).unwrap();
+        let error = hash_stream(text.unchecked_into(), None, None, None).await.unwrap_err();
+        assert_eq!(kind(&error).as_deref(), Some(VerificationError::InvalidArgument.name()));
+        assert_eq!(cancelled.length(), 1);
+
+        let locked = stream(&[b"in"]);
+        let _reader = locked.get_reader().unwrap();
+        assert!(hash_stream(locked, None, None, None).await.is_err());
+
+        let error = read_limited(&stream(&[b"1234", b"5678"]), 6, "Proof").await.unwrap_err();
+        assert_eq!(kind(&error).as_deref(), Some(VerificationError::PayloadTooLarge.name()));
+        assert_eq!(read_limited(&stream(&[b"1234", b"56"]), 6, "Proof").await.unwrap(), b"123456");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_verify_streams() {
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let proof = fixtures::proof("0xmodel");
+        let (input, output) = INPUT.split_at(1);
+        let report = verifier.verify_streams(&proof, stream(&[input, output]), stream(&[OUTPUT]), None).await.unwrap();
+        assert!(report.passed());
+
+        let report = verifier.verify_streams(&proof, stream(&[INPUT]), stream(&[b"tampered"]), None).await.unwrap();
+        assert!(!report.passed());
+        assert_eq!(report.error(), Some(VerificationError::OutputHashMismatch));
+    }
+}
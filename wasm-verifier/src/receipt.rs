@@ -0,0 +1,94 @@
+// Verification receipts
+// Compact EdDSA JWS statements that this verifier passed a proof, issued and checked by the core
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+#[cfg(feature = "rust-signatures")]
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Claims of a receipt
+///
+/// ```json
+/// {
+///   "verifier": "did:key:z6Mk…",
+///   "proof_id": "sha3:0x…",
+///   "model_hash": "0x…",
+///   "result": "passed",
+///   "verified_at": 1700000000000
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct Receipt(zkml_verifier_core::receipt::Receipt);
+
+wrap!(Receipt => zkml_verifier_core::receipt::Receipt);
+
+#[wasm_bindgen]
+impl Receipt {
+    #[wasm_bindgen(getter)]
+    pub fn verifier(&self) -> String {
+        self.0.verifier().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proof_id(&self) -> String {
+        self.0.proof_id().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash().to_string()
+    }
+
+    /// Always `"passed"`: failed verifications get no receipt
+    #[wasm_bindgen(getter)]
+    pub fn result(&self) -> String {
+        self.0.result().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn verified_at(&self) -> u64 {
+        self.0.verified_at()
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[cfg(feature = "rust-signatures")]
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Issue a signed receipt for a passed verification
+    ///
+    /// ```js
+    /// const report = verifier.verify_report(proof, input, output);
+    /// const receipt = verifier.issue_receipt(proof, report); // send this instead of proof + I/O
+    /// ```
+    ///
+    /// A compact EdDSA JWS (`typ: zkml-receipt+jwt`) signed with the
+    /// `set_issuer_key` key, naming the verifier's `did:key`, the proof ID,
+    /// its model hash, the result and when it was verified. Check it with
+    /// `verify_receipt`. Throws `VerificationError.InvalidArgument` if the
+    /// report did not pass or no issuer key is set.
+    #[wasm_bindgen]
+    pub fn issue_receipt(&self, proof_data: &ProofData, report: &VerificationReport) -> Result<String, JsValue> {
+        self.verifier.issue_receipt(proof_data, report).map_err(|(error, reason)| error.to_js(reason))
+    }
+}
+
+/// Check a receipt from `issue_receipt` against the issuer's `did:key` (or hex Ed25519 key)
+///
+/// Returns its claims; how recent `verified_at` must be is up to the caller.
+/// Throws `VerificationError.SignatureInvalid` if another key signed it, or
+/// `VerificationError.MalformedProof` if it isn't a receipt.
+#[wasm_bindgen]
+pub fn verify_receipt(token: &str, issuer: &str) -> Result<Receipt, JsValue> {
+    zkml_verifier_core::receipt::verify_receipt(token, issuer)
+        .map(Receipt)
+        .map_err(|(error, reason)| error.to_js(reason))
+}
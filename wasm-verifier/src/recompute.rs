@@ -0,0 +1,115 @@
+// Output cross-check by re-execution (native `onnxruntime` feature)
+// Re-runs the model in ONNX Runtime and compares its output with the claimed one
+
+use ort::session::Session;
+use ort::value::Tensor;
+use zkml_verifier_core::TensorSpec;
+
+use crate::error::VerificationError;
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::tensor::f32_elements;
+use crate::{ProofData, WasmVerifier};
+
+/// How far recomputed outputs may drift from the claimed ones
+///
+/// An element passes when `|recomputed - claimed| <= absolute + relative * |claimed|`,
+/// which absorbs kernel and quantization differences between runtimes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f32,
+    pub relative: f32,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance { absolute: 1e-5, relative: 1e-3 }
+    }
+}
+
+impl Tolerance {
+    /// Whether `recomputed` is within tolerance of `claimed`, element by element
+    pub fn accepts(&self, recomputed: &[f32], claimed: &[f32]) -> bool {
+        recomputed.len() == claimed.len()
+            && recomputed
+                .iter()
+                .zip(claimed)
+                .all(|(r, c)| (r - c).abs() <= self.absolute + self.relative * c.abs())
+    }
+}
+
+type RecomputeFailure = (VerificationError, String);
+
+/// Run the model's first input through ONNX Runtime and return its first output
+fn run_model(model_bytes: &[u8], spec: &TensorSpec, input: Vec<f32>) -> Result<Vec<f32>, RecomputeFailure> {
+    let runtime = |e: ort::Error| (VerificationError::InvalidModel, format!("ONNX Runtime failed: {}", e));
+    let mut session = Session::builder().and_then(|b| b.commit_from_memory(model_bytes)).map_err(runtime)?;
+    let shape: Vec<i64> = spec.shape().iter().map(|&d| i64::from(d)).collect();
+    let tensor = Tensor::from_array((shape, input)).map_err(runtime)?;
+    let outputs = session.run(ort::inputs![tensor]).map_err(runtime)?;
+    let (_, output) = outputs[0].try_extract_tensor::<f32>().map_err(runtime)?;
+    Ok(output.to_vec())
+}
+
+/// The float32 elements of `data`, shaped by the proof's spec
+fn elements<'a>(spec: Option<&'a TensorSpec>, data: &[u8], what: &str) -> Result<(&'a TensorSpec, Vec<f32>), RecomputeFailure> {
+    spec.and_then(|spec| Some((spec, f32_elements(spec, data)?))).ok_or_else(|| {
+        let reason = format!("{} is not a float32 tensor matching the proof's {}_spec", what, what.to_lowercase());
+        (VerificationError::ShapeMismatch, reason)
+    })
+}
+
+fn recompute(
+    proof_data: &ProofData,
+    model_bytes: &[u8],
+    input: &[u8],
+    output: &[u8],
+    tolerance: Tolerance,
+) -> Result<(), RecomputeFailure> {
+    let (input_spec, input) = elements(proof_data.input_spec.as_ref(), input, "Input")?;
+    let (_, claimed) = elements(proof_data.output_spec.as_ref(), output, "Output")?;
+
+    let recomputed = run_model(model_bytes, input_spec, input)?;
+    if tolerance.accepts(&recomputed, &claimed) {
+        Ok(())
+    } else {
+        Err((VerificationError::RecomputationMismatch, "Recomputed output differs from the claimed output".to_string()))
+    }
+}
+
+impl WasmVerifier {
+    /// `verify_report`, plus re-executing the model on the input
+    ///
+    /// On success the report's confidence is `Confidence::Recomputed`.
+    /// Only the model's first input and output are compared, as float32
+    /// tensors shaped by the proof's `input_spec`/`output_spec`. Needs the
+    /// ONNX Runtime library at run time (`ORT_DYLIB_PATH`).
+    pub fn verify_recomputed(
+        &self,
+        proof_data: &ProofData,
+        model_bytes: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+        tolerance: Tolerance,
+    ) -> VerificationReport {
+        let mut report = self.verify_report(proof_data, input_bytes, output_bytes);
+        let started = Stopwatch::start();
+        match recompute(proof_data, model_bytes, input_bytes, output_bytes, tolerance) {
+            Ok(()) => report.record(Check::Recomputation, true, "", started),
+            Err((error, reason)) => report.record_error(Check::Recomputation, error, &reason, started),
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tolerance() {
+        let tolerance = Tolerance::default();
+        assert!(tolerance.accepts(&[1.0, 100.05], &[1.000_001, 100.0]));
+        assert!(!tolerance.accepts(&[1.0, 100.2], &[1.0, 100.0]));
+        assert!(!tolerance.accepts(&[1.0], &[1.0, 2.0]));
+    }
+}
@@ -0,0 +1,104 @@
+// Multi-model allowlist
+// One verifier for several approved models, each with a label and version
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::WasmVerifier;
+
+/// An approved model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ModelEntry(zkml_verifier_core::ModelEntry);
+
+/// Set of approved model hashes
+///
+/// ```js
+/// const registry = new ModelRegistry();
+/// registry.add(sentimentHash, "sentiment", "1.2.0");
+/// registry.add(toxicityHash, "toxicity", "3.0.1");
+/// const verifier = WasmVerifier.with_registry(registry);
+/// verifier.verify_report(proof, input, output).matched_model.label;
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct ModelRegistry(zkml_verifier_core::ModelRegistry);
+
+wrap!(ModelEntry => zkml_verifier_core::ModelEntry, ModelRegistry => zkml_verifier_core::ModelRegistry);
+
+#[wasm_bindgen]
+impl ModelEntry {
+    #[wasm_bindgen(getter)]
+    pub fn model_hash(&self) -> String {
+        self.0.model_hash().to_string()
+    }
+
+    /// Human-readable name, e.g. `"sentiment-classifier"`
+    #[wasm_bindgen(getter)]
+    pub fn label(&self) -> String {
+        self.0.label().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> String {
+        self.0.version().to_string()
+    }
+}
+
+#[wasm_bindgen]
+impl ModelRegistry {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ModelRegistry {
+        ModelRegistry::default()
+    }
+
+    /// Approve a model hash under `label` and `version`
+    #[wasm_bindgen]
+    pub fn add(&mut self, model_hash: String, label: String, version: String) {
+        self.0.add(model_hash, label, version);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn entries(&self) -> Vec<ModelEntry> {
+        self.0.entries().iter().cloned().map(ModelEntry).collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[wasm_bindgen]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Load entries from `[{"model_hash", "label", "version"}, ...]`
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<ModelRegistry, JsValue> {
+        zkml_verifier_core::ModelRegistry::from_json(json)
+            .map(ModelRegistry)
+            .map_err(|_| VerificationError::InvalidArgument.to_js("Invalid model registry JSON"))
+    }
+
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Create a verifier accepting proofs for any model in `registry`
+    ///
+    /// The report's `matched_model` says which entry a proof matched.
+    #[wasm_bindgen]
+    pub fn with_registry(registry: &ModelRegistry, config: Option<VerifierConfig>) -> WasmVerifier {
+        zkml_verifier_core::Verifier::with_registry(registry.0.clone(), &config.unwrap_or_default()).into()
+    }
+}
@@ -0,0 +1,119 @@
+// Rekor transparency log
+// Inclusion proofs of Sigstore Rekor entries and `rekord` entries for receipts, checked by the core
+
+use wasm_bindgen::prelude::*;
+
+use crate::boundary::wrap;
+use crate::{ProofData, WasmVerifier};
+
+/// Where a verified entry sits in the log
+#[derive(Debug, Clone, PartialEq)]
+#[wasm_bindgen]
+pub struct RekorInclusion(zkml_verifier_core::rekor::RekorInclusion);
+
+wrap!(RekorInclusion => zkml_verifier_core::rekor::RekorInclusion);
+
+#[wasm_bindgen]
+impl RekorInclusion {
+    /// Global index of the entry
+    #[wasm_bindgen(getter)]
+    pub fn log_index(&self) -> u64 {
+        self.0.log_index()
+    }
+
+    /// When the log took the entry in (Unix seconds)
+    #[wasm_bindgen(getter)]
+    pub fn integrated_time(&self) -> u64 {
+        self.0.integrated_time()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn log_id(&self) -> String {
+        self.0.log_id().to_string()
+    }
+
+    /// Size of the tree the checkpoint signs
+    #[wasm_bindgen(getter)]
+    pub fn tree_size(&self) -> u64 {
+        self.0.tree_size()
+    }
+
+    /// Root of that tree (hex)
+    #[wasm_bindgen(getter)]
+    pub fn root_hash(&self) -> String {
+        self.0.root_hash().to_string()
+    }
+
+    /// Ed25519 key (hex) the entry's signature over its data verifies under
+    #[wasm_bindgen(getter)]
+    pub fn signer_pubkey(&self) -> String {
+        self.0.signer_pubkey().to_string()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Check that a prover published `proof_data` in Rekor
+    ///
+    /// ```js
+    /// const entry = await (await fetch(`${rekor}/api/v1/log/entries/${uuid}`)).text();
+    /// const inclusion = verifier.verify_rekor_proof(proof, entry, rekorPublicKeyPem);
+    /// ```
+    ///
+    /// `verify_rekor_entry` for `proof.signing_bytes()`, with the entry
+    /// signed by a trusted signer or the key the proof's `kid` or trusted
+    /// `prover_did` resolves to; the proof's own `signer_pubkey` is not
+    /// enough. Throws as `verify_rekor_entry` does, or
+    /// `VerificationError.UntrustedSigner` if someone else logged the proof.
+    #[wasm_bindgen]
+    pub fn verify_rekor_proof(
+        &self,
+        proof_data: &ProofData,
+        entry: &str,
+        log_key: &str,
+    ) -> Result<RekorInclusion, JsValue> {
+        self.verifier
+            .verify_rekor_proof(proof_data, entry, log_key)
+            .map(RekorInclusion)
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+}
+
+/// Check that a Rekor entry logs `data` signed by `signer_pubkey` and is included in the log
+///
+/// ```js
+/// const entry = await (await fetch(`${rekor}/api/v1/log/entries/${uuid}`)).text();
+/// verify_rekor_entry(entry, receipt.slice(0, receipt.lastIndexOf(".")), issuerPubkey, rekorPublicKeyPem);
+/// ```
+///
+/// `data` is what was logged: a `rekor_receipt_entry` logs the receipt's
+/// JWS signing input (the token up to its last `.`), a prover's entry
+/// `proof.signing_bytes()` (see `WasmVerifier.verify_rekor_proof`). The
+/// entry's signature must verify over `data` under `signer_pubkey` (hex
+/// Ed25519), and its inclusion proof lead to the root of a checkpoint
+/// signed by `log_key` (PEM or SEC1 hex, P-256). The entry's signed entry
+/// timestamp is not checked. Throws `VerificationError.InclusionInvalid`
+/// if the entry logs other data or isn't in the tree,
+/// `VerificationError.SignatureInvalid` if the entry isn't signed by
+/// `signer_pubkey` or the checkpoint by `log_key`, or
+/// `VerificationError.MalformedProof` if it isn't an entry.
+#[wasm_bindgen]
+pub fn verify_rekor_entry(
+    entry: &str,
+    data: &[u8],
+    signer_pubkey: &str,
+    log_key: &str,
+) -> Result<RekorInclusion, JsValue> {
+    zkml_verifier_core::rekor::verify_rekor_entry(entry, data, signer_pubkey, log_key)
+        .map(RekorInclusion)
+        .map_err(|(error, reason)| error.to_js(reason))
+}
+
+/// Rekor `rekord` entry for a receipt from `issue_receipt`, to `POST /api/v1/log/entries`
+///
+/// Logs the receipt's signing input with its signature and the issuer's
+/// key, which Rekor checks before accepting it.
+#[wasm_bindgen]
+pub fn rekor_receipt_entry(token: &str) -> Result<String, JsValue> {
+    zkml_verifier_core::rekor::rekor_receipt_entry(token).map_err(|(error, reason)| error.to_js(reason))
+}
@@ -0,0 +1,272 @@
+// Remote proof retrieval
+// `verify_from_url`: fetch a JSON or binary proof, check its size and type, and verify it in one call
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::compress::decompress;
+use crate::error::{Failure, VerificationError};
+use crate::readable::{read_limited, ReadableStream};
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+#[wasm_bindgen]
+extern "C" {
+    /// The parts of a fetch `Response` used here, so test doubles can be plain objects
+    type Response;
+
+    #[wasm_bindgen(method, getter)]
+    fn ok(this: &Response) -> bool;
+
+    #[wasm_bindgen(method, getter)]
+    fn status(this: &Response) -> u16;
+
+    #[wasm_bindgen(method, getter)]
+    fn headers(this: &Response) -> Headers;
+
+    #[wasm_bindgen(method, getter)]
+    fn body(this: &Response) -> Option<ReadableStream>;
+
+    #[wasm_bindgen(method, catch, js_name = arrayBuffer)]
+    fn array_buffer(this: &Response) -> Result<js_sys::Promise, JsValue>;
+
+    type Headers;
+
+    #[wasm_bindgen(method)]
+    fn get(this: &Headers, name: &str) -> Option<String>;
+}
+
+/// Proof encoding, as announced by the response's `Content-Type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProofFormat {
+    Json,
+    /// `ZKPF` binary format (`ProofData::encode_binary`)
+    Binary,
+}
+
+impl ProofFormat {
+    /// Format for a `Content-Type` value; parameters such as `charset` are ignored
+    fn from_content_type(content_type: &str) -> Option<ProofFormat> {
+        let essence = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "application/json" | "text/json" => Some(ProofFormat::Json),
+            "application/octet-stream" | "application/vnd.zkml.proof" => Some(ProofFormat::Binary),
+            other if other.starts_with("application/") && other.ends_with("+json") => Some(ProofFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a downloaded proof body, which may additionally be gzip- or zstd-compressed
+fn parse_proof(body: &[u8], content_type: &str, max_decompressed_bytes: u64) -> Result<ProofData, Failure> {
+    let format = ProofFormat::from_content_type(content_type)
+        .ok_or((VerificationError::MalformedProof, "Proof has an unsupported content-type"))?;
+    let body = decompress(body, max_decompressed_bytes)?;
+    match format {
+        ProofFormat::Json => {
+            serde_json::from_slice(&body).map_err(|_| (VerificationError::MalformedProof, "Invalid proof JSON"))
+        }
+        ProofFormat::Binary => {
+            zkml_verifier_core::ProofData::from_binary(&body).map(ProofData).map_err(|e| (e, "Invalid binary proof"))
+        }
+    }
+}
+
+fn unavailable(message: &str) -> JsValue {
+    VerificationError::ProofUnavailable.to_js(message)
+}
+
+impl WasmVerifier {
+    /// `proof_fetcher`, or the global `fetch`
+    pub(crate) fn fetcher(&self) -> Result<js_sys::Function, JsValue> {
+        match &self.proof_fetcher {
+            Some(fetcher) => Ok(fetcher.clone()),
+            None => js_sys::Reflect::get(&js_sys::global(), &"fetch".into())?
+                .dyn_into::<js_sys::Function>()
+                .map_err(|_| unavailable("No fetch() in this environment; set proof_fetcher")),
+        }
+    }
+
+    /// Download and parse the proof at `url`
+    ///
+    /// A `Content-Length` over `max_proof_bytes` fails before the body is
+    /// read; bodies without one are cut off once they exceed it.
+    pub(crate) async fn fetch_proof(&self, url: &str) -> Result<ProofData, JsValue> {
+        let fetch = self.fetcher()?;
+        let pending = fetch.call1(&JsValue::NULL, &url.into()).map_err(|_| unavailable("Proof request failed"))?;
+        let response: Response = JsFuture::from(js_sys::Promise::resolve(&pending))
+            .await
+            .map_err(|_| unavailable("Proof request failed"))?
+            .unchecked_into();
+        if !response.ok() {
+            return Err(unavailable(&format!("Proof request returned HTTP {}", response.status())));
+        }
+
+        let limit = self.verifier.config().max_proof_bytes();
+        let headers = response.headers();
+        let declared = headers.get("content-length").and_then(|len| len.trim().parse::<u64>().ok());
+        if declared.is_some_and(|len| len > limit) {
+            return Err(VerificationError::PayloadTooLarge.to_js(&format!("Proof exceeds {} bytes", limit)));
+        }
+        let body = match response.body() {
+            Some(stream) => read_limited(&stream, limit, "Proof").await?,
+            None => {
+                let buffer = JsFuture::from(response.array_buffer()?).await?;
+                let body = js_sys::Uint8Array::new(&buffer);
+                if u64::from(body.length()) > limit {
+                    return Err(VerificationError::PayloadTooLarge.to_js(&format!("Proof exceeds {} bytes", limit)));
+                }
+                body.to_vec()
+            }
+        };
+
+        let content_type = headers.get("content-type").unwrap_or_default();
+        parse_proof(&body, &content_type, self.verifier.config().max_decompressed_bytes()).map_err(|(e, reason)| e.to_js(reason))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Fetch the proof at `proof_url` and verify it against `input_bytes` and `output_bytes`
+    ///
+    /// ```js
+    /// const ok = await verifier.verify_from_url("https://prover.example/proofs/42", input, output);
+    /// ```
+    ///
+    /// JSON (`application/json`) and binary (`application/octet-stream`)
+    /// proofs are accepted, optionally gzip- or zstd-compressed. Rejects
+    /// with `VerificationError.ProofUnavailable` if the request fails,
+    /// `PayloadTooLarge` beyond `config.max_proof_bytes`, and
+    /// `MalformedProof` for any other content-type or an unparsable body.
+    #[wasm_bindgen]
+    pub async fn verify_from_url(
+        &self,
+        proof_url: String,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+    ) -> Result<bool, JsValue> {
+        Ok(self.verify_report_from_url(proof_url, input_bytes, output_bytes).await?.passed())
+    }
+
+    /// `verify_from_url`, resolving to the per-check report
+    #[wasm_bindgen]
+    pub async fn verify_report_from_url(
+        &self,
+        proof_url: String,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+    ) -> Result<VerificationReport, JsValue> {
+        let proof_data = self.fetch_proof(&proof_url).await?;
+        Ok(self.verify_report(&proof_data, &input_bytes, &output_bytes))
+    }
+
+    /// Function used by `verify_from_url` and `intercept_fetch` in place of the global `fetch`
+    ///
+    /// Called as `fetcher(url)` and must return a `Response` (or a Promise of
+    /// one), e.g. undici's `fetch` on older Node or a stub in tests.
+    #[wasm_bindgen(setter)]
+    pub fn set_proof_fetcher(&mut self, fetcher: Option<js_sys::Function>) {
+        self.proof_fetcher = fetcher;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use zkml_verifier_core::config::DEFAULT_MAX_PROOF_BYTES;
+
+    #[test]
+    fn test_content_types() {
+        assert_eq!(ProofFormat::from_content_type("application/json; charset=utf-8"), Some(ProofFormat::Json));
+        assert_eq!(ProofFormat::from_content_type("application/ld+json"), Some(ProofFormat::Json));
+        assert_eq!(ProofFormat::from_content_type("Application/Octet-Stream"), Some(ProofFormat::Binary));
+        assert_eq!(ProofFormat::from_content_type("text/html"), None);
+        assert_eq!(ProofFormat::from_content_type(""), None);
+    }
+
+    #[test]
+    fn test_parse_fetched_proof() {
+        let proof = fixtures::proof("0xmodel");
+        let json = serde_json::to_vec(&proof).unwrap();
+        let limit = DEFAULT_MAX_PROOF_BYTES;
+
+        assert_eq!(parse_proof(&json, "application/json", limit).unwrap().model_hash(), "0xmodel");
+        assert_eq!(parse_proof(&proof.encode_binary(), "application/octet-stream", limit).unwrap().model_hash(), "0xmodel");
+        assert_eq!(parse_proof(&json, "text/html", limit).unwrap_err().0, VerificationError::MalformedProof);
+        assert_eq!(parse_proof(&json, "application/octet-stream", limit).unwrap_err().0, VerificationError::MalformedProof);
+    }
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod js_tests {
+    use super::*;
+    use crate::fixtures::{self, kind};
+    use crate::VerifierConfig;
+    use wasm_bindgen_test::wasm_bindgen_test;
+    use zkml_verifier_core::fixtures::{INPUT, OUTPUT};
+
+    /// Verifier whose `fetch` answers every URL with `response(body)`, the body of a JS arrow function
+    fn verifier(response: &str, body: &[u8], max_proof_bytes: Option<u64>) -> WasmVerifier {
+        let mut config = VerifierConfig::default();
+        if let Some(max) = max_proof_bytes {
+            config.set_max_proof_bytes(max);
+        }
+        let mut verifier = WasmVerifier::with_config("0xmodel".to_string(), &config);
+        let fetcher = js_sys::Function::new_with_args("body", &format!("return (url) => {};", response))
+            .call1(&JsValue::NULL, &js_sys::Uint8Array::from(body))
+            .unwrap();
+        verifier.set_proof_fetcher(Some(fetcher.unchecked_into()));
+        verifier
+    }
+
+    async fn verify(verifier: &WasmVerifier) -> Result<bool, Option<String>> {
+        let url = "https://prover.example/proofs/42".to_string();
+        verifier.verify_from_url(url, INPUT.to_vec(), OUTPUT.to_vec()).await.map_err(|error| kind(&error))
+    }
+
+    fn error(error: VerificationError) -> Result<bool, Option<String>> {
+        Err(Some(error.name().to_string()))
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_verify_from_url() {
+        let proof = fixtures::proof("0xmodel");
+        let json = serde_json::to_vec(&proof).unwrap();
+        let typed = |content_type: &str| {
+            format!("new Response(body, {{ headers: {{ 'content-type': '{}' }} }})", content_type)
+        };
+
+        assert_eq!(verify(&verifier(&typed("application/json; charset=utf-8"), &json, None)).await, Ok(true));
+        assert_eq!(verify(&verifier(&typed("application/octet-stream"), &proof.encode_binary(), None)).await, Ok(true));
+        let other = fixtures::proof("0xother");
+        let other = serde_json::to_vec(&other).unwrap();
+        assert_eq!(verify(&verifier(&typed("application/json"), &other, None)).await, Ok(false));
+        assert_eq!(verify(&verifier(&typed("text/html"), &json, None)).await, error(VerificationError::MalformedProof));
+
+        // A plain object without a body stream is read with arrayBuffer()
+        let bufferable = "({ ok: true, status: 200, headers: new Headers({ 'content-type': 'application/json' }), \
+            body: null, arrayBuffer: async () => body.buffer })";
+        assert_eq!(verify(&verifier(bufferable, &json, None)).await, Ok(true));
+        let limited = verifier(bufferable, &json, Some(json.len() as u64 - 1));
+        assert_eq!(verify(&limited).await, error(VerificationError::PayloadTooLarge));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_verify_from_url_failures() {
+        let json = serde_json::to_vec(&fixtures::proof("0xmodel")).unwrap();
+        let unavailable = error(VerificationError::ProofUnavailable);
+        assert_eq!(verify(&verifier("new Response(body, { status: 404 })", &json, None)).await, unavailable);
+        assert_eq!(verify(&verifier("Promise.reject(new TypeError('offline'))", &json, None)).await, unavailable);
+        assert_eq!(verify(&verifier("{ throw new TypeError('offline'); }", &json, None)).await, unavailable);
+
+        // Declared lengths fail before the body is read, streamed ones once it's too long
+        let declared = "({ ok: true, status: 200, headers: new Headers({ 'content-length': '1000000' }), \
+            get body() { throw new Error('read'); } })";
+        assert_eq!(verify(&verifier(declared, &json, Some(1000))).await, error(VerificationError::PayloadTooLarge));
+        let streamed = "new Response(new Blob([body]).stream(), { headers: { 'content-type': 'application/json' } })";
+        let limit = json.len() as u64;
+        assert_eq!(verify(&verifier(streamed, &json, Some(limit))).await, Ok(true));
+        let too_large = error(VerificationError::PayloadTooLarge);
+        assert_eq!(verify(&verifier(streamed, &json, Some(limit - 1))).await, too_large);
+    }
+}
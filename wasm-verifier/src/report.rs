@@ -0,0 +1,158 @@
+// Verification report
+// Per-check outcome of a `WasmVerifier` run, exposed to JS with getters
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+#[cfg(feature = "onnx")]
+use crate::onnx::diagnostic::ModelDiagnostic;
+#[cfg(feature = "extras")]
+use crate::registry::ModelEntry;
+
+pub use zkml_verifier_core::report::{Check, Confidence};
+#[cfg(any(test, feature = "async"))]
+pub(crate) use zkml_verifier_core::report::Stopwatch;
+
+/// Result of a single check
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct CheckResult(zkml_verifier_core::CheckResult);
+
+/// Outcome of a full verification run
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct VerificationReport(zkml_verifier_core::VerificationReport);
+
+wrap!(
+    CheckResult => zkml_verifier_core::CheckResult,
+    VerificationReport => zkml_verifier_core::VerificationReport,
+);
+
+#[wasm_bindgen]
+impl CheckResult {
+    #[wasm_bindgen(getter)]
+    pub fn check(&self) -> Check {
+        self.0.check()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    /// Why the check failed (`undefined` when it passed)
+    #[wasm_bindgen(getter)]
+    pub fn reason(&self) -> Option<String> {
+        self.0.reason().map(str::to_string)
+    }
+
+    /// Error code of the failure (`undefined` when it passed)
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<VerificationError> {
+        self.0.error()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn duration_ms(&self) -> f64 {
+        self.0.duration_ms()
+    }
+}
+
+#[wasm_bindgen]
+impl VerificationReport {
+    /// True when every check passed
+    #[wasm_bindgen(getter)]
+    pub fn passed(&self) -> bool {
+        self.0.passed()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn checks(&self) -> Vec<CheckResult> {
+        self.0.checks().iter().cloned().map(CheckResult).collect()
+    }
+
+    /// Reasons of all failed checks, in check order
+    #[wasm_bindgen(getter)]
+    pub fn failure_reasons(&self) -> Vec<String> {
+        self.0.failure_reasons()
+    }
+
+    /// Error code of the first failed check (`undefined` when all passed)
+    #[wasm_bindgen(getter)]
+    pub fn error(&self) -> Option<VerificationError> {
+        self.0.error()
+    }
+
+    /// Registry entry the proof's model matched (registry verifiers only)
+    #[cfg(feature = "extras")]
+    #[wasm_bindgen(getter)]
+    pub fn matched_model(&self) -> Option<ModelEntry> {
+        self.0.matched_model().cloned().map(ModelEntry::from)
+    }
+
+    /// Why model binding failed, for verifiers built with `from_model_bytes`
+    #[cfg(feature = "onnx")]
+    #[wasm_bindgen(getter)]
+    pub fn model_diagnostic(&self) -> Option<ModelDiagnostic> {
+        self.0.model_diagnostic().cloned().map(ModelDiagnostic::from)
+    }
+
+    /// Block the proof was anchored at, once `verify_anchored` confirmed it
+    #[wasm_bindgen(getter)]
+    pub fn anchored_block(&self) -> Option<u64> {
+        self.0.anchored_block()
+    }
+
+    /// Confidence tier of the result
+    #[wasm_bindgen(getter)]
+    pub fn confidence(&self) -> Confidence {
+        self.0.confidence()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn model_binding(&self) -> bool {
+        self.check_passed(Check::ModelBinding)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn input_hash(&self) -> bool {
+        self.check_passed(Check::InputHash)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn output_hash(&self) -> bool {
+        self.check_passed(Check::OutputHash)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn timestamp(&self) -> bool {
+        self.check_passed(Check::Timestamp)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn proof_validity(&self) -> bool {
+        self.check_passed(Check::ProofValidity)
+    }
+
+    /// Serialize report to JSON string
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+impl VerificationReport {
+    /// Convert into a typed JS error for the first failed check
+    pub(crate) fn into_js_result(self) -> Result<(), JsValue> {
+        self.0.into_result().map_err(|(error, reason)| error.to_js(&reason))
+    }
+}
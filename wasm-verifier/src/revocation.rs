@@ -0,0 +1,26 @@
+// Revocation lists
+// Signed lists of revoked model hashes, prover keys and proofs that `verify` consults
+
+use wasm_bindgen::prelude::*;
+
+use crate::WasmVerifier;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Load a revocation list signed by `issuer_pubkey` (Ed25519, hex)
+    ///
+    /// Replaces the current list. Throws `VerificationError.SignatureInvalid`
+    /// or `UntrustedSigner` if the list isn't validly signed by the issuer,
+    /// and `InvalidArgument` if it is older than the list already loaded,
+    /// so a stale list can't be replayed to un-revoke anything.
+    #[wasm_bindgen]
+    pub fn load_revocation_list(&mut self, list_json: &str, issuer_pubkey: &str) -> Result<(), JsValue> {
+        self.verifier.load_revocation_list(list_json, issuer_pubkey).map_err(|(error, reason)| error.to_js(reason))
+    }
+
+    /// `issued_at` of the loaded revocation list (`undefined` if none)
+    #[wasm_bindgen(getter)]
+    pub fn revocation_list_issued_at(&self) -> Option<u64> {
+        self.verifier.revocation_list_issued_at()
+    }
+}
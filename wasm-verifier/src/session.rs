@@ -0,0 +1,145 @@
+// Verifier sessions
+// Running pass/fail tallies and seen proof IDs across many verifications, for UI state
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::{typescript, ProofData, VerificationReport, WasmVerifier};
+
+/// Tallies of a `VerifierSession` so far
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct SessionSummary(zkml_verifier_core::session::SessionSummary);
+
+wrap!(SessionSummary => zkml_verifier_core::session::SessionSummary);
+
+#[wasm_bindgen]
+impl SessionSummary {
+    /// Distinct proofs verified
+    #[wasm_bindgen(getter)]
+    pub fn total(&self) -> u32 {
+        self.0.total()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn passed(&self) -> u32 {
+        self.0.passed()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn failed(&self) -> u32 {
+        self.0.failed()
+    }
+
+    /// Proofs verified again after their first time (not in `total`)
+    #[wasm_bindgen(getter)]
+    pub fn duplicates(&self) -> u32 {
+        self.0.duplicates()
+    }
+
+    /// Failed proofs counted by error kind, e.g. `{ Expired: 2, OutputHashMismatch: 1 }`
+    #[wasm_bindgen(getter, unchecked_return_type = "Record<string, number>")]
+    pub fn failures_by_error(&self) -> JsValue {
+        typescript::to_object(self.0.failures_by_error())
+    }
+
+    /// When the session started (ms since the Unix epoch, verifier clock)
+    #[wasm_bindgen(getter)]
+    pub fn started_at(&self) -> u64 {
+        self.0.started_at()
+    }
+
+    /// When the latest proof was recorded (`undefined` before the first)
+    #[wasm_bindgen(getter)]
+    pub fn last_verified_at(&self) -> Option<u64> {
+        self.0.last_verified_at()
+    }
+
+    /// Time spent in checks, over all recorded proofs
+    #[wasm_bindgen(getter)]
+    pub fn total_ms(&self) -> f64 {
+        self.0.total_ms()
+    }
+
+    /// Serialize summary to JSON string
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> String {
+        self.0.to_json()
+    }
+}
+
+/// A verifier that keeps count of what it has verified
+///
+/// ```js
+/// const session = new VerifierSession(new WasmVerifier(modelHash));
+/// const report = session.verify_json(proofJson, input, output);
+/// setStats(session.summary()); // { total, passed, failed, failures_by_error, ... }
+/// ```
+///
+/// Proofs are told apart by `ProofData.proof_id`. A proof seen before is
+/// verified again (its report may differ, e.g. once it expires) but only
+/// counted under `duplicates`, so tallies count distinct proofs.
+#[wasm_bindgen]
+pub struct VerifierSession {
+    verifier: WasmVerifier,
+    session: zkml_verifier_core::session::Session,
+}
+
+#[wasm_bindgen]
+impl VerifierSession {
+    /// Start a session with `verifier`, which the session takes over
+    #[wasm_bindgen(constructor)]
+    pub fn new(verifier: WasmVerifier) -> VerifierSession {
+        let session = zkml_verifier_core::session::Session::new(verifier.now_ms());
+        VerifierSession { verifier, session }
+    }
+
+    /// Verify a proof and record the outcome
+    #[wasm_bindgen]
+    pub fn verify(&mut self, proof_data: &ProofData, input_bytes: &[u8], output_bytes: &[u8]) -> VerificationReport {
+        let report = self.verifier.verify_report(proof_data, input_bytes, output_bytes);
+        self.record(proof_data, &report);
+        report
+    }
+
+    /// `verify` for a proof JSON string
+    ///
+    /// Throws `VerificationError.MalformedProof` (recording nothing) if the
+    /// JSON isn't a proof.
+    #[wasm_bindgen]
+    pub fn verify_json(
+        &mut self,
+        proof_json: &str,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, JsValue> {
+        let proof_data = ProofData::parse_json(proof_json)?;
+        Ok(self.verify(&proof_data, input_bytes, output_bytes))
+    }
+
+    /// Record a report produced elsewhere, e.g. by `verify_report_async` on the same verifier
+    #[wasm_bindgen]
+    pub fn record(&mut self, proof_data: &ProofData, report: &VerificationReport) {
+        self.session.record(proof_data, report, self.verifier.now_ms());
+    }
+
+    /// Whether a proof with this `proof_id` was recorded in this session
+    #[wasm_bindgen]
+    pub fn has_seen(&self, proof_id: &str) -> bool {
+        self.session.has_seen(proof_id)
+    }
+
+    /// Snapshot of the session's tallies
+    #[wasm_bindgen]
+    pub fn summary(&self) -> SessionSummary {
+        SessionSummary(self.session.summary().clone())
+    }
+
+    /// Forget every recorded proof and start the tallies over
+    #[wasm_bindgen]
+    pub fn reset(&mut self) {
+        self.session.reset(self.verifier.now_ms());
+    }
+}
@@ -0,0 +1,34 @@
+// Prover signatures
+// Ed25519 and secp256k1 signatures over the canonical proof payload (see `ProofData::signing_bytes`)
+
+use wasm_bindgen::prelude::*;
+
+pub(crate) use zkml_verifier_core::signature::Failure;
+
+use crate::{ProofData, WasmVerifier};
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Ethereum address that signed this proof (`signature_alg = "secp256k1"`)
+    ///
+    /// Throws `VerificationError.SignatureInvalid` if the proof is not
+    /// secp256k1-signed or the signature is malformed. This only recovers the
+    /// address; `WasmVerifier::verify` decides whether it is trusted.
+    #[wasm_bindgen]
+    pub fn recover_signer_address(&self) -> Result<String, JsValue> {
+        self.0.recover_signer_address().map_err(|(error, reason)| error.to_js(reason))
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Trust proofs signed by this Ed25519 public key or Ethereum address (hex)
+    ///
+    /// Signatures by any other key fail with `VerificationError.UntrustedSigner`.
+    /// With no trusted signers, `KeyResolver` or trust store, every signature
+    /// fails with `VerificationError.SignatureInvalid`.
+    #[wasm_bindgen]
+    pub fn add_trusted_signer(&mut self, pubkey: String) {
+        self.verifier.add_trusted_signer(pubkey);
+    }
+}
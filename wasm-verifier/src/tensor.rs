@@ -0,0 +1,183 @@
+// Typed tensor commitments
+// Canonical little-endian, row-major encoding of a tensor with its shape and dtype
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+pub use zkml_verifier_core::tensor::{DType, TypedElements};
+pub(crate) use zkml_verifier_core::tensor::{encode, encode_f32};
+#[cfg(all(feature = "onnxruntime", not(target_arch = "wasm32")))]
+pub(crate) use zkml_verifier_core::tensor::f32_elements;
+
+/// Copy out a `Float32Array`, `Int8Array` or `Int32Array`
+pub(crate) fn typed_array(array: &JsValue) -> Option<TypedElements> {
+    if let Some(array) = array.dyn_ref::<js_sys::Float32Array>() {
+        Some(TypedElements::Float32(array.to_vec()))
+    } else if let Some(array) = array.dyn_ref::<js_sys::Int8Array>() {
+        Some(TypedElements::Int8(array.to_vec()))
+    } else {
+        array.dyn_ref::<js_sys::Int32Array>().map(|array| TypedElements::Int32(array.to_vec()))
+    }
+}
+
+/// Read a typed-array argument, throwing `InvalidArgument` for anything else
+fn typed_elements(array: &JsValue, what: &str) -> Result<TypedElements, JsValue> {
+    typed_array(array).ok_or_else(|| {
+        VerificationError::InvalidArgument.to_js(&format!("{} is not a Float32Array, Int8Array or Int32Array", what))
+    })
+}
+
+/// Declared shape, dtype and (optionally) memory layout of a model input or output
+///
+/// The compact form, used by the binary proof format, is `dtype[d0,d1,...]`
+/// with an optional `@layout` suffix, e.g. `float32[1,3,224,224]@NCHW`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct TensorSpec(zkml_verifier_core::TensorSpec);
+
+wrap!(TensorSpec => zkml_verifier_core::TensorSpec);
+
+#[wasm_bindgen]
+impl TensorSpec {
+    #[wasm_bindgen(constructor)]
+    pub fn new(shape: Vec<u32>, dtype: DType, layout: Option<String>) -> TensorSpec {
+        TensorSpec(zkml_verifier_core::TensorSpec::new(shape, dtype, layout))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn shape(&self) -> Vec<u32> {
+        self.0.shape().to_vec()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn dtype(&self) -> DType {
+        self.0.dtype()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn layout(&self) -> Option<String> {
+        self.0.layout().map(str::to_string)
+    }
+
+    /// Size in bytes of a raw buffer holding this tensor
+    #[wasm_bindgen(getter)]
+    pub fn byte_length(&self) -> Option<u64> {
+        self.0.byte_length()
+    }
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    #[wasm_bindgen(getter)]
+    pub fn input_spec(&self) -> Option<TensorSpec> {
+        self.0.input_spec.clone().map(TensorSpec::from)
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_input_spec(&mut self, input_spec: Option<TensorSpec>) {
+        self.0.input_spec = input_spec.map(Into::into);
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn output_spec(&self) -> Option<TensorSpec> {
+        self.0.output_spec.clone().map(TensorSpec::from)
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_output_spec(&mut self, output_spec: Option<TensorSpec>) {
+        self.0.output_spec = output_spec.map(Into::into);
+    }
+}
+
+const SHAPE_MISMATCH: &str = "Tensor data does not match its shape and dtype";
+
+/// Canonical bytes of a `Float32Array` tensor (row-major)
+///
+/// Pass these to `WasmVerifier::verify` in place of raw buffers when the
+/// proof commits with `hash_tensor_f32`.
+#[wasm_bindgen]
+pub fn encode_tensor_f32(data: &[f32], shape: &[u32]) -> Result<Vec<u8>, JsValue> {
+    encode_f32(data, shape).map_err(|e| e.to_js(SHAPE_MISMATCH))
+}
+
+/// Hash a `Float32Array` tensor (row-major) together with its shape and dtype
+///
+/// Unlike `hash_data` over a serialized buffer, the result doesn't depend
+/// on the platform's byte order or NaN representation, and a tensor can't
+/// collide with the same values under a different shape.
+#[wasm_bindgen]
+pub fn hash_tensor_f32(data: &[f32], shape: &[u32], algorithm: Option<HashAlgorithm>) -> Result<String, JsValue> {
+    let encoded = encode_f32(data, shape).map_err(|e| e.to_js(SHAPE_MISMATCH))?;
+    hash_bytes(&encoded, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+}
+
+/// Hash a tensor given as raw little-endian element bytes (e.g. `new Uint8Array(typed.buffer)`)
+#[wasm_bindgen]
+pub fn hash_tensor(
+    data: &[u8],
+    dtype: DType,
+    shape: &[u32],
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String, JsValue> {
+    let encoded = encode(dtype, shape, data).map_err(|e| e.to_js(SHAPE_MISMATCH))?;
+    hash_bytes(&encoded, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+}
+
+/// Canonical bytes of a `Float32Array`, `Int8Array` or `Int32Array` tensor (row-major)
+///
+/// The dtype follows from the array type; `shape` defaults to one dimension
+/// holding every element.
+#[wasm_bindgen]
+pub fn encode_typed_tensor(
+    #[wasm_bindgen(unchecked_param_type = "TensorArray")] data: &JsValue,
+    shape: Option<Vec<u32>>,
+) -> Result<Vec<u8>, JsValue> {
+    typed_elements(data, "Tensor")?.encode(shape.as_deref()).map_err(|e| e.to_js(SHAPE_MISMATCH))
+}
+
+/// Hash a `Float32Array`, `Int8Array` or `Int32Array` tensor together with its shape and dtype
+///
+/// Same commitment as `hash_tensor` over the array's little-endian bytes
+/// (and as `hash_tensor_f32` for a `Float32Array`).
+#[wasm_bindgen]
+pub fn hash_typed_tensor(
+    #[wasm_bindgen(unchecked_param_type = "TensorArray")] data: &JsValue,
+    shape: Option<Vec<u32>>,
+    algorithm: Option<HashAlgorithm>,
+) -> Result<String, JsValue> {
+    let encoded = encode_typed_tensor(data, shape)?;
+    hash_bytes(&encoded, algorithm.unwrap_or_default())
+        .map_err(|e| e.to_js("Hash algorithm is not enabled in this build"))
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Verify a proof whose I/O commitments are tensor hashes, given the typed arrays themselves
+    ///
+    /// ```js
+    /// verifier.verify_tensors(proof, new Float32Array(pixels), logits);
+    /// ```
+    ///
+    /// Each array is canonically encoded (as by `encode_typed_tensor`) in
+    /// the shape of the proof's `input_spec`/`output_spec`, or as a 1-d
+    /// tensor when the proof declares none.
+    #[wasm_bindgen]
+    pub fn verify_tensors(
+        &self,
+        proof_data: &ProofData,
+        #[wasm_bindgen(unchecked_param_type = "TensorArray")] input: &JsValue,
+        #[wasm_bindgen(unchecked_param_type = "TensorArray")] output: &JsValue,
+    ) -> Result<VerificationReport, JsValue> {
+        let input = typed_elements(input, "Input")?;
+        let output = typed_elements(output, "Output")?;
+        self.verifier.tensors_report(proof_data, &input, &output).map(Into::into).map_err(|e| e.to_js(SHAPE_MISMATCH))
+    }
+}
@@ -0,0 +1,82 @@
+// Tolerance-based output verification
+// Proofs commit to the quantized output; float outputs are re-quantized and compared within epsilon
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::boundary::wrap;
+use crate::report::VerificationReport;
+use crate::{ProofData, WasmVerifier};
+
+/// Affine per-tensor quantization of outputs, and how far re-quantized floats may drift
+///
+/// `q = clamp(round(x / scale) + zero_point)` as a signed `bit_width`-bit
+/// integer; the committed output is these integers, little-endian.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(transparent)]
+#[wasm_bindgen]
+pub struct OutputTolerance(zkml_verifier_core::tolerance::OutputTolerance);
+
+wrap!(OutputTolerance => zkml_verifier_core::tolerance::OutputTolerance);
+
+#[wasm_bindgen]
+impl OutputTolerance {
+    /// `epsilon` is the allowed difference in quantization steps
+    #[wasm_bindgen(constructor)]
+    pub fn new(scale: f32, zero_point: i32, bit_width: u32, epsilon: u32) -> OutputTolerance {
+        OutputTolerance(zkml_verifier_core::tolerance::OutputTolerance::new(scale, zero_point, bit_width, epsilon))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn scale(&self) -> f32 {
+        self.0.scale()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn zero_point(&self) -> i32 {
+        self.0.zero_point()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bit_width(&self) -> u32 {
+        self.0.bit_width()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn epsilon(&self) -> u32 {
+        self.0.epsilon()
+    }
+
+    /// Quantized encoding of a float output, as provers commit to it in `output_hash`
+    ///
+    /// Returns `undefined` for unsupported bit widths or non-finite values.
+    #[wasm_bindgen]
+    pub fn quantize_output(&self, output: &[f32]) -> Option<Vec<u8>> {
+        self.0.encode(output)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Accept float outputs within `tolerance` of the committed quantized output (see `verify_tolerant`)
+    #[wasm_bindgen]
+    pub fn set_output_tolerance(&mut self, tolerance: &OutputTolerance) {
+        self.verifier.set_output_tolerance(**tolerance);
+    }
+
+    /// Verify a proof committing to a quantized output, given the float output observed
+    ///
+    /// `output_hash` is checked against `quantized_output` exactly; `output`
+    /// (e.g. computed on other hardware) must re-quantize to within the
+    /// configured epsilon of it, element by element.
+    #[wasm_bindgen]
+    pub fn verify_tolerant(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[f32],
+        quantized_output: &[u8],
+    ) -> VerificationReport {
+        self.verifier.tolerant_report(proof_data, input, output, quantized_output).into()
+    }
+}
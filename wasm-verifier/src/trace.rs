@@ -0,0 +1,92 @@
+// Tracing
+// The core's check spans (feature `tracing`), printed to the JS console by `init_tracing`
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+mod console {
+    use std::io::Write;
+    use std::str::FromStr;
+
+    use tracing::{Level, Metadata};
+    use tracing_subscriber::fmt::MakeWriter;
+    use wasm_bindgen::prelude::*;
+
+    use crate::error::VerificationError;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = console, js_name = debug)]
+        fn console_debug(line: &str);
+        #[wasm_bindgen(js_namespace = console, js_name = warn)]
+        fn console_warn(line: &str);
+        #[wasm_bindgen(js_namespace = console, js_name = error)]
+        fn console_error(line: &str);
+    }
+
+    /// One formatted event, written to the console when dropped
+    struct Line {
+        level: Level,
+        text: Vec<u8>,
+    }
+
+    impl Write for Line {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.text.extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Drop for Line {
+        fn drop(&mut self) {
+            let text = String::from_utf8_lossy(&self.text);
+            match self.level {
+                Level::ERROR => console_error(text.trim_end()),
+                Level::WARN => console_warn(text.trim_end()),
+                _ => console_debug(text.trim_end()),
+            }
+        }
+    }
+
+    struct Console;
+
+    impl<'a> MakeWriter<'a> for Console {
+        type Writer = Line;
+
+        fn make_writer(&'a self) -> Line {
+            Line { level: Level::INFO, text: Vec::new() }
+        }
+
+        fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Line {
+            Line { level: *meta.level(), text: Vec::new() }
+        }
+    }
+
+    /// Print the verifier's check spans to the browser console, up to `level`
+    ///
+    /// ```js
+    /// init_tracing("debug"); // every check; "warn" for failures only
+    /// ```
+    ///
+    /// Failures go to `console.warn`, everything else to `console.debug`.
+    /// Throws `VerificationError.InvalidArgument` for an unknown level, or if
+    /// tracing was already initialized.
+    #[wasm_bindgen]
+    pub fn init_tracing(level: &str) -> Result<(), JsValue> {
+        let level = Level::from_str(level)
+            .map_err(|_| VerificationError::InvalidArgument.to_js(&format!("Unknown log level '{}'", level)))?;
+        tracing_subscriber::fmt()
+            .with_writer(Console)
+            .with_max_level(level)
+            .without_time()
+            .with_ansi(false)
+            .with_target(false)
+            .try_init()
+            .map_err(|_| VerificationError::InvalidArgument.to_js("Tracing is already initialized"))
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+pub use console::init_tracing;
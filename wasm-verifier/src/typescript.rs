@@ -0,0 +1,288 @@
+// TypeScript definitions
+// Hand-written types for the JSON shapes and error objects the verifier exchanges with JS
+
+use wasm_bindgen::prelude::*;
+
+use crate::{ProofData, VerificationReport};
+
+/// Appended verbatim to the generated `.d.ts`; keep in sync with the serde
+/// representations (the tests below check the unions). rustc doesn't count
+/// the custom section's use of it, hence the `allow`.
+#[allow(dead_code)]
+const TS_TYPES: &str = r#"
+/** Commitment hash names accepted in `hash_alg` and `VerifierConfigJson.hash_algorithm` */
+export type HashAlgorithmName = "sha3-256" | "keccak-256" | "sha-256" | "blake3";
+
+/** `kind` of a `VerificationError` (the name of the `VerificationError` enum variant) */
+export type VerificationErrorKind =
+  | "ModelMismatch"
+  | "InputHashMismatch"
+  | "OutputHashMismatch"
+  | "Expired"
+  | "NotVerified"
+  | "ProofHashMismatch"
+  | "MalformedProof"
+  | "InvalidArgument"
+  | "UnsupportedHashAlgorithm"
+  | "NonceMismatch"
+  | "NonceReplayed"
+  | "SignatureInvalid"
+  | "UntrustedSigner"
+  | "AggregateSignatureInvalid"
+  | "InsufficientAttestors"
+  | "CertificateChainInvalid"
+  | "DidUnresolvable"
+  | "PayloadTooLarge"
+  | "InvalidModel"
+  | "PolicyViolation"
+  | "QuantizationMismatch"
+  | "ShapeMismatch"
+  | "PreprocessingMismatch"
+  | "RecomputationMismatch"
+  | "Revoked"
+  | "VersionNotAllowed"
+  | "CompositeInvalid"
+  | "InclusionInvalid"
+  | "ToleranceExceeded"
+  | "ClassMismatch"
+  | "ProofUnavailable"
+  | "Aborted"
+  | "AnchorInvalid"
+  | "NotRegistered";
+
+/** Error thrown by every failing verifier method */
+export interface VerificationErrorObject extends Error {
+  name: "VerificationError";
+  code: VerificationError;
+  kind: VerificationErrorKind;
+}
+
+/** Name of a check in `CheckResultJson.check` */
+export type CheckName =
+  | "model_binding"
+  | "input_hash"
+  | "output_hash"
+  | "input_spec"
+  | "output_spec"
+  | "timestamp"
+  | "proof_validity"
+  | "proof_binding"
+  | "nonce"
+  | "signature"
+  | "aggregate_signature"
+  | "certificate_chain"
+  | "quantization"
+  | "preprocessing"
+  | "recomputation"
+  | "revocation"
+  | "model_version"
+  | "composite"
+  | "inclusion"
+  | "output_tolerance"
+  | "classification"
+  | "anchor"
+  | "registry_model"
+  | "registry_prover";
+
+/** ONNX element type names */
+export type DTypeName = "float32" | "float64" | "float16" | "int8" | "uint8" | "int16" | "int32" | "int64" | "bool";
+
+/** Typed arrays accepted as tensors by `verify_tensors` and `hash_typed_tensor` */
+export type TensorArray = Float32Array | Int8Array | Int32Array;
+
+export interface TensorSpecJson {
+  shape: number[];
+  dtype: DTypeName;
+  /** One letter per dimension, e.g. "NCHW" */
+  layout?: string;
+}
+
+/** JSON form of `ProofData`, as accepted by `verify_json` */
+export interface ProofDataJson {
+  model_hash: string;
+  proof_hash: string;
+  input_hash?: string;
+  output_hash?: string;
+  /** Unix milliseconds */
+  timestamp: number;
+  verified: boolean;
+  hash_alg?: HashAlgorithmName;
+  nonce?: string;
+  signature_alg?: "ed25519" | "secp256k1" | "eip712";
+  signature?: string;
+  signer_pubkey?: string;
+  kid?: string;
+  prover_did?: string;
+  attestor_pubkeys?: string[];
+  aggregate_signature?: string;
+  inputs?: Record<string, string>;
+  outputs?: Record<string, string>;
+  input_spec?: TensorSpecJson;
+  output_spec?: TensorSpecJson;
+  model_version?: string;
+  partial_model_hash?: string;
+  partial_layers?: string[];
+  quantization_hash?: string;
+  preprocessing_hash?: string;
+  architecture_hash?: string;
+  weights_hash?: string;
+  metadata_hash?: string;
+  cert_chain?: string[];
+}
+
+export interface CheckResultJson {
+  check: CheckName;
+  passed: boolean;
+  reason: string | null;
+  error: VerificationErrorKind | null;
+  duration_ms: number;
+}
+
+export interface ModelEntryJson {
+  model_hash: string;
+  label: string;
+  version: string;
+}
+
+export interface ModelDiagnosticJson {
+  architecture: boolean | null;
+  weights: boolean | null;
+  metadata: boolean | null;
+  file_hash: boolean;
+  summary: string;
+}
+
+/** JSON form of `VerificationReport` (`to_json` / `to_object`) */
+export interface VerificationReportJson {
+  checks: CheckResultJson[];
+  total_ms: number;
+  matched_model?: ModelEntryJson;
+  model_diagnostic?: ModelDiagnosticJson;
+  /** Block the proof was anchored at (`verify_anchored`) */
+  anchored_block?: number;
+}
+
+/** A commitment in a proof, as listed by `describe` */
+export interface CommitmentJson {
+  /** Proof field, e.g. "input_hash" or "inputs.image" */
+  field: string;
+  /** null when the verifier's default algorithm applies */
+  algorithm: HashAlgorithmName | null;
+  value: string;
+}
+
+/** A signer a proof names, as listed by `describe` */
+export interface SignerJson {
+  /** Proof field the identity comes from, "signature" (recovered address), "jws" or "cose" */
+  source: string;
+  algorithm: "ed25519" | "secp256k1" | "bls12-381" | null;
+  identity: string | null;
+}
+
+/** Storage behind `verify_cached` (`use_indexed_db_cache` provides one); values are opaque strings */
+export interface ProofCacheStorage {
+  get(key: string): string | null | undefined | Promise<string | null | undefined>;
+  set(key: string, value: string): void | Promise<void>;
+}
+
+/** `VerifierConfig` fields; omitted ones take their defaults */
+export interface VerifierConfigJson {
+  max_age_ms?: number;
+  check_freshness?: boolean;
+  clock_skew_ms?: number;
+  hash_algorithm?: HashAlgorithmName;
+  require_nonce?: boolean;
+  require_signature?: boolean;
+  min_attestors?: number;
+  max_decompressed_bytes?: number;
+  max_proof_bytes?: number;
+  max_input_bytes?: number;
+}
+
+/** `VerifierPolicy` document: `VerifierConfigJson` fields plus the policy's lists; unknown fields are rejected */
+export interface VerifierPolicyJson extends VerifierConfigJson {
+  models?: ModelEntryJson[];
+  trusted_signers?: string[];
+  trusted_attestors?: string[];
+  hash_algorithms?: HashAlgorithmName[];
+  required_checks?: CheckName[];
+}
+"#;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_APPEND_CONTENT: &str = TS_TYPES;
+
+/// Round-trip a serde value through JSON into a plain JS object
+pub(crate) fn to_object<T: serde::Serialize>(value: &T) -> JsValue {
+    let json = serde_json::to_string(value).unwrap_or_default();
+    js_sys::JSON::parse(&json).unwrap_or(JsValue::UNDEFINED)
+}
+
+#[wasm_bindgen]
+impl ProofData {
+    /// Plain-object form of the proof
+    #[wasm_bindgen(unchecked_return_type = "ProofDataJson")]
+    pub fn to_object(&self) -> JsValue {
+        to_object(self)
+    }
+
+    /// `to_object`, under the name `JSON.stringify` looks for
+    #[wasm_bindgen(js_name = toJSON, unchecked_return_type = "ProofDataJson")]
+    pub fn to_json_object(&self) -> JsValue {
+        to_object(self)
+    }
+}
+
+#[wasm_bindgen]
+impl VerificationReport {
+    /// Plain-object form of the report
+    #[wasm_bindgen(unchecked_return_type = "VerificationReportJson")]
+    pub fn to_object(&self) -> JsValue {
+        to_object(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::VerificationError;
+    use crate::hash::HashAlgorithm;
+    use crate::report::Check;
+    use crate::VerifierConfig;
+    use zkml_verifier_core::tensor::DType;
+    use serde::de::DeserializeOwned;
+
+    /// Quoted members of `export type <name> = ...;`
+    fn union(name: &str) -> Vec<String> {
+        let start = TS_TYPES.find(&format!("export type {} =", name)).unwrap();
+        let body = &TS_TYPES[start..start + TS_TYPES[start..].find(';').unwrap()];
+        body.split('"').skip(1).step_by(2).map(str::to_string).collect()
+    }
+
+    fn parse_all<T: DeserializeOwned>(name: &str) -> Vec<T> {
+        union(name)
+            .iter()
+            .map(|m| serde_json::from_value(serde_json::Value::String(m.clone())).unwrap_or_else(|_| panic!("{}", m)))
+            .collect()
+    }
+
+    #[test]
+    fn test_unions_match_serde() {
+        let errors: Vec<VerificationError> = parse_all("VerificationErrorKind");
+        assert_eq!(errors.len() as u32, VerificationError::NotRegistered as u32);
+        assert!(errors.iter().all(|e| union("VerificationErrorKind").contains(&e.name().to_string())));
+
+        let checks: Vec<Check> = parse_all("CheckName");
+        assert_eq!(checks.len() as u32, Check::RegistryProver as u32 + 1);
+
+        assert_eq!(parse_all::<HashAlgorithm>("HashAlgorithmName").len(), 4);
+        assert_eq!(parse_all::<DType>("DTypeName").len(), 9);
+    }
+
+    #[test]
+    fn test_config_defaults_omitted_fields() {
+        let config: VerifierConfig = serde_json::from_str(r#"{"require_nonce": true}"#).unwrap();
+        assert!(config.require_nonce());
+        assert_eq!(config.max_age_ms(), VerifierConfig::default().max_age_ms());
+    }
+}
@@ -0,0 +1,39 @@
+// Verifiable Credential export
+// W3C VC 2.0 documents attesting to a verification result, issued by the core with the verifier's issuer key
+
+use wasm_bindgen::prelude::*;
+
+use crate::error::VerificationError;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Set the Ed25519 secret key (32 bytes, hex) credentials are issued with
+    ///
+    /// The issuer is identified as the key's `did:key`.
+    #[wasm_bindgen]
+    pub fn set_issuer_key(&mut self, secret_key: &str) -> Result<(), JsValue> {
+        self.verifier
+            .set_issuer_key(secret_key)
+            .map_err(|_| VerificationError::InvalidArgument.to_js("Issuer key must be 32 bytes of hex"))
+    }
+
+    /// `did:key` of the credential issuer (`undefined` until `set_issuer_key`)
+    #[wasm_bindgen(getter)]
+    pub fn issuer_did(&self) -> Option<String> {
+        self.verifier.issuer_did()
+    }
+
+    /// Export a passed verification as a signed W3C Verifiable Credential (JSON-LD)
+    ///
+    /// The credential asserts that the proof's model produced `output_hash`
+    /// for `input_hash` at the proof timestamp, and that this verifier
+    /// checked it. Throws `VerificationError.InvalidArgument` if the report
+    /// did not pass or no issuer key is set.
+    #[wasm_bindgen]
+    pub fn to_verifiable_credential(&self, proof_data: &ProofData, report: &VerificationReport) -> Result<String, JsValue> {
+        self.verifier
+            .to_verifiable_credential(proof_data, report)
+            .map_err(|(error, reason)| error.to_js(reason))
+    }
+}
@@ -0,0 +1,20 @@
+// Semantic model versions
+// Version-range policies (`2.x`, `>=1.4, <2`, `^2.1 || ^3`) over registry versions
+
+use wasm_bindgen::prelude::*;
+
+use crate::WasmVerifier;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Only accept models whose version satisfies `policy`, e.g. `"2.x"` or `">=2.1, <3 || ^4"`
+    ///
+    /// The version comes from the registry entry the proof matched (see
+    /// `with_registry`), or from the proof's `model_version` for a
+    /// single-model verifier. Throws `VerificationError.InvalidArgument` if
+    /// the policy doesn't parse.
+    #[wasm_bindgen]
+    pub fn set_version_policy(&mut self, policy: &str) -> Result<(), JsValue> {
+        self.verifier.set_version_policy(policy).map_err(|e| e.to_js("Invalid version policy"))
+    }
+}
@@ -0,0 +1,143 @@
+// WebCrypto signatures
+// Ed25519 and P-256 checks answered by the engine's `crypto.subtle` in the async verify paths
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use zkml_verifier_core::crypto::{self, Scheme, Verdicts};
+use zkml_verifier_core::hash::HashAlgorithm;
+
+use crate::report::Check;
+use crate::{ProofData, VerificationReport, WasmVerifier};
+
+/// Policy checks whose signatures WebCrypto may check (the others check none, or only secp256k1 and BLS)
+pub(crate) const DELEGATED: [Check; 2] = [Check::Signature, Check::CertificateChain];
+
+/// Times a check is re-run with new verdicts before settling: one per certificate in a long chain, and then some
+const MAX_ROUNDS: usize = 8;
+
+#[wasm_bindgen]
+extern "C" {
+    type SubtleCrypto;
+
+    #[wasm_bindgen(method, catch, js_name = importKey)]
+    fn import_key(
+        this: &SubtleCrypto,
+        format: &str,
+        key_data: &js_sys::Uint8Array,
+        algorithm: &JsValue,
+        extractable: bool,
+        usages: &js_sys::Array,
+    ) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn verify(
+        this: &SubtleCrypto,
+        algorithm: &JsValue,
+        key: &JsValue,
+        signature: &js_sys::Uint8Array,
+        data: &js_sys::Uint8Array,
+    ) -> Result<js_sys::Promise, JsValue>;
+}
+
+/// `crypto.subtle`, if this environment has one
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+fn subtle() -> Option<SubtleCrypto> {
+    let crypto = js_sys::Reflect::get(&js_sys::global(), &"crypto".into()).ok()?;
+    let subtle = js_sys::Reflect::get(&crypto, &"subtle".into()).ok()?;
+    subtle.is_object().then(|| subtle.unchecked_into())
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "wasi"))]
+fn subtle() -> Option<SubtleCrypto> {
+    None
+}
+
+/// `{ name, [field]: value }`, a WebCrypto algorithm parameter object
+fn algorithm(name: &str, field: &str, value: &str) -> JsValue {
+    let object = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&object, &"name".into(), &name.into());
+    let _ = js_sys::Reflect::set(&object, &field.into(), &value.into());
+    object.into()
+}
+
+/// WebCrypto's verdict on `check`, `None` if it has no such algorithm or key format
+///
+/// The encoding checks `verify_strict` makes are `crypto::verify`'s, which
+/// runs them before it looks up this verdict.
+async fn verify_check(subtle: &SubtleCrypto, check: &crypto::Check) -> Option<bool> {
+    let (import, verify) = match check.scheme {
+        Scheme::Ed25519 => (JsValue::from("Ed25519"), JsValue::from("Ed25519")),
+        Scheme::P256 => (algorithm("ECDSA", "namedCurve", "P-256"), algorithm("ECDSA", "hash", "SHA-256")),
+    };
+    let usages = js_sys::Array::of1(&"verify".into());
+    let key_data = js_sys::Uint8Array::from(&check.key[..]);
+    let imported = subtle.import_key("raw", &key_data, &import, false, &usages).ok()?;
+    let key = JsFuture::from(imported).await.ok()?;
+
+    let signature = js_sys::Uint8Array::from(&check.signature[..]);
+    let message = js_sys::Uint8Array::from(&check.message[..]);
+    let verified = subtle.verify(&verify, &key, &signature, &message).ok()?;
+    Some(JsFuture::from(verified).await.ok()?.is_truthy())
+}
+
+impl WasmVerifier {
+    /// `run_policy_check`, with its Ed25519 and P-256 signatures checked by WebCrypto
+    ///
+    /// The check runs on a scratch report to learn which signatures it
+    /// needs, WebCrypto checks them, and it runs again until it needs no
+    /// more (a certificate chain asks for one link at a time). Then it's
+    /// recorded with those verdicts. Returns false, having recorded nothing, if
+    /// there is no WebCrypto or it lacks one of the algorithms: the caller
+    /// then runs the check as the sync paths do.
+    pub(crate) async fn run_policy_check_subtle(
+        &self,
+        check: Check,
+        report: &mut VerificationReport,
+        proof_data: &ProofData,
+        alg: &Result<HashAlgorithm, String>,
+    ) -> bool {
+        let Some(subtle) = subtle() else {
+            return false;
+        };
+        let mut verdicts = Verdicts::new();
+        for _ in 0..MAX_ROUNDS {
+            let mut scratch = zkml_verifier_core::VerificationReport::default();
+            let probe = || self.run_policy_check(check, &mut scratch, proof_data, alg);
+            let ((), unanswered) = crypto::with_verdicts(verdicts.clone(), probe);
+            if unanswered.is_empty() {
+                crypto::with_verdicts(verdicts, || self.run_policy_check(check, report, proof_data, alg));
+                return true;
+            }
+            for needed in unanswered {
+                let Some(verdict) = verify_check(&subtle, &needed).await else {
+                    return false;
+                };
+                verdicts.insert(needed, verdict);
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_webcrypto_leaves_checks_to_the_sync_path() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut proof = crate::fixtures::proof("0xmodel");
+        proof.set_signer_pubkey(Some(crate::hex::encode(key.verifying_key().as_bytes())));
+        proof.set_signature(Some(crate::hex::encode(&key.sign(&proof.signing_bytes()).to_bytes())));
+        let mut verifier = WasmVerifier::new("0xmodel".to_string());
+        verifier.add_trusted_signer(crate::hex::encode(key.verifying_key().as_bytes()));
+
+        let mut report = verifier.new_report();
+        let alg = verifier.hash_algorithm_for(&proof);
+        let future = verifier.run_policy_check_subtle(Check::Signature, &mut report, &proof, &alg);
+        assert!(!crate::promise::tests::block_on(future));
+        assert!(!report.ran(Check::Signature));
+    }
+}
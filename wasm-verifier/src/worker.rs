@@ -0,0 +1,122 @@
+// Web Worker offloading
+// `WorkerVerifier` runs `verify_report` in a dedicated worker (js/worker.js) and keeps the main thread free
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::error::VerificationError;
+use crate::{ProofData, VerificationReport, VerifierConfig};
+
+#[wasm_bindgen(module = "/js/worker.js")]
+extern "C" {
+    type VerifierWorker;
+
+    #[wasm_bindgen(constructor, catch)]
+    fn new(url: Option<String>) -> Result<VerifierWorker, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn request(this: &VerifierWorker, message: &JsValue, transfer: &js_sys::Array) -> js_sys::Promise;
+
+    #[wasm_bindgen(method)]
+    fn terminate(this: &VerifierWorker);
+}
+
+/// Verifier whose checks run in a Web Worker
+///
+/// ```js
+/// const verifier = new WorkerVerifier(modelHash);
+/// const report = await verifier.verify_report(proof, input, output);
+/// ```
+///
+/// Covers verifiers described by a model hash and a `VerifierConfig`; trust
+/// stores, registries and nonce challenges need an in-thread `WasmVerifier`.
+#[wasm_bindgen]
+pub struct WorkerVerifier {
+    worker: VerifierWorker,
+    model_hash: String,
+    config: VerifierConfig,
+}
+
+#[wasm_bindgen]
+impl WorkerVerifier {
+    /// Spawn the worker (`worker_url` overrides the bundled js/worker.js, e.g. for a CDN copy)
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        model_hash: String,
+        config: Option<VerifierConfig>,
+        worker_url: Option<String>,
+    ) -> Result<WorkerVerifier, JsValue> {
+        let worker = VerifierWorker::new(worker_url)?;
+        Ok(WorkerVerifier { worker, model_hash, config: config.unwrap_or_default() })
+    }
+
+    /// Verify in the worker, resolving to whether every check passed
+    #[wasm_bindgen]
+    pub async fn verify(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+    ) -> Result<bool, JsValue> {
+        Ok(self.verify_report(proof_data, input_bytes, output_bytes).await?.passed())
+    }
+
+    /// Verify in the worker, resolving to the per-check report
+    ///
+    /// The I/O buffers are transferred to the worker rather than copied.
+    #[wasm_bindgen]
+    pub async fn verify_report(
+        &self,
+        proof_data: &ProofData,
+        input_bytes: Vec<u8>,
+        output_bytes: Vec<u8>,
+    ) -> Result<VerificationReport, JsValue> {
+        let input = js_sys::Uint8Array::from(input_bytes.as_slice());
+        let output = js_sys::Uint8Array::from(output_bytes.as_slice());
+        let message = js_sys::Object::new();
+        let fields: [(&str, JsValue); 5] = [
+            ("model_hash", self.model_hash.clone().into()),
+            ("config", serde_json::to_string(&self.config).unwrap_or_default().into()),
+            ("proof", serde_json::to_string(proof_data).unwrap_or_default().into()),
+            ("input", input.clone().into()),
+            ("output", output.clone().into()),
+        ];
+        for (name, value) in fields {
+            js_sys::Reflect::set(&message, &name.into(), &value)?;
+        }
+        let transfer = js_sys::Array::of2(&input.buffer(), &output.buffer());
+
+        let report = JsFuture::from(self.worker.request(&message, &transfer)).await?;
+        parse_report(&report.as_string().unwrap_or_default())
+    }
+
+    /// Stop the worker; pending verifications reject
+    #[wasm_bindgen]
+    pub fn terminate(&self) {
+        self.worker.terminate();
+    }
+}
+
+/// Rebuild the report the worker serialized with `to_json`
+fn parse_report(json: &str) -> Result<VerificationReport, JsValue> {
+    serde_json::from_str(json).map_err(|_| VerificationError::MalformedProof.to_js("Invalid report from verifier worker"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::WasmVerifier;
+
+    #[test]
+    fn test_report_survives_worker_round_trip() {
+        let verifier = WasmVerifier::new("0xmodel".to_string());
+        let proof = fixtures::proof("0xmodel");
+        let report = verifier.verify_report(&proof, b"in", b"tampered");
+
+        let received = parse_report(&report.to_json()).unwrap();
+        assert!(!received.passed());
+        assert_eq!(received.error(), Some(VerificationError::OutputHashMismatch));
+        assert_eq!(received.failure_reasons(), report.failure_reasons());
+    }
+}
@@ -0,0 +1,18 @@
+// X.509 prover identity
+// Validates that the proof signer's key chains to a configured root CA
+
+use wasm_bindgen::prelude::*;
+
+use crate::WasmVerifier;
+
+#[wasm_bindgen]
+impl WasmVerifier {
+    /// Add a root CA certificate (PEM or base64 DER) to the trust store
+    ///
+    /// Once a root is configured, every proof must carry a `cert_chain`
+    /// whose leaf certifies `signer_pubkey` and which chains to a trusted root.
+    #[wasm_bindgen]
+    pub fn add_trusted_root(&mut self, certificate: &str) -> Result<(), JsValue> {
+        self.verifier.add_trusted_root(certificate).map_err(|(error, reason)| error.to_js(reason))
+    }
+}
@@ -0,0 +1,60 @@
+// Browser tests
+// The paths Node lacks the APIs for (IndexedDB, Web Workers): `build.sh --test --browser`, with chromedriver on PATH
+
+#![cfg(all(target_arch = "wasm32", feature = "async"))]
+
+use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+use wasm_bindgen::JsValue;
+use zkml_wasm_verifier::{get_timestamp, hash_data, ProofData, VerificationError, WasmVerifier, WorkerVerifier};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn proof() -> ProofData {
+    let (input, output) = (hash_data(b"in", None).unwrap(), hash_data(b"out", None).unwrap());
+    ProofData::new("0xmodel".to_string(), "0xproof".to_string(), input, output, get_timestamp(), true)
+}
+
+#[wasm_bindgen_test]
+async fn test_indexed_db_cache() {
+    let mut verifier = WasmVerifier::new("0xmodel".to_string());
+    verifier.use_indexed_db_cache(Some(format!("zkml-verifier-test-{}", js_sys::Date::now())));
+    let proof = proof();
+    let verify = |input: &[u8], output: &[u8]| {
+        let (input, output) = (js_sys::Uint8Array::from(input), js_sys::Uint8Array::from(output));
+        verifier.verify_cached(&proof, "io".to_string(), input, output)
+    };
+
+    assert!(!verify(b"", b"").await.passed());
+    assert!(verify(b"in", b"out").await.passed());
+    // Served from the database, without the data
+    assert!(verify(b"", b"").await.passed());
+}
+
+/// Object URL of a worker that answers requests for `"out"` with `report` and fails the rest
+fn stub_worker(report: &str) -> String {
+    let source = format!(
+        "const report = {};\n\
+        self.onmessage = ({{ data }}) => self.postMessage(new TextDecoder().decode(data.output) === 'out'\n\
+          ? {{ id: data.id, report }}\n\
+          : {{ id: data.id, error: {{ message: JSON.parse(data.proof).model_hash, kind: 'NotVerified' }} }});",
+        serde_json::to_string(report).unwrap()
+    );
+    let url = "return URL.createObjectURL(new Blob([source], { type: 'text/javascript' }));";
+    js_sys::Function::new_with_args("source", url).call1(&JsValue::NULL, &source.into()).unwrap().as_string().unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_worker_verifier() {
+    let report = WasmVerifier::new("0xmodel".to_string()).verify_report(&proof(), b"in", b"out").to_json();
+    let worker = WorkerVerifier::new("0xmodel".to_string(), None, Some(stub_worker(&report))).unwrap();
+
+    let received = worker.verify_report(&proof(), b"in".to_vec(), b"out".to_vec()).await.unwrap();
+    assert!(received.passed());
+    assert_eq!(received.to_json(), report);
+
+    // Errors thrown in the worker reject with their kind
+    let error = worker.verify(&proof(), b"in".to_vec(), b"tampered".to_vec()).await.unwrap_err();
+    assert_eq!(js_sys::Reflect::get(&error, &"kind".into()).unwrap(), VerificationError::NotVerified.name());
+    assert_eq!(js_sys::Reflect::get(&error, &"message".into()).unwrap(), "0xmodel");
+    worker.terminate();
+}
@@ -0,0 +1,207 @@
+// Aggregated inference proofs
+// One proof over a Merkle root of N (input, output) pairs of the same model, with per-inference inclusion proofs
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::hash::{self, hash_bytes, split_commitment, HashAlgorithm, IoSource};
+use crate::merkle::{audit_path, leaf_hash, root_from_path, tree_root};
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::{hex, ProofData, Verifier};
+
+/// Domain separators (format version 1)
+const LEAF_DOMAIN: &[u8] = b"zkml/inference/v1\0";
+const COUNT_DOMAIN: &[u8] = b"zkml/aggregate/count/v1\0";
+
+/// `domain || u32 len || input digest || u32 len || output digest`
+fn leaf(input_hash: &str, output_hash: &str) -> Result<Vec<u8>, VerificationError> {
+    let mut out = LEAF_DOMAIN.to_vec();
+    for commitment in [input_hash, output_hash] {
+        let digest = hex::decode(split_commitment(commitment)?.1).ok_or(VerificationError::InvalidArgument)?;
+        out.extend_from_slice(&(digest.len() as u32).to_le_bytes());
+        out.extend_from_slice(&digest);
+    }
+    Ok(out)
+}
+
+fn leaf_hashes(inputs: &[String], outputs: &[String], alg: HashAlgorithm) -> Result<Vec<Vec<u8>>, VerificationError> {
+    if inputs.is_empty() || inputs.len() != outputs.len() {
+        return Err(VerificationError::InvalidArgument);
+    }
+    inputs.iter().zip(outputs).map(|(i, o)| leaf_hash(&leaf(i, o)?, alg)).collect()
+}
+
+/// A proof over many inferences of one model
+///
+/// `input_hash` is the Merkle root (`aggregate_inferences`) over the
+/// inferences' `(input_hash, output_hash)` pairs and `output_hash` commits
+/// to their count (`AggregatedProof::count_commitment`), so a signature over
+/// the proof covers both.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AggregatedProof {
+    proof: ProofData,
+    count: u64,
+}
+
+impl AggregatedProof {
+    pub fn new(proof: ProofData, count: u64) -> AggregatedProof {
+        AggregatedProof { proof, count }
+    }
+
+    pub fn proof(&self) -> &ProofData {
+        &self.proof
+    }
+
+    /// Number of aggregated inferences
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Merkle root over the inferences (the proof's `input_hash`)
+    pub fn root(&self) -> &str {
+        &self.proof.input_hash
+    }
+
+    /// `output_hash` an aggregate over `count` inferences must carry
+    pub fn count_commitment(count: u64, alg: HashAlgorithm) -> Result<String, VerificationError> {
+        hash_bytes(&[COUNT_DOMAIN, &count.to_le_bytes()].concat(), alg)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<AggregatedProof, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Inclusion proof for one inference of an `AggregatedProof`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InferenceProof {
+    index: u64,
+    /// Sibling hashes from the leaf up to the root (hex)
+    path: Vec<String>,
+}
+
+impl InferenceProof {
+    /// Inclusion proof for inference `index` (see `aggregate_inferences`)
+    pub fn create(
+        inputs: &[String],
+        outputs: &[String],
+        index: usize,
+        alg: HashAlgorithm,
+    ) -> Result<InferenceProof, VerificationError> {
+        let hashes = leaf_hashes(inputs, outputs, alg)?;
+        if index >= hashes.len() {
+            return Err(VerificationError::InvalidArgument);
+        }
+        let path = audit_path(index, &hashes, alg)?.iter().map(|h| hex::encode(h)).collect();
+        Ok(InferenceProof { index: index as u64, path })
+    }
+
+    /// Position of the inference in the aggregate
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Whether the inference `(input, output)` (hashes) is leaf `index` of `count` under `root`
+    pub fn verifies(&self, root: &str, count: u64, input: &str, output: &str, alg: HashAlgorithm) -> bool {
+        let path: Option<Vec<Vec<u8>>> = self.path.iter().map(|h| hex::decode(h)).collect();
+        let (Ok(leaf), Some(path), Ok((_, root))) = (leaf(input, output), path, split_commitment(root)) else {
+            return false;
+        };
+        leaf_hash(&leaf, alg)
+            .and_then(|leaf| root_from_path(self.index, count, leaf, &path, alg))
+            .ok()
+            .flatten()
+            .is_some_and(|computed| hash::digests_equal(&hex::encode(&computed), root))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<InferenceProof, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Merkle root over `(input_hashes[i], output_hashes[i])` pairs, for an aggregate's `input_hash`
+pub fn aggregate_inferences(
+    input_hashes: &[String],
+    output_hashes: &[String],
+    alg: HashAlgorithm,
+) -> Result<String, VerificationError> {
+    leaf_hashes(input_hashes, output_hashes, alg)
+        .and_then(|hashes| tree_root(&hashes, alg))
+        .map(|root| format!("0x{}", hex::encode(&root)))
+}
+
+impl Verifier {
+    /// Verify an aggregated proof (model binding, count, validity, signature, ...)
+    pub fn aggregate_report(&self, aggregate: &AggregatedProof) -> VerificationReport {
+        let mut report = self.new_report();
+        let alg = self.hash_algorithm_for(&aggregate.proof).unwrap_or_default();
+        // The root is the proof's own input commitment; only its tag has to agree
+        let root_alg = split_commitment(aggregate.root()).ok().and_then(|(tag, _)| tag).unwrap_or(alg);
+        let root = IoSource::Hashed(root_alg, aggregate.root().to_string(), aggregate.count);
+        let count = [COUNT_DOMAIN, &aggregate.count.to_le_bytes()].concat();
+        self.run_checks(&mut report, &aggregate.proof, root, IoSource::Bytes(&count));
+        report
+    }
+
+    /// Verify an aggregate and that `input`/`output` is one of its inferences
+    pub fn inference_report(
+        &self,
+        aggregate: &AggregatedProof,
+        input: &[u8],
+        output: &[u8],
+        inclusion: &InferenceProof,
+    ) -> VerificationReport {
+        let mut report = self.aggregate_report(aggregate);
+        let started = Stopwatch::start();
+        let alg = self.hash_algorithm_for(&aggregate.proof).unwrap_or_default();
+        let included = match (hash_bytes(input, alg), hash_bytes(output, alg)) {
+            (Ok(i), Ok(o)) => inclusion.verifies(aggregate.root(), aggregate.count, &i, &o, alg),
+            _ => false,
+        };
+        let reason = format!("Inference is not leaf {} of the aggregate's {}", inclusion.index, aggregate.count);
+        report.record(Check::Inclusion, included, &reason, started);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+
+    #[test]
+    fn test_verify_inference() {
+        let alg = HashAlgorithm::default();
+        let inputs: Vec<String> = (0..5u8).map(|i| hash_bytes(&[i], alg).unwrap()).collect();
+        let outputs: Vec<String> = (0..5u8).map(|i| hash_bytes(&[i, i], alg).unwrap()).collect();
+        let root = aggregate_inferences(&inputs, &outputs, alg).unwrap();
+        let proof = ProofData::new(
+            "0xmodel".to_string(),
+            "0xproof".to_string(),
+            root,
+            AggregatedProof::count_commitment(5, alg).unwrap(),
+            system_now_ms(),
+            true,
+        );
+        let aggregate = AggregatedProof::from_json(&AggregatedProof::new(proof, 5).to_json()).unwrap();
+        let verifier = Verifier::new("0xmodel".to_string());
+        assert!(verifier.aggregate_report(&aggregate).passed());
+
+        let inclusion = InferenceProof::create(&inputs, &outputs, 3, alg).unwrap();
+        assert!(verifier.inference_report(&aggregate, &[3], &[3, 3], &inclusion).passed());
+        let report = verifier.inference_report(&aggregate, &[3], &[4, 4], &inclusion);
+        assert_eq!(report.error(), Some(VerificationError::InclusionInvalid));
+
+        // The count is committed, so it can't be inflated
+        let inflated = AggregatedProof::new(aggregate.proof().clone(), 6);
+        assert_eq!(verifier.aggregate_report(&inflated).error(), Some(VerificationError::OutputHashMismatch));
+    }
+}
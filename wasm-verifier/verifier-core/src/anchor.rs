@@ -0,0 +1,159 @@
+// On-chain anchoring
+// Checks that a proof is in a Merkle root a transaction committed on chain
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash;
+use crate::inclusion::MerkleProof;
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::{ProofData, Verifier};
+
+/// Where a prover says a proof was anchored
+///
+/// ```json
+/// { "chain": "eip155:1", "tx_hash": "0x…", "block_number": 19000000, "root": "0x…",
+///   "path": { "index": 3, "leaf_count": 1000, "algorithm": "sha3-256", "path": ["0x…"] } }
+/// ```
+///
+/// `path` leads from the proof's `leaf_hash` to `root`, which the
+/// transaction `tx_hash` committed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnchorRecord {
+    /// Chain identifier passed on to the chain query (e.g. CAIP-2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chain: Option<String>,
+    tx_hash: String,
+    block_number: u64,
+    root: String,
+    path: MerkleProof,
+}
+
+/// What the chain says about an anchoring transaction
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AnchorTx {
+    /// Block the transaction was included in
+    pub block_number: u64,
+    /// Root the transaction committed (from its calldata or logs)
+    pub root: String,
+}
+
+impl AnchorRecord {
+    pub fn new(tx_hash: String, block_number: u64, root: String, path: MerkleProof, chain: Option<String>) -> Self {
+        AnchorRecord { chain, tx_hash, block_number, root, path }
+    }
+
+    pub fn chain(&self) -> Option<&str> {
+        self.chain.as_deref()
+    }
+
+    pub fn tx_hash(&self) -> &str {
+        &self.tx_hash
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    pub fn path(&self) -> &MerkleProof {
+        &self.path
+    }
+
+    /// Block `proof` is anchored at, given the chain's view of the transaction
+    pub fn check(&self, proof: &ProofData, tx: Option<&AnchorTx>) -> Result<u64, &'static str> {
+        let tx = tx.ok_or("Anchor transaction was not found on chain")?;
+        if tx.block_number != self.block_number {
+            return Err("Anchor transaction is in another block than the record claims");
+        }
+        if !hash::commitments_equal(&tx.root, &self.root) {
+            return Err("Anchor transaction committed another root than the record claims");
+        }
+        let leaf = proof.leaf_hash(self.path.algorithm()).map_err(|_| "Anchor path hash is not supported")?;
+        if !self.path.verifies(&leaf, &self.root) {
+            return Err("Proof is not in the anchored root");
+        }
+        Ok(tx.block_number)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<AnchorRecord, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Record the `anchor` check and, when it passed, the report's `anchored_block`
+pub fn record_anchor(report: &mut VerificationReport, anchored: Result<u64, &str>, started: Stopwatch) {
+    report.record(Check::Anchor, anchored.is_ok(), anchored.err().unwrap_or_default(), started);
+    report.set_anchored_block(anchored.ok());
+}
+
+impl Verifier {
+    /// Verify a proof and its anchor, with the transaction already looked up (`None`: not on chain)
+    pub fn anchor_report(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[u8],
+        anchor: &AnchorRecord,
+        tx: Option<&AnchorTx>,
+    ) -> VerificationReport {
+        let mut report = self.verify_report(proof_data, input, output);
+        let started = report.start(Check::Anchor);
+        record_anchor(&mut report, anchor.check(proof_data, tx), started);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hash::HashAlgorithm;
+    use crate::inclusion::merkle_root;
+    use crate::VerificationError;
+
+    #[test]
+    fn test_anchor() {
+        let verifier = Verifier::new("0xmodel".to_string());
+        let alg = HashAlgorithm::Sha3_256;
+        let proofs: Vec<ProofData> = (0..5)
+            .map(|i| {
+                let mut proof = fixtures::proof_for("0xmodel", b"in", b"out", alg);
+                proof.proof_hash = format!("0xproof{}", i);
+                proof
+            })
+            .collect();
+        let leaves: Vec<String> = proofs.iter().map(|p| p.leaf_hash(alg).unwrap()).collect();
+        let root = merkle_root(&leaves, alg).unwrap();
+        let path = MerkleProof::create(&leaves, 2, alg).unwrap();
+        let anchor = AnchorRecord::new("0xtx".to_string(), 19_000_000, root.clone(), path, None);
+        let tx = AnchorTx { block_number: 19_000_000, root: format!("sha3:{}", root) };
+
+        let report = verifier.anchor_report(&proofs[2], b"in", b"out", &anchor, Some(&tx));
+        assert!(report.passed());
+        assert_eq!((report.anchored_block(), report.check_passed(Check::Anchor)), (Some(19_000_000), true));
+        assert!(report.to_json().contains(r#""anchored_block":19000000"#));
+
+        let failure = |proof: &ProofData, tx: Option<&AnchorTx>| {
+            let report = verifier.anchor_report(proof, b"in", b"out", &anchor, tx);
+            assert_eq!((report.error(), report.anchored_block()), (Some(VerificationError::AnchorInvalid), None));
+            report.failure_reasons()[0].clone()
+        };
+        assert_eq!(failure(&proofs[3], Some(&tx)), "Proof is not in the anchored root");
+        assert_eq!(failure(&proofs[2], None), "Anchor transaction was not found on chain");
+        let reorged = AnchorTx { block_number: 19_000_001, ..tx.clone() };
+        let error = failure(&proofs[2], Some(&reorged));
+        assert_eq!(error, "Anchor transaction is in another block than the record claims");
+        let other = AnchorTx { root: leaves[0].clone(), ..tx };
+        let error = failure(&proofs[2], Some(&other));
+        assert_eq!(error, "Anchor transaction committed another root than the record claims");
+
+        assert_eq!(AnchorRecord::from_json(&anchor.to_json()).unwrap(), anchor);
+    }
+}
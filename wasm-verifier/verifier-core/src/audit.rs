@@ -0,0 +1,226 @@
+// Audit log
+// Append-only record of verification attempts, each entry hash-chained to the one before, exportable as JSONL
+
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{hash_bytes_tagged, HashAlgorithm};
+use crate::report::VerificationReport;
+use crate::{jcs, ProofData, Verifier};
+
+/// `prev_hash` of the first entry
+pub const AUDIT_GENESIS: &str = "sha3:0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One verification attempt
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    /// Position in the log, from 0
+    seq: u64,
+    /// When it was verified (Unix ms, by the verifier's clock)
+    timestamp: u64,
+    proof_id: String,
+    model_hash: String,
+    passed: bool,
+    /// `VerificationError` name, if it failed
+    error: Option<String>,
+    /// `hash` of the entry before, `AUDIT_GENESIS` for the first
+    prev_hash: String,
+    /// Tagged SHA3-256 of the RFC 8785 canonical JSON of the other fields
+    hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> String {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("hash");
+        }
+        hash_bytes_tagged(jcs::canonicalize(&value).as_bytes(), HashAlgorithm::Sha3_256).unwrap_or_default()
+    }
+
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn proof_id(&self) -> &str {
+        &self.proof_id
+    }
+
+    pub fn model_hash(&self) -> &str {
+        &self.model_hash
+    }
+
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn prev_hash(&self) -> &str {
+        &self.prev_hash
+    }
+
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+}
+
+/// Hash-chained verification history
+///
+/// Each entry commits to the one before it, so changing or dropping an
+/// entry breaks every later hash. Dropping entries from the end can only
+/// be caught against a `head` kept elsewhere.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditLog {
+    entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Append `report`'s outcome for `proof_data`, verified at `now` (Unix ms)
+    pub fn append(&mut self, proof_data: &ProofData, report: &VerificationReport, now: u64) {
+        let mut entry = AuditEntry {
+            seq: self.entries.len() as u64,
+            timestamp: now,
+            proof_id: proof_data.proof_id(),
+            model_hash: proof_data.model_hash.clone(),
+            passed: report.passed(),
+            error: report.error().map(|e| e.name().to_string()),
+            prev_hash: self.head(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hash of the last entry, which commits to the whole log (`AUDIT_GENESIS` while empty)
+    pub fn head(&self) -> String {
+        self.entries.last().map_or_else(|| AUDIT_GENESIS.to_string(), |entry| entry.hash.clone())
+    }
+
+    /// One JSON entry per line, oldest first
+    pub fn to_jsonl(&self) -> String {
+        self.entries.iter().map(|entry| serde_json::to_string(entry).unwrap_or_default() + "\n").collect()
+    }
+
+    /// Read a `to_jsonl` export back, checking its chain; the reason names the first bad entry
+    pub fn try_from_jsonl(jsonl: &str) -> Result<AuditLog, String> {
+        let entries = jsonl
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(line, json)| serde_json::from_str(json).map_err(|e| format!("Line {}: {}", line + 1, e)))
+            .collect::<Result<Vec<AuditEntry>, String>>()?;
+        let log = AuditLog { entries };
+        log.try_verify()?;
+        Ok(log)
+    }
+
+    /// Recompute every hash and link, failing at the first broken one
+    pub fn try_verify(&self) -> Result<(), String> {
+        let mut prev_hash = AUDIT_GENESIS.to_string();
+        for (seq, entry) in self.entries.iter().enumerate() {
+            if entry.seq != seq as u64 {
+                return Err(format!("Entry {} is numbered {}: entries were removed or reordered", seq, entry.seq));
+            }
+            if entry.prev_hash != prev_hash {
+                return Err(format!("Entry {} doesn't chain to the entry before it", seq));
+            }
+            if entry.hash != entry.compute_hash() {
+                return Err(format!("Entry {} was modified: its hash doesn't match its contents", seq));
+            }
+            prev_hash = entry.hash.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Where a verifier records its attempts, if anywhere
+#[derive(Debug, Default)]
+pub struct AuditTrail(Mutex<Option<AuditLog>>);
+
+impl AuditTrail {
+    fn lock(&self) -> MutexGuard<'_, Option<AuditLog>> {
+        self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Verifier {
+    /// Record a finished verification in the audit log, if one is set
+    pub fn audit(&self, proof_data: &ProofData, report: &VerificationReport) {
+        if let Some(log) = self.audit_trail.lock().as_mut() {
+            log.append(proof_data, report, self.now_ms());
+        }
+    }
+
+    /// Log every verification from now on to `log` (`None` stops logging)
+    ///
+    /// Pass a log read back with `AuditLog::try_from_jsonl` to continue its chain.
+    pub fn set_audit_log(&self, log: Option<AuditLog>) {
+        *self.audit_trail.lock() = log;
+    }
+
+    /// Copy of the audit log so far (`None` unless one was set)
+    pub fn audit_log(&self) -> Option<AuditLog> {
+        self.audit_trail.lock().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_audit_log() {
+        let verifier = Verifier::new("0xmodel".to_string());
+        verifier.set_audit_log(Some(AuditLog::default()));
+        let alg = HashAlgorithm::default();
+        let proof = fixtures::proof_for("0xmodel", b"in", b"out", alg);
+        verifier.verify_report(&proof, b"in", b"out");
+        verifier.verify_report(&proof, b"in", b"tampered");
+        verifier.verify_report(&proof, b"in", b"out");
+
+        let log = verifier.audit_log().unwrap();
+        let outcomes: Vec<_> = log.entries().iter().map(|e| (e.seq(), e.passed(), e.error())).collect();
+        assert_eq!(outcomes[1], (1, false, Some("OutputHashMismatch")));
+        assert_eq!(log.entries()[0].prev_hash(), AUDIT_GENESIS);
+        assert_eq!(log.entries()[2].prev_hash(), log.entries()[1].hash());
+        assert_eq!(log.entries()[0].proof_id(), proof.proof_id());
+
+        let jsonl = log.to_jsonl();
+        assert_eq!(AuditLog::try_from_jsonl(&jsonl), Ok(log.clone()));
+        let altered = jsonl.replacen(r#""passed":false"#, r#""passed":true"#, 1);
+        let error = AuditLog::try_from_jsonl(&altered).unwrap_err();
+        assert_eq!(error, "Entry 1 was modified: its hash doesn't match its contents");
+        let lines: Vec<&str> = jsonl.lines().collect();
+        let dropped = format!("{}\n{}\n", lines[0], lines[2]);
+        assert!(AuditLog::try_from_jsonl(&dropped).unwrap_err().starts_with("Entry 1 is numbered 2"));
+        assert!(AuditLog::try_from_jsonl("{").unwrap_err().starts_with("Line 1:"));
+
+        // A restored log keeps its chain going
+        verifier.set_audit_log(Some(AuditLog::try_from_jsonl(&jsonl).unwrap()));
+        verifier.verify_report(&proof, b"in", b"out");
+        let continued = verifier.audit_log().unwrap();
+        assert_eq!((continued.len(), continued.try_verify()), (4, Ok(())));
+        assert_eq!(continued.entries()[3].prev_hash(), log.head());
+    }
+}
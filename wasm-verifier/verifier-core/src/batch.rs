@@ -0,0 +1,71 @@
+// Batch verification
+// Per-proof reports of a batch with their aggregate counts
+
+use serde::{Deserialize, Serialize};
+
+use crate::report::VerificationReport;
+
+/// Per-proof results plus aggregate counts for a batch
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BatchReport {
+    reports: Vec<VerificationReport>,
+    passed: u32,
+    failed: u32,
+    total_ms: f64,
+}
+
+impl BatchReport {
+    /// Add the next proof's report
+    pub fn push(&mut self, report: VerificationReport) {
+        if report.passed() {
+            self.passed += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.total_ms += report.total_ms();
+        self.reports.push(report);
+    }
+
+    /// Per-proof reports, in input order
+    pub fn reports(&self) -> &[VerificationReport] {
+        &self.reports
+    }
+
+    /// Per-proof pass/fail flags, in input order
+    pub fn results(&self) -> Vec<u8> {
+        self.reports.iter().map(|r| r.passed() as u8).collect()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.reports.len() as u32
+    }
+
+    pub fn passed(&self) -> u32 {
+        self.passed
+    }
+
+    pub fn failed(&self) -> u32 {
+        self.failed
+    }
+
+    /// True when the batch is non-empty and every proof passed
+    pub fn all_passed(&self) -> bool {
+        !self.reports.is_empty() && self.failed == 0
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.total_ms
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl FromIterator<VerificationReport> for BatchReport {
+    fn from_iter<T: IntoIterator<Item = VerificationReport>>(reports: T) -> BatchReport {
+        let mut batch = BatchReport::default();
+        reports.into_iter().for_each(|report| batch.push(report));
+        batch
+    }
+}
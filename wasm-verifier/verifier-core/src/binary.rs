@@ -0,0 +1,304 @@
+// Compact binary proof encoding
+// Versioned wire format: magic, version, fixed-width digests, then tagged optional fields
+
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::tensor::TensorSpec;
+use crate::{hex, ProofData};
+
+/// `ZKPF`, first four bytes of every binary proof
+pub const BINARY_MAGIC: [u8; 4] = *b"ZKPF";
+
+/// Current binary format version; bump on any incompatible layout change
+pub const BINARY_VERSION: u8 = 1;
+
+const FLAG_VERIFIED: u8 = 0x01;
+
+/// Value encodings. Anything not exactly reproducible by a compact form is
+/// stored as text, so decoding always restores the original strings.
+const TEXT: u8 = 0;
+/// `0x` + 64 lowercase hex chars, stored as 32 raw bytes
+const DIGEST: u8 = 1;
+/// `<prefix>:0x` + 64 lowercase hex chars, stored as algorithm id + 32 raw bytes
+const TAGGED_DIGEST: u8 = 2;
+/// `0x` + lowercase hex of any length
+const HEX: u8 = 3;
+/// Lowercase hex without prefix (signatures, public keys)
+const BARE_HEX: u8 = 4;
+
+/// Ids of the optional fields following the fixed header
+const FIELD_HASH_ALG: u8 = 1;
+const FIELD_NONCE: u8 = 2;
+const FIELD_SIGNATURE_ALG: u8 = 3;
+const FIELD_SIGNATURE: u8 = 4;
+const FIELD_SIGNER_PUBKEY: u8 = 5;
+const FIELD_KID: u8 = 6;
+const FIELD_PROVER_DID: u8 = 7;
+const FIELD_CERT: u8 = 8;
+const FIELD_ATTESTOR_PUBKEY: u8 = 9;
+const FIELD_AGGREGATE_SIGNATURE: u8 = 10;
+const FIELD_QUANTIZATION_HASH: u8 = 11;
+/// Tensor specs are stored in their compact `dtype[dims]@layout` text form
+const FIELD_INPUT_SPEC: u8 = 12;
+const FIELD_OUTPUT_SPEC: u8 = 13;
+/// Named commitments carry two values: the name, then the commitment
+const FIELD_NAMED_INPUT: u8 = 14;
+const FIELD_NAMED_OUTPUT: u8 = 15;
+const FIELD_PREPROCESSING_HASH: u8 = 16;
+const FIELD_PARTIAL_MODEL_HASH: u8 = 17;
+const FIELD_PARTIAL_LAYER: u8 = 18;
+const FIELD_MODEL_VERSION: u8 = 19;
+const FIELD_ARCHITECTURE_HASH: u8 = 20;
+const FIELD_WEIGHTS_HASH: u8 = 21;
+const FIELD_METADATA_HASH: u8 = 22;
+
+/// Algorithm ids used by `TAGGED_DIGEST` (index + 1)
+const ALGORITHMS: [HashAlgorithm; 4] =
+    [HashAlgorithm::Sha3_256, HashAlgorithm::Keccak256, HashAlgorithm::Sha256, HashAlgorithm::Blake3];
+
+fn malformed<T>() -> Result<T, VerificationError> {
+    Err(VerificationError::MalformedProof)
+}
+
+fn parse_spec(compact: &str) -> Result<TensorSpec, VerificationError> {
+    TensorSpec::from_compact(compact).ok_or(VerificationError::MalformedProof)
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn varint(&mut self, mut n: u64) {
+        while n >= 0x80 {
+            self.0.push((n as u8) | 0x80);
+            n >>= 7;
+        }
+        self.0.push(n as u8);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn value(&mut self, s: &str) {
+        let lower_hex = |h: &str| if h.bytes().any(|b| b.is_ascii_uppercase()) { None } else { hex::decode(h) };
+
+        if let Some(digest) = s.strip_prefix("0x").and_then(lower_hex) {
+            if digest.len() == 32 {
+                self.0.push(DIGEST);
+                self.0.extend_from_slice(&digest);
+            } else {
+                self.0.push(HEX);
+                self.bytes(&digest);
+            }
+            return;
+        }
+        if let Some((prefix, digest)) = s.split_once(":0x") {
+            let id = ALGORITHMS.iter().position(|alg| alg.prefix() == prefix);
+            if let (Some(id), Some(digest)) = (id, lower_hex(digest).filter(|d| d.len() == 32)) {
+                self.0.push(TAGGED_DIGEST);
+                self.0.push(id as u8 + 1);
+                self.0.extend_from_slice(&digest);
+                return;
+            }
+        }
+        match lower_hex(s).filter(|bytes| !bytes.is_empty()) {
+            Some(bytes) => {
+                self.0.push(BARE_HEX);
+                self.bytes(&bytes);
+            }
+            None => {
+                self.0.push(TEXT);
+                self.bytes(s.as_bytes());
+            }
+        }
+    }
+
+    fn field(&mut self, id: u8, value: Option<&String>) {
+        if let Some(value) = value {
+            self.0.push(id);
+            self.value(value);
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], VerificationError> {
+        if self.0.len() < n {
+            return malformed();
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn byte(&mut self) -> Result<u8, VerificationError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, VerificationError> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            n |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        malformed()
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], VerificationError> {
+        let len = usize::try_from(self.varint()?).map_err(|_| VerificationError::MalformedProof)?;
+        self.take(len)
+    }
+
+    fn value(&mut self) -> Result<String, VerificationError> {
+        Ok(match self.byte()? {
+            TEXT => String::from_utf8(self.bytes()?.to_vec()).map_err(|_| VerificationError::MalformedProof)?,
+            DIGEST => format!("0x{}", hex::encode(self.take(32)?)),
+            TAGGED_DIGEST => {
+                let alg = ALGORITHMS.get(usize::from(self.byte()?).wrapping_sub(1)).ok_or(VerificationError::MalformedProof)?;
+                format!("{}:0x{}", alg.prefix(), hex::encode(self.take(32)?))
+            }
+            HEX => format!("0x{}", hex::encode(self.bytes()?)),
+            BARE_HEX => hex::encode(self.bytes()?),
+            _ => return malformed(),
+        })
+    }
+}
+
+impl ProofData {
+    /// Serialize to the compact binary format (`ZKPF`, version 1)
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut w = Writer(BINARY_MAGIC.to_vec());
+        w.0.push(BINARY_VERSION);
+        w.0.push(if self.verified { FLAG_VERIFIED } else { 0 });
+        w.0.extend_from_slice(&self.timestamp.to_be_bytes());
+        for commitment in [&self.model_hash, &self.proof_hash, &self.input_hash, &self.output_hash] {
+            w.value(commitment);
+        }
+
+        w.field(FIELD_HASH_ALG, self.hash_alg.as_ref());
+        w.field(FIELD_NONCE, self.nonce.as_ref());
+        w.field(FIELD_SIGNATURE_ALG, self.signature_alg.as_ref());
+        w.field(FIELD_SIGNATURE, self.signature.as_ref());
+        w.field(FIELD_SIGNER_PUBKEY, self.signer_pubkey.as_ref());
+        w.field(FIELD_KID, self.kid.as_ref());
+        w.field(FIELD_PROVER_DID, self.prover_did.as_ref());
+        for cert in &self.cert_chain {
+            w.field(FIELD_CERT, Some(cert));
+        }
+        for pubkey in &self.attestor_pubkeys {
+            w.field(FIELD_ATTESTOR_PUBKEY, Some(pubkey));
+        }
+        w.field(FIELD_AGGREGATE_SIGNATURE, self.aggregate_signature.as_ref());
+        w.field(FIELD_QUANTIZATION_HASH, self.quantization_hash.as_ref());
+        w.field(FIELD_PREPROCESSING_HASH, self.preprocessing_hash.as_ref());
+        w.field(FIELD_MODEL_VERSION, self.model_version.as_ref());
+        w.field(FIELD_ARCHITECTURE_HASH, self.architecture_hash.as_ref());
+        w.field(FIELD_WEIGHTS_HASH, self.weights_hash.as_ref());
+        w.field(FIELD_METADATA_HASH, self.metadata_hash.as_ref());
+        w.field(FIELD_PARTIAL_MODEL_HASH, self.partial_model_hash.as_ref());
+        for layer in &self.partial_layers {
+            w.field(FIELD_PARTIAL_LAYER, Some(layer));
+        }
+        w.field(FIELD_INPUT_SPEC, self.input_spec.as_ref().map(TensorSpec::to_compact).as_ref());
+        w.field(FIELD_OUTPUT_SPEC, self.output_spec.as_ref().map(TensorSpec::to_compact).as_ref());
+        for (id, named) in [(FIELD_NAMED_INPUT, &self.inputs), (FIELD_NAMED_OUTPUT, &self.outputs)] {
+            for (name, commitment) in named {
+                w.field(id, Some(name));
+                w.value(commitment);
+            }
+        }
+        w.0
+    }
+
+    /// Deserialize from the compact binary format
+    ///
+    /// Fails with `MalformedProof` on a bad magic, an unknown version or
+    /// field, or truncated input.
+    pub fn from_binary(bytes: &[u8]) -> Result<ProofData, VerificationError> {
+        let mut r = Reader(bytes);
+        if r.take(4)? != BINARY_MAGIC {
+            return malformed();
+        }
+        if r.byte()? != BINARY_VERSION {
+            return malformed();
+        }
+        let flags = r.byte()?;
+        if flags & !FLAG_VERIFIED != 0 {
+            return malformed();
+        }
+        let timestamp = u64::from_be_bytes(r.take(8)?.try_into().expect("took 8 bytes"));
+
+        let mut proof = ProofData::new(r.value()?, r.value()?, r.value()?, r.value()?, timestamp, flags & FLAG_VERIFIED != 0);
+        while !r.0.is_empty() {
+            let id = r.byte()?;
+            let value = r.value()?;
+            match id {
+                FIELD_HASH_ALG => proof.hash_alg = Some(value),
+                FIELD_NONCE => proof.nonce = Some(value),
+                FIELD_SIGNATURE_ALG => proof.signature_alg = Some(value),
+                FIELD_SIGNATURE => proof.signature = Some(value),
+                FIELD_SIGNER_PUBKEY => proof.signer_pubkey = Some(value),
+                FIELD_KID => proof.kid = Some(value),
+                FIELD_PROVER_DID => proof.prover_did = Some(value),
+                FIELD_CERT => proof.cert_chain.push(value),
+                FIELD_ATTESTOR_PUBKEY => proof.attestor_pubkeys.push(value),
+                FIELD_AGGREGATE_SIGNATURE => proof.aggregate_signature = Some(value),
+                FIELD_QUANTIZATION_HASH => proof.quantization_hash = Some(value),
+                FIELD_PREPROCESSING_HASH => proof.preprocessing_hash = Some(value),
+                FIELD_MODEL_VERSION => proof.model_version = Some(value),
+                FIELD_ARCHITECTURE_HASH => proof.architecture_hash = Some(value),
+                FIELD_WEIGHTS_HASH => proof.weights_hash = Some(value),
+                FIELD_METADATA_HASH => proof.metadata_hash = Some(value),
+                FIELD_PARTIAL_MODEL_HASH => proof.partial_model_hash = Some(value),
+                FIELD_PARTIAL_LAYER => proof.partial_layers.push(value),
+                FIELD_NAMED_INPUT => {
+                    proof.inputs.insert(value, r.value()?);
+                }
+                FIELD_NAMED_OUTPUT => {
+                    proof.outputs.insert(value, r.value()?);
+                }
+                FIELD_INPUT_SPEC => proof.input_spec = Some(parse_spec(&value)?),
+                FIELD_OUTPUT_SPEC => proof.output_spec = Some(parse_spec(&value)?),
+                _ => return malformed(),
+            }
+        }
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{hash_bytes, hash_bytes_tagged};
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let mut proof = ProofData::new(
+            "0x1234567890abcdef".to_string(),
+            "0xproof".to_string(),
+            hash_bytes(b"in", HashAlgorithm::default()).unwrap(),
+            hash_bytes_tagged(b"out", HashAlgorithm::Keccak256).unwrap(),
+            1_700_000_000_000,
+            true,
+        );
+        proof.signature = Some("ab".repeat(64));
+        proof.attestor_pubkeys = vec!["0xAA".to_string(), "00".to_string()];
+        proof.inputs.insert("attention_mask".to_string(), hash_bytes(b"mask", HashAlgorithm::default()).unwrap());
+
+        let binary = proof.to_binary();
+        let decoded = ProofData::from_binary(&binary).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), serde_json::to_string(&proof).unwrap());
+        assert!(binary.len() * 2 < serde_json::to_vec(&proof).unwrap().len());
+
+        let mut future = binary.clone();
+        future[4] = BINARY_VERSION + 1;
+        assert!(ProofData::from_binary(&future).is_err());
+        assert!(ProofData::from_binary(&binary[..binary.len() - 1]).is_err());
+    }
+}
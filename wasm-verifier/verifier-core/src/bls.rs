@@ -0,0 +1,133 @@
+// BLS aggregate signatures
+// Multi-attestor proofs: every attestor signs `signing_bytes()`, signatures are
+// aggregated into one BLS12-381 signature checked with a single pairing equation
+
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective};
+
+use crate::error::VerificationError;
+use crate::signature::Failure;
+use crate::{hash, hex, ProofData, Verifier};
+
+/// Ciphersuite of the IETF BLS draft, min-pk variant with proof of possession
+pub const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Verify an aggregate signature by `pubkeys` (48-byte compressed G1, hex) over `message`
+///
+/// All keys must be distinct and in `trusted`: aggregation is only safe
+/// against rogue-key attacks for keys whose possession was proven out of
+/// band, so an empty trust list rejects every proof. At least `min_attestors`
+/// keys must participate.
+pub fn verify_aggregate(
+    pubkeys: &[String],
+    signature: &str,
+    message: &[u8],
+    trusted: &[String],
+    min_attestors: u32,
+) -> Result<(), Failure> {
+    if (pubkeys.len() as u64) < u64::from(min_attestors.max(1)) {
+        return Err((VerificationError::InsufficientAttestors, "Too few attestors signed the proof"));
+    }
+
+    let mut aggregate_key = G1Projective::identity();
+    for (i, key) in pubkeys.iter().enumerate() {
+        if pubkeys[..i].iter().any(|seen| hash::digests_equal(seen, key)) {
+            return Err((VerificationError::AggregateSignatureInvalid, "Attestor public keys are not distinct"));
+        }
+        if !trusted.iter().any(|t| hash::digests_equal(t, key)) {
+            return Err((VerificationError::UntrustedSigner, "Proof lists an untrusted attestor"));
+        }
+        aggregate_key += decode_g1(key)?;
+    }
+
+    let signature = decode_g2(signature)?;
+    let message_point = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve([message], BLS_DST);
+
+    // e(pk_1 + ... + pk_n, H(m)) == e(g1, sig)
+    let lhs = pairing(&G1Affine::from(aggregate_key), &G2Affine::from(message_point));
+    let rhs = pairing(&G1Affine::generator(), &signature);
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err((VerificationError::AggregateSignatureInvalid, "Aggregate signature does not verify"))
+    }
+}
+
+fn decode_g1(key: &str) -> Result<G1Affine, Failure> {
+    let bad = (VerificationError::AggregateSignatureInvalid, "Attestor key is not a 48-byte compressed G1 point");
+    let bytes: [u8; 48] = hex::decode(key).and_then(|b| b.try_into().ok()).ok_or(bad)?;
+    let point = Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)).ok_or(bad)?;
+    if bool::from(point.is_identity()) {
+        return Err(bad);
+    }
+    Ok(point)
+}
+
+fn decode_g2(signature: &str) -> Result<G2Affine, Failure> {
+    let bad = (VerificationError::AggregateSignatureInvalid, "Aggregate signature is not a 96-byte compressed G2 point");
+    let bytes: [u8; 96] = hex::decode(signature).and_then(|b| b.try_into().ok()).ok_or(bad)?;
+    Option::<G2Affine>::from(G2Affine::from_compressed(&bytes)).ok_or(bad)
+}
+
+impl Verifier {
+    /// Trust a BLS12-381 attestor public key (48-byte compressed G1, hex)
+    ///
+    /// Only add keys whose proof of possession you have checked.
+    pub fn add_trusted_attestor(&mut self, pubkey: String) {
+        self.trusted_attestors.push(pubkey);
+    }
+
+    /// Verify the attestors' aggregate signature against the trusted set and quorum
+    pub fn check_aggregate_signature(&self, proof_data: &ProofData) -> Result<(), Failure> {
+        let Some(sig) = &proof_data.aggregate_signature else {
+            return Err((VerificationError::InsufficientAttestors, "Proof has no aggregate signature"));
+        };
+        verify_aggregate(
+            &proof_data.attestor_pubkeys,
+            sig,
+            &proof_data.signing_bytes(),
+            &self.trusted_attestors,
+            self.config.min_attestors(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Scalar;
+
+    fn keypair(seed: u64) -> (Scalar, String) {
+        let secret = Scalar::from(seed);
+        let public = G1Affine::from(G1Affine::generator() * secret);
+        (secret, hex::encode(&public.to_compressed()))
+    }
+
+    fn sign(secrets: &[Scalar], message: &[u8]) -> String {
+        let h = <G2Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve([message], BLS_DST);
+        let aggregate: G2Projective = secrets.iter().map(|s| h * s).sum();
+        hex::encode(&G2Affine::from(aggregate).to_compressed())
+    }
+
+    #[test]
+    fn test_verify_aggregate() {
+        let nodes: Vec<_> = (11..14).map(keypair).collect();
+        let secrets: Vec<Scalar> = nodes.iter().map(|(s, _)| *s).collect();
+        let pubkeys: Vec<String> = nodes.iter().map(|(_, p)| p.clone()).collect();
+        let signature = sign(&secrets, b"proof");
+
+        assert!(verify_aggregate(&pubkeys, &signature, b"proof", &pubkeys, 3).is_ok());
+        assert_eq!(
+            verify_aggregate(&pubkeys, &signature, b"other", &pubkeys, 3).unwrap_err().0,
+            VerificationError::AggregateSignatureInvalid
+        );
+        assert_eq!(
+            verify_aggregate(&pubkeys[..2], &signature, b"proof", &pubkeys, 3).unwrap_err().0,
+            VerificationError::InsufficientAttestors
+        );
+        assert_eq!(
+            verify_aggregate(&pubkeys, &signature, b"proof", &pubkeys[..2], 3).unwrap_err().0,
+            VerificationError::UntrustedSigner
+        );
+    }
+}
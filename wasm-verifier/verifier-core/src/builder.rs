@@ -0,0 +1,153 @@
+// Verifier builder
+// Fluent construction of a `Verifier`, so new options don't change any constructor signature
+
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::{Verifier, VerifierConfig};
+
+/// Step-by-step `Verifier` setup
+///
+/// ```ignore
+/// let verifier = VerifierBuilder::new()
+///     .model_hash(model_hash)
+///     .max_age_ms(10 * 60 * 1000)
+///     .require_signature(true)
+///     .trusted_signer(prover_key)
+///     .try_build()?;
+/// ```
+///
+/// Every method consumes the builder and returns it. Options not set keep
+/// their `VerifierConfig` defaults.
+#[derive(Clone, Default)]
+pub struct VerifierBuilder {
+    model_hash: Option<String>,
+    config: VerifierConfig,
+    #[cfg(feature = "signatures")]
+    trusted_signers: Vec<String>,
+    #[cfg(feature = "signatures")]
+    trusted_attestors: Vec<String>,
+}
+
+impl VerifierBuilder {
+    pub fn new() -> VerifierBuilder {
+        VerifierBuilder::default()
+    }
+
+    /// Model the proofs must be bound to (required)
+    pub fn model_hash(mut self, model_hash: String) -> VerifierBuilder {
+        self.model_hash = Some(model_hash);
+        self
+    }
+
+    /// Start from an existing policy; later options override its fields
+    pub fn config(mut self, config: &VerifierConfig) -> VerifierBuilder {
+        self.config = *config;
+        self
+    }
+
+    pub fn max_age_ms(mut self, max_age_ms: u64) -> VerifierBuilder {
+        self.config.set_max_age_ms(max_age_ms);
+        self
+    }
+
+    pub fn check_freshness(mut self, check_freshness: bool) -> VerifierBuilder {
+        self.config.set_check_freshness(check_freshness);
+        self
+    }
+
+    pub fn clock_skew_ms(mut self, clock_skew_ms: u64) -> VerifierBuilder {
+        self.config.set_clock_skew_ms(clock_skew_ms);
+        self
+    }
+
+    pub fn hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> VerifierBuilder {
+        self.config.set_hash_algorithm(hash_algorithm);
+        self
+    }
+
+    pub fn require_nonce(mut self, require_nonce: bool) -> VerifierBuilder {
+        self.config.set_require_nonce(require_nonce);
+        self
+    }
+
+    pub fn require_signature(mut self, require_signature: bool) -> VerifierBuilder {
+        self.config.set_require_signature(require_signature);
+        self
+    }
+
+    pub fn min_attestors(mut self, min_attestors: u32) -> VerifierBuilder {
+        self.config.set_min_attestors(min_attestors);
+        self
+    }
+
+    pub fn max_decompressed_bytes(mut self, max_decompressed_bytes: u64) -> VerifierBuilder {
+        self.config.set_max_decompressed_bytes(max_decompressed_bytes);
+        self
+    }
+
+    pub fn max_proof_bytes(mut self, max_proof_bytes: u64) -> VerifierBuilder {
+        self.config.set_max_proof_bytes(max_proof_bytes);
+        self
+    }
+
+    pub fn max_input_bytes(mut self, max_input_bytes: u64) -> VerifierBuilder {
+        self.config.set_max_input_bytes(max_input_bytes);
+        self
+    }
+
+    /// Trust an Ed25519 key or Ethereum address (see `Verifier::add_trusted_signer`)
+    #[cfg(feature = "signatures")]
+    pub fn trusted_signer(mut self, pubkey: String) -> VerifierBuilder {
+        self.trusted_signers.push(pubkey);
+        self
+    }
+
+    /// Trust a BLS attestor key (see `Verifier::add_trusted_attestor`)
+    #[cfg(feature = "signatures")]
+    pub fn trusted_attestor(mut self, pubkey: String) -> VerifierBuilder {
+        self.trusted_attestors.push(pubkey);
+        self
+    }
+
+    /// Create the verifier
+    ///
+    /// Fails with `InvalidArgument` if no model hash was set.
+    pub fn try_build(self) -> Result<Verifier, VerificationError> {
+        let model_hash = self.model_hash.ok_or(VerificationError::InvalidArgument)?;
+        Ok(Verifier {
+            #[cfg(feature = "signatures")]
+            trusted_signers: self.trusted_signers,
+            #[cfg(feature = "signatures")]
+            trusted_attestors: self.trusted_attestors,
+            ..Verifier::with_config(model_hash, &self.config)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let verifier = VerifierBuilder::new()
+            .model_hash("0xmodel".to_string())
+            .max_age_ms(1_000)
+            .require_signature(true)
+            .try_build()
+            .unwrap();
+        assert_eq!(verifier.config().max_age_ms(), 1_000);
+        assert!(verifier.config().require_signature());
+        #[cfg(feature = "signatures")]
+        {
+            let signed = VerifierBuilder::new().model_hash("0xmodel".to_string()).trusted_signer("0xsigner".to_string());
+            assert_eq!(signed.try_build().unwrap().trusted_signers, ["0xsigner"]);
+        }
+        assert!(verifier.config().check_freshness());
+
+        let archival = VerifierBuilder::new().config(&VerifierConfig::archival()).model_hash("0xmodel".to_string());
+        assert!(!archival.try_build().unwrap().config().check_freshness());
+
+        assert_eq!(VerifierBuilder::new().max_age_ms(1).try_build().err(), Some(VerificationError::InvalidArgument));
+    }
+}
@@ -0,0 +1,116 @@
+// CBOR encoding for ProofData
+// Compact binary form for constrained channels; hex strings travel as raw bytes
+
+use ciborium::Value;
+use serde_json::Value as Json;
+
+use crate::error::VerificationError;
+use crate::{hex, ProofData};
+
+/// RFC 8949 tag "expected conversion to base16": marks hex without a `0x` prefix
+const BASE16_TAG: u64 = 23;
+
+/// Map ProofData JSON to CBOR, turning lowercase hex strings into byte strings
+///
+/// `0x`-prefixed hex becomes a plain byte string and bare hex a tag-23 byte
+/// string, so decoding restores the exact original text. Anything else
+/// (mixed case, tagged commitments, PEM) stays a text string.
+fn to_value(json: Json) -> Value {
+    match json {
+        Json::Null => Value::Null,
+        Json::Bool(b) => Value::Bool(b),
+        Json::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(u), _) => Value::from(u),
+            (None, Some(i)) => Value::from(i),
+            _ => Value::Float(n.as_f64().unwrap_or_default()),
+        },
+        Json::String(s) => match s.strip_prefix("0x").map(lowercase_hex) {
+            Some(Some(bytes)) => Value::Bytes(bytes),
+            _ => match lowercase_hex(&s) {
+                Some(bytes) => Value::Tag(BASE16_TAG, Box::new(Value::Bytes(bytes))),
+                None => Value::Text(s),
+            },
+        },
+        Json::Array(items) => Value::Array(items.into_iter().map(to_value).collect()),
+        Json::Object(fields) => Value::Map(fields.into_iter().map(|(k, v)| (Value::Text(k), to_value(v))).collect()),
+    }
+}
+
+fn from_value(value: Value) -> Option<Json> {
+    Some(match value {
+        Value::Null => Json::Null,
+        Value::Bool(b) => Json::Bool(b),
+        Value::Integer(i) => match u64::try_from(i) {
+            Ok(u) => Json::from(u),
+            Err(_) => Json::from(i64::try_from(i).ok()?),
+        },
+        Value::Float(f) => Json::from(f),
+        Value::Text(s) => Json::String(s),
+        Value::Bytes(bytes) => Json::String(format!("0x{}", hex::encode(&bytes))),
+        Value::Tag(BASE16_TAG, inner) => match *inner {
+            Value::Bytes(bytes) => Json::String(hex::encode(&bytes)),
+            _ => return None,
+        },
+        Value::Array(items) => Json::Array(items.into_iter().map(from_value).collect::<Option<_>>()?),
+        Value::Map(entries) => Json::Object(
+            entries
+                .into_iter()
+                .map(|(k, v)| Some((k.into_text().ok()?, from_value(v)?)))
+                .collect::<Option<_>>()?,
+        ),
+        _ => return None,
+    })
+}
+
+/// Even-length, non-empty, lowercase hex
+fn lowercase_hex(s: &str) -> Option<Vec<u8>> {
+    if s.is_empty() || s.bytes().any(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    hex::decode(s)
+}
+
+impl ProofData {
+    /// Decode CBOR produced by `to_cbor`
+    pub fn decode_cbor(bytes: &[u8]) -> Result<ProofData, VerificationError> {
+        let value: Value = ciborium::from_reader(bytes).map_err(|_| VerificationError::MalformedProof)?;
+        from_value(value)
+            .and_then(|json| serde_json::from_value(json).ok())
+            .ok_or(VerificationError::MalformedProof)
+    }
+
+    /// Serialize to CBOR (roughly half the size of the JSON form)
+    pub fn encode_cbor(&self) -> Vec<u8> {
+        let json = serde_json::to_value(self).expect("ProofData always serializes");
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&to_value(json), &mut bytes).expect("writing to a Vec cannot fail");
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{hash_bytes, HashAlgorithm};
+
+    #[test]
+    fn test_cbor_roundtrip() {
+        let mut proof = ProofData::new(
+            "0x1234567890abcdef".to_string(),
+            "sha3:0xABCD".to_string(),
+            hash_bytes(b"in", HashAlgorithm::default()).unwrap(),
+            hash_bytes(b"out", HashAlgorithm::default()).unwrap(),
+            1_700_000_000_000,
+            true,
+        );
+        proof.signature = Some("ab".repeat(64));
+        proof.kid = Some("prover-1".to_string());
+
+        let cbor = proof.encode_cbor();
+        let decoded = ProofData::decode_cbor(&cbor).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), serde_json::to_string(&proof).unwrap());
+        assert!(cbor.len() * 10 < serde_json::to_vec(&proof).unwrap().len() * 6);
+
+        assert_eq!(ProofData::decode_cbor(b"\xff").unwrap_err(), VerificationError::MalformedProof);
+    }
+}
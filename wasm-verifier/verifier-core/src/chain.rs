@@ -0,0 +1,114 @@
+// Pipeline proof chaining
+// Verifies multi-stage pipelines (e.g. embedder -> classifier) where each stage's output feeds the next
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::report::{Check, VerificationReport};
+use crate::{hash, ProofData, Verifier};
+
+/// Per-stage results plus the links between stages
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ChainReport {
+    stages: Vec<VerificationReport>,
+    links: Vec<bool>,
+    end_to_end: bool,
+    total_ms: f64,
+}
+
+impl ChainReport {
+    /// Per-stage reports, in pipeline order
+    pub fn stages(&self) -> &[VerificationReport] {
+        &self.stages
+    }
+
+    /// `links[i]`: stage `i`'s output is stage `i + 1`'s input
+    pub fn links(&self) -> &[bool] {
+        &self.links
+    }
+
+    /// The first stage consumed the pipeline input and the last produced the pipeline output
+    pub fn end_to_end(&self) -> bool {
+        self.end_to_end
+    }
+
+    /// True when every stage passed, every link holds and the ends are bound
+    pub fn passed(&self) -> bool {
+        let stages_passed = !self.stages.is_empty() && self.stages.iter().all(|s| s.passed());
+        stages_passed && self.links.iter().all(|&l| l) && self.end_to_end
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.total_ms
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl Verifier {
+    /// Verify a pipeline of proofs; `io_buffers[i]` and `io_buffers[i + 1]` are stage `i`'s input and output
+    ///
+    /// Stages for different models need a registry verifier. Fails with
+    /// `InvalidArgument` unless `io_buffers` has one more entry than `proofs`.
+    pub fn check_chain<B: AsRef<[u8]>>(
+        &self,
+        proofs: &[ProofData],
+        io_buffers: &[B],
+    ) -> Result<ChainReport, VerificationError> {
+        if proofs.is_empty() || io_buffers.len() != proofs.len() + 1 {
+            return Err(VerificationError::InvalidArgument);
+        }
+
+        let mut chain = ChainReport::default();
+        for (i, proof) in proofs.iter().enumerate() {
+            let report = self.verify_report(proof, io_buffers[i].as_ref(), io_buffers[i + 1].as_ref());
+            chain.total_ms += report.total_ms();
+            chain.stages.push(report);
+        }
+        for (i, pair) in proofs.windows(2).enumerate() {
+            // The same intermediate buffer satisfied both sides, or both commit to the same digest
+            let bound =
+                chain.stages[i].check_passed(Check::OutputHash) && chain.stages[i + 1].check_passed(Check::InputHash);
+            chain.links.push(bound || hash::commitments_equal(&pair[0].output_hash, &pair[1].input_hash));
+        }
+        let last = chain.stages.len() - 1;
+        chain.end_to_end =
+            chain.stages[0].check_passed(Check::InputHash) && chain.stages[last].check_passed(Check::OutputHash);
+        Ok(chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hash::HashAlgorithm;
+    use crate::{ModelRegistry, VerifierConfig};
+
+    #[test]
+    fn test_check_chain() {
+        let mut registry = ModelRegistry::new();
+        registry.add("0xembedder".into(), "embedder".into(), "1.0.0".into());
+        registry.add("0xclassifier".into(), "classifier".into(), "1.0.0".into());
+        let verifier = Verifier::with_registry(registry, &VerifierConfig::default());
+
+        let proof = |model: &str, input: &[u8], output: &[u8]| {
+            fixtures::proof_for(model, input, output, HashAlgorithm::default())
+        };
+        let proofs = vec![proof("0xembedder", b"text", b"embedding"), proof("0xclassifier", b"embedding", b"label")];
+        let io: Vec<&[u8]> = vec![b"text", b"embedding", b"label"];
+        let chain = verifier.check_chain(&proofs, &io).unwrap();
+        assert!(chain.passed());
+        assert_eq!(chain.links(), [true]);
+
+        // The classifier ran on some other embedding
+        let broken = vec![proofs[0].clone(), proof("0xclassifier", b"other", b"label")];
+        let chain = verifier.check_chain(&broken, &io).unwrap();
+        assert_eq!(chain.links(), [false]);
+        assert!(chain.end_to_end() && !chain.passed());
+
+        assert_eq!(verifier.check_chain(&proofs, &io[..2]).unwrap_err(), VerificationError::InvalidArgument);
+    }
+}
@@ -0,0 +1,87 @@
+// Classification semantics
+// Check the committed output's argmax / top-k against a claimed label set
+
+use crate::hash::IoSource;
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::tensor::{numeric_elements, DType, TensorSpec};
+use crate::{ProofData, Verifier};
+
+/// Output scores, decoded per `output_spec` (raw little-endian float32 when the proof declares none)
+fn scores(proof: &ProofData, output: &[u8]) -> Result<Vec<f64>, String> {
+    let flat;
+    let spec = match &proof.output_spec {
+        Some(spec) => spec,
+        None => {
+            flat = TensorSpec::new(vec![(output.len() / 4) as u32], DType::Float32, None);
+            &flat
+        }
+    };
+    let scores = numeric_elements(spec, output).ok_or("Output is not a tensor matching output_spec")?;
+    if scores.iter().any(|s| s.is_nan()) {
+        return Err("Output contains NaN scores".to_string());
+    }
+    Ok(scores)
+}
+
+/// Indices of the `k` highest scores, best first (ties go to the lower index)
+pub fn top_k(scores: &[f64], k: usize) -> Vec<u32> {
+    let mut indices: Vec<u32> = (0..scores.len() as u32).collect();
+    indices.sort_by(|&a, &b| scores[b as usize].total_cmp(&scores[a as usize]).then(a.cmp(&b)));
+    indices.truncate(k);
+    indices
+}
+
+impl Verifier {
+    /// Verify a proof and that its output's `labels.len()` highest-scoring classes are `labels` (in any order)
+    ///
+    /// Scores are decoded per the proof's `output_spec`, or as raw
+    /// float32 without one; ties rank the lower class index first.
+    pub fn top_k_report(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[u8],
+        labels: &[u32],
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(&mut report, proof_data, IoSource::Bytes(input), IoSource::Bytes(output));
+
+        let started = Stopwatch::start();
+        let result = scores(proof_data, output).and_then(|scores| {
+            if labels.is_empty() || labels.len() > scores.len() {
+                return Err(format!("Cannot claim {} of {} classes", labels.len(), scores.len()));
+            }
+            let (mut top, mut claimed) = (top_k(&scores, labels.len()), labels.to_vec());
+            top.sort_unstable();
+            claimed.sort_unstable();
+            if top == claimed {
+                Ok(())
+            } else {
+                Err(format!("Output's top-{} classes are {:?}, not {:?}", labels.len(), top, claimed))
+            }
+        });
+        report.record(Check::Classification, result.is_ok(), &result.err().unwrap_or_default(), started);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hash::HashAlgorithm;
+    use crate::VerificationError;
+
+    #[test]
+    fn test_verify_top_k() {
+        let logits: Vec<u8> = [0.1f32, 2.5, -1.0, 2.5, 0.7].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let proof = fixtures::proof_for("0xmodel", b"cat.png", &logits, HashAlgorithm::default());
+        let verifier = Verifier::new("0xmodel".to_string());
+        assert!(verifier.top_k_report(&proof, b"cat.png", &logits, &[1]).passed());
+        assert!(verifier.top_k_report(&proof, b"cat.png", &logits, &[4, 3, 1]).passed());
+
+        let report = verifier.top_k_report(&proof, b"cat.png", &logits, &[3]);
+        assert_eq!(report.error(), Some(VerificationError::ClassMismatch));
+        assert_eq!(report.failure_reasons(), ["Output's top-1 classes are [1], not [3]"]);
+    }
+}
@@ -0,0 +1,71 @@
+// Time source
+// Where freshness, nonce and certificate checks read "now" from: the host runtime's clock, or a host callback
+
+use std::sync::Arc;
+
+use crate::host::Clock;
+
+/// Clock a verifier reads the current time from
+#[derive(Clone, Default)]
+pub enum ClockSource {
+    /// `Date.now()` (or `performance` where there's no `Date`) in JS hosts, `SystemTime` natively and under WASI
+    #[default]
+    System,
+    /// Host callback, e.g. a JS `() => number`
+    Callback(Arc<dyn Clock>),
+    /// Frozen time, for tests and replays
+    Fixed(u64),
+}
+
+impl ClockSource {
+    /// Current time in milliseconds since the Unix epoch
+    ///
+    /// A callback that fails or returns something other than a finite,
+    /// non-negative number falls back to the system clock.
+    pub fn now_ms(&self) -> u64 {
+        match self {
+            ClockSource::System => system_now_ms(),
+            ClockSource::Callback(callback) => callback
+                .now_ms()
+                .filter(|now| now.is_finite() && *now >= 0.0)
+                .map_or_else(system_now_ms, |now| now as u64),
+            ClockSource::Fixed(now) => *now,
+        }
+    }
+}
+
+/// `globalThis[object].now()`, if this runtime has it
+#[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi")))]
+fn global_now(object: &str) -> Option<f64> {
+    use wasm_bindgen::JsCast;
+
+    let object = js_sys::Reflect::get(&js_sys::global(), &object.into()).ok()?;
+    let now = js_sys::Reflect::get(&object, &"now".into()).ok()?.dyn_into::<js_sys::Function>().ok()?;
+    now.call0(&object).ok()?.as_f64()
+}
+
+/// Wall-clock time in milliseconds since the Unix epoch
+#[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi")))]
+pub fn system_now_ms() -> u64 {
+    let performance_epoch = || {
+        let performance = js_sys::Reflect::get(&js_sys::global(), &"performance".into()).ok()?;
+        let origin = js_sys::Reflect::get(&performance, &"timeOrigin".into()).ok()?.as_f64()?;
+        Some(origin + global_now("performance")?)
+    };
+    global_now("Date").or_else(performance_epoch).unwrap_or(0.0) as u64
+}
+
+/// Wall-clock time in milliseconds since the Unix epoch (native builds and WASI's `clock_time_get`)
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi"))))]
+pub fn system_now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Milliseconds on a clock that only moves forward where the runtime has one (`performance.now()`)
+#[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi")))]
+pub fn monotonic_ms() -> f64 {
+    global_now("performance").or_else(|| global_now("Date")).unwrap_or(0.0)
+}
@@ -0,0 +1,181 @@
+// Composite proofs
+// A proof attesting to the verification of other proofs, folded server-side and checked in one call
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm, IoSource};
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::{ProofData, Verifier};
+
+/// Domain separators for the children encodings (format version 1)
+const INPUTS_DOMAIN: &[u8] = b"zkml/composite/inputs/v1\0";
+const OUTPUTS_DOMAIN: &[u8] = b"zkml/composite/outputs/v1\0";
+
+/// Deepest nesting `Verifier::composite_report` follows
+pub const MAX_COMPOSITE_DEPTH: usize = 16;
+
+/// A folding proof plus the proofs it attests to
+///
+/// The folding proof's `input_hash` commits to the children (the canonical
+/// hashes of their proofs, in order) and its `output_hash` to their
+/// `output_hash`es; see `children_input_hash`/`children_output_hash`. A
+/// child with children of its own is itself a composite.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CompositeProof {
+    proof: ProofData,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<CompositeProof>,
+}
+
+fn put(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+impl CompositeProof {
+    pub fn new(proof: ProofData, children: Vec<CompositeProof>) -> CompositeProof {
+        CompositeProof { proof, children }
+    }
+
+    /// Wrap a plain proof as a leaf child
+    pub fn leaf(proof: ProofData) -> CompositeProof {
+        CompositeProof { proof, children: Vec::new() }
+    }
+
+    pub fn proof(&self) -> &ProofData {
+        &self.proof
+    }
+
+    pub fn children(&self) -> &[CompositeProof] {
+        &self.children
+    }
+
+    /// Total number of leaf proofs under this composite
+    pub fn leaf_count(&self) -> u32 {
+        if self.children.is_empty() {
+            1
+        } else {
+            self.children.iter().map(CompositeProof::leaf_count).sum()
+        }
+    }
+
+    /// `domain || u32 count || (u32 len || canonical hash)...` over the children
+    fn inputs_encoding(&self, alg: HashAlgorithm) -> Result<Vec<u8>, VerificationError> {
+        let mut out = INPUTS_DOMAIN.to_vec();
+        out.extend_from_slice(&(self.children.len() as u32).to_le_bytes());
+        for child in &self.children {
+            put(&mut out, &hash_bytes(child.proof.canonical_json().as_bytes(), alg)?);
+        }
+        Ok(out)
+    }
+
+    /// `domain || u32 count || (u32 len || output_hash)...` over the children
+    fn outputs_encoding(&self) -> Vec<u8> {
+        let mut out = OUTPUTS_DOMAIN.to_vec();
+        out.extend_from_slice(&(self.children.len() as u32).to_le_bytes());
+        self.children.iter().for_each(|child| put(&mut out, &child.proof.output_hash));
+        out
+    }
+
+    /// `input_hash` the folding proof must carry for these children
+    pub fn children_input_hash(&self, alg: HashAlgorithm) -> Result<String, VerificationError> {
+        hash_bytes(&self.inputs_encoding(alg)?, alg)
+    }
+
+    /// `output_hash` the folding proof must carry for these children
+    pub fn children_output_hash(&self, alg: HashAlgorithm) -> Result<String, VerificationError> {
+        hash_bytes(&self.outputs_encoding(), alg)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<CompositeProof, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl Verifier {
+    fn check_composite(&self, composite: &CompositeProof, depth: usize) -> VerificationReport {
+        let mut report = self.new_report();
+        let started = Stopwatch::start();
+        if composite.children.is_empty() || depth >= MAX_COMPOSITE_DEPTH {
+            let reason = format!("Composite proof must have children and nest at most {} deep", MAX_COMPOSITE_DEPTH);
+            report.record(Check::Composite, false, &reason, started);
+            return report;
+        }
+
+        // Nested composites are folding proofs too, verified the same way
+        let mut nested = composite.children.iter().enumerate().filter(|(_, c)| !c.children.is_empty());
+        let failed = nested.find_map(|(i, child)| {
+            let reason = self.check_composite(child, depth + 1).failure_reasons().into_iter().next()?;
+            Some(format!("Child {}: {}", i, reason))
+        });
+        match failed {
+            Some(reason) => report.record(Check::Composite, false, &reason, started),
+            None => report.record(Check::Composite, true, "", started),
+        }
+
+        let alg = self.hash_algorithm_for(&composite.proof).unwrap_or_default();
+        let inputs = composite.inputs_encoding(alg).unwrap_or_default();
+        let outputs = composite.outputs_encoding();
+        self.run_checks(&mut report, &composite.proof, IoSource::Bytes(&inputs), IoSource::Bytes(&outputs));
+        report
+    }
+
+    /// Verify a composite proof and, recursively, every nested composite
+    ///
+    /// This verifier's model is the folding model. Its input/output checks
+    /// bind the folding proof to its children, a `Composite` check reports
+    /// the first failing nested composite. Leaf proofs are covered by the
+    /// folding proof and aren't re-checked individually.
+    pub fn composite_report(&self, composite: &CompositeProof) -> VerificationReport {
+        self.check_composite(composite, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+
+    fn hash(data: &[u8]) -> String {
+        hash_bytes(data, HashAlgorithm::default()).unwrap()
+    }
+
+    fn proof(model: &str, input: String, output: String) -> ProofData {
+        ProofData::new(model.to_string(), "0xproof".to_string(), input, output, system_now_ms(), true)
+    }
+
+    fn fold(children: Vec<CompositeProof>) -> CompositeProof {
+        let mut composite = CompositeProof::new(proof("0xfolder", String::new(), String::new()), children);
+        composite.proof.input_hash = composite.children_input_hash(HashAlgorithm::default()).unwrap();
+        composite.proof.output_hash = composite.children_output_hash(HashAlgorithm::default()).unwrap();
+        composite
+    }
+
+    #[test]
+    fn test_verify_composite() {
+        let verifier = Verifier::new("0xfolder".to_string());
+        let leaf = |i: u8| CompositeProof::leaf(proof("0xmodel", hash(&[i]), hash(&[i, i])));
+        let nested = fold(vec![leaf(1), leaf(2)]);
+        let root = fold(vec![nested, leaf(3)]);
+        assert_eq!(root.leaf_count(), 3);
+        assert!(verifier.composite_report(&CompositeProof::from_json(&root.to_json()).unwrap()).passed());
+
+        // Swapping a leaf's output breaks both of the root's commitments
+        let mut tampered = root.clone();
+        tampered.children[1].proof.output_hash = hash(b"forged");
+        let report = verifier.composite_report(&tampered);
+        assert!(!report.check_passed(Check::InputHash) && !report.check_passed(Check::OutputHash));
+
+        // ...and tampering deeper down is caught in the nested composite
+        let mut deep = root.clone();
+        deep.children[0].children[0].proof.output_hash = hash(b"forged");
+        let report = verifier.composite_report(&deep);
+        assert_eq!(report.error(), Some(VerificationError::CompositeInvalid));
+        assert_eq!(report.failure_reasons()[0], "Child 0: Input does not match input_hash");
+    }
+}
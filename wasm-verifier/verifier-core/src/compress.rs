@@ -0,0 +1,70 @@
+// Compressed proof payloads
+// Transparent gzip/zstd decompression with a hard output limit against decompression bombs
+
+use std::borrow::Cow;
+use std::io::Read;
+
+use crate::error::{Failure, VerificationError};
+
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+pub const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Decompress gzip or zstd `bytes`, passing anything else through unchanged
+///
+/// The format is detected from its magic bytes. Output beyond `limit` bytes
+/// is never produced: decoding stops and fails with `PayloadTooLarge`.
+pub fn decompress(bytes: &[u8], limit: u64) -> Result<Cow<'_, [u8]>, Failure> {
+    if !bytes.starts_with(&GZIP_MAGIC) && !bytes.starts_with(&ZSTD_MAGIC) {
+        return Ok(Cow::Borrowed(bytes));
+    }
+    let reader = decoder(bytes)?;
+
+    let mut out = Vec::new();
+    reader
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut out)
+        .map_err(|_| (VerificationError::MalformedProof, "Compressed payload is corrupt"))?;
+    if out.len() as u64 > limit {
+        return Err((VerificationError::PayloadTooLarge, "Decompressed payload exceeds max_decompressed_bytes"));
+    }
+    Ok(Cow::Owned(out))
+}
+
+/// Streaming decoder for a gzip or zstd payload
+#[cfg(feature = "compression")]
+fn decoder(bytes: &[u8]) -> Result<Box<dyn Read + '_>, Failure> {
+    if bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(Box::new(flate2::read::MultiGzDecoder::new(bytes)));
+    }
+    let zstd = ruzstd::decoding::StreamingDecoder::new(bytes)
+        .map_err(|_| (VerificationError::MalformedProof, "Invalid zstd frame"))?;
+    Ok(Box::new(zstd))
+}
+
+#[cfg(not(feature = "compression"))]
+fn decoder(_bytes: &[u8]) -> Result<Box<dyn Read + '_>, Failure> {
+    Err((VerificationError::MalformedProof, "Compressed proofs are not enabled in this build"))
+}
+
+#[cfg(all(test, feature = "compression"))]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_decompress_limits() {
+        let data = vec![b'a'; 10_000];
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(&data).unwrap();
+        let gz = gz.finish().unwrap();
+        let zstd = ruzstd::encoding::compress_to_vec(data.as_slice(), ruzstd::encoding::CompressionLevel::Fastest);
+
+        assert_eq!(decompress(&gz, 10_000).unwrap(), data.as_slice());
+        assert_eq!(decompress(&zstd, 10_000).unwrap(), data.as_slice());
+        assert_eq!(decompress(b"{}", 1).unwrap(), b"{}".as_slice());
+
+        assert_eq!(decompress(&gz, 9_999).unwrap_err().0, VerificationError::PayloadTooLarge);
+        assert_eq!(decompress(&zstd, 9_999).unwrap_err().0, VerificationError::PayloadTooLarge);
+        assert_eq!(decompress(&gz[..gz.len() / 2], 10_000).unwrap_err().0, VerificationError::MalformedProof);
+    }
+}
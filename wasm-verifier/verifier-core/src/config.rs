@@ -0,0 +1,191 @@
+// Verifier configuration
+// Policy knobs for the checks performed by `Verifier::verify_report`
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::HashAlgorithm;
+
+/// Default maximum proof age (1 hour)
+pub const DEFAULT_MAX_AGE_MS: u64 = 3_600_000;
+
+/// Default tolerance for clocks that disagree between prover and verifier (1 minute)
+pub const DEFAULT_CLOCK_SKEW_MS: u64 = 60_000;
+
+/// Default cap on decompressed payload size (64 MiB)
+pub const DEFAULT_MAX_DECOMPRESSED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Default cap on the size of a fetched proof (16 MiB)
+pub const DEFAULT_MAX_PROOF_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Verification policy
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct VerifierConfig {
+    max_age_ms: u64,
+    check_freshness: bool,
+    clock_skew_ms: u64,
+    hash_algorithm: HashAlgorithm,
+    require_nonce: bool,
+    require_signature: bool,
+    min_attestors: u32,
+    max_decompressed_bytes: u64,
+    max_proof_bytes: u64,
+    max_input_bytes: u64,
+}
+
+impl Default for VerifierConfig {
+    fn default() -> Self {
+        VerifierConfig {
+            max_age_ms: DEFAULT_MAX_AGE_MS,
+            check_freshness: true,
+            clock_skew_ms: DEFAULT_CLOCK_SKEW_MS,
+            hash_algorithm: HashAlgorithm::default(),
+            require_nonce: false,
+            require_signature: false,
+            min_attestors: 0,
+            max_decompressed_bytes: DEFAULT_MAX_DECOMPRESSED_BYTES,
+            max_proof_bytes: DEFAULT_MAX_PROOF_BYTES,
+            max_input_bytes: 0,
+        }
+    }
+}
+
+impl VerifierConfig {
+    /// Create config with the default policy (1 hour max age, 1 minute skew)
+    pub fn new() -> VerifierConfig {
+        VerifierConfig::default()
+    }
+
+    /// Config for archival verification: timestamps are not checked
+    pub fn archival() -> VerifierConfig {
+        VerifierConfig {
+            check_freshness: false,
+            ..VerifierConfig::default()
+        }
+    }
+
+    pub fn max_age_ms(&self) -> u64 {
+        self.max_age_ms
+    }
+
+    pub fn set_max_age_ms(&mut self, max_age_ms: u64) {
+        self.max_age_ms = max_age_ms;
+    }
+
+    pub fn check_freshness(&self) -> bool {
+        self.check_freshness
+    }
+
+    pub fn set_check_freshness(&mut self, check_freshness: bool) {
+        self.check_freshness = check_freshness;
+    }
+
+    pub fn clock_skew_ms(&self) -> u64 {
+        self.clock_skew_ms
+    }
+
+    pub fn set_clock_skew_ms(&mut self, clock_skew_ms: u64) {
+        self.clock_skew_ms = clock_skew_ms;
+    }
+
+    /// Hash algorithm assumed for proofs that don't declare `hash_alg`
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    pub fn set_hash_algorithm(&mut self, hash_algorithm: HashAlgorithm) {
+        self.hash_algorithm = hash_algorithm;
+    }
+
+    /// Challenge-response mode: proofs must carry a nonce from `Verifier::issue_nonce`
+    pub fn require_nonce(&self) -> bool {
+        self.require_nonce
+    }
+
+    pub fn set_require_nonce(&mut self, require_nonce: bool) {
+        self.require_nonce = require_nonce;
+    }
+
+    /// Reject proofs without a valid prover signature
+    pub fn require_signature(&self) -> bool {
+        self.require_signature
+    }
+
+    pub fn set_require_signature(&mut self, require_signature: bool) {
+        self.require_signature = require_signature;
+    }
+
+    /// Minimum number of BLS attestors that must co-sign (0 = not required)
+    pub fn min_attestors(&self) -> u32 {
+        self.min_attestors
+    }
+
+    pub fn set_min_attestors(&mut self, min_attestors: u32) {
+        self.min_attestors = min_attestors;
+    }
+
+    /// Largest payload compressed proofs will inflate (default 64 MiB)
+    pub fn max_decompressed_bytes(&self) -> u64 {
+        self.max_decompressed_bytes
+    }
+
+    pub fn set_max_decompressed_bytes(&mut self, max_decompressed_bytes: u64) {
+        self.max_decompressed_bytes = max_decompressed_bytes;
+    }
+
+    /// Largest proof a remote proof fetch will download (default 16 MiB)
+    pub fn max_proof_bytes(&self) -> u64 {
+        self.max_proof_bytes
+    }
+
+    pub fn set_max_proof_bytes(&mut self, max_proof_bytes: u64) {
+        self.max_proof_bytes = max_proof_bytes;
+    }
+
+    /// Largest input, output or proof blob accepted for hashing (0 = no limit)
+    ///
+    /// Larger ones fail their hash check with `PayloadTooLarge` before any
+    /// hashing.
+    pub fn max_input_bytes(&self) -> u64 {
+        self.max_input_bytes
+    }
+
+    pub fn set_max_input_bytes(&mut self, max_input_bytes: u64) {
+        self.max_input_bytes = max_input_bytes;
+    }
+
+    /// Check a proof timestamp against this policy
+    ///
+    /// Proofs older than `max_age_ms` or dated further in the future than
+    /// `clock_skew_ms` are rejected; the skew is also granted on the age side.
+    pub fn is_fresh(&self, timestamp: u64, now: u64) -> bool {
+        if !self.check_freshness {
+            return true;
+        }
+
+        if timestamp > now.saturating_add(self.clock_skew_ms) {
+            return false;
+        }
+
+        let age_ms = now.saturating_sub(timestamp);
+        age_ms <= self.max_age_ms.saturating_add(self.clock_skew_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freshness_policy() {
+        let now = 10_000_000;
+        let config = VerifierConfig::default();
+
+        assert!(config.is_fresh(now - DEFAULT_MAX_AGE_MS, now));
+        assert!(!config.is_fresh(now - DEFAULT_MAX_AGE_MS - DEFAULT_CLOCK_SKEW_MS - 1, now));
+        assert!(config.is_fresh(now + DEFAULT_CLOCK_SKEW_MS, now));
+        assert!(!config.is_fresh(now + DEFAULT_CLOCK_SKEW_MS + 1, now));
+
+        assert!(VerifierConfig::archival().is_fresh(0, now));
+    }
+}
@@ -0,0 +1,229 @@
+// COSE_Sign1 proof envelopes
+// Verifies proofs shipped as CBOR COSE_Sign1 messages (RFC 9052) whose payload is `ProofData::encode_cbor`
+
+use ciborium::Value;
+
+use crate::error::VerificationError;
+use crate::hash::IoSource;
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::signature::{self, Failure, SignatureAlgorithm, Signed};
+use crate::{hex, ProofData, Verifier};
+
+/// CBOR tag of a COSE_Sign1 message
+const COSE_SIGN1_TAG: u64 = 18;
+
+/// COSE header labels
+const HEADER_ALG: i128 = 1;
+const HEADER_CRIT: i128 = 2;
+const HEADER_KID: i128 = 4;
+
+/// COSE algorithm identifiers
+const ALG_EDDSA: i128 = -8;
+const ALG_ES256K: i128 = -47;
+
+fn malformed(reason: &'static str) -> Failure {
+    (VerificationError::MalformedProof, reason)
+}
+
+/// A parsed COSE_Sign1 message
+pub struct CoseSign1 {
+    protected: Vec<u8>,
+    payload: Vec<u8>,
+    signature: Vec<u8>,
+    pub algorithm: SignatureAlgorithm,
+    pub kid: Option<String>,
+}
+
+impl CoseSign1 {
+    /// Parse a (optionally tagged) COSE_Sign1 message
+    ///
+    /// The algorithm must be in the protected header, so it is covered by the
+    /// signature, and must be EdDSA or ES256K. Messages with critical headers
+    /// or a detached payload are rejected.
+    pub fn parse(bytes: &[u8]) -> Result<CoseSign1, Failure> {
+        let value: Value = ciborium::from_reader(bytes).map_err(|_| malformed("COSE envelope is not valid CBOR"))?;
+        let value = match value {
+            Value::Tag(COSE_SIGN1_TAG, inner) => *inner,
+            Value::Tag(..) => return Err(malformed("COSE envelope is not a COSE_Sign1 message")),
+            value => value,
+        };
+
+        let Value::Array(parts) = value else {
+            return Err(malformed("COSE_Sign1 must be a 4-element array"));
+        };
+        let [Value::Bytes(protected), Value::Map(unprotected), payload, Value::Bytes(signature)] =
+            <[Value; 4]>::try_from(parts).map_err(|_| malformed("COSE_Sign1 must be a 4-element array"))?
+        else {
+            return Err(malformed("COSE_Sign1 has malformed members"));
+        };
+        let Value::Bytes(payload) = payload else {
+            return Err(malformed("Detached COSE payloads are not supported"));
+        };
+
+        let protected_map = if protected.is_empty() {
+            Vec::new()
+        } else {
+            match ciborium::from_reader(protected.as_slice()) {
+                Ok(Value::Map(map)) => map,
+                _ => return Err(malformed("COSE protected header is not a CBOR map")),
+            }
+        };
+
+        if header(&protected_map, HEADER_CRIT).is_some() {
+            return Err(malformed("COSE critical headers are not supported"));
+        }
+        if header(&unprotected, HEADER_ALG).is_some() {
+            return Err(malformed("COSE alg must be in the protected header"));
+        }
+        let algorithm = match header(&protected_map, HEADER_ALG).and_then(label) {
+            Some(ALG_EDDSA) => SignatureAlgorithm::Ed25519,
+            Some(ALG_ES256K) => SignatureAlgorithm::Secp256k1,
+            Some(_) => return Err((VerificationError::SignatureInvalid, "Unsupported COSE algorithm")),
+            None => return Err(malformed("COSE protected header has no alg")),
+        };
+        let kid = match header(&protected_map, HEADER_KID).or_else(|| header(&unprotected, HEADER_KID)) {
+            Some(Value::Bytes(kid)) => {
+                Some(String::from_utf8(kid.clone()).map_err(|_| malformed("COSE kid is not UTF-8"))?)
+            }
+            Some(_) => return Err(malformed("COSE kid must be a byte string")),
+            None => None,
+        };
+
+        Ok(CoseSign1 { protected, payload, signature, algorithm, kid })
+    }
+
+    /// The `Sig_structure` the signature covers (no external AAD)
+    pub fn signing_input(&self) -> Vec<u8> {
+        let structure = Value::Array(vec![
+            Value::Text("Signature1".into()),
+            Value::Bytes(self.protected.clone()),
+            Value::Bytes(Vec::new()),
+            Value::Bytes(self.payload.clone()),
+        ]);
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&structure, &mut bytes).expect("writing to a Vec cannot fail");
+        bytes
+    }
+
+    /// The ProofData carried as the CBOR payload
+    pub fn proof(&self) -> Result<ProofData, Failure> {
+        ProofData::decode_cbor(&self.payload).map_err(|_| malformed("COSE payload is not a CBOR ProofData"))
+    }
+}
+
+fn header(map: &[(Value, Value)], wanted: i128) -> Option<&Value> {
+    map.iter().find(|(key, _)| label(key) == Some(wanted)).map(|(_, value)| value)
+}
+
+fn label(value: &Value) -> Option<i128> {
+    match value {
+        Value::Integer(i) => Some(i128::from(*i)),
+        _ => None,
+    }
+}
+
+impl Verifier {
+    /// Record whether the envelope signature is by a key `proof_data`'s signer may use
+    pub fn check_cose(&self, report: &mut VerificationReport, cose: &CoseSign1, proof_data: &ProofData) {
+        let started = Stopwatch::start();
+        let message = cose.signing_input();
+        let kid = cose.kid.as_deref().or(proof_data.kid.as_deref());
+        let signature = hex::encode(&cose.signature);
+        let signed = match cose.algorithm {
+            SignatureAlgorithm::Ed25519 => Ok(Signed::Ed25519 { signature: &signature, message: &message }),
+            SignatureAlgorithm::Secp256k1 => {
+                signature::recover_es256k_addresses(&cose.signature, &message).map(Signed::Recovered)
+            }
+        };
+        let outcome = signed.and_then(|signed| self.check_prover_key(proof_data, kid, cose.algorithm, signed));
+        match outcome {
+            Ok(()) => report.record(Check::Signature, true, "", started),
+            Err((error, reason)) => report.record_error(Check::Signature, error, reason, started),
+        }
+    }
+
+    /// Verify a proof wrapped in a COSE_Sign1 envelope
+    ///
+    /// The envelope signature (EdDSA or ES256K) takes the place of the
+    /// in-proof `signature`; its key is found like a proof signer's, with
+    /// the COSE `kid` header naming a `KeyResolver` key. Fails if the
+    /// envelope cannot be parsed.
+    pub fn verify_cose(
+        &self,
+        envelope: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, Failure> {
+        let cose = CoseSign1::parse(envelope)?;
+        let proof_data = cose.proof()?;
+
+        let mut report = self.new_report();
+        self.check_cose(&mut report, &cose, &proof_data);
+        self.run_checks(&mut report, &proof_data, IoSource::Bytes(input_bytes), IoSource::Bytes(output_bytes));
+        Ok(report)
+    }
+}
+
+#[cfg(all(test, feature = "rust-signatures"))]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn envelope(
+        protected: &[(i64, Value)],
+        unprotected: &[(i64, Value)],
+        proof: &ProofData,
+        key: &SigningKey,
+    ) -> Vec<u8> {
+        let encode = |value: &Value| {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(value, &mut bytes).unwrap();
+            bytes
+        };
+        let map = |entries: &[(i64, Value)]| entries.iter().map(|(k, v)| (Value::from(*k), v.clone())).collect();
+
+        let mut cose = CoseSign1 {
+            protected: encode(&Value::Map(map(protected))),
+            payload: proof.encode_cbor(),
+            signature: Vec::new(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            kid: None,
+        };
+        cose.signature = key.sign(&cose.signing_input()).to_bytes().to_vec();
+
+        encode(&Value::Tag(
+            COSE_SIGN1_TAG,
+            Box::new(Value::Array(vec![
+                Value::Bytes(cose.protected),
+                Value::Map(map(unprotected)),
+                Value::Bytes(cose.payload),
+                Value::Bytes(cose.signature),
+            ])),
+        ))
+    }
+
+    #[test]
+    fn test_verify_cose() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let mut verifier = Verifier::new(model_hash.clone());
+        verifier.add_trusted_signer(hex::encode(key.verifying_key().as_bytes()));
+
+        let mut proof = fixtures::proof(&model_hash);
+        proof.signer_pubkey = Some(hex::encode(key.verifying_key().as_bytes()));
+
+        let signed = envelope(&[(1, Value::from(ALG_EDDSA as i64))], &[], &proof, &key);
+        let report = verifier.verify_cose(&signed, b"in", b"out").unwrap();
+        assert!(report.passed());
+        assert!(report.check_passed(Check::Signature));
+
+        let other = SigningKey::from_bytes(&[6u8; 32]);
+        let forged = envelope(&[(1, Value::from(ALG_EDDSA as i64))], &[], &proof, &other);
+        assert!(!verifier.verify_cose(&forged, b"in", b"out").unwrap().passed());
+
+        // alg outside the protected header is not covered by the signature
+        let unprotected = envelope(&[], &[(1, Value::from(ALG_EDDSA as i64))], &proof, &key);
+        assert_eq!(verifier.verify_cose(&unprotected, b"in", b"out").unwrap_err().0, VerificationError::MalformedProof);
+    }
+}
@@ -0,0 +1,238 @@
+// Signature primitives
+// Ed25519 and P-256 checks, in Rust with `rust-signatures` or answered by the host (WebCrypto) via `with_verdicts`
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::scalar::Scalar;
+use x509_cert::der::asn1::UintRef;
+use x509_cert::der::{Decode, Reader, SliceReader};
+
+#[cfg(not(feature = "rust-signatures"))]
+use crate::error::VerificationError;
+use crate::signature::Failure;
+
+/// Signature scheme of a `Check`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// `key` is 32 bytes, `signature` 64
+    Ed25519,
+    /// ECDSA-SHA256 on P-256: `key` is SEC1, `signature` the 64-byte `r || s`
+    P256,
+}
+
+/// One signature to check over `message`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Check {
+    pub scheme: Scheme,
+    pub key: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+impl Check {
+    pub fn ed25519(key: [u8; 32], signature: [u8; 64], message: &[u8]) -> Check {
+        Check { scheme: Scheme::Ed25519, key: key.to_vec(), signature: signature.to_vec(), message: message.to_vec() }
+    }
+
+    /// A P-256 check from a DER `ECDSA-Sig-Value`, `None` if it isn't one
+    pub fn p256_der(key: &[u8], der: &[u8], message: &[u8]) -> Option<Check> {
+        let signature = der_to_fixed(der)?;
+        Some(Check { scheme: Scheme::P256, key: key.to_vec(), signature, message: message.to_vec() })
+    }
+}
+
+/// `r || s`, each padded to 32 bytes, of a DER `SEQUENCE { r INTEGER, s INTEGER }`
+fn der_to_fixed(der: &[u8]) -> Option<Vec<u8>> {
+    let mut reader = SliceReader::new(der).ok()?;
+    let (r, s) = reader.sequence(|seq| Ok((UintRef::decode(seq)?, UintRef::decode(seq)?))).ok()?;
+    reader.finish(()).ok()?;
+    let mut fixed = vec![0u8; 64];
+    for (half, int) in fixed.chunks_mut(32).zip([r, s]) {
+        let bytes = int.as_bytes();
+        half.get_mut(32usize.checked_sub(bytes.len())?..)?.copy_from_slice(bytes);
+    }
+    Some(fixed)
+}
+
+/// Verdicts the host has reached, for the checks run inside `with_verdicts`
+pub type Verdicts = HashMap<Check, bool>;
+
+/// Checks a `with_verdicts` scope is answering from outside, and those it couldn't
+struct Scope {
+    verdicts: Verdicts,
+    unanswered: Vec<Check>,
+}
+
+thread_local! {
+    static SCOPE: RefCell<Option<Scope>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with Ed25519 and P-256 checks answered from `verdicts` rather than in Rust
+///
+/// Checks without a verdict fail and are returned, for the host to check
+/// (with WebCrypto, say) and run `f` again with their verdicts added. The
+/// encoding checks still run here, so a lenient host can't accept what
+/// `verify_strict` would reject.
+pub fn with_verdicts<T>(verdicts: Verdicts, f: impl FnOnce() -> T) -> (T, Vec<Check>) {
+    let outer = SCOPE.with(|scope| scope.replace(Some(Scope { verdicts, unanswered: Vec::new() })));
+    let result = f();
+    let scope = SCOPE.with(|scope| scope.replace(outer));
+    (result, scope.map(|scope| scope.unanswered).unwrap_or_default())
+}
+
+/// Whether `check` holds
+///
+/// Fails with `SignatureInvalid` if this build can't check it: no
+/// `rust-signatures`, and not inside `with_verdicts`.
+pub fn verify(check: Check) -> Result<bool, Failure> {
+    if check.scheme == Scheme::Ed25519 && !ed25519_strictly_encoded(&check.key, &check.signature) {
+        return Ok(false);
+    }
+    let answered = SCOPE.with(|scope| {
+        let mut scope = scope.borrow_mut();
+        let scope = scope.as_mut()?;
+        let verdict = scope.verdicts.get(&check).copied();
+        if verdict.is_none() {
+            scope.unanswered.push(check.clone());
+        }
+        Some(verdict.unwrap_or(false))
+    });
+    match answered {
+        Some(verdict) => Ok(verdict),
+        None => verify_here(&check),
+    }
+}
+
+#[cfg(feature = "rust-signatures")]
+fn verify_here(check: &Check) -> Result<bool, Failure> {
+    Ok(match check.scheme {
+        Scheme::Ed25519 => {
+            let key = check.key.as_slice().try_into().ok().and_then(|key| ed25519_dalek::VerifyingKey::from_bytes(key).ok());
+            let signature = check.signature.as_slice().try_into().map(ed25519_dalek::Signature::from_bytes);
+            key.zip(signature.ok()).is_some_and(|(key, signature)| key.verify_strict(&check.message, &signature).is_ok())
+        }
+        Scheme::P256 => {
+            use p256::ecdsa::signature::Verifier as _;
+            let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&check.key).ok();
+            let signature = p256::ecdsa::Signature::from_slice(&check.signature).ok();
+            key.zip(signature).is_some_and(|(key, signature)| key.verify(&check.message, &signature).is_ok())
+        }
+    })
+}
+
+#[cfg(not(feature = "rust-signatures"))]
+fn verify_here(_: &Check) -> Result<bool, Failure> {
+    Err((VerificationError::SignatureInvalid, "Ed25519 and P-256 are only checked by the async paths in this build"))
+}
+
+/// Whether an Ed25519 key and signature pass the checks `verify_strict` makes besides the equation
+///
+/// The key and `R` are canonical points not of small order, and `S` is
+/// reduced. WebCrypto implementations differ on these, so they're settled
+/// here, with curve25519-dalek, for every path to agree.
+pub fn ed25519_strictly_encoded(key: &[u8], signature: &[u8]) -> bool {
+    let strict_point = |bytes: &[u8]| {
+        let Ok(bytes) = <[u8; 32]>::try_from(bytes) else {
+            return false;
+        };
+        CompressedEdwardsY(bytes)
+            .decompress()
+            .is_some_and(|point| !point.is_small_order() && point.compress().to_bytes() == bytes)
+    };
+    let Some((r, s)) = signature.split_first_chunk::<32>().filter(|(_, s)| s.len() == 32) else {
+        return false;
+    };
+    let reduced_s = s.try_into().is_ok_and(|s| bool::from(Scalar::from_canonical_bytes(s).is_some()));
+    strict_point(key) && strict_point(r) && reduced_s
+}
+
+/// Whether `key` (32 bytes) decodes to an Ed25519 point
+pub fn ed25519_point(key: &[u8; 32]) -> bool {
+    CompressedEdwardsY(*key).decompress().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A key and its signature over `b"proof"`
+    fn signed() -> ([u8; 32], [u8; 64]) {
+        use ed25519_dalek::{Signer, SigningKey};
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        (key.verifying_key().to_bytes(), key.sign(b"proof").to_bytes())
+    }
+
+    #[test]
+    fn test_strict_encoding_matches_verify_strict() {
+        let (key, signature) = signed();
+        assert!(ed25519_strictly_encoded(&key, &signature));
+        let verifying = ed25519_dalek::VerifyingKey::from_bytes(&key).unwrap();
+
+        // S + order verifies under the plain equation, but isn't reduced
+        let mut order = (-Scalar::ONE).to_bytes();
+        order[0] += 1;
+        let (mut unreduced, mut carry) = (signature, 0u16);
+        for (byte, order) in unreduced[32..].iter_mut().zip(order) {
+            let sum = *byte as u16 + order as u16 + carry;
+            (*byte, carry) = (sum as u8, sum >> 8);
+        }
+        // The identity as R is a small-order point
+        let mut identity = [0u8; 32];
+        identity[0] = 1;
+        let mut small_order = signature;
+        small_order[..32].copy_from_slice(&identity);
+        // y = p + 1 is a non-canonical encoding of y = 1, the identity again
+        let mut non_canonical = signature;
+        non_canonical[..32].fill(0xff);
+        (non_canonical[0], non_canonical[31]) = (0xee, 0x7f);
+
+        for rejected in [unreduced, small_order, non_canonical] {
+            assert!(!ed25519_strictly_encoded(&key, &rejected));
+            let rejected = ed25519_dalek::Signature::from_bytes(&rejected);
+            assert!(verifying.verify_strict(b"proof", &rejected).is_err());
+        }
+        assert!(!ed25519_strictly_encoded(&identity, &signature));
+        assert!(!ed25519_strictly_encoded(&key, &signature[..63]));
+    }
+
+    #[test]
+    fn test_verdicts_answer_checks() {
+        let (key, signature) = signed();
+        let check = Check::ed25519(key, signature, b"proof");
+
+        let (verified, unanswered) = with_verdicts(Verdicts::new(), || verify(check.clone()));
+        assert_eq!((verified, unanswered), (Ok(false), vec![check.clone()]));
+        let verdicts = Verdicts::from([(check.clone(), true)]);
+        assert_eq!(with_verdicts(verdicts, || verify(check.clone())), (Ok(true), Vec::new()));
+
+        // Badly encoded signatures fail whatever the host says
+        let mut unreduced = signature;
+        unreduced[63] = 0xff;
+        let lenient = Check::ed25519(key, unreduced, b"proof");
+        let verdicts = Verdicts::from([(lenient.clone(), true)]);
+        assert_eq!(with_verdicts(verdicts, || verify(lenient.clone())), (Ok(false), Vec::new()));
+
+        #[cfg(feature = "rust-signatures")]
+        assert_eq!(verify(check), Ok(true));
+        #[cfg(not(feature = "rust-signatures"))]
+        assert!(verify(check).is_err());
+    }
+
+    #[test]
+    fn test_p256_der() {
+        let key = [4u8; 65];
+        let check = Check::p256_der(&key, &[0x30, 0x06, 0x02, 0x01, 0x05, 0x02, 0x01, 0x07], b"m").unwrap();
+        let mut expected = [0u8; 64];
+        (expected[31], expected[63]) = (5, 7);
+        assert_eq!(check.signature, expected);
+
+        let mut long = vec![0x30, 0x25, 0x02, 0x21];
+        long.extend([0u8; 33]);
+        long.extend([0x02, 0x01, 0x01]);
+        assert!(Check::p256_der(&key, &long, b"m").is_none());
+        assert!(Check::p256_der(&key, &[0x30, 0x03, 0x02, 0x01, 0x05], b"m").is_none());
+        assert!(Check::p256_der(&key, &[0x30, 0x06, 0x02, 0x01, 0x05, 0x02, 0x01, 0x07, 0x00], b"m").is_none());
+    }
+}
@@ -0,0 +1,471 @@
+// Proof inspection
+// `describe`: decodes a proof in any supported encoding and lists what it claims, for debugging failed verifications
+
+use serde::Serialize;
+
+use crate::binary::BINARY_MAGIC;
+use crate::compress::{decompress, GZIP_MAGIC, ZSTD_MAGIC};
+use crate::config::DEFAULT_MAX_DECOMPRESSED_BYTES;
+use crate::error::VerificationError;
+use crate::hash::{split_commitment, HashAlgorithm};
+use crate::{hex, ProofData};
+
+/// Top-level fields of the proof JSON; serde skips anything else, so other keys are most likely typos
+const PROOF_FIELDS: [&str; 28] = [
+    "model_hash",
+    "proof_hash",
+    "input_hash",
+    "output_hash",
+    "timestamp",
+    "verified",
+    "hash_alg",
+    "nonce",
+    "signature_alg",
+    "signature",
+    "signer_pubkey",
+    "kid",
+    "prover_did",
+    "attestor_pubkeys",
+    "aggregate_signature",
+    "inputs",
+    "outputs",
+    "input_spec",
+    "output_spec",
+    "model_version",
+    "partial_model_hash",
+    "partial_layers",
+    "quantization_hash",
+    "preprocessing_hash",
+    "architecture_hash",
+    "weights_hash",
+    "metadata_hash",
+    "cert_chain",
+];
+
+/// Why a proof couldn't be decoded
+pub type DescribeError = (VerificationError, String);
+
+fn malformed(reason: impl Into<String>) -> DescribeError {
+    (VerificationError::MalformedProof, reason.into())
+}
+
+/// A commitment in the proof and the algorithm it's checked with
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Commitment {
+    /// Proof field, e.g. `input_hash` or `inputs.image`
+    pub field: String,
+    /// `None` for untagged commitments without `hash_alg`: the verifier's default applies
+    pub algorithm: Option<&'static str>,
+    pub value: String,
+}
+
+/// Someone the proof names as its signer
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Signer {
+    /// Where the identity comes from: a proof field, `signature` (recovered), `jws` or `cose`
+    pub source: &'static str,
+    pub algorithm: Option<&'static str>,
+    pub identity: Option<String>,
+}
+
+/// What `describe` found in a proof
+#[derive(Serialize, Debug, Clone)]
+pub struct ProofDescription {
+    format: &'static str,
+    compression: Option<&'static str>,
+    proof_id: String,
+    proof: ProofData,
+    commitments: Vec<Commitment>,
+    signers: Vec<Signer>,
+    age_ms: f64,
+    issues: Vec<String>,
+}
+
+impl ProofDescription {
+    /// Encoding the proof was decoded from: `json`, `binary`, `cbor`, `protobuf`, `jws` or `cose`
+    pub fn format(&self) -> &'static str {
+        self.format
+    }
+
+    /// `gzip` or `zstd` if the proof was compressed
+    pub fn compression(&self) -> Option<&'static str> {
+        self.compression
+    }
+
+    pub fn proof_id(&self) -> &str {
+        &self.proof_id
+    }
+
+    /// The decoded proof (the payload, for JWS and COSE envelopes)
+    pub fn proof(&self) -> &ProofData {
+        &self.proof
+    }
+
+    /// Every commitment with the algorithm it's checked with
+    pub fn commitments(&self) -> &[Commitment] {
+        &self.commitments
+    }
+
+    /// Keys, addresses, DIDs and certificate subjects the proof names as signers
+    pub fn signers(&self) -> &[Signer] {
+        &self.signers
+    }
+
+    /// Milliseconds since the proof's `timestamp`; negative if it's in the future
+    pub fn age_ms(&self) -> f64 {
+        self.age_ms
+    }
+
+    /// Schema problems: unknown fields, undecodable values, half-present signatures
+    pub fn issues(&self) -> &[String] {
+        &self.issues
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// A decoded proof, before it's described
+struct Decoded {
+    format: &'static str,
+    proof: ProofData,
+    /// The JSON it was parsed from, for the unknown-field check
+    json: Option<serde_json::Value>,
+    envelope: Option<Signer>,
+}
+
+fn from_json(format: &'static str, bytes: &[u8], envelope: Option<Signer>) -> Result<Decoded, DescribeError> {
+    let json: serde_json::Value =
+        serde_json::from_slice(bytes).map_err(|e| malformed(format!("Proof is not valid JSON: {}", e)))?;
+    let proof = serde_json::from_value(json.clone()).map_err(|e| malformed(format!("Invalid proof: {}", e)))?;
+    Ok(Decoded { format, proof, json: Some(json), envelope })
+}
+
+/// Decode `bytes` in whichever encoding its first bytes announce
+fn decode(bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    if bytes.is_empty() {
+        return Err(malformed("Proof is empty"));
+    }
+    let text = std::str::from_utf8(bytes).ok().map(str::trim);
+    if bytes.starts_with(&BINARY_MAGIC) {
+        let proof = ProofData::from_binary(bytes).map_err(|_| malformed("Invalid binary proof"))?;
+        return Ok(Decoded { format: "binary", proof, json: None, envelope: None });
+    }
+    if text.is_some_and(|text| text.starts_with('{')) {
+        return from_json("json", bytes, None);
+    }
+    if let Some(token) = text.filter(|t| t.split('.').count() == 3 && !t.contains(char::is_whitespace)) {
+        return decode_jws(token);
+    }
+    match bytes.first() {
+        // Tagged (18) or bare 4-element array: COSE_Sign1
+        Some(0xd2 | 0x84) => decode_cose(bytes),
+        // CBOR map
+        Some(0xa0..=0xbf) => decode_cbor(bytes),
+        _ => decode_protobuf(bytes),
+    }
+}
+
+#[cfg(feature = "signatures")]
+fn decode_jws(token: &str) -> Result<Decoded, DescribeError> {
+    let jws = crate::jws::Jws::parse(token).map_err(|(error, reason)| (error, reason.to_string()))?;
+    let envelope = Signer { source: "jws", algorithm: Some(jws.algorithm.name()), identity: jws.kid.clone() };
+    from_json("jws", &jws.payload, Some(envelope))
+}
+
+#[cfg(not(feature = "signatures"))]
+fn decode_jws(_token: &str) -> Result<Decoded, DescribeError> {
+    Err(malformed("JWS proofs are not enabled in this build"))
+}
+
+#[cfg(feature = "cbor")]
+fn decode_cbor(bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    let proof = ProofData::decode_cbor(bytes).map_err(|_| malformed("Invalid ProofData CBOR"))?;
+    Ok(Decoded { format: "cbor", proof, json: None, envelope: None })
+}
+
+#[cfg(not(feature = "cbor"))]
+fn decode_cbor(_bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    Err(malformed("CBOR proofs are not enabled in this build"))
+}
+
+#[cfg(feature = "signatures")]
+fn decode_cose(bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    let cose = crate::cose::CoseSign1::parse(bytes).map_err(|(error, reason)| (error, reason.to_string()))?;
+    let proof = cose.proof().map_err(|(error, reason)| (error, reason.to_string()))?;
+    let envelope = Signer { source: "cose", algorithm: Some(cose.algorithm.name()), identity: cose.kid.clone() };
+    Ok(Decoded { format: "cose", proof, json: None, envelope: Some(envelope) })
+}
+
+#[cfg(not(feature = "signatures"))]
+fn decode_cose(_bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    Err(malformed("COSE proofs are not enabled in this build"))
+}
+
+#[cfg(feature = "onnx")]
+fn decode_protobuf(bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    let proof = ProofData::decode_protobuf(bytes)
+        .map_err(|_| malformed("Not a JSON, binary, CBOR, protobuf, JWS or COSE proof"))?;
+    Ok(Decoded { format: "protobuf", proof, json: None, envelope: None })
+}
+
+#[cfg(not(feature = "onnx"))]
+fn decode_protobuf(_bytes: &[u8]) -> Result<Decoded, DescribeError> {
+    Err(malformed("Not a JSON, binary, CBOR, JWS or COSE proof (protobuf is not enabled in this build)"))
+}
+
+impl ProofData {
+    /// `(field, value)` of every commitment the proof carries
+    fn commitment_fields(&self) -> Vec<(String, &str)> {
+        let single = [
+            ("model_hash", Some(&self.model_hash)),
+            ("proof_hash", Some(&self.proof_hash)),
+            ("input_hash", Some(&self.input_hash)),
+            ("output_hash", Some(&self.output_hash)),
+            ("partial_model_hash", self.partial_model_hash.as_ref()),
+            ("quantization_hash", self.quantization_hash.as_ref()),
+            ("preprocessing_hash", self.preprocessing_hash.as_ref()),
+            ("architecture_hash", self.architecture_hash.as_ref()),
+            ("weights_hash", self.weights_hash.as_ref()),
+            ("metadata_hash", self.metadata_hash.as_ref()),
+        ];
+        let single = single.into_iter().filter_map(|(field, value)| Some((field.to_string(), value?.as_str())));
+        let inputs = self.inputs.iter().map(|(name, value)| (format!("inputs.{}", name), value.as_str()));
+        let outputs = self.outputs.iter().map(|(name, value)| (format!("outputs.{}", name), value.as_str()));
+        single.chain(inputs).chain(outputs).filter(|(_, value)| !value.is_empty()).collect()
+    }
+}
+
+fn describe_commitments(proof: &ProofData, issues: &mut Vec<String>) -> Vec<Commitment> {
+    let hash_alg = proof.hash_alg.as_deref().map(|name| (name, HashAlgorithm::from_name(name)));
+    if let Some((name, None)) = hash_alg {
+        issues.push(format!("hash_alg: unknown algorithm '{}'", name));
+    }
+    let default_alg = hash_alg.and_then(|(_, alg)| alg);
+    proof
+        .commitment_fields()
+        .into_iter()
+        .map(|(field, value)| {
+            let algorithm = match split_commitment(value) {
+                Ok((tag, digest)) => {
+                    if hex::decode(digest).is_none() {
+                        issues.push(format!("{}: digest is not hex", field));
+                    }
+                    tag.or(default_alg)
+                }
+                Err(_) => {
+                    issues.push(format!("{}: unknown algorithm tag", field));
+                    None
+                }
+            };
+            if let Some(algorithm) = algorithm.filter(|alg| !alg.is_available()) {
+                issues.push(format!("{}: {} is not enabled in this build", field, algorithm.name()));
+            }
+            Commitment { field, algorithm: algorithm.map(HashAlgorithm::name), value: value.to_string() }
+        })
+        .collect()
+}
+
+fn describe_signers(proof: &ProofData, envelope: Option<Signer>, issues: &mut Vec<String>) -> Vec<Signer> {
+    let algorithm = signature_algorithm(proof, issues);
+    let named = |source, identity: &Option<String>| {
+        identity.clone().map(|identity| Signer { source, algorithm, identity: Some(identity) })
+    };
+    let mut signers: Vec<Signer> = envelope.into_iter().collect();
+    signers.extend(named("signer_pubkey", &proof.signer_pubkey));
+    signers.extend(recovered_signer(proof, issues));
+    signers.extend(named("kid", &proof.kid));
+    signers.extend(named("prover_did", &proof.prover_did));
+    signers.extend(proof.cert_chain.first().and_then(|leaf| certificate_subject(leaf, issues)));
+    signers.extend(proof.attestor_pubkeys.iter().map(|key| Signer {
+        source: "attestor_pubkeys",
+        algorithm: Some("bls12-381"),
+        identity: Some(key.clone()),
+    }));
+
+    let signed = proof.signature.is_some();
+    let enveloped = signers.first().is_some_and(|s| matches!(s.source, "jws" | "cose"));
+    if signed && signers.is_empty() {
+        issues.push("signature: no signer_pubkey, kid, prover_did or cert_chain names the signer".to_string());
+    }
+    let named = [&proof.signer_pubkey, &proof.kid, &proof.prover_did, &proof.signature_alg];
+    if !signed && !enveloped && named.iter().any(|field| field.is_some()) {
+        issues.push("signature: the proof names a signer but carries no signature".to_string());
+    }
+    match (proof.attestor_pubkeys.is_empty(), proof.aggregate_signature.is_some()) {
+        (true, true) => issues.push("aggregate_signature: no attestor_pubkeys".to_string()),
+        (false, false) => issues.push("attestor_pubkeys: no aggregate_signature".to_string()),
+        _ => {}
+    }
+    signers
+}
+
+#[cfg(feature = "signatures")]
+fn signature_algorithm(proof: &ProofData, issues: &mut Vec<String>) -> Option<&'static str> {
+    use crate::eip712::{is_eip712, EIP712};
+    use crate::signature::SignatureAlgorithm;
+    let algorithm = SignatureAlgorithm::from_name(proof.signature_alg.as_deref()).map(SignatureAlgorithm::name);
+    let algorithm = algorithm.map(|name| if is_eip712(proof.signature_alg.as_deref()) { EIP712 } else { name });
+    if algorithm.is_none() {
+        issues.push(format!("signature_alg: unsupported '{}'", proof.signature_alg.as_deref().unwrap_or_default()));
+    }
+    algorithm
+}
+
+#[cfg(not(feature = "signatures"))]
+fn signature_algorithm(proof: &ProofData, issues: &mut Vec<String>) -> Option<&'static str> {
+    if proof.signature.is_some() {
+        issues.push("signature: signatures are not enabled in this build".to_string());
+    }
+    None
+}
+
+/// The Ethereum address a secp256k1 signature recovers to
+#[cfg(feature = "signatures")]
+fn recovered_signer(proof: &ProofData, issues: &mut Vec<String>) -> Option<Signer> {
+    use crate::signature::{recover_eth_address, SignatureAlgorithm};
+    let signature = proof.signature.as_deref()?;
+    // EIP-712 signatures recover to a different address under each domain
+    if SignatureAlgorithm::from_name(proof.signature_alg.as_deref()) != Some(SignatureAlgorithm::Secp256k1)
+        || crate::eip712::is_eip712(proof.signature_alg.as_deref())
+    {
+        return None;
+    }
+    match recover_eth_address(signature, &proof.signing_bytes()) {
+        Ok(address) => Some(Signer { source: "signature", algorithm: Some("secp256k1"), identity: Some(address) }),
+        Err((_, reason)) => {
+            issues.push(format!("signature: {}", reason));
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "signatures"))]
+fn recovered_signer(_proof: &ProofData, _issues: &mut [String]) -> Option<Signer> {
+    None
+}
+
+/// Subject of the leaf certificate, e.g. `CN=prover.example`
+#[cfg(feature = "signatures")]
+fn certificate_subject(leaf: &str, issues: &mut Vec<String>) -> Option<Signer> {
+    match crate::x509::parse_certificate(leaf) {
+        Ok(cert) => Some(Signer {
+            source: "cert_chain",
+            algorithm: None,
+            identity: Some(cert.tbs_certificate.subject.to_string()),
+        }),
+        Err((_, reason)) => {
+            issues.push(format!("cert_chain: {}", reason));
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "signatures"))]
+fn certificate_subject(_leaf: &str, _issues: &mut [String]) -> Option<Signer> {
+    None
+}
+
+/// Decode a proof in any encoding the verifier reads and list its fields, algorithms, signers, age and schema issues
+///
+/// Takes the raw bytes of a JSON, binary, CBOR, protobuf, JWS or COSE proof,
+/// optionally gzip- or zstd-compressed; its age is taken as of `now_ms`.
+/// Nothing is verified. Fails only if the bytes can't be decoded at all;
+/// anything odd about a decoded proof goes in `issues`.
+pub fn describe(bytes: &[u8], now_ms: u64) -> Result<ProofDescription, DescribeError> {
+    let compression = if bytes.starts_with(&GZIP_MAGIC) {
+        Some("gzip")
+    } else if bytes.starts_with(&ZSTD_MAGIC) {
+        Some("zstd")
+    } else {
+        None
+    };
+    let bytes =
+        decompress(bytes, DEFAULT_MAX_DECOMPRESSED_BYTES).map_err(|(error, reason)| (error, reason.to_string()))?;
+    let Decoded { format, proof, json, envelope } = decode(&bytes)?;
+
+    let mut issues = Vec::new();
+    if let Some(serde_json::Value::Object(fields)) = &json {
+        let unknown = fields.keys().filter(|key| !PROOF_FIELDS.contains(&key.as_str()));
+        issues.extend(unknown.map(|key| format!("{}: unknown field, ignored", key)));
+    }
+    if proof.model_hash.is_empty() {
+        issues.push("model_hash: missing".to_string());
+    }
+    let commitments = describe_commitments(&proof, &mut issues);
+    let signers = describe_signers(&proof, envelope, &mut issues);
+    if proof.timestamp > now_ms {
+        issues.push(format!("timestamp: {} ms in the future", proof.timestamp - now_ms));
+    }
+    if !proof.verified {
+        issues.push("verified: false, the prover's own check failed".to_string());
+    }
+    if !proof.partial_layers.is_empty() && proof.partial_model_hash.is_none() {
+        issues.push("partial_layers: no partial_model_hash".to_string());
+    }
+
+    Ok(ProofDescription {
+        format,
+        compression,
+        proof_id: proof.proof_id(),
+        age_ms: now_ms as f64 - proof.timestamp as f64,
+        proof,
+        commitments,
+        signers,
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hash_bytes;
+
+    fn proof() -> ProofData {
+        let mut proof = ProofData::new(
+            "0xmodel".to_string(),
+            "0xab".to_string(),
+            hash_bytes(b"in", HashAlgorithm::default()).unwrap(),
+            hash_bytes(b"out", HashAlgorithm::Sha256).unwrap(),
+            1_000,
+            true,
+        );
+        proof.hash_alg = Some("sha-256".to_string());
+        proof
+    }
+
+    #[test]
+    fn test_describe_formats() {
+        let proof = proof();
+        let encodings = [("json", proof.to_json().into_bytes()), ("binary", proof.to_binary())];
+        #[cfg(feature = "cbor")]
+        let encodings = encodings.into_iter().chain([("cbor", proof.encode_cbor())]);
+        for (format, bytes) in encodings {
+            let description = describe(&bytes, 61_000).unwrap();
+            assert_eq!((description.format, description.age_ms), (format, 60_000.0));
+            assert_eq!(description.proof_id, proof.proof_id());
+        }
+
+        let description = describe(&proof.to_json().into_bytes(), 0).unwrap();
+        let algorithms: Vec<_> = description.commitments.iter().map(|c| (c.field.as_str(), c.algorithm)).collect();
+        assert_eq!(algorithms[0], ("model_hash", Some("sha-256")));
+        assert!(description.issues.iter().any(|i| i == "model_hash: digest is not hex"));
+        assert!(description.issues.iter().any(|i| i.starts_with("timestamp: 1000 ms in the future")));
+        assert!(description.signers.is_empty());
+
+        assert!(describe(b"{\"model_hash\": 1}", 0).unwrap_err().1.starts_with("Invalid proof"));
+        assert_eq!(describe(b"", 0).unwrap_err(), malformed("Proof is empty"));
+    }
+
+    #[test]
+    fn test_describe_schema_issues() {
+        let mut json = serde_json::to_value(proof()).unwrap();
+        json["signer_pubkey"] = "ab".repeat(32).into();
+        json["modle_version"] = "1.0".into();
+        let description = describe(json.to_string().as_bytes(), 1_000).unwrap();
+        assert_eq!(description.signers[0].source, "signer_pubkey");
+        assert!(description.issues.contains(&"modle_version: unknown field, ignored".to_string()));
+        assert!(description.issues.iter().any(|i| i.contains("carries no signature")));
+    }
+}
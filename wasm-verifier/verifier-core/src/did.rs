@@ -0,0 +1,178 @@
+// DID prover identifiers
+// Resolves `did:key` locally and `did:web` through a host fetch callback
+
+use serde_json::Value;
+
+use crate::error::VerificationError;
+use crate::host::Fetcher;
+use crate::jwks::{self, Jwk, ResolvedKey};
+use crate::signature::{self, Failure, SignatureAlgorithm};
+use crate::{hex, Verifier};
+
+/// Multicodec prefix of an Ed25519 public key (`ed25519-pub`, varint 0xed)
+pub const ED25519_PUB: [u8; 2] = [0xed, 0x01];
+/// Multicodec prefix of a compressed secp256k1 public key (`secp256k1-pub`, varint 0xe7)
+const SECP256K1_PUB: [u8; 2] = [0xe7, 0x01];
+
+fn unresolvable(reason: &'static str) -> Failure {
+    (VerificationError::DidUnresolvable, reason)
+}
+
+/// Resolve `did` to the keys that may sign for it under `algorithm`
+///
+/// A `#fragment` selects a single verification method; otherwise every key
+/// listed under `assertionMethod` (or all keys, if there is none) is returned.
+pub fn resolve(
+    did: &str,
+    algorithm: SignatureAlgorithm,
+    fetcher: Option<&Fetcher>,
+) -> Result<Vec<String>, Failure> {
+    let (base, fragment) = split_fragment(did);
+
+    let keys = if let Some(multikey) = base.strip_prefix("did:key:") {
+        vec![decode_multikey(multikey).ok_or(unresolvable("did:key is not a supported Ed25519 or secp256k1 key"))?]
+    } else if base.starts_with("did:web:") {
+        let url = web_document_url(base).ok_or(unresolvable("did:web identifier is malformed"))?;
+        let fetcher = fetcher.ok_or(unresolvable("Resolving did:web requires a DID fetcher"))?;
+        let document = fetcher.fetch(&url).ok_or(unresolvable("DID fetcher did not return a document"))?;
+        document_keys(&document, base, fragment)?
+    } else {
+        return Err(unresolvable("Only did:key and did:web are supported"));
+    };
+
+    let keys: Vec<String> = keys.into_iter().filter(|k| k.algorithm == algorithm).map(|k| k.key).collect();
+    if keys.is_empty() {
+        return Err(unresolvable("DID has no key for the proof's signature_alg"));
+    }
+    Ok(keys)
+}
+
+/// Strip the DID URL fragment (`did:...#key-1`)
+pub fn split_fragment(did: &str) -> (&str, Option<&str>) {
+    match did.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (did, None),
+    }
+}
+
+/// Decode a multibase (base58btc, `z`) multicodec public key
+fn decode_multikey(multikey: &str) -> Option<ResolvedKey> {
+    let bytes = bs58::decode(multikey.strip_prefix('z')?).into_vec().ok()?;
+    if let Some(key) = bytes.strip_prefix(&ED25519_PUB).filter(|key| key.len() == 32) {
+        Some(ResolvedKey { algorithm: SignatureAlgorithm::Ed25519, key: hex::encode(key) })
+    } else if let Some(key) = bytes.strip_prefix(&SECP256K1_PUB) {
+        let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(key).ok()?;
+        Some(ResolvedKey { algorithm: SignatureAlgorithm::Secp256k1, key: signature::eth_address(&key) })
+    } else {
+        None
+    }
+}
+
+/// HTTPS URL of a `did:web` document, per the did:web method spec
+fn web_document_url(did: &str) -> Option<String> {
+    let mut segments = did.strip_prefix("did:web:")?.split(':');
+    let domain = segments.next().filter(|d| !d.is_empty())?.replace("%3A", ":");
+    let path: Vec<&str> = segments.collect();
+    if path.iter().any(|s| s.is_empty() || s.contains('/')) || domain.contains('/') {
+        return None;
+    }
+
+    Some(if path.is_empty() {
+        format!("https://{domain}/.well-known/did.json")
+    } else {
+        format!("https://{domain}/{}/did.json", path.join("/"))
+    })
+}
+
+/// Keys of a DID document usable for proof signatures
+fn document_keys(document: &str, did: &str, fragment: Option<&str>) -> Result<Vec<ResolvedKey>, Failure> {
+    let document: Value = serde_json::from_str(document).map_err(|_| unresolvable("DID document is not valid JSON"))?;
+    if document.get("id").and_then(Value::as_str) != Some(did) {
+        return Err(unresolvable("DID document id does not match the DID"));
+    }
+
+    let method_id = |m: &Value| m.get("id").and_then(Value::as_str).map(str::to_string);
+    let is_id = |id: &str, wanted: &str| id == wanted || id == format!("{did}#{}", wanted.trim_start_matches('#'));
+    let assertion: Option<Vec<&Value>> =
+        document.get("assertionMethod").and_then(Value::as_array).map(|refs| refs.iter().collect());
+
+    let methods = document.get("verificationMethod").and_then(Value::as_array).into_iter().flatten();
+    let embedded = assertion.iter().flatten().copied().filter(|m| m.is_object());
+
+    Ok(methods
+        .chain(embedded)
+        .filter(|m| {
+            let Some(id) = method_id(m) else { return false };
+            match (fragment, &assertion) {
+                (Some(fragment), _) => is_id(&id, fragment),
+                (None, Some(refs)) => refs.iter().any(|r| match r {
+                    Value::String(r) => is_id(&id, r) || id == *r,
+                    _ => method_id(r).as_deref() == Some(&id),
+                }),
+                (None, None) => true,
+            }
+        })
+        .filter_map(method_key)
+        .collect())
+}
+
+fn method_key(method: &Value) -> Option<ResolvedKey> {
+    if let Some(jwk) = method.get("publicKeyJwk") {
+        jwks::resolve_jwk(&serde_json::from_value::<Jwk>(jwk.clone()).ok()?)
+    } else {
+        decode_multikey(method.get("publicKeyMultibase")?.as_str()?)
+    }
+}
+
+impl Verifier {
+    /// Callback returning `did:web` documents by URL
+    ///
+    /// Must return the DID document JSON synchronously, e.g. from a cache
+    /// the host fills asynchronously.
+    pub fn set_did_fetcher(&mut self, fetcher: Option<Fetcher>) {
+        self.did_fetcher = fetcher;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_did_key() {
+        let pubkey = [7u8; 32];
+        let did = format!("did:key:z{}", bs58::encode([&ED25519_PUB[..], &pubkey].concat()).into_string());
+
+        assert_eq!(resolve(&did, SignatureAlgorithm::Ed25519, None).unwrap(), vec![hex::encode(&pubkey)]);
+        assert_eq!(
+            resolve(&did, SignatureAlgorithm::Secp256k1, None).unwrap_err().0,
+            VerificationError::DidUnresolvable
+        );
+        assert!(resolve("did:web:example.com", SignatureAlgorithm::Ed25519, None).is_err());
+    }
+
+    #[test]
+    fn test_did_web_document() {
+        assert_eq!(web_document_url("did:web:example.com").unwrap(), "https://example.com/.well-known/did.json");
+        assert_eq!(
+            web_document_url("did:web:localhost%3A8443:provers:a").unwrap(),
+            "https://localhost:8443/provers/a/did.json"
+        );
+
+        let doc = r##"{
+            "id": "did:web:example.com",
+            "verificationMethod": [
+                {"id": "did:web:example.com#sig", "type": "JsonWebKey2020",
+                 "publicKeyJwk": {"kty": "OKP", "crv": "Ed25519", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}},
+                {"id": "did:web:example.com#auth", "type": "Multikey",
+                 "publicKeyMultibase": "z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"}
+            ],
+            "assertionMethod": ["#sig"]
+        }"##;
+        let keys = document_keys(doc, "did:web:example.com", None).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        assert_eq!(document_keys(doc, "did:web:example.com", Some("auth")).unwrap().len(), 1);
+        assert!(document_keys(doc, "did:web:other.org", None).is_err());
+    }
+}
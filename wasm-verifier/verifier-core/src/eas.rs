@@ -0,0 +1,176 @@
+// EAS attestations
+// Turns a passed verification into an Ethereum Attestation Service `attest` request
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::error::{Failure, VerificationError};
+use crate::evm::{bytes32, uint};
+use crate::report::VerificationReport;
+use crate::{hex, ProofData, Verifier};
+
+/// Schema of verified-inference attestations (times in Unix ms)
+pub const EAS_SCHEMA: &str = "bytes32 modelHash,bytes32 inputHash,bytes32 outputHash,bytes32 proofHash,\
+                              bytes32 proofId,uint64 provenAt,uint64 verifiedAt";
+
+/// UID EAS's SchemaRegistry gives `EAS_SCHEMA` registered with `resolver`
+pub fn schema_uid(resolver: Option<&str>, revocable: bool) -> Result<String, Failure> {
+    let resolver = match resolver {
+        Some(resolver) => address(resolver, "Resolver is not an Ethereum address")?,
+        None => [0u8; 20],
+    };
+    let uid = Keccak256::digest([EAS_SCHEMA.as_bytes(), &resolver, &[revocable as u8]].concat());
+    Ok(format!("0x{}", hex::encode(&uid)))
+}
+
+fn address(address: &str, reason: &'static str) -> Result<[u8; 20], Failure> {
+    let bytes = hex::decode(address).ok_or((VerificationError::InvalidArgument, reason))?;
+    bytes.try_into().map_err(|_| (VerificationError::InvalidArgument, reason))
+}
+
+/// An `attest` request, ready for the EAS SDK or contract
+///
+/// `to_json` gives the `AttestationRequest` shape:
+///
+/// ```json
+/// { "schema": "0x…", "data": { "recipient": "0x…", "expirationTime": 0, "revocable": true,
+///   "refUID": "0x00…", "data": "0x…", "value": 0 } }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EasAttestation {
+    schema: String,
+    data: AttestationData,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct AttestationData {
+    recipient: String,
+    expiration_time: u64,
+    revocable: bool,
+    #[serde(rename = "refUID")]
+    ref_uid: String,
+    /// ABI-encoded `EAS_SCHEMA` fields
+    data: String,
+    value: u64,
+}
+
+impl EasAttestation {
+    /// UID of the schema the data is encoded with
+    pub fn schema_uid(&self) -> &str {
+        &self.schema
+    }
+
+    /// ABI-encoded attestation data (hex)
+    pub fn data(&self) -> &str {
+        &self.data.data
+    }
+
+    /// Address the attestation is about (zero address if none)
+    pub fn recipient(&self) -> &str {
+        &self.data.recipient
+    }
+
+    pub fn revocable(&self) -> bool {
+        self.data.revocable
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl Verifier {
+    /// EAS attestation request for a passed verification of `proof_data` at `now`
+    ///
+    /// The data encodes `EAS_SCHEMA`: the proof's commitments, its ID, when
+    /// it was proven and when it was verified. `schema` defaults to the
+    /// schema registered revocable with no resolver. Fails with
+    /// `InvalidArgument` if the report did not pass, an address is malformed
+    /// or a commitment isn't a 32-byte digest.
+    pub fn attestation(
+        &self,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+        recipient: Option<&str>,
+        schema: Option<&str>,
+        now: u64,
+    ) -> Result<EasAttestation, Failure> {
+        if !report.passed() {
+            return Err((VerificationError::InvalidArgument, "Only passed verifications get attestations"));
+        }
+        let recipient = match recipient {
+            Some(recipient) => address(recipient, "Recipient is not an Ethereum address")?,
+            None => [0u8; 20],
+        };
+        let schema = match schema {
+            Some(schema) => format!("0x{}", hex::encode(&bytes32(schema, "Schema UID is not 32 bytes of hex")?)),
+            None => schema_uid(None, true)?,
+        };
+        let fields = [
+            bytes32(&proof_data.model_hash, "model_hash is not a 32-byte digest")?,
+            bytes32(&proof_data.input_hash, "input_hash is not a 32-byte digest")?,
+            bytes32(&proof_data.output_hash, "output_hash is not a 32-byte digest")?,
+            bytes32(&proof_data.proof_hash, "proof_hash is not a 32-byte digest")?,
+            bytes32(&proof_data.proof_id(), "proof_id is not a 32-byte digest")?,
+            uint(proof_data.timestamp),
+            uint(now),
+        ];
+        Ok(EasAttestation {
+            schema,
+            data: AttestationData {
+                recipient: format!("0x{}", hex::encode(&recipient)),
+                expiration_time: 0,
+                revocable: true,
+                ref_uid: format!("0x{}", hex::encode(&[0u8; 32])),
+                data: format!("0x{}", hex::encode(&fields.concat())),
+                value: 0,
+            },
+        })
+    }
+
+    /// `attestation` verified now, by this verifier's clock
+    pub fn eas_attestation(
+        &self,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+        recipient: Option<&str>,
+        schema_uid: Option<&str>,
+    ) -> Result<EasAttestation, Failure> {
+        self.attestation(proof_data, report, recipient, schema_uid, self.now_ms())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+    use crate::hash::{hash_bytes, HashAlgorithm};
+
+    #[test]
+    fn test_eas_attestation() {
+        let hash = |data: &[u8]| hash_bytes(data, HashAlgorithm::default()).unwrap();
+        let verifier = Verifier::new(hash(b"model"));
+        let proof = ProofData::new(hash(b"model"), hash(b"proof"), hash(b"in"), hash(b"out"), system_now_ms(), true);
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        let recipient = format!("0x{}", "11".repeat(20));
+        let attestation = verifier.attestation(&proof, &report, Some(&recipient), None, 1_700_000_000_500).unwrap();
+
+        assert_eq!(attestation.schema_uid(), schema_uid(None, true).unwrap());
+        assert_ne!(attestation.schema_uid(), schema_uid(Some(&recipient), true).unwrap());
+        let data = hex::decode(attestation.data()).unwrap();
+        assert_eq!(data.len(), 7 * 32);
+        assert_eq!(data[4 * 32..5 * 32], bytes32(&proof.proof_id(), "").unwrap());
+        assert_eq!(data[5 * 32..6 * 32], uint(proof.timestamp));
+        assert_eq!(data[6 * 32..], uint(1_700_000_000_500));
+        let json: serde_json::Value = serde_json::from_str(&attestation.to_json()).unwrap();
+        assert_eq!((&json["data"]["recipient"], &json["data"]["revocable"]), (&recipient.into(), &true.into()));
+        assert_eq!(json["data"]["refUID"].as_str().unwrap().len(), 66);
+
+        let failed = verifier.verify_report(&proof, b"in", b"tampered");
+        let error = verifier.attestation(&proof, &failed, None, None, 0).unwrap_err();
+        assert_eq!(error.1, "Only passed verifications get attestations");
+        let error = verifier.attestation(&proof, &report, Some("0x1234"), None, 0).unwrap_err();
+        assert_eq!(error, (VerificationError::InvalidArgument, "Recipient is not an Ethereum address"));
+    }
+}
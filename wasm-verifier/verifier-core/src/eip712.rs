@@ -0,0 +1,290 @@
+// EIP-712 proof signatures
+// `signature_alg = "eip712"`: wallet signatures (`eth_signTypedData_v4`) over a proof as typed data
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha3::{Digest, Keccak256};
+
+use crate::error::{Failure, VerificationError};
+use crate::evm::{bytes32, uint};
+use crate::signature::{self, recover_prehashed};
+use crate::{hex, jcs, ProofData, Verifier};
+
+/// `signature_alg` of typed-data signed proofs
+pub const EIP712: &str = "eip712";
+
+/// Primary type; fields past `nonce` commit to the rest of the proof
+const PROOF_TYPE: &str = "Proof(bytes32 modelHash,bytes32 proofHash,bytes32 inputHash,bytes32 outputHash,\
+                          uint64 timestamp,bool verified,bytes32 nonce,bytes32 extensionsHash)";
+
+/// Proof fields with a typed-data member of their own
+const TYPED_FIELDS: [&str; 7] =
+    ["model_hash", "proof_hash", "input_hash", "output_hash", "timestamp", "verified", "nonce"];
+
+/// Whether a proof's `signature_alg` names EIP-712
+pub fn is_eip712(signature_alg: Option<&str>) -> bool {
+    signature_alg.is_some_and(|name| name.eq_ignore_ascii_case(EIP712))
+}
+
+fn keccak(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+/// EIP-712 signing domain; defaults to `{ name: "zkML Proof", version: "1" }`
+///
+/// Contracts that check proof signatures should pin `chain_id` and
+/// `verifying_contract` so signatures can't be replayed elsewhere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Eip712Domain {
+    name: String,
+    version: String,
+    chain_id: Option<u64>,
+    verifying_contract: Option<[u8; 20]>,
+}
+
+impl Default for Eip712Domain {
+    fn default() -> Self {
+        Eip712Domain {
+            name: "zkML Proof".to_string(),
+            version: "1".to_string(),
+            chain_id: None,
+            verifying_contract: None,
+        }
+    }
+}
+
+impl Eip712Domain {
+    /// `(name, type)` of the domain fields that are set, in EIP-712 order
+    pub fn fields(&self) -> Vec<(&'static str, &'static str)> {
+        let mut fields = vec![("name", "string"), ("version", "string")];
+        if self.chain_id.is_some() {
+            fields.push(("chainId", "uint256"));
+        }
+        if self.verifying_contract.is_some() {
+            fields.push(("verifyingContract", "address"));
+        }
+        fields
+    }
+
+    pub fn separator(&self) -> [u8; 32] {
+        let types: Vec<String> = self.fields().iter().map(|(name, kind)| format!("{} {}", kind, name)).collect();
+        let mut encoded = keccak(format!("EIP712Domain({})", types.join(",")).as_bytes()).to_vec();
+        encoded.extend_from_slice(&keccak(self.name.as_bytes()));
+        encoded.extend_from_slice(&keccak(self.version.as_bytes()));
+        if let Some(chain_id) = self.chain_id {
+            encoded.extend_from_slice(&uint(chain_id));
+        }
+        if let Some(contract) = self.verifying_contract {
+            encoded.extend_from_slice(&[[0u8; 12].as_slice(), &contract].concat());
+        }
+        keccak(&encoded)
+    }
+
+    pub fn to_value(&self) -> serde_json::Value {
+        let mut domain = json!({ "name": self.name, "version": self.version });
+        if let Some(chain_id) = self.chain_id {
+            domain["chainId"] = chain_id.into();
+        }
+        if let Some(contract) = self.verifying_contract {
+            domain["verifyingContract"] = signature::to_checksum_address(&contract).into();
+        }
+        domain
+    }
+}
+
+impl Eip712Domain {
+    /// Domain with the given fields (`name` and `version` default as above)
+    ///
+    /// Fails with `InvalidArgument` if `verifying_contract` isn't a 20-byte hex address.
+    pub fn try_new(
+        name: Option<String>,
+        version: Option<String>,
+        chain_id: Option<u64>,
+        verifying_contract: Option<&str>,
+    ) -> Result<Eip712Domain, Failure> {
+        let invalid = (VerificationError::InvalidArgument, "verifying_contract is not an Ethereum address");
+        let verifying_contract = match verifying_contract {
+            Some(contract) => Some(hex::decode(contract).and_then(|bytes| bytes.try_into().ok()).ok_or(invalid)?),
+            None => None,
+        };
+        let default = Eip712Domain::default();
+        Ok(Eip712Domain {
+            name: name.unwrap_or(default.name),
+            version: version.unwrap_or(default.version),
+            chain_id,
+            verifying_contract,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+
+    /// Checksummed address of the verifying contract
+    pub fn verifying_contract(&self) -> Option<String> {
+        self.verifying_contract.map(|contract| signature::to_checksum_address(&contract))
+    }
+}
+
+impl ProofData {
+    /// Members of the `Proof` typed-data message
+    pub fn typed_message(&self) -> Result<serde_json::Value, Failure> {
+        let word = |commitment: &str, reason| bytes32(commitment, reason).map(|w| format!("0x{}", hex::encode(&w)));
+        let nonce = match &self.nonce {
+            Some(nonce) => word(nonce, "nonce is not 32 bytes of hex")?,
+            None => format!("0x{}", hex::encode(&[0u8; 32])),
+        };
+        Ok(json!({
+            "modelHash": word(&self.model_hash, "model_hash is not a 32-byte digest")?,
+            "proofHash": word(&self.proof_hash, "proof_hash is not a 32-byte digest")?,
+            "inputHash": word(&self.input_hash, "input_hash is not a 32-byte digest")?,
+            "outputHash": word(&self.output_hash, "output_hash is not a 32-byte digest")?,
+            "timestamp": self.timestamp,
+            "verified": self.verified,
+            "nonce": nonce,
+            "extensionsHash": format!("0x{}", hex::encode(&self.extensions_hash())),
+        }))
+    }
+
+    /// Keccak-256 of the JCS of the signed fields without a typed member (zero if there are none)
+    pub fn extensions_hash(&self) -> [u8; 32] {
+        let mut value: serde_json::Value = serde_json::from_slice(&self.signing_bytes()).unwrap_or_default();
+        let Some(fields) = value.as_object_mut() else {
+            return [0u8; 32];
+        };
+        fields.retain(|name, _| !TYPED_FIELDS.contains(&name.as_str()) && name != "signature_alg");
+        if fields.is_empty() {
+            return [0u8; 32];
+        }
+        keccak(jcs::canonicalize(&value).as_bytes())
+    }
+
+    /// Digest a wallet signs for this proof under `domain`
+    pub fn eip712_digest(&self, domain: &Eip712Domain) -> Result<[u8; 32], Failure> {
+        let message = self.typed_message()?;
+        let mut encoded = keccak(PROOF_TYPE.as_bytes()).to_vec();
+        for (name, kind) in proof_members() {
+            let word = match kind {
+                "uint64" => uint(message[name].as_u64().unwrap_or_default()),
+                "bool" => uint(message[name].as_bool().unwrap_or_default() as u64),
+                _ => bytes32(message[name].as_str().unwrap_or_default(), "")?,
+            };
+            encoded.extend_from_slice(&word);
+        }
+        let struct_hash = keccak(&encoded);
+        Ok(keccak(&[&[0x19, 0x01][..], &domain.separator(), &struct_hash].concat()))
+    }
+}
+
+/// `(name, type)` of the `Proof` members, from `PROOF_TYPE`
+pub fn proof_members() -> impl Iterator<Item = (&'static str, &'static str)> {
+    let members = PROOF_TYPE.trim_start_matches("Proof(").trim_end_matches(')');
+    members.split(',').filter_map(|member| member.split_once(' ').map(|(kind, name)| (name, kind)))
+}
+
+impl ProofData {
+    /// Typed data to sign with a wallet, as JSON for `eth_signTypedData_v4`
+    ///
+    /// The `Proof` message carries the commitments, timestamp, `verified`
+    /// and nonce, plus `extensionsHash` over every other signed field.
+    /// Fails with `InvalidArgument` unless the commitments are 32-byte digests.
+    pub fn eip712_typed_data(&self, domain: &Eip712Domain) -> Result<String, Failure> {
+        let message = self.typed_message()?;
+        let member = |(name, kind): (&str, &str)| json!({ "name": name, "type": kind });
+        let typed_data = json!({
+            "types": {
+                "EIP712Domain": domain.fields().into_iter().map(member).collect::<Vec<_>>(),
+                "Proof": proof_members().map(member).collect::<Vec<_>>(),
+            },
+            "primaryType": "Proof",
+            "domain": domain.to_value(),
+            "message": message,
+        });
+        Ok(typed_data.to_string())
+    }
+}
+
+impl Verifier {
+    /// Address an `eip712` proof signature recovers to under this verifier's domain
+    pub fn recover_eip712(&self, proof_data: &ProofData, signature: &str) -> Result<String, Failure> {
+        let digest = proof_data.eip712_digest(&self.eip712_domain)?;
+        recover_prehashed(signature, &digest)
+    }
+}
+
+impl Verifier {
+    /// Domain `eip712` proof signatures are checked under (`None` resets the default)
+    pub fn set_eip712_domain(&mut self, domain: Option<Eip712Domain>) {
+        self.eip712_domain = domain.unwrap_or_default();
+    }
+
+    pub fn eip712_domain(&self) -> &Eip712Domain {
+        &self.eip712_domain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+    use crate::hash::{hash_bytes, HashAlgorithm};
+
+    fn hash_data(data: &[u8]) -> String {
+        hash_bytes(data, HashAlgorithm::default()).unwrap()
+    }
+
+    fn sign(key: &k256::ecdsa::SigningKey, digest: &[u8]) -> String {
+        let (signature, recovery_id) = key.sign_prehash_recoverable(digest).unwrap();
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte() + 27);
+        hex::encode(&bytes)
+    }
+
+    #[test]
+    fn test_eip712() {
+        // Domain separator of the EIP-712 reference example ("Ether Mail")
+        let contract = Some("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC");
+        let mail = Eip712Domain::try_new(Some("Ether Mail".into()), None, Some(1), contract).unwrap();
+        let expected = "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f";
+        assert_eq!(hex::encode(&mail.separator()), expected);
+
+        let key = k256::ecdsa::SigningKey::from_slice(&[5u8; 32]).unwrap();
+        let address = signature::eth_address(key.verifying_key());
+        let mut verifier = Verifier::new(hash_data(b"model"));
+        verifier.add_trusted_signer(address.clone());
+        let mut proof = ProofData::new(
+            hash_data(b"model"),
+            hash_data(b"proof"),
+            hash_data(b"in"),
+            hash_data(b"out"),
+            system_now_ms(),
+            true,
+        );
+        proof.signature_alg = Some(EIP712.to_string());
+        proof.signer_pubkey = Some(address);
+        proof.signature = Some(sign(&key, &proof.eip712_digest(&Eip712Domain::default()).unwrap()));
+        assert!(verifier.verify_report(&proof, b"in", b"out").passed());
+
+        // Signed for another contract, or over a field outside the typed members
+        verifier.set_eip712_domain(Some(mail));
+        assert_eq!(verifier.verify_report(&proof, b"in", b"out").error(), Some(VerificationError::SignatureInvalid));
+        verifier.set_eip712_domain(None);
+        proof.model_version = Some("2.0.0".to_string());
+        assert_eq!(verifier.verify_report(&proof, b"in", b"out").error(), Some(VerificationError::SignatureInvalid));
+
+        let typed_data = proof.eip712_typed_data(&Eip712Domain::default()).unwrap();
+        let typed_data: serde_json::Value = serde_json::from_str(&typed_data).unwrap();
+        assert_eq!(typed_data["types"]["Proof"].as_array().unwrap().len(), 8);
+        assert_eq!(typed_data["domain"], json!({ "name": "zkML Proof", "version": "1" }));
+        assert_eq!(typed_data["message"]["extensionsHash"], format!("0x{}", hex::encode(&proof.extensions_hash())));
+        assert_eq!(typed_data["message"]["nonce"], format!("0x{}", "00".repeat(32)));
+    }
+}
@@ -0,0 +1,179 @@
+// Verification error codes
+// Stable numeric codes so JS callers can branch on failure type
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Why a proof was rejected
+///
+/// Discriminants are part of the public API and must never be renumbered;
+/// new variants get new codes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum VerificationError {
+    ModelMismatch = 1,
+    InputHashMismatch = 2,
+    OutputHashMismatch = 3,
+    Expired = 4,
+    NotVerified = 5,
+    ProofHashMismatch = 6,
+    MalformedProof = 7,
+    InvalidArgument = 8,
+    UnsupportedHashAlgorithm = 9,
+    NonceMismatch = 10,
+    NonceReplayed = 11,
+    SignatureInvalid = 12,
+    UntrustedSigner = 13,
+    AggregateSignatureInvalid = 14,
+    InsufficientAttestors = 15,
+    CertificateChainInvalid = 16,
+    DidUnresolvable = 17,
+    PayloadTooLarge = 18,
+    InvalidModel = 19,
+    PolicyViolation = 20,
+    QuantizationMismatch = 21,
+    ShapeMismatch = 22,
+    PreprocessingMismatch = 23,
+    RecomputationMismatch = 24,
+    Revoked = 25,
+    VersionNotAllowed = 26,
+    CompositeInvalid = 27,
+    InclusionInvalid = 28,
+    ToleranceExceeded = 29,
+    ClassMismatch = 30,
+    ProofUnavailable = 31,
+    Aborted = 32,
+    AnchorInvalid = 33,
+    NotRegistered = 34,
+}
+
+impl VerificationError {
+    /// Every variant, in code order
+    pub const ALL: [VerificationError; 34] = [
+        VerificationError::ModelMismatch,
+        VerificationError::InputHashMismatch,
+        VerificationError::OutputHashMismatch,
+        VerificationError::Expired,
+        VerificationError::NotVerified,
+        VerificationError::ProofHashMismatch,
+        VerificationError::MalformedProof,
+        VerificationError::InvalidArgument,
+        VerificationError::UnsupportedHashAlgorithm,
+        VerificationError::NonceMismatch,
+        VerificationError::NonceReplayed,
+        VerificationError::SignatureInvalid,
+        VerificationError::UntrustedSigner,
+        VerificationError::AggregateSignatureInvalid,
+        VerificationError::InsufficientAttestors,
+        VerificationError::CertificateChainInvalid,
+        VerificationError::DidUnresolvable,
+        VerificationError::PayloadTooLarge,
+        VerificationError::InvalidModel,
+        VerificationError::PolicyViolation,
+        VerificationError::QuantizationMismatch,
+        VerificationError::ShapeMismatch,
+        VerificationError::PreprocessingMismatch,
+        VerificationError::RecomputationMismatch,
+        VerificationError::Revoked,
+        VerificationError::VersionNotAllowed,
+        VerificationError::CompositeInvalid,
+        VerificationError::InclusionInvalid,
+        VerificationError::ToleranceExceeded,
+        VerificationError::ClassMismatch,
+        VerificationError::ProofUnavailable,
+        VerificationError::Aborted,
+        VerificationError::AnchorInvalid,
+        VerificationError::NotRegistered,
+    ];
+
+    /// Stable numeric code
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Variant with this code, `None` for codes not (yet) assigned
+    pub fn from_code(code: u32) -> Option<VerificationError> {
+        VerificationError::ALL.into_iter().find(|e| e.code() == code)
+    }
+
+    /// Variant name, used as `kind` on JS error objects
+    pub fn name(self) -> &'static str {
+        match self {
+            VerificationError::ModelMismatch => "ModelMismatch",
+            VerificationError::InputHashMismatch => "InputHashMismatch",
+            VerificationError::OutputHashMismatch => "OutputHashMismatch",
+            VerificationError::Expired => "Expired",
+            VerificationError::NotVerified => "NotVerified",
+            VerificationError::ProofHashMismatch => "ProofHashMismatch",
+            VerificationError::MalformedProof => "MalformedProof",
+            VerificationError::InvalidArgument => "InvalidArgument",
+            VerificationError::UnsupportedHashAlgorithm => "UnsupportedHashAlgorithm",
+            VerificationError::NonceMismatch => "NonceMismatch",
+            VerificationError::NonceReplayed => "NonceReplayed",
+            VerificationError::SignatureInvalid => "SignatureInvalid",
+            VerificationError::UntrustedSigner => "UntrustedSigner",
+            VerificationError::AggregateSignatureInvalid => "AggregateSignatureInvalid",
+            VerificationError::InsufficientAttestors => "InsufficientAttestors",
+            VerificationError::CertificateChainInvalid => "CertificateChainInvalid",
+            VerificationError::DidUnresolvable => "DidUnresolvable",
+            VerificationError::PayloadTooLarge => "PayloadTooLarge",
+            VerificationError::InvalidModel => "InvalidModel",
+            VerificationError::PolicyViolation => "PolicyViolation",
+            VerificationError::QuantizationMismatch => "QuantizationMismatch",
+            VerificationError::ShapeMismatch => "ShapeMismatch",
+            VerificationError::PreprocessingMismatch => "PreprocessingMismatch",
+            VerificationError::RecomputationMismatch => "RecomputationMismatch",
+            VerificationError::Revoked => "Revoked",
+            VerificationError::VersionNotAllowed => "VersionNotAllowed",
+            VerificationError::CompositeInvalid => "CompositeInvalid",
+            VerificationError::InclusionInvalid => "InclusionInvalid",
+            VerificationError::ToleranceExceeded => "ToleranceExceeded",
+            VerificationError::ClassMismatch => "ClassMismatch",
+            VerificationError::ProofUnavailable => "ProofUnavailable",
+            VerificationError::Aborted => "Aborted",
+            VerificationError::AnchorInvalid => "AnchorInvalid",
+            VerificationError::NotRegistered => "NotRegistered",
+        }
+    }
+
+    /// Build a JS `Error` carrying `code` and `kind` properties
+    ///
+    /// `err.name` is always `"VerificationError"`, so callers can
+    /// `if (e.name === "VerificationError" && e.code === VerificationError.Expired)`.
+    #[cfg(feature = "wasm")]
+    pub fn to_js(self, message: &str) -> JsValue {
+        let err = js_sys::Error::new(message);
+        err.set_name("VerificationError");
+        let _ = js_sys::Reflect::set(&err, &"code".into(), &JsValue::from(self.code()));
+        let _ = js_sys::Reflect::set(&err, &"kind".into(), &self.name().into());
+        err.into()
+    }
+}
+
+/// Why a check, decode or limit failed: its error and a human-readable reason
+pub type Failure = (VerificationError, &'static str);
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (code {})", self.name(), self.code())
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_round_trip() {
+        for (i, error) in VerificationError::ALL.into_iter().enumerate() {
+            assert_eq!(error.code(), i as u32 + 1);
+            assert_eq!(VerificationError::from_code(error.code()), Some(error));
+        }
+        assert_eq!(VerificationError::from_code(0), None);
+        assert_eq!(VerificationError::from_code(VerificationError::ALL.len() as u32 + 1), None);
+    }
+}
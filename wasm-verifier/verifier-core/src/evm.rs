@@ -0,0 +1,100 @@
+// EVM ABI words and calldata
+// 32-byte ABI encodings shared by EIP-712 digests and registry calls, and a proof's `verifyInference` calldata
+
+use sha3::{Digest, Keccak256};
+
+use crate::error::{Failure, VerificationError};
+use crate::hash::split_commitment;
+use crate::{hex, ProofData};
+
+/// Function `ProofData::evm_calldata` encodes a call to
+pub const EVM_VERIFY_SIGNATURE: &str = "verifyInference(bytes32,bytes32,bytes32,bytes32,uint64,bytes32,bytes)";
+
+/// The same function as a human-readable ABI fragment (ethers.js / viem)
+pub const EVM_VERIFY_ABI: &str = "function verifyInference(bytes32 modelHash, bytes32 inputHash, bytes32 outputHash, \
+                                  bytes32 proofHash, uint64 timestamp, bytes32 nonce, bytes signature) returns (bool)";
+
+/// ABI word of a 32-byte hex digest, tagged or not
+pub fn bytes32(commitment: &str, reason: &'static str) -> Result<[u8; 32], Failure> {
+    let digest = split_commitment(commitment).map_err(|e| (e, reason))?.1;
+    let digest = hex::decode(digest).ok_or((VerificationError::InvalidArgument, reason))?;
+    digest.try_into().map_err(|_| (VerificationError::InvalidArgument, reason))
+}
+
+/// ABI word of an unsigned integer
+pub fn uint(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+impl ProofData {
+    /// Calldata for the proof's `verifyInference` call on an on-chain verifier
+    ///
+    /// Hash tags are dropped, and a missing nonce or signature encodes as
+    /// zero or empty. Fails with `InvalidArgument` unless every commitment
+    /// is a 32-byte digest.
+    pub fn evm_calldata(&self) -> Result<Vec<u8>, Failure> {
+        if self.input_hash.is_empty() || self.output_hash.is_empty() {
+            let reason = "Calldata needs single input_hash and output_hash commitments";
+            return Err((VerificationError::InvalidArgument, reason));
+        }
+        let signature = match &self.signature {
+            Some(signature) => {
+                hex::decode(signature).ok_or((VerificationError::MalformedProof, "Signature is not hex"))?
+            }
+            None => Vec::new(),
+        };
+        let nonce = match &self.nonce {
+            Some(nonce) => bytes32(nonce, "Nonce is not 32 bytes of hex")?,
+            None => [0u8; 32],
+        };
+        let head = [
+            bytes32(&self.model_hash, "model_hash is not a 32-byte digest")?,
+            bytes32(&self.input_hash, "input_hash is not a 32-byte digest")?,
+            bytes32(&self.output_hash, "output_hash is not a 32-byte digest")?,
+            bytes32(&self.proof_hash, "proof_hash is not a 32-byte digest")?,
+            uint(self.timestamp),
+            nonce,
+        ];
+
+        let mut calldata = Keccak256::digest(EVM_VERIFY_SIGNATURE.as_bytes())[..4].to_vec();
+        head.iter().for_each(|word| calldata.extend_from_slice(word));
+        // `bytes` goes in the tail: its offset from the first argument, then length and right-padded data
+        calldata.extend_from_slice(&uint(32 * (head.len() as u64 + 1)));
+        calldata.extend_from_slice(&uint(signature.len() as u64));
+        calldata.extend_from_slice(&signature);
+        calldata.resize(calldata.len() + (32 - signature.len() % 32) % 32, 0);
+        Ok(calldata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{hash_bytes, HashAlgorithm};
+
+    #[test]
+    fn test_evm_calldata() {
+        let hash = |data: &[u8]| hash_bytes(data, HashAlgorithm::default()).unwrap();
+        let timestamp = 1_700_000_000_000;
+        let mut proof = ProofData::new(hash(b"model"), hash(b"proof"), hash(b"in"), hash(b"out"), timestamp, true);
+        proof.signature = Some(format!("0x{}", "ab".repeat(65)));
+        let calldata = proof.evm_calldata().unwrap();
+        let word = |i: usize| &calldata[4 + 32 * i..4 + 32 * (i + 1)];
+
+        assert_eq!(calldata.len(), 4 + 32 * 7 + 32 + 96);
+        assert_eq!(calldata[..4], Keccak256::digest(EVM_VERIFY_SIGNATURE)[..4]);
+        assert_eq!(hex::encode(word(0)), split_commitment(&proof.model_hash).unwrap().1.trim_start_matches("0x"));
+        assert_eq!((word(4), word(5)), (&uint(1_700_000_000_000)[..], &[0u8; 32][..]));
+        assert_eq!((word(6), word(7)), (&uint(224)[..], &uint(65)[..]));
+        assert_eq!(word(8), &[0xab; 32]);
+        assert_eq!(calldata[4 + 32 * 10 + 1..], [0u8; 31]);
+        assert!(EVM_VERIFY_ABI.contains("bytes32 nonce, bytes signature"));
+
+        proof.signature = None;
+        assert_eq!(proof.evm_calldata().unwrap().len(), 4 + 32 * 8);
+        proof.proof_hash = "0xproof".to_string();
+        assert_eq!(proof.evm_calldata().unwrap_err().1, "proof_hash is not a 32-byte digest");
+    }
+}
@@ -0,0 +1,73 @@
+// Fixed-point conversions
+// The float <-> scaled integer mapping JOLT-Atlas's onnx-tracer applies to model I/O
+
+use crate::error::VerificationError;
+use crate::tolerance::OutputTolerance;
+
+/// Multiplier of a fixed-point scale: `2^scale`
+fn scale_to_multiplier(scale: i32) -> f64 {
+    2f64.powi(scale)
+}
+
+/// `round(x * 2^scale)` as the tracer computes it
+///
+/// The product is taken in f64 and rounded half away from zero (Rust's
+/// `f64::round`, not JS `Math.round`, which rounds -2.5 to -2). Values
+/// outside the i32 range are an error rather than saturating.
+pub fn quantize(x: f32, scale: i32) -> Result<i32, VerificationError> {
+    let scaled = (f64::from(x) * scale_to_multiplier(scale)).round();
+    if !scaled.is_finite() || scaled < f64::from(i32::MIN) || scaled > f64::from(i32::MAX) {
+        return Err(VerificationError::InvalidArgument);
+    }
+    Ok(scaled as i32)
+}
+
+/// `q / 2^scale`, computed in f64 and narrowed to f32
+pub fn dequantize(q: i32, scale: i32) -> f32 {
+    (f64::from(q) / scale_to_multiplier(scale)) as f32
+}
+
+/// Convert floats to fixed-point integers with `scale` fractional bits
+///
+/// `InvalidArgument` if a value is NaN, infinite or out of the i32 range once scaled.
+pub fn to_fixed_point(values: &[f32], scale: i32) -> Result<Vec<i32>, VerificationError> {
+    values.iter().map(|&x| quantize(x, scale)).collect()
+}
+
+/// Convert fixed-point integers with `scale` fractional bits back to floats
+pub fn from_fixed_point(values: &[i32], scale: i32) -> Vec<f32> {
+    values.iter().map(|&q| dequantize(q, scale)).collect()
+}
+
+/// Fixed-point I/O encoding the prover commits to: the integers as i32 little-endian
+pub fn encode_fixed_point(values: &[f32], scale: i32) -> Result<Vec<u8>, VerificationError> {
+    Ok(to_fixed_point(values, scale)?.iter().flat_map(|q| q.to_le_bytes()).collect())
+}
+
+impl OutputTolerance {
+    /// Tolerance over fixed-point outputs (`encode_fixed_point` with `scale`), `epsilon` steps apart
+    pub fn fixed_point(scale: i32, epsilon: u32) -> OutputTolerance {
+        OutputTolerance::new(scale_to_multiplier(-scale) as f32, 0, 32, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_point() {
+        assert_eq!(quantize(1.5, 7).unwrap(), 192);
+        // Ties round away from zero on both sides
+        assert_eq!(quantize(-2.5, 0).unwrap(), -3);
+        assert_eq!(quantize(2.5, 0).unwrap(), 3);
+        assert_eq!(quantize(-0.3, 7).unwrap(), -38);
+        assert_eq!(dequantize(-38, 7), -0.296875);
+        assert!(quantize(f32::NAN, 7).is_err());
+        assert!(quantize(3.0e7, 7).is_err());
+
+        let values = [0.25f32, -1.0, 0.33];
+        let tolerance = OutputTolerance::fixed_point(7, 0);
+        assert_eq!(tolerance.encode(&values).unwrap(), encode_fixed_point(&values, 7).unwrap());
+    }
+}
@@ -0,0 +1,92 @@
+// Test fixtures
+// The proof most tests start from, shared by the workspace's crates through the `fixtures` feature
+
+use crate::clock::system_now_ms;
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::ProofData;
+
+/// Input bytes `proof` commits to
+pub const INPUT: &[u8] = b"in";
+
+/// Output bytes `proof` commits to
+pub const OUTPUT: &[u8] = b"out";
+
+/// Fresh, verified proof for `model_hash` over `INPUT` and `OUTPUT`, with the default hash
+pub fn proof(model_hash: &str) -> ProofData {
+    proof_for(model_hash, INPUT, OUTPUT, HashAlgorithm::default())
+}
+
+/// Fresh, verified proof for `model_hash` over `input` and `output`, hashed with `algorithm`
+pub fn proof_for(model_hash: &str, input: &[u8], output: &[u8], algorithm: HashAlgorithm) -> ProofData {
+    ProofData::new(
+        model_hash.to_string(),
+        "0xproof".to_string(),
+        hash_bytes(input, algorithm).unwrap(),
+        hash_bytes(output, algorithm).unwrap(),
+        system_now_ms(),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Check, VerificationError, Verifier};
+
+    #[test]
+    fn test_fixture_proof_verifies() {
+        let verifier = Verifier::new("0xmodel".to_string());
+        assert!(verifier.verify(&proof("0xmodel"), INPUT, OUTPUT));
+        let empty = proof_for("0xmodel", b"", b"", HashAlgorithm::default());
+        assert!(verifier.verify(&empty, b"", b""));
+        assert!(!verifier.verify(&empty, b"\0", b""));
+    }
+
+    #[test]
+    fn test_fixture_proof_rejects_each_mismatch() {
+        let verifier = Verifier::new("0xmodel".to_string());
+        let report = verifier.verify_report(&proof("0xother"), INPUT, OUTPUT);
+        assert_eq!(report.error(), Some(VerificationError::ModelMismatch));
+        let report = verifier.verify_report(&proof("0xmodel"), OUTPUT, OUTPUT);
+        assert_eq!(report.error(), Some(VerificationError::InputHashMismatch));
+        let report = verifier.verify_report(&proof("0xmodel"), INPUT, b"");
+        assert_eq!(report.error(), Some(VerificationError::OutputHashMismatch));
+
+        let mut unverified = proof("0xmodel");
+        unverified.verified = false;
+        assert!(!verifier.verify_report(&unverified, INPUT, OUTPUT).check_passed(Check::ProofValidity));
+        let mut stale = proof("0xmodel");
+        stale.timestamp = 0;
+        assert_eq!(verifier.verify_report(&stale, INPUT, OUTPUT).error(), Some(VerificationError::Expired));
+    }
+
+    #[test]
+    fn test_fixture_proof_edge_cases() {
+        let verifier = Verifier::new("0xmodel".to_string());
+        let skew = verifier.config().clock_skew_ms();
+
+        // Dated ahead of the verifier's clock: fine within the skew, not beyond it
+        let mut ahead = proof("0xmodel");
+        ahead.timestamp = verifier.now_ms() + skew / 2;
+        assert!(verifier.verify(&ahead, INPUT, OUTPUT));
+        ahead.timestamp = verifier.now_ms() + skew + 60_000;
+        assert_eq!(verifier.verify_report(&ahead, INPUT, OUTPUT).error(), Some(VerificationError::Expired));
+        ahead.timestamp = u64::MAX;
+        assert_eq!(verifier.verify_report(&ahead, INPUT, OUTPUT).error(), Some(VerificationError::Expired));
+
+        // Commitments compare as digests: case and `0x` don't matter, truncation and garbage do
+        let mut recased = proof("0xmodel");
+        recased.input_hash = recased.input_hash.to_uppercase().replacen("0X", "0x", 1);
+        assert!(verifier.verify(&recased, INPUT, OUTPUT));
+        let mut truncated = proof("0xmodel");
+        truncated.output_hash.pop();
+        let report = verifier.verify_report(&truncated, INPUT, OUTPUT);
+        assert_eq!(report.error(), Some(VerificationError::OutputHashMismatch));
+        let mut garbage = proof("0xmodel");
+        garbage.input_hash = "not a digest".to_string();
+        assert_eq!(verifier.verify_report(&garbage, INPUT, OUTPUT).error(), Some(VerificationError::InputHashMismatch));
+        let mut empty = proof("0xmodel");
+        empty.input_hash.clear();
+        assert!(!verifier.verify(&empty, b"", OUTPUT));
+    }
+}
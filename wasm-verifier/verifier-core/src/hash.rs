@@ -0,0 +1,443 @@
+// Hashing
+// Commitments over I/O buffers, one-shot or chunk-by-chunk
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sha3::Digest;
+#[cfg(not(feature = "simd"))]
+use sha3::{Keccak256, Sha3_256};
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::VerificationError;
+use crate::hex;
+
+/// Bytes fed to any `Hasher`
+static HASHED: AtomicU64 = AtomicU64::new(0);
+
+/// Hash function used for model/proof/I/O commitments
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum HashAlgorithm {
+    /// FIPS 202 SHA3-256 (default)
+    #[default]
+    #[serde(rename = "sha3-256")]
+    Sha3_256,
+    /// Original Keccak-256 as used by the EVM (`keccak256` in Solidity)
+    #[serde(rename = "keccak-256")]
+    Keccak256,
+    /// FIPS 180-4 SHA-256
+    #[serde(rename = "sha-256")]
+    Sha256,
+    /// BLAKE3 (requires the `blake3` feature)
+    #[serde(rename = "blake3")]
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Canonical name, as written in `ProofData.hash_alg`
+    pub fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            HashAlgorithm::Keccak256 => "keccak-256",
+            HashAlgorithm::Sha256 => "sha-256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Parse a `hash_alg` name, case-insensitively and with common aliases
+    pub fn from_name(name: &str) -> Option<HashAlgorithm> {
+        match name.to_ascii_lowercase().as_str() {
+            "sha3-256" | "sha3_256" | "sha3" => Some(HashAlgorithm::Sha3_256),
+            "keccak-256" | "keccak256" | "keccak" => Some(HashAlgorithm::Keccak256),
+            "sha-256" | "sha256" | "sha2-256" => Some(HashAlgorithm::Sha256),
+            "blake3" => Some(HashAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    /// Prefix used in tagged hash strings, e.g. `blake3:0x...`
+    pub fn prefix(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha3_256 => "sha3",
+            HashAlgorithm::Keccak256 => "keccak",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Look up an algorithm by its tagged-hash prefix
+    pub fn from_prefix(prefix: &str) -> Option<HashAlgorithm> {
+        match prefix {
+            "sha3" => Some(HashAlgorithm::Sha3_256),
+            "keccak" => Some(HashAlgorithm::Keccak256),
+            "sha256" => Some(HashAlgorithm::Sha256),
+            "blake3" => Some(HashAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    /// Whether this build can compute the algorithm
+    pub fn is_available(self) -> bool {
+        match self {
+            HashAlgorithm::Sha3_256 | HashAlgorithm::Keccak256 | HashAlgorithm::Sha256 => true,
+            HashAlgorithm::Blake3 => cfg!(feature = "blake3"),
+        }
+    }
+}
+
+/// Hash bytes to hex string
+pub fn hash_bytes(data: &[u8], alg: HashAlgorithm) -> Result<String, VerificationError> {
+    let mut hasher = Hasher::new(alg)?;
+    hasher.update(data);
+    Ok(hasher.finalize_reset())
+}
+
+/// Raw digest of the concatenation of `parts`
+pub fn digest(parts: &[&[u8]], alg: HashAlgorithm) -> Result<Vec<u8>, VerificationError> {
+    let mut hasher = Hasher::new(alg)?;
+    parts.iter().for_each(|part| hasher.update(part));
+    Ok(hasher.finalize_bytes_reset())
+}
+
+/// Hash bytes to a tagged hex string (`<prefix>:0x...`)
+pub fn hash_bytes_tagged(data: &[u8], alg: HashAlgorithm) -> Result<String, VerificationError> {
+    Ok(format!("{}:{}", alg.prefix(), hash_bytes(data, alg)?))
+}
+
+/// Split a commitment into its algorithm tag (if any) and the `0x...` digest
+///
+/// Untagged commitments are returned with `None` so the caller can apply
+/// the proof's or verifier's default algorithm.
+pub fn split_commitment(commitment: &str) -> Result<(Option<HashAlgorithm>, &str), VerificationError> {
+    match commitment.split_once(':') {
+        Some((prefix, digest)) => HashAlgorithm::from_prefix(prefix)
+            .map(|alg| (Some(alg), digest))
+            .ok_or(VerificationError::UnsupportedHashAlgorithm),
+        None => Ok((None, commitment)),
+    }
+}
+
+/// Compare two hex digests in constant time
+///
+/// Both sides are decoded first, so `0x` prefixes and letter case don't
+/// matter. Values that aren't valid hex are compared byte-for-byte (still
+/// in constant time). Only the length may leak, which is public anyway.
+pub fn digests_equal(a: &str, b: &str) -> bool {
+    match (hex::decode(a), hex::decode(b)) {
+        (Some(a), Some(b)) => ct_eq(&a, &b),
+        _ => ct_eq(a.as_bytes(), b.as_bytes()),
+    }
+}
+
+/// Compare two possibly tagged commitments (`[prefix:]0x...`) in constant time
+///
+/// Tags must agree when both sides carry one.
+pub fn commitments_equal(a: &str, b: &str) -> bool {
+    match (split_commitment(a), split_commitment(b)) {
+        (Ok((tag_a, a)), Ok((tag_b, b))) => {
+            let tags_agree = tag_a.is_none() || tag_b.is_none() || tag_a == tag_b;
+            digests_equal(a, b) & tags_agree
+        }
+        _ => ct_eq(a.as_bytes(), b.as_bytes()),
+    }
+}
+
+/// Constant-time byte comparison
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
+/// Data to compare against a commitment: raw bytes, or an already computed digest
+pub enum IoSource<'a> {
+    Bytes(&'a [u8]),
+    /// Algorithm, digest and number of bytes hashed
+    Hashed(HashAlgorithm, String, u64),
+    /// Bytes together with their digest, computed ahead of the checks (see `prehash`)
+    Prehashed(&'a [u8], HashAlgorithm, String),
+}
+
+impl<'a> IoSource<'a> {
+    /// Hash `data` now with the algorithm `matches` will use for `commitment`
+    ///
+    /// Falls back to plain bytes when no algorithm can be settled on, so the
+    /// commitment check reports the error exactly as it would have.
+    pub fn prehash(data: &'a [u8], commitment: &str, default_alg: &Result<HashAlgorithm, String>) -> Self {
+        let alg = match split_commitment(commitment) {
+            Ok((tag, _)) => tag.or(default_alg.as_ref().ok().copied()),
+            Err(_) => None,
+        };
+        match alg.map(|alg| (alg, hash_bytes(data, alg))) {
+            Some((alg, Ok(digest))) => IoSource::Prehashed(data, alg, digest),
+            _ => IoSource::Bytes(data),
+        }
+    }
+
+    /// Raw bytes, unless only the digest was kept
+    pub fn bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            IoSource::Bytes(data) | IoSource::Prehashed(data, _, _) => Some(data),
+            IoSource::Hashed(..) => None,
+        }
+    }
+
+    /// Length of the underlying data in bytes
+    pub fn len(&self) -> u64 {
+        match self {
+            IoSource::Bytes(data) | IoSource::Prehashed(data, _, _) => data.len() as u64,
+            IoSource::Hashed(_, _, len) => *len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check this source against `commitment`
+    ///
+    /// The commitment's tag, if present, overrides `default_alg`. A
+    /// pre-hashed source only matches if it was hashed with that algorithm.
+    pub fn matches(self, commitment: &str, default_alg: HashAlgorithm) -> Result<bool, VerificationError> {
+        let (tag, expected) = split_commitment(commitment)?;
+        let alg = tag.unwrap_or(default_alg);
+        match self {
+            IoSource::Bytes(data) => Ok(digests_equal(&hash_bytes(data, alg)?, expected)),
+            IoSource::Hashed(hashed_alg, hash, _) => Ok(hashed_alg == alg && digests_equal(&hash, expected)),
+            IoSource::Prehashed(_, hashed_alg, hash) if hashed_alg == alg => Ok(digests_equal(&hash, expected)),
+            IoSource::Prehashed(data, _, _) => Ok(digests_equal(&hash_bytes(data, alg)?, expected)),
+        }
+    }
+}
+
+/// Incremental hasher for any supported algorithm
+#[derive(Clone)]
+pub struct Hasher(State);
+
+#[derive(Clone)]
+enum State {
+    #[cfg(not(feature = "simd"))]
+    Sha3_256(Sha3_256),
+    #[cfg(not(feature = "simd"))]
+    Keccak256(Keccak256),
+    /// SHA3-256 or Keccak-256 on the lane-pair permutation (`simd` feature)
+    #[cfg(feature = "simd")]
+    Sponge(Box<crate::keccak::Sponge>),
+    Sha256(Sha256),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    pub fn new(alg: HashAlgorithm) -> Result<Self, VerificationError> {
+        let state = match alg {
+            #[cfg(feature = "simd")]
+            HashAlgorithm::Sha3_256 => State::Sponge(Box::new(crate::keccak::Sponge::sha3_256())),
+            #[cfg(feature = "simd")]
+            HashAlgorithm::Keccak256 => State::Sponge(Box::new(crate::keccak::Sponge::keccak256())),
+            #[cfg(not(feature = "simd"))]
+            HashAlgorithm::Sha3_256 => State::Sha3_256(Sha3_256::new()),
+            #[cfg(not(feature = "simd"))]
+            HashAlgorithm::Keccak256 => State::Keccak256(Keccak256::new()),
+            HashAlgorithm::Sha256 => State::Sha256(Sha256::new()),
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => State::Blake3(Box::default()),
+            #[cfg(not(feature = "blake3"))]
+            HashAlgorithm::Blake3 => return Err(VerificationError::UnsupportedHashAlgorithm),
+        };
+        Ok(Hasher(state))
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        HASHED.fetch_add(data.len() as u64, Ordering::Relaxed);
+        match &mut self.0 {
+            #[cfg(not(feature = "simd"))]
+            State::Sha3_256(h) => h.update(data),
+            #[cfg(not(feature = "simd"))]
+            State::Keccak256(h) => h.update(data),
+            #[cfg(feature = "simd")]
+            State::Sponge(h) => h.update(data),
+            State::Sha256(h) => h.update(data),
+            #[cfg(feature = "blake3")]
+            State::Blake3(h) => {
+                h.update(data);
+            }
+        }
+    }
+
+    /// `0x`-prefixed hex digest; the hasher starts over afterwards
+    pub fn finalize_reset(&mut self) -> String {
+        format!("0x{}", hex::encode(&self.finalize_bytes_reset()))
+    }
+
+    pub fn finalize_bytes_reset(&mut self) -> Vec<u8> {
+        match &mut self.0 {
+            #[cfg(not(feature = "simd"))]
+            State::Sha3_256(h) => h.finalize_reset().to_vec(),
+            #[cfg(not(feature = "simd"))]
+            State::Keccak256(h) => h.finalize_reset().to_vec(),
+            #[cfg(feature = "simd")]
+            State::Sponge(h) => h.finalize_reset().to_vec(),
+            State::Sha256(h) => h.finalize_reset().to_vec(),
+            #[cfg(feature = "blake3")]
+            State::Blake3(h) => {
+                let digest = h.finalize().as_bytes().to_vec();
+                h.reset();
+                digest
+            }
+        }
+    }
+}
+
+/// `Hasher` that also counts its bytes and remembers its algorithm, for I/O hashed chunk-by-chunk
+#[derive(Clone)]
+pub struct StreamingHasher {
+    hasher: Hasher,
+    algorithm: HashAlgorithm,
+    bytes_processed: u64,
+}
+
+impl StreamingHasher {
+    pub fn with_algorithm(algorithm: HashAlgorithm) -> Result<StreamingHasher, VerificationError> {
+        Ok(StreamingHasher {
+            hasher: Hasher::new(algorithm)?,
+            algorithm,
+            bytes_processed: 0,
+        })
+    }
+
+    /// Hasher for the algorithm `IoSource::matches` will check `commitment` with
+    ///
+    /// `None` when the commitment's tag is unknown, or no algorithm can be
+    /// settled on, or it isn't compiled into this build.
+    pub fn for_commitment(commitment: &str, default_alg: &Result<HashAlgorithm, String>) -> Option<StreamingHasher> {
+        let (tag, _) = split_commitment(commitment).ok()?;
+        StreamingHasher::with_algorithm(tag.or(default_alg.as_ref().ok().copied())?).ok()
+    }
+
+    /// Absorb the next chunk
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+        self.bytes_processed += chunk.len() as u64;
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Total bytes absorbed so far
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+
+    /// `0x`-prefixed hex digest; the hasher starts over afterwards
+    pub fn finalize(&mut self) -> String {
+        self.bytes_processed = 0;
+        self.hasher.finalize_reset()
+    }
+
+    /// Finalize into a source for commitment checks
+    pub fn finalize_source(&mut self) -> IoSource<'static> {
+        let len = self.bytes_processed;
+        IoSource::Hashed(self.algorithm, self.finalize(), len)
+    }
+}
+
+/// Bytes hashed since start (or the last `reset_bytes_hashed`), by every `Hasher` in the process
+pub fn bytes_hashed() -> u64 {
+    HASHED.load(Ordering::Relaxed)
+}
+
+pub fn reset_bytes_hashed() {
+    HASHED.store(0, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hasher_counts_bytes() {
+        let before = bytes_hashed();
+        let mut hasher = Hasher::new(HashAlgorithm::Sha256).unwrap();
+        hasher.update(&[0u8; 1000]);
+        assert_eq!(hasher.finalize_reset(), hash_bytes(&[0u8; 1000], HashAlgorithm::Sha256).unwrap());
+        // Tests hash concurrently, so only a lower bound holds
+        assert!(bytes_hashed() >= before + 2000);
+    }
+
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let data = vec![7u8; 10_000];
+        let mut hasher = StreamingHasher::with_algorithm(HashAlgorithm::Sha3_256).unwrap();
+        for chunk in data.chunks(333) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.bytes_processed(), 10_000);
+        assert_eq!(hasher.finalize(), hash_bytes(&data, HashAlgorithm::Sha3_256).unwrap());
+        assert_eq!(hasher.finalize(), hash_bytes(b"", HashAlgorithm::Sha3_256).unwrap());
+    }
+
+    #[test]
+    fn test_hasher_for_commitment() {
+        let (sha3, unknown) = (Ok(HashAlgorithm::Sha3_256), Err("Unsupported hash algorithm 'md5'".to_string()));
+        let hasher = |commitment, alg| StreamingHasher::for_commitment(commitment, alg).map(|h| h.algorithm());
+        assert_eq!(hasher("0xabc", &sha3), Some(HashAlgorithm::Sha3_256));
+        assert_eq!(hasher("keccak:0xabc", &sha3), Some(HashAlgorithm::Keccak256));
+        assert_eq!(hasher("keccak:0xabc", &unknown), Some(HashAlgorithm::Keccak256));
+        assert_eq!(hasher("0xabc", &unknown), None);
+        assert_eq!(hasher("md5:0xabc", &sha3), None);
+    }
+
+    #[test]
+    fn test_keccak_256() {
+        // keccak256("") as returned by Solidity / ethers.js
+        assert_eq!(
+            hash_bytes(b"", HashAlgorithm::Keccak256).unwrap(),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_ne!(
+            hash_bytes(b"", HashAlgorithm::Keccak256).unwrap(),
+            hash_bytes(b"", HashAlgorithm::Sha3_256).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tagged_commitments() {
+        let tagged = hash_bytes_tagged(b"data", HashAlgorithm::Keccak256).unwrap();
+        assert!(tagged.starts_with("keccak:0x"));
+
+        // Tag wins over the default algorithm
+        assert!(IoSource::Bytes(b"data").matches(&tagged, HashAlgorithm::Sha3_256).unwrap());
+        assert_eq!(
+            IoSource::Bytes(b"data").matches("md5:0x00", HashAlgorithm::Sha3_256),
+            Err(VerificationError::UnsupportedHashAlgorithm)
+        );
+    }
+
+    #[test]
+    fn test_digest_comparison_normalizes() {
+        let digest = hash_bytes(b"data", HashAlgorithm::Sha3_256).unwrap();
+        assert!(digests_equal(&digest, &digest[2..].to_uppercase()));
+        assert!(!digests_equal(&digest, &hash_bytes(b"other", HashAlgorithm::Sha3_256).unwrap()));
+
+        assert!(commitments_equal(&format!("sha3:{}", digest), &digest));
+        assert!(!commitments_equal(&format!("sha3:{}", digest), &format!("blake3:{}", digest)));
+        assert!(commitments_equal("not-hex", "not-hex"));
+        assert!(!commitments_equal("not-hex", "not-hxe"));
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn test_blake3() {
+        assert_eq!(
+            hash_bytes_tagged(b"", HashAlgorithm::Blake3).unwrap(),
+            "blake3:0xaf1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+}
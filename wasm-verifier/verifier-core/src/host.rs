@@ -0,0 +1,30 @@
+// Host callbacks
+// The clock, document fetchers and check observers an embedding runtime plugs into a `Verifier`
+
+/// Current time in milliseconds since the Unix epoch
+///
+/// `None`, or a value that isn't finite and non-negative, falls back to the system clock.
+/// Callbacks are `Send + Sync`, like the `Verifier` holding them.
+pub trait Clock: Send + Sync {
+    fn now_ms(&self) -> Option<f64>;
+}
+
+impl<F: Fn() -> Option<f64> + Send + Sync> Clock for F {
+    fn now_ms(&self) -> Option<f64> {
+        self()
+    }
+}
+
+/// Synchronous document lookup: a `did:web` document by URL, or a JWKS by key id
+pub trait Fetch: Send + Sync {
+    fn fetch(&self, key: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String> + Send + Sync> Fetch for F {
+    fn fetch(&self, key: &str) -> Option<String> {
+        self(key)
+    }
+}
+
+/// A `Fetch` callback, as the verifier stores it
+pub type Fetcher = Box<dyn Fetch>;
@@ -0,0 +1,139 @@
+// Proof log inclusion
+// Merkle audit paths showing one proof is in a published batch root, checked without the rest of the batch
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::hash::{self, split_commitment, HashAlgorithm};
+use crate::merkle::{audit_path, leaf_hash, root_from_path, tree_root};
+use crate::{hex, ProofData};
+
+/// Audit path of one leaf in an RFC 9162 Merkle tree
+///
+/// ```json
+/// { "index": 3, "leaf_count": 1000, "algorithm": "sha3-256", "path": ["0x…", "0x…"] }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MerkleProof {
+    index: u64,
+    leaf_count: u64,
+    algorithm: HashAlgorithm,
+    /// Sibling hashes from the leaf up to the root (hex)
+    path: Vec<String>,
+}
+
+fn decode_hashes(hashes: &[String]) -> Result<Vec<Vec<u8>>, VerificationError> {
+    hashes.iter().map(|h| hex::decode(h).ok_or(VerificationError::InvalidArgument)).collect()
+}
+
+impl MerkleProof {
+    /// Audit path as a prover publishes it, leaf first
+    pub fn new(index: u64, leaf_count: u64, path: Vec<String>, algorithm: HashAlgorithm) -> MerkleProof {
+        MerkleProof { index, leaf_count, algorithm, path }
+    }
+
+    /// Audit path of leaf `index` of a batch of (hex) leaf hashes
+    pub fn create(leaf_hashes: &[String], index: usize, alg: HashAlgorithm) -> Result<MerkleProof, VerificationError> {
+        let hashes = decode_hashes(leaf_hashes)?;
+        if index >= hashes.len() {
+            return Err(VerificationError::InvalidArgument);
+        }
+        Ok(MerkleProof {
+            index: index as u64,
+            leaf_count: hashes.len() as u64,
+            algorithm: alg,
+            path: audit_path(index, &hashes, alg)?.iter().map(|h| format!("0x{}", hex::encode(h))).collect(),
+        })
+    }
+
+    /// Position of the leaf in the batch
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Number of leaves in the batch
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Root this path leads to from `leaf_hash`, if it's well-formed
+    fn root_from(&self, leaf_hash: &str) -> Option<Vec<u8>> {
+        let (leaf, path) = (hex::decode(leaf_hash)?, decode_hashes(&self.path).ok()?);
+        root_from_path(self.index, self.leaf_count, leaf, &path, self.algorithm).ok().flatten()
+    }
+
+    /// Whether `leaf_hash` is leaf `index` of the tree with root `root` (hex, optionally tagged)
+    ///
+    /// `false` for paths that are malformed or lead to another root.
+    pub fn verifies(&self, leaf_hash: &str, root: &str) -> bool {
+        let Ok((_, root)) = split_commitment(root) else {
+            return false;
+        };
+        self.root_from(leaf_hash).is_some_and(|computed| hash::digests_equal(&hex::encode(&computed), root))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<MerkleProof, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl ProofData {
+    /// This proof's leaf in a proof log: the Merkle leaf hash of its `proof_id`
+    pub fn leaf_hash(&self, alg: HashAlgorithm) -> Result<String, VerificationError> {
+        merkle_leaf_hash(self.proof_id().as_bytes(), alg)
+    }
+}
+
+/// Merkle leaf hash of `data`, `H(0x00 || data)`
+pub fn merkle_leaf_hash(data: &[u8], alg: HashAlgorithm) -> Result<String, VerificationError> {
+    leaf_hash(data, alg).map(|hash| format!("0x{}", hex::encode(&hash)))
+}
+
+/// Root of a batch of (hex) leaf hashes, as a prover publishes it (e.g. daily)
+pub fn merkle_root(leaf_hashes: &[String], alg: HashAlgorithm) -> Result<String, VerificationError> {
+    decode_hashes(leaf_hashes)
+        .and_then(|hashes| tree_root(&hashes, alg))
+        .map(|root| format!("0x{}", hex::encode(&root)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inclusion() {
+        let alg = HashAlgorithm::Sha3_256;
+        let leaves: Vec<String> =
+            (0..7u8).map(|i| merkle_leaf_hash(format!("sha3:0x{:064x}", i).as_bytes(), alg).unwrap()).collect();
+        let root = merkle_root(&leaves, alg).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = MerkleProof::create(&leaves, index, alg).unwrap();
+            assert!(proof.verifies(leaf, &root), "leaf {}", index);
+            assert!(!proof.verifies(&leaves[(index + 1) % leaves.len()], &root));
+        }
+        let proof = MerkleProof::create(&leaves, 5, alg).unwrap();
+        assert!(proof.verifies(&leaves[5], &format!("sha3:{}", root)));
+        assert_eq!(MerkleProof::from_json(&proof.to_json()).unwrap(), MerkleProof::new(5, 7, proof.path.clone(), alg));
+
+        let mut forged = proof.clone();
+        forged.index = 4;
+        assert!(!forged.verifies(&leaves[5], &root));
+        forged.path[0] = "zz".to_string();
+        assert!(!forged.verifies(&leaves[5], &root));
+        assert!(!proof.verifies(&leaves[5], "not hex"));
+        assert_eq!(MerkleProof::create(&leaves, 7, alg), Err(VerificationError::InvalidArgument));
+    }
+}
@@ -0,0 +1,120 @@
+// JSON Canonicalization Scheme
+// RFC 8785 serialization, so hashes and signatures over proofs are byte-for-byte reproducible
+
+use serde_json::Value;
+
+/// Serialize `value` in RFC 8785 canonical form
+///
+/// Object members are sorted by their UTF-16 code units, strings use the
+/// minimal JSON escapes, and numbers are formatted like ECMAScript's
+/// `Number.prototype.toString`.
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(u), _) => out.push_str(&u.to_string()),
+            (None, Some(i)) => out.push_str(&i.to_string()),
+            _ => out.push_str(&format_f64(n.as_f64().unwrap_or_default())),
+        },
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(fields) => {
+            let mut fields: Vec<(&String, &Value)> = fields.iter().collect();
+            fields.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// ECMAScript number-to-string for finite doubles
+fn format_f64(f: f64) -> String {
+    if f == 0.0 || !f.is_finite() {
+        return "0".to_string();
+    }
+    // `{:e}` gives the shortest round-tripping digits, e.g. "1.25e-7"
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exponent) = sci.split_once('e').expect("{:e} always has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().expect("{:e} exponent is an integer") + 1;
+
+    let body = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat((-n) as usize))
+    } else {
+        let (first, rest) = digits.split_at(1);
+        let fraction = if rest.is_empty() { String::new() } else { format!(".{rest}") };
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        format!("{first}{fraction}e{sign}{}", (n - 1).abs())
+    };
+    if f < 0.0 { format!("-{body}") } else { body }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonicalize() {
+        // RFC 8785 section 3.2.2 sample, minus the non-ASCII member names
+        let mut value: Value = serde_json::from_str(
+            r#"{"string": "€$\u000F\u000aA'B\"\\\\\"\/", "literals": [null, true, false]}"#,
+        )
+        .unwrap();
+        value["numbers"] = json!([333_333_333.333_333_3_f64, 1E30, 4.50, 2e-3, 0.000000000000000000000000001]);
+        assert_eq!(
+            canonicalize(&value),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+
+        // UTF-16 ordering puts U+1F600 (surrogates 0xD83D..) before U+FB01
+        assert_eq!(canonicalize(&json!({"\u{fb01}": 1, "\u{1f600}": 2})), "{\"\u{1f600}\":2,\"\u{fb01}\":1}");
+    }
+}
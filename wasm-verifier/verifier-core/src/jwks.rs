@@ -0,0 +1,180 @@
+// JWKS signer key resolution
+// Resolves `ProofData.kid` to a prover public key from a JSON Web Key Set
+
+use base64::Engine;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::error::VerificationError;
+use crate::host::Fetcher;
+use crate::signature::{self, Failure, SignatureAlgorithm};
+use crate::{hex, Verifier};
+
+/// A single JWK, restricted to the fields we use
+#[derive(Deserialize)]
+pub struct Jwk {
+    kty: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default, rename = "use")]
+    key_use: Option<String>,
+    #[serde(default)]
+    crv: Option<String>,
+    #[serde(default)]
+    x: Option<String>,
+    #[serde(default)]
+    y: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+/// Key resolved from a JWK, in the form `signer_pubkey` uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedKey {
+    pub algorithm: SignatureAlgorithm,
+    /// Ed25519 public key (hex) or checksummed Ethereum address
+    pub key: String,
+}
+
+/// Maps key ids (`kid`) to prover keys, so provers can rotate keys without
+/// redeploying the verifier
+///
+/// Load it with `insert_jwks`, or give it a `fetcher` that is called with an
+/// unknown `kid` and must synchronously return a JWKS JSON string (for
+/// example from a cache the host refreshes asynchronously).
+/// Supported keys: OKP/Ed25519 and EC/secp256k1.
+#[derive(Default)]
+pub struct KeyResolver {
+    keys: Mutex<HashMap<String, ResolvedKey>>,
+    fetcher: Option<Fetcher>,
+}
+
+impl KeyResolver {
+    pub fn new() -> KeyResolver {
+        KeyResolver::default()
+    }
+
+    /// Callback consulted for unknown key ids
+    pub fn set_fetcher(&mut self, fetcher: Option<Fetcher>) {
+        self.fetcher = fetcher;
+    }
+
+    /// Number of keys currently known
+    pub fn len(&self) -> usize {
+        self.keys.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parse a JWKS document and remember its signing keys
+    ///
+    /// Keys without a `kid`, marked for encryption, or of unsupported types
+    /// are skipped, so a shared JWKS may also carry unrelated keys.
+    pub fn insert_jwks(&self, jwks: &str) -> Result<usize, Failure> {
+        let jwks: Jwks = serde_json::from_str(jwks)
+            .map_err(|_| (VerificationError::InvalidArgument, "Not a valid JWKS document"))?;
+
+        let mut keys = self.keys.lock().unwrap();
+        let mut loaded = 0;
+        for jwk in jwks.keys {
+            if jwk.key_use.as_deref().is_some_and(|u| u != "sig") {
+                continue;
+            }
+            if let (Some(kid), Some(key)) = (jwk.kid.clone(), resolve_jwk(&jwk)) {
+                keys.insert(kid, key);
+                loaded += 1;
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Resolve `kid` to the key `algorithm` signatures should verify against
+    pub fn resolve(&self, kid: &str, algorithm: SignatureAlgorithm) -> Result<String, Failure> {
+        let cached = self.keys.lock().unwrap().get(kid).cloned();
+        let resolved = match cached {
+            Some(key) => Some(key),
+            None => self.fetch(kid),
+        };
+
+        match resolved {
+            Some(key) if key.algorithm == algorithm => Ok(key.key),
+            Some(_) => Err((VerificationError::SignatureInvalid, "Key named by kid is for a different signature_alg")),
+            None => Err((VerificationError::UntrustedSigner, "kid does not name a known key")),
+        }
+    }
+
+    fn fetch(&self, kid: &str) -> Option<ResolvedKey> {
+        let fetcher = self.fetcher.as_ref()?;
+        let jwks = fetcher.fetch(kid)?;
+        self.insert_jwks(&jwks).ok()?;
+        self.keys.lock().unwrap().get(kid).cloned()
+    }
+}
+
+pub fn resolve_jwk(jwk: &Jwk) -> Option<ResolvedKey> {
+    let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let x = b64.decode(jwk.x.as_deref()?).ok()?;
+
+    match (jwk.kty.as_str(), jwk.crv.as_deref()?) {
+        ("OKP", "Ed25519") if x.len() == 32 => Some(ResolvedKey {
+            algorithm: SignatureAlgorithm::Ed25519,
+            key: hex::encode(&x),
+        }),
+        ("EC", "secp256k1") => {
+            let y = b64.decode(jwk.y.as_deref()?).ok()?;
+            let mut point = vec![0x04];
+            point.extend_from_slice(&x);
+            point.extend_from_slice(&y);
+            let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&point).ok()?;
+            Some(ResolvedKey {
+                algorithm: SignatureAlgorithm::Secp256k1,
+                key: signature::eth_address(&key),
+            })
+        }
+        _ => None,
+    }
+}
+
+impl Verifier {
+    /// Resolve proof `kid`s through `resolver` before checking signatures
+    ///
+    /// Keys resolved this way are trusted without `add_trusted_signer`.
+    pub fn set_key_resolver(&mut self, resolver: KeyResolver) {
+        self.key_resolver = Some(resolver);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_kid() {
+        let resolver = KeyResolver::new();
+        let jwks = r#"{"keys": [
+            {"kty": "OKP", "crv": "Ed25519", "kid": "prover-2024", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"},
+            {"kty": "OKP", "crv": "Ed25519", "kid": "enc", "use": "enc", "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"},
+            {"kty": "RSA", "kid": "legacy", "n": "AQAB", "e": "AQAB"}
+        ]}"#;
+        assert_eq!(resolver.insert_jwks(jwks).unwrap(), 1);
+
+        assert_eq!(
+            resolver.resolve("prover-2024", SignatureAlgorithm::Ed25519).unwrap(),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(
+            resolver.resolve("prover-2024", SignatureAlgorithm::Secp256k1).unwrap_err().0,
+            VerificationError::SignatureInvalid
+        );
+        assert_eq!(
+            resolver.resolve("enc", SignatureAlgorithm::Ed25519).unwrap_err().0,
+            VerificationError::UntrustedSigner
+        );
+    }
+}
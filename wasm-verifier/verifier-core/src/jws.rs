@@ -0,0 +1,194 @@
+// JWS proof envelopes
+// Verifies compact JWS tokens (RFC 7515) whose payload is the ProofData JSON claims
+
+use base64::Engine;
+use serde::Deserialize;
+
+use crate::error::VerificationError;
+use crate::hash::IoSource;
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::signature::{self, Failure, SignatureAlgorithm, Signed};
+use crate::{hex, ProofData, Verifier};
+
+fn malformed(reason: &'static str) -> Failure {
+    (VerificationError::MalformedProof, reason)
+}
+
+/// JOSE header, restricted to the fields we use
+#[derive(Deserialize)]
+struct JoseHeader {
+    alg: String,
+    #[serde(default)]
+    kid: Option<String>,
+    #[serde(default)]
+    crit: Option<Vec<String>>,
+}
+
+/// A parsed compact JWS
+pub struct Jws<'a> {
+    pub signing_input: &'a str,
+    pub payload: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub algorithm: SignatureAlgorithm,
+    pub kid: Option<String>,
+}
+
+impl<'a> Jws<'a> {
+    /// Parse `header.payload.signature`
+    ///
+    /// Only `EdDSA` (Ed25519) and `ES256K` are accepted; `none`, HMAC and
+    /// any `crit` extension are rejected.
+    pub fn parse(token: &'a str) -> Result<Jws<'a>, Failure> {
+        let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let token = token.trim();
+        let (signing_input, signature) = token.rsplit_once('.').ok_or(malformed("JWS must have three parts"))?;
+        let (header, payload) = signing_input.split_once('.').ok_or(malformed("JWS must have three parts"))?;
+        if payload.contains('.') {
+            return Err(malformed("JWS must have three parts"));
+        }
+
+        let header: JoseHeader = b64
+            .decode(header)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or(malformed("JWS header is not base64url JSON"))?;
+        if header.crit.is_some() {
+            return Err(malformed("JWS critical headers are not supported"));
+        }
+        let algorithm = match header.alg.as_str() {
+            "EdDSA" => SignatureAlgorithm::Ed25519,
+            "ES256K" => SignatureAlgorithm::Secp256k1,
+            _ => return Err((VerificationError::SignatureInvalid, "Unsupported JWS alg")),
+        };
+
+        Ok(Jws {
+            signing_input,
+            payload: b64.decode(payload).map_err(|_| malformed("JWS payload is not base64url"))?,
+            signature: b64.decode(signature).map_err(|_| malformed("JWS signature is not base64url"))?,
+            algorithm,
+            kid: header.kid,
+        })
+    }
+
+    /// The ProofData carried as the JSON claims
+    pub fn proof(&self) -> Result<ProofData, Failure> {
+        serde_json::from_slice(&self.payload).map_err(|_| malformed("JWS payload is not a ProofData claim set"))
+    }
+}
+
+impl Verifier {
+    /// Record whether the token signature is by a key `proof_data`'s signer may use
+    pub fn check_jws(&self, report: &mut VerificationReport, jws: &Jws<'_>, proof_data: &ProofData) {
+        let started = Stopwatch::start();
+        let message = jws.signing_input.as_bytes();
+        let kid = jws.kid.as_deref().or(proof_data.kid.as_deref());
+        let signature = hex::encode(&jws.signature);
+        let signed = match jws.algorithm {
+            SignatureAlgorithm::Ed25519 => Ok(Signed::Ed25519 { signature: &signature, message }),
+            SignatureAlgorithm::Secp256k1 => {
+                signature::recover_es256k_addresses(&jws.signature, message).map(Signed::Recovered)
+            }
+        };
+        match signed.and_then(|signed| self.check_prover_key(proof_data, kid, jws.algorithm, signed)) {
+            Ok(()) => report.record(Check::Signature, true, "", started),
+            Err((error, reason)) => report.record_error(Check::Signature, error, reason, started),
+        }
+    }
+
+    /// Verify a compact JWS whose payload is the ProofData claims
+    ///
+    /// The token signature (EdDSA or ES256K) takes the place of the in-proof
+    /// `signature`. It must verify under a configured key: the `KeyResolver`
+    /// key the header `kid` names, or else the claims' `signer_pubkey`, which
+    /// the token asserts about itself and so only counts once it was added
+    /// with `add_trusted_signer`. Freshness comes from the
+    /// `timestamp` claim; JWT `exp`/`nbf` are not interpreted. Fails if the
+    /// token cannot be parsed.
+    pub fn verify_jws(
+        &self,
+        token: &str,
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> Result<VerificationReport, Failure> {
+        let jws = Jws::parse(token)?;
+        let proof_data = jws.proof()?;
+
+        let mut report = self.new_report();
+        self.check_jws(&mut report, &jws, &proof_data);
+        self.run_checks(&mut report, &proof_data, IoSource::Bytes(input_bytes), IoSource::Bytes(output_bytes));
+        Ok(report)
+    }
+}
+
+#[cfg(all(test, feature = "rust-signatures"))]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::KeyResolver;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn token(header: &str, proof: &ProofData, key: &SigningKey) -> String {
+        let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let input = format!("{}.{}", b64.encode(header), b64.encode(serde_json::to_vec(proof).unwrap()));
+        let signature = key.sign(input.as_bytes()).to_bytes();
+        format!("{input}.{}", b64.encode(signature))
+    }
+
+    #[test]
+    fn test_verify_jws() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let key = SigningKey::from_bytes(&[3u8; 32]);
+        let pubkey = hex::encode(key.verifying_key().as_bytes());
+        let mut verifier = Verifier::new(model_hash.clone());
+        verifier.add_trusted_signer(pubkey.clone());
+
+        let mut proof = fixtures::proof(&model_hash);
+        proof.signer_pubkey = Some(pubkey);
+
+        let jwt = token(r#"{"alg":"EdDSA","typ":"JWT"}"#, &proof, &key);
+        assert!(verifier.verify_jws(&jwt, b"in", b"out").unwrap().passed());
+        assert!(!verifier.verify_jws(&jwt, b"in", b"other").unwrap().passed());
+
+        let tampered = jwt.replacen('.', ".e30", 1);
+        assert!(verifier.verify_jws(&tampered, b"in", b"out").is_err());
+
+        let unsigned = token(r#"{"alg":"none"}"#, &proof, &key);
+        assert_eq!(verifier.verify_jws(&unsigned, b"in", b"out").unwrap_err().0, VerificationError::SignatureInvalid);
+    }
+
+    #[test]
+    fn test_jws_needs_a_configured_key() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let attacker = SigningKey::from_bytes(&[9u8; 32]);
+        let mut proof = fixtures::proof(&model_hash);
+        proof.signer_pubkey = Some(hex::encode(attacker.verifying_key().as_bytes()));
+        let forged = token(r#"{"alg":"EdDSA"}"#, &proof, &attacker);
+        let signature_error = |verifier: &Verifier, jwt: &str| verifier.verify_jws(jwt, b"in", b"out").unwrap().error();
+
+        // Self-signed: the token's own signer_pubkey vouches for nothing
+        let mut verifier = Verifier::new(model_hash.clone());
+        assert_eq!(signature_error(&verifier, &forged), Some(VerificationError::SignatureInvalid));
+
+        let prover = SigningKey::from_bytes(&[3u8; 32]);
+        verifier.add_trusted_signer(hex::encode(prover.verifying_key().as_bytes()));
+        assert_eq!(signature_error(&verifier, &forged), Some(VerificationError::UntrustedSigner));
+
+        // A kid resolves to a configured key; the attacker can't claim it
+        let x = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(prover.verifying_key().as_bytes());
+        let resolver = KeyResolver::new();
+        resolver.insert_jwks(&format!(r#"{{"keys":[{{"kty":"OKP","crv":"Ed25519","kid":"prover","x":"{x}"}}]}}"#)).unwrap();
+        let mut verifier = Verifier::new(model_hash);
+        verifier.set_key_resolver(resolver);
+
+        let claimed = token(r#"{"alg":"EdDSA","kid":"prover"}"#, &proof, &attacker);
+        assert_eq!(signature_error(&verifier, &claimed), Some(VerificationError::SignatureInvalid));
+        proof.signer_pubkey = None;
+        let claimed = token(r#"{"alg":"EdDSA","kid":"prover"}"#, &proof, &attacker);
+        assert_eq!(signature_error(&verifier, &claimed), Some(VerificationError::SignatureInvalid));
+        let unknown = token(r#"{"alg":"EdDSA","kid":"attacker"}"#, &proof, &attacker);
+        assert!(signature_error(&verifier, &unknown).is_some());
+
+        let genuine = token(r#"{"alg":"EdDSA","kid":"prover"}"#, &proof, &prover);
+        assert!(verifier.verify_jws(&genuine, b"in", b"out").unwrap().passed());
+    }
+}
@@ -0,0 +1,236 @@
+// Keccak sponge
+// SHA3-256 / Keccak-256 whose permutation works on lane pairs, as wasm simd128 vectors when available
+
+use lanes::Pair;
+
+/// Sponge rate of both 256-bit variants, in bytes
+const RATE: usize = 136;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// ρ rotation offsets, in π visiting order
+const RHO: [u32; 24] = [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+
+/// π lane visiting order, starting from lane 1
+const PI: [usize; 24] = [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+/// Two adjacent lanes in one 128-bit vector
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod lanes {
+    use core::arch::wasm32::*;
+
+    #[derive(Clone, Copy)]
+    pub(super) struct Pair(v128);
+
+    impl Pair {
+        pub(super) fn new(a: u64, b: u64) -> Pair {
+            Pair(u64x2(a, b))
+        }
+
+        pub(super) fn lanes(self) -> (u64, u64) {
+            (u64x2_extract_lane::<0>(self.0), u64x2_extract_lane::<1>(self.0))
+        }
+
+        pub(super) fn xor(self, other: Pair) -> Pair {
+            Pair(v128_xor(self.0, other.0))
+        }
+
+        /// `self & !other`
+        pub(super) fn and_not(self, other: Pair) -> Pair {
+            Pair(v128_andnot(self.0, other.0))
+        }
+
+        pub(super) fn rotate_left_1(self) -> Pair {
+            Pair(v128_or(u64x2_shl(self.0, 1), u64x2_shr(self.0, 63)))
+        }
+    }
+}
+
+/// Scalar stand-in with the same interface, for builds without simd128
+#[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+mod lanes {
+    #[derive(Clone, Copy)]
+    pub(super) struct Pair(u64, u64);
+
+    impl Pair {
+        pub(super) fn new(a: u64, b: u64) -> Pair {
+            Pair(a, b)
+        }
+
+        pub(super) fn lanes(self) -> (u64, u64) {
+            (self.0, self.1)
+        }
+
+        pub(super) fn xor(self, other: Pair) -> Pair {
+            Pair(self.0 ^ other.0, self.1 ^ other.1)
+        }
+
+        /// `self & !other`
+        pub(super) fn and_not(self, other: Pair) -> Pair {
+            Pair(self.0 & !other.0, self.1 & !other.1)
+        }
+
+        pub(super) fn rotate_left_1(self) -> Pair {
+            Pair(self.0.rotate_left(1), self.1.rotate_left(1))
+        }
+    }
+}
+
+/// Keccak-f[1600] with θ and χ computed two lanes at a time
+fn permute(a: &mut [u64; 25]) {
+    for rc in ROUND_CONSTANTS {
+        // θ: column parities, then mix each column's neighbours back in
+        let mut c01 = Pair::new(a[0], a[1]);
+        let mut c23 = Pair::new(a[2], a[3]);
+        let mut c4 = a[4];
+        for row in (5..25).step_by(5) {
+            c01 = c01.xor(Pair::new(a[row], a[row + 1]));
+            c23 = c23.xor(Pair::new(a[row + 2], a[row + 3]));
+            c4 ^= a[row + 4];
+        }
+        let (c0, c1) = c01.lanes();
+        let (c2, c3) = c23.lanes();
+        let d01 = Pair::new(c4, c0).xor(Pair::new(c1, c2).rotate_left_1());
+        let d23 = Pair::new(c1, c2).xor(Pair::new(c3, c4).rotate_left_1());
+        let d4 = c3 ^ c0.rotate_left(1);
+        for row in (0..25).step_by(5) {
+            (a[row], a[row + 1]) = Pair::new(a[row], a[row + 1]).xor(d01).lanes();
+            (a[row + 2], a[row + 3]) = Pair::new(a[row + 2], a[row + 3]).xor(d23).lanes();
+            a[row + 4] ^= d4;
+        }
+
+        // ρ and π: per-lane rotations, so scalar
+        let mut last = a[1];
+        for (&pi, &rho) in PI.iter().zip(&RHO) {
+            let next = a[pi];
+            a[pi] = last.rotate_left(rho);
+            last = next;
+        }
+
+        // χ: a[x] ^= !a[x + 1] & a[x + 2] within each row
+        for row in (0..25).step_by(5) {
+            let [b0, b1, b2, b3, b4] = [a[row], a[row + 1], a[row + 2], a[row + 3], a[row + 4]];
+            (a[row], a[row + 1]) = Pair::new(b0, b1).xor(Pair::new(b2, b3).and_not(Pair::new(b1, b2))).lanes();
+            (a[row + 2], a[row + 3]) = Pair::new(b2, b3).xor(Pair::new(b4, b0).and_not(Pair::new(b3, b4))).lanes();
+            a[row + 4] = b4 ^ (!b0 & b1);
+        }
+
+        // ι
+        a[0] ^= rc;
+    }
+}
+
+/// Incremental SHA3-256 or Keccak-256 (they differ only in the padding byte)
+#[derive(Clone)]
+pub(crate) struct Sponge {
+    state: [u64; 25],
+    buffer: [u8; RATE],
+    filled: usize,
+    domain: u8,
+}
+
+impl Sponge {
+    pub(crate) fn sha3_256() -> Sponge {
+        Sponge::new(0x06)
+    }
+
+    pub(crate) fn keccak256() -> Sponge {
+        Sponge::new(0x01)
+    }
+
+    fn new(domain: u8) -> Sponge {
+        Sponge { state: [0; 25], buffer: [0; RATE], filled: 0, domain }
+    }
+
+    fn absorb_block(&mut self, block: &[u8]) {
+        for (lane, bytes) in self.state.iter_mut().zip(block.chunks_exact(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().unwrap_or_default());
+        }
+        permute(&mut self.state);
+    }
+
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        if self.filled > 0 {
+            let take = data.len().min(RATE - self.filled);
+            self.buffer[self.filled..self.filled + take].copy_from_slice(&data[..take]);
+            self.filled += take;
+            data = &data[take..];
+            if self.filled < RATE {
+                return;
+            }
+            let block = self.buffer;
+            self.absorb_block(&block);
+            self.filled = 0;
+        }
+        let mut blocks = data.chunks_exact(RATE);
+        for block in &mut blocks {
+            self.absorb_block(block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.filled = rest.len();
+    }
+
+    /// Pad, squeeze the 32-byte digest and start over
+    pub(crate) fn finalize_reset(&mut self) -> [u8; 32] {
+        let mut block = [0u8; RATE];
+        block[..self.filled].copy_from_slice(&self.buffer[..self.filled]);
+        block[self.filled] ^= self.domain;
+        block[RATE - 1] ^= 0x80;
+        self.absorb_block(&block);
+
+        let mut digest = [0u8; 32];
+        for (out, lane) in digest.chunks_exact_mut(8).zip(&self.state) {
+            out.copy_from_slice(&lane.to_le_bytes());
+        }
+        *self = Sponge::new(self.domain);
+        digest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha3::{Digest, Keccak256, Sha3_256};
+
+    #[test]
+    fn test_sponge_matches_sha3_crate() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 % 251) as u8).collect();
+        for len in [0, 1, RATE - 1, RATE, RATE + 1, 3 * RATE, data.len()] {
+            let mut sha3 = Sponge::sha3_256();
+            let mut keccak = Sponge::keccak256();
+            // Uneven chunks exercise the partial-block buffer
+            for chunk in data[..len].chunks(57) {
+                sha3.update(chunk);
+                keccak.update(chunk);
+            }
+            assert_eq!(sha3.finalize_reset()[..], Sha3_256::digest(&data[..len])[..], "sha3, {} bytes", len);
+            assert_eq!(keccak.finalize_reset()[..], Keccak256::digest(&data[..len])[..], "keccak, {} bytes", len);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// Verifier core
+// Proofs, config, reports and the check pipeline shared by the WASM and native verifiers
+
+pub mod aggregate;
+pub mod anchor;
+pub mod audit;
+pub mod batch;
+pub mod binary;
+pub mod builder;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+pub mod chain;
+pub mod classify;
+pub mod clock;
+pub mod composite;
+pub mod compress;
+pub mod config;
+pub mod describe;
+pub mod eas;
+pub mod error;
+pub mod evm;
+pub mod fixed;
+#[cfg(any(test, feature = "fixtures"))]
+#[doc(hidden)]
+pub mod fixtures;
+pub mod hash;
+pub mod host;
+pub mod inclusion;
+pub mod jcs;
+#[cfg(any(test, feature = "simd"))]
+mod keccak;
+pub mod memory;
+pub mod merkle;
+pub mod messages;
+pub mod metadata;
+pub mod multi;
+pub mod nonce;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+pub mod policy;
+pub mod preprocessing;
+pub mod proof;
+#[cfg(feature = "onnx")]
+pub mod protobuf;
+pub mod quantization;
+pub mod registry;
+pub mod report;
+pub mod session;
+pub mod tensor;
+pub mod tolerance;
+#[cfg(feature = "tracing")]
+mod trace;
+pub mod verifier;
+pub mod version;
+#[cfg(feature = "signatures")]
+pub mod bls;
+#[cfg(feature = "signatures")]
+pub mod cose;
+#[cfg(feature = "signatures")]
+pub mod crypto;
+#[cfg(feature = "signatures")]
+pub mod did;
+#[cfg(feature = "signatures")]
+pub mod eip712;
+#[cfg(feature = "signatures")]
+pub mod jwks;
+#[cfg(feature = "signatures")]
+pub mod jws;
+#[cfg(feature = "signatures")]
+pub mod receipt;
+#[cfg(feature = "signatures")]
+pub mod rekor;
+#[cfg(feature = "signatures")]
+pub mod revocation;
+#[cfg(feature = "signatures")]
+pub mod signature;
+#[cfg(feature = "signatures")]
+pub mod vc;
+#[cfg(feature = "signatures")]
+pub mod x509;
+
+pub use builder::VerifierBuilder;
+pub use config::VerifierConfig;
+#[cfg(feature = "signatures")]
+pub use eip712::Eip712Domain;
+pub use error::VerificationError;
+pub use hash::{HashAlgorithm, Hasher, IoSource};
+#[cfg(feature = "signatures")]
+pub use jwks::KeyResolver;
+#[cfg(feature = "onnx")]
+pub use onnx::{
+    commitment::{CommitmentPolicy, ModelCommitment},
+    diagnostic::ModelDiagnostic,
+};
+pub use policy::VerifierPolicy;
+pub use preprocessing::PreprocessingConfig;
+pub use proof::ProofData;
+pub use quantization::QuantizationConfig;
+pub use registry::{ModelEntry, ModelRegistry};
+pub use report::{Check, CheckResult, Confidence, VerificationReport};
+pub use tensor::{DType, TensorSpec};
+pub use verifier::Verifier;
+
+pub mod hex {
+    use std::fmt::Write;
+
+    pub fn encode(bytes: &[u8]) -> String {
+        let mut s = String::with_capacity(bytes.len() * 2);
+        for &b in bytes {
+            write!(&mut s, "{:02x}", b).unwrap();
+        }
+        s
+    }
+
+    /// Decode hex (either case, optional `0x` prefix); `None` if malformed
+    pub fn decode(s: &str) -> Option<Vec<u8>> {
+        let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+        if !s.len().is_multiple_of(2) {
+            return None;
+        }
+        (0..s.len())
+            .step_by(2)
+            .map(|i| s.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+            .collect()
+    }
+}
@@ -0,0 +1,161 @@
+// Memory accounting
+// Heap usage counted by the global allocator, and bytes hashed, for embedders with memory budgets
+
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::hash::{bytes_hashed, reset_bytes_hashed};
+
+/// Live heap bytes
+static HEAP: AtomicUsize = AtomicUsize::new(0);
+
+/// Most live heap bytes since start (or the last `reset_memory_stats`)
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator wrapper that keeps `HEAP` and `PEAK` current
+///
+/// Heap figures in `memory_stats` stay zero unless the binary installs it:
+/// `#[global_allocator] static ALLOC: Tracking<System> = Tracking::new(System);`
+pub struct Tracking<A> {
+    inner: A,
+}
+
+impl<A> Tracking<A> {
+    pub const fn new(inner: A) -> Self {
+        Tracking { inner }
+    }
+}
+
+fn grow(bytes: usize) {
+    let now = HEAP.fetch_add(bytes, Ordering::Relaxed) + bytes;
+    PEAK.fetch_max(now, Ordering::Relaxed);
+}
+
+fn shrink(bytes: usize) {
+    HEAP.fetch_sub(bytes, Ordering::Relaxed);
+}
+
+// SAFETY: every call is forwarded to `inner` unchanged; only counters are updated
+unsafe impl<A: GlobalAlloc> GlobalAlloc for Tracking<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                shrink(layout.size() - new_size);
+            }
+        }
+        new_ptr
+    }
+}
+
+/// Size of WASM linear memory, which never shrinks
+#[cfg(target_arch = "wasm32")]
+fn linear_memory_bytes() -> Option<u64> {
+    Some(core::arch::wasm32::memory_size(0) as u64 * 65_536)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn linear_memory_bytes() -> Option<u64> {
+    None
+}
+
+/// What `memory_stats` reports
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MemoryStats {
+    heap_bytes: u64,
+    peak_heap_bytes: u64,
+    memory_bytes: Option<u64>,
+    bytes_hashed: u64,
+}
+
+impl MemoryStats {
+    /// Bytes currently allocated on the heap
+    pub fn heap_bytes(&self) -> u64 {
+        self.heap_bytes
+    }
+
+    /// Most bytes allocated at once since load (or `reset_memory_stats`)
+    pub fn peak_heap_bytes(&self) -> u64 {
+        self.peak_heap_bytes
+    }
+
+    /// Size of WASM linear memory (`None` outside WASM); it grows to fit the heap and never shrinks
+    pub fn memory_bytes(&self) -> Option<u64> {
+        self.memory_bytes
+    }
+
+    /// Bytes hashed since load (or `reset_memory_stats`), including proof and model data
+    pub fn bytes_hashed(&self) -> u64 {
+        self.bytes_hashed
+    }
+
+    /// Serialize stats to JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Current heap usage, its peak, and bytes hashed
+///
+/// Counts are shared by everything in the process (or WASM module instance).
+pub fn memory_stats() -> MemoryStats {
+    MemoryStats {
+        heap_bytes: HEAP.load(Ordering::Relaxed) as u64,
+        peak_heap_bytes: PEAK.load(Ordering::Relaxed) as u64,
+        memory_bytes: linear_memory_bytes(),
+        bytes_hashed: bytes_hashed(),
+    }
+}
+
+/// Restart the peak at the current heap size and the hashed byte count at zero
+pub fn reset_memory_stats() {
+    PEAK.store(HEAP.load(Ordering::Relaxed), Ordering::Relaxed);
+    reset_bytes_hashed();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{hash_bytes, HashAlgorithm};
+
+    #[global_allocator]
+    static ALLOC: Tracking<std::alloc::System> = Tracking::new(std::alloc::System);
+
+    #[test]
+    fn test_memory_stats() {
+        let before = memory_stats();
+        let block = vec![1u8; 1 << 20];
+        hash_bytes(&block, HashAlgorithm::default()).unwrap();
+
+        // Other tests allocate and hash concurrently, so only lower bounds hold
+        let during = memory_stats();
+        assert!(during.heap_bytes() >= block.len() as u64);
+        assert!(during.peak_heap_bytes() >= block.len() as u64);
+        assert!(during.bytes_hashed() >= before.bytes_hashed() + block.len() as u64);
+        assert_eq!(during.memory_bytes(), None);
+    }
+}
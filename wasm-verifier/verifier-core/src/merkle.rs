@@ -0,0 +1,77 @@
+// Merkle trees
+// RFC 9162-style trees, inclusion paths and root recomputation, shared by ONNX layer commitments and aggregated inferences
+
+use crate::error::VerificationError;
+use crate::hash::{digest, HashAlgorithm};
+
+pub const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+pub fn leaf_hash(data: &[u8], alg: HashAlgorithm) -> Result<Vec<u8>, VerificationError> {
+    digest(&[&[LEAF_PREFIX], data], alg)
+}
+
+fn node_hash(left: &[u8], right: &[u8], alg: HashAlgorithm) -> Result<Vec<u8>, VerificationError> {
+    digest(&[&[NODE_PREFIX], left, right], alg)
+}
+
+/// Largest power of two strictly less than `n` (n >= 2)
+fn split(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+pub fn tree_root(hashes: &[Vec<u8>], alg: HashAlgorithm) -> Result<Vec<u8>, VerificationError> {
+    match hashes.len() {
+        0 => digest(&[], alg),
+        1 => Ok(hashes[0].clone()),
+        n => {
+            let k = split(n);
+            node_hash(&tree_root(&hashes[..k], alg)?, &tree_root(&hashes[k..], alg)?, alg)
+        }
+    }
+}
+
+pub fn audit_path(index: usize, hashes: &[Vec<u8>], alg: HashAlgorithm) -> Result<Vec<Vec<u8>>, VerificationError> {
+    if hashes.len() <= 1 {
+        return Ok(Vec::new());
+    }
+    let k = split(hashes.len());
+    let (mut path, sibling) = if index < k {
+        (audit_path(index, &hashes[..k], alg)?, tree_root(&hashes[k..], alg)?)
+    } else {
+        (audit_path(index - k, &hashes[k..], alg)?, tree_root(&hashes[..k], alg)?)
+    };
+    path.push(sibling);
+    Ok(path)
+}
+
+/// Recompute the root from a leaf hash and its audit path (RFC 9162 section 2.1.3.2)
+pub fn root_from_path(
+    index: u64,
+    size: u64,
+    leaf: Vec<u8>,
+    path: &[Vec<u8>],
+    alg: HashAlgorithm,
+) -> Result<Option<Vec<u8>>, VerificationError> {
+    if index >= size {
+        return Ok(None);
+    }
+    let (mut fn_, mut sn, mut r) = (index, size - 1, leaf);
+    for p in path {
+        if sn == 0 {
+            return Ok(None);
+        }
+        if fn_ & 1 == 1 || fn_ == sn {
+            r = node_hash(p, &r, alg)?;
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            r = node_hash(&r, p, alg)?;
+        }
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    Ok((sn == 0).then_some(r))
+}
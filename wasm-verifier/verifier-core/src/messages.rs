@@ -0,0 +1,262 @@
+// End-user messages
+// Localized, non-technical wording for each `VerificationError`, separate from the developer-facing reasons
+
+use crate::error::VerificationError;
+use crate::report::{CheckResult, VerificationReport};
+
+/// Locales with a message catalog; anything else falls back to English
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Locale {
+    En,
+    De,
+    Es,
+    Fr,
+}
+
+impl Locale {
+    const ALL: [Locale; 4] = [Locale::En, Locale::De, Locale::Es, Locale::Fr];
+
+    fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Es => "es",
+            Locale::Fr => "fr",
+        }
+    }
+
+    /// Catalog for a BCP 47 tag such as `"de-AT"` or `"es_MX"`, by primary language
+    fn negotiate(tag: Option<&str>) -> Locale {
+        let language = tag.unwrap_or_default().split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        Locale::ALL.into_iter().find(|locale| locale.tag() == language).unwrap_or(Locale::En)
+    }
+}
+
+fn en(error: VerificationError) -> &'static str {
+    use VerificationError::*;
+    match error {
+        ModelMismatch => "This proof was generated for a different model.",
+        InputHashMismatch => "The input doesn't match the one this proof was generated for.",
+        OutputHashMismatch => "The result doesn't match the one this proof vouches for.",
+        Expired => "This proof has expired. Please request a new one.",
+        NotVerified => "This proof was never successfully verified by its prover.",
+        ProofHashMismatch => "The proof data has been altered.",
+        MalformedProof => "This proof is damaged or in an unknown format.",
+        InvalidArgument => "The verifier was given invalid data.",
+        UnsupportedHashAlgorithm => "This proof uses a method this verifier doesn't support.",
+        NonceMismatch => "This proof was not made in response to this request.",
+        NonceReplayed => "This proof has already been used.",
+        SignatureInvalid => "The prover's signature on this proof is invalid.",
+        UntrustedSigner => "This proof was signed by an unknown or untrusted prover.",
+        AggregateSignatureInvalid => "The co-signatures on this proof are invalid.",
+        InsufficientAttestors => "Not enough independent parties have confirmed this proof.",
+        CertificateChainInvalid => "The prover's certificate could not be validated.",
+        DidUnresolvable => "The prover's identity could not be looked up.",
+        PayloadTooLarge => "The data is too large to verify here.",
+        InvalidModel => "The model file is damaged or not supported.",
+        PolicyViolation => "The model doesn't meet the required policy.",
+        QuantizationMismatch => "The model ran with different numeric settings than expected.",
+        ShapeMismatch => "The data doesn't have the size or format the proof describes.",
+        PreprocessingMismatch => "The input was prepared differently than expected.",
+        RecomputationMismatch => "Re-running the model gave a different result.",
+        Revoked => "This proof has been withdrawn.",
+        VersionNotAllowed => "This proof comes from a model version that isn't allowed.",
+        CompositeInvalid => "One of the steps this result depends on could not be verified.",
+        InclusionInvalid => "This proof is not part of the batch it claims to belong to.",
+        ToleranceExceeded => "The result differs from the proven one by more than allowed.",
+        ClassMismatch => "The predicted category doesn't match the proven one.",
+        ProofUnavailable => "The proof could not be downloaded.",
+        Aborted => "Verification was cancelled.",
+        AnchorInvalid => "This proof was not recorded on the blockchain where it claims to be.",
+        NotRegistered => "This model or its prover isn't approved in the on-chain registry.",
+    }
+}
+
+fn de(error: VerificationError) -> &'static str {
+    use VerificationError::*;
+    match error {
+        ModelMismatch => "Dieser Nachweis wurde für ein anderes Modell erstellt.",
+        InputHashMismatch => "Die Eingabe stimmt nicht mit der überein, für die dieser Nachweis erstellt wurde.",
+        OutputHashMismatch => "Das Ergebnis stimmt nicht mit dem nachgewiesenen überein.",
+        Expired => "Dieser Nachweis ist abgelaufen. Bitte fordern Sie einen neuen an.",
+        NotVerified => "Dieser Nachweis wurde vom Ersteller nie erfolgreich geprüft.",
+        ProofHashMismatch => "Die Nachweisdaten wurden verändert.",
+        MalformedProof => "Dieser Nachweis ist beschädigt oder hat ein unbekanntes Format.",
+        InvalidArgument => "Die Prüfung hat ungültige Daten erhalten.",
+        UnsupportedHashAlgorithm => "Dieser Nachweis verwendet ein Verfahren, das hier nicht unterstützt wird.",
+        NonceMismatch => "Dieser Nachweis wurde nicht für diese Anfrage erstellt.",
+        NonceReplayed => "Dieser Nachweis wurde bereits verwendet.",
+        SignatureInvalid => "Die Signatur des Erstellers ist ungültig.",
+        UntrustedSigner => {
+            "Dieser Nachweis wurde von einem unbekannten oder nicht vertrauenswürdigen Ersteller signiert."
+        }
+        AggregateSignatureInvalid => "Die Mitsignaturen dieses Nachweises sind ungültig.",
+        InsufficientAttestors => "Nicht genügend unabhängige Stellen haben diesen Nachweis bestätigt.",
+        CertificateChainInvalid => "Das Zertifikat des Erstellers konnte nicht bestätigt werden.",
+        DidUnresolvable => "Die Identität des Erstellers konnte nicht ermittelt werden.",
+        PayloadTooLarge => "Die Daten sind zu groß, um sie hier zu prüfen.",
+        InvalidModel => "Die Modelldatei ist beschädigt oder wird nicht unterstützt.",
+        PolicyViolation => "Das Modell erfüllt die geforderten Richtlinien nicht.",
+        QuantizationMismatch => "Das Modell lief mit anderen numerischen Einstellungen als erwartet.",
+        ShapeMismatch => "Die Daten haben nicht die Größe oder das Format, das der Nachweis beschreibt.",
+        PreprocessingMismatch => "Die Eingabe wurde anders aufbereitet als erwartet.",
+        RecomputationMismatch => "Eine erneute Ausführung des Modells ergab ein anderes Ergebnis.",
+        Revoked => "Dieser Nachweis wurde zurückgezogen.",
+        VersionNotAllowed => "Dieser Nachweis stammt von einer nicht zugelassenen Modellversion.",
+        CompositeInvalid => "Einer der Schritte, auf denen dieses Ergebnis beruht, konnte nicht geprüft werden.",
+        InclusionInvalid => "Dieser Nachweis gehört nicht zu dem Stapel, zu dem er angeblich gehört.",
+        ToleranceExceeded => "Das Ergebnis weicht stärker als erlaubt vom nachgewiesenen ab.",
+        ClassMismatch => "Die vorhergesagte Kategorie stimmt nicht mit der nachgewiesenen überein.",
+        ProofUnavailable => "Der Nachweis konnte nicht heruntergeladen werden.",
+        Aborted => "Die Prüfung wurde abgebrochen.",
+        AnchorInvalid => "Dieser Nachweis wurde nicht dort auf der Blockchain verankert, wo er es angibt.",
+        NotRegistered => "Dieses Modell oder sein Ersteller ist in der On-Chain-Registry nicht zugelassen.",
+    }
+}
+
+fn es(error: VerificationError) -> &'static str {
+    use VerificationError::*;
+    match error {
+        ModelMismatch => "Esta prueba se generó para un modelo diferente.",
+        InputHashMismatch => "La entrada no coincide con la que se usó para generar esta prueba.",
+        OutputHashMismatch => "El resultado no coincide con el que respalda esta prueba.",
+        Expired => "Esta prueba ha caducado. Solicite una nueva.",
+        NotVerified => "Esta prueba nunca fue verificada correctamente por quien la generó.",
+        ProofHashMismatch => "Los datos de la prueba han sido alterados.",
+        MalformedProof => "Esta prueba está dañada o tiene un formato desconocido.",
+        InvalidArgument => "El verificador recibió datos no válidos.",
+        UnsupportedHashAlgorithm => "Esta prueba usa un método que este verificador no admite.",
+        NonceMismatch => "Esta prueba no se generó en respuesta a esta solicitud.",
+        NonceReplayed => "Esta prueba ya se ha utilizado.",
+        SignatureInvalid => "La firma de quien generó esta prueba no es válida.",
+        UntrustedSigner => "Esta prueba la firmó un emisor desconocido o que no es de confianza.",
+        AggregateSignatureInvalid => "Las firmas conjuntas de esta prueba no son válidas.",
+        InsufficientAttestors => "No hay suficientes partes independientes que hayan confirmado esta prueba.",
+        CertificateChainInvalid => "No se pudo validar el certificado de quien generó la prueba.",
+        DidUnresolvable => "No se pudo consultar la identidad de quien generó la prueba.",
+        PayloadTooLarge => "Los datos son demasiado grandes para verificarlos aquí.",
+        InvalidModel => "El archivo del modelo está dañado o no es compatible.",
+        PolicyViolation => "El modelo no cumple la política requerida.",
+        QuantizationMismatch => "El modelo se ejecutó con una configuración numérica distinta de la esperada.",
+        ShapeMismatch => "Los datos no tienen el tamaño o el formato que describe la prueba.",
+        PreprocessingMismatch => "La entrada se preparó de forma distinta a la esperada.",
+        RecomputationMismatch => "Al volver a ejecutar el modelo se obtuvo un resultado diferente.",
+        Revoked => "Esta prueba ha sido retirada.",
+        VersionNotAllowed => "Esta prueba proviene de una versión del modelo que no está permitida.",
+        CompositeInvalid => "No se pudo verificar uno de los pasos de los que depende este resultado.",
+        InclusionInvalid => "Esta prueba no forma parte del lote al que dice pertenecer.",
+        ToleranceExceeded => "El resultado difiere del probado más de lo permitido.",
+        ClassMismatch => "La categoría predicha no coincide con la probada.",
+        ProofUnavailable => "No se pudo descargar la prueba.",
+        Aborted => "La verificación se canceló.",
+        AnchorInvalid => "Esta prueba no quedó registrada en la blockchain donde dice estarlo.",
+        NotRegistered => "Este modelo o su emisor no está aprobado en el registro on-chain.",
+    }
+}
+
+fn fr(error: VerificationError) -> &'static str {
+    use VerificationError::*;
+    match error {
+        ModelMismatch => "Cette preuve a été générée pour un autre modèle.",
+        InputHashMismatch => "L'entrée ne correspond pas à celle pour laquelle cette preuve a été générée.",
+        OutputHashMismatch => "Le résultat ne correspond pas à celui que garantit cette preuve.",
+        Expired => "Cette preuve a expiré. Veuillez en demander une nouvelle.",
+        NotVerified => "Cette preuve n'a jamais été vérifiée avec succès par son auteur.",
+        ProofHashMismatch => "Les données de la preuve ont été modifiées.",
+        MalformedProof => "Cette preuve est endommagée ou dans un format inconnu.",
+        InvalidArgument => "Le vérificateur a reçu des données invalides.",
+        UnsupportedHashAlgorithm => "Cette preuve utilise une méthode que ce vérificateur ne prend pas en charge.",
+        NonceMismatch => "Cette preuve n'a pas été produite en réponse à cette demande.",
+        NonceReplayed => "Cette preuve a déjà été utilisée.",
+        SignatureInvalid => "La signature de l'auteur de cette preuve est invalide.",
+        UntrustedSigner => "Cette preuve a été signée par un auteur inconnu ou non fiable.",
+        AggregateSignatureInvalid => "Les cosignatures de cette preuve sont invalides.",
+        InsufficientAttestors => "Trop peu de parties indépendantes ont confirmé cette preuve.",
+        CertificateChainInvalid => "Le certificat de l'auteur n'a pas pu être validé.",
+        DidUnresolvable => "L'identité de l'auteur n'a pas pu être vérifiée.",
+        PayloadTooLarge => "Les données sont trop volumineuses pour être vérifiées ici.",
+        InvalidModel => "Le fichier du modèle est endommagé ou non pris en charge.",
+        PolicyViolation => "Le modèle ne respecte pas la politique requise.",
+        QuantizationMismatch => {
+            "Le modèle a été exécuté avec des paramètres numériques différents de ceux attendus."
+        }
+        ShapeMismatch => "Les données n'ont pas la taille ou le format décrits par la preuve.",
+        PreprocessingMismatch => "L'entrée a été préparée différemment de ce qui était attendu.",
+        RecomputationMismatch => "Une nouvelle exécution du modèle a donné un résultat différent.",
+        Revoked => "Cette preuve a été retirée.",
+        VersionNotAllowed => "Cette preuve provient d'une version du modèle non autorisée.",
+        CompositeInvalid => "L'une des étapes dont dépend ce résultat n'a pas pu être vérifiée.",
+        InclusionInvalid => "Cette preuve ne fait pas partie du lot auquel elle prétend appartenir.",
+        ToleranceExceeded => "Le résultat s'écarte du résultat prouvé au-delà de la marge autorisée.",
+        ClassMismatch => "La catégorie prédite ne correspond pas à celle prouvée.",
+        ProofUnavailable => "La preuve n'a pas pu être téléchargée.",
+        Aborted => "La vérification a été annulée.",
+        AnchorInvalid => "Cette preuve n'a pas été ancrée sur la blockchain là où elle le prétend.",
+        NotRegistered => "Ce modèle ou son auteur n'est pas approuvé dans le registre on-chain.",
+    }
+}
+
+fn message(error: VerificationError, locale: Locale) -> &'static str {
+    match locale {
+        Locale::En => en(error),
+        Locale::De => de(error),
+        Locale::Es => es(error),
+        Locale::Fr => fr(error),
+    }
+}
+
+/// End-user description of `error`, in `locale` (a BCP 47 tag)
+///
+/// Unknown locales fall back to English. Unlike a check's `reason`, the
+/// wording is meant for end users and stays the same across releases.
+pub fn user_message(error: VerificationError, locale: Option<&str>) -> &'static str {
+    message(error, Locale::negotiate(locale))
+}
+
+/// Language tags `user_message` has a catalog for
+pub fn supported_locales() -> Vec<&'static str> {
+    Locale::ALL.iter().map(|locale| locale.tag()).collect()
+}
+
+impl CheckResult {
+    /// `user_message` for this check's failure (`None` when it passed)
+    pub fn user_message(&self, locale: Option<&str>) -> Option<&'static str> {
+        self.error().map(|error| user_message(error, locale))
+    }
+}
+
+impl VerificationReport {
+    /// `user_message` for the first failed check (`None` when all passed)
+    pub fn user_message(&self, locale: Option<&str>) -> Option<&'static str> {
+        self.error().map(|error| user_message(error, locale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_are_translated() {
+        // The matches are exhaustive; this guards against English left in a catalog
+        let errors =
+            [VerificationError::ModelMismatch, VerificationError::Expired, VerificationError::ProofUnavailable];
+        for locale in Locale::ALL.into_iter().filter(|&locale| locale != Locale::En) {
+            for error in errors {
+                assert_ne!(message(error, locale), en(error), "{:?} {:?}", locale, error);
+            }
+        }
+    }
+
+    #[test]
+    fn test_locale_negotiation() {
+        assert_eq!(Locale::negotiate(Some("de-AT")), Locale::De);
+        assert_eq!(Locale::negotiate(Some("ES_mx")), Locale::Es);
+        assert_eq!(Locale::negotiate(Some("pt-BR")), Locale::En);
+        assert_eq!(Locale::negotiate(None), Locale::En);
+        assert_eq!(
+            user_message(VerificationError::ModelMismatch, Some("fr")),
+            "Cette preuve a été générée pour un autre modèle."
+        );
+    }
+}
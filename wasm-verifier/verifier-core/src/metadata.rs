@@ -0,0 +1,147 @@
+// Verifier metadata
+// Structured description of a configured verifier, for UIs and diagnostics (`info()` is the text summary)
+
+use serde::{Deserialize, Serialize};
+
+use crate::binary::BINARY_VERSION;
+use crate::hash::HashAlgorithm;
+use crate::report::Check;
+use crate::verifier::POLICY_CHECKS;
+use crate::Verifier;
+
+/// Every hash algorithm, whether or not this build enables it
+const HASH_ALGORITHMS: [HashAlgorithm; 4] =
+    [HashAlgorithm::Sha3_256, HashAlgorithm::Keccak256, HashAlgorithm::Sha256, HashAlgorithm::Blake3];
+
+/// What `Verifier::metadata` reports
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VerifierMetadata {
+    version: String,
+    proof_formats: Vec<String>,
+    model_hashes: Vec<String>,
+    checks: Vec<String>,
+    hash_algorithms: Vec<String>,
+    default_hash_algorithm: String,
+}
+
+impl VerifierMetadata {
+    /// Version of this verifier package
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Proof encodings this build accepts, e.g. `"json"` or `"zkpf/1"` (binary format version 1)
+    pub fn proof_formats(&self) -> &[String] {
+        &self.proof_formats
+    }
+
+    /// Models proofs may be bound to: the pinned hash, or every registry entry
+    pub fn model_hashes(&self) -> &[String] {
+        &self.model_hashes
+    }
+
+    /// Checks this verifier runs, by `CheckName`, in report order
+    ///
+    /// Includes the ones that only run when a proof carries the data they
+    /// check (I/O specs, signatures); policy checks that are switched off
+    /// are left out.
+    pub fn checks(&self) -> &[String] {
+        &self.checks
+    }
+
+    /// Hash algorithms enabled in this build
+    pub fn hash_algorithms(&self) -> &[String] {
+        &self.hash_algorithms
+    }
+
+    /// Algorithm for commitments of proofs that don't name one
+    pub fn default_hash_algorithm(&self) -> &str {
+        &self.default_hash_algorithm
+    }
+
+    /// Serialize metadata to JSON string
+        pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+fn check_name(check: Check) -> String {
+    serde_json::to_value(check).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+impl Verifier {
+    /// Checks that can record anything with this verifier's configuration
+    pub fn enabled_checks(&self) -> Vec<Check> {
+        let mut checks =
+            vec![Check::ModelBinding, Check::InputSpec, Check::InputHash, Check::OutputSpec, Check::OutputHash];
+        checks.extend(POLICY_CHECKS.into_iter().filter(|check| match check {
+            #[cfg(feature = "signatures")]
+            Check::CertificateChain => !self.trusted_roots.is_empty(),
+            Check::Quantization => self.quantization.is_some(),
+            Check::Preprocessing => self.preprocessing.is_some(),
+            #[cfg(feature = "signatures")]
+            Check::Revocation => self.revocations.is_some(),
+            #[cfg(not(feature = "signatures"))]
+            Check::CertificateChain | Check::Revocation => false,
+            Check::ModelVersion => self.version_policy.is_some() || self.registry.is_some(),
+            Check::Nonce => self.config.require_nonce(),
+            _ => true,
+        }));
+        checks
+    }
+
+    /// Structured description of this verifier, for a binding at package `version`
+    pub fn metadata(&self, version: &str) -> VerifierMetadata {
+        #[cfg(feature = "onnx")]
+        let pinned_leaves = self.commitment_policy.is_some();
+        #[cfg(not(feature = "onnx"))]
+        let pinned_leaves = false;
+        let model_hashes = match (&self.registry, pinned_leaves) {
+            (Some(registry), _) => registry.entries().iter().map(|entry| entry.model_hash().to_string()).collect(),
+            (None, true) => Vec::new(),
+            (None, false) => vec![self.model_hash.clone()],
+        };
+        let mut proof_formats = vec!["json".to_string(), format!("zkpf/{}", BINARY_VERSION)];
+        if cfg!(feature = "cbor") {
+            proof_formats.push("cbor".to_string());
+        }
+        if cfg!(feature = "onnx") {
+            proof_formats.push("protobuf".to_string());
+        }
+        if cfg!(feature = "signatures") {
+            proof_formats.extend(["jws", "cose"].map(str::to_string));
+        }
+        VerifierMetadata {
+            version: version.to_string(),
+            proof_formats,
+            model_hashes,
+            checks: self.enabled_checks().into_iter().map(check_name).collect(),
+            hash_algorithms: HASH_ALGORITHMS
+                .into_iter()
+                .filter(|alg| alg.is_available())
+                .map(|alg| alg.name().to_string())
+                .collect(),
+            default_hash_algorithm: self.config.hash_algorithm().name().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VerifierConfig;
+
+    #[test]
+    fn test_metadata() {
+        let verifier = Verifier::with_config("0xab".to_string(), &VerifierConfig::archival());
+        let metadata = verifier.metadata("1.2.3");
+        assert_eq!(metadata.model_hashes(), ["0xab"]);
+        assert_eq!(metadata.checks()[..3], ["model_binding", "input_spec", "input_hash"]);
+        assert!(metadata.checks().contains(&"timestamp".to_string()));
+        assert!(!metadata.checks().contains(&"nonce".to_string()));
+        assert!(metadata.hash_algorithms().contains(&"sha3-256".to_string()));
+        assert_eq!(metadata.hash_algorithms().contains(&"blake3".to_string()), cfg!(feature = "blake3"));
+        assert!(metadata.proof_formats().contains(&"zkpf/1".to_string()));
+        assert_eq!(metadata.version(), "1.2.3");
+    }
+}
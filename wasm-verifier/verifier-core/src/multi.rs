@@ -0,0 +1,129 @@
+// Named multi-input / multi-output proofs
+// Per-name I/O commitments for models with several inputs or outputs (e.g. tokens + attention mask)
+
+use std::collections::BTreeMap;
+
+use crate::error::VerificationError;
+use crate::hash::{HashAlgorithm, IoSource};
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::{ProofData, Verifier};
+
+/// What a proof's input or output commitment is checked against
+pub enum Io<'a> {
+    /// One buffer (or digest) for `input_hash`/`output_hash`
+    Single(IoSource<'a>),
+    /// Named buffers for the `inputs`/`outputs` maps
+    Named(&'a [(String, Vec<u8>)]),
+}
+
+/// Check named buffers against named commitments, recorded as one `check`
+///
+/// The names must match exactly: a committed tensor that wasn't supplied,
+/// or a supplied one the proof doesn't commit to, fails the check.
+pub fn check_named(
+    report: &mut VerificationReport,
+    check: Check,
+    buffers: &[(String, Vec<u8>)],
+    commitments: &BTreeMap<String, String>,
+    alg: &Result<HashAlgorithm, String>,
+    what: &str,
+) {
+    let started = Stopwatch::start();
+    let alg = match alg {
+        Ok(alg) => *alg,
+        Err(unsupported) => {
+            return report.record_error(check, VerificationError::UnsupportedHashAlgorithm, unsupported, started)
+        }
+    };
+    if commitments.is_empty() {
+        let reason = format!("Proof has no named {} commitments", what.to_ascii_lowercase());
+        return report.record(check, false, &reason, started);
+    }
+    if let Some((name, _)) = buffers.iter().find(|(name, _)| !commitments.contains_key(name)) {
+        return report.record(check, false, &format!("{} '{}' is not committed by the proof", what, name), started);
+    }
+    for (name, commitment) in commitments {
+        let Some((_, data)) = buffers.iter().find(|(n, _)| n == name) else {
+            return report.record(check, false, &format!("{} '{}' was not supplied", what, name), started);
+        };
+        match IoSource::Bytes(data).matches(commitment, alg) {
+            Ok(true) => {}
+            Ok(false) => {
+                let reason = format!("{} '{}' does not match its commitment", what, name);
+                return report.record(check, false, &reason, started);
+            }
+            Err(e) => {
+                let reason = "Commitment uses a hash algorithm not enabled in this build";
+                return report.record_error(check, e, reason, started);
+            }
+        }
+    }
+    report.record(check, true, "", started)
+}
+
+impl Verifier {
+    /// Verify a proof over named inputs and outputs
+    ///
+    /// Each buffer is checked against the proof's commitment of the same
+    /// name; `input_hash` and `output_hash` are not used.
+    pub fn multi_report(
+        &self,
+        proof_data: &ProofData,
+        inputs: &[(String, Vec<u8>)],
+        outputs: &[(String, Vec<u8>)],
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks_io(&mut report, proof_data, Io::Named(inputs), Io::Named(outputs));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+    use crate::hash::hash_bytes;
+
+    #[test]
+    fn test_multi_report() {
+        let hash = |data: &[u8]| hash_bytes(data, HashAlgorithm::default()).unwrap();
+        let verifier = Verifier::new("0xmodel".to_string());
+        let mut proof = ProofData::new(
+            "0xmodel".to_string(),
+            "0xproof".to_string(),
+            String::new(),
+            String::new(),
+            system_now_ms(),
+            true,
+        );
+        proof.inputs.insert("input_ids".into(), hash(b"tokens"));
+        proof.inputs.insert("attention_mask".into(), hash(b"mask"));
+        proof.outputs.insert("logits".into(), hash(b"logits"));
+
+        let named = |pairs: &[(&str, &[u8])]| -> Vec<(String, Vec<u8>)> {
+            pairs.iter().map(|(name, data)| (name.to_string(), data.to_vec())).collect()
+        };
+        let outputs = named(&[("logits", b"logits")]);
+        let inputs = named(&[("attention_mask", b"mask"), ("input_ids", b"tokens")]);
+        assert!(verifier.multi_report(&proof, &inputs, &outputs).passed());
+
+        let swapped = named(&[("attention_mask", b"tokens"), ("input_ids", b"mask")]);
+        let report = verifier.multi_report(&proof, &swapped, &outputs);
+        assert_eq!(report.failure_reasons(), ["Input 'attention_mask' does not match its commitment"]);
+
+        let missing = named(&[("input_ids", b"tokens")]);
+        let report = verifier.multi_report(&proof, &missing, &outputs);
+        assert_eq!(report.failure_reasons(), ["Input 'attention_mask' was not supplied"]);
+
+        // A single-buffer proof has nothing to check named buffers against
+        let single = ProofData::new(
+            "0xmodel".to_string(),
+            "0xproof".to_string(),
+            hash(b"tokens"),
+            hash(b"logits"),
+            system_now_ms(),
+            true,
+        );
+        assert!(!verifier.multi_report(&single, &missing, &outputs).check_passed(Check::InputHash));
+    }
+}
@@ -0,0 +1,156 @@
+// Challenge/nonce replay protection
+// The verifier issues single-use nonces that provers bind into `ProofData.nonce`
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::error::VerificationError;
+use crate::hex;
+
+/// Nonce length in bytes (hex-encoded to 64 characters)
+const NONCE_LEN: usize = 32;
+
+/// Nonces issued by a verifier and not yet consumed, plus those already used
+#[derive(Default)]
+pub struct NonceRegistry {
+    nonces: Mutex<Nonces>,
+}
+
+/// Issue time of each outstanding and each used nonce, behind one lock
+#[derive(Default)]
+struct Nonces {
+    issued: HashMap<String, u64>,
+    used: HashMap<String, u64>,
+}
+
+impl Nonces {
+    /// Forget nonces older than `max_age_ms`: they fail as unissued from now on
+    fn evict(&mut self, now: u64, max_age_ms: u64) {
+        let live = |_: &String, issued_at: &mut u64| now.saturating_sub(*issued_at) <= max_age_ms;
+        self.issued.retain(live);
+        self.used.retain(live);
+    }
+}
+
+impl NonceRegistry {
+    /// Issue a fresh random nonce, remembering when it was issued and
+    /// forgetting those older than `max_age_ms`
+    pub fn issue(&self, now: u64, max_age_ms: u64) -> Result<String, getrandom::Error> {
+        let mut bytes = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut bytes)?;
+        let nonce = format!("0x{}", hex::encode(&bytes));
+        let mut nonces = self.nonces.lock().unwrap();
+        nonces.evict(now, max_age_ms);
+        nonces.issued.insert(nonce.clone(), now);
+        Ok(nonce)
+    }
+
+    /// Check that `nonce` was issued by us, is unused, and is not older than
+    /// `max_age_ms`, then (if `consume`) mark it used so later proofs carrying
+    /// it are rejected. Both happen under one lock, so of two concurrent
+    /// verifications of the same nonce only one is accepted.
+    pub fn check_and_consume(
+        &self,
+        nonce: Option<&str>,
+        now: u64,
+        max_age_ms: u64,
+        consume: bool,
+    ) -> Result<(), (VerificationError, &'static str)> {
+        let nonce = nonce
+            .map(normalize)
+            .ok_or((VerificationError::NonceMismatch, "Proof does not carry a nonce"))?;
+
+        let mut nonces = self.nonces.lock().unwrap();
+        if nonces.used.contains_key(&nonce) {
+            return Err((VerificationError::NonceReplayed, "Proof nonce has already been used"));
+        }
+        let issued_at = match nonces.issued.get(&nonce) {
+            Some(&issued_at) if now.saturating_sub(issued_at) <= max_age_ms => issued_at,
+            Some(_) => return Err((VerificationError::NonceMismatch, "Proof nonce has expired")),
+            None => return Err((VerificationError::NonceMismatch, "Proof nonce was not issued by this verifier")),
+        };
+        if consume {
+            nonces.issued.remove(&nonce);
+            nonces.used.insert(nonce, issued_at);
+            nonces.evict(now, max_age_ms);
+        }
+        Ok(())
+    }
+}
+
+/// Nonces are compared case-insensitively, with or without `0x`
+fn normalize(nonce: &str) -> String {
+    let lower = nonce.to_ascii_lowercase();
+    match lower.strip_prefix("0x") {
+        Some(rest) => format!("0x{}", rest),
+        None => format!("0x{}", lower),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Barrier;
+
+    #[test]
+    fn test_nonce_lifecycle() {
+        let registry = NonceRegistry::default();
+        let nonce = registry.issue(1_000, 10_000).unwrap();
+
+        assert!(registry.check_and_consume(Some(&nonce.to_uppercase()[2..]), 1_500, 10_000, false).is_ok());
+        let expired = registry.check_and_consume(Some(&nonce), 20_000, 10_000, true).unwrap_err();
+        assert_eq!(expired, (VerificationError::NonceMismatch, "Proof nonce has expired"));
+        let missing = registry.check_and_consume(None, 1_500, 10_000, true).unwrap_err();
+        assert_eq!(missing, (VerificationError::NonceMismatch, "Proof does not carry a nonce"));
+        let unknown = registry.check_and_consume(Some("0xdead"), 1_500, 10_000, true).unwrap_err();
+        assert_eq!(unknown.0, VerificationError::NonceMismatch);
+
+        assert!(registry.check_and_consume(Some(&nonce), 1_500, 10_000, true).is_ok());
+        let replayed = registry.check_and_consume(Some(&nonce), 1_500, 10_000, true).unwrap_err();
+        assert_eq!(replayed.0, VerificationError::NonceReplayed);
+    }
+
+    #[test]
+    fn test_expired_nonces_are_evicted() {
+        let registry = NonceRegistry::default();
+        let stale = registry.issue(1_000, 10_000).unwrap();
+        let used = registry.issue(1_000, 10_000).unwrap();
+        registry.check_and_consume(Some(&used), 1_500, 10_000, true).unwrap();
+        assert_eq!(registry.nonces.lock().unwrap().issued.len(), 1);
+        assert_eq!(registry.nonces.lock().unwrap().used.len(), 1);
+
+        let fresh = registry.issue(20_000, 10_000).unwrap();
+        let nonces = registry.nonces.lock().unwrap();
+        assert_eq!(nonces.issued.keys().collect::<Vec<_>>(), [&fresh]);
+        assert!(nonces.used.is_empty());
+        drop(nonces);
+
+        // Forgotten nonces are still rejected, as never issued
+        for nonce in [stale, used] {
+            let error = registry.check_and_consume(Some(&nonce), 20_000, 10_000, true).unwrap_err();
+            assert_eq!(error, (VerificationError::NonceMismatch, "Proof nonce was not issued by this verifier"));
+        }
+    }
+
+    #[test]
+    fn test_concurrent_replay_is_accepted_once() {
+        const THREADS: usize = 16;
+        let registry = NonceRegistry::default();
+        for _ in 0..50 {
+            let nonce = registry.issue(1_000, 10_000).unwrap();
+            let barrier = Barrier::new(THREADS);
+            let accepted = std::thread::scope(|scope| {
+                let attempts: Vec<_> = (0..THREADS)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            barrier.wait();
+                            registry.check_and_consume(Some(&nonce), 1_500, 10_000, true)
+                        })
+                    })
+                    .collect();
+                attempts.into_iter().filter_map(|attempt| attempt.join().unwrap().ok()).count()
+            });
+            assert_eq!(accepted, 1);
+        }
+    }
+}
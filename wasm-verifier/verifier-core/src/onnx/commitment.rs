@@ -0,0 +1,239 @@
+// Structured model commitment
+// `model_hash` as the root over separate architecture, weights and metadata hashes
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use super::proto::{GraphProto, ModelProto, SparseTensorProto, TensorProto};
+use super::{canonical_model, parse_model, strip_graph};
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::hash::{self, hash_bytes, split_commitment, HashAlgorithm};
+use crate::{hex, ProofData, Verifier};
+
+/// Domain separator for the root (format version 1)
+const ROOT_DOMAIN: &[u8] = b"zkml/model-commitment/v1\0";
+
+/// Name, shape and type of a tensor, without its data
+fn tensor_header(tensor: &TensorProto) -> TensorProto {
+    TensorProto {
+        name: tensor.name.clone(),
+        dims: tensor.dims.clone(),
+        data_type: tensor.data_type,
+        ..Default::default()
+    }
+}
+
+/// Architecture, weights and metadata encodings of a model
+///
+/// The architecture is the canonical model (see `canonical_model`) with
+/// initializers reduced to their name, shape and type; the weights are the
+/// initializers themselves; the metadata is what `canonical_model` strips
+/// from the model header. Constant nodes stay part of the architecture.
+fn leaves(model_bytes: &[u8]) -> Result<[Vec<u8>; 3], VerificationError> {
+    let mut model = parse_model(model_bytes)?;
+    let metadata = ModelProto {
+        producer_name: model.producer_name.clone(),
+        producer_version: model.producer_version.clone(),
+        domain: model.domain.clone(),
+        model_version: model.model_version,
+        doc_string: model.doc_string.clone(),
+        metadata_props: model.metadata_props.clone(),
+        ..Default::default()
+    };
+
+    let graph = model.graph.get_or_insert_with(GraphProto::default);
+    let mut weights = GraphProto {
+        initializer: std::mem::take(&mut graph.initializer),
+        sparse_initializer: std::mem::take(&mut graph.sparse_initializer),
+        ..Default::default()
+    };
+    graph.initializer = weights.initializer.iter().map(tensor_header).collect();
+    graph.sparse_initializer = weights
+        .sparse_initializer
+        .iter()
+        .map(|sparse| SparseTensorProto {
+            values: sparse.values.as_ref().map(tensor_header),
+            indices: sparse.indices.as_ref().map(tensor_header),
+            dims: sparse.dims.clone(),
+        })
+        .collect();
+    strip_graph(&mut weights);
+
+    Ok([canonical_model(model), weights.encode_to_vec(), metadata.encode_to_vec()])
+}
+
+/// `H(domain || (u32 len || digest) x 3)` over the leaf commitments
+pub fn root(leaves: [&str; 3], alg: HashAlgorithm) -> Result<String, VerificationError> {
+    let mut out = ROOT_DOMAIN.to_vec();
+    for leaf in leaves {
+        let digest = hex::decode(split_commitment(leaf)?.1).ok_or(VerificationError::InvalidArgument)?;
+        out.extend_from_slice(&(digest.len() as u32).to_le_bytes());
+        out.extend_from_slice(&digest);
+    }
+    hash_bytes(&out, alg)
+}
+
+/// Architecture, weights and metadata hashes of a model, and their root
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModelCommitment {
+    architecture_hash: String,
+    weights_hash: String,
+    metadata_hash: String,
+    root: String,
+}
+
+impl ModelCommitment {
+    pub fn create(model_bytes: &[u8], alg: HashAlgorithm) -> Result<ModelCommitment, VerificationError> {
+        let [architecture, weights, metadata] = leaves(model_bytes)?;
+        let (architecture_hash, weights_hash, metadata_hash) =
+            (hash_bytes(&architecture, alg)?, hash_bytes(&weights, alg)?, hash_bytes(&metadata, alg)?);
+        let root = root([&architecture_hash, &weights_hash, &metadata_hash], alg)?;
+        Ok(ModelCommitment { architecture_hash, weights_hash, metadata_hash, root })
+    }
+
+    pub fn architecture_hash(&self) -> &str {
+        &self.architecture_hash
+    }
+
+    pub fn weights_hash(&self) -> &str {
+        &self.weights_hash
+    }
+
+    pub fn metadata_hash(&self) -> &str {
+        &self.metadata_hash
+    }
+
+    /// Root over the three leaves, for use as `model_hash`
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+}
+
+impl ProofData {
+    /// Set `model_hash` to the commitment's root and carry its three leaves
+    pub fn set_model_commitment(&mut self, commitment: &ModelCommitment) {
+        self.model_hash = commitment.root.clone();
+        self.architecture_hash = Some(commitment.architecture_hash.clone());
+        self.weights_hash = Some(commitment.weights_hash.clone());
+        self.metadata_hash = Some(commitment.metadata_hash.clone());
+    }
+}
+
+/// Which leaves of a structured model commitment a verifier pins
+///
+/// Unpinned leaves may take any value, e.g. pin the architecture and
+/// approve a list of fine-tuned weights.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommitmentPolicy {
+    architecture_hash: Option<String>,
+    weights_hashes: Vec<String>,
+    metadata_hash: Option<String>,
+}
+
+impl CommitmentPolicy {
+    /// Check a proof's leaves against its `model_hash` and this policy
+    pub fn check(&self, proof: &ProofData, alg: HashAlgorithm) -> Result<(), &'static str> {
+        let (Some(architecture), Some(weights), Some(metadata)) =
+            (&proof.architecture_hash, &proof.weights_hash, &proof.metadata_hash)
+        else {
+            return Err("Proof does not carry a structured model commitment");
+        };
+        let root = root([architecture, weights, metadata], alg).map_err(|_| "Malformed model commitment leaves")?;
+        if !hash::commitments_equal(&root, &proof.model_hash) {
+            return Err("model_hash is not the root of the proof's model commitment");
+        }
+        if self.architecture_hash.as_ref().is_some_and(|pinned| !hash::commitments_equal(pinned, architecture)) {
+            return Err("Proof's model architecture is not the pinned one");
+        }
+        if !self.weights_hashes.is_empty() && !self.weights_hashes.iter().any(|w| hash::commitments_equal(w, weights)) {
+            return Err("Proof's model weights are not among the approved ones");
+        }
+        if self.metadata_hash.as_ref().is_some_and(|pinned| !hash::commitments_equal(pinned, metadata)) {
+            return Err("Proof's model metadata is not the pinned one");
+        }
+        Ok(())
+    }
+
+    /// Policy pinning nothing (any well-formed commitment passes)
+    pub fn new() -> CommitmentPolicy {
+        CommitmentPolicy::default()
+    }
+
+    pub fn architecture_hash(&self) -> Option<&str> {
+        self.architecture_hash.as_deref()
+    }
+
+    pub fn set_architecture_hash(&mut self, architecture_hash: Option<String>) {
+        self.architecture_hash = architecture_hash;
+    }
+
+    /// Approved weights hashes (empty = any weights)
+    pub fn weights_hashes(&self) -> &[String] {
+        &self.weights_hashes
+    }
+
+    pub fn approve_weights(&mut self, weights_hash: String) {
+        self.weights_hashes.push(weights_hash);
+    }
+
+    pub fn metadata_hash(&self) -> Option<&str> {
+        self.metadata_hash.as_deref()
+    }
+
+    pub fn set_metadata_hash(&mut self, metadata_hash: Option<String>) {
+        self.metadata_hash = metadata_hash;
+    }
+}
+
+impl Verifier {
+    /// Verifier binding proofs to structured model commitments allowed by `policy`
+    ///
+    /// `model_hash` is then unused: proofs must carry the three leaves,
+    /// their root must be the proof's `model_hash`, and pinned leaves must match.
+    pub fn with_commitment_policy(policy: &CommitmentPolicy, config: &VerifierConfig) -> Verifier {
+        let mut verifier = Verifier::with_config(String::new(), config);
+        verifier.commitment_policy = Some(policy.clone());
+        verifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+    use crate::onnx::tests::sample_model;
+
+    #[test]
+    fn test_commitment_policy() {
+        let base = sample_model();
+        let mut fine_tuned = base.clone();
+        fine_tuned.graph.as_mut().unwrap().initializer[0].float_data[0] = 0.5;
+        fine_tuned.producer_name = "trainer".into();
+        let mut widened = base.clone();
+        widened.graph.as_mut().unwrap().initializer[0].dims = vec![1, 4];
+
+        let alg = HashAlgorithm::default();
+        let commit = |model: &ModelProto| ModelCommitment::create(&model.encode_to_vec(), alg).unwrap();
+        let (base, fine_tuned, widened) = (commit(&base), commit(&fine_tuned), commit(&widened));
+        assert_eq!(base.architecture_hash(), fine_tuned.architecture_hash());
+        assert_ne!(base.weights_hash(), fine_tuned.weights_hash());
+        assert_ne!(base.architecture_hash(), widened.architecture_hash());
+
+        let mut policy = CommitmentPolicy::new();
+        policy.set_architecture_hash(Some(base.architecture_hash().to_string()));
+        policy.approve_weights(base.weights_hash().to_string());
+        policy.approve_weights(fine_tuned.weights_hash().to_string());
+        let verifier = Verifier::with_commitment_policy(&policy, &VerifierConfig::default());
+
+        let prove = |commitment: &ModelCommitment| {
+            let io = hash_bytes(b"io", alg).unwrap();
+            let mut proof = ProofData::new(String::new(), "0xproof".into(), io.clone(), io, system_now_ms(), true);
+            proof.set_model_commitment(commitment);
+            verifier.verify_report(&proof, b"io", b"io")
+        };
+        assert!(prove(&base).passed());
+        assert!(prove(&fine_tuned).passed());
+        assert_eq!(prove(&widened).error(), Some(VerificationError::ModelMismatch));
+    }
+}
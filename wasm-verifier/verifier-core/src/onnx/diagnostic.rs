@@ -0,0 +1,154 @@
+// Model binding diagnostics
+// Why a proof's model_hash doesn't match a verifier built from the model file
+
+use serde::{Deserialize, Serialize};
+
+use super::commitment::{root, ModelCommitment};
+use crate::error::VerificationError;
+use crate::hash::{self, hash_bytes, HashAlgorithm};
+use crate::ProofData;
+
+/// Sub-hashes of the verifier's model, kept to explain binding failures
+pub struct ModelFingerprint {
+    algorithm: HashAlgorithm,
+    /// Hash of the file bytes as given
+    file_hash: String,
+    commitment: ModelCommitment,
+}
+
+impl ModelFingerprint {
+    pub fn create(model_bytes: &[u8], algorithm: HashAlgorithm) -> Result<ModelFingerprint, VerificationError> {
+        Ok(ModelFingerprint {
+            algorithm,
+            file_hash: hash_bytes(model_bytes, algorithm)?,
+            commitment: ModelCommitment::create(model_bytes, algorithm)?,
+        })
+    }
+
+    /// Compare what the proof commits to against this model's sub-hashes
+    pub fn diagnose(&self, proof: &ProofData, alg: HashAlgorithm) -> ModelDiagnostic {
+        if alg != self.algorithm {
+            let summary = format!(
+                "Proof hashes with {} but the model's sub-hashes use {}; cannot compare",
+                alg.name(),
+                self.algorithm.name()
+            );
+            return ModelDiagnostic { summary, ..Default::default() };
+        }
+        let leaf =
+            |claimed: &Option<String>, actual: String| claimed.as_ref().map(|c| hash::commitments_equal(c, &actual));
+        let c = &self.commitment;
+        let mut diagnostic = ModelDiagnostic {
+            architecture: leaf(&proof.architecture_hash, c.architecture_hash().to_string()),
+            weights: leaf(&proof.weights_hash, c.weights_hash().to_string()),
+            metadata: leaf(&proof.metadata_hash, c.metadata_hash().to_string()),
+            file_hash: hash::commitments_equal(&proof.model_hash, &self.file_hash),
+            summary: String::new(),
+        };
+        diagnostic.summary = diagnostic.summarize(proof, alg);
+        diagnostic
+    }
+}
+
+/// Which parts of the model a failed binding differs in
+///
+/// Leaf comparisons are `None` when the proof doesn't carry that
+/// sub-hash (see `ProofData::set_model_commitment`).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDiagnostic {
+    architecture: Option<bool>,
+    weights: Option<bool>,
+    metadata: Option<bool>,
+    file_hash: bool,
+    summary: String,
+}
+
+impl ModelDiagnostic {
+    fn summarize(&self, proof: &ProofData, alg: HashAlgorithm) -> String {
+        if self.file_hash {
+            return "model_hash is the hash of the model file bytes, not its canonical hash_onnx_model hash".into();
+        }
+        let (Some(architecture), Some(weights), Some(metadata)) = (self.architecture, self.weights, self.metadata)
+        else {
+            return "Proof carries no architecture/weights/metadata hashes to compare against".into();
+        };
+        let leaves =
+            [&proof.architecture_hash, &proof.weights_hash, &proof.metadata_hash].map(|l| l.as_deref().unwrap_or(""));
+        let consistent = root(leaves, alg).is_ok_and(|r| hash::commitments_equal(&r, &proof.model_hash));
+        let differing: Vec<&str> = [(architecture, "architecture"), (weights, "weights"), (metadata, "metadata")]
+            .into_iter()
+            .filter_map(|(matches, part)| (!matches).then_some(part))
+            .collect();
+        match differing.as_slice() {
+            [] if consistent => "model_hash is this model's structured commitment root, not its canonical hash".into(),
+            [] => "Sub-hashes match this model but model_hash is none of its hashes".into(),
+            ["metadata"] => "Only the model metadata differs (a re-export of the same model)".into(),
+            parts => format!("Model {} differ", parts.join(" and ")),
+        }
+    }
+
+    /// Whether the proof's `architecture_hash` matches (`None` if it has none)
+    pub fn architecture(&self) -> Option<bool> {
+        self.architecture
+    }
+
+    pub fn weights(&self) -> Option<bool> {
+        self.weights
+    }
+
+    pub fn metadata(&self) -> Option<bool> {
+        self.metadata
+    }
+
+    /// Whether `model_hash` is the plain hash of the model file bytes
+    pub fn file_hash(&self) -> bool {
+        self.file_hash
+    }
+
+    /// One-line explanation, also appended to the binding failure reason
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::system_now_ms;
+    use crate::onnx::tests::sample_model;
+    use crate::{Verifier, VerifierConfig};
+    use prost::Message;
+
+    #[test]
+    fn test_model_diagnostic() {
+        let mut model = sample_model();
+        model.producer_name = "exporter".into();
+        let bytes = model.encode_to_vec();
+        let verifier = Verifier::try_from_model_bytes(&bytes, &VerifierConfig::default()).unwrap();
+        let alg = HashAlgorithm::default();
+        let io = hash_bytes(b"io", alg).unwrap();
+        let proof = |model_hash: String| {
+            ProofData::new(model_hash, "0xproof".into(), io.clone(), io.clone(), system_now_ms(), true)
+        };
+
+        let report = verifier.verify_report(&proof(hash_bytes(&bytes, alg).unwrap()), b"io", b"io");
+        assert!(report.model_diagnostic().unwrap().file_hash());
+
+        let mut reexported = model.clone();
+        reexported.producer_name = "another exporter".into();
+        let mut fine_tuned = model.clone();
+        fine_tuned.graph.as_mut().unwrap().initializer[0].float_data[0] = 9.0;
+        let diagnose = |model: &crate::onnx::proto::ModelProto| {
+            let mut p = proof(String::new());
+            let commitment = ModelCommitment::create(&model.encode_to_vec(), alg).unwrap();
+            p.set_model_commitment(&commitment);
+            let report = verifier.verify_report(&p, b"io", b"io");
+            let summary = report.model_diagnostic().map(|d| d.summary().to_string());
+            (report.check_passed(crate::Check::ModelBinding), summary)
+        };
+        let (bound, summary) = diagnose(&reexported);
+        assert!(!bound);
+        assert_eq!(summary.unwrap(), "Only the model metadata differs (a re-export of the same model)");
+        assert_eq!(diagnose(&fine_tuned).1.unwrap(), "Model weights differ");
+    }
+}
@@ -0,0 +1,150 @@
+// External-data ONNX model hashing
+// Canonical hash of a model whose initializers live in separate `.bin` files
+
+use std::collections::HashMap;
+
+use super::proto::{ModelProto, StringStringEntryProto};
+use super::{canonical_model, parse_model};
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm, StreamingHasher};
+
+/// `TensorProto.DataLocation.EXTERNAL`
+const EXTERNAL: i32 = 1;
+
+/// One initializer stored outside the model file
+struct ExternalTensor {
+    initializer: usize,
+    location: String,
+    offset: u64,
+    /// `None` means "to the end of the file"
+    length: Option<u64>,
+    hasher: StreamingHasher,
+}
+
+impl ExternalTensor {
+    /// Byte range `[start, end)` of the tensor in its file, given how much of the file has been seen
+    fn range(&self, file_len: u64) -> (u64, u64) {
+        (self.offset, self.length.map_or(file_len, |len| self.offset.saturating_add(len)))
+    }
+}
+
+/// Canonical hash of an ONNX model plus its external data files
+///
+/// Each external tensor is hashed on its own and its `external_data`
+/// entries are replaced by that digest and its length before the model is
+/// hashed as by `model_hash`. The hash therefore doesn't depend on file
+/// names or how tensors are laid out across files, and files can be fed
+/// in chunks of any size.
+pub struct ExternalDataHasher {
+    model: ModelProto,
+    algorithm: HashAlgorithm,
+    tensors: Vec<ExternalTensor>,
+    /// Bytes seen so far per file
+    positions: HashMap<String, u64>,
+}
+
+impl ExternalDataHasher {
+    pub fn create(model_bytes: &[u8], algorithm: HashAlgorithm) -> Result<ExternalDataHasher, VerificationError> {
+        let model = parse_model(model_bytes)?;
+        let mut tensors = Vec::new();
+        let initializers = model.graph.as_ref().map(|g| g.initializer.as_slice()).unwrap_or_default();
+        for (initializer, tensor) in initializers.iter().enumerate() {
+            if tensor.data_location != EXTERNAL {
+                continue;
+            }
+            let entry = |key: &str| tensor.external_data.iter().find(|e| e.key == key).map(|e| e.value.as_str());
+            let number = |key: &str| entry(key).map(|v| v.parse::<u64>().map_err(|_| VerificationError::InvalidModel));
+            tensors.push(ExternalTensor {
+                initializer,
+                location: entry("location").ok_or(VerificationError::InvalidModel)?.to_string(),
+                offset: number("offset").transpose()?.unwrap_or(0),
+                length: number("length").transpose()?,
+                hasher: StreamingHasher::with_algorithm(algorithm)?,
+            });
+        }
+        let positions = tensors.iter().map(|t| (t.location.clone(), 0)).collect();
+        Ok(ExternalDataHasher { model, algorithm, tensors, positions })
+    }
+
+    /// External data files the model references (`location` entries), sorted
+    pub fn required_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self.positions.keys().cloned().collect();
+        files.sort();
+        files
+    }
+
+    /// Feed the next `chunk` of `location`; chunks of a file must arrive in order
+    pub fn feed(&mut self, location: &str, chunk: &[u8]) -> Result<(), VerificationError> {
+        let pos = self.positions.get_mut(location).ok_or(VerificationError::InvalidArgument)?;
+        let (chunk_start, chunk_end) = (*pos, *pos + chunk.len() as u64);
+        for tensor in self.tensors.iter_mut().filter(|t| t.location == location) {
+            let (start, end) = tensor.range(u64::MAX);
+            let (from, to) = (start.max(chunk_start), end.min(chunk_end));
+            if from < to {
+                tensor.hasher.update(&chunk[(from - chunk_start) as usize..(to - chunk_start) as usize]);
+            }
+        }
+        *pos = chunk_end;
+        Ok(())
+    }
+
+    /// Canonical model hash; `InvalidModel` unless every external tensor was fed in full
+    pub fn finish(mut self) -> Result<String, VerificationError> {
+        let graph = self.model.graph.as_mut().ok_or(VerificationError::InvalidModel)?;
+        for mut tensor in self.tensors {
+            let (start, end) = tensor.range(self.positions[&tensor.location]);
+            let length = end.checked_sub(start).ok_or(VerificationError::InvalidModel)?;
+            if tensor.hasher.bytes_processed() != length {
+                return Err(VerificationError::InvalidModel);
+            }
+            graph.initializer[tensor.initializer].external_data = vec![
+                StringStringEntryProto { key: "digest".into(), value: tensor.hasher.finalize() },
+                StringStringEntryProto { key: "length".into(), value: length.to_string() },
+            ];
+        }
+        hash_bytes(&canonical_model(self.model), self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onnx::tests::sample_model;
+    use prost::Message;
+
+    fn external_model(location: &str, offset: u64) -> Vec<u8> {
+        let mut model = sample_model();
+        let weight = &mut model.graph.as_mut().unwrap().initializer[0];
+        weight.float_data.clear();
+        weight.data_location = EXTERNAL;
+        let entry = |key: &str, value: String| StringStringEntryProto { key: key.into(), value };
+        weight.external_data =
+            vec![entry("location", location.into()), entry("offset", offset.to_string()), entry("length", "16".into())];
+        model.encode_to_vec()
+    }
+
+    fn hash(model: &[u8], location: &str, file: &[u8], chunk: usize) -> Result<String, VerificationError> {
+        let mut hasher = ExternalDataHasher::create(model, HashAlgorithm::Sha3_256)?;
+        file.chunks(chunk).try_for_each(|c| hasher.feed(location, c))?;
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_external_data_hash() {
+        let weights: Vec<u8> = [1.0f32, -2.0, 3.0, -4.0].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let file = [b"pad!".as_slice(), &weights, b"trailing"].concat();
+        let model = external_model("weights.bin", 4);
+        let expected = hash(&model, "weights.bin", &file, file.len()).unwrap();
+        assert_eq!(hash(&model, "weights.bin", &file, 3).unwrap(), expected);
+
+        // Same tensor under another file name and offset
+        let moved = [b"other header".as_slice(), &weights].concat();
+        assert_eq!(hash(&external_model("model.data", 12), "model.data", &moved, 5).unwrap(), expected);
+
+        let mut tampered = file.clone();
+        tampered[10] ^= 1;
+        assert_ne!(hash(&model, "weights.bin", &tampered, 7).unwrap(), expected);
+        assert_eq!(hash(&model, "weights.bin", &file[..12], 4), Err(VerificationError::InvalidModel));
+        assert_eq!(hash(&model, "other.bin", &file, 4), Err(VerificationError::InvalidArgument));
+    }
+}
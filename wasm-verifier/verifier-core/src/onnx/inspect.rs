@@ -0,0 +1,207 @@
+// ONNX model inspection
+// Summarizes a model's interface and size for display in verifier UIs
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+use super::proto::{ModelProto, NodeProto, TensorProto, ValueInfoProto};
+use super::{dtype_name, parse_model};
+use crate::error::VerificationError;
+
+/// A graph input or output
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TensorInfo {
+    name: String,
+    dtype: String,
+    shape: Vec<i64>,
+    dim_params: Vec<String>,
+}
+
+impl TensorInfo {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Element type, e.g. `"float32"` (`"unknown"` for non-tensor values)
+    pub fn dtype(&self) -> &str {
+        &self.dtype
+    }
+
+    /// Dimensions, with -1 for dynamic ones
+    pub fn shape(&self) -> &[i64] {
+        &self.shape
+    }
+
+    /// Symbolic names of the dimensions (`""` where the size is fixed or unnamed)
+    pub fn dim_params(&self) -> &[String] {
+        &self.dim_params
+    }
+}
+
+/// What `inspect_model` reports about an ONNX model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModelInfo {
+    ir_version: i64,
+    opset_version: i64,
+    producer_name: String,
+    inputs: Vec<TensorInfo>,
+    outputs: Vec<TensorInfo>,
+    parameter_count: u64,
+    node_count: u32,
+    op_types: Vec<String>,
+}
+
+impl ModelInfo {
+    pub fn ir_version(&self) -> i64 {
+        self.ir_version
+    }
+
+    /// Version of the default (`ai.onnx`) operator set, 0 if not imported
+    pub fn opset_version(&self) -> i64 {
+        self.opset_version
+    }
+
+    pub fn producer_name(&self) -> &str {
+        &self.producer_name
+    }
+
+    /// Runtime inputs (initializers listed as graph inputs are excluded)
+    pub fn inputs(&self) -> &[TensorInfo] {
+        &self.inputs
+    }
+
+    pub fn outputs(&self) -> &[TensorInfo] {
+        &self.outputs
+    }
+
+    /// Total number of weight elements across all initializers
+    pub fn parameter_count(&self) -> u64 {
+        self.parameter_count
+    }
+
+    pub fn node_count(&self) -> u32 {
+        self.node_count
+    }
+
+    /// Distinct operator types, `domain::Op` for non-default domains, sorted
+    pub fn op_types(&self) -> &[String] {
+        &self.op_types
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+fn tensor_info(info: &ValueInfoProto) -> TensorInfo {
+    let tensor = info.r#type.as_ref().and_then(|t| t.tensor_type.as_ref());
+    let dims = tensor.and_then(|t| t.shape.as_ref()).map(|s| s.dim.as_slice()).unwrap_or_default();
+    TensorInfo {
+        name: info.name.clone(),
+        dtype: tensor.map_or("unknown", |t| dtype_name(t.elem_type)).to_string(),
+        shape: dims.iter().map(|d| d.dim_value.unwrap_or(-1)).collect(),
+        dim_params: dims.iter().map(|d| d.dim_param.clone().unwrap_or_default()).collect(),
+    }
+}
+
+/// Number of elements in a tensor (product of its dims)
+pub fn element_count(tensor: &TensorProto) -> u64 {
+    tensor.dims.iter().map(|&d| u64::try_from(d).unwrap_or(0)).product()
+}
+
+/// Distinct operator types used by a model's graph, its subgraphs (`If`,
+/// `Loop`, ...) and its local functions
+pub fn op_types(model: &ModelProto) -> BTreeSet<String> {
+    fn collect(nodes: &[NodeProto], ops: &mut BTreeSet<String>) {
+        for node in nodes {
+            ops.insert(match node.domain.as_str() {
+                "" | "ai.onnx" => node.op_type.clone(),
+                domain => format!("{domain}::{}", node.op_type),
+            });
+            for attribute in &node.attribute {
+                for graph in attribute.g.iter().chain(attribute.graphs.iter()) {
+                    collect(&graph.node, ops);
+                }
+            }
+        }
+    }
+
+    let mut ops = BTreeSet::new();
+    if let Some(graph) = &model.graph {
+        collect(&graph.node, &mut ops);
+    }
+    for function in &model.functions {
+        collect(&function.node, &mut ops);
+    }
+    ops
+}
+
+/// Version of the default operator set a model imports
+pub fn default_opset(model: &ModelProto) -> i64 {
+    model
+        .opset_import
+        .iter()
+        .find(|opset| opset.domain.is_empty() || opset.domain == "ai.onnx")
+        .map_or(0, |opset| opset.version)
+}
+
+pub fn inspect(model: &ModelProto) -> ModelInfo {
+    let graph = model.graph.clone().unwrap_or_default();
+    let weights: HashSet<&str> = graph.initializer.iter().map(|t| t.name.as_str()).collect();
+    let sparse_count: u64 = graph.sparse_initializer.iter().filter_map(|s| s.values.as_ref()).map(element_count).sum();
+
+    ModelInfo {
+        ir_version: model.ir_version,
+        opset_version: default_opset(model),
+        producer_name: model.producer_name.clone(),
+        inputs: graph.input.iter().filter(|i| !weights.contains(i.name.as_str())).map(tensor_info).collect(),
+        outputs: graph.output.iter().map(tensor_info).collect(),
+        parameter_count: graph.initializer.iter().map(element_count).sum::<u64>() + sparse_count,
+        node_count: graph.node.len() as u32,
+        op_types: op_types(model).into_iter().collect(),
+    }
+}
+
+/// Describe an ONNX model: opset, inputs/outputs (names, shapes, dtypes) and parameter count
+pub fn inspect_model(model_bytes: &[u8]) -> Result<ModelInfo, VerificationError> {
+    parse_model(model_bytes).map(|model| inspect(&model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onnx::proto::{Dimension, TensorShapeProto, TypeProto, TypeTensor};
+    use crate::onnx::tests::sample_model;
+
+    #[test]
+    fn test_inspect_model() {
+        let mut model = sample_model();
+        let graph = model.graph.as_mut().unwrap();
+        graph.input[0].r#type = Some(TypeProto {
+            tensor_type: Some(TypeTensor {
+                elem_type: 1,
+                shape: Some(TensorShapeProto {
+                    dim: vec![
+                        Dimension { dim_param: Some("batch".into()), ..Default::default() },
+                        Dimension { dim_value: Some(2), ..Default::default() },
+                    ],
+                }),
+            }),
+            ..Default::default()
+        });
+        // Pre-IR-4 exporters list weights as inputs too
+        graph.input.push(ValueInfoProto { name: "W".into(), ..Default::default() });
+
+        let info = inspect(&model);
+        assert_eq!(info.opset_version(), 17);
+        assert_eq!(info.parameter_count(), 4);
+        assert_eq!(info.op_types(), ["MatMul", "Relu"]);
+        assert_eq!(info.inputs().len(), 1);
+
+        let input = &info.inputs()[0];
+        assert_eq!(input.dtype(), "float32");
+        assert_eq!(input.shape(), [-1, 2]);
+        assert_eq!(input.dim_params(), ["batch", ""]);
+        assert_eq!(info.outputs()[0].dtype(), "unknown");
+    }
+}
@@ -0,0 +1,173 @@
+// Per-layer Merkle commitment of an ONNX graph
+// RFC 9162-style tree over the graph header, each node and each initializer
+
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use super::{canonical_model, parse_model, strip_node, strip_tensor};
+use crate::error::VerificationError;
+use crate::hash::{self, HashAlgorithm};
+pub use crate::merkle::{audit_path, leaf_hash, root_from_path, tree_root, LEAF_PREFIX};
+use crate::hex;
+
+/// Label of the leaf holding everything but nodes and initializers
+pub const HEADER_LAYER: &str = "header";
+
+/// Labelled leaves of a model, in tree order: header, nodes, initializers
+///
+/// Nodes are labelled `node/<index>` (their position in the graph) and
+/// initializers `initializer/<name>`. Each leaf is `label || 0x00 || canonical protobuf`.
+pub fn leaves(model_bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, VerificationError> {
+    let mut model = parse_model(model_bytes)?;
+    let graph = model.graph.take().unwrap_or_default();
+
+    let mut header = model;
+    header.graph = Some(super::proto::GraphProto {
+        node: Vec::new(),
+        initializer: Vec::new(),
+        sparse_initializer: Vec::new(),
+        ..graph.clone()
+    });
+    let mut leaves = vec![(HEADER_LAYER.to_string(), canonical_model(header))];
+
+    for (i, mut node) in graph.node.into_iter().enumerate() {
+        strip_node(&mut node);
+        leaves.push((format!("node/{i}"), node.encode_to_vec()));
+    }
+    for mut tensor in graph.initializer.into_iter() {
+        strip_tensor(&mut tensor);
+        leaves.push((format!("initializer/{}", tensor.name), tensor.encode_to_vec()));
+    }
+    Ok(leaves)
+}
+
+/// Labels of the layers committed by `merkle_root`, in leaf order
+pub fn layer_labels(model_bytes: &[u8]) -> Result<Vec<String>, VerificationError> {
+    Ok(leaves(model_bytes)?.into_iter().map(|(label, _)| label).collect())
+}
+
+pub fn leaf_data(label: &str, data: &[u8]) -> Vec<u8> {
+    [label.as_bytes(), &[0], data].concat()
+}
+
+fn leaf_hashes(model_bytes: &[u8], alg: HashAlgorithm) -> Result<(Vec<String>, Vec<Vec<u8>>), VerificationError> {
+    let leaves = leaves(model_bytes)?;
+    let hashes = leaves.iter().map(|(label, data)| leaf_hash(&leaf_data(label, data), alg)).collect::<Result<_, _>>()?;
+    Ok((leaves.into_iter().map(|(label, _)| label).collect(), hashes))
+}
+
+/// Merkle root of a model's layers, as a `0x...` commitment
+pub fn merkle_root(model_bytes: &[u8], alg: HashAlgorithm) -> Result<String, VerificationError> {
+    let (_, hashes) = leaf_hashes(model_bytes, alg)?;
+    Ok(format!("0x{}", hex::encode(&tree_root(&hashes, alg)?)))
+}
+
+/// Inclusion proof for one layer of a Merkle-committed ONNX model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LayerProof {
+    layer: String,
+    index: u64,
+    leaf_count: u64,
+    algorithm: HashAlgorithm,
+    /// Canonical protobuf of the layer (hex)
+    data: String,
+    /// Sibling hashes from the leaf up to the root (hex)
+    path: Vec<String>,
+}
+
+impl LayerProof {
+    /// Proof for `layer` (see `layer_labels`); `InvalidArgument` if the model has no such layer
+    pub fn create(model_bytes: &[u8], layer: &str, alg: HashAlgorithm) -> Result<LayerProof, VerificationError> {
+        let leaves = leaves(model_bytes)?;
+        let index = leaves.iter().position(|(label, _)| label == layer).ok_or(VerificationError::InvalidArgument)?;
+        let hashes: Vec<Vec<u8>> =
+            leaves.iter().map(|(label, data)| leaf_hash(&leaf_data(label, data), alg)).collect::<Result<_, _>>()?;
+
+        Ok(LayerProof {
+            layer: layer.to_string(),
+            index: index as u64,
+            leaf_count: leaves.len() as u64,
+            algorithm: alg,
+            data: hex::encode(&leaves[index].1),
+            path: audit_path(index, &hashes, alg)?.iter().map(|h| hex::encode(h)).collect(),
+        })
+    }
+
+    /// Whether this proof places the layer under `root`
+    pub fn verifies(&self, root: &str) -> bool {
+        let path: Option<Vec<Vec<u8>>> = self.path.iter().map(|h| hex::decode(h)).collect();
+        let (Some(data), Some(path)) = (hex::decode(&self.data), path) else {
+            return false;
+        };
+        leaf_hash(&leaf_data(&self.layer, &data), self.algorithm)
+            .and_then(|leaf| root_from_path(self.index, self.leaf_count, leaf, &path, self.algorithm))
+            .ok()
+            .flatten()
+            .is_some_and(|computed| hash::digests_equal(&hex::encode(&computed), root))
+    }
+
+    /// Layer label, e.g. `node/3` or `initializer/classifier.weight`
+    pub fn layer(&self) -> &str {
+        &self.layer
+    }
+
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Canonical protobuf encoding of the layer (`NodeProto` or `TensorProto`)
+    pub fn data(&self) -> Vec<u8> {
+        hex::decode(&self.data).unwrap_or_default()
+    }
+
+    /// Hash of the layer's canonical protobuf, to compare against a published layer hash
+    pub fn layer_hash(&self) -> String {
+        hash::hash_bytes(&self.data(), self.algorithm).unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<LayerProof, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onnx::tests::sample_model;
+
+    #[test]
+    fn test_layer_inclusion() {
+        let mut model = sample_model();
+        // Five leaves, so the tree is unbalanced
+        let graph = model.graph.as_mut().unwrap();
+        graph.node.push(graph.node[1].clone());
+        let bytes = model.encode_to_vec();
+        let root = merkle_root(&bytes, HashAlgorithm::Sha3_256).unwrap();
+        let layers = layer_labels(&bytes).unwrap();
+        assert_eq!(layers, ["header", "node/0", "node/1", "node/2", "initializer/W"]);
+
+        for layer in &layers {
+            let proof = LayerProof::create(&bytes, layer, HashAlgorithm::Sha3_256).unwrap();
+            assert!(proof.verifies(&root), "{layer}");
+        }
+
+        let mut forged = LayerProof::create(&bytes, "initializer/W", HashAlgorithm::Sha3_256).unwrap();
+        forged.data.replace_range(..2, "ff");
+        assert!(!forged.verifies(&root));
+        let mut moved = LayerProof::create(&bytes, "node/1", HashAlgorithm::Sha3_256).unwrap();
+        moved.index += 1;
+        assert!(!moved.verifies(&root));
+    }
+}
@@ -0,0 +1,217 @@
+// ONNX model hashing
+// Commits to a model's semantics rather than its exact serialized bytes
+
+use prost::Message;
+
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::Verifier;
+
+pub mod commitment;
+pub mod diagnostic;
+pub mod external;
+pub mod inspect;
+pub mod merkle;
+pub mod partial;
+pub mod policy;
+pub mod proto;
+pub mod stream;
+
+use proto::{AttributeProto, FunctionProto, GraphProto, ModelProto, NodeProto, TensorProto, ValueInfoProto};
+
+/// Name of an ONNX `TensorProto.DataType`
+pub fn dtype_name(data_type: i32) -> &'static str {
+    match data_type {
+        1 => "float32",
+        2 => "uint8",
+        3 => "int8",
+        4 => "uint16",
+        5 => "int16",
+        6 => "int32",
+        7 => "int64",
+        8 => "string",
+        9 => "bool",
+        10 => "float16",
+        11 => "float64",
+        12 => "uint32",
+        13 => "uint64",
+        14 => "complex64",
+        15 => "complex128",
+        16 => "bfloat16",
+        17 => "float8e4m3fn",
+        18 => "float8e4m3fnuz",
+        19 => "float8e5m2",
+        20 => "float8e5m2fnuz",
+        21 => "uint4",
+        22 => "int4",
+        23 => "float4e2m1",
+        _ => "unknown",
+    }
+}
+
+/// Parse ONNX `ModelProto` bytes
+pub fn parse_model(bytes: &[u8]) -> Result<ModelProto, VerificationError> {
+    ModelProto::decode(bytes).map_err(|_| VerificationError::InvalidModel)
+}
+
+/// Canonical encoding of a model: metadata stripped, fields in tag order
+///
+/// Drops everything that doesn't change what the model computes: producer
+/// name/version, model version and domain, doc strings, metadata props,
+/// graph and node names, and inferred `value_info`. Unknown fields are
+/// dropped by the decoder. Tensor, input and output names are kept since
+/// the graph wires nodes together by them.
+pub fn canonical_model(mut model: ModelProto) -> Vec<u8> {
+    model.producer_name.clear();
+    model.producer_version.clear();
+    model.domain.clear();
+    model.model_version = 0;
+    model.doc_string.clear();
+    model.metadata_props.clear();
+    if let Some(graph) = &mut model.graph {
+        strip_graph(graph);
+    }
+    model.functions.iter_mut().for_each(strip_function);
+    model.encode_to_vec()
+}
+
+pub fn strip_graph(graph: &mut GraphProto) {
+    graph.name.clear();
+    graph.doc_string.clear();
+    graph.metadata_props.clear();
+    graph.value_info.clear();
+    graph.node.iter_mut().for_each(strip_node);
+    graph.initializer.iter_mut().for_each(strip_tensor);
+    for sparse in &mut graph.sparse_initializer {
+        sparse.values.iter_mut().chain(sparse.indices.iter_mut()).for_each(strip_tensor);
+    }
+    graph.input.iter_mut().chain(graph.output.iter_mut()).for_each(strip_value_info);
+}
+
+pub fn strip_node(node: &mut NodeProto) {
+    node.name.clear();
+    node.doc_string.clear();
+    node.metadata_props.clear();
+    node.attribute.iter_mut().for_each(strip_attribute);
+}
+
+fn strip_attribute(attribute: &mut AttributeProto) {
+    attribute.doc_string.clear();
+    attribute.t.iter_mut().chain(attribute.tensors.iter_mut()).for_each(strip_tensor);
+    attribute.g.iter_mut().chain(attribute.graphs.iter_mut()).for_each(strip_graph);
+}
+
+pub fn strip_tensor(tensor: &mut TensorProto) {
+    tensor.doc_string.clear();
+    tensor.metadata_props.clear();
+}
+
+fn strip_value_info(info: &mut ValueInfoProto) {
+    info.doc_string.clear();
+    info.metadata_props.clear();
+}
+
+fn strip_function(function: &mut FunctionProto) {
+    function.doc_string.clear();
+    function.metadata_props.clear();
+    function.value_info.clear();
+    function.node.iter_mut().for_each(strip_node);
+    function.attribute_proto.iter_mut().for_each(strip_attribute);
+}
+
+/// Hash ONNX model bytes in canonical form
+pub fn model_hash(bytes: &[u8], algorithm: HashAlgorithm) -> Result<String, VerificationError> {
+    hash_bytes(&canonical_model(parse_model(bytes)?), algorithm)
+}
+
+impl Verifier {
+    /// Verifier for an ONNX model file, hashing it internally
+    ///
+    /// The model is kept in canonical form and re-hashed with each proof's
+    /// algorithm, and binding failures get a `ModelDiagnostic`.
+    pub fn try_from_model_bytes(model: &[u8], config: &VerifierConfig) -> Result<Verifier, VerificationError> {
+        let canonical = canonical_model(parse_model(model)?);
+        let model_hash = hash_bytes(&canonical, config.hash_algorithm())?;
+        let fingerprint = diagnostic::ModelFingerprint::create(model, config.hash_algorithm())?;
+        let mut verifier = Verifier::with_config(model_hash, config);
+        verifier.canonical_model = Some(canonical);
+        verifier.model_fingerprint = Some(fingerprint);
+        Ok(verifier)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use proto::{
+        GraphProto, ModelProto, NodeProto, OperatorSetIdProto, StringStringEntryProto, TensorProto, ValueInfoProto,
+    };
+
+    /// `y = Relu(x · W)` with a 2x2 weight
+    pub(crate) fn sample_model() -> ModelProto {
+        let weight = TensorProto {
+            name: "W".into(),
+            dims: vec![2, 2],
+            data_type: 1,
+            float_data: vec![1.0, -2.0, 3.0, -4.0],
+            ..Default::default()
+        };
+        let node = |op: &str, inputs: &[&str], output: &str| NodeProto {
+            op_type: op.into(),
+            input: inputs.iter().map(|s| s.to_string()).collect(),
+            output: vec![output.into()],
+            ..Default::default()
+        };
+        ModelProto {
+            ir_version: 9,
+            opset_import: vec![OperatorSetIdProto { domain: String::new(), version: 17 }],
+            graph: Some(GraphProto {
+                node: vec![node("MatMul", &["x", "W"], "h"), node("Relu", &["h"], "y")],
+                initializer: vec![weight],
+                input: vec![ValueInfoProto { name: "x".into(), ..Default::default() }],
+                output: vec![ValueInfoProto { name: "y".into(), ..Default::default() }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_canonical_model_hash() {
+        let model = sample_model();
+        let hash = model_hash(&model.encode_to_vec(), HashAlgorithm::Sha3_256).unwrap();
+
+        let mut reexported = model.clone();
+        reexported.producer_name = "pytorch".into();
+        reexported.doc_string = "exported on Tuesday".into();
+        reexported.metadata_props.push(StringStringEntryProto { key: "date".into(), value: "2024".into() });
+        let graph = reexported.graph.as_mut().unwrap();
+        graph.name = "main_graph".into();
+        graph.node[0].name = "/layer1/MatMul".into();
+        assert_eq!(model_hash(&reexported.encode_to_vec(), HashAlgorithm::Sha3_256).unwrap(), hash);
+
+        let mut changed = model;
+        changed.graph.as_mut().unwrap().node[1].op_type = "Sigmoid".into();
+        assert_ne!(model_hash(&changed.encode_to_vec(), HashAlgorithm::Sha3_256).unwrap(), hash);
+
+        assert_eq!(model_hash(b"not onnx", HashAlgorithm::Sha3_256).unwrap_err(), VerificationError::InvalidModel);
+    }
+
+    #[test]
+    fn test_from_model_bytes() {
+        let bytes = sample_model().encode_to_vec();
+        let verifier = Verifier::try_from_model_bytes(&bytes, &VerifierConfig::default()).unwrap();
+        assert_eq!(verifier.model_hash, model_hash(&bytes, HashAlgorithm::Sha3_256).unwrap());
+
+        // A SHA-256 proof binds to the same verifier
+        let io = hash_bytes(b"io", HashAlgorithm::Sha256).unwrap();
+        let model = model_hash(&bytes, HashAlgorithm::Sha256).unwrap();
+        let now = crate::clock::system_now_ms();
+        let mut proof = crate::ProofData::new(model, "0xproof".into(), io.clone(), io, now, true);
+        proof.hash_alg = Some("sha256".into());
+        assert!(verifier.verify_report(&proof, b"io", b"io").passed());
+        proof.hash_alg = None;
+        assert!(!verifier.verify_report(&proof, b"io", b"io").check_passed(crate::Check::ModelBinding));
+    }
+}
@@ -0,0 +1,87 @@
+// Partial model commitment
+// Commits to a chosen subset of an ONNX model's layers, e.g. only a fine-tuned classification head
+
+use super::merkle::{leaf_data, leaf_hash, leaves, tree_root};
+use crate::error::VerificationError;
+use crate::hash::HashAlgorithm;
+use crate::{hex, Verifier};
+
+/// Merkle root over the selected layers (labels as in `merkle::layer_labels`)
+///
+/// Layers are sorted by label first, so the selection order doesn't matter.
+/// Returns the root and the sorted labels. Fails with `InvalidArgument` if
+/// a label isn't in the model or the selection is empty.
+pub fn partial_root(
+    model_bytes: &[u8],
+    layers: &[String],
+    alg: HashAlgorithm,
+) -> Result<(String, Vec<String>), VerificationError> {
+    let mut selected = layers.to_vec();
+    selected.sort();
+    selected.dedup();
+    if selected.is_empty() {
+        return Err(VerificationError::InvalidArgument);
+    }
+
+    let leaves = leaves(model_bytes)?;
+    let hashes = selected
+        .iter()
+        .map(|label| {
+            let (_, data) = leaves.iter().find(|(l, _)| l == label).ok_or(VerificationError::InvalidArgument)?;
+            leaf_hash(&leaf_data(label, data), alg)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((format!("0x{}", hex::encode(&tree_root(&hashes, alg)?)), selected))
+}
+
+impl Verifier {
+    /// Verifier bound only to some layers of a model
+    ///
+    /// Model binding then checks the proof's `partial_model_hash` and
+    /// `partial_layers` instead of `model_hash`, so any model sharing these
+    /// layers (e.g. the same fine-tuned head on a public base) is accepted.
+    pub fn from_model_layers(
+        model_bytes: &[u8],
+        layers: &[String],
+        alg: HashAlgorithm,
+    ) -> Result<Verifier, VerificationError> {
+        let (root, layers) = partial_root(model_bytes, layers, alg)?;
+        let mut verifier = Verifier::with_hash_algorithm(root, alg);
+        verifier.partial_layers = Some(layers);
+        Ok(verifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::onnx::tests::sample_model;
+    use crate::Check;
+    use prost::Message;
+
+    #[test]
+    fn test_partial_model_binding() {
+        let base = sample_model();
+        let bytes = base.encode_to_vec();
+        let head = vec!["initializer/W".to_string()];
+        let verifier = Verifier::from_model_layers(&bytes, &head, HashAlgorithm::Sha3_256).unwrap();
+
+        // Another version of the model: different graph, same head
+        let mut other = base.clone();
+        other.graph.as_mut().unwrap().node.truncate(1);
+        let (root, layers) = partial_root(&other.encode_to_vec(), &head, HashAlgorithm::Sha3_256).unwrap();
+
+        let mut proof = fixtures::proof_for("0xsomeothermodel", b"in", b"out", HashAlgorithm::Sha3_256);
+        proof.partial_model_hash = Some(root);
+        proof.partial_layers = layers;
+        assert!(verifier.verify_report(&proof, b"in", b"out").passed());
+
+        // Claiming the commitment covers a different selection fails
+        proof.partial_layers = vec!["node/0".to_string()];
+        assert!(!verifier.verify_report(&proof, b"in", b"out").check_passed(Check::ModelBinding));
+
+        let unknown = partial_root(&bytes, &["initializer/missing".to_string()], HashAlgorithm::Sha3_256);
+        assert_eq!(unknown.unwrap_err(), VerificationError::InvalidArgument);
+    }
+}
@@ -0,0 +1,169 @@
+// Model architecture policy
+// Constraints an ONNX graph must satisfy before its hash is accepted by a verifier
+
+use serde::{Deserialize, Serialize};
+
+use super::inspect::{default_opset, element_count, op_types};
+use super::proto::ModelProto;
+use super::{canonical_model, parse_model};
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::Verifier;
+
+/// Operator domains defined by the ONNX standard; anything else is a custom op
+const STANDARD_DOMAINS: [&str; 3] = ["", "ai.onnx", "ai.onnx.ml"];
+
+/// Architecture constraints for ONNX models
+///
+/// The default policy only rejects custom (non-standard-domain) ops.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ModelPolicy {
+    min_opset: i64,
+    max_opset: i64,
+    allowed_ops: Vec<String>,
+    allow_custom_ops: bool,
+    max_parameters: u64,
+}
+
+impl ModelPolicy {
+    /// Lowest accepted `ai.onnx` opset (0 = no minimum)
+    pub fn min_opset(&self) -> i64 {
+        self.min_opset
+    }
+
+    pub fn set_min_opset(&mut self, min_opset: i64) {
+        self.min_opset = min_opset;
+    }
+
+    /// Highest accepted `ai.onnx` opset (0 = no maximum)
+    pub fn max_opset(&self) -> i64 {
+        self.max_opset
+    }
+
+    pub fn set_max_opset(&mut self, max_opset: i64) {
+        self.max_opset = max_opset;
+    }
+
+    /// Operator types the graph may use (empty = any standard op)
+    pub fn allowed_ops(&self) -> &[String] {
+        &self.allowed_ops
+    }
+
+    pub fn set_allowed_ops(&mut self, allowed_ops: Vec<String>) {
+        self.allowed_ops = allowed_ops;
+    }
+
+    /// Accept ops from non-standard domains and model-local functions
+    pub fn allow_custom_ops(&self) -> bool {
+        self.allow_custom_ops
+    }
+
+    pub fn set_allow_custom_ops(&mut self, allow_custom_ops: bool) {
+        self.allow_custom_ops = allow_custom_ops;
+    }
+
+    /// Largest accepted parameter count (0 = unlimited)
+    pub fn max_parameters(&self) -> u64 {
+        self.max_parameters
+    }
+
+    pub fn set_max_parameters(&mut self, max_parameters: u64) {
+        self.max_parameters = max_parameters;
+    }
+
+    /// Every way the model breaks this policy (empty if it complies)
+    pub fn violations(&self, model_bytes: &[u8]) -> Result<Vec<String>, VerificationError> {
+        parse_model(model_bytes).map(|model| self.check(&model))
+    }
+
+    pub fn check(&self, model: &ModelProto) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        let opset = default_opset(model);
+        if self.min_opset > 0 && opset < self.min_opset {
+            violations.push(format!("opset {opset} is below the minimum {}", self.min_opset));
+        }
+        if self.max_opset > 0 && opset > self.max_opset {
+            violations.push(format!("opset {opset} is above the maximum {}", self.max_opset));
+        }
+
+        let local_functions: Vec<String> =
+            model.functions.iter().map(|f| format!("{}::{}", f.domain, f.name)).collect();
+        for op in op_types(model) {
+            let custom = match op.split_once("::") {
+                Some((domain, _)) => !STANDARD_DOMAINS.contains(&domain) || local_functions.contains(&op),
+                None => local_functions.contains(&format!("::{op}")),
+            };
+            if custom && !self.allow_custom_ops {
+                violations.push(format!("custom op {op} is not allowed"));
+            } else if !self.allowed_ops.is_empty() && !self.allowed_ops.contains(&op) {
+                violations.push(format!("op {op} is not in the allowed set"));
+            }
+        }
+
+        if self.max_parameters > 0 {
+            let parameters: u64 = model.graph.iter().flat_map(|g| g.initializer.iter()).map(element_count).sum();
+            if parameters > self.max_parameters {
+                violations.push(format!("{parameters} parameters exceed the maximum {}", self.max_parameters));
+            }
+        }
+        violations
+    }
+}
+
+impl Verifier {
+    /// Verifier for an ONNX model, enforcing `policy` first
+    ///
+    /// The model hash is the canonical `model_hash`. Fails with
+    /// `PolicyViolation` listing every violation, so a model with custom
+    /// ops is rejected even if its hash is allowlisted.
+    pub fn from_onnx_model(
+        model_bytes: &[u8],
+        policy: &ModelPolicy,
+        alg: HashAlgorithm,
+    ) -> Result<Verifier, (VerificationError, String)> {
+        let model = parse_model(model_bytes).map_err(|e| (e, "Bytes are not a valid ONNX model".to_string()))?;
+        let violations = policy.check(&model);
+        if !violations.is_empty() {
+            return Err((VerificationError::PolicyViolation, violations.join("; ")));
+        }
+
+        let model_hash = hash_bytes(&canonical_model(model), alg)
+            .map_err(|e| (e, "Hash algorithm is not enabled in this build".to_string()))?;
+        Ok(Verifier::with_hash_algorithm(model_hash, alg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onnx::proto::NodeProto;
+    use crate::onnx::tests::sample_model;
+
+    #[test]
+    fn test_model_policy() {
+        let mut model = sample_model();
+        let mut policy = ModelPolicy::default();
+        assert!(policy.check(&model).is_empty());
+
+        policy.set_min_opset(18);
+        policy.set_allowed_ops(vec!["MatMul".into()]);
+        policy.set_max_parameters(3);
+        assert_eq!(
+            policy.check(&model),
+            [
+                "opset 17 is below the minimum 18",
+                "op Relu is not in the allowed set",
+                "4 parameters exceed the maximum 3",
+            ]
+        );
+
+        model.graph.as_mut().unwrap().node.push(NodeProto {
+            op_type: "Backdoor".into(),
+            domain: "com.example".into(),
+            ..Default::default()
+        });
+        let policy = ModelPolicy::default();
+        assert_eq!(policy.check(&model), ["custom op com.example::Backdoor is not allowed"]);
+    }
+}
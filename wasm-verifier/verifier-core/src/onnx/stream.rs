@@ -0,0 +1,353 @@
+// Streaming ONNX model hashing
+// Layer Merkle root of a model fed in chunks, without ever holding its weights in memory
+
+use prost::encoding::{encode_key, encode_varint, WireType};
+use prost::Message;
+
+use super::merkle::{leaf_data, leaf_hash, tree_root, HEADER_LAYER, LEAF_PREFIX};
+use super::proto::{GraphProto, ModelProto, NodeProto, TensorProto};
+use super::{canonical_model, strip_node, strip_tensor};
+use crate::error::VerificationError;
+use crate::hash::{HashAlgorithm, StreamingHasher};
+use crate::hex;
+
+/// Fields the parser descends into rather than buffering
+const MODEL_GRAPH: u64 = 7;
+const GRAPH_NODE: u64 = 1;
+const GRAPH_INITIALIZER: u64 = 5;
+const TENSOR_RAW_DATA: u64 = 9;
+const LENGTH_DELIMITED: u64 = 2;
+
+fn invalid<E>(_: E) -> VerificationError {
+    VerificationError::InvalidModel
+}
+
+/// Varint at the start of `buf` and its length (`None` if incomplete)
+fn varint(buf: &[u8]) -> Result<Option<(u64, usize)>, VerificationError> {
+    let mut value = 0u64;
+    for (i, &b) in buf.iter().enumerate().take(10) {
+        value |= u64::from(b & 0x7f) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok(Some((value, i + 1)));
+        }
+    }
+    if buf.len() >= 10 { Err(VerificationError::InvalidModel) } else { Ok(None) }
+}
+
+/// Key of a protobuf field, with the length of its key (and length prefix) and of its body
+struct Field {
+    number: u64,
+    wire: u64,
+    header: usize,
+    body: u64,
+}
+
+/// Parse the field starting `buf` (`None` until its key and length prefix are complete)
+fn field(buf: &[u8]) -> Result<Option<Field>, VerificationError> {
+    let Some((key, key_len)) = varint(buf)? else {
+        return Ok(None);
+    };
+    let (header, body) = match key & 7 {
+        0 => match varint(&buf[key_len..])? {
+            Some((_, len)) => (key_len, len as u64),
+            None => return Ok(None),
+        },
+        1 => (key_len, 8),
+        5 => (key_len, 4),
+        2 => match varint(&buf[key_len..])? {
+            Some((len, prefix_len)) => (key_len + prefix_len, len),
+            None => return Ok(None),
+        },
+        _ => return Err(VerificationError::InvalidModel),
+    };
+    Ok(Some(Field { number: key >> 3, wire: key & 7, header, body }))
+}
+
+/// Tensor fields encoded before `raw_data` (field 9) in canonical, tag-ordered form
+fn before_raw_data(t: &TensorProto) -> TensorProto {
+    TensorProto {
+        dims: t.dims.clone(),
+        data_type: t.data_type,
+        float_data: t.float_data.clone(),
+        int32_data: t.int32_data.clone(),
+        string_data: t.string_data.clone(),
+        int64_data: t.int64_data.clone(),
+        name: t.name.clone(),
+        ..Default::default()
+    }
+}
+
+/// Tensor fields encoded after `raw_data` (`doc_string` and `metadata_props` are stripped)
+fn after_raw_data(t: &TensorProto) -> TensorProto {
+    TensorProto {
+        double_data: t.double_data.clone(),
+        uint64_data: t.uint64_data.clone(),
+        external_data: t.external_data.clone(),
+        data_location: t.data_location,
+        ..Default::default()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    Model,
+    Graph,
+    Initializer,
+    RawData,
+}
+
+/// Chunked hasher producing `merkle::merkle_root` of a model
+///
+/// The flat `model_hash` hash can't be streamed: the canonical graph
+/// is length-prefixed, and its length depends on fields after the weights.
+/// The Merkle root can, so use it as `model_hash` for models too large to
+/// load at once. Initializer `raw_data` is hashed as it arrives; everything
+/// else (nodes, graph inputs and outputs, tensor headers) is buffered, one
+/// field at a time. Initializers must have their fields in tag order, as
+/// ONNX exporters write them.
+///
+pub struct ModelHasher {
+    algorithm: HashAlgorithm,
+    /// Bytes received but not consumed yet (at most one buffered field)
+    pending: Vec<u8>,
+    /// Bytes consumed so far
+    offset: u64,
+    scope: Scope,
+    graph_end: u64,
+    initializer_end: u64,
+    raw_data_end: u64,
+    seen_graph: bool,
+    /// `ModelProto` fields other than `graph`
+    model_fields: Vec<u8>,
+    /// `GraphProto` fields other than nodes and initializers
+    graph_fields: Vec<u8>,
+    /// Fields of the current initializer (after `raw_data`, once it started)
+    initializer_fields: Vec<u8>,
+    /// Leaf hasher of the current initializer, once its `raw_data` started
+    leaf: Option<StreamingHasher>,
+    nodes: Vec<Vec<u8>>,
+    initializers: Vec<Vec<u8>>,
+    failed: bool,
+}
+
+impl ModelHasher {
+    pub fn create(algorithm: HashAlgorithm) -> Result<ModelHasher, VerificationError> {
+        StreamingHasher::with_algorithm(algorithm)?;
+        Ok(ModelHasher {
+            algorithm,
+            pending: Vec::new(),
+            offset: 0,
+            scope: Scope::Model,
+            graph_end: 0,
+            initializer_end: 0,
+            raw_data_end: 0,
+            seen_graph: false,
+            model_fields: Vec::new(),
+            graph_fields: Vec::new(),
+            initializer_fields: Vec::new(),
+            leaf: None,
+            nodes: Vec::new(),
+            initializers: Vec::new(),
+            failed: false,
+        })
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), VerificationError> {
+        if self.failed {
+            return Err(VerificationError::InvalidModel);
+        }
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.extend_from_slice(chunk);
+        let result = self.consume(&pending);
+        self.failed = result.is_err();
+        self.pending = pending.split_off(result.unwrap_or(0));
+        result.map(|_| ())
+    }
+
+    /// Consume as much of `buf` as possible, returning how much was consumed
+    fn consume(&mut self, buf: &[u8]) -> Result<usize, VerificationError> {
+        let mut pos = 0;
+        loop {
+            let rest = &buf[pos..];
+            let used = match self.scope {
+                Scope::RawData => {
+                    let n = rest.len().min(usize::try_from(self.raw_data_end - self.offset).unwrap_or(usize::MAX));
+                    self.leaf.as_mut().expect("raw_data started").update(&rest[..n]);
+                    if self.offset + n as u64 == self.raw_data_end {
+                        self.scope = Scope::Initializer;
+                    } else if n == 0 {
+                        return Ok(pos);
+                    }
+                    n
+                }
+                Scope::Initializer if self.offset == self.initializer_end => {
+                    self.finish_initializer()?;
+                    self.scope = Scope::Graph;
+                    0
+                }
+                Scope::Graph if self.offset == self.graph_end => {
+                    self.scope = Scope::Model;
+                    0
+                }
+                scope => {
+                    let Some(field) = field(rest)? else {
+                        return Ok(pos);
+                    };
+                    let end = self.offset + field.header as u64 + field.body;
+                    let limit = match scope {
+                        Scope::Graph => self.graph_end,
+                        Scope::Initializer => self.initializer_end,
+                        _ => u64::MAX,
+                    };
+                    if end > limit {
+                        return Err(VerificationError::InvalidModel);
+                    }
+                    match (scope, field.number, field.wire) {
+                        (Scope::Model, MODEL_GRAPH, LENGTH_DELIMITED) if !self.seen_graph => {
+                            (self.seen_graph, self.graph_end, self.scope) = (true, end, Scope::Graph);
+                            field.header
+                        }
+                        (Scope::Graph, GRAPH_INITIALIZER, LENGTH_DELIMITED) => {
+                            (self.initializer_end, self.scope) = (end, Scope::Initializer);
+                            self.initializer_fields.clear();
+                            field.header
+                        }
+                        (Scope::Initializer, TENSOR_RAW_DATA, LENGTH_DELIMITED) => {
+                            self.start_raw_data(field.body)?;
+                            (self.raw_data_end, self.scope) = (end, Scope::RawData);
+                            field.header
+                        }
+                        (Scope::Model, MODEL_GRAPH, _) => return Err(VerificationError::InvalidModel),
+                        (scope, number, wire) => {
+                            let total = field.header + usize::try_from(field.body).map_err(invalid)?;
+                            let Some(bytes) = rest.get(..total) else {
+                                return Ok(pos);
+                            };
+                            match scope {
+                                Scope::Graph if number == GRAPH_NODE && wire == LENGTH_DELIMITED => {
+                                    self.add_node(&bytes[field.header..])?
+                                }
+                                Scope::Graph => self.graph_fields.extend_from_slice(bytes),
+                                Scope::Initializer => self.initializer_fields.extend_from_slice(bytes),
+                                _ => self.model_fields.extend_from_slice(bytes),
+                            }
+                            total
+                        }
+                    }
+                }
+            };
+            pos += used;
+            self.offset += used as u64;
+        }
+    }
+
+    fn add_node(&mut self, body: &[u8]) -> Result<(), VerificationError> {
+        let mut node = NodeProto::decode(body).map_err(invalid)?;
+        strip_node(&mut node);
+        let label = format!("node/{}", self.nodes.len());
+        self.nodes.push(leaf_hash(&leaf_data(&label, &node.encode_to_vec()), self.algorithm)?);
+        Ok(())
+    }
+
+    /// Hash the initializer's leaf up to its `raw_data` bytes
+    fn start_raw_data(&mut self, len: u64) -> Result<(), VerificationError> {
+        if self.leaf.is_some() {
+            return Err(VerificationError::InvalidModel);
+        }
+        let tensor = TensorProto::decode(self.initializer_fields.as_slice()).map_err(invalid)?;
+        let mut leaf = StreamingHasher::with_algorithm(self.algorithm)?;
+        leaf.update(&[LEAF_PREFIX]);
+        leaf.update(&leaf_data(&format!("initializer/{}", tensor.name), &before_raw_data(&tensor).encode_to_vec()));
+        if len > 0 {
+            let mut key = Vec::new();
+            encode_key(TENSOR_RAW_DATA as u32, WireType::LengthDelimited, &mut key);
+            encode_varint(len, &mut key);
+            leaf.update(&key);
+        }
+        self.leaf = Some(leaf);
+        self.initializer_fields = after_raw_data(&tensor).encode_to_vec();
+        Ok(())
+    }
+
+    fn finish_initializer(&mut self) -> Result<(), VerificationError> {
+        let mut tensor = TensorProto::decode(self.initializer_fields.as_slice()).map_err(invalid)?;
+        strip_tensor(&mut tensor);
+        let hash = match self.leaf.take() {
+            None => {
+                let label = format!("initializer/{}", tensor.name);
+                leaf_hash(&leaf_data(&label, &tensor.encode_to_vec()), self.algorithm)?
+            }
+            Some(mut leaf) => {
+                // Fields that sort before raw_data must have come before it
+                if before_raw_data(&tensor) != TensorProto::default() {
+                    return Err(VerificationError::InvalidModel);
+                }
+                leaf.update(&tensor.encode_to_vec());
+                hex::decode(&leaf.finalize()).ok_or(VerificationError::InvalidModel)?
+            }
+        };
+        self.initializers.push(hash);
+        Ok(())
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Total bytes absorbed so far
+    pub fn bytes_processed(&self) -> u64 {
+        self.offset + self.pending.len() as u64
+    }
+
+    /// Merkle root, once the whole model has been fed
+    pub fn finish(self) -> Result<String, VerificationError> {
+        if self.failed || self.scope != Scope::Model || !self.pending.is_empty() {
+            return Err(VerificationError::InvalidModel);
+        }
+        let mut header = ModelProto::decode(self.model_fields.as_slice()).map_err(invalid)?;
+        let graph = GraphProto::decode(self.graph_fields.as_slice()).map_err(invalid)?;
+        header.graph = Some(GraphProto { sparse_initializer: Vec::new(), ..graph });
+
+        let mut hashes = vec![leaf_hash(&leaf_data(HEADER_LAYER, &canonical_model(header)), self.algorithm)?];
+        hashes.extend(self.nodes);
+        hashes.extend(self.initializers);
+        Ok(format!("0x{}", hex::encode(&tree_root(&hashes, self.algorithm)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onnx::merkle::merkle_root;
+    use crate::onnx::proto::StringStringEntryProto;
+    use crate::onnx::tests::sample_model;
+
+    fn stream(bytes: &[u8], chunk: usize) -> Result<String, VerificationError> {
+        let mut hasher = ModelHasher::create(HashAlgorithm::Sha3_256)?;
+        bytes.chunks(chunk).try_for_each(|c| hasher.feed(c))?;
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_streamed_merkle_root() {
+        let mut model = sample_model();
+        model.doc_string = "exported".into();
+        let graph = model.graph.as_mut().unwrap();
+        let mut raw = graph.initializer[0].clone();
+        raw.name = "W2".into();
+        raw.float_data.clear();
+        raw.raw_data = [1.0f32, 2.0, 3.0, 4.0].iter().flat_map(|v| v.to_le_bytes()).collect();
+        raw.metadata_props.push(StringStringEntryProto { key: "k".into(), value: "v".into() });
+        raw.data_location = 0;
+        raw.uint64_data = vec![7];
+        graph.initializer.push(raw);
+        let bytes = model.encode_to_vec();
+
+        let expected = merkle_root(&bytes, HashAlgorithm::Sha3_256).unwrap();
+        for chunk in [1, 7, bytes.len()] {
+            assert_eq!(stream(&bytes, chunk).unwrap(), expected, "chunk size {chunk}");
+        }
+        assert_eq!(stream(&bytes[..bytes.len() - 3], 5).unwrap_err(), VerificationError::InvalidModel);
+        assert_eq!(stream(b"\xff\xff\xff", 1).unwrap_err(), VerificationError::InvalidModel);
+    }
+}
@@ -0,0 +1,220 @@
+// Verifier policy
+// Allowed models, freshness, trusted signers, hash algorithms and required checks as one versionable document
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::hash::{self, HashAlgorithm};
+use crate::registry::{ModelEntry, ModelRegistry};
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::verifier::{Verifier, POLICY_CHECKS};
+
+/// Policy for a `Verifier`, kept in config rather than code
+///
+/// ```json
+/// {
+///   "models": [{ "model_hash": "0x…", "label": "sentiment", "version": "1.2.0" }],
+///   "max_age_ms": 600000,
+///   "require_signature": true,
+///   "trusted_signers": ["0x…"],
+///   "hash_algorithms": ["sha3-256", "sha-256"],
+///   "required_checks": ["signature", "input_spec"]
+/// }
+/// ```
+///
+/// `VerifierConfig` fields sit at the top level. Omitted fields keep their
+/// defaults; unknown ones are rejected, so a typo can't loosen the policy.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct VerifierPolicy {
+    #[serde(flatten)]
+    config: VerifierConfig,
+    /// Approved models (empty: the verifier's own model hash)
+    models: Vec<ModelEntry>,
+    trusted_signers: Vec<String>,
+    trusted_attestors: Vec<String>,
+    /// Commitment hashes proofs may use (empty: any this build supports)
+    hash_algorithms: Vec<HashAlgorithm>,
+    /// Checks that must run, and pass, for a proof to verify
+    required_checks: Vec<Check>,
+}
+
+/// Whether every `verify*` method runs `check` when it applies; the others
+/// (proof binding, composite, inclusion, ...) depend on the method called
+pub fn requirable(check: Check) -> bool {
+    matches!(check, Check::ModelBinding | Check::InputHash | Check::OutputHash | Check::InputSpec | Check::OutputSpec)
+        || POLICY_CHECKS.contains(&check)
+}
+
+impl VerifierPolicy {
+    /// Default policy: `VerifierConfig` defaults, no models, keys or restrictions
+    pub fn new() -> VerifierPolicy {
+        VerifierPolicy::default()
+    }
+
+    /// Parse a policy document, failing with a reason naming the offending field
+    pub fn try_from_json(json: &str) -> Result<VerifierPolicy, String> {
+        let value = serde_json::from_str(json).map_err(|e| format!("Invalid policy JSON: {}", e))?;
+        VerifierPolicy::try_from_value(value)
+    }
+
+    /// Read a policy from an already-parsed document (e.g. TOML converted to JSON values)
+    pub fn try_from_value(value: Value) -> Result<VerifierPolicy, String> {
+        let known = serde_json::to_value(VerifierPolicy::default()).unwrap_or_default();
+        if let (Some(fields), Some(known)) = (value.as_object(), known.as_object()) {
+            if let Some(unknown) = fields.keys().find(|field| !known.contains_key(*field)) {
+                return Err(format!("Unknown policy field '{}'", unknown));
+            }
+        }
+        let policy: VerifierPolicy = serde_json::from_value(value).map_err(|e| format!("Invalid policy: {}", e))?;
+        if let Some(check) = policy.required_checks.iter().find(|check| !requirable(**check)) {
+            let name = serde_json::to_value(check).unwrap_or_default();
+            return Err(format!("required_checks: {} depends on the verify method and can't be required", name));
+        }
+        Ok(policy)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn config(&self) -> VerifierConfig {
+        self.config
+    }
+
+    pub fn models(&self) -> &[ModelEntry] {
+        &self.models
+    }
+
+    pub fn trusted_signers(&self) -> &[String] {
+        &self.trusted_signers
+    }
+
+    pub fn trusted_attestors(&self) -> &[String] {
+        &self.trusted_attestors
+    }
+
+    pub fn hash_algorithms(&self) -> &[HashAlgorithm] {
+        &self.hash_algorithms
+    }
+
+    pub fn required_checks(&self) -> &[Check] {
+        &self.required_checks
+    }
+}
+
+impl Verifier {
+    /// Verifier accepting proofs for the models `policy` lists
+    ///
+    /// Fails with `InvalidArgument` if it lists none: create the verifier
+    /// for its model instead, then call `set_policy`.
+    pub fn try_from_policy(policy: &VerifierPolicy) -> Result<Verifier, VerificationError> {
+        if policy.models.is_empty() {
+            return Err(VerificationError::InvalidArgument);
+        }
+        let mut verifier = Verifier::new(String::new());
+        verifier.set_policy(policy);
+        Ok(verifier)
+    }
+
+    /// Apply `policy`: its config replaces the current one, its trusted keys are
+    /// added, and its models (if any) replace the expected model hash
+    pub fn set_policy(&mut self, policy: &VerifierPolicy) {
+        self.config = policy.config;
+        if !policy.models.is_empty() {
+            self.registry = Some(ModelRegistry::from_entries(policy.models.clone()));
+        }
+        #[cfg(feature = "signatures")]
+        {
+            self.trusted_signers.extend(policy.trusted_signers.iter().cloned());
+            self.trusted_attestors.extend(policy.trusted_attestors.iter().cloned());
+        }
+        self.hash_algorithms = policy.hash_algorithms.clone();
+        self.required_checks = policy.required_checks.clone();
+    }
+
+    /// `alg`, unless the policy restricts commitments to other hash algorithms
+    pub fn allowed_hash(&self, alg: HashAlgorithm) -> Result<HashAlgorithm, String> {
+        match self.hash_algorithms.is_empty() || self.hash_algorithms.contains(&alg) {
+            true => Ok(alg),
+            false => Err(format!("Hash algorithm {} is not allowed by policy", alg.name())),
+        }
+    }
+
+    /// `allowed_hash` for the tag of a tagged commitment (`sha256:0x…`), which overrides `hash_alg`
+    pub fn allowed_tag(&self, commitment: &str) -> Result<(), String> {
+        match hash::split_commitment(commitment) {
+            Ok((Some(alg), _)) => self.allowed_hash(alg).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail the required checks that didn't apply to this proof
+    ///
+    /// Runs before the nonce check, so a required `nonce` fails here only if
+    /// the config doesn't turn challenge-response mode on.
+    pub fn check_required(&self, report: &mut VerificationReport) {
+        for &check in &self.required_checks {
+            let pending = check == Check::Nonce && self.config.require_nonce();
+            if !pending && !report.ran(check) {
+                report.record(check, false, "Required by policy but not applicable to this proof", Stopwatch::start());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hash::{hash_bytes, hash_bytes_tagged};
+    use crate::ProofData;
+
+    fn proof(model_hash: &str, input_hash: String) -> ProofData {
+        let mut proof = fixtures::proof(model_hash);
+        proof.input_hash = input_hash;
+        proof
+    }
+
+    #[test]
+    fn test_policy() {
+        let policy = VerifierPolicy::try_from_json(
+            r#"{
+                "models": [{ "model_hash": "0x1111", "label": "sentiment", "version": "1.2.0" }],
+                "max_age_ms": 600000,
+                "hash_algorithms": ["sha3-256"],
+                "required_checks": ["input_spec"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(policy.config().max_age_ms(), 600_000);
+        assert!(policy.config().check_freshness());
+        assert_eq!(VerifierPolicy::try_from_json(&policy.to_json()), Ok(policy.clone()));
+
+        let verifier = Verifier::try_from_policy(&policy).unwrap();
+        let input = hash_bytes(b"in", HashAlgorithm::default()).unwrap();
+        let report = verifier.verify_report(&proof("0x1111", input.clone()), b"in", b"out");
+        assert_eq!(report.failure_reasons(), ["Required by policy but not applicable to this proof"]);
+        assert_eq!(report.error(), Some(VerificationError::ShapeMismatch));
+        let unlisted = verifier.verify_report(&proof("0x2222", input), b"in", b"out");
+        assert_eq!(unlisted.error(), Some(VerificationError::ModelMismatch));
+
+        let sha256 = hash_bytes_tagged(b"in", HashAlgorithm::Sha256).unwrap();
+        let report = verifier.verify_report(&proof("0x1111", sha256), b"in", b"out");
+        assert_eq!(report.error(), Some(VerificationError::UnsupportedHashAlgorithm));
+        assert_eq!(report.failure_reasons()[0], "Hash algorithm sha-256 is not allowed by policy");
+    }
+
+    #[test]
+    fn test_policy_errors() {
+        let error = |json: &str| VerifierPolicy::try_from_json(json).unwrap_err();
+        assert_eq!(error(r#"{"max_age": 1}"#), "Unknown policy field 'max_age'");
+        let composite = error(r#"{"required_checks": ["composite"]}"#);
+        assert_eq!(composite, r#"required_checks: "composite" depends on the verify method and can't be required"#);
+        assert!(error(r#"{"hash_algorithms": ["md5"]}"#).starts_with("Invalid policy"));
+        let unbound = Verifier::try_from_policy(&VerifierPolicy::new());
+        assert_eq!(unbound.err(), Some(VerificationError::InvalidArgument));
+    }
+}
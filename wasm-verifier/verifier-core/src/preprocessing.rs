@@ -0,0 +1,134 @@
+// Preprocessing pipeline commitment
+// Binds a proof to the resize, normalization and tokenizer settings its inputs went through
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::Verifier;
+
+/// Domain separator for preprocessing config hashes (format version 1)
+const PREPROCESSING_DOMAIN: &[u8] = b"zkml/preprocessing/v1\0";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Resize {
+    width: u32,
+    height: u32,
+    interpolation: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Normalization {
+    scale: f32,
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Tokenizer {
+    name: String,
+    vocab_hash: String,
+    max_length: u32,
+    lowercase: bool,
+}
+
+/// Preprocessing applied to raw data before it became the model input
+///
+/// Prover and verifier build the same config and compare hashes:
+///
+/// ```ignore
+/// let mut pre = PreprocessingConfig::new();
+/// pre.set_resize(224, 224, "bilinear".into());
+/// pre.set_normalization(1.0 / 255.0, vec![0.485, 0.456, 0.406], vec![0.229, 0.224, 0.225]);
+/// proof.preprocessing_hash = Some(pre.hash(HashAlgorithm::default())?);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct PreprocessingConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resize: Option<Resize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    normalization: Option<Normalization>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tokenizer: Option<Tokenizer>,
+}
+
+impl PreprocessingConfig {
+    pub fn new() -> PreprocessingConfig {
+        PreprocessingConfig::default()
+    }
+
+    /// Images were resized to `width` x `height` with `interpolation` (e.g. `"bilinear"`)
+    pub fn set_resize(&mut self, width: u32, height: u32, interpolation: String) {
+        self.resize = Some(Resize { width, height, interpolation });
+    }
+
+    /// Values were multiplied by `scale`, then normalized per channel as `(x - mean) / std`
+    pub fn set_normalization(&mut self, scale: f32, mean: Vec<f32>, std: Vec<f32>) {
+        self.normalization = Some(Normalization { scale, mean, std });
+    }
+
+    /// Text was tokenized by `name` with the vocabulary hashing to `vocab_hash`
+    pub fn set_tokenizer(&mut self, name: String, vocab_hash: String, max_length: u32, lowercase: bool) {
+        self.tokenizer = Some(Tokenizer { name, vocab_hash, max_length, lowercase });
+    }
+
+    /// Commitment to this config, for `ProofData.preprocessing_hash`
+    ///
+    /// Hashes a fixed little-endian encoding of the set stages, so provers
+    /// in any language get the same value.
+    pub fn hash(&self, algorithm: HashAlgorithm) -> Result<String, VerificationError> {
+        hash_bytes(&self.encode(), algorithm)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    pub fn from_json(json: &str) -> Result<PreprocessingConfig, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn put_f32s(out: &mut Vec<u8>, values: &[f32]) {
+    out.extend_from_slice(&(values.len() as u32).to_le_bytes());
+    values.iter().for_each(|v| out.extend_from_slice(&v.to_bits().to_le_bytes()));
+}
+
+impl PreprocessingConfig {
+    /// Domain tag, then each stage in fixed order behind a presence byte
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = PREPROCESSING_DOMAIN.to_vec();
+        out.push(self.resize.is_some() as u8);
+        if let Some(resize) = &self.resize {
+            out.extend_from_slice(&resize.width.to_le_bytes());
+            out.extend_from_slice(&resize.height.to_le_bytes());
+            put_str(&mut out, &resize.interpolation);
+        }
+        out.push(self.normalization.is_some() as u8);
+        if let Some(normalization) = &self.normalization {
+            out.extend_from_slice(&normalization.scale.to_bits().to_le_bytes());
+            put_f32s(&mut out, &normalization.mean);
+            put_f32s(&mut out, &normalization.std);
+        }
+        out.push(self.tokenizer.is_some() as u8);
+        if let Some(tokenizer) = &self.tokenizer {
+            put_str(&mut out, &tokenizer.name);
+            put_str(&mut out, &tokenizer.vocab_hash.to_ascii_lowercase());
+            out.extend_from_slice(&tokenizer.max_length.to_le_bytes());
+            out.push(tokenizer.lowercase as u8);
+        }
+        out
+    }
+}
+
+impl Verifier {
+    /// Require proofs to commit to this preprocessing pipeline via `preprocessing_hash`
+    pub fn set_preprocessing(&mut self, preprocessing: &PreprocessingConfig) {
+        self.preprocessing = Some(preprocessing.clone());
+    }
+}
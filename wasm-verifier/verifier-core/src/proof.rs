@@ -0,0 +1,209 @@
+// Proof data
+// The claim a JOLT-Atlas prover publishes, and its canonical forms for signing and hashing
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::hash::{hash_bytes_tagged, HashAlgorithm};
+use crate::jcs;
+use crate::tensor::TensorSpec;
+
+/// A proof claim: which model ran on which input to give which output, plus
+/// optional signatures, attestations and policy commitments
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProofData {
+    pub model_hash: String,
+    pub proof_hash: String,
+    /// Single-buffer commitments; may be empty for proofs using named `inputs`/`outputs`
+    #[serde(default)]
+    pub input_hash: String,
+    #[serde(default)]
+    pub output_hash: String,
+    pub timestamp: u64,
+    pub verified: bool,
+    /// Name of the commitment hash (see `HashAlgorithm::from_name`); kept as a
+    /// string so unknown algorithms surface as a verification error, not a parse error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash_alg: Option<String>,
+    /// Challenge issued by the verifier (see `NonceRegistry::issue`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    /// Signature scheme: `"ed25519"` (default) or `"secp256k1"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_alg: Option<String>,
+    /// Signature (hex) over `signing_bytes()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Ed25519 public key or Ethereum address (hex) of the signer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer_pubkey: Option<String>,
+    /// Id of the signing key in the verifier's `KeyResolver`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// `did:key` or `did:web` identifier of the prover
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prover_did: Option<String>,
+    /// BLS12-381 public keys (hex) of the nodes that co-signed the proof
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attestor_pubkeys: Vec<String>,
+    /// Aggregate BLS signature (hex) of all attestors over `signing_bytes()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregate_signature: Option<String>,
+    /// Named input commitments, for models with several inputs (see `verify_multi`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inputs: BTreeMap<String, String>,
+    /// Named output commitments
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub outputs: BTreeMap<String, String>,
+    /// Declared shape/dtype of the input, checked against the buffer before hashing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_spec: Option<TensorSpec>,
+    /// Declared shape/dtype of the output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_spec: Option<TensorSpec>,
+    /// Semantic version of the model, e.g. `"2.1.0"` (checked against the registry and version policy)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_version: Option<String>,
+    /// Commitment to a subset of the model's layers (`onnx_partial_hash`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial_model_hash: Option<String>,
+    /// Layer labels covered by `partial_model_hash`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partial_layers: Vec<String>,
+    /// Commitment to the quantization scheme (`QuantizationConfig::hash`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization_hash: Option<String>,
+    /// Commitment to the input preprocessing (`PreprocessingConfig::hash`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preprocessing_hash: Option<String>,
+    /// Leaves of a structured model commitment (`ModelCommitment`), whose root is `model_hash`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub architecture_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_hash: Option<String>,
+    /// X.509 certificates (PEM or base64 DER), leaf first, certifying `signer_pubkey`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cert_chain: Vec<String>,
+}
+
+impl ProofData {
+    pub fn new(
+        model_hash: String,
+        proof_hash: String,
+        input_hash: String,
+        output_hash: String,
+        timestamp: u64,
+        verified: bool,
+    ) -> ProofData {
+        ProofData {
+            model_hash,
+            proof_hash,
+            input_hash,
+            output_hash,
+            timestamp,
+            verified,
+            hash_alg: None,
+            nonce: None,
+            signature_alg: None,
+            signature: None,
+            signer_pubkey: None,
+            kid: None,
+            prover_did: None,
+            attestor_pubkeys: Vec::new(),
+            aggregate_signature: None,
+            inputs: BTreeMap::new(),
+            outputs: BTreeMap::new(),
+            input_spec: None,
+            output_spec: None,
+            model_version: None,
+            partial_model_hash: None,
+            partial_layers: Vec::new(),
+            quantization_hash: None,
+            preprocessing_hash: None,
+            architecture_hash: None,
+            weights_hash: None,
+            metadata_hash: None,
+            cert_chain: Vec::new(),
+        }
+    }
+
+    /// Declare the commitment hash algorithm (`None` = verifier default)
+    pub fn set_hash_algorithm(&mut self, algorithm: Option<HashAlgorithm>) {
+        self.hash_alg = algorithm.map(|alg| alg.name().to_string());
+    }
+
+    /// Canonical bytes the prover (and every attestor) signs
+    ///
+    /// RFC 8785 (JCS) canonical JSON of every field except `signature`,
+    /// `signer_pubkey`, `aggregate_signature` and `cert_chain`, so
+    /// re-serializing the proof doesn't change what was signed.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("signature");
+            fields.remove("signer_pubkey");
+            fields.remove("aggregate_signature");
+            fields.remove("cert_chain");
+        }
+        jcs::canonicalize(&value).into_bytes()
+    }
+
+    /// The whole proof as RFC 8785 canonical JSON
+    pub fn canonical_json(&self) -> String {
+        jcs::canonicalize(&serde_json::to_value(self).unwrap_or_default())
+    }
+
+    /// Content hash identifying the proof, e.g. as a storage key
+    ///
+    /// Tagged SHA3-256 over the RFC 8785 canonical JSON of every field,
+    /// signatures included, so it doesn't depend on key order or whitespace
+    /// of the JSON the proof was parsed from.
+    pub fn proof_id(&self) -> String {
+        hash_bytes_tagged(self.canonical_json().as_bytes(), HashAlgorithm::Sha3_256).unwrap_or_default()
+    }
+
+    /// Serialize proof to JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parse a proof from JSON; the error is the reason for a `MalformedProof` failure
+    pub fn from_json(proof_json: &str) -> Result<ProofData, String> {
+        serde_json::from_str(proof_json).map_err(|e| format!("Failed to parse proof JSON: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_id_ignores_formatting() {
+        let mut proof = ProofData::new(
+            "0xmodel".to_string(),
+            "0xproof".to_string(),
+            "0xin".to_string(),
+            "0xout".to_string(),
+            1_700_000_000_000,
+            true,
+        );
+        proof.nonce = Some("0xnonce".to_string());
+        assert!(proof.proof_id().starts_with("sha3:0x"));
+
+        // Key order doesn't matter, content does
+        let mut value: serde_json::Value = serde_json::from_str(&proof.to_json()).unwrap();
+        let fields: Vec<String> = value.as_object().unwrap().iter().rev().map(|(k, v)| format!("{:?}: {}", k, v)).collect();
+        let reordered = format!("{{{}}}", fields.join(", "));
+        assert_eq!(ProofData::from_json(&reordered).unwrap().proof_id(), proof.proof_id());
+        value["nonce"] = "0xother".into();
+        assert_ne!(ProofData::from_json(&value.to_string()).unwrap().proof_id(), proof.proof_id());
+
+        // Signatures are left out of what is signed
+        let signing_bytes = proof.signing_bytes();
+        proof.signature = Some("0xsig".to_string());
+        assert_eq!(proof.signing_bytes(), signing_bytes);
+        assert!(ProofData::from_json("{}").unwrap_err().starts_with("Failed to parse proof JSON: "));
+    }
+}
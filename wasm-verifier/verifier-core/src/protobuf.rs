@@ -0,0 +1,205 @@
+// Protobuf proof format
+// Decodes `zkml.verifier.v1.Proof` messages (proto/proof.proto) into ProofData
+
+use prost::Message;
+use std::collections::BTreeMap;
+
+use crate::error::VerificationError;
+use crate::tensor::{DType, TensorSpec};
+use crate::ProofData;
+
+/// `zkml.verifier.v1.Proof`, kept in sync with proto/proof.proto by hand
+#[derive(Clone, PartialEq, Message)]
+struct ProofMessage {
+    #[prost(string, tag = "1")]
+    model_hash: String,
+    #[prost(string, tag = "2")]
+    proof_hash: String,
+    #[prost(string, tag = "3")]
+    input_hash: String,
+    #[prost(string, tag = "4")]
+    output_hash: String,
+    #[prost(uint64, tag = "5")]
+    timestamp: u64,
+    #[prost(bool, tag = "6")]
+    verified: bool,
+    #[prost(string, optional, tag = "7")]
+    hash_alg: Option<String>,
+    #[prost(string, optional, tag = "8")]
+    nonce: Option<String>,
+    #[prost(string, optional, tag = "9")]
+    signature_alg: Option<String>,
+    #[prost(string, optional, tag = "10")]
+    signature: Option<String>,
+    #[prost(string, optional, tag = "11")]
+    signer_pubkey: Option<String>,
+    #[prost(string, optional, tag = "12")]
+    kid: Option<String>,
+    #[prost(string, optional, tag = "13")]
+    prover_did: Option<String>,
+    #[prost(string, repeated, tag = "14")]
+    cert_chain: Vec<String>,
+    #[prost(string, repeated, tag = "15")]
+    attestor_pubkeys: Vec<String>,
+    #[prost(string, optional, tag = "16")]
+    aggregate_signature: Option<String>,
+    #[prost(string, optional, tag = "17")]
+    quantization_hash: Option<String>,
+    #[prost(message, optional, tag = "18")]
+    input_spec: Option<TensorSpecMessage>,
+    #[prost(message, optional, tag = "19")]
+    output_spec: Option<TensorSpecMessage>,
+    #[prost(btree_map = "string, string", tag = "20")]
+    inputs: BTreeMap<String, String>,
+    #[prost(btree_map = "string, string", tag = "21")]
+    outputs: BTreeMap<String, String>,
+    #[prost(string, optional, tag = "22")]
+    preprocessing_hash: Option<String>,
+    #[prost(string, optional, tag = "23")]
+    partial_model_hash: Option<String>,
+    #[prost(string, repeated, tag = "24")]
+    partial_layers: Vec<String>,
+    #[prost(string, optional, tag = "25")]
+    model_version: Option<String>,
+    #[prost(string, optional, tag = "26")]
+    architecture_hash: Option<String>,
+    #[prost(string, optional, tag = "27")]
+    weights_hash: Option<String>,
+    #[prost(string, optional, tag = "28")]
+    metadata_hash: Option<String>,
+}
+
+/// `zkml.verifier.v1.TensorSpec`
+#[derive(Clone, PartialEq, Message)]
+struct TensorSpecMessage {
+    #[prost(uint32, repeated, tag = "1")]
+    shape: Vec<u32>,
+    #[prost(string, tag = "2")]
+    dtype: String,
+    #[prost(string, optional, tag = "3")]
+    layout: Option<String>,
+}
+
+impl TryFrom<TensorSpecMessage> for TensorSpec {
+    type Error = VerificationError;
+
+    fn try_from(m: TensorSpecMessage) -> Result<TensorSpec, VerificationError> {
+        let dtype = DType::from_name(&m.dtype).ok_or(VerificationError::MalformedProof)?;
+        Ok(TensorSpec::new(m.shape, dtype, m.layout))
+    }
+}
+
+impl From<&TensorSpec> for TensorSpecMessage {
+    fn from(spec: &TensorSpec) -> TensorSpecMessage {
+        let layout = spec.layout().map(str::to_string);
+        TensorSpecMessage { shape: spec.shape().to_vec(), dtype: spec.dtype().name().to_string(), layout }
+    }
+}
+
+impl TryFrom<ProofMessage> for ProofData {
+    type Error = VerificationError;
+
+    fn try_from(m: ProofMessage) -> Result<ProofData, VerificationError> {
+        Ok(ProofData {
+            model_hash: m.model_hash,
+            proof_hash: m.proof_hash,
+            input_hash: m.input_hash,
+            output_hash: m.output_hash,
+            timestamp: m.timestamp,
+            verified: m.verified,
+            hash_alg: m.hash_alg,
+            nonce: m.nonce,
+            signature_alg: m.signature_alg,
+            signature: m.signature,
+            signer_pubkey: m.signer_pubkey,
+            kid: m.kid,
+            prover_did: m.prover_did,
+            cert_chain: m.cert_chain,
+            attestor_pubkeys: m.attestor_pubkeys,
+            aggregate_signature: m.aggregate_signature,
+            inputs: m.inputs,
+            outputs: m.outputs,
+            input_spec: m.input_spec.map(TensorSpec::try_from).transpose()?,
+            output_spec: m.output_spec.map(TensorSpec::try_from).transpose()?,
+            quantization_hash: m.quantization_hash,
+            preprocessing_hash: m.preprocessing_hash,
+            model_version: m.model_version,
+            architecture_hash: m.architecture_hash,
+            weights_hash: m.weights_hash,
+            metadata_hash: m.metadata_hash,
+            partial_model_hash: m.partial_model_hash,
+            partial_layers: m.partial_layers,
+        })
+    }
+}
+
+impl From<&ProofData> for ProofMessage {
+    fn from(p: &ProofData) -> ProofMessage {
+        ProofMessage {
+            model_hash: p.model_hash.clone(),
+            proof_hash: p.proof_hash.clone(),
+            input_hash: p.input_hash.clone(),
+            output_hash: p.output_hash.clone(),
+            timestamp: p.timestamp,
+            verified: p.verified,
+            hash_alg: p.hash_alg.clone(),
+            nonce: p.nonce.clone(),
+            signature_alg: p.signature_alg.clone(),
+            signature: p.signature.clone(),
+            signer_pubkey: p.signer_pubkey.clone(),
+            kid: p.kid.clone(),
+            prover_did: p.prover_did.clone(),
+            cert_chain: p.cert_chain.clone(),
+            attestor_pubkeys: p.attestor_pubkeys.clone(),
+            aggregate_signature: p.aggregate_signature.clone(),
+            quantization_hash: p.quantization_hash.clone(),
+            preprocessing_hash: p.preprocessing_hash.clone(),
+            model_version: p.model_version.clone(),
+            architecture_hash: p.architecture_hash.clone(),
+            weights_hash: p.weights_hash.clone(),
+            metadata_hash: p.metadata_hash.clone(),
+            partial_model_hash: p.partial_model_hash.clone(),
+            partial_layers: p.partial_layers.clone(),
+            inputs: p.inputs.clone(),
+            outputs: p.outputs.clone(),
+            input_spec: p.input_spec.as_ref().map(TensorSpecMessage::from),
+            output_spec: p.output_spec.as_ref().map(TensorSpecMessage::from),
+        }
+    }
+}
+
+impl ProofData {
+    /// Deserialize from a `zkml.verifier.v1.Proof` protobuf message
+    pub fn decode_protobuf(bytes: &[u8]) -> Result<ProofData, VerificationError> {
+        ProofMessage::decode(bytes).map_err(|_| VerificationError::MalformedProof)?.try_into()
+    }
+
+    /// Serialize to a `zkml.verifier.v1.Proof` protobuf message
+    pub fn encode_protobuf(&self) -> Vec<u8> {
+        ProofMessage::from(self).encode_to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::{hash_bytes, HashAlgorithm};
+
+    #[test]
+    fn test_protobuf_roundtrip() {
+        let mut proof = ProofData::new(
+            "0x1234567890abcdef".to_string(),
+            "0xproof".to_string(),
+            hash_bytes(b"in", HashAlgorithm::default()).unwrap(),
+            hash_bytes(b"out", HashAlgorithm::default()).unwrap(),
+            1_700_000_000_000,
+            true,
+        );
+        proof.kid = Some("prover-1".to_string());
+        proof.output_spec = Some(TensorSpec::new(vec![1, 10], DType::Float32, None));
+
+        let decoded = ProofData::decode_protobuf(&proof.encode_protobuf()).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), serde_json::to_string(&proof).unwrap());
+        assert_eq!(ProofData::decode_protobuf(&[0x0a, 0xff]).unwrap_err(), VerificationError::MalformedProof);
+    }
+}
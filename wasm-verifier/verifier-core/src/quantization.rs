@@ -0,0 +1,71 @@
+// Quantization parameter binding
+// Commits to the quantization scheme JOLT-Atlas ran the model with
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::hash::{hash_bytes, HashAlgorithm};
+use crate::Verifier;
+
+/// Domain separator for quantization config hashes (format version 1)
+const QUANTIZATION_DOMAIN: &[u8] = b"zkml/quantization/v1\0";
+
+/// Quantization scheme of a model: per-tensor or per-channel scales and zero-points
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct QuantizationConfig {
+    scheme: String,
+    bit_width: u32,
+    scales: Vec<f32>,
+    zero_points: Vec<i32>,
+}
+
+impl QuantizationConfig {
+    pub fn new(scheme: String, bit_width: u32, scales: Vec<f32>, zero_points: Vec<i32>) -> QuantizationConfig {
+        QuantizationConfig { scheme, bit_width, scales, zero_points }
+    }
+
+    /// Scheme name, e.g. `"per-tensor-affine"` or `"per-channel-symmetric"`
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    pub fn bit_width(&self) -> u32 {
+        self.bit_width
+    }
+
+    pub fn scales(&self) -> &[f32] {
+        &self.scales
+    }
+
+    pub fn zero_points(&self) -> &[i32] {
+        &self.zero_points
+    }
+
+    /// Commitment to this config, for `ProofData.quantization_hash`
+    ///
+    /// Hashes a fixed little-endian encoding (domain tag, scheme, bit width,
+    /// scales as IEEE-754 f32, zero-points as i32), so provers in any
+    /// language get the same value.
+    pub fn hash(&self, algorithm: HashAlgorithm) -> Result<String, VerificationError> {
+        hash_bytes(&self.encode(), algorithm)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = QUANTIZATION_DOMAIN.to_vec();
+        out.extend_from_slice(&(self.scheme.len() as u32).to_le_bytes());
+        out.extend_from_slice(self.scheme.as_bytes());
+        out.extend_from_slice(&self.bit_width.to_le_bytes());
+        out.extend_from_slice(&(self.scales.len() as u32).to_le_bytes());
+        self.scales.iter().for_each(|s| out.extend_from_slice(&s.to_bits().to_le_bytes()));
+        out.extend_from_slice(&(self.zero_points.len() as u32).to_le_bytes());
+        self.zero_points.iter().for_each(|z| out.extend_from_slice(&z.to_le_bytes()));
+        out
+    }
+}
+
+impl Verifier {
+    /// Require proofs to commit to this quantization config via `quantization_hash`
+    pub fn set_quantization(&mut self, quantization: &QuantizationConfig) {
+        self.quantization = Some(quantization.clone());
+    }
+}
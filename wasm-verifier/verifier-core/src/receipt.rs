@@ -0,0 +1,184 @@
+// Verification receipts
+// Compact EdDSA JWS statements that this verifier passed a proof, for backends to accept instead of re-verifying
+
+#[cfg(feature = "rust-signatures")]
+use base64::Engine;
+#[cfg(feature = "rust-signatures")]
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::jws::Jws;
+use crate::signature::{self, Failure, SignatureAlgorithm};
+#[cfg(feature = "rust-signatures")]
+use crate::vc::did_key;
+use crate::{did, hex};
+#[cfg(feature = "rust-signatures")]
+use crate::{ProofData, VerificationReport, Verifier};
+
+/// JOSE `typ` of receipts, so they can't be confused with proof envelopes
+#[cfg(feature = "rust-signatures")]
+const RECEIPT_TYPE: &str = "zkml-receipt+jwt";
+
+/// Claims of a receipt
+///
+/// ```json
+/// {
+///   "verifier": "did:key:z6Mk…",
+///   "proof_id": "sha3:0x…",
+///   "model_hash": "0x…",
+///   "result": "passed",
+///   "verified_at": 1700000000000
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Receipt {
+    /// `did:key` of the verifier's issuer key
+    verifier: String,
+    proof_id: String,
+    model_hash: String,
+    result: String,
+    /// Unix milliseconds, by the verifier's clock
+    verified_at: u64,
+}
+
+impl Receipt {
+    pub fn verifier(&self) -> &str {
+        &self.verifier
+    }
+
+    pub fn proof_id(&self) -> &str {
+        &self.proof_id
+    }
+
+    pub fn model_hash(&self) -> &str {
+        &self.model_hash
+    }
+
+    /// Always `"passed"`: failed verifications get no receipt
+    pub fn result(&self) -> &str {
+        &self.result
+    }
+
+    pub fn verified_at(&self) -> u64 {
+        self.verified_at
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Ed25519 public key of `issuer`: a `did:key` or 32 bytes of hex
+fn issuer_key(issuer: &str) -> Option<[u8; 32]> {
+    match issuer.strip_prefix("did:key:z") {
+        Some(multibase) => {
+            let multikey = bs58::decode(multibase).into_vec().ok()?;
+            multikey.strip_prefix(&did::ED25519_PUB[..])?.try_into().ok()
+        }
+        None => hex::decode(issuer)?.try_into().ok(),
+    }
+}
+
+/// Check a receipt from `issue_receipt` against the issuer's `did:key` (or hex Ed25519 key)
+///
+/// Returns its claims; how recent `verified_at` must be is up to the caller.
+/// Fails with `SignatureInvalid` if another key signed it, or
+/// `MalformedProof` if it isn't a receipt.
+pub fn verify_receipt(token: &str, issuer: &str) -> Result<Receipt, Failure> {
+    let key = issuer_key(issuer)
+        .ok_or((VerificationError::InvalidArgument, "Issuer must be a did:key or hex Ed25519 key"))?;
+    let jws = Jws::parse(token)?;
+    if jws.algorithm != SignatureAlgorithm::Ed25519 {
+        return Err((VerificationError::SignatureInvalid, "Receipts are signed with EdDSA"));
+    }
+    let signature = hex::encode(&jws.signature);
+    signature::verify_ed25519(&hex::encode(&key), &signature, jws.signing_input.as_bytes())
+        .map_err(|(error, _)| (error, "Receipt signature does not verify for this issuer"))?;
+    let receipt: Receipt = serde_json::from_slice(&jws.payload)
+        .map_err(|_| (VerificationError::MalformedProof, "Receipt payload is not a receipt claim set"))?;
+    Ok(receipt)
+}
+
+#[cfg(feature = "rust-signatures")]
+impl Verifier {
+    /// Sign a receipt for a passed verification of `proof_data` at `now`
+    pub fn receipt(
+        &self,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+        now: u64,
+    ) -> Result<String, Failure> {
+        let issuer = self
+            .issuer_key
+            .as_ref()
+            .ok_or((VerificationError::InvalidArgument, "No issuer key set; call set_issuer_key first"))?;
+        if !report.passed() {
+            return Err((VerificationError::InvalidArgument, "Only passed verifications get receipts"));
+        }
+        let verifier = did_key(issuer.verifying_key().as_bytes());
+        let header = serde_json::json!({ "alg": "EdDSA", "typ": RECEIPT_TYPE, "kid": verifier });
+        let claims = Receipt {
+            verifier,
+            proof_id: proof_data.proof_id(),
+            model_hash: proof_data.model_hash.clone(),
+            result: "passed".to_string(),
+            verified_at: now,
+        };
+        Ok(sign(issuer, &header.to_string(), &claims.to_json()))
+    }
+
+    /// Issue a signed receipt for a passed verification
+    ///
+    /// A compact EdDSA JWS (`typ: zkml-receipt+jwt`) signed with the
+    /// `set_issuer_key` key, naming the verifier's `did:key`, the proof ID,
+    /// its model hash, the result and when it was verified. Check it with
+    /// `verify_receipt`. Fails with `InvalidArgument` if the report did not
+    /// pass or no issuer key is set.
+    pub fn issue_receipt(&self, proof_data: &ProofData, report: &VerificationReport) -> Result<String, Failure> {
+        self.receipt(proof_data, report, self.now_ms())
+    }
+}
+
+/// Compact JWS of `payload` under `header`
+#[cfg(feature = "rust-signatures")]
+fn sign(key: &SigningKey, header: &str, payload: &str) -> String {
+    let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let signing_input = format!("{}.{}", b64.encode(header), b64.encode(payload));
+    let signature = key.sign(signing_input.as_bytes());
+    format!("{}.{}", signing_input, b64.encode(signature.to_bytes()))
+}
+
+#[cfg(all(test, feature = "rust-signatures"))]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_receipt() {
+        let mut verifier = Verifier::new("0xmodel".to_string());
+        verifier.issuer_key = Some(SigningKey::from_bytes(&[4u8; 32]));
+        let proof = fixtures::proof("0xmodel");
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        let token = verifier.receipt(&proof, &report, 1_700_000_000_000).unwrap();
+
+        let issuer = verifier.issuer_did().unwrap();
+        let receipt = verify_receipt(&token, &issuer).unwrap();
+        assert_eq!((receipt.proof_id(), receipt.result()), (proof.proof_id().as_str(), "passed"));
+        assert_eq!((receipt.verifier(), receipt.verified_at()), (issuer.as_str(), 1_700_000_000_000));
+        let hex_key = hex::encode(verifier.issuer_key.as_ref().unwrap().verifying_key().as_bytes());
+        assert_eq!(verify_receipt(&token, &hex_key), Ok(receipt));
+
+        let other = did_key(SigningKey::from_bytes(&[5u8; 32]).verifying_key().as_bytes());
+        assert_eq!(verify_receipt(&token, &other).unwrap_err().1, "Receipt signature does not verify for this issuer");
+        let (header, rest) = token.split_once('.').unwrap();
+        let (_, signature) = rest.rsplit_once('.').unwrap();
+        let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let forged = format!("{}.{}.{}", header, b64.encode(r#"{"result":"passed"}"#), signature);
+        assert_eq!(verify_receipt(&forged, &hex_key).unwrap_err().0, VerificationError::SignatureInvalid);
+        assert_eq!(verify_receipt(&token, "did:web:x").unwrap_err().0, VerificationError::InvalidArgument);
+
+        let failed = verifier.verify_report(&proof, b"in", b"other");
+        assert_eq!(verifier.receipt(&proof, &failed, 0).unwrap_err().1, "Only passed verifications get receipts");
+    }
+}
@@ -0,0 +1,142 @@
+// Multi-model allowlist
+// One verifier for several approved models, each with a label and version
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::VerifierConfig;
+use crate::hash;
+use crate::Verifier;
+
+/// An approved model
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ModelEntry {
+    model_hash: String,
+    label: String,
+    version: String,
+}
+
+impl ModelEntry {
+    pub fn new(model_hash: String, label: String, version: String) -> ModelEntry {
+        ModelEntry { model_hash, label, version }
+    }
+
+    pub fn model_hash(&self) -> &str {
+        &self.model_hash
+    }
+
+    /// Human-readable name, e.g. `"sentiment-classifier"`
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+/// Set of approved model hashes
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ModelRegistry {
+    entries: Vec<ModelEntry>,
+}
+
+impl ModelRegistry {
+    pub fn new() -> ModelRegistry {
+        ModelRegistry::default()
+    }
+
+    pub fn from_entries(entries: Vec<ModelEntry>) -> ModelRegistry {
+        ModelRegistry { entries }
+    }
+
+    /// Approve a model hash under `label` and `version`
+    pub fn add(&mut self, model_hash: String, label: String, version: String) {
+        self.entries.push(ModelEntry { model_hash, label, version });
+    }
+
+    pub fn entries(&self) -> &[ModelEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Load entries from `[{"model_hash", "label", "version"}, ...]`
+    pub fn from_json(json: &str) -> Result<ModelRegistry, serde_json::Error> {
+        serde_json::from_str(json).map(ModelRegistry::from_entries)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.entries).unwrap_or_default()
+    }
+
+    /// Entry whose hash matches `model_hash`, if any
+    pub fn lookup(&self, model_hash: &str) -> Option<&ModelEntry> {
+        self.entries.iter().find(|entry| hash::commitments_equal(&entry.model_hash, model_hash))
+    }
+}
+
+impl Verifier {
+    /// Verifier accepting proofs for any model in `registry`
+    ///
+    /// The report's `matched_model` says which entry a proof matched.
+    pub fn with_registry(registry: ModelRegistry, config: &VerifierConfig) -> Verifier {
+        let mut verifier = Verifier::with_config(String::new(), config);
+        verifier.registry = Some(registry);
+        verifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::error::VerificationError;
+
+    #[test]
+    fn test_registry_binding() {
+        let mut registry = ModelRegistry::new();
+        registry.add("0x1111".into(), "sentiment".into(), "1.2.0".into());
+        registry.add("0x2222".into(), "toxicity".into(), "3.0.1".into());
+        let registry = ModelRegistry::from_json(&registry.to_json()).unwrap();
+        let verifier = Verifier::with_registry(registry, &VerifierConfig::default());
+
+        let proof = |model_hash: &str| {
+            fixtures::proof(model_hash)
+        };
+        let report = verifier.verify_report(&proof("0x2222"), b"in", b"out");
+        assert!(report.passed());
+        assert_eq!(report.matched_model().unwrap().label(), "toxicity");
+
+        let report = verifier.verify_report(&proof("0x3333"), b"in", b"out");
+        assert_eq!(report.error(), Some(VerificationError::ModelMismatch));
+        assert!(report.matched_model().is_none());
+    }
+
+    #[test]
+    fn test_version_policy_over_registry() {
+        let mut registry = ModelRegistry::new();
+        registry.add("0x1111".into(), "classifier".into(), "1.9.0".into());
+        registry.add("0x2222".into(), "classifier".into(), "2.3.1".into());
+        let mut verifier = Verifier::with_registry(registry, &VerifierConfig::default());
+        verifier.set_version_policy("2.x").unwrap();
+
+        let mut proof = fixtures::proof("0x2222");
+        assert!(verifier.verify_report(&proof, b"in", b"out").passed());
+
+        proof.model_version = Some("2.4.0".into());
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        assert_eq!(report.failure_reasons(), ["Proof claims model_version 2.4.0 but the registry lists 2.3.1"]);
+
+        let mut old = proof.clone();
+        old.model_version = None;
+        old.model_hash = "0x1111".to_string();
+        let report = verifier.verify_report(&old, b"in", b"out");
+        assert_eq!(report.error(), Some(VerificationError::VersionNotAllowed));
+    }
+}
@@ -0,0 +1,442 @@
+// Rekor transparency log
+// Inclusion proofs of Sigstore Rekor entries against a signed checkpoint, and `rekord` entries for receipts
+
+use base64::Engine;
+use serde::Deserialize;
+use x509_cert::der::asn1::ObjectIdentifier;
+use sha2::{Digest, Sha256};
+use x509_cert::der::DecodePem;
+use x509_cert::spki::SubjectPublicKeyInfoOwned;
+
+use crate::crypto::{self, Check};
+use crate::error::VerificationError;
+use crate::hash::{self, HashAlgorithm};
+use crate::jws::Jws;
+use crate::merkle::{leaf_hash, root_from_path};
+use crate::signature::{self, verify_ed25519, Failure, SignatureAlgorithm};
+use crate::{did, hex, ProofData, Verifier};
+
+const EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
+/// DER prefix of an Ed25519 `SubjectPublicKeyInfo`, followed by the 32-byte key
+const ED25519_SPKI: [u8; 12] = [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
+
+/// Where a checkpoint's signature lines start (an em dash and a space, per the signed note format)
+const SIGNATURE_LINE: &str = "\u{2014} ";
+
+/// An entry as `GET /api/v1/log/entries/{uuid}` returns it (inside its `{uuid: entry}` map or not)
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntry {
+    /// Base64 canonical entry, the Merkle leaf
+    body: String,
+    integrated_time: u64,
+    #[serde(rename = "logID")]
+    log_id: String,
+    log_index: u64,
+    verification: EntryVerification,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntryVerification {
+    inclusion_proof: InclusionProof,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InclusionProof {
+    /// Index in the tree `checkpoint` signs, which the entry's global `logIndex` can be ahead of
+    log_index: u64,
+    root_hash: String,
+    tree_size: u64,
+    hashes: Vec<String>,
+    checkpoint: String,
+}
+
+/// Where a verified entry sits in the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct RekorInclusion {
+    log_index: u64,
+    integrated_time: u64,
+    log_id: String,
+    tree_size: u64,
+    root_hash: String,
+    signer_pubkey: String,
+}
+
+impl RekorInclusion {
+    /// Global index of the entry
+    pub fn log_index(&self) -> u64 {
+        self.log_index
+    }
+
+    /// When the log took the entry in (Unix seconds)
+    pub fn integrated_time(&self) -> u64 {
+        self.integrated_time
+    }
+
+    pub fn log_id(&self) -> &str {
+        &self.log_id
+    }
+
+    /// Size of the tree the checkpoint signs
+    pub fn tree_size(&self) -> u64 {
+        self.tree_size
+    }
+
+    /// Root of that tree (hex)
+    pub fn root_hash(&self) -> &str {
+        &self.root_hash
+    }
+
+    /// Ed25519 key (hex) the entry's signature over its data verifies under
+    pub fn signer_pubkey(&self) -> &str {
+        &self.signer_pubkey
+    }
+}
+
+fn malformed(reason: &'static str) -> Failure {
+    (VerificationError::MalformedProof, reason)
+}
+
+/// Rekor's public key, SEC1: PEM `PUBLIC KEY` (as `GET /api/v1/log/publicKey` serves it) or SEC1 hex, P-256 only
+fn log_key(key: &str) -> Result<Vec<u8>, Failure> {
+    let invalid = (VerificationError::InvalidArgument, "Log key must be a P-256 public key, PEM or SEC1 hex");
+    let key = key.trim();
+    let sec1 = if key.starts_with("-----BEGIN") {
+        let spki = SubjectPublicKeyInfoOwned::from_pem(key).map_err(|_| invalid)?;
+        if spki.algorithm.oid != EC_PUBLIC_KEY {
+            return Err(invalid);
+        }
+        spki.subject_public_key.raw_bytes().to_vec()
+    } else {
+        hex::decode(key).ok_or(invalid)?
+    };
+    match (sec1.len(), sec1.first()) {
+        (65, Some(4)) | (33, Some(2 | 3)) => Ok(sec1),
+        _ => Err(invalid),
+    }
+}
+
+/// Tree size and root hash of a checkpoint signed by `key`
+///
+/// A checkpoint is a signed note: origin, size and base64 root on separate
+/// lines, a blank line, then `— <name> <base64(key hint || signature)>` lines.
+fn checkpoint(note: &str, key: &[u8]) -> Result<(u64, Vec<u8>), Failure> {
+    let (text, signatures) = note.split_once("\n\n").ok_or(malformed("Checkpoint is not a signed note"))?;
+    let signed = format!("{}\n", text);
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let mut verified = false;
+    for line in signatures.lines().filter_map(|line| line.strip_prefix(SIGNATURE_LINE)) {
+        let signature = line.rsplit(' ').next().and_then(|s| b64.decode(s).ok()).unwrap_or_default();
+        if let Some(check) = signature.get(4..).and_then(|der| Check::p256_der(key, der, signed.as_bytes())) {
+            verified = crypto::verify(check)?;
+        }
+        if verified {
+            break;
+        }
+    }
+    if !verified {
+        return Err((VerificationError::SignatureInvalid, "Checkpoint is not signed by the log key"));
+    }
+    let mut lines = text.lines().skip(1);
+    let size = lines.next().and_then(|size| size.parse().ok());
+    let root = lines.next().and_then(|root| b64.decode(root).ok());
+    size.zip(root).ok_or(malformed("Checkpoint has no tree size and root"))
+}
+
+/// Ed25519 key (hex) of an entry whose signature verifies over `data`
+///
+/// Rekor checks an entry's signature when it is logged but the entry itself
+/// carries no proof of that, so the signature is checked again here.
+fn entry_signer(logged: &serde_json::Value, data: &[u8]) -> Result<String, Failure> {
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let signature = &logged["spec"]["signature"];
+    let pem = signature["publicKey"]["content"].as_str().and_then(|pem| b64.decode(pem).ok());
+    let spki = pem.and_then(|pem| SubjectPublicKeyInfoOwned::from_pem(pem).ok());
+    let spki = spki.ok_or(malformed("Rekor entry has no signing key"))?;
+    if spki.algorithm.oid != ED25519 {
+        return Err((VerificationError::SignatureInvalid, "Rekor entry is not signed with an Ed25519 key"));
+    }
+    let key = hex::encode(spki.subject_public_key.raw_bytes());
+    let content = signature["content"].as_str().and_then(|content| b64.decode(content).ok());
+    let content = content.ok_or(malformed("Rekor entry has no signature"))?;
+    verify_ed25519(&key, &hex::encode(&content), data)
+        .map_err(|_| (VerificationError::SignatureInvalid, "Rekor entry signature does not verify over its data"))?;
+    Ok(key)
+}
+
+/// Check that `entry` logs `data`, signed, and is included in the tree a checkpoint signed by `key` commits to
+fn check_entry(entry: &str, data: &[u8], key: &str) -> Result<RekorInclusion, Failure> {
+    let key = log_key(key)?;
+    let value: serde_json::Value = serde_json::from_str(entry).map_err(|_| malformed("Rekor entry is not JSON"))?;
+    let value = match value.as_object() {
+        Some(map) if map.len() == 1 && !map.contains_key("body") => map.values().next().cloned().unwrap_or_default(),
+        _ => value,
+    };
+    let entry: LogEntry = serde_json::from_value(value).map_err(|_| malformed("Rekor entry has no inclusion proof"))?;
+    let body = base64::engine::general_purpose::STANDARD
+        .decode(&entry.body)
+        .map_err(|_| malformed("Rekor entry body is not base64"))?;
+    let logged: serde_json::Value =
+        serde_json::from_slice(&body).map_err(|_| malformed("Rekor entry body is not JSON"))?;
+    // `rekord` and `hashedrekord` entries both record the data's digest here
+    let hash = &logged["spec"]["data"]["hash"];
+    if hash["algorithm"] != "sha256" {
+        return Err(malformed("Rekor entry doesn't record a SHA-256 digest of its data"));
+    }
+    let expected = hex::encode(&Sha256::digest(data));
+    if !hash["value"].as_str().is_some_and(|value| value.eq_ignore_ascii_case(&expected)) {
+        return Err((VerificationError::InclusionInvalid, "Rekor entry logs different data"));
+    }
+    let signer_pubkey = entry_signer(&logged, data)?;
+
+    let proof = &entry.verification.inclusion_proof;
+    let (size, root) = checkpoint(&proof.checkpoint, &key)?;
+    let claimed_root = hex::decode(&proof.root_hash).ok_or(malformed("Inclusion proof root is not hex"))?;
+    if size != proof.tree_size || root != claimed_root {
+        return Err((VerificationError::InclusionInvalid, "Inclusion proof is for another tree than its checkpoint"));
+    }
+    let path = proof.hashes.iter().map(|h| hex::decode(h)).collect::<Option<Vec<_>>>();
+    let path = path.ok_or(malformed("Inclusion proof hashes are not hex"))?;
+    let recomputed = leaf_hash(&body, HashAlgorithm::Sha256)
+        .and_then(|leaf| root_from_path(proof.log_index, proof.tree_size, leaf, &path, HashAlgorithm::Sha256))
+        .map_err(|error| (error, "Cannot hash the inclusion proof"))?;
+    if recomputed != Some(root) {
+        return Err((VerificationError::InclusionInvalid, "Rekor entry is not in the checkpoint's tree"));
+    }
+    Ok(RekorInclusion {
+        log_index: entry.log_index,
+        integrated_time: entry.integrated_time,
+        log_id: entry.log_id,
+        tree_size: proof.tree_size,
+        root_hash: proof.root_hash.to_ascii_lowercase(),
+        signer_pubkey,
+    })
+}
+
+/// `check_entry`, requiring the entry to be signed by `signer` (hex Ed25519 key)
+fn check_signed_entry(
+    entry: &str,
+    data: &[u8],
+    log_key: &str,
+    signer: &str,
+) -> Result<RekorInclusion, Failure> {
+    let inclusion = check_entry(entry, data, log_key)?;
+    if !hash::digests_equal(&inclusion.signer_pubkey, signer) {
+        return Err((VerificationError::SignatureInvalid, "Rekor entry is signed by another key"));
+    }
+    Ok(inclusion)
+}
+
+impl Verifier {
+    /// Check that a prover published `proof_data` in Rekor
+    ///
+    /// `verify_rekor_entry` for `proof_data.signing_bytes()`, with the entry
+    /// signed by a key the verifier trusts for this prover: a trusted signer,
+    /// or the key the proof's `kid` or trusted `prover_did` resolves to. The
+    /// proof's own `signer_pubkey` vouches for nothing, since anyone can log
+    /// a proof under their key. Fails as `verify_rekor_entry` does, or with
+    /// `UntrustedSigner` if someone else logged the proof.
+    pub fn verify_rekor_proof(
+        &self,
+        proof_data: &ProofData,
+        entry: &str,
+        log_key: &str,
+    ) -> Result<RekorInclusion, Failure> {
+        let inclusion = check_entry(entry, &proof_data.signing_bytes(), log_key)?;
+        let signer = &inclusion.signer_pubkey;
+        let resolved = || {
+            self.signer_keys(proof_data, proof_data.kid.as_deref(), SignatureAlgorithm::Ed25519)
+                .and_then(|(candidates, pretrusted)| self.trusted_candidates(candidates, pretrusted))
+                .is_ok_and(|keys| keys.iter().any(|key| hash::digests_equal(key, signer)))
+        };
+        if !signature::is_trusted(&self.trusted_signers, signer) && !resolved() {
+            return Err((VerificationError::UntrustedSigner, "Rekor entry is not signed by a trusted key of the prover"));
+        }
+        Ok(inclusion)
+    }
+}
+
+/// Rekor `rekord` entry for a receipt from `issue_receipt`, to `POST /api/v1/log/entries`
+///
+/// Logs the receipt's signing input with its signature and the issuer's
+/// key, which Rekor checks before accepting it.
+pub fn rekor_receipt_entry(token: &str) -> Result<String, Failure> {
+    let jws = Jws::parse(token)?;
+    if jws.algorithm != SignatureAlgorithm::Ed25519 {
+        return Err((VerificationError::SignatureInvalid, "Receipts are signed with EdDSA"));
+    }
+    let multikey = jws.kid.as_deref().and_then(|kid| kid.strip_prefix("did:key:z"));
+    let multikey = multikey.and_then(|multibase| bs58::decode(multibase).into_vec().ok()).unwrap_or_default();
+    let key = multikey.strip_prefix(&did::ED25519_PUB[..]).ok_or(malformed("Receipt kid is not an Ed25519 did:key"))?;
+
+    let std = base64::engine::general_purpose::STANDARD;
+    let spki = std.encode([&ED25519_SPKI, key].concat());
+    let pem = format!("-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n", spki);
+    let entry = serde_json::json!({
+        "apiVersion": "0.0.1",
+        "kind": "rekord",
+        "spec": {
+            "data": { "content": std.encode(jws.signing_input) },
+            "signature": {
+                "format": "x509",
+                "content": std.encode(&jws.signature),
+                "publicKey": { "content": std.encode(pem) },
+            },
+        },
+    });
+    Ok(entry.to_string())
+}
+
+/// Check that a Rekor entry logs `data` signed by `signer_pubkey` and is included in the log
+///
+/// `data` is what was logged: a `rekor_receipt_entry` logs the receipt's
+/// JWS signing input (the token up to its last `.`), a prover's entry
+/// `proof.signing_bytes()` (see `Verifier::verify_rekor_proof`). The
+/// entry's signature must verify over `data` under `signer_pubkey` (hex
+/// Ed25519), and its inclusion proof lead to the root of a checkpoint
+/// signed by `log_key` (PEM or SEC1 hex, P-256). The entry's signed entry
+/// timestamp is not checked.
+pub fn verify_rekor_entry(
+    entry: &str,
+    data: &[u8],
+    signer_pubkey: &str,
+    log_key: &str,
+) -> Result<RekorInclusion, Failure> {
+    check_signed_entry(entry, data, log_key, signer_pubkey)
+}
+
+#[cfg(all(test, feature = "rust-signatures"))]
+mod tests {
+    use super::*;
+    use crate::merkle::{audit_path, tree_root};
+    use p256::ecdsa::signature::Signer;
+    use p256::ecdsa::{Signature, SigningKey};
+    use crate::fixtures;
+
+    /// A log of `bodies` and the entry for the one at `index`, signed by `key`
+    fn entry(bodies: &[Vec<u8>], index: usize, key: &SigningKey) -> serde_json::Value {
+        let leaves: Vec<_> = bodies.iter().map(|b| leaf_hash(b, HashAlgorithm::Sha256).unwrap()).collect();
+        let root = tree_root(&leaves, HashAlgorithm::Sha256).unwrap();
+        let path = audit_path(index, &leaves, HashAlgorithm::Sha256).unwrap();
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let note = format!("rekor.test - 42\n{}\n{}\n", bodies.len(), b64.encode(&root));
+        let signature: Signature = key.sign(note.as_bytes());
+        let line = b64.encode([&[0u8; 4][..], signature.to_der().as_bytes()].concat());
+        serde_json::json!({ "24296fb24b8ad77a": {
+            "body": b64.encode(&bodies[index]),
+            "integratedTime": 1_700_000_000,
+            "logID": "c0d23d6ad406973f",
+            "logIndex": 1000 + index,
+            "verification": { "inclusionProof": {
+                "logIndex": index,
+                "rootHash": hex::encode(&root),
+                "treeSize": bodies.len(),
+                "hashes": path.iter().map(|h| hex::encode(h)).collect::<Vec<_>>(),
+                "checkpoint": format!("{}\n\u{2014} rekor.test {}\n", note, line),
+            }},
+        }})
+    }
+
+    /// Canonical `hashedrekord` body of `data` signed by `signer`
+    fn body(data: &[u8], signer: &ed25519_dalek::SigningKey) -> Vec<u8> {
+        use ed25519_dalek::Signer as _;
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let spki = b64.encode([&ED25519_SPKI[..], signer.verifying_key().as_bytes()].concat());
+        let pem = format!("-----BEGIN PUBLIC KEY-----\n{}\n-----END PUBLIC KEY-----\n", spki);
+        let body = serde_json::json!({ "kind": "hashedrekord", "spec": {
+            "data": { "hash": { "algorithm": "sha256", "value": hex::encode(&Sha256::digest(data)) }},
+            "signature": {
+                "content": b64.encode(signer.sign(data).to_bytes()),
+                "publicKey": { "content": b64.encode(pem) },
+            },
+        }});
+        body.to_string().into_bytes()
+    }
+
+    #[test]
+    fn test_rekor_entry() {
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let log_key = hex::encode(key.verifying_key().to_encoded_point(false).as_bytes());
+        let prover = ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]);
+        let signer = hex::encode(prover.verifying_key().as_bytes());
+        let bodies: Vec<_> = (0..5u8).map(|i| body(&[i], &prover)).collect();
+        let logged = entry(&bodies, 3, &key).to_string();
+
+        let inclusion = verify_rekor_entry(&logged, &[3], &signer, &log_key).unwrap();
+        assert_eq!((inclusion.log_index(), inclusion.tree_size()), (1003, 5));
+        assert_eq!(inclusion.signer_pubkey(), signer);
+        assert_eq!(check_entry(&logged, &[4], &log_key).unwrap_err().1, "Rekor entry logs different data");
+        let error = check_signed_entry(&logged, &[3], &log_key, &"ab".repeat(32)).unwrap_err();
+        assert_eq!(error, (VerificationError::SignatureInvalid, "Rekor entry is signed by another key"));
+
+        let other = SigningKey::from_bytes(&[8u8; 32].into()).unwrap();
+        let other_key = hex::encode(other.verifying_key().to_encoded_point(true).as_bytes());
+        let error = verify_rekor_entry(&logged, &[3], &signer, &other_key).unwrap_err();
+        assert_eq!(error.0, VerificationError::SignatureInvalid);
+
+        // An entry whose path leads elsewhere, under an honestly signed checkpoint
+        let mut moved = entry(&bodies, 3, &key);
+        moved["24296fb24b8ad77a"]["verification"]["inclusionProof"]["logIndex"] = 2.into();
+        let error = check_entry(&moved.to_string(), &[3], &log_key).unwrap_err();
+        assert_eq!(error, (VerificationError::InclusionInvalid, "Rekor entry is not in the checkpoint's tree"));
+        let error = verify_rekor_entry("{}", &[3], &signer, &log_key).unwrap_err();
+        assert_eq!(error.0, VerificationError::MalformedProof);
+        let error = verify_rekor_entry(&logged, &[3], &signer, "0xab").unwrap_err();
+        assert_eq!(error.0, VerificationError::InvalidArgument);
+
+        // A logged digest with a signature over something else
+        let mut forged: serde_json::Value = serde_json::from_slice(&body(&[9], &prover)).unwrap();
+        forged["spec"]["data"]["hash"]["value"] = hex::encode(&Sha256::digest([3])).into();
+        let forged = entry(&[forged.to_string().into_bytes()], 0, &key).to_string();
+        let error = check_entry(&forged, &[3], &log_key).unwrap_err();
+        assert_eq!(error.1, "Rekor entry signature does not verify over its data");
+    }
+
+    #[test]
+    fn test_rekor_proof() {
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let log_key = hex::encode(key.verifying_key().to_encoded_point(false).as_bytes());
+        let prover = ed25519_dalek::SigningKey::from_bytes(&[5u8; 32]);
+        let mut proof = fixtures::proof("0xmodel");
+        proof.signer_pubkey = Some(hex::encode(prover.verifying_key().as_bytes()));
+        let mut verifier = Verifier::new("0xmodel".to_string());
+        let published = entry(&[body(&proof.signing_bytes(), &prover)], 0, &key).to_string();
+        let untrusted = (VerificationError::UntrustedSigner, "Rekor entry is not signed by a trusted key of the prover");
+        assert_eq!(verifier.verify_rekor_proof(&proof, &published, &log_key).unwrap_err(), untrusted);
+        verifier.add_trusted_signer(hex::encode(prover.verifying_key().as_bytes()));
+        assert_eq!(verifier.verify_rekor_proof(&proof, &published, &log_key).unwrap().log_index(), 1000);
+
+        // Anyone can log a proof's signing bytes under their own key, and claim it as signer_pubkey
+        let squatter = ed25519_dalek::SigningKey::from_bytes(&[6u8; 32]);
+        let squatted = entry(&[body(&proof.signing_bytes(), &squatter)], 0, &key).to_string();
+        assert_eq!(verifier.verify_rekor_proof(&proof, &squatted, &log_key).unwrap_err(), untrusted);
+        proof.signer_pubkey = Some(hex::encode(squatter.verifying_key().as_bytes()));
+        let squatted = entry(&[body(&proof.signing_bytes(), &squatter)], 0, &key).to_string();
+        assert_eq!(verifier.verify_rekor_proof(&proof, &squatted, &log_key).unwrap_err(), untrusted);
+        assert_eq!(Verifier::new("0xmodel".to_string()).verify_rekor_proof(&proof, &squatted, &log_key).unwrap_err(), untrusted);
+    }
+
+    #[test]
+    fn test_rekor_receipt_entry() {
+        let mut verifier = Verifier::new("0xmodel".to_string());
+        verifier.set_issuer_key(&hex::encode(&[4u8; 32])).unwrap();
+        let proof = fixtures::proof("0xmodel");
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        let token = verifier.issue_receipt(&proof, &report).unwrap();
+
+        let entry: serde_json::Value = serde_json::from_str(&rekor_receipt_entry(&token).unwrap()).unwrap();
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let signing_input = token.rsplit_once('.').unwrap().0;
+        let content = b64.decode(entry["spec"]["data"]["content"].as_str().unwrap()).unwrap();
+        assert_eq!(content, signing_input.as_bytes());
+        let pem = b64.decode(entry["spec"]["signature"]["publicKey"]["content"].as_str().unwrap()).unwrap();
+        let spki = SubjectPublicKeyInfoOwned::from_pem(&pem).unwrap();
+        let issuer = ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]);
+        assert_eq!(spki.subject_public_key.raw_bytes(), issuer.verifying_key().as_bytes());
+        assert_eq!(rekor_receipt_entry("a.b.c").unwrap_err().0, VerificationError::MalformedProof);
+    }
+}
@@ -0,0 +1,394 @@
+// Verification report
+// Per-check outcome of a `Verifier` run
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::VerificationError;
+#[cfg(feature = "onnx")]
+use crate::onnx::diagnostic::ModelDiagnostic;
+use crate::registry::ModelEntry;
+
+/// Individual checks performed during verification
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Check {
+    ModelBinding,
+    InputHash,
+    OutputHash,
+    InputSpec,
+    OutputSpec,
+    Timestamp,
+    ProofValidity,
+    ProofBinding,
+    Nonce,
+    Signature,
+    AggregateSignature,
+    CertificateChain,
+    Quantization,
+    Preprocessing,
+    Recomputation,
+    Revocation,
+    ModelVersion,
+    Composite,
+    Inclusion,
+    OutputTolerance,
+    Classification,
+    Anchor,
+    RegistryModel,
+    RegistryProver,
+}
+
+impl Check {
+    /// Error reported when this check fails
+    pub fn error(self) -> VerificationError {
+        match self {
+            Check::ModelBinding => VerificationError::ModelMismatch,
+            Check::InputHash => VerificationError::InputHashMismatch,
+            Check::OutputHash => VerificationError::OutputHashMismatch,
+            Check::InputSpec | Check::OutputSpec => VerificationError::ShapeMismatch,
+            Check::Timestamp => VerificationError::Expired,
+            Check::ProofValidity => VerificationError::NotVerified,
+            Check::ProofBinding => VerificationError::ProofHashMismatch,
+            Check::Nonce => VerificationError::NonceMismatch,
+            Check::Signature => VerificationError::SignatureInvalid,
+            Check::AggregateSignature => VerificationError::AggregateSignatureInvalid,
+            Check::CertificateChain => VerificationError::CertificateChainInvalid,
+            Check::Quantization => VerificationError::QuantizationMismatch,
+            Check::Preprocessing => VerificationError::PreprocessingMismatch,
+            Check::Recomputation => VerificationError::RecomputationMismatch,
+            Check::Revocation => VerificationError::Revoked,
+            Check::ModelVersion => VerificationError::VersionNotAllowed,
+            Check::Composite => VerificationError::CompositeInvalid,
+            Check::Inclusion => VerificationError::InclusionInvalid,
+            Check::OutputTolerance => VerificationError::ToleranceExceeded,
+            Check::Classification => VerificationError::ClassMismatch,
+            Check::Anchor => VerificationError::AnchorInvalid,
+            Check::RegistryModel | Check::RegistryProver => VerificationError::NotRegistered,
+        }
+    }
+}
+
+/// How much a passing report vouches for the claimed output
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum Confidence {
+    /// Some check failed
+    Unverified,
+    /// All commitment checks passed; the output rests on the proof
+    Proof,
+    /// Also re-executed and matched (the WASM crate's native `onnxruntime` feature)
+    Recomputed,
+}
+
+/// Notified as a report's checks start and finish, e.g. by progress UIs
+pub trait CheckObserver: Send + Sync {
+    /// `check` began; fired once per check, before its outcome
+    fn started(&self, check: Check);
+
+    /// `result` was recorded, in report order
+    fn finished(&self, result: &CheckResult);
+}
+
+/// A report's observer, left out of its `Debug` output and JSON
+#[derive(Clone, Default)]
+struct Observer(Option<Arc<dyn CheckObserver>>);
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() { "Observer(Some(..))" } else { "Observer(None)" })
+    }
+}
+
+/// Result of a single check
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CheckResult {
+    check: Check,
+    passed: bool,
+    reason: Option<String>,
+    error: Option<VerificationError>,
+    duration_ms: f64,
+}
+
+impl CheckResult {
+    pub fn check(&self) -> Check {
+        self.check
+    }
+
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    /// Why the check failed (`None` when it passed)
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Error code of the failure (`None` when it passed)
+    pub fn error(&self) -> Option<VerificationError> {
+        self.error
+    }
+
+    pub fn duration_ms(&self) -> f64 {
+        self.duration_ms
+    }
+}
+
+/// Outcome of a full verification run
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct VerificationReport {
+    checks: Vec<CheckResult>,
+    total_ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    matched_model: Option<ModelEntry>,
+    #[cfg(feature = "onnx")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model_diagnostic: Option<ModelDiagnostic>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    anchored_block: Option<u64>,
+    #[serde(skip)]
+    observer: Observer,
+    /// Checks announced as started whose outcome isn't recorded yet
+    #[serde(skip)]
+    running: Vec<Check>,
+    /// Spans of the running checks
+    #[cfg(feature = "tracing")]
+    #[serde(skip)]
+    spans: Vec<(Check, tracing::Span)>,
+}
+
+impl VerificationReport {
+    /// Empty report that notifies `observer` as checks start and finish
+    pub fn with_observer(observer: Arc<dyn CheckObserver>) -> Self {
+        VerificationReport { observer: Observer(Some(observer)), ..VerificationReport::default() }
+    }
+
+    /// True when every check passed
+    pub fn passed(&self) -> bool {
+        !self.checks.is_empty() && self.checks.iter().all(|c| c.passed)
+    }
+
+    pub fn checks(&self) -> &[CheckResult] {
+        &self.checks
+    }
+
+    /// Reasons of all failed checks, in check order
+    pub fn failure_reasons(&self) -> Vec<String> {
+        self.checks.iter().filter_map(|c| c.reason.clone()).collect()
+    }
+
+    /// Error code of the first failed check (`None` when all passed)
+    pub fn error(&self) -> Option<VerificationError> {
+        self.first_failure().and_then(|c| c.error)
+    }
+
+    /// Registry entry the proof's model matched (registry verifiers only)
+    pub fn matched_model(&self) -> Option<&ModelEntry> {
+        self.matched_model.as_ref()
+    }
+
+    /// Why model binding failed, for verifiers built from the model file
+    #[cfg(feature = "onnx")]
+    pub fn model_diagnostic(&self) -> Option<&ModelDiagnostic> {
+        self.model_diagnostic.as_ref()
+    }
+
+    /// Block the proof was anchored at, once an anchoring check confirmed it
+    pub fn anchored_block(&self) -> Option<u64> {
+        self.anchored_block
+    }
+
+    /// Confidence tier of the result
+    pub fn confidence(&self) -> Confidence {
+        match (self.passed(), self.ran(Check::Recomputation)) {
+            (false, _) => Confidence::Unverified,
+            (true, false) => Confidence::Proof,
+            (true, true) => Confidence::Recomputed,
+        }
+    }
+
+    pub fn total_ms(&self) -> f64 {
+        self.total_ms
+    }
+
+    /// Serialize report to JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Announce `check` and time it from now
+    ///
+    /// Only needed where a check should show as running before its outcome
+    /// is known; recording a check announces it too, if it wasn't already.
+    pub fn start(&mut self, check: Check) -> Stopwatch {
+        self.announce(check);
+        Stopwatch::start()
+    }
+
+    /// Fire the started event for `check` unless it is already running
+    pub fn announce(&mut self, check: Check) -> bool {
+        if self.running.contains(&check) {
+            return false;
+        }
+        self.running.push(check);
+        #[cfg(feature = "tracing")]
+        self.spans.push((check, crate::trace::check_span(check)));
+        if let Some(observer) = &self.observer.0 {
+            observer.started(check);
+        }
+        true
+    }
+
+    /// Record the outcome of a check started at `started` (see `Stopwatch`)
+    pub fn record(&mut self, check: Check, passed: bool, reason: &str, started: Stopwatch) {
+        let error = if passed { None } else { Some(check.error()) };
+        self.push(check, error, reason, started);
+    }
+
+    /// Record a check that failed with a specific error rather than its default one
+    pub fn record_error(&mut self, check: Check, error: VerificationError, reason: &str, started: Stopwatch) {
+        self.push(check, Some(error), reason, started);
+    }
+
+    fn push(&mut self, check: Check, error: Option<VerificationError>, reason: &str, started: Stopwatch) {
+        let duration_ms = started.elapsed_ms();
+        self.announce(check);
+        self.running.retain(|&c| c != check);
+        self.total_ms += duration_ms;
+        let result = CheckResult {
+            check,
+            passed: error.is_none(),
+            reason: error.map(|_| reason.to_string()),
+            error,
+            duration_ms,
+        };
+        #[cfg(feature = "tracing")]
+        if let Some(i) = self.spans.iter().position(|(c, _)| *c == check) {
+            crate::trace::record(&self.spans.remove(i).1, &result);
+        }
+        if let Some(observer) = &self.observer.0 {
+            observer.finished(&result);
+        }
+        self.checks.push(result);
+    }
+
+    pub fn set_matched_model(&mut self, entry: Option<ModelEntry>) {
+        self.matched_model = entry;
+    }
+
+    #[cfg(feature = "onnx")]
+    pub fn set_model_diagnostic(&mut self, diagnostic: Option<ModelDiagnostic>) {
+        self.model_diagnostic = diagnostic;
+    }
+
+    pub fn set_anchored_block(&mut self, block: Option<u64>) {
+        self.anchored_block = block;
+    }
+
+    /// First failed check, if any
+    pub fn first_failure(&self) -> Option<&CheckResult> {
+        self.checks.iter().find(|c| !c.passed)
+    }
+
+    /// The first failed check's error and reason, if any
+    pub fn into_result(self) -> Result<(), (VerificationError, String)> {
+        match self.first_failure() {
+            Some(failed) => {
+                Err((failed.error.unwrap_or(failed.check.error()), failed.reason.clone().unwrap_or_default()))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Whether `check` has been recorded, pass or fail
+    pub fn ran(&self, check: Check) -> bool {
+        self.checks.iter().any(|c| c.check == check)
+    }
+
+    /// Whether `check` ran and passed
+    pub fn check_passed(&self, check: Check) -> bool {
+        self.checks.iter().any(|c| c.check == check && c.passed)
+    }
+}
+
+/// Wall-clock timer used for per-check timings
+#[derive(Clone, Copy)]
+pub struct Stopwatch {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi")))]
+    start: f64,
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi"))))]
+    start: std::time::Instant,
+}
+
+impl Stopwatch {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi")))]
+    pub fn start() -> Self {
+        Stopwatch { start: crate::clock::monotonic_ms() }
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi"))))]
+    pub fn start() -> Self {
+        Stopwatch { start: std::time::Instant::now() }
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi")))]
+    pub fn elapsed_ms(&self) -> f64 {
+        crate::clock::monotonic_ms() - self.start
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32", not(target_os = "wasi"))))]
+    pub fn elapsed_ms(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Observer logging what it's told
+    #[derive(Default)]
+    struct Log(Mutex<Vec<String>>);
+
+    impl CheckObserver for Log {
+        fn started(&self, check: Check) {
+            self.0.lock().unwrap().push(format!("started {:?}", check));
+        }
+
+        fn finished(&self, result: &CheckResult) {
+            self.0.lock().unwrap().push(format!("{:?} {}", result.check(), result.passed()));
+        }
+    }
+
+    #[test]
+    fn test_checks_announced_once() {
+        let log = Arc::new(Log::default());
+        let mut report = VerificationReport::with_observer(log.clone());
+
+        // Announced ahead of a spec check recorded in between
+        assert!(report.announce(Check::InputHash));
+        assert!(!report.announce(Check::InputHash));
+        report.record(Check::InputSpec, true, "", Stopwatch::start());
+        let started = report.start(Check::InputHash);
+        assert!(!report.announce(Check::InputHash));
+        report.record(Check::InputHash, false, "Input does not match input_hash", started);
+
+        // Finished checks are announced afresh if recorded again
+        assert!(report.announce(Check::InputHash));
+        assert_eq!(report.checks().len(), 2);
+        assert_eq!(
+            *log.0.lock().unwrap(),
+            ["started InputHash", "started InputSpec", "InputSpec true", "InputHash false", "started InputHash"]
+        );
+        assert_eq!(
+            report.into_result(),
+            Err((VerificationError::InputHashMismatch, "Input does not match input_hash".to_string()))
+        );
+    }
+}
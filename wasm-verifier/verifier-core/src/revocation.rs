@@ -0,0 +1,133 @@
+// Revocation lists
+// Signed lists of revoked model hashes, prover keys and proofs that `verify` consults
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::signature::{self, Failure};
+use crate::{hash, jcs, ProofData, Verifier};
+
+/// Revocation list, signed with Ed25519 by its issuer
+///
+/// ```json
+/// { "issuer": "<ed25519 pubkey hex>", "issued_at": 1700000000000,
+///   "models": ["0x..."], "keys": ["<pubkey, address, kid or DID>"],
+///   "proofs": ["<proof_hash>"], "signature": "<hex>" }
+/// ```
+///
+/// The signature covers the RFC 8785 canonical JSON of every other field.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RevocationList {
+    issuer: String,
+    issued_at: u64,
+    #[serde(default)]
+    models: Vec<String>,
+    #[serde(default)]
+    keys: Vec<String>,
+    #[serde(default)]
+    proofs: Vec<String>,
+    #[serde(default, skip_serializing)]
+    signature: String,
+}
+
+impl RevocationList {
+    /// Parse a list and check it is signed by `issuer`
+    pub fn parse_signed(json: &str, issuer: &str) -> Result<RevocationList, Failure> {
+        let list: RevocationList = serde_json::from_str(json)
+            .map_err(|_| (VerificationError::MalformedProof, "Invalid revocation list JSON"))?;
+        if !hash::digests_equal(&list.issuer, issuer) {
+            return Err((VerificationError::UntrustedSigner, "Revocation list is not from the trusted issuer"));
+        }
+        signature::verify_ed25519(issuer, &list.signature, &list.signing_bytes())?;
+        Ok(list)
+    }
+
+    /// Unix milliseconds the issuer dated the list
+    pub fn issued_at(&self) -> u64 {
+        self.issued_at
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        jcs::canonicalize(&serde_json::to_value(self).unwrap_or_default()).into_bytes()
+    }
+
+    /// Why the list rejects `proof`, if it does
+    pub fn revokes(&self, proof: &ProofData) -> Option<&'static str> {
+        if self.models.iter().any(|m| hash::commitments_equal(m, &proof.model_hash)) {
+            return Some("Proof's model has been revoked");
+        }
+        if self.proofs.iter().any(|p| hash::commitments_equal(p, &proof.proof_hash)) {
+            return Some("Proof has been revoked");
+        }
+        let signer_revoked = |key: &String| {
+            proof.signer_pubkey.as_deref().is_some_and(|pubkey| hash::digests_equal(key, pubkey))
+                || proof.kid.as_ref() == Some(key)
+                || proof.prover_did.as_deref().is_some_and(|did| crate::did::split_fragment(did).0 == key)
+        };
+        self.keys.iter().any(signer_revoked).then_some("Proof's signing key has been revoked")
+    }
+}
+
+impl Verifier {
+    /// Load a revocation list signed by `issuer_pubkey` (Ed25519, hex)
+    ///
+    /// Replaces the current list. Fails with `SignatureInvalid` or
+    /// `UntrustedSigner` if the list isn't validly signed by the issuer, and
+    /// `InvalidArgument` if it is older than the list already loaded, so a
+    /// stale list can't be replayed to un-revoke anything.
+    pub fn load_revocation_list(&mut self, list_json: &str, issuer_pubkey: &str) -> Result<(), Failure> {
+        let list = RevocationList::parse_signed(list_json, issuer_pubkey)?;
+        if self.revocations.as_ref().is_some_and(|current| list.issued_at < current.issued_at) {
+            return Err((VerificationError::InvalidArgument, "Revocation list is older than the one loaded"));
+        }
+        self.revocations = Some(list);
+        Ok(())
+    }
+
+    /// `issued_at` of the loaded revocation list (`None` if none)
+    pub fn revocation_list_issued_at(&self) -> Option<u64> {
+        self.revocations.as_ref().map(|list| list.issued_at)
+    }
+}
+
+#[cfg(all(test, feature = "rust-signatures"))]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hex;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed_list(key: &SigningKey, issued_at: u64, models: &[&str]) -> String {
+        let mut list = RevocationList {
+            issuer: hex::encode(key.verifying_key().as_bytes()),
+            issued_at,
+            models: models.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        };
+        list.signature = hex::encode(&key.sign(&list.signing_bytes()).to_bytes());
+        let mut json = serde_json::to_value(&list).unwrap();
+        json["signature"] = list.signature.clone().into();
+        json.to_string()
+    }
+
+    #[test]
+    fn test_revoked_model() {
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let issuer = hex::encode(key.verifying_key().as_bytes());
+        let model_hash = "0x1234567890abcdef".to_string();
+        let mut verifier = Verifier::new(model_hash.clone());
+        let proof = fixtures::proof(&model_hash);
+        assert!(verifier.verify_report(&proof, b"in", b"out").passed());
+
+        let list = signed_list(&key, 2, &[&model_hash]);
+        let list = RevocationList::parse_signed(&list, &issuer).unwrap();
+        assert_eq!(list.revokes(&proof), Some("Proof's model has been revoked"));
+        verifier.revocations = Some(list);
+        assert_eq!(verifier.verify_report(&proof, b"in", b"out").error(), Some(VerificationError::Revoked));
+
+        let forged = signed_list(&SigningKey::from_bytes(&[1u8; 32]), 3, &[]);
+        assert_eq!(RevocationList::parse_signed(&forged, &issuer).unwrap_err().0, VerificationError::UntrustedSigner);
+        let tampered = signed_list(&key, 3, &[&model_hash]).replace(&model_hash, "0x00");
+        assert_eq!(RevocationList::parse_signed(&tampered, &issuer).unwrap_err().0, VerificationError::SignatureInvalid);
+    }
+}
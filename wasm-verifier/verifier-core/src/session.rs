@@ -0,0 +1,148 @@
+// Verifier sessions
+// Running pass/fail tallies and seen proof IDs across many verifications, for UI state
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ProofData, VerificationReport};
+
+/// Tallies of a `Session` so far
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SessionSummary {
+    passed: u32,
+    failed: u32,
+    duplicates: u32,
+    /// Failed proofs per `VerificationError` kind of their first failed check
+    failures_by_error: BTreeMap<String, u32>,
+    started_at: u64,
+    last_verified_at: Option<u64>,
+    total_ms: f64,
+}
+
+impl SessionSummary {
+    /// Distinct proofs verified
+    pub fn total(&self) -> u32 {
+        self.passed + self.failed
+    }
+
+    pub fn passed(&self) -> u32 {
+        self.passed
+    }
+
+    pub fn failed(&self) -> u32 {
+        self.failed
+    }
+
+    /// Proofs verified again after their first time (not in `total`)
+    pub fn duplicates(&self) -> u32 {
+        self.duplicates
+    }
+
+    /// Failed proofs counted by error kind, e.g. `{ Expired: 2, OutputHashMismatch: 1 }`
+    pub fn failures_by_error(&self) -> &BTreeMap<String, u32> {
+        &self.failures_by_error
+    }
+
+    /// When the session started (ms since the Unix epoch, verifier clock)
+    pub fn started_at(&self) -> u64 {
+        self.started_at
+    }
+
+    /// When the latest proof was recorded (`None` before the first)
+    pub fn last_verified_at(&self) -> Option<u64> {
+        self.last_verified_at
+    }
+
+    /// Time spent in checks, over all recorded proofs
+    pub fn total_ms(&self) -> f64 {
+        self.total_ms
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Outcomes recorded so far, by proof
+///
+/// Proofs are told apart by `ProofData::proof_id`. A proof seen before is
+/// only counted under `duplicates`, so tallies count distinct proofs.
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    seen: HashSet<String>,
+    summary: SessionSummary,
+}
+
+impl Session {
+    /// Empty session started at `now` (Unix ms)
+    pub fn new(now: u64) -> Session {
+        Session { seen: HashSet::new(), summary: SessionSummary { started_at: now, ..SessionSummary::default() } }
+    }
+
+    /// Record `report`'s outcome for `proof_data`, verified at `now`
+    pub fn record(&mut self, proof_data: &ProofData, report: &VerificationReport, now: u64) {
+        let summary = &mut self.summary;
+        summary.last_verified_at = Some(now);
+        summary.total_ms += report.total_ms();
+        if !self.seen.insert(proof_data.proof_id()) {
+            summary.duplicates += 1;
+        } else if report.passed() {
+            summary.passed += 1;
+        } else {
+            summary.failed += 1;
+            let kind = report.error().map_or("Unknown", |e| e.name());
+            *summary.failures_by_error.entry(kind.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Whether a proof with this `proof_id` was recorded
+    pub fn has_seen(&self, proof_id: &str) -> bool {
+        self.seen.contains(proof_id)
+    }
+
+    pub fn summary(&self) -> &SessionSummary {
+        &self.summary
+    }
+
+    /// Forget every recorded proof and start the tallies over at `now`
+    pub fn reset(&mut self, now: u64) {
+        *self = Session::new(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::Verifier;
+
+    #[test]
+    fn test_session_tallies() {
+        let verifier = Verifier::new("0xmodel".to_string());
+        let mut session = Session::new(verifier.now_ms());
+        let proof = fixtures::proof("0xmodel");
+        let verify = |session: &mut Session, proof: &ProofData, output: &[u8]| {
+            let report = verifier.verify_report(proof, b"in", output);
+            session.record(proof, &report, verifier.now_ms());
+            report.passed()
+        };
+
+        assert!(verify(&mut session, &proof, b"out"));
+        assert!(!verify(&mut session, &proof, b"tampered"));
+        assert!(session.has_seen(&proof.proof_id()));
+
+        let mut other = proof.clone();
+        other.nonce = Some("n1".to_string());
+        assert!(!verify(&mut session, &other, b"tampered"));
+
+        let summary = session.summary();
+        assert_eq!((summary.total(), summary.passed(), summary.failed(), summary.duplicates()), (2, 1, 1, 1));
+        assert_eq!(summary.failures_by_error, BTreeMap::from([("OutputHashMismatch".to_string(), 1)]));
+        assert!(summary.last_verified_at().unwrap() >= summary.started_at());
+
+        session.reset(verifier.now_ms());
+        assert_eq!(session.summary().total(), 0);
+        assert!(!session.has_seen(&proof.proof_id()));
+    }
+}
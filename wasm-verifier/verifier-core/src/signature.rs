@@ -0,0 +1,396 @@
+// Prover signatures
+// Ed25519 and secp256k1 signatures over the canonical proof payload (see `ProofData::signing_bytes`)
+
+use sha3::{Digest, Keccak256};
+
+pub use crate::error::Failure;
+use crate::error::VerificationError;
+use crate::crypto::{self, Check};
+use crate::{did, eip712, hex, hash, ProofData, Verifier};
+
+/// How a signature checks out, for `Verifier::check_prover_key`
+pub enum Signed<'a> {
+    /// Ed25519 signature (hex) to verify under the prover's key
+    Ed25519 { signature: &'a str, message: &'a [u8] },
+    /// Addresses the secp256k1 signature recovers to (one per plausible recovery id)
+    Recovered(Vec<String>),
+}
+
+/// Signature scheme named by `ProofData.signature_alg`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureAlgorithm {
+    /// Ed25519 over `signing_bytes()`; `signer_pubkey` is the 32-byte key
+    Ed25519,
+    /// Ethereum `personal_sign` (EIP-191) over `signing_bytes()`; 65-byte
+    /// `r || s || v` signature, `signer_pubkey` is the signer's address.
+    /// `"eip712"` names the same keys signing the proof as typed data
+    Secp256k1,
+}
+
+impl SignatureAlgorithm {
+    /// Parse a `signature_alg` name (missing = Ed25519)
+    pub fn from_name(name: Option<&str>) -> Option<SignatureAlgorithm> {
+        match name.map(|n| n.to_ascii_lowercase()).as_deref() {
+            None | Some("ed25519") | Some("eddsa") => Some(SignatureAlgorithm::Ed25519),
+            Some("secp256k1") | Some("es256k") | Some(eip712::EIP712) => Some(SignatureAlgorithm::Secp256k1),
+            _ => None,
+        }
+    }
+
+    /// Canonical `signature_alg` name
+    pub fn name(self) -> &'static str {
+        match self {
+            SignatureAlgorithm::Ed25519 => "ed25519",
+            SignatureAlgorithm::Secp256k1 => "secp256k1",
+        }
+    }
+}
+
+/// Verify an Ed25519 signature (64 bytes, hex) by `pubkey` (32 bytes, hex) over `message`
+///
+/// Strictly, as `verify_strict`; see `crypto::verify` for where it's checked.
+pub fn verify_ed25519(pubkey: &str, signature: &str, message: &[u8]) -> Result<(), Failure> {
+    let (key, signature) = parse_ed25519(pubkey, signature)?;
+    match crypto::verify(Check::ed25519(key, signature, message))? {
+        true => Ok(()),
+        false => Err((VerificationError::SignatureInvalid, "Proof signature does not verify")),
+    }
+}
+
+/// Decode a hex Ed25519 public key and signature, checking the key is a curve point
+pub fn parse_ed25519(pubkey: &str, signature: &str) -> Result<([u8; 32], [u8; 64]), Failure> {
+    let pubkey: [u8; 32] = hex::decode(pubkey)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or((VerificationError::SignatureInvalid, "signer_pubkey is not a 32-byte hex Ed25519 key"))?;
+    let signature: [u8; 64] = hex::decode(signature)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or((VerificationError::SignatureInvalid, "signature is not a 64-byte hex Ed25519 signature"))?;
+
+    if !crypto::ed25519_point(&pubkey) {
+        return Err((VerificationError::SignatureInvalid, "signer_pubkey is not a valid Ed25519 point"));
+    }
+    Ok((pubkey, signature))
+}
+
+/// Recover the Ethereum address that produced an EIP-191 `personal_sign` signature
+///
+/// `signature` is 65 bytes of hex (`r || s || v`, `v` in {0, 1, 27, 28}).
+/// High-s signatures are rejected, as on Ethereum since EIP-2.
+pub fn recover_eth_address(signature: &str, message: &[u8]) -> Result<String, Failure> {
+    recover_prehashed(signature, &Keccak256::digest(eip191_message(message)))
+}
+
+/// Recover the Ethereum address that signed a 32-byte `digest` (`r || s || v`, as above)
+pub fn recover_prehashed(signature: &str, digest: &[u8]) -> Result<String, Failure> {
+    let bytes = hex::decode(signature)
+        .filter(|bytes| bytes.len() == 65)
+        .ok_or((VerificationError::SignatureInvalid, "signature is not a 65-byte hex secp256k1 signature"))?;
+
+    let sig = k256::ecdsa::Signature::from_slice(&bytes[..64])
+        .map_err(|_| (VerificationError::SignatureInvalid, "signature is not a valid secp256k1 signature"))?;
+    if sig.normalize_s().is_some() {
+        return Err((VerificationError::SignatureInvalid, "signature has a non-canonical (high) s value"));
+    }
+    let v = match bytes[64] {
+        27 | 28 => bytes[64] - 27,
+        v => v,
+    };
+    let recovery_id = k256::ecdsa::RecoveryId::from_byte(v)
+        .ok_or((VerificationError::SignatureInvalid, "signature has an invalid recovery id"))?;
+
+    let key = k256::ecdsa::VerifyingKey::recover_from_prehash(digest, &sig, recovery_id)
+        .map_err(|_| (VerificationError::SignatureInvalid, "Proof signature does not verify"))?;
+    Ok(eth_address(&key))
+}
+
+/// Recover the Ethereum addresses that may have produced a JOSE/COSE
+/// `ES256K` signature (64-byte `r || s` over SHA-256 of `message`)
+///
+/// Without a recovery id both candidates are returned; the caller decides
+/// which one it expected.
+pub fn recover_es256k_addresses(signature: &[u8], message: &[u8]) -> Result<Vec<String>, Failure> {
+    let sig = k256::ecdsa::Signature::from_slice(signature)
+        .map_err(|_| (VerificationError::SignatureInvalid, "signature is not a 64-byte secp256k1 signature"))?;
+    if sig.normalize_s().is_some() {
+        return Err((VerificationError::SignatureInvalid, "signature has a non-canonical (high) s value"));
+    }
+
+    let digest = sha2::Sha256::digest(message);
+    let addresses: Vec<String> = [0, 1]
+        .into_iter()
+        .filter_map(k256::ecdsa::RecoveryId::from_byte)
+        .filter_map(|id| k256::ecdsa::VerifyingKey::recover_from_prehash(&digest, &sig, id).ok())
+        .map(|key| eth_address(&key))
+        .collect();
+    if addresses.is_empty() {
+        return Err((VerificationError::SignatureInvalid, "Proof signature does not verify"));
+    }
+    Ok(addresses)
+}
+
+/// `"\x19Ethereum Signed Message:\n" || len || message`
+fn eip191_message(message: &[u8]) -> Vec<u8> {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    prefixed
+}
+
+/// EIP-55 checksummed address of a secp256k1 public key
+pub fn eth_address(key: &k256::ecdsa::VerifyingKey) -> String {
+    let point = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+    to_checksum_address(&hash[12..])
+}
+
+/// EIP-55 mixed-case hex encoding of a 20-byte address
+pub fn to_checksum_address(address: &[u8]) -> String {
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+/// Whether `pubkey` is one of `trusted` (an empty list trusts no key)
+pub fn is_trusted(trusted: &[String], pubkey: &str) -> bool {
+    trusted.iter().any(|t| hash::digests_equal(t, pubkey))
+}
+
+impl ProofData {
+    /// Ethereum address that signed this proof (`signature_alg = "secp256k1"`)
+    ///
+    /// Fails if the proof is not secp256k1-signed or the signature is
+    /// malformed. This only recovers the address; `Verifier::verify_report`
+    /// decides whether it is trusted.
+    pub fn recover_signer_address(&self) -> Result<String, Failure> {
+        if SignatureAlgorithm::from_name(self.signature_alg.as_deref()) != Some(SignatureAlgorithm::Secp256k1) {
+            return Err((VerificationError::SignatureInvalid, "Proof is not secp256k1-signed"));
+        }
+        if eip712::is_eip712(self.signature_alg.as_deref()) {
+            return Err((VerificationError::SignatureInvalid, "EIP-712 signers depend on the verifier's domain"));
+        }
+        let signature = self.signature.as_deref().ok_or((VerificationError::SignatureInvalid, "Proof is not signed"))?;
+        recover_eth_address(signature, &self.signing_bytes())
+    }
+}
+
+impl Verifier {
+    /// Trust proofs signed by this Ed25519 public key or Ethereum address (hex)
+    ///
+    /// Signatures by any other key fail with `VerificationError::UntrustedSigner`.
+    /// With no trusted signers, `KeyResolver` or trust store, every signature
+    /// fails with `VerificationError::SignatureInvalid`: a proof carrying its
+    /// own `signer_pubkey` proves nothing about who signed it.
+    pub fn add_trusted_signer(&mut self, pubkey: String) {
+        self.trusted_signers.push(pubkey);
+    }
+
+    /// Verify the proof's signature and that the signer is trusted
+    pub fn check_signature(&self, proof_data: &ProofData) -> Result<(), Failure> {
+        let Some(sig) = &proof_data.signature else {
+            return Err((VerificationError::SignatureInvalid, "Proof is not signed"));
+        };
+        let message = proof_data.signing_bytes();
+        let algorithm = SignatureAlgorithm::from_name(proof_data.signature_alg.as_deref())
+            .ok_or((VerificationError::SignatureInvalid, "Unsupported signature_alg"))?;
+
+        let signed = match algorithm {
+            SignatureAlgorithm::Ed25519 => Signed::Ed25519 { signature: sig, message: &message },
+            SignatureAlgorithm::Secp256k1 if eip712::is_eip712(proof_data.signature_alg.as_deref()) => {
+                Signed::Recovered(vec![self.recover_eip712(proof_data, sig)?])
+            }
+            SignatureAlgorithm::Secp256k1 => Signed::Recovered(vec![recover_eth_address(sig, &message)?]),
+        };
+        self.check_prover_key(proof_data, proof_data.kid.as_deref(), algorithm, signed)
+    }
+
+    /// Check that a signature was made by a trusted key of the prover
+    ///
+    /// Shared by the in-proof signature and the COSE/JWS envelopes; `kid`
+    /// names the key in the `KeyResolver`, if any.
+    pub fn check_prover_key(
+        &self,
+        proof_data: &ProofData,
+        kid: Option<&str>,
+        algorithm: SignatureAlgorithm,
+        signed: Signed<'_>,
+    ) -> Result<(), Failure> {
+        let (candidates, pretrusted) = self.signer_keys(proof_data, kid, algorithm)?;
+        let is_trusted = |key: &str| pretrusted || is_trusted(&self.trusted_signers, key);
+
+        match signed {
+            Signed::Ed25519 { signature: sig, message } => {
+                // A DID may list several keys; any one of them may have signed
+                let mut outcome = Ok(());
+                for pubkey in &self.trusted_candidates(candidates, pretrusted)? {
+                    outcome = verify_ed25519(pubkey, sig, message);
+                    if outcome.is_ok() {
+                        break;
+                    }
+                }
+                outcome
+            }
+            Signed::Recovered(addresses) => {
+                let claimed: Vec<&String> = addresses
+                    .iter()
+                    .filter(|address| candidates.is_empty() || candidates.iter().any(|key| hash::digests_equal(key, address)))
+                    .collect();
+                if claimed.is_empty() {
+                    return Err((VerificationError::SignatureInvalid, "Signature was not made by signer_pubkey"));
+                }
+                if !claimed.iter().any(|address| is_trusted(address)) {
+                    return Err((VerificationError::UntrustedSigner, "Proof was signed by an untrusted address"));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The trusted keys among an Ed25519 signature's candidates, failing if there are none
+    pub fn trusted_candidates(
+        &self,
+        candidates: Vec<String>,
+        pretrusted: bool,
+    ) -> Result<Vec<String>, Failure> {
+        if candidates.is_empty() {
+            return Err((VerificationError::SignatureInvalid, "Proof has no signer_pubkey"));
+        }
+        let trusted: Vec<String> = candidates
+            .into_iter()
+            .filter(|key| pretrusted || is_trusted(&self.trusted_signers, key))
+            .collect();
+        if trusted.is_empty() {
+            return Err((VerificationError::UntrustedSigner, "Proof was signed by an untrusted key"));
+        }
+        Ok(trusted)
+    }
+
+    /// Whether any signer can be trusted: a trusted key or DID, a `KeyResolver`
+    /// or an X.509 trust store (whose chain check then vouches for the key)
+    pub fn has_signer_trust(&self) -> bool {
+        !self.trusted_signers.is_empty() || self.key_resolver.is_some() || !self.trusted_roots.is_empty()
+    }
+
+    /// Keys the proof signature may verify under, and whether they are
+    /// trusted by virtue of how they were resolved
+    ///
+    /// `prover_did` takes precedence over `kid`. Keys from the `KeyResolver`
+    /// are always trusted; DID keys are trusted if the DID itself was added
+    /// with `add_trusted_signer`. A `signer_pubkey` must be one of the
+    /// resolved keys.
+    pub fn signer_keys(
+        &self,
+        proof_data: &ProofData,
+        kid: Option<&str>,
+        algorithm: SignatureAlgorithm,
+    ) -> Result<(Vec<String>, bool), Failure> {
+        if !self.has_signer_trust() {
+            return Err((VerificationError::SignatureInvalid, "No trusted signers are configured"));
+        }
+        let (resolved, pretrusted) = if let Some(did) = &proof_data.prover_did {
+            let keys = did::resolve(did, algorithm, self.did_fetcher.as_ref())?;
+            let (base, _) = did::split_fragment(did);
+            (Some(keys), self.trusted_signers.iter().any(|t| t == base))
+        } else if let (Some(kid), Some(resolver)) = (kid, &self.key_resolver) {
+            (Some(vec![resolver.resolve(kid, algorithm)?]), true)
+        } else {
+            (None, false)
+        };
+
+        match (resolved, &proof_data.signer_pubkey) {
+            (Some(keys), Some(claimed)) => {
+                if !keys.iter().any(|key| hash::digests_equal(key, claimed)) {
+                    return Err((VerificationError::SignatureInvalid, "signer_pubkey is not a key of the prover_did or kid"));
+                }
+                Ok((vec![claimed.clone()], pretrusted))
+            }
+            (Some(keys), None) => Ok((keys, pretrusted)),
+            (None, claimed) => Ok((claimed.iter().cloned().collect(), false)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "rust-signatures")]
+    use ed25519_dalek::{Signer, SigningKey};
+    #[cfg(feature = "rust-signatures")]
+    use crate::fixtures;
+
+    #[test]
+    #[cfg(feature = "rust-signatures")]
+    fn test_verify_ed25519() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let pubkey = hex::encode(key.verifying_key().as_bytes());
+        let signature = hex::encode(&key.sign(b"payload").to_bytes());
+
+        assert!(verify_ed25519(&pubkey, &signature, b"payload").is_ok());
+        assert!(verify_ed25519(&pubkey, &signature, b"tampered").is_err());
+        assert!(verify_ed25519("0x00", &signature, b"payload").is_err());
+
+        assert!(!is_trusted(&[], &pubkey));
+        assert!(is_trusted(&[format!("0x{}", pubkey.to_uppercase())], &pubkey));
+        assert!(!is_trusted(&["0xabcd".to_string()], &pubkey));
+    }
+
+    /// A proof signed by `key`, carrying that key as its `signer_pubkey`
+    #[cfg(feature = "rust-signatures")]
+    fn signed_proof(model_hash: &str, key: &SigningKey) -> ProofData {
+        let mut proof = fixtures::proof(model_hash);
+        proof.signer_pubkey = Some(hex::encode(key.verifying_key().as_bytes()));
+        proof.signature = Some(hex::encode(&key.sign(&proof.signing_bytes()).to_bytes()));
+        proof
+    }
+
+    #[test]
+    #[cfg(feature = "rust-signatures")]
+    fn test_self_signed_forgery_is_rejected() {
+        let model_hash = "0x1234567890abcdef";
+        let attacker = SigningKey::from_bytes(&[9u8; 32]);
+        let forged = signed_proof(model_hash, &attacker);
+
+        // No trust configured: the signature verifies, but proves nothing
+        let mut config = crate::VerifierConfig::default();
+        config.set_require_signature(true);
+        let verifier = Verifier::with_config(model_hash.to_string(), &config);
+        let report = verifier.verify_report(&forged, b"in", b"out");
+        assert!(!report.passed());
+        assert_eq!(report.error(), Some(VerificationError::SignatureInvalid));
+        assert_eq!(verifier.check_signature(&forged).unwrap_err().0, VerificationError::SignatureInvalid);
+
+        // Trusting someone else's key: the attacker's is untrusted
+        let mut verifier = Verifier::with_config(model_hash.to_string(), &config);
+        let prover = SigningKey::from_bytes(&[7u8; 32]);
+        verifier.add_trusted_signer(hex::encode(prover.verifying_key().as_bytes()));
+        assert_eq!(verifier.check_signature(&forged).unwrap_err().0, VerificationError::UntrustedSigner);
+        assert!(verifier.verify_report(&signed_proof(model_hash, &prover), b"in", b"out").passed());
+
+        // Even unrequired, a signed proof is checked and fails closed
+        let verifier = Verifier::new(model_hash.to_string());
+        assert!(!verifier.verify_report(&forged, b"in", b"out").passed());
+    }
+
+    #[test]
+    fn test_recover_eth_address() {
+        // EIP-55 reference vector
+        let address = hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+        assert_eq!(to_checksum_address(&address), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+
+        let key = k256::ecdsa::SigningKey::from_slice(&[3u8; 32]).unwrap();
+        let digest = Keccak256::digest(eip191_message(b"payload"));
+        let (sig, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+        let mut bytes = sig.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte() + 27);
+
+        let recovered = recover_eth_address(&hex::encode(&bytes), b"payload").unwrap();
+        assert_eq!(recovered, eth_address(key.verifying_key()));
+        assert_ne!(recover_eth_address(&hex::encode(&bytes), b"tampered").ok(), Some(recovered));
+    }
+}
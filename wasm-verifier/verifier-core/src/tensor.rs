@@ -0,0 +1,400 @@
+// Typed tensor commitments
+// Canonical little-endian, row-major encoding of a tensor with its shape and dtype
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::VerificationError;
+use crate::hash::IoSource;
+use crate::{ProofData, VerificationReport, Verifier};
+
+/// Domain separator for tensor encodings (format version 1)
+pub const TENSOR_DOMAIN: &[u8] = b"zkml/tensor/v1\0";
+
+/// Canonical quiet NaN, so every platform's NaN payload hashes the same
+const CANONICAL_NAN: u32 = 0x7fc0_0000;
+
+/// Element type of a tensor, named as in ONNX
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub enum DType {
+    Float32,
+    Float64,
+    Float16,
+    Int8,
+    Uint8,
+    Int16,
+    Int32,
+    Int64,
+    Bool,
+}
+
+impl DType {
+    /// ONNX name of the type, as written into the encoding
+    pub fn name(self) -> &'static str {
+        match self {
+            DType::Float32 => "float32",
+            DType::Float64 => "float64",
+            DType::Float16 => "float16",
+            DType::Int8 => "int8",
+            DType::Uint8 => "uint8",
+            DType::Int16 => "int16",
+            DType::Int32 => "int32",
+            DType::Int64 => "int64",
+            DType::Bool => "bool",
+        }
+    }
+
+    /// Parse an ONNX type name, e.g. `"float32"`
+    pub fn from_name(name: &str) -> Option<DType> {
+        const ALL: [DType; 9] = [
+            DType::Float32,
+            DType::Float64,
+            DType::Float16,
+            DType::Int8,
+            DType::Uint8,
+            DType::Int16,
+            DType::Int32,
+            DType::Int64,
+            DType::Bool,
+        ];
+        ALL.into_iter().find(|dtype| dtype.name() == name.to_ascii_lowercase())
+    }
+
+    /// Size of one element in bytes
+    pub fn size(self) -> usize {
+        match self {
+            DType::Int8 | DType::Uint8 | DType::Bool => 1,
+            DType::Float16 | DType::Int16 => 2,
+            DType::Float32 | DType::Int32 => 4,
+            DType::Float64 | DType::Int64 => 8,
+        }
+    }
+}
+
+/// Number of elements of `shape` (1 for a scalar), or `None` on overflow
+pub fn element_count(shape: &[u32]) -> Option<usize> {
+    shape.iter().try_fold(1usize, |n, &d| n.checked_mul(d as usize))
+}
+
+/// Canonical encoding of a tensor whose elements are already little-endian
+///
+/// `domain || u32 len || dtype name || u32 rank || u64 dims... || data`,
+/// all integers little-endian. Fails if `data` doesn't hold exactly the
+/// elements `shape` calls for.
+pub fn encode(dtype: DType, shape: &[u32], data: &[u8]) -> Result<Vec<u8>, VerificationError> {
+    if data_size(dtype, shape) != Some(data.len()) {
+        return Err(VerificationError::InvalidArgument);
+    }
+    let mut out = header(dtype, shape);
+    out.extend_from_slice(data);
+    Ok(out)
+}
+
+/// Byte size of the elements of a `dtype` tensor of `shape`
+pub fn data_size(dtype: DType, shape: &[u32]) -> Option<usize> {
+    element_count(shape).and_then(|n| n.checked_mul(dtype.size()))
+}
+
+/// Everything in the canonical encoding that precedes the element data
+pub fn header(dtype: DType, shape: &[u32]) -> Vec<u8> {
+    let name = dtype.name().as_bytes();
+    let mut out = Vec::with_capacity(TENSOR_DOMAIN.len() + 8 + name.len() + 8 * shape.len());
+    out.extend_from_slice(TENSOR_DOMAIN);
+    out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+    out.extend_from_slice(name);
+    out.extend_from_slice(&(shape.len() as u32).to_le_bytes());
+    shape.iter().for_each(|&d| out.extend_from_slice(&(d as u64).to_le_bytes()));
+    out
+}
+
+/// Canonical encoding of an f32 tensor (NaNs normalized to one bit pattern)
+pub fn encode_f32(data: &[f32], shape: &[u32]) -> Result<Vec<u8>, VerificationError> {
+    let bytes: Vec<u8> = data
+        .iter()
+        .flat_map(|v| if v.is_nan() { CANONICAL_NAN } else { v.to_bits() }.to_le_bytes())
+        .collect();
+    encode(DType::Float32, shape, &bytes)
+}
+
+/// Declared shape, dtype and (optionally) memory layout of a model input or output
+///
+/// The compact form, used by the binary proof format, is `dtype[d0,d1,...]`
+/// with an optional `@layout` suffix, e.g. `float32[1,3,224,224]@NCHW`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TensorSpec {
+    shape: Vec<u32>,
+    dtype: DType,
+    /// One letter per dimension, e.g. `NCHW` or `NHWC`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    layout: Option<String>,
+}
+
+impl TensorSpec {
+    pub fn new(shape: Vec<u32>, dtype: DType, layout: Option<String>) -> TensorSpec {
+        TensorSpec { shape, dtype, layout }
+    }
+
+    pub fn shape(&self) -> &[u32] {
+        &self.shape
+    }
+
+    pub fn dtype(&self) -> DType {
+        self.dtype
+    }
+
+    pub fn layout(&self) -> Option<&str> {
+        self.layout.as_deref()
+    }
+
+    /// Size in bytes of a raw buffer holding this tensor
+    pub fn byte_length(&self) -> Option<u64> {
+        data_size(self.dtype, &self.shape).map(|n| n as u64)
+    }
+
+    /// Compact `dtype[dims]@layout` form
+    pub fn to_compact(&self) -> String {
+        let dims: Vec<String> = self.shape.iter().map(u32::to_string).collect();
+        match &self.layout {
+            Some(layout) => format!("{}[{}]@{}", self.dtype.name(), dims.join(","), layout),
+            None => format!("{}[{}]", self.dtype.name(), dims.join(",")),
+        }
+    }
+
+    pub fn from_compact(s: &str) -> Option<TensorSpec> {
+        let (spec, layout) = match s.split_once('@') {
+            Some((spec, layout)) => (spec, Some(layout.to_string())),
+            None => (s, None),
+        };
+        let (dtype, dims) = spec.strip_suffix(']')?.split_once('[')?;
+        let shape = if dims.is_empty() {
+            Vec::new()
+        } else {
+            dims.split(',').map(|d| d.parse().ok()).collect::<Option<_>>()?
+        };
+        Some(TensorSpec { shape, dtype: DType::from_name(dtype)?, layout })
+    }
+
+    /// Check that `source` holds exactly this tensor, as raw little-endian
+    /// elements or in the canonical encoding of `encode_tensor_f32`/`hash_tensor`
+    pub fn check_source(&self, source: &IoSource<'_>) -> Result<(), String> {
+        if let Some(layout) = &self.layout {
+            if layout.chars().count() != self.shape.len() {
+                return Err(format!("layout {} does not match the {}-d shape", layout, self.shape.len()));
+            }
+        }
+        let Some(size) = data_size(self.dtype, &self.shape) else {
+            return Err("shape is too large".to_string());
+        };
+        let header = header(self.dtype, &self.shape);
+        let len = source.len();
+        let canonical = (header.len() + size) as u64;
+        let matches = match source.bytes() {
+            Some(data) if data.starts_with(TENSOR_DOMAIN) && !data.starts_with(&header) => {
+                return Err(format!("encodes a different shape or dtype than {}", self.to_compact()));
+            }
+            Some(data) if data.starts_with(TENSOR_DOMAIN) => len == canonical,
+            _ => len == size as u64 || len == canonical,
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(format!("buffer is {} bytes, but {} needs {}", len, self.to_compact(), size))
+        }
+    }
+}
+
+/// Element bytes of a buffer matching `spec`, raw or canonically encoded
+pub fn element_bytes<'a>(spec: &TensorSpec, data: &'a [u8]) -> Option<&'a [u8]> {
+    spec.check_source(&IoSource::Bytes(data)).ok()?;
+    let size = data_size(spec.dtype, &spec.shape)?;
+    Some(&data[data.len() - size..])
+}
+
+/// Elements of a float32 buffer matching `spec`, raw or canonically encoded
+pub fn f32_elements(spec: &TensorSpec, data: &[u8]) -> Option<Vec<f32>> {
+    if spec.dtype != DType::Float32 {
+        return None;
+    }
+    let elements = element_bytes(spec, data)?;
+    Some(elements.chunks_exact(4).map(|b| f32::from_le_bytes(b.try_into().expect("4-byte chunk"))).collect())
+}
+
+/// IEEE-754 half precision to f64
+pub fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits >> 15 == 1 { -1.0 } else { 1.0 };
+    let (exp, mantissa) = (i32::from((bits >> 10) & 0x1f), f64::from(bits & 0x3ff));
+    match exp {
+        0 => sign * mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => sign * f64::INFINITY,
+        31 => f64::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f64.powi(exp - 15),
+    }
+}
+
+/// Elements of any buffer matching `spec`, widened to f64 (int64 beyond 2^53 loses precision)
+pub fn numeric_elements(spec: &TensorSpec, data: &[u8]) -> Option<Vec<f64>> {
+    let elements = element_bytes(spec, data)?;
+    let values = elements.chunks_exact(spec.dtype.size()).map(|b| match spec.dtype {
+        DType::Float32 => f64::from(f32::from_le_bytes(b.try_into().expect("4-byte chunk"))),
+        DType::Float64 => f64::from_le_bytes(b.try_into().expect("8-byte chunk")),
+        DType::Float16 => f16_to_f64(u16::from_le_bytes(b.try_into().expect("2-byte chunk"))),
+        DType::Int8 => f64::from(b[0] as i8),
+        DType::Uint8 | DType::Bool => f64::from(b[0]),
+        DType::Int16 => f64::from(i16::from_le_bytes(b.try_into().expect("2-byte chunk"))),
+        DType::Int32 => f64::from(i32::from_le_bytes(b.try_into().expect("4-byte chunk"))),
+        DType::Int64 => i64::from_le_bytes(b.try_into().expect("8-byte chunk")) as f64,
+    });
+    Some(values.collect())
+}
+
+/// Elements of a typed array, with the dtype the array type implies
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedElements {
+    Float32(Vec<f32>),
+    Int8(Vec<i8>),
+    Int32(Vec<i32>),
+}
+
+impl TypedElements {
+    pub fn dtype(&self) -> DType {
+        match self {
+            TypedElements::Float32(_) => DType::Float32,
+            TypedElements::Int8(_) => DType::Int8,
+            TypedElements::Int32(_) => DType::Int32,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            TypedElements::Float32(values) => values.len(),
+            TypedElements::Int8(values) => values.len(),
+            TypedElements::Int32(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Canonical encoding with `shape`, or as a 1-d tensor when `shape` is `None`
+    pub fn encode(&self, shape: Option<&[u32]>) -> Result<Vec<u8>, VerificationError> {
+        let flat = [u32::try_from(self.len()).map_err(|_| VerificationError::InvalidArgument)?];
+        let shape = shape.unwrap_or(&flat);
+        match self {
+            TypedElements::Float32(values) => encode_f32(values, shape),
+            TypedElements::Int8(values) => {
+                let bytes: Vec<u8> = values.iter().map(|&v| v as u8).collect();
+                encode(DType::Int8, shape, &bytes)
+            }
+            TypedElements::Int32(values) => {
+                let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+                encode(DType::Int32, shape, &bytes)
+            }
+        }
+    }
+
+    /// Canonical encoding in the shape `spec` declares, if it fits the elements
+    ///
+    /// Falls back to a 1-d tensor otherwise, so the spec check reports the mismatch.
+    pub fn encode_for(&self, spec: Option<&TensorSpec>) -> Result<Vec<u8>, VerificationError> {
+        let shape = spec
+            .filter(|spec| spec.dtype() == self.dtype() && element_count(spec.shape()) == Some(self.len()))
+            .map(|spec| spec.shape());
+        self.encode(shape)
+    }
+}
+
+impl Verifier {
+    /// Verify a proof whose I/O commitments are tensor hashes, given the elements themselves
+    ///
+    /// Each side is encoded in the shape of the proof's `input_spec`/`output_spec`,
+    /// or as a 1-d tensor when the proof declares none.
+    pub fn tensors_report(
+        &self,
+        proof_data: &ProofData,
+        input: &TypedElements,
+        output: &TypedElements,
+    ) -> Result<VerificationReport, VerificationError> {
+        let input = input.encode_for(proof_data.input_spec.as_ref())?;
+        let output = output.encode_for(proof_data.output_spec.as_ref())?;
+        Ok(self.verify_report(proof_data, &input, &output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_tensor_encoding() {
+        let values = [1.0f32, -2.5, 0.0, 4.0, 5.0, 6.0];
+        let encoded = encode_f32(&values, &[2, 3]).unwrap();
+        let raw: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(encoded, encode(DType::Float32, &[2, 3], &raw).unwrap());
+        assert!(encoded.ends_with(&raw));
+
+        // Same values, different shape: different commitment
+        assert_ne!(encoded, encode_f32(&values, &[3, 2]).unwrap());
+        assert_eq!(encode_f32(&values[..5], &[2, 3]), Err(VerificationError::InvalidArgument));
+        assert_eq!(encode(DType::Int64, &[2, 3], &raw), Err(VerificationError::InvalidArgument));
+
+        let nan = f32::from_bits(0x7fc0_0001);
+        assert_eq!(encode_f32(&[nan], &[1]).unwrap(), encode_f32(&[f32::NAN], &[1]).unwrap());
+    }
+
+    #[test]
+    fn test_tensor_spec() {
+        let spec = TensorSpec::new(vec![1, 3, 2, 2], DType::Float32, Some("NCHW".into()));
+        assert_eq!(spec.to_compact(), "float32[1,3,2,2]@NCHW");
+        assert_eq!(TensorSpec::from_compact(&spec.to_compact()), Some(spec.clone()));
+        assert_eq!(TensorSpec::from_compact("int64[]").unwrap().shape(), &[] as &[u32]);
+
+        let values = [0.5f32; 12];
+        let raw: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let canonical = encode_f32(&values, &[1, 3, 2, 2]).unwrap();
+        assert!(spec.check_source(&IoSource::Bytes(&raw)).is_ok());
+        assert!(spec.check_source(&IoSource::Bytes(&canonical)).is_ok());
+        assert!(spec.check_source(&IoSource::Bytes(&raw[..44])).unwrap_err().contains("44 bytes"));
+        // Right size, wrong shape in the header
+        assert!(spec.check_source(&IoSource::Bytes(&encode_f32(&values, &[3, 4]).unwrap())).is_err());
+    }
+
+    #[test]
+    fn test_typed_elements() {
+        let ints = TypedElements::Int32(vec![1, -2, 3, 4]);
+        let raw: Vec<u8> = [1i32, -2, 3, 4].iter().flat_map(|v| v.to_le_bytes()).collect();
+        assert_eq!(ints.encode(Some(&[2, 2])).unwrap(), encode(DType::Int32, &[2, 2], &raw).unwrap());
+        assert_eq!(ints.encode(None).unwrap(), encode(DType::Int32, &[4], &raw).unwrap());
+        assert_eq!(ints.encode(Some(&[3])), Err(VerificationError::InvalidArgument));
+
+        let floats = TypedElements::Float32(vec![0.5, f32::NAN]);
+        assert_eq!(floats.encode(None).unwrap(), encode_f32(&[0.5, f32::NAN], &[2]).unwrap());
+        assert_eq!(TypedElements::Int8(vec![-1]).encode(None).unwrap(), encode(DType::Int8, &[1], &[0xff]).unwrap());
+
+        // Spec shape is used when it fits; otherwise the spec check reports the mismatch
+        let spec = TensorSpec::new(vec![2, 2], DType::Int32, None);
+        assert_eq!(ints.encode_for(Some(&spec)).unwrap(), ints.encode(Some(&[2, 2])).unwrap());
+        let floats = TypedElements::Float32(vec![0.0; 4]).encode_for(Some(&spec)).unwrap();
+        assert!(spec.check_source(&IoSource::Bytes(&floats)).unwrap_err().contains("different shape or dtype"));
+    }
+
+    #[test]
+    fn test_tensors_report() {
+        use crate::hash::HashAlgorithm;
+
+        let input = TypedElements::Float32(vec![0.25; 6]);
+        let output = TypedElements::Int8(vec![3, -3]);
+        let (input_bytes, output_bytes) = (input.encode(Some(&[2, 3])).unwrap(), output.encode(None).unwrap());
+        let mut proof = fixtures::proof_for("0xmodel", &input_bytes, &output_bytes, HashAlgorithm::Sha3_256);
+        proof.input_spec = Some(TensorSpec::new(vec![2, 3], DType::Float32, None));
+
+        let verifier = Verifier::new("0xmodel".to_string());
+        assert!(verifier.tensors_report(&proof, &input, &output).unwrap().passed());
+        let swapped = TypedElements::Int8(vec![-3, 3]);
+        assert!(!verifier.tensors_report(&proof, &input, &swapped).unwrap().check_passed(crate::Check::OutputHash));
+    }
+}
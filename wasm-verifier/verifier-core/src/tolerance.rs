@@ -0,0 +1,141 @@
+// Tolerance-based output verification
+// Proofs commit to the quantized output; float outputs are re-quantized and compared within epsilon
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::IoSource;
+use crate::report::{Check, Stopwatch, VerificationReport};
+use crate::{ProofData, Verifier};
+
+/// Affine per-tensor quantization of outputs, and how far re-quantized floats may drift
+///
+/// `q = clamp(round(x / scale) + zero_point)` as a signed `bit_width`-bit
+/// integer; the committed output is these integers, little-endian.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct OutputTolerance {
+    scale: f32,
+    zero_point: i32,
+    bit_width: u32,
+    epsilon: u32,
+}
+
+impl OutputTolerance {
+    /// `epsilon` is the allowed difference in quantization steps
+    pub fn new(scale: f32, zero_point: i32, bit_width: u32, epsilon: u32) -> OutputTolerance {
+        OutputTolerance { scale, zero_point, bit_width, epsilon }
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    pub fn zero_point(&self) -> i32 {
+        self.zero_point
+    }
+
+    pub fn bit_width(&self) -> u32 {
+        self.bit_width
+    }
+
+    pub fn epsilon(&self) -> u32 {
+        self.epsilon
+    }
+
+    fn width(&self) -> Option<usize> {
+        matches!(self.bit_width, 8 | 16 | 32).then_some(self.bit_width as usize / 8)
+    }
+
+    fn quantize(&self, x: f32) -> Option<i64> {
+        if !(x.is_finite() && self.scale.is_finite() && self.scale > 0.0) {
+            return None;
+        }
+        let max = (1i64 << (self.bit_width - 1)) - 1;
+        let q = (f64::from(x) / f64::from(self.scale)).round() as i64 + i64::from(self.zero_point);
+        Some(q.clamp(-max - 1, max))
+    }
+
+    /// Quantized encoding of a float output, as provers commit to it in `output_hash`
+    ///
+    /// `None` for unsupported bit widths or non-finite values.
+    pub fn encode(&self, values: &[f32]) -> Option<Vec<u8>> {
+        let width = self.width()?;
+        let mut out = Vec::with_capacity(values.len() * width);
+        for &x in values {
+            out.extend_from_slice(&self.quantize(x)?.to_le_bytes()[..width]);
+        }
+        Some(out)
+    }
+
+    /// Check `output` re-quantizes to within `epsilon` steps of `quantized`
+    pub fn check(&self, output: &[f32], quantized: &[u8]) -> Result<(), String> {
+        let width = self.width().ok_or("Output tolerance bit_width must be 8, 16 or 32")?;
+        if quantized.len() != output.len() * width {
+            return Err(format!("Quantized output has {} bytes, expected {}", quantized.len(), output.len() * width));
+        }
+        for (i, (&x, committed)) in output.iter().zip(quantized.chunks_exact(width)).enumerate() {
+            let mut bytes = [if committed[width - 1] & 0x80 != 0 { 0xff } else { 0 }; 8];
+            bytes[..width].copy_from_slice(committed);
+            let committed = i64::from_le_bytes(bytes);
+            match self.quantize(x) {
+                Some(q) if q.abs_diff(committed) <= u64::from(self.epsilon) => {}
+                Some(q) => return Err(format!("Output element {} re-quantizes to {}, committed {}", i, q, committed)),
+                None => return Err(format!("Output element {} cannot be quantized", i)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Verifier {
+    /// Accept float outputs within `tolerance` of the committed quantized output (see `tolerant_report`)
+    pub fn set_output_tolerance(&mut self, tolerance: OutputTolerance) {
+        self.output_tolerance = Some(tolerance);
+    }
+
+    /// Verify a proof committing to a quantized output, given the float output observed
+    ///
+    /// `output_hash` is checked against `quantized_output` exactly; `output`
+    /// (e.g. computed on other hardware) must re-quantize to within the
+    /// configured epsilon of it, element by element.
+    pub fn tolerant_report(
+        &self,
+        proof_data: &ProofData,
+        input: &[u8],
+        output: &[f32],
+        quantized_output: &[u8],
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(&mut report, proof_data, IoSource::Bytes(input), IoSource::Bytes(quantized_output));
+        let started = Stopwatch::start();
+        let result = match &self.output_tolerance {
+            Some(tolerance) => tolerance.check(output, quantized_output),
+            None => Err("Verifier has no output tolerance configured".to_string()),
+        };
+        report.record(Check::OutputTolerance, result.is_ok(), &result.err().unwrap_or_default(), started);
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use crate::hash::HashAlgorithm;
+    use crate::VerificationError;
+
+    #[test]
+    fn test_verify_tolerant() {
+        let tolerance = OutputTolerance::new(0.01, 0, 16, 1);
+        let committed = tolerance.encode(&[0.5, -1.25, 3.0]).unwrap();
+        assert_eq!(committed, [50, 0, 0x83, 0xff, 0x2c, 0x01]);
+
+        let mut verifier = Verifier::new("0xmodel".to_string());
+        verifier.set_output_tolerance(tolerance);
+        let proof = fixtures::proof_for("0xmodel", b"in", &committed, HashAlgorithm::default());
+        // Off by under a step on another GPU
+        assert!(verifier.tolerant_report(&proof, b"in", &[0.5001, -1.2549, 3.006], &committed).passed());
+        let report = verifier.tolerant_report(&proof, b"in", &[0.5, -1.25, 3.03], &committed);
+        assert_eq!(report.error(), Some(VerificationError::ToleranceExceeded));
+        assert_eq!(report.failure_reasons(), ["Output element 2 re-quantizes to 303, committed 300"]);
+    }
+}
@@ -0,0 +1,28 @@
+// Tracing
+// A `tracing` span per check (feature `tracing`), opened and closed by `VerificationReport`
+
+use crate::report::{Check, CheckResult};
+
+/// Span for `check`, opened when it's announced and closed once its outcome is recorded
+///
+/// Created under whatever span is current, so hosts can wrap a verification in their own.
+pub(crate) fn check_span(check: Check) -> tracing::Span {
+    tracing::debug_span!("check", check = ?check)
+}
+
+/// Log `result` inside its check's span: passes at DEBUG, failures at WARN
+///
+/// Failures name the check themselves, since the span is filtered out below DEBUG.
+pub(crate) fn record(span: &tracing::Span, result: &CheckResult) {
+    let _entered = span.enter();
+    match (result.error(), result.reason()) {
+        (Some(error), reason) => tracing::warn!(
+            check = ?result.check(),
+            error = error.name(),
+            reason = %reason.unwrap_or_default(),
+            duration_ms = result.duration_ms(),
+            "failed"
+        ),
+        (None, _) => tracing::debug!(duration_ms = result.duration_ms(), "passed"),
+    }
+}
@@ -0,0 +1,178 @@
+// Verifiable Credential export
+// Issues W3C VC 2.0 documents (eddsa-jcs-2022 Data Integrity proofs) attesting to a verification result
+
+#[cfg(feature = "rust-signatures")]
+use ed25519_dalek::{Signer, SigningKey};
+#[cfg(feature = "rust-signatures")]
+use serde_json::{json, Value};
+#[cfg(feature = "rust-signatures")]
+use sha2::{Digest, Sha256};
+
+use crate::did;
+#[cfg(feature = "rust-signatures")]
+use crate::error::VerificationError;
+#[cfg(feature = "rust-signatures")]
+use crate::signature::Failure;
+#[cfg(feature = "rust-signatures")]
+use crate::{hex, jcs, ProofData, VerificationReport, Verifier};
+
+#[cfg(feature = "rust-signatures")]
+const CREDENTIALS_V2: &str = "https://www.w3.org/ns/credentials/v2";
+#[cfg(feature = "rust-signatures")]
+const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+
+/// `did:key` of an Ed25519 public key (`z` + base58btc of `0xed01 || key`)
+pub fn did_key(key: &[u8; 32]) -> String {
+    let multikey = [&did::ED25519_PUB[..], key].concat();
+    format!("did:key:z{}", bs58::encode(multikey).into_string())
+}
+
+/// RFC 3339 UTC timestamp (`2024-01-31T12:00:00Z`) for Unix milliseconds
+pub fn iso8601(ms: u64) -> String {
+    let secs = ms / 1000;
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil-from-days (Howard Hinnant's algorithm), valid for all u64 dates we produce
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// Hash an eddsa-jcs-2022 proof configuration and document for signing
+#[cfg(feature = "rust-signatures")]
+fn signing_hash(proof_config: &Value, document: &Value) -> Vec<u8> {
+    let mut hash = Sha256::digest(jcs::canonicalize(proof_config)).to_vec();
+    hash.extend_from_slice(&Sha256::digest(jcs::canonicalize(document)));
+    hash
+}
+
+#[cfg(feature = "rust-signatures")]
+impl Verifier {
+    /// Set the Ed25519 secret key (32 bytes, hex) credentials and receipts are issued with
+    ///
+    /// The issuer is identified as the key's `did:key`.
+    pub fn set_issuer_key(&mut self, secret_key: &str) -> Result<(), VerificationError> {
+        let secret: [u8; 32] = hex::decode(secret_key)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(VerificationError::InvalidArgument)?;
+        self.issuer_key = Some(SigningKey::from_bytes(&secret));
+        Ok(())
+    }
+
+    /// Build the signed credential for a passed verification of `proof_data`
+    pub fn credential(
+        &self,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+        now: u64,
+    ) -> Result<Value, Failure> {
+        let issuer = self
+            .issuer_key
+            .as_ref()
+            .ok_or((VerificationError::InvalidArgument, "No issuer key set; call set_issuer_key first"))?;
+        if !report.passed() {
+            return Err((VerificationError::InvalidArgument, "Only passed verifications can be issued as credentials"));
+        }
+
+        let did = did_key(issuer.verifying_key().as_bytes());
+        let created = iso8601(now);
+        let checks: Vec<Value> = report.checks().iter().map(|c| json!(c.check())).collect();
+        let mut document = json!({
+            "@context": [CREDENTIALS_V2],
+            "type": ["VerifiableCredential", "ZkmlVerificationCredential"],
+            "issuer": did,
+            "validFrom": created,
+            "credentialSubject": {
+                "type": "ZkmlInference",
+                "modelHash": proof_data.model_hash,
+                "inputHash": proof_data.input_hash,
+                "outputHash": proof_data.output_hash,
+                "proofHash": proof_data.proof_hash,
+                "proofCreated": iso8601(proof_data.timestamp),
+                "checks": checks,
+            },
+        });
+
+        let mut proof = json!({
+            "@context": [CREDENTIALS_V2],
+            "type": "DataIntegrityProof",
+            "cryptosuite": CRYPTOSUITE,
+            "created": created,
+            "verificationMethod": format!("{did}#{}", did.trim_start_matches("did:key:")),
+            "proofPurpose": "assertionMethod",
+        });
+        let signature = issuer.sign(&signing_hash(&proof, &document));
+        proof["proofValue"] = json!(format!("z{}", bs58::encode(signature.to_bytes()).into_string()));
+        document["proof"] = proof;
+        Ok(document)
+    }
+
+    /// `did:key` of the credential issuer (`None` until `set_issuer_key`)
+    pub fn issuer_did(&self) -> Option<String> {
+        self.issuer_key.as_ref().map(|key| did_key(key.verifying_key().as_bytes()))
+    }
+
+    /// Export a passed verification as a signed W3C Verifiable Credential (JSON-LD)
+    ///
+    /// The credential asserts that the proof's model produced `output_hash`
+    /// for `input_hash` at the proof timestamp, and that this verifier
+    /// checked it. Fails with `InvalidArgument` if the report did not pass
+    /// or no issuer key is set.
+    pub fn to_verifiable_credential(
+        &self,
+        proof_data: &ProofData,
+        report: &VerificationReport,
+    ) -> Result<String, Failure> {
+        self.credential(proof_data, report, self.now_ms()).map(|vc| vc.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso8601() {
+        assert_eq!(iso8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(iso8601(951_782_400_000), "2000-02-29T00:00:00Z");
+        assert_eq!(iso8601(1_700_000_000_123), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    #[cfg(feature = "rust-signatures")]
+    fn test_verifiable_credential() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let mut verifier = Verifier::new(model_hash.clone());
+        verifier.issuer_key = Some(SigningKey::from_bytes(&[4u8; 32]));
+
+        let proof = crate::fixtures::proof(&model_hash);
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        let mut vc = verifier.credential(&proof, &report, 1_700_000_000_000).unwrap();
+        assert_eq!(vc["credentialSubject"]["modelHash"], model_hash);
+        assert_eq!(vc["issuer"], verifier.issuer_did().unwrap());
+
+        // The proof value verifies over the document without its proof
+        let mut proof_config = vc.as_object_mut().unwrap().remove("proof").unwrap();
+        let value = proof_config.as_object_mut().unwrap().remove("proofValue").unwrap();
+        let signature = bs58::decode(value.as_str().unwrap().trim_start_matches('z')).into_vec().unwrap();
+        let key = verifier.issuer_key.as_ref().unwrap().verifying_key();
+        let signature = ed25519_dalek::Signature::from_slice(&signature).unwrap();
+        assert!(key.verify_strict(&signing_hash(&proof_config, &vc), &signature).is_ok());
+
+        let failed = verifier.verify_report(&proof, b"in", b"other");
+        assert!(verifier.credential(&proof, &failed, 0).is_err());
+    }
+}
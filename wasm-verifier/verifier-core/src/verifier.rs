@@ -0,0 +1,855 @@
+// Verifier
+// Binds a proof to its model, inputs and outputs, and runs the freshness, signature and policy checks
+
+use std::sync::Arc;
+
+#[cfg(not(feature = "signatures"))]
+use crate::error::Failure;
+use crate::audit::AuditTrail;
+use crate::clock::ClockSource;
+use crate::config::VerifierConfig;
+use crate::error::VerificationError;
+use crate::hash::{self, HashAlgorithm, IoSource, StreamingHasher};
+use crate::multi::{self, Io};
+use crate::nonce::NonceRegistry;
+use crate::preprocessing::PreprocessingConfig;
+use crate::proof::ProofData;
+use crate::quantization::QuantizationConfig;
+use crate::registry::{ModelEntry, ModelRegistry};
+use crate::report::{Check, CheckObserver, Stopwatch, VerificationReport};
+use crate::tensor::TensorSpec;
+use crate::tolerance::OutputTolerance;
+use crate::version;
+#[cfg(feature = "signatures")]
+use crate::{eip712::Eip712Domain, host::Fetcher, jwks::KeyResolver, revocation::RevocationList, x509};
+#[cfg(feature = "onnx")]
+use crate::onnx::{commitment::CommitmentPolicy, diagnostic::ModelFingerprint};
+
+/// Checks run after model binding and the I/O commitments, in order
+///
+/// `Nonce` must stay last: the nonce is only burned once everything else passed.
+pub const POLICY_CHECKS: [Check; 10] = [
+    Check::Timestamp,
+    Check::ProofValidity,
+    Check::Signature,
+    Check::CertificateChain,
+    Check::AggregateSignature,
+    Check::Quantization,
+    Check::Preprocessing,
+    Check::Revocation,
+    Check::ModelVersion,
+    Check::Nonce,
+];
+
+/// Validates proofs against the expected model and the caller's I/O
+///
+/// Configured through the constructors and `set_*`/`add_*` methods, and
+/// read back through accessors such as `config` and `model_hash`.
+pub struct Verifier {
+    pub(crate) model_hash: String,
+    pub(crate) config: VerifierConfig,
+    pub(crate) nonces: NonceRegistry,
+    #[cfg(feature = "signatures")]
+    pub(crate) trusted_signers: Vec<String>,
+    #[cfg(feature = "signatures")]
+    pub(crate) trusted_attestors: Vec<String>,
+    #[cfg(feature = "signatures")]
+    pub(crate) trusted_roots: Vec<x509_cert::Certificate>,
+    #[cfg(feature = "signatures")]
+    pub(crate) key_resolver: Option<KeyResolver>,
+    #[cfg(feature = "signatures")]
+    pub(crate) did_fetcher: Option<Fetcher>,
+    /// Key credentials and receipts are issued with
+    #[cfg(feature = "rust-signatures")]
+    pub(crate) issuer_key: Option<ed25519_dalek::SigningKey>,
+    pub(crate) quantization: Option<QuantizationConfig>,
+    pub(crate) preprocessing: Option<PreprocessingConfig>,
+    /// Sorted layer labels `model_hash` commits to, for partial-model verifiers
+    pub(crate) partial_layers: Option<Vec<String>>,
+    /// Approved models, for multi-model verifiers (`model_hash` is then unused)
+    pub(crate) registry: Option<ModelRegistry>,
+    /// Commitment hashes a `VerifierPolicy` allows (empty: any)
+    pub(crate) hash_algorithms: Vec<HashAlgorithm>,
+    /// Checks a `VerifierPolicy` requires
+    pub(crate) required_checks: Vec<Check>,
+    #[cfg(feature = "signatures")]
+    pub(crate) revocations: Option<RevocationList>,
+    /// Policy as written, and parsed
+    pub(crate) version_policy: Option<(String, version::VersionReq)>,
+    /// Canonical model encoding, for verifiers built from the model file (re-hashed per proof algorithm)
+    pub(crate) canonical_model: Option<Vec<u8>>,
+    #[cfg(feature = "onnx")]
+    pub(crate) model_fingerprint: Option<ModelFingerprint>,
+    /// Pinned leaves, for structured-commitment verifiers (`model_hash` is then unused)
+    #[cfg(feature = "onnx")]
+    pub(crate) commitment_policy: Option<CommitmentPolicy>,
+    pub(crate) clock: ClockSource,
+    #[cfg(feature = "signatures")]
+    pub(crate) eip712_domain: Eip712Domain,
+    /// How far float outputs may drift from the committed quantized output
+    pub(crate) output_tolerance: Option<OutputTolerance>,
+    /// Notified as each report's checks start and finish
+    pub(crate) observer: Option<Arc<dyn CheckObserver>>,
+    /// Where `set_audit_log` has verifications recorded
+    pub(crate) audit_trail: AuditTrail,
+}
+
+impl Verifier {
+    /// Verifier for `model_hash` with the default `VerifierConfig`
+    pub fn new(model_hash: String) -> Verifier {
+        Verifier::with_config(model_hash, &VerifierConfig::default())
+    }
+
+    pub fn with_config(model_hash: String, config: &VerifierConfig) -> Verifier {
+        Verifier {
+            model_hash,
+            config: *config,
+            nonces: NonceRegistry::default(),
+            #[cfg(feature = "signatures")]
+            trusted_signers: Vec::new(),
+            #[cfg(feature = "signatures")]
+            trusted_attestors: Vec::new(),
+            #[cfg(feature = "signatures")]
+            trusted_roots: Vec::new(),
+            #[cfg(feature = "signatures")]
+            key_resolver: None,
+            #[cfg(feature = "signatures")]
+            did_fetcher: None,
+            #[cfg(feature = "rust-signatures")]
+            issuer_key: None,
+            quantization: None,
+            preprocessing: None,
+            partial_layers: None,
+            registry: None,
+            hash_algorithms: Vec::new(),
+            required_checks: Vec::new(),
+            #[cfg(feature = "signatures")]
+            revocations: None,
+            version_policy: None,
+            canonical_model: None,
+            #[cfg(feature = "onnx")]
+            model_fingerprint: None,
+            #[cfg(feature = "onnx")]
+            commitment_policy: None,
+            clock: ClockSource::default(),
+            #[cfg(feature = "signatures")]
+            eip712_domain: Eip712Domain::default(),
+            output_tolerance: None,
+            observer: None,
+            audit_trail: AuditTrail::default(),
+        }
+    }
+
+    /// Verifier whose default commitment hash is `algorithm`
+    pub fn with_hash_algorithm(model_hash: String, algorithm: HashAlgorithm) -> Verifier {
+        let mut config = VerifierConfig::default();
+        config.set_hash_algorithm(algorithm);
+        Verifier::with_config(model_hash, &config)
+    }
+
+    pub fn config(&self) -> VerifierConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: &VerifierConfig) {
+        self.config = *config;
+    }
+
+    /// Model proofs must be for (unused by multi-model and structured-commitment verifiers)
+    pub fn model_hash(&self) -> &str {
+        &self.model_hash
+    }
+
+    /// Approved models, for multi-model verifiers
+    pub fn registry(&self) -> Option<&ModelRegistry> {
+        self.registry.as_ref()
+    }
+
+    /// Sorted layer labels `model_hash` commits to, for partial-model verifiers
+    pub fn partial_layers(&self) -> Option<&[String]> {
+        self.partial_layers.as_deref()
+    }
+
+    /// Notify `observer` as each report's checks start and finish
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn CheckObserver>>) {
+        self.observer = observer;
+    }
+
+    /// Where freshness, nonce and certificate checks read the current time from
+    pub fn set_clock(&mut self, clock: ClockSource) {
+        self.clock = clock;
+    }
+
+    /// Current time according to this verifier's clock
+    pub fn now_ms(&self) -> u64 {
+        self.clock.now_ms()
+    }
+
+    /// Empty report wired to this verifier's observer
+    pub fn new_report(&self) -> VerificationReport {
+        self.observer.clone().map_or_else(VerificationReport::default, VerificationReport::with_observer)
+    }
+
+    /// Issue a single-use challenge nonce for the prover to bind into its proof
+    ///
+    /// Only checked when `VerifierConfig.require_nonce` is set. Nonces expire
+    /// after `max_age_ms` and are consumed by the first proof that verifies.
+    pub fn issue_nonce(&self) -> Result<String, getrandom::Error> {
+        self.nonces.issue(self.now_ms(), self.config.max_age_ms())
+    }
+
+    /// Whether the proof passes every check (see `verify_report`)
+    pub fn verify(&self, proof_data: &ProofData, input_bytes: &[u8], output_bytes: &[u8]) -> bool {
+        self.verify_report(proof_data, input_bytes, output_bytes).passed()
+    }
+
+    /// Verify proof and return per-check results
+    ///
+    /// Checks model binding, the I/O commitments, the proof's `verified` flag,
+    /// its freshness (see `VerifierConfig`), and, where they apply, its
+    /// signatures, certificate chain and the pinned policies.
+    pub fn verify_report(&self, proof_data: &ProofData, input_bytes: &[u8], output_bytes: &[u8]) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(&mut report, proof_data, IoSource::Bytes(input_bytes), IoSource::Bytes(output_bytes));
+        report
+    }
+
+    /// `verify_report` that also binds the serialized JOLT proof blob to `proof_hash`
+    ///
+    /// The SNARK itself is not re-verified (see LIMITATIONS.md): this only
+    /// guarantees the blob is the one the prover committed to.
+    pub fn verify_report_with_proof_bytes(
+        &self,
+        proof_data: &ProofData,
+        proof_bytes: &[u8],
+        input_bytes: &[u8],
+        output_bytes: &[u8],
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.check_proof_bytes(&mut report, proof_data, proof_bytes);
+        self.run_checks(&mut report, proof_data, IoSource::Bytes(input_bytes), IoSource::Bytes(output_bytes));
+        report
+    }
+
+    /// Verify proof against inputs/outputs hashed chunk-by-chunk
+    ///
+    /// Both hashers are finalized (and reset) by this call.
+    pub fn verify_streamed(
+        &self,
+        proof_data: &ProofData,
+        input_hasher: &mut StreamingHasher,
+        output_hasher: &mut StreamingHasher,
+    ) -> VerificationReport {
+        let mut report = self.new_report();
+        self.run_checks(&mut report, proof_data, input_hasher.finalize_source(), output_hasher.finalize_source());
+        report
+    }
+
+    /// `StreamingHasher` for `verify_streamed` matching the proof's input commitment
+    ///
+    /// `None` if the commitment needs an algorithm this build can't compute.
+    pub fn input_hasher(&self, proof_data: &ProofData) -> Option<StreamingHasher> {
+        StreamingHasher::for_commitment(&proof_data.input_hash, &self.hash_algorithm_for(proof_data))
+    }
+
+    /// `StreamingHasher` for `verify_streamed` matching the proof's output commitment
+    pub fn output_hasher(&self, proof_data: &ProofData) -> Option<StreamingHasher> {
+        StreamingHasher::for_commitment(&proof_data.output_hash, &self.hash_algorithm_for(proof_data))
+    }
+
+    /// Record whether `proof_bytes` match the proof's `proof_hash`
+    pub fn check_proof_bytes(&self, report: &mut VerificationReport, proof_data: &ProofData, proof_bytes: &[u8]) {
+        self.check_commitment(
+            report,
+            Check::ProofBinding,
+            IoSource::Bytes(proof_bytes),
+            &proof_data.proof_hash,
+            &self.hash_algorithm_for(proof_data),
+            "Proof bytes do not match proof_hash",
+        );
+    }
+}
+
+impl Verifier {
+    /// Algorithm of the proof's commitments, falling back to the configured default
+    ///
+    /// Fails with a human-readable reason if the proof names an unknown algorithm,
+    /// or one the policy doesn't allow.
+    pub fn hash_algorithm_for(&self, proof_data: &ProofData) -> Result<HashAlgorithm, String> {
+        let alg = match &proof_data.hash_alg {
+            None => self.config.hash_algorithm(),
+            Some(name) => HashAlgorithm::from_name(name)
+                .ok_or_else(|| format!("Unsupported hash algorithm '{}'", name))?,
+        };
+        self.allowed_hash(alg)
+    }
+
+    /// Compare `source` against a commitment string and record the outcome
+    pub fn check_commitment(
+        &self,
+        report: &mut VerificationReport,
+        check: Check,
+        source: IoSource<'_>,
+        commitment: &str,
+        alg: &Result<HashAlgorithm, String>,
+        reason: &str,
+    ) {
+        let started = report.start(check);
+        let limit = self.config.max_input_bytes();
+        if limit > 0 && source.len() > limit {
+            let reason = match check {
+                Check::OutputHash => "Output exceeds max_input_bytes",
+                Check::ProofBinding => "Proof bytes exceed max_input_bytes",
+                _ => "Input exceeds max_input_bytes",
+            };
+            return report.record_error(check, VerificationError::PayloadTooLarge, reason, started);
+        }
+        if let Err(reason) = self.allowed_tag(commitment) {
+            return report.record_error(check, VerificationError::UnsupportedHashAlgorithm, &reason, started);
+        }
+        match alg {
+            Ok(alg) => match source.matches(commitment, *alg) {
+                Ok(passed) => report.record(check, passed, reason, started),
+                Err(e) => report.record_error(
+                    check,
+                    e,
+                    "Commitment uses a hash algorithm not enabled in this build",
+                    started,
+                ),
+            },
+            Err(unsupported) => {
+                report.record_error(check, VerificationError::UnsupportedHashAlgorithm, unsupported, started)
+            }
+        }
+    }
+
+    /// Record whether an I/O buffer has the shape and dtype the proof declares
+    pub fn check_spec(
+        report: &mut VerificationReport,
+        check: Check,
+        spec: Option<&TensorSpec>,
+        source: &IoSource<'_>,
+        what: &str,
+    ) {
+        let Some(spec) = spec else { return };
+        let started = report.start(check);
+        match spec.check_source(source) {
+            Ok(()) => report.record(check, true, "", started),
+            Err(reason) => report.record(check, false, &format!("{} {}", what, reason), started),
+        }
+    }
+
+    /// Check the model's version against the registry entry and the version policy
+    pub fn check_model_version(&self, proof_data: &ProofData, matched: Option<&ModelEntry>) -> Result<(), String> {
+        let version = match (matched, &proof_data.model_version) {
+            (Some(entry), Some(claimed)) if *claimed != entry.version() => {
+                return Err(format!("Proof claims model_version {} but the registry lists {}", claimed, entry.version()))
+            }
+            (Some(entry), _) => entry.version().to_string(),
+            (None, Some(claimed)) if self.registry.is_none() => claimed.clone(),
+            _ => return Err("Model version is unknown".to_string()),
+        };
+        if let Some((policy, req)) = &self.version_policy {
+            let parsed = version::Version::parse(&version)
+                .ok_or_else(|| format!("Model version {} is not a semantic version", version))?;
+            if !req.matches(&parsed) {
+                return Err(format!("Model version {} is not allowed by policy {}", version, policy));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record whether a `<what>_hash` commitment matches the verifier's expected config
+    pub fn check_config(
+        report: &mut VerificationReport,
+        check: Check,
+        commitment: Option<&str>,
+        encoded: &[u8],
+        alg: &Result<HashAlgorithm, String>,
+        what: &str,
+    ) {
+        let started = report.start(check);
+        match (commitment, alg) {
+            (Some(commitment), Ok(alg)) => match IoSource::Bytes(encoded).matches(commitment, *alg) {
+                Ok(passed) => {
+                    let reason = format!("{}_hash does not match the expected {} config", what, what);
+                    report.record(check, passed, &reason, started)
+                }
+                Err(e) => report.record_error(check, e, &format!("Unsupported {}_hash algorithm", what), started),
+            },
+            (None, _) => report.record(check, false, &format!("Proof does not commit to a {} config", what), started),
+            (_, Err(reason)) => {
+                report.record_error(check, VerificationError::UnsupportedHashAlgorithm, reason, started)
+            }
+        }
+    }
+
+    /// Run all binding checks, recording each outcome in `report` and the audit log
+    pub fn run_checks(
+        &self,
+        report: &mut VerificationReport,
+        proof_data: &ProofData,
+        input: IoSource<'_>,
+        output: IoSource<'_>,
+    ) {
+        self.run_checks_io(report, proof_data, Io::Single(input), Io::Single(output));
+    }
+
+    /// `run_checks` against single or named I/O
+    pub fn run_checks_io(
+        &self,
+        report: &mut VerificationReport,
+        proof_data: &ProofData,
+        input: Io<'_>,
+        output: Io<'_>,
+    ) {
+        let alg = self.hash_algorithm_for(proof_data);
+
+        // 1. Model Binding Check
+        self.check_model_binding(report, proof_data, &alg);
+
+        // 2. Input Hash Verification (buffer checked against input_spec first, if declared;
+        // named inputs against the `inputs` map instead)
+        match input {
+            Io::Single(input) => {
+                Self::check_spec(report, Check::InputSpec, proof_data.input_spec.as_ref(), &input, "Input");
+                self.check_commitment(
+                    report,
+                    Check::InputHash,
+                    input,
+                    &proof_data.input_hash,
+                    &alg,
+                    "Input does not match input_hash",
+                );
+            }
+            Io::Named(inputs) => {
+                multi::check_named(report, Check::InputHash, inputs, &proof_data.inputs, &alg, "Input")
+            }
+        }
+
+        // 3. Output Hash Verification (likewise for output_spec and named outputs)
+        match output {
+            Io::Single(output) => {
+                Self::check_spec(report, Check::OutputSpec, proof_data.output_spec.as_ref(), &output, "Output");
+                self.check_commitment(
+                    report,
+                    Check::OutputHash,
+                    output,
+                    &proof_data.output_hash,
+                    &alg,
+                    "Output does not match output_hash",
+                );
+            }
+            Io::Named(outputs) => {
+                multi::check_named(report, Check::OutputHash, outputs, &proof_data.outputs, &alg, "Output")
+            }
+        }
+
+        // 4.-13. Freshness, signature and policy checks; checks a `VerifierPolicy`
+        // requires but that didn't apply fail just before the nonce is burned
+        for check in POLICY_CHECKS {
+            if check == Check::Nonce {
+                self.check_required(report);
+            }
+            self.run_policy_check(check, report, proof_data, &alg);
+        }
+        self.audit(proof_data, report);
+    }
+
+    /// Model binding: against selected layers only, for partial-model verifiers;
+    /// against every approved model, for registry verifiers; against pinned leaves,
+    /// for structured-commitment verifiers
+    pub fn check_model_binding(
+        &self,
+        report: &mut VerificationReport,
+        proof_data: &ProofData,
+        alg: &Result<HashAlgorithm, String>,
+    ) {
+        let started = report.start(Check::ModelBinding);
+        #[cfg(feature = "onnx")]
+        if let Some(policy) = &self.commitment_policy {
+            match alg {
+                Ok(alg) => {
+                    let result = policy.check(proof_data, *alg);
+                    report.record(Check::ModelBinding, result.is_ok(), result.err().unwrap_or_default(), started);
+                }
+                Err(reason) => {
+                    let error = VerificationError::UnsupportedHashAlgorithm;
+                    report.record_error(Check::ModelBinding, error, reason, started)
+                }
+            }
+            return;
+        }
+        match (&self.partial_layers, &self.registry) {
+            (None, None) => {
+                let passed = match (&self.canonical_model, alg) {
+                    (Some(model), Ok(alg)) => {
+                        IoSource::Bytes(model).matches(&proof_data.model_hash, *alg).unwrap_or(false)
+                    }
+                    _ => hash::commitments_equal(&proof_data.model_hash, &self.model_hash),
+                };
+                #[cfg(feature = "onnx")]
+                if let (Some(fingerprint), Ok(alg), false) = (&self.model_fingerprint, alg, passed) {
+                    let diagnostic = fingerprint.diagnose(proof_data, *alg);
+                    let reason = format!("Proof was generated for a different model: {}", diagnostic.summary());
+                    report.set_model_diagnostic(Some(diagnostic));
+                    report.record(Check::ModelBinding, false, &reason, started);
+                    return;
+                }
+                report.record(Check::ModelBinding, passed, "Proof was generated for a different model", started);
+            }
+            (None, Some(registry)) => {
+                let entry = registry.lookup(&proof_data.model_hash);
+                report.set_matched_model(entry.cloned());
+                let reason = "Proof was generated for an unregistered model";
+                report.record(Check::ModelBinding, entry.is_some(), reason, started);
+            }
+            (Some(layers), _) => {
+                let mut claimed = proof_data.partial_layers.clone();
+                claimed.sort();
+                let committed = proof_data.partial_model_hash.as_deref();
+                let passed =
+                    claimed == *layers && committed.is_some_and(|h| hash::commitments_equal(h, &self.model_hash));
+                let reason = "Proof does not commit to the expected model layers";
+                report.record(Check::ModelBinding, passed, reason, started);
+            }
+        }
+    }
+
+    /// Run one of the checks that follow the I/O commitments (see `POLICY_CHECKS`)
+    ///
+    /// Checks that don't apply to this verifier or proof record nothing.
+    pub fn run_policy_check(
+        &self,
+        check: Check,
+        report: &mut VerificationReport,
+        proof_data: &ProofData,
+        alg: &Result<HashAlgorithm, String>,
+    ) {
+        let started = Stopwatch::start();
+        match check {
+            // 4. Timestamp Check (must be recent, per config)
+            Check::Timestamp => {
+                let passed = self.config.is_fresh(proof_data.timestamp, self.now_ms());
+                report.record(Check::Timestamp, passed, "Proof timestamp is outside the freshness window", started);
+            }
+
+            // 5. JOLT Verification Flag Check
+            // This flag is set during proof generation after JOLT cryptographic verification;
+            // it is the prover's own claim, and the SNARK is not re-verified here (see LIMITATIONS.md)
+            Check::ProofValidity => {
+                let reason = "Proof was not verified at generation time";
+                report.record(Check::ProofValidity, proof_data.verified, reason, started);
+            }
+
+            // 6. Prover Signature Check (when signed, or required by config),
+            // unless an envelope signature was already checked
+            Check::Signature
+                if (proof_data.signature.is_some() || self.config.require_signature())
+                    && !report.ran(Check::Signature) =>
+            {
+                match self.check_signature(proof_data) {
+                    Ok(()) => report.record(Check::Signature, true, "", started),
+                    Err((error, reason)) => report.record_error(Check::Signature, error, reason, started),
+                }
+            }
+
+            // 7. Certificate Chain Check (when a trust store is configured)
+            #[cfg(feature = "signatures")]
+            Check::CertificateChain if !self.trusted_roots.is_empty() => {
+                let outcome = match (&proof_data.signature, &proof_data.signer_pubkey) {
+                    (Some(_), Some(signer)) => {
+                        x509::validate_chain(&proof_data.cert_chain, &self.trusted_roots, self.now_ms(), signer)
+                    }
+                    _ => {
+                        Err((VerificationError::CertificateChainInvalid, "Proof is not signed by a certified key"))
+                    }
+                };
+                match outcome {
+                    Ok(()) => report.record(Check::CertificateChain, true, "", started),
+                    Err((error, reason)) => report.record_error(Check::CertificateChain, error, reason, started),
+                }
+            }
+
+            // 8. Attestor Aggregate Signature Check (when co-signed, or required by config)
+            Check::AggregateSignature
+                if proof_data.aggregate_signature.is_some() || self.config.min_attestors() > 0 =>
+            {
+                match self.check_aggregate_signature(proof_data) {
+                    Ok(()) => report.record(Check::AggregateSignature, true, "", started),
+                    Err((error, reason)) => report.record_error(Check::AggregateSignature, error, reason, started),
+                }
+            }
+
+            // 9. Quantization Binding Check (when the verifier pins a quantization config)
+            Check::Quantization => {
+                if let Some(quantization) = &self.quantization {
+                    let commitment = proof_data.quantization_hash.as_deref();
+                    Self::check_config(report, check, commitment, &quantization.encode(), alg, "quantization");
+                }
+            }
+
+            // 10. Preprocessing Binding Check (when the verifier pins a preprocessing pipeline)
+            Check::Preprocessing => {
+                if let Some(preprocessing) = &self.preprocessing {
+                    let commitment = proof_data.preprocessing_hash.as_deref();
+                    Self::check_config(report, check, commitment, &preprocessing.encode(), alg, "preprocessing");
+                }
+            }
+
+            // 11. Revocation Check (when a revocation list is loaded)
+            #[cfg(feature = "signatures")]
+            Check::Revocation => {
+                if let Some(revocations) = &self.revocations {
+                    let revoked = revocations.revokes(proof_data);
+                    report.record(Check::Revocation, revoked.is_none(), revoked.unwrap_or_default(), started);
+                }
+            }
+
+            // 12. Model Version Check (when a version policy is set, or a registry
+            // verifier's proof claims a version)
+            Check::ModelVersion
+                if self.version_policy.is_some() || (self.registry.is_some() && proof_data.model_version.is_some()) =>
+            {
+                match self.check_model_version(proof_data, report.matched_model()) {
+                    Ok(()) => report.record(Check::ModelVersion, true, "", started),
+                    Err(reason) => report.record(Check::ModelVersion, false, &reason, started),
+                }
+            }
+
+            // 13. Challenge Nonce Check (only in challenge-response mode)
+            Check::Nonce if self.config.require_nonce() => {
+                // Nonces are single-use: burn it if the rest of the proof has verified
+                let nonce = proof_data.nonce.as_deref();
+                match self.nonces.check_and_consume(nonce, self.now_ms(), self.config.max_age_ms(), report.passed()) {
+                    Ok(()) => report.record(Check::Nonce, true, "", started),
+                    Err((error, reason)) => report.record_error(Check::Nonce, error, reason, started),
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Builds without the `signatures` feature can't check signatures: a signed
+/// or co-signed proof, or a policy requiring one, fails the check instead
+/// of passing unchecked
+#[cfg(not(feature = "signatures"))]
+impl Verifier {
+    pub fn check_signature(&self, _proof_data: &ProofData) -> Result<(), Failure> {
+        Err((VerificationError::SignatureInvalid, "Signature checks are not enabled in this build"))
+    }
+
+    pub fn check_aggregate_signature(&self, _proof_data: &ProofData) -> Result<(), Failure> {
+        Err((VerificationError::AggregateSignatureInvalid, "Signature checks are not enabled in this build"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hash_bytes;
+    #[cfg(feature = "rust-signatures")]
+    use crate::hex;
+    use crate::tensor::DType;
+    use crate::fixtures;
+
+    #[test]
+    fn test_verifier() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+
+        let input_data = b"test input";
+        let output_data = b"test output";
+        let proof = fixtures::proof_for(&model_hash, input_data, output_data, HashAlgorithm::default());
+
+        let result = verifier.verify(&proof, input_data, output_data);
+        assert!(result, "Verification should succeed");
+    }
+
+    #[test]
+    fn test_verifier_is_shared_across_threads() {
+        // With every feature set, including `wasm`: hosts keep JS handles out of the core
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Verifier>();
+
+        let verifier = Verifier::new("0xmodel".to_string());
+        let proof = fixtures::proof("0xmodel");
+        std::thread::scope(|scope| {
+            let verify = || verifier.verify(&proof, fixtures::INPUT, fixtures::OUTPUT);
+            let threads: Vec<_> = (0..4).map(|_| scope.spawn(verify)).collect();
+            assert!(threads.into_iter().all(|thread| thread.join().unwrap()));
+        });
+    }
+
+    #[test]
+    fn test_verify_streamed() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+
+        let input = vec![42u8; 4096];
+        let mut proof = fixtures::proof_for(&model_hash, &input, b"out", HashAlgorithm::default());
+
+        let mut input_hasher = verifier.input_hasher(&proof).unwrap();
+        for chunk in input.chunks(1000) {
+            input_hasher.update(chunk);
+        }
+        let mut output_hasher = verifier.output_hasher(&proof).unwrap();
+        output_hasher.update(b"out");
+        assert!(verifier.verify_streamed(&proof, &mut input_hasher, &mut output_hasher).passed());
+
+        proof.set_hash_algorithm(Some(HashAlgorithm::Keccak256));
+        assert_eq!(verifier.input_hasher(&proof).unwrap().algorithm(), HashAlgorithm::Keccak256);
+        proof.hash_alg = Some("md5".to_string());
+        assert!(verifier.output_hasher(&proof).is_none());
+    }
+
+    #[test]
+    fn test_verify_with_proof_bytes() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+
+        let proof_bytes = b"jolt proof blob";
+        let mut proof = fixtures::proof(&model_hash);
+        proof.proof_hash = hash_bytes(proof_bytes, HashAlgorithm::default()).unwrap();
+
+        assert!(verifier.verify_report_with_proof_bytes(&proof, proof_bytes, b"in", b"out").passed());
+        assert!(!verifier.verify_report_with_proof_bytes(&proof, b"tampered", b"in", b"out").passed());
+
+        let mut config = VerifierConfig::new();
+        config.set_max_input_bytes(4);
+        let verifier = Verifier::with_config(proof.model_hash.clone(), &config);
+        let mut report = VerificationReport::default();
+        let alg = verifier.hash_algorithm_for(&proof);
+        let source = IoSource::Bytes(proof_bytes);
+        verifier.check_commitment(&mut report, Check::ProofBinding, source, &proof.proof_hash, &alg, "");
+        assert_eq!(report.failure_reasons(), ["Proof bytes exceed max_input_bytes"]);
+    }
+
+    #[test]
+    fn test_verify_report() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+
+        let proof = fixtures::proof(&model_hash);
+
+        let report = verifier.verify_report(&proof, b"in", b"truncated");
+        assert!(!report.passed());
+        assert!(report.check_passed(Check::ModelBinding));
+        assert!(report.check_passed(Check::InputHash));
+        assert!(!report.check_passed(Check::OutputHash));
+        assert_eq!(report.checks().len(), 5);
+        assert_eq!(report.failure_reasons(), vec!["Output does not match output_hash".to_string()]);
+        assert_eq!(report.error(), Some(VerificationError::OutputHashMismatch));
+        assert_eq!(VerificationError::OutputHashMismatch.code(), 3);
+    }
+
+    #[test]
+    fn test_proof_selects_hash_algorithm() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+
+        let keccak = HashAlgorithm::Keccak256;
+        let mut proof = fixtures::proof_for(&model_hash, b"in", b"out", keccak);
+        assert!(!verifier.verify(&proof, b"in", b"out"));
+
+        proof.set_hash_algorithm(Some(keccak));
+        assert!(verifier.verify(&proof, b"in", b"out"));
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert!(json.contains("\"hash_alg\":\"keccak-256\""));
+        assert!(verifier.verify(&ProofData::from_json(&json).unwrap(), b"in", b"out"));
+    }
+
+    #[test]
+    fn test_sha256_and_unknown_hash_alg() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+
+        let sha256 = HashAlgorithm::Sha256;
+        let mut proof = fixtures::proof_for(&model_hash, b"in", b"out", sha256);
+        proof.hash_alg = Some("SHA-256".to_string());
+        assert!(verifier.verify(&proof, b"in", b"out"));
+
+        proof.hash_alg = Some("md5".to_string());
+        let report = verifier.verify_report(&proof, b"in", b"out");
+        assert_eq!(report.error(), Some(VerificationError::UnsupportedHashAlgorithm));
+        assert_eq!(report.failure_reasons()[0], "Unsupported hash algorithm 'md5'");
+    }
+
+    #[test]
+    fn test_nonce_replay_rejected() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let mut config = VerifierConfig::new();
+        config.set_require_nonce(true);
+        let verifier = Verifier::with_config(model_hash.clone(), &config);
+
+        let mut proof = fixtures::proof(&model_hash);
+        assert!(!verifier.verify(&proof, b"in", b"out"));
+
+        proof.nonce = Some(verifier.issue_nonce().unwrap());
+        assert!(verifier.verify(&proof, b"in", b"out"));
+
+        let replay = verifier.verify_report(&proof, b"in", b"out");
+        assert_eq!(replay.error(), Some(VerificationError::NonceReplayed));
+    }
+
+    #[test]
+    fn test_max_input_bytes() {
+        let mut config = VerifierConfig::new();
+        config.set_max_input_bytes(4);
+        let verifier = Verifier::with_config("0xmodel".to_string(), &config);
+        let proof = fixtures::proof_for("0xmodel", b"in", b"large output", HashAlgorithm::default());
+
+        let report = verifier.verify_report(&proof, b"in", b"large output");
+        assert_eq!(report.error(), Some(VerificationError::PayloadTooLarge));
+        assert!(report.check_passed(Check::InputHash));
+        assert_eq!(report.failure_reasons(), ["Output exceeds max_input_bytes"]);
+    }
+
+    #[test]
+    #[cfg(feature = "rust-signatures")]
+    fn test_signed_proof() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let model_hash = "0x1234567890abcdef".to_string();
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let pubkey = hex::encode(key.verifying_key().as_bytes());
+
+        let mut config = VerifierConfig::new();
+        config.set_require_signature(true);
+        let mut verifier = Verifier::with_config(model_hash.clone(), &config);
+        verifier.add_trusted_signer(pubkey.clone());
+
+        let mut proof = fixtures::proof(&model_hash);
+        assert!(!verifier.verify(&proof, b"in", b"out"));
+
+        proof.signer_pubkey = Some(pubkey);
+        proof.signature = Some(hex::encode(&key.sign(&proof.signing_bytes()).to_bytes()));
+        assert!(verifier.verify(&proof, b"in", b"out"));
+
+        // Any change to a signed field invalidates the signature
+        let json = serde_json::to_string(&proof).unwrap().replace("\"verified\":true", "\"verified\":false");
+        let tampered: ProofData = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            verifier.verify_report(&tampered, b"in", b"out").checks()[5].error(),
+            Some(VerificationError::SignatureInvalid)
+        );
+    }
+
+    #[test]
+    fn test_tensor_spec_check() {
+        let model_hash = "0x1234567890abcdef".to_string();
+        let verifier = Verifier::new(model_hash.clone());
+        let output = [0u8; 40];
+
+        let mut proof = fixtures::proof_for(&model_hash, b"in", &output, HashAlgorithm::default());
+        proof.output_spec = Some(TensorSpec::new(vec![1, 10], DType::Float32, None));
+        assert!(verifier.verify_report(&proof, b"in", &output).passed());
+
+        let report = verifier.verify_report(&proof, b"in", &output[..36]);
+        assert_eq!(report.error(), Some(VerificationError::ShapeMismatch));
+        assert_eq!(report.failure_reasons()[0], "Output buffer is 36 bytes, but float32[1,10] needs 40");
+    }
+}
@@ -0,0 +1,181 @@
+// Semantic model versions
+// Version-range policies (`2.x`, `>=1.4, <2`, `^2.1 || ^3`) over registry versions
+
+use std::cmp::Ordering;
+
+use crate::error::VerificationError;
+use crate::Verifier;
+
+/// `MAJOR.MINOR.PATCH[-pre]`; build metadata (`+...`) is ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Option<String>,
+}
+
+impl Version {
+    pub fn parse(s: &str) -> Option<Version> {
+        let s = s.trim().trim_start_matches('v');
+        let s = s.split_once('+').map_or(s, |(core, _)| core);
+        let (core, pre) = match s.split_once('-') {
+            Some((core, pre)) => (core, Some(pre.to_string())),
+            None => (s, None),
+        };
+        let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+        let version = Version {
+            major: parts.next()??,
+            minor: parts.next().unwrap_or(Some(0))?,
+            patch: parts.next().unwrap_or(Some(0))?,
+            pre,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    fn new(major: u64, minor: u64, patch: u64) -> Version {
+        Version { major, minor, patch, pre: None }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch)).then_with(|| {
+            // A pre-release sorts before its release
+            match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            }
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Half-open range `[min, max)`; `None` is unbounded
+#[derive(Debug, Clone, PartialEq)]
+struct Range {
+    min: Option<(Version, bool)>,
+    max: Option<(Version, bool)>,
+}
+
+impl Range {
+    const ANY: Range = Range { min: None, max: None };
+
+    fn contains(&self, v: &Version) -> bool {
+        let above = self.min.as_ref().is_none_or(|(min, inclusive)| if *inclusive { v >= min } else { v > min });
+        let below = self.max.as_ref().is_none_or(|(max, inclusive)| if *inclusive { v <= max } else { v < max });
+        above && below
+    }
+}
+
+/// A version policy: `||`-separated alternatives of `,`-separated comparators
+///
+/// Comparators are `=`, `>`, `>=`, `<`, `<=` against a (possibly partial)
+/// version, `^1.2` (same major), `~1.2.3` (same minor), and wildcards such
+/// as `2`, `2.x` or `2.1.*`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionReq(Vec<Vec<Range>>);
+
+impl VersionReq {
+    pub fn parse(policy: &str) -> Option<VersionReq> {
+        let alternatives = policy
+            .split("||")
+            .map(|alt| alt.split(',').map(|c| comparator(c.trim())).collect::<Option<Vec<_>>>())
+            .collect::<Option<Vec<_>>>()?;
+        Some(VersionReq(alternatives))
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.0.iter().any(|alt| alt.iter().all(|range| range.contains(version)))
+    }
+}
+
+/// Parse one comparator into the range of versions it allows
+fn comparator(c: &str) -> Option<Range> {
+    if c == "*" || c.eq_ignore_ascii_case("x") {
+        return Some(Range::ANY);
+    }
+    let (op, rest) = [">=", "<=", ">", "<", "=", "^", "~"]
+        .iter()
+        .find_map(|op| c.strip_prefix(op).map(|rest| (*op, rest.trim())))
+        .unwrap_or(("", c));
+
+    // Count the given numeric parts, cutting at the first wildcard
+    let parts: Vec<&str> = rest.split('-').next()?.split('.').collect();
+    let given = parts.iter().take_while(|p| !matches!(**p, "x" | "X" | "*")).count();
+    if given == 0 || parts.len() > 3 {
+        return None;
+    }
+    let version = Version::parse(&if given < parts.len() { parts[..given].join(".") } else { rest.to_string() })?;
+    let (major, minor, patch) = (version.major, version.minor, version.patch);
+
+    // Upper bound of a partial version: `2` -> `<3.0.0`, `2.1` -> `<2.2.0`
+    let next = |n: usize| match n {
+        1 => Version::new(major + 1, 0, 0),
+        2 => Version::new(major, minor + 1, 0),
+        _ => Version::new(major, minor, patch + 1),
+    };
+    let partial = given < 3;
+    Some(match op {
+        ">=" => Range { min: Some((version, true)), max: None },
+        ">" if partial => Range { min: Some((next(given), true)), max: None },
+        ">" => Range { min: Some((version, false)), max: None },
+        "<" => Range { min: None, max: Some((version, false)) },
+        "<=" if partial => Range { min: None, max: Some((next(given), false)) },
+        "<=" => Range { min: None, max: Some((version, true)) },
+        "^" => {
+            let max = match (major, minor) {
+                (0, 0) if given == 3 => next(3),
+                (0, _) if given >= 2 => next(2),
+                _ => next(1),
+            };
+            Range { min: Some((version, true)), max: Some((max, false)) }
+        }
+        "~" => Range { min: Some((version, true)), max: Some((next(given.min(2)), false)) },
+        _ if partial => Range { min: Some((version, true)), max: Some((next(given), false)) },
+        _ => Range { min: Some((version.clone(), true)), max: Some((version, true)) },
+    })
+}
+
+impl Verifier {
+    /// Only accept models whose version satisfies `policy`, e.g. `"2.x"` or `">=2.1, <3 || ^4"`
+    ///
+    /// The version comes from the registry entry the proof matched, or from the
+    /// proof's `model_version` for a single-model verifier. Fails with
+    /// `InvalidArgument` if the policy doesn't parse.
+    pub fn set_version_policy(&mut self, policy: &str) -> Result<(), VerificationError> {
+        let req = VersionReq::parse(policy).ok_or(VerificationError::InvalidArgument)?;
+        self.version_policy = Some((policy.to_string(), req));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allows(policy: &str, version: &str) -> bool {
+        VersionReq::parse(policy).unwrap().matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn test_version_policy() {
+        assert!(allows("2.x", "2.9.1") && !allows("2.x", "1.9.0") && !allows("2.x", "3.0.0"));
+        assert!(allows(">=1.4, <2", "1.4.0") && !allows(">=1.4, <2", "2.0.0"));
+        assert!(allows("^2.1 || ^4", "2.5.0") && allows("^2.1 || ^4", "4.0.0") && !allows("^2.1 || ^4", "3.1.0"));
+        assert!(allows("~1.2.3", "1.2.9") && !allows("~1.2.3", "1.3.0"));
+        assert!(allows("^0.3.1", "0.3.5") && !allows("^0.3.1", "0.4.0"));
+        assert!(allows("=1.0.0", "v1.0.0+build7") && !allows("=1.0.0", "1.0.1"));
+        assert!(!allows(">=2.0.0", "2.0.0-rc1") && allows("*", "0.0.1"));
+        assert!(allows("<=2.1", "2.1.7") && !allows(">2.1", "2.1.7"));
+        assert!(VersionReq::parse(">=two").is_none());
+        assert!(Version::parse("1.2.3.4").is_none());
+    }
+}
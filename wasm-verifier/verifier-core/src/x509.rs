@@ -0,0 +1,192 @@
+// X.509 prover identity
+// Validates that the proof signer's key chains to a configured root CA
+
+use base64::Engine;
+use x509_cert::der::asn1::ObjectIdentifier;
+use x509_cert::der::{Decode, DecodePem, Encode};
+use x509_cert::ext::pkix::BasicConstraints;
+use x509_cert::spki::SubjectPublicKeyInfoOwned;
+use x509_cert::Certificate;
+
+use crate::crypto;
+use crate::error::VerificationError;
+use crate::signature::{self, Failure};
+use crate::{hash, Verifier};
+
+const ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const PRIME256V1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const SECP256K1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.10");
+const BASIC_CONSTRAINTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.19");
+
+/// Maximum accepted chain length (leaf + intermediates)
+const MAX_CHAIN_LEN: usize = 8;
+
+fn invalid(reason: &'static str) -> Failure {
+    (VerificationError::CertificateChainInvalid, reason)
+}
+
+/// Parse a certificate given as PEM or base64 DER (the JOSE `x5c` form)
+pub fn parse_certificate(encoded: &str) -> Result<Certificate, Failure> {
+    let encoded = encoded.trim();
+    if encoded.starts_with("-----BEGIN") {
+        Certificate::from_pem(encoded).map_err(|_| invalid("Certificate is not valid PEM"))
+    } else {
+        let der = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| invalid("Certificate is neither PEM nor base64 DER"))?;
+        Certificate::from_der(&der).map_err(|_| invalid("Certificate is not valid DER"))
+    }
+}
+
+/// Validate `chain` (leaf first) against `roots` at time `now_ms`, and check
+/// that the leaf certifies `signer` (Ed25519 key or Ethereum address, hex)
+///
+/// Supported certificate signatures: Ed25519 and ECDSA-SHA256 over P-256 or
+/// secp256k1. RSA-signed chains are rejected.
+pub fn validate_chain(
+    chain: &[String],
+    roots: &[Certificate],
+    now_ms: u64,
+    signer: &str,
+) -> Result<(), Failure> {
+    if chain.is_empty() {
+        return Err(invalid("Proof does not carry a cert_chain"));
+    }
+    if chain.len() > MAX_CHAIN_LEN {
+        return Err(invalid("cert_chain is too long"));
+    }
+    let certs = chain.iter().map(|c| parse_certificate(c)).collect::<Result<Vec<_>, _>>()?;
+
+    for (i, cert) in certs.iter().enumerate() {
+        check_validity(cert, now_ms)?;
+        if i > 0 && !is_ca(cert) {
+            return Err(invalid("Intermediate certificate is not a CA"));
+        }
+    }
+    for pair in certs.windows(2) {
+        check_issued_by(&pair[0], &pair[1])?;
+    }
+
+    // The top of the chain must be a trusted root, or be issued by one
+    let top = certs.last().expect("chain is non-empty");
+    let anchored = roots.iter().any(|root| root == top)
+        || roots.iter().any(|root| {
+            check_validity(root, now_ms).is_ok() && is_ca(root) && check_issued_by(top, root).is_ok()
+        });
+    if !anchored {
+        return Err((VerificationError::UntrustedSigner, "cert_chain does not chain to a trusted root"));
+    }
+
+    if !certifies(&certs[0].tbs_certificate.subject_public_key_info, signer) {
+        return Err(invalid("Leaf certificate does not certify signer_pubkey"));
+    }
+    Ok(())
+}
+
+fn check_validity(cert: &Certificate, now_ms: u64) -> Result<(), Failure> {
+    let validity = &cert.tbs_certificate.validity;
+    let not_before = validity.not_before.to_unix_duration().as_millis() as u64;
+    let not_after = validity.not_after.to_unix_duration().as_millis() as u64;
+    if now_ms < not_before || now_ms > not_after {
+        return Err(invalid("Certificate is expired or not yet valid"));
+    }
+    Ok(())
+}
+
+fn is_ca(cert: &Certificate) -> bool {
+    cert.tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .find(|ext| ext.extn_id == BASIC_CONSTRAINTS)
+        .and_then(|ext| BasicConstraints::from_der(ext.extn_value.as_bytes()).ok())
+        .is_some_and(|bc| bc.ca)
+}
+
+/// Check that `issuer` issued and signed `cert`
+fn check_issued_by(cert: &Certificate, issuer: &Certificate) -> Result<(), Failure> {
+    if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        return Err(invalid("Certificate issuer does not match the next certificate's subject"));
+    }
+
+    let tbs = cert.tbs_certificate.to_der().map_err(|_| invalid("Certificate cannot be re-encoded"))?;
+    let signature = cert.signature.raw_bytes();
+    let spki = &issuer.tbs_certificate.subject_public_key_info;
+    let issuer_key = spki.subject_public_key.raw_bytes();
+    let bad_signature = invalid("Certificate signature does not verify");
+
+    match (cert.signature_algorithm.oid, spki.algorithm.oid, curve(spki)) {
+        (ED25519, ED25519, _) => {
+            let key = issuer_key.try_into().map_err(|_| bad_signature)?;
+            let check = crypto::Check::ed25519(key, signature.try_into().map_err(|_| bad_signature)?, &tbs);
+            crypto::verify(check)?.then_some(()).ok_or(bad_signature)
+        }
+        (ECDSA_WITH_SHA256, EC_PUBLIC_KEY, Some(PRIME256V1)) => {
+            let check = crypto::Check::p256_der(issuer_key, signature, &tbs).ok_or(bad_signature)?;
+            crypto::verify(check)?.then_some(()).ok_or(bad_signature)
+        }
+        (ECDSA_WITH_SHA256, EC_PUBLIC_KEY, Some(SECP256K1)) => {
+            use k256::ecdsa::signature::Verifier;
+            let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(issuer_key).map_err(|_| bad_signature)?;
+            let sig = k256::ecdsa::Signature::from_der(signature).map_err(|_| bad_signature)?;
+            key.verify(&tbs, &sig).map_err(|_| bad_signature)
+        }
+        _ => Err(invalid("Unsupported certificate signature algorithm")),
+    }
+}
+
+/// Named curve of an EC public key
+fn curve(spki: &SubjectPublicKeyInfoOwned) -> Option<ObjectIdentifier> {
+    spki.algorithm.parameters.as_ref().and_then(|p| p.decode_as::<ObjectIdentifier>().ok())
+}
+
+/// Whether a certificate key is the proof signer
+fn certifies(spki: &SubjectPublicKeyInfoOwned, signer: &str) -> bool {
+    let key = spki.subject_public_key.raw_bytes();
+    match (spki.algorithm.oid, curve(spki)) {
+        (ED25519, _) => hash::digests_equal(&crate::hex::encode(key), signer),
+        (EC_PUBLIC_KEY, Some(SECP256K1)) => k256::ecdsa::VerifyingKey::from_sec1_bytes(key)
+            .map(|key| hash::digests_equal(&signature::eth_address(&key), signer))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+impl Verifier {
+    /// Add a root CA certificate (PEM or base64 DER) to the trust store
+    ///
+    /// Once a root is configured, every proof must carry a `cert_chain`
+    /// whose leaf certifies `signer_pubkey` and which chains to a trusted root.
+    pub fn add_trusted_root(&mut self, certificate: &str) -> Result<(), Failure> {
+        let root = parse_certificate(certificate)?;
+        self.trusted_roots.push(root);
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "rust-signatures"))]
+mod tests {
+    use super::*;
+
+    const ROOT: &str = include_str!("../../testdata/x509_root.pem");
+    const LEAF: &str = include_str!("../../testdata/x509_leaf.pem");
+    const LEAF_PUBKEY: &str = "021cf78ab5b0858c0bbb2378be14be0ee37ae65e309333cb100ec7c7a8de7f41";
+    const NOW: u64 = 1_800_000_000_000;
+
+    #[test]
+    fn test_validate_chain() {
+        let roots = vec![parse_certificate(ROOT).unwrap()];
+        let chain = vec![LEAF.to_string()];
+
+        assert!(validate_chain(&chain, &roots, NOW, LEAF_PUBKEY).is_ok());
+        assert_eq!(validate_chain(&chain, &roots, NOW, "0xabcd").unwrap_err().0, VerificationError::CertificateChainInvalid);
+        assert_eq!(validate_chain(&chain, &[], NOW, LEAF_PUBKEY).unwrap_err().0, VerificationError::UntrustedSigner);
+        assert_eq!(validate_chain(&[], &roots, NOW, LEAF_PUBKEY).unwrap_err().0, VerificationError::CertificateChainInvalid);
+
+        // A leaf cannot act as a CA for itself
+        let leaf_as_root = vec![parse_certificate(LEAF).unwrap()];
+        assert!(validate_chain(&[ROOT.to_string()], &leaf_as_root, NOW, LEAF_PUBKEY).is_err());
+    }
+}